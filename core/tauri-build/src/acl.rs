@@ -309,6 +309,29 @@ pub fn save_acl_manifests(acl_manifests: &BTreeMap<String, Manifest>) -> Result<
   Ok(acl_manifests_path)
 }
 
+/// Saves the configuration schemas of the plugins and the app configuration schema extended with them.
+pub fn save_config_schemas() -> Result<()> {
+  let plugin_schemas = tauri_utils::plugin::read_config_schemas()
+    .context("failed to read plugin configuration schemas")?;
+  let out_dir = PathBuf::from(CAPABILITIES_SCHEMA_FOLDER_PATH);
+
+  let plugin_schemas_path = out_dir.join(tauri_utils::plugin::PLUGIN_CONFIG_SCHEMAS_FILE_NAME);
+  let plugin_schemas_json = serde_json::to_string(&plugin_schemas)?;
+  if plugin_schemas_json != read_to_string(&plugin_schemas_path).unwrap_or_default() {
+    write(&plugin_schemas_path, plugin_schemas_json)?;
+  }
+
+  let mut schema = serde_json::to_value(schema_for!(tauri_utils::config::Config))?;
+  tauri_utils::plugin::extend_config_schema(&mut schema, &plugin_schemas);
+  let schema_path = out_dir.join(tauri_utils::plugin::CONFIG_SCHEMA_FILE_NAME);
+  let schema_str = serde_json::to_string_pretty(&schema)?;
+  if schema_str != read_to_string(&schema_path).unwrap_or_default() {
+    write(&schema_path, schema_str)?;
+  }
+
+  Ok(())
+}
+
 pub fn get_manifests_from_plugins() -> Result<BTreeMap<String, Manifest>> {
   let permission_map =
     tauri_utils::acl::build::read_permissions().context("failed to read plugin permissions")?;
@@ -18,7 +18,7 @@ use cargo_toml::Manifest;
 
 use tauri_utils::{
   acl::{build::parse_capabilities, APP_ACL_KEY},
-  config::{BundleResources, Config, WebviewInstallMode},
+  config::{BundleResources, Config, ConfigBuilder, WebviewInstallMode},
   resources::{external_binaries, ResourcePaths},
 };
 
@@ -339,6 +339,7 @@ pub struct Attributes {
   codegen: Option<codegen::context::CodegenContext>,
   inlined_plugins: HashMap<&'static str, InlinedPlugin>,
   app_manifest: AppManifest,
+  config: Option<Config>,
 }
 
 impl Attributes {
@@ -365,6 +366,16 @@ impl Attributes {
     self
   }
 
+  /// Uses the given configuration instead of reading the Tauri configuration files,
+  /// e.g. patched with [`ConfigBuilder`](tauri_utils::config::ConfigBuilder).
+  ///
+  /// The configuration is also used by the `tauri::generate_context!` macro.
+  #[must_use]
+  pub fn config(mut self, config: Config) -> Self {
+    self.config.replace(config);
+    self
+  }
+
   /// Adds the given plugin to the list of inlined plugins (a plugin that is part of your application).
   ///
   /// See [`InlinedPlugin`] for more information.
@@ -419,7 +430,7 @@ pub fn build() {
   if let Err(error) = try_build(Attributes::default()) {
     let error = format!("{error:#}");
     println!("{error}");
-    if error.starts_with("unknown field") {
+    if error.contains("unknown field") {
       print!("found an unknown configuration field. This usually means that you are using a CLI version that is newer than `tauri-build` and is incompatible. ");
       println!(
         "Please try updating the Rust crates by running `cargo update` in the Tauri app folder."
@@ -435,12 +446,27 @@ pub fn try_build(attributes: Attributes) -> Result<()> {
   use anyhow::anyhow;
 
   println!("cargo:rerun-if-env-changed=TAURI_CONFIG");
+  println!(
+    "cargo:rerun-if-env-changed={}",
+    tauri_utils::config::parse::PROFILE_ENV
+  );
   #[cfg(feature = "config-json")]
   println!("cargo:rerun-if-changed=tauri.conf.json");
   #[cfg(feature = "config-json5")]
   println!("cargo:rerun-if-changed=tauri.conf.json5");
   #[cfg(feature = "config-toml")]
   println!("cargo:rerun-if-changed=Tauri.toml");
+  if let Some(profile) = std::env::var(tauri_utils::config::parse::PROFILE_ENV)
+    .ok()
+    .filter(|profile| !profile.is_empty())
+  {
+    #[cfg(feature = "config-json")]
+    println!("cargo:rerun-if-changed=tauri.conf.{profile}.json");
+    #[cfg(feature = "config-json5")]
+    println!("cargo:rerun-if-changed=tauri.conf.{profile}.json5");
+    #[cfg(feature = "config-toml")]
+    println!("cargo:rerun-if-changed=Tauri.{profile}.toml");
+  }
 
   let target_os = std::env::var("CARGO_CFG_TARGET_OS").unwrap();
   let mobile = target_os == "ios" || target_os == "android";
@@ -450,15 +476,24 @@ pub fn try_build(attributes: Attributes) -> Result<()> {
   let target_triple = std::env::var("TARGET").unwrap();
   let target = tauri_utils::platform::Target::from_triple(&target_triple);
 
-  let mut config = serde_json::from_value(tauri_utils::config::parse::read_from(
-    target,
-    std::env::current_dir().unwrap(),
-  )?)?;
-  if let Ok(env) = std::env::var("TAURI_CONFIG") {
-    let merge_config: serde_json::Value = serde_json::from_str(&env)?;
-    json_patch::merge(&mut config, &merge_config);
+  let config = match attributes.config.clone() {
+    Some(config) => {
+      // the context macro and the codegen merge `TAURI_CONFIG` on top of the config files
+      let config_json = serde_json::to_string(&config)?;
+      println!("cargo:rustc-env=TAURI_CONFIG={config_json}");
+      std::env::set_var("TAURI_CONFIG", config_json);
+      config
+    }
+    None => ConfigBuilder::from_dir(target, std::env::current_dir().unwrap())?
+      .merge_env()?
+      .build()?,
+  };
+
+  for name in &config.build.interpolate_env {
+    if !name.ends_with('*') {
+      println!("cargo:rerun-if-env-changed={name}");
+    }
   }
-  let config: Config = serde_json::from_value(config)?;
 
   let s = config.identifier.split('.');
   let last = s.clone().count() - 1;
@@ -480,6 +515,13 @@ pub fn try_build(attributes: Attributes) -> Result<()> {
 
   cfg_alias("dev", is_dev());
 
+  // warns about the commands that are not allowed by any capability in `tauri::generate_handler!`
+  println!("cargo:rerun-if-env-changed=TAURI_CHECK_CAPABILITIES");
+  cfg_alias(
+    "tauri_check_capabilities",
+    var_os("TAURI_CHECK_CAPABILITIES").is_some_and(|v| v == "true" || v == "1"),
+  );
+
   let ws_path = get_workspace_dir()?;
   let mut manifest =
     Manifest::<cargo_toml::Value>::from_slice_with_metadata(&std::fs::read("Cargo.toml")?)?;
@@ -530,6 +572,7 @@ pub fn try_build(attributes: Attributes) -> Result<()> {
   copy(capabilities_path, out_dir.join(CAPABILITIES_FILE_NAME))?;
 
   acl::save_acl_manifests(&acl_manifests)?;
+  acl::save_config_schemas()?;
 
   tauri_utils::plugin::load_global_api_scripts(&out_dir);
 
@@ -4,7 +4,7 @@
 
 use anyhow::{anyhow, Result};
 use cargo_toml::{Dependency, Manifest};
-use tauri_utils::config::{AppConfig, Config, PatternKind};
+use tauri_utils::config::{AppConfig, AssetStorage, Config, PatternKind};
 
 #[derive(Debug, Default, PartialEq, Eq)]
 struct Diff {
@@ -28,6 +28,18 @@ struct AllowlistedDependency {
 }
 
 pub fn check(config: &Config, manifest: &mut Manifest) -> Result<()> {
+  let mut tauri_features = config
+    .app
+    .features()
+    .into_iter()
+    .filter(|f| f != &"tray-icon")
+    .map(|f| f.to_string())
+    .collect::<Vec<String>>();
+  if config.build.asset_storage == AssetStorage::Packfile {
+    tauri_features.push("asset-packfile".into());
+    tauri_features.sort_unstable();
+  }
+
   let dependencies = vec![
     AllowlistedDependency {
       name: "tauri-build".into(),
@@ -49,13 +61,7 @@ pub fn check(config: &Config, manifest: &mut Manifest) -> Result<()> {
           .filter(|f| f != &"tray-icon")
           .collect(),
       ),
-      expected_features: config
-        .app
-        .features()
-        .into_iter()
-        .filter(|f| f != &"tray-icon")
-        .map(|f| f.to_string())
-        .collect::<Vec<String>>(),
+      expected_features: tauri_features,
     },
   ];
 
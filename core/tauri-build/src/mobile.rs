@@ -2,11 +2,18 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
-use std::{fs::write, path::PathBuf};
+use std::{
+  collections::HashMap,
+  fs::{create_dir_all, remove_dir_all, write},
+  path::{Path, PathBuf},
+};
 
 use anyhow::{Context, Result};
 use semver::Version;
-use tauri_utils::config::Config;
+use tauri_utils::{
+  config::{AndroidAssetPack, BundleResources, Config},
+  resources::ResourcePaths,
+};
 
 use crate::is_dev;
 
@@ -80,6 +87,35 @@ dependencies {"
     }
   }
 
+  if let Some(signing) = &config.bundle.android.signing {
+    let key_store = std::env::current_dir()?.join(&signing.key_store);
+    app_tauri_properties.push(format!(
+      "tauri.android.signing.keyStore={}",
+      tauri_utils::display_path(key_store).replace('\\', "/")
+    ));
+    app_tauri_properties.push(format!(
+      "tauri.android.signing.keyAlias={}",
+      signing.key_alias
+    ));
+  }
+
+  let asset_packs = &config.bundle.android.asset_packs;
+  for asset_pack in asset_packs {
+    generate_asset_pack(&project_dir, asset_pack, config.bundle.resources.as_ref())?;
+    gradle_settings.push_str(&format!("include ':{}'", asset_pack.name));
+    gradle_settings.push('\n');
+  }
+  if !asset_packs.is_empty() {
+    app_tauri_properties.push(format!(
+      "tauri.android.assetPacks={}",
+      asset_packs
+        .iter()
+        .map(|p| p.name.as_str())
+        .collect::<Vec<_>>()
+        .join(",")
+    ));
+  }
+
   write(&gradle_settings_path, gradle_settings).context("failed to write tauri.settings.gradle")?;
 
   write(&app_build_gradle_path, app_build_gradle)
@@ -107,3 +143,98 @@ dependencies {"
 
   Ok(())
 }
+
+/// Generates the Gradle module of a Play Asset Delivery pack, with its resources as the module assets.
+fn generate_asset_pack(
+  project_dir: &Path,
+  asset_pack: &AndroidAssetPack,
+  resources: Option<&BundleResources>,
+) -> Result<()> {
+  let name = &asset_pack.name;
+  check_asset_pack_name(name)?;
+
+  let pack_dir = project_dir.join(name);
+  let assets_dir = pack_dir.join("src/main/assets");
+  // remove the resources that were moved out of the pack
+  if assets_dir.exists() {
+    remove_dir_all(&assets_dir)?;
+  }
+  create_dir_all(&assets_dir)?;
+
+  write(
+    pack_dir.join("build.gradle.kts"),
+    format!(
+      r#"// THIS IS AN AUTOGENERATED FILE. DO NOT EDIT THIS FILE DIRECTLY.
+plugins {{
+    id("com.android.asset-pack")
+}}
+
+assetPack {{
+    packName.set("{name}")
+    dynamicDelivery {{
+        deliveryType.set("install-time")
+    }}
+}}
+"#
+    ),
+  )
+  .with_context(|| format!("failed to write the {name} asset pack build.gradle.kts"))?;
+
+  match resources {
+    Some(BundleResources::Map(map)) => {
+      let mut pack_resources = HashMap::new();
+      for resource in &asset_pack.resources {
+        let target = map
+          .get(resource)
+          .ok_or_else(|| not_a_resource(name, resource))?;
+        pack_resources.insert(resource.clone(), target.clone());
+      }
+      crate::copy_resources(ResourcePaths::from_map(&pack_resources, true), &assets_dir)
+    }
+    Some(BundleResources::List(list)) => {
+      if let Some(resource) = asset_pack.resources.iter().find(|r| !list.contains(r)) {
+        return Err(not_a_resource(name, resource));
+      }
+      crate::copy_resources(ResourcePaths::new(&asset_pack.resources, true), &assets_dir)
+    }
+    None => match asset_pack.resources.first() {
+      Some(resource) => Err(not_a_resource(name, resource)),
+      None => Ok(()),
+    },
+  }
+}
+
+/// Checks the name of an asset pack, which must be a valid Play Asset Delivery pack name and not shadow the app module.
+fn check_asset_pack_name(name: &str) -> Result<()> {
+  if !name.starts_with(|c: char| c.is_ascii_alphabetic())
+    || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+    || name == "app"
+    || name == "buildSrc"
+  {
+    anyhow::bail!(
+      "invalid asset pack name `{name}`, it must start with a letter and only contain letters, digits and underscores"
+    );
+  }
+  Ok(())
+}
+
+fn not_a_resource(pack: &str, resource: &str) -> anyhow::Error {
+  anyhow::anyhow!(
+    "the `{resource}` resource of the {pack} asset pack is not declared in `bundle > resources`"
+  )
+}
+
+#[cfg(test)]
+mod tests {
+  use super::check_asset_pack_name;
+
+  #[test]
+  fn asset_pack_names() {
+    assert!(check_asset_pack_name("textures").is_ok());
+    assert!(check_asset_pack_name("level_2").is_ok());
+    assert!(check_asset_pack_name("2d").is_err());
+    assert!(check_asset_pack_name("video-pack").is_err());
+    assert!(check_asset_pack_name("../app").is_err());
+    assert!(check_asset_pack_name("app").is_err());
+  }
+}
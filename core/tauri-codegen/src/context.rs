@@ -23,15 +23,15 @@ use tauri_utils::{
   acl::manifest::Manifest,
   acl::resolved::Resolved,
   assets::AssetKey,
-  config::{CapabilityEntry, Config, FrontendDist, PatternKind},
+  config::{AssetStorage, CapabilityEntry, Config, FrontendDist, PatternKind},
   html::{inject_nonce_token, parse as parse_html, serialize_node as serialize_html_node, NodeRef},
   platform::Target,
   plugin::GLOBAL_API_SCRIPT_FILE_LIST_PATH,
   tokens::{map_lit, str_lit},
 };
 
-const ACL_MANIFESTS_FILE_NAME: &str = "acl-manifests.json";
-const CAPABILITIES_FILE_NAME: &str = "capabilities.json";
+pub(crate) const ACL_MANIFESTS_FILE_NAME: &str = "acl-manifests.json";
+pub(crate) const CAPABILITIES_FILE_NAME: &str = "capabilities.json";
 
 /// Necessary data needed by [`context_codegen`] to generate code for a Tauri application context.
 pub struct ContextData {
@@ -148,6 +148,8 @@ pub fn context_codegen(data: ContextData) -> EmbeddedAssetsResult<TokenStream> {
   #[allow(unused_variables)]
   let running_tests = test;
 
+  validate_window_labels(&config);
+
   let target = std::env::var("TAURI_ENV_TARGET_TRIPLE")
     .as_deref()
     .map(Target::from_triple)
@@ -206,6 +208,12 @@ pub fn context_codegen(data: ContextData) -> EmbeddedAssetsResult<TokenStream> {
       },
       None => Default::default(),
     };
+    // mobile apps can't ship files next to their executable
+    let assets = if config.build.asset_storage == AssetStorage::Packfile && target.is_desktop() {
+      assets.with_packfile(format!("{}.pak", config.identifier))?
+    } else {
+      assets
+    };
     quote!(#assets)
   };
 
@@ -510,6 +518,22 @@ pub fn context_codegen(data: ContextData) -> EmbeddedAssetsResult<TokenStream> {
   }))
 }
 
+/// Rejects the invalid, reserved and duplicated labels of the windows in the config.
+fn validate_window_labels(config: &Config) {
+  let mut labels = std::collections::HashSet::new();
+  for window in &config.app.windows {
+    if let Err(e) = tauri_utils::label::validate(&window.label) {
+      panic!("invalid window label `{}` in the config: {e}", window.label);
+    }
+    if !labels.insert(&window.label) {
+      panic!(
+        "the window label `{}` is used by multiple windows in the config",
+        window.label
+      );
+    }
+  }
+}
+
 fn find_icon(
   config: &Config,
   config_parent: &Path,
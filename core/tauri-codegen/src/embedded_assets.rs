@@ -12,7 +12,10 @@ use std::{
   path::{Path, PathBuf},
 };
 use tauri_utils::config::PatternKind;
-use tauri_utils::{assets::AssetKey, config::DisabledCspModificationKind};
+use tauri_utils::{
+  assets::{AssetKey, PACKFILE_ID_LEN, PACKFILE_MAGIC},
+  config::DisabledCspModificationKind,
+};
 use thiserror::Error;
 use walkdir::{DirEntry, WalkDir};
 
@@ -25,6 +28,12 @@ const TARGET_PATH: &str = "tauri-codegen-assets";
 /// (key, (original filepath, compressed bytes))
 type Asset = (AssetKey, (PathBuf, PathBuf));
 
+/// Compile-time metadata of an asset, embedded as [`tauri_utils::assets::AssetMetadata`].
+struct AssetMetadata {
+  hash: String,
+  mime_type: String,
+}
+
 /// All possible errors while reading and compressing an [`EmbeddedAssets`] directory
 #[derive(Debug, Error)]
 #[non_exhaustive]
@@ -77,7 +86,17 @@ pub type EmbeddedAssetsResult<T> = Result<T, EmbeddedAssetsError>;
 #[derive(Default)]
 pub struct EmbeddedAssets {
   assets: HashMap<AssetKey, (PathBuf, PathBuf)>,
+  manifest: HashMap<AssetKey, AssetMetadata>,
   csp_hashes: CspHashes,
+  packfile: Option<PackfileIndex>,
+}
+
+/// The assets written to a packfile instead of being embedded, see [`EmbeddedAssets::with_packfile`].
+struct PackfileIndex {
+  file_name: String,
+  id: String,
+  // (offset, length, hex-encoded SHA-256) of the compressed assets in the packfile.
+  entries: Vec<(AssetKey, usize, usize, String)>,
 }
 
 pub struct EmbeddedAssetsInput(Vec<PathBuf>);
@@ -265,22 +284,89 @@ impl EmbeddedAssets {
     struct CompressState {
       csp_hashes: CspHashes,
       assets: HashMap<AssetKey, (PathBuf, PathBuf)>,
+      manifest: HashMap<AssetKey, AssetMetadata>,
     }
 
-    let CompressState { assets, csp_hashes } = paths.into_iter().try_fold(
+    let CompressState {
+      assets,
+      manifest,
+      csp_hashes,
+    } = paths.into_iter().try_fold(
       CompressState {
         csp_hashes,
         assets: HashMap::new(),
+        manifest: HashMap::new(),
       },
       move |mut state, (prefix, entry)| {
-        let (key, asset) =
+        let ((key, asset), metadata) =
           Self::compress_file(&prefix, entry.path(), &mut map, &mut state.csp_hashes)?;
+        state.manifest.insert(key.clone(), metadata);
         state.assets.insert(key, asset);
         Result::<_, EmbeddedAssetsError>::Ok(state)
       },
     )?;
 
-    Ok(Self { assets, csp_hashes })
+    Ok(Self {
+      assets,
+      manifest,
+      csp_hashes,
+      packfile: None,
+    })
+  }
+
+  /// Writes the compressed assets to a packfile with the given name in the target directory, next to the executable,
+  /// so the application memory-maps them on demand instead of embedding them in its binary.
+  ///
+  /// The packfile is only rewritten when its content changes.
+  pub fn with_packfile(mut self, file_name: impl Into<String>) -> EmbeddedAssetsResult<Self> {
+    let file_name = file_name.into();
+
+    // sort the assets so the packfile is reproducible
+    let mut keys = self.assets.keys().cloned().collect::<Vec<_>>();
+    keys.sort();
+
+    let mut data = Vec::new();
+    let mut entries = Vec::with_capacity(keys.len());
+    let header_len = PACKFILE_MAGIC.len() + PACKFILE_ID_LEN;
+    for key in keys {
+      let (_, output) = &self.assets[&key];
+      let bytes = std::fs::read(output).map_err(|error| EmbeddedAssetsError::AssetRead {
+        path: output.clone(),
+        error,
+      })?;
+      // the app checks the assets before serving them so a tampered packfile isn't served
+      let hash = Sha256::digest(&bytes)
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect::<String>();
+      entries.push((key, header_len + data.len(), bytes.len(), hash));
+      data.extend(bytes);
+    }
+    let id = crate::checksum(&data).map_err(EmbeddedAssetsError::Hex)?;
+
+    let mut packfile = Vec::with_capacity(header_len + data.len());
+    packfile.extend(PACKFILE_MAGIC);
+    packfile.extend(id.as_bytes());
+    packfile.extend(data);
+
+    // OUT_DIR is `target/<profile>/build/<crate>-<hash>/out`
+    let out_dir = ensure_out_dir()?;
+    let target_dir = out_dir
+      .ancestors()
+      .nth(3)
+      .ok_or(EmbeddedAssetsError::OutDir)?;
+    let path = target_dir.join(&file_name);
+    if std::fs::read(&path).map_or(true, |existing| existing != packfile) {
+      std::fs::write(&path, packfile)
+        .map_err(|error| EmbeddedAssetsError::AssetWrite { path, error })?;
+    }
+
+    self.packfile.replace(PackfileIndex {
+      file_name,
+      id,
+      entries,
+    });
+    Ok(self)
   }
 
   /// Use highest compression level for release, the fastest one for everything else
@@ -311,7 +397,7 @@ impl EmbeddedAssets {
       &mut CspHashes,
     ) -> Result<(), EmbeddedAssetsError>,
     csp_hashes: &mut CspHashes,
-  ) -> Result<Asset, EmbeddedAssetsError> {
+  ) -> Result<(Asset, AssetMetadata), EmbeddedAssetsError> {
     let mut input = std::fs::read(path).map_err(|error| EmbeddedAssetsError::AssetRead {
       path: path.to_owned(),
       error,
@@ -341,12 +427,13 @@ impl EmbeddedAssets {
 
     // get a hash of the input - allows for caching existing files
     let hash = crate::checksum(&input).map_err(EmbeddedAssetsError::Hex)?;
+    let mime_type = tauri_utils::mime_type::MimeType::parse(&input, key.as_ref());
 
     // use the content hash to determine filename, keep extensions that exist
     let out_path = if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
       out_dir.join(format!("{hash}.{ext}"))
     } else {
-      out_dir.join(hash)
+      out_dir.join(&hash)
     };
 
     // only compress and write to the file if it doesn't already exist.
@@ -382,23 +469,32 @@ impl EmbeddedAssets {
       }
     }
 
-    Ok((key, (path.into(), out_path)))
+    let metadata = AssetMetadata { hash, mime_type };
+
+    Ok(((key, (path.into(), out_path)), metadata))
   }
 }
 
 impl ToTokens for EmbeddedAssets {
   fn to_tokens(&self, tokens: &mut TokenStream) {
     let mut assets = TokenStream::new();
+    let mut inputs = TokenStream::new();
     for (key, (input, output)) in &self.assets {
       let key: &str = key.as_ref();
       let input = input.display().to_string();
       let output = output.display().to_string();
 
       // add original asset as a compiler dependency, rely on dead code elimination to clean it up
-      assets.append_all(quote!(#key => {
-        const _: &[u8] = include_bytes!(#input);
-        include_bytes!(#output)
-      },));
+      if self.packfile.is_some() {
+        inputs.append_all(quote!(
+          const _: &[u8] = include_bytes!(#input);
+        ));
+      } else {
+        assets.append_all(quote!(#key => {
+          const _: &[u8] = include_bytes!(#input);
+          include_bytes!(#output)
+        },));
+      }
     }
 
     let mut global_hashes = TokenStream::new();
@@ -423,11 +519,46 @@ impl ToTokens for EmbeddedAssets {
       html_hashes.append_all(quote!(#key => &[#value],));
     }
 
+    let mut manifest = TokenStream::new();
+    let mut hashed_keys = TokenStream::new();
+    for (key, AssetMetadata { hash, mime_type }) in &self.manifest {
+      let hashed_key = key.with_content_hash(hash);
+      let hashed_key: &str = hashed_key.as_ref();
+      let key: &str = key.as_ref();
+      let hash = hash.as_str();
+      let mime_type = mime_type.as_str();
+      manifest.append_all(quote!(#key => AssetMetadata {
+        hash: #hash,
+        hashed_key: #hashed_key,
+        mime_type: #mime_type,
+      },));
+      hashed_keys.append_all(quote!(#hashed_key => #key,));
+    }
+
+    let assets = match &self.packfile {
+      Some(PackfileIndex {
+        file_name,
+        id,
+        entries,
+      }) => {
+        let mut index = TokenStream::new();
+        for (key, offset, len, hash) in entries {
+          let key: &str = key.as_ref();
+          index.append_all(quote!(#key => (#offset, #len, #hash),));
+        }
+        quote!(EmbeddedAssets::from_packfile(#file_name, #id, phf_map! { #index }, &[#global_hashes], phf_map! { #html_hashes }))
+      }
+      None => {
+        quote!(EmbeddedAssets::new(phf_map! { #assets }, &[#global_hashes], phf_map! { #html_hashes }))
+      }
+    };
+
     // we expect phf related items to be in path when generating the path code
     tokens.append_all(quote! {{
         #[allow(unused_imports)]
-        use ::tauri::utils::assets::{CspHash, EmbeddedAssets, phf, phf::phf_map};
-        EmbeddedAssets::new(phf_map! { #assets }, &[#global_hashes], phf_map! { #html_hashes })
+        use ::tauri::utils::assets::{AssetMetadata, CspHash, EmbeddedAssets, phf, phf::phf_map};
+        #inputs
+        #assets.with_manifest(phf_map! { #manifest }, phf_map! { #hashed_keys })
     }});
   }
 }
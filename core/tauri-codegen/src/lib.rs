@@ -18,9 +18,13 @@ use proc_macro2::TokenStream;
 use quote::{quote, ToTokens, TokenStreamExt};
 use std::{
   borrow::Cow,
+  collections::{BTreeMap, BTreeSet},
   fmt::{self, Write},
   path::{Path, PathBuf},
 };
+use tauri_utils::acl::{
+  capability::Capability, manifest::Manifest, resolved::Resolved, APP_ACL_KEY,
+};
 pub use tauri_utils::config::{parse::ConfigError, Config};
 use tauri_utils::platform::Target;
 use tauri_utils::write_if_changed;
@@ -102,6 +106,41 @@ pub fn get_config(path: &Path) -> Result<(Config, PathBuf), CodegenConfigError>
   Ok((config, parent))
 }
 
+/// The app commands allowed by any of the capabilities written by `tauri-build` to the `OUT_DIR`.
+///
+/// Returns [`None`] if the ACL is not available or if the app does not define the permissions of its commands,
+/// in which case they are not checked at runtime.
+pub fn app_allowed_commands() -> Option<BTreeSet<String>> {
+  let out_dir = PathBuf::from(std::env::var_os("OUT_DIR")?);
+
+  let acl: BTreeMap<String, Manifest> = serde_json::from_str(
+    &std::fs::read_to_string(out_dir.join(context::ACL_MANIFESTS_FILE_NAME)).ok()?,
+  )
+  .ok()?;
+  if !acl.contains_key(APP_ACL_KEY) {
+    return None;
+  }
+  let capabilities: BTreeMap<String, Capability> = serde_json::from_str(
+    &std::fs::read_to_string(out_dir.join(context::CAPABILITIES_FILE_NAME)).ok()?,
+  )
+  .ok()?;
+
+  let target = std::env::var("TAURI_ENV_TARGET_TRIPLE")
+    .as_deref()
+    .map(Target::from_triple)
+    .unwrap_or_else(|_| Target::current());
+  // the context codegen reports the ACL errors
+  let resolved = Resolved::resolve(&acl, capabilities, target).ok()?;
+
+  Some(
+    resolved
+      .allowed_commands
+      .into_keys()
+      .filter(|command| !command.starts_with("plugin:"))
+      .collect(),
+  )
+}
+
 /// Create a blake3 checksum of the passed bytes.
 fn checksum(bytes: &[u8]) -> Result<String, fmt::Error> {
   let mut hasher = vendor::blake3_reference::Hasher::default();
@@ -2,10 +2,14 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
-use quote::format_ident;
+use std::collections::{HashMap, HashSet};
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote, quote_spanned, ToTokens, TokenStreamExt};
 use syn::{
   parse::{Parse, ParseBuffer, ParseStream},
-  Attribute, Ident, Path, Token,
+  spanned::Spanned,
+  Attribute, Ident, Path, Token, Type,
 };
 
 struct CommandDef {
@@ -13,12 +17,29 @@ struct CommandDef {
   attrs: Vec<Attribute>,
 }
 
-impl Parse for CommandDef {
+/// A type with a command `impl` block, written as `<Type>`.
+struct ServiceDef {
+  ty: Type,
+  attrs: Vec<Attribute>,
+}
+
+enum HandlerItem {
+  Command(CommandDef),
+  Service(ServiceDef),
+}
+
+impl Parse for HandlerItem {
   fn parse(input: ParseStream) -> syn::Result<Self> {
     let attrs = input.call(Attribute::parse_outer)?;
-    let path = input.parse()?;
-
-    Ok(CommandDef { path, attrs })
+    if input.peek(Token![<]) {
+      input.parse::<Token![<]>()?;
+      let ty = input.parse()?;
+      input.parse::<Token![>]>()?;
+      Ok(HandlerItem::Service(ServiceDef { ty, attrs }))
+    } else {
+      let path = input.parse()?;
+      Ok(HandlerItem::Command(CommandDef { path, attrs }))
+    }
   }
 }
 
@@ -27,11 +48,50 @@ pub struct Handler {
   command_defs: Vec<CommandDef>,
   commands: Vec<Ident>,
   wrappers: Vec<Path>,
+  services: Vec<ServiceDef>,
 }
 
 impl Parse for Handler {
   fn parse(input: &ParseBuffer<'_>) -> syn::Result<Self> {
-    let command_defs = input.parse_terminated(CommandDef::parse, Token![,])?;
+    let mut command_defs = Vec::new();
+    let mut services = Vec::new();
+    for item in input.parse_terminated(HandlerItem::parse, Token![,])? {
+      match item {
+        HandlerItem::Command(command_def) => command_defs.push(command_def),
+        HandlerItem::Service(service_def) => services.push(service_def),
+      }
+    }
+
+    // the same name would always be handled by the first command,
+    // unless the attributes differ, e.g. `#[cfg(desktop)]` and `#[cfg(mobile)]` commands
+    let mut registered: HashMap<String, (&Path, String)> = HashMap::new();
+    for command_def in &command_defs {
+      let name = command_def
+        .path
+        .segments
+        .last()
+        .expect("parsed syn::Path has no segment")
+        .ident
+        .to_string();
+      let attrs = command_def
+        .attrs
+        .iter()
+        .map(|attr| attr.to_token_stream().to_string())
+        .collect::<String>();
+      if let Some((path, registered_attrs)) = registered.get(&name) {
+        if *registered_attrs == attrs {
+          return Err(syn::Error::new(
+            command_def.path.span(),
+            format!(
+              "the `{name}` command is already registered by `{}`",
+              path.to_token_stream().to_string().replace(' ', "")
+            ),
+          ));
+        }
+      } else {
+        registered.insert(name, (&command_def.path, attrs));
+      }
+    }
 
     // parse the command names and wrappers from the passed paths
     let (commands, wrappers) = command_defs
@@ -51,9 +111,10 @@ impl Parse for Handler {
       .unzip();
 
     Ok(Self {
-      command_defs: command_defs.into_iter().collect(), // remove punctuation separators
+      command_defs,
       commands,
       wrappers,
+      services,
     })
   }
 }
@@ -64,6 +125,7 @@ impl From<Handler> for proc_macro::TokenStream {
       command_defs,
       commands,
       wrappers,
+      services,
     }: Handler,
   ) -> Self {
     let cmd = format_ident!("__tauri_cmd__");
@@ -72,15 +134,68 @@ impl From<Handler> for proc_macro::TokenStream {
       .into_iter()
       .map(|def| (def.path, def.attrs))
       .unzip();
-    quote::quote!(move |#invoke| {
+    let (services, service_attrs): (Vec<Type>, Vec<Vec<Attribute>>) =
+      services.into_iter().map(|def| (def.ty, def.attrs)).unzip();
+    let unused_commands_check = unused_commands_check(&commands, &paths);
+    quote::quote!({
+      #unused_commands_check
+
+      move |#invoke| {
       let #cmd = #invoke.message.command();
       match #cmd {
         #(#(#attrs)* stringify!(#commands) => #wrappers!(#paths, #invoke),)*
+        #(
+          #(#service_attrs)*
+          _ if <#services as ::tauri::ipc::Commands<_>>::accepts(&#invoke) => {
+            <#services as ::tauri::ipc::Commands<_>>::handle(#invoke)
+          }
+        )*
         _ => {
           return false;
         },
       }
-    })
+    }})
     .into()
   }
 }
+
+/// Warns about the app commands that are not allowed by any capability when the `tauri_check_capabilities` cfg is set,
+/// so they would be rejected at runtime.
+///
+/// The warnings are emitted as deprecation warnings since proc macros can't emit warnings on stable Rust.
+fn unused_commands_check(commands: &[Ident], paths: &[Path]) -> TokenStream2 {
+  let is_plugin =
+    std::env::var("CARGO_PKG_NAME").is_ok_and(|name| name.starts_with("tauri-plugin-"));
+  let Some(allowed_commands) = (!is_plugin)
+    .then(tauri_codegen::app_allowed_commands)
+    .flatten()
+  else {
+    return TokenStream2::new();
+  };
+
+  let mut checks = TokenStream2::new();
+  let mut checked = HashSet::new();
+  for (command, path) in commands.iter().zip(paths) {
+    let name = command.to_string();
+    if !allowed_commands.contains(&name) && checked.insert(name.clone()) {
+      let note = format!("the `{name}` command is not allowed by any capability");
+      checks.append_all(quote_spanned! {path.span() =>
+        #[deprecated(note = #note)]
+        #[allow(non_upper_case_globals)]
+        const #command: () = ();
+        let _: () = #command;
+      });
+    }
+  }
+
+  if checks.is_empty() {
+    return checks;
+  }
+
+  quote!(
+    #[cfg(tauri_check_capabilities)]
+    const _: () = {
+      #checks
+    };
+  )
+}
@@ -5,9 +5,14 @@
 use proc_macro2::Ident;
 use syn::{Path, PathSegment};
 
-pub use self::{handler::Handler, wrapper::wrapper};
+pub use self::{
+  handler::Handler,
+  service::{derive_commands, service},
+  wrapper::wrapper,
+};
 
 mod handler;
+mod service;
 mod wrapper;
 
 /// The autogenerated wrapper ident.
@@ -0,0 +1,209 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use proc_macro::TokenStream;
+use proc_macro2::{Ident, Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::{
+  parse_macro_input, parse_quote, spanned::Spanned, DeriveInput, FnArg, ImplItem, ImplItemFn,
+  ItemImpl, LitStr,
+};
+
+use super::wrapper::{
+  async_ref_return_type, body_async, body_blocking, handler_span, plugin_name, result_check,
+  ExecutionContext, Invoke, WrapperAttributes,
+};
+
+/// The generated associated function handling the invokes of the commands of an `impl` block.
+const INVOKE_FN: &str = "__tauri_invoke__";
+/// The generated associated constant listing the commands of an `impl` block.
+const COMMANDS_CONST: &str = "__TAURI_COMMANDS__";
+
+/// Whether the method is a command, i.e. takes `&self` so it can be called on the managed state.
+fn is_command(method: &ImplItemFn) -> bool {
+  matches!(
+    method.sig.inputs.first(),
+    Some(FnArg::Receiver(receiver)) if receiver.reference.is_some() && receiver.mutability.is_none()
+  )
+}
+
+/// Turns the `&self` methods of an `impl` block into commands of the managed state of the type.
+///
+/// The block is kept as is, and an inherent `impl` block is generated with the command names
+/// and a function handling their invokes, used by the [`Commands`] derive.
+///
+/// [`Commands`]: crate::Commands
+pub fn service(attributes: TokenStream, item: TokenStream) -> TokenStream {
+  let mut attrs = parse_macro_input!(attributes as WrapperAttributes);
+  let item_impl = parse_macro_input!(item as ItemImpl);
+  attrs.methods = true;
+  // the commands are not generated in a macro
+  if attrs.crate_root {
+    attrs.root = quote!(crate);
+  }
+
+  let invoke = Invoke::default();
+  let Invoke {
+    message,
+    resolver,
+    acl,
+  } = &invoke;
+  let root = &attrs.root;
+  let plugin_name = plugin_name();
+
+  let methods = item_impl
+    .items
+    .iter()
+    .filter_map(|item| match item {
+      ImplItem::Fn(method) if is_command(method) => Some(method),
+      _ => None,
+    })
+    .collect::<Vec<_>>();
+
+  let mut commands = Vec::new();
+  let mut arms = Vec::new();
+  for method in methods {
+    let ident = &method.sig.ident;
+    let call = match &item_impl.trait_ {
+      Some((_, trait_path, _)) => quote!(<Self as #trait_path>::#ident),
+      None => quote!(Self::#ident),
+    };
+
+    let execution_context = if method.sig.asyncness.is_some() {
+      ExecutionContext::Async
+    } else {
+      attrs.execution_context.clone()
+    };
+    // the body reads the execution context from the attributes
+    let method_attrs = WrapperAttributes {
+      execution_context,
+      ..attrs.clone()
+    };
+
+    let async_command_check = match async_ref_return_type(&method.sig) {
+      Ok(Some(return_type)) => {
+        let check = result_check(return_type);
+        quote! {
+          #[allow(unreachable_code, clippy::diverging_sub_expression)]
+          let _: () = #check;
+        }
+      }
+      Ok(None) => TokenStream2::new(),
+      Err(e) => e.into_compile_error(),
+    };
+
+    let body = match method_attrs.execution_context {
+      ExecutionContext::Async => {
+        body_async(&plugin_name, &method.sig, &call, &invoke, &method_attrs)
+      }
+      ExecutionContext::Blocking => {
+        body_blocking(&plugin_name, &method.sig, &call, &invoke, &method_attrs)
+      }
+    }
+    .unwrap_or_else(syn::Error::into_compile_error);
+
+    let maybe_span = handler_span(
+      message,
+      &method.sig,
+      &method_attrs.execution_context,
+      method.span(),
+    );
+
+    commands.push(ident);
+    arms.push(quote!(stringify!(#ident) => {
+      #async_command_check
+
+      #[allow(unused_variables)]
+      let #root::ipc::Invoke { message: #message, resolver: #resolver, acl: #acl } = __tauri_invoke__;
+
+      #maybe_span
+
+      #body
+    }));
+  }
+
+  let (impl_generics, _, where_clause) = item_impl.generics.split_for_impl();
+  let self_ty = &item_impl.self_ty;
+  let invoke_fn = Ident::new(INVOKE_FN, Span::call_site());
+  let commands_const = Ident::new(COMMANDS_CONST, Span::call_site());
+  let runtime = format_ident!("__TauriRuntime");
+
+  quote!(
+    #item_impl
+
+    #[doc(hidden)]
+    impl #impl_generics #self_ty #where_clause {
+      /// The commands of the type.
+      pub const #commands_const: &'static [&'static str] = &[#(stringify!(#commands)),*];
+
+      /// Handles the invoke of one of the commands of the type, returns `false` if the command is unknown.
+      #[allow(unused_imports)]
+      pub fn #invoke_fn<#runtime: #root::Runtime>(__tauri_invoke__: #root::ipc::Invoke<#runtime>) -> bool
+      where
+        Self: ::std::marker::Send + ::std::marker::Sync + 'static,
+      {
+        use #root::ipc::private::*;
+        let __tauri_cmd__ = __tauri_invoke__.message.command();
+        match __tauri_cmd__ {
+          #(#arms)*
+          _ => false,
+        }
+      }
+    }
+  )
+  .into()
+}
+
+/// Implements `Commands` for a type with a command `impl` block.
+pub fn derive_commands(input: TokenStream) -> TokenStream {
+  let input = parse_macro_input!(input as DeriveInput);
+
+  let mut root = quote!(::tauri);
+  for attr in input.attrs.iter().filter(|a| a.path().is_ident("commands")) {
+    let result = attr.parse_nested_meta(|meta| {
+      if meta.path.is_ident("root") {
+        let lit: LitStr = meta.value()?.parse()?;
+        root = if lit.value() == "crate" {
+          quote!(crate)
+        } else {
+          let ident = Ident::new(&lit.value(), Span::call_site());
+          quote!(#ident)
+        };
+        Ok(())
+      } else {
+        Err(meta.error("unexpected input, expected `root`"))
+      }
+    });
+    if let Err(e) = result {
+      return e.into_compile_error().into();
+    }
+  }
+
+  let ident = &input.ident;
+  let runtime = format_ident!("__TauriRuntime");
+  let invoke_fn = Ident::new(INVOKE_FN, Span::call_site());
+  let commands_const = Ident::new(COMMANDS_CONST, Span::call_site());
+
+  let (_, ty_generics, _) = input.generics.split_for_impl();
+  let mut generics = input.generics.clone();
+  generics.params.push(parse_quote!(#runtime: #root::Runtime));
+  generics
+    .make_where_clause()
+    .predicates
+    .push(parse_quote!(Self: ::std::marker::Send + ::std::marker::Sync + 'static));
+  let (impl_generics, _, where_clause) = generics.split_for_impl();
+
+  quote!(
+    impl #impl_generics #root::ipc::Commands<#runtime> for #ident #ty_generics #where_clause {
+      fn commands() -> &'static [&'static str] {
+        Self::#commands_const
+      }
+
+      fn handle(invoke: #root::ipc::Invoke<#runtime>) -> bool {
+        Self::#invoke_fn(invoke)
+      }
+    }
+  )
+  .into()
+}
@@ -14,7 +14,7 @@ use syn::{
   parse_macro_input,
   punctuated::Punctuated,
   spanned::Spanned,
-  Expr, ExprLit, FnArg, ItemFn, Lit, Meta, Pat, Token, Visibility,
+  Expr, ExprLit, FnArg, ItemFn, Lit, Meta, Pat, Signature, Token, Type, Visibility,
 };
 
 enum WrapperAttributeKind {
@@ -34,10 +34,15 @@ impl Parse for WrapperAttributeKind {
   }
 }
 
-struct WrapperAttributes {
-  root: TokenStream2,
-  execution_context: ExecutionContext,
-  argument_case: ArgumentCase,
+#[derive(Clone)]
+pub(super) struct WrapperAttributes {
+  pub(super) root: TokenStream2,
+  pub(super) execution_context: ExecutionContext,
+  pub(super) argument_case: ArgumentCase,
+  /// Whether the commands are the methods of an `impl` block.
+  pub(super) methods: bool,
+  /// Whether the root is the current crate, available as `$crate` in the command wrapper macros.
+  pub(super) crate_root: bool,
 }
 
 impl Parse for WrapperAttributes {
@@ -46,6 +51,8 @@ impl Parse for WrapperAttributes {
       root: quote!(::tauri),
       execution_context: ExecutionContext::Blocking,
       argument_case: ArgumentCase::Camel,
+      methods: false,
+      crate_root: false,
     };
 
     let attrs = Punctuated::<WrapperAttributeKind, Token![,]>::parse_terminated(input)?;
@@ -80,6 +87,7 @@ impl Parse for WrapperAttributes {
             {
               let lit = s.value();
 
+              wrapper_attributes.crate_root = lit == "crate";
               wrapper_attributes.root = if lit == "crate" {
                 quote!($crate)
               } else {
@@ -106,23 +114,34 @@ impl Parse for WrapperAttributes {
 }
 
 /// The execution context of the command.
-enum ExecutionContext {
+#[derive(Clone)]
+pub(super) enum ExecutionContext {
   Async,
   Blocking,
 }
 
 /// The case of each argument name.
 #[derive(Copy, Clone)]
-enum ArgumentCase {
+pub(super) enum ArgumentCase {
   Snake,
   Camel,
 }
 
 /// The bindings we attach to `tauri::Invoke`.
-struct Invoke {
-  message: Ident,
-  resolver: Ident,
-  acl: Ident,
+pub(super) struct Invoke {
+  pub(super) message: Ident,
+  pub(super) resolver: Ident,
+  pub(super) acl: Ident,
+}
+
+impl Default for Invoke {
+  fn default() -> Self {
+    Self {
+      message: format_ident!("__tauri_message__"),
+      resolver: format_ident!("__tauri_resolver__"),
+      acl: format_ident!("__tauri_acl__"),
+    }
+  }
 }
 
 /// Create a new [`Wrapper`] from the function and the generated code parsed from the function.
@@ -142,80 +161,30 @@ pub fn wrapper(attributes: TokenStream, item: TokenStream) -> TokenStream {
     _ => TokenStream2::default(),
   };
 
-  let invoke = Invoke {
-    message: format_ident!("__tauri_message__"),
-    resolver: format_ident!("__tauri_resolver__"),
-    acl: format_ident!("__tauri_acl__"),
-  };
+  let invoke = Invoke::default();
 
-  // Tauri currently doesn't support async commands that take a reference as input and don't return
-  // a result. See: https://github.com/tauri-apps/tauri/issues/2533
-  //
-  // For now, we provide an informative error message to the user in that case. Once #2533 is
-  // resolved, this check can be removed.
-  let mut async_command_check = TokenStream2::new();
-  if function.sig.asyncness.is_some() {
-    // This check won't catch all possible problems but it should catch the most common ones.
-    let mut ref_argument_span = None;
-
-    for arg in &function.sig.inputs {
-      if let syn::FnArg::Typed(pat) = arg {
-        match &*pat.ty {
-          syn::Type::Reference(_) => {
-            ref_argument_span = Some(pat.span());
-          }
-          syn::Type::Path(path) => {
-            // Check if the type contains a lifetime argument
-            let last = path.path.segments.last().unwrap();
-            if let syn::PathArguments::AngleBracketed(args) = &last.arguments {
-              if args
-                .args
-                .iter()
-                .any(|arg| matches!(arg, syn::GenericArgument::Lifetime(_)))
-              {
-                ref_argument_span = Some(pat.span());
-              }
-            }
-          }
-          _ => {}
-        }
-
-        if let Some(span) = ref_argument_span {
-          if let syn::ReturnType::Type(_, return_type) = &function.sig.output {
-            // To check if the return type is `Result` we require it to check a trait that is
-            // only implemented by `Result`. That way we don't exclude renamed result types
-            // which we wouldn't otherwise be able to detect purely from the token stream.
-            // The "error message" displayed to the user is simply the trait name.
-            async_command_check = quote_spanned! {return_type.span() =>
-              #[allow(unreachable_code, clippy::diverging_sub_expression)]
-              const _: () = if false {
-                trait AsyncCommandMustReturnResult {}
-                impl<A, B> AsyncCommandMustReturnResult for ::std::result::Result<A, B> {}
-                let _check: #return_type = unreachable!();
-                let _: &dyn AsyncCommandMustReturnResult = &_check;
-              };
-            };
-          } else {
-            return quote_spanned! {
-              span => compile_error!("async commands that contain references as inputs must return a `Result`");
-            }.into();
-          }
-        }
+  let async_command_check = match async_ref_return_type(&function.sig) {
+    Ok(Some(return_type)) => {
+      let check = result_check(return_type);
+      quote_spanned! {return_type.span() =>
+        #[allow(unreachable_code, clippy::diverging_sub_expression)]
+        const _: () = #check;
       }
     }
-  }
+    Ok(None) => TokenStream2::new(),
+    Err(e) => return e.into_compile_error().into(),
+  };
 
-  let plugin_name = var("CARGO_PKG_NAME")
-    .expect("missing `CARGO_PKG_NAME` environment variable")
-    .strip_prefix("tauri-plugin-")
-    .map(|name| quote!(::core::option::Option::Some(#name)))
-    .unwrap_or_else(|| quote!(::core::option::Option::None));
+  let plugin_name = plugin_name();
 
+  let call = quote!($path);
   let body = match attrs.execution_context {
-    ExecutionContext::Async => body_async(&plugin_name, &function, &invoke, &attrs)
-      .unwrap_or_else(syn::Error::into_compile_error),
-    ExecutionContext::Blocking => body_blocking(&plugin_name, &function, &invoke, &attrs)
+    ExecutionContext::Async => body_async(&plugin_name, &function.sig, &call, &invoke, &attrs)
       .unwrap_or_else(syn::Error::into_compile_error),
+    ExecutionContext::Blocking => {
+      body_blocking(&plugin_name, &function.sig, &call, &invoke, &attrs)
+        .unwrap_or_else(syn::Error::into_compile_error)
+    }
   };
 
   let Invoke {
@@ -226,31 +195,12 @@ pub fn wrapper(attributes: TokenStream, item: TokenStream) -> TokenStream {
 
   let root = attrs.root;
 
-  let kind = match attrs.execution_context {
-    ExecutionContext::Async if function.sig.asyncness.is_none() => "sync_threadpool",
-    ExecutionContext::Async => "async",
-    ExecutionContext::Blocking => "sync",
-  };
-
-  let loc = function.span().start();
-  let line = loc.line;
-  let col = loc.column;
-
-  let maybe_span = if cfg!(feature = "tracing") {
-    quote!({
-      let _span = tracing::debug_span!(
-        "ipc::request::handler",
-        cmd = #message.command(),
-        kind = #kind,
-        loc.line = #line,
-        loc.col = #col,
-        is_internal = false,
-      )
-      .entered();
-    })
-  } else {
-    quote!()
-  };
+  let maybe_span = handler_span(
+    &message,
+    &function.sig,
+    &attrs.execution_context,
+    function.span(),
+  );
 
   // Rely on rust 2018 edition to allow importing a macro from a path.
   quote!(
@@ -282,14 +232,118 @@ pub fn wrapper(attributes: TokenStream, item: TokenStream) -> TokenStream {
   .into()
 }
 
+/// The plugin name of the commands, if the crate is a Tauri plugin.
+pub(super) fn plugin_name() -> TokenStream2 {
+  var("CARGO_PKG_NAME")
+    .expect("missing `CARGO_PKG_NAME` environment variable")
+    .strip_prefix("tauri-plugin-")
+    .map(|name| quote!(::core::option::Option::Some(#name)))
+    .unwrap_or_else(|| quote!(::core::option::Option::None))
+}
+
+/// Tauri currently doesn't support async commands that take a reference as input and don't return
+/// a result. See: https://github.com/tauri-apps/tauri/issues/2533
+///
+/// Returns the return type that must be checked to be a `Result` if the async command takes a reference,
+/// and an informative error if it doesn't return anything. Once #2533 is resolved, this check can be removed.
+pub(super) fn async_ref_return_type(sig: &Signature) -> syn::Result<Option<&Type>> {
+  if sig.asyncness.is_none() {
+    return Ok(None);
+  }
+
+  // This check won't catch all possible problems but it should catch the most common ones.
+  let ref_argument_span = sig.inputs.iter().find_map(|arg| match arg {
+    // `&self` receivers are taken from the managed state
+    FnArg::Receiver(receiver) => Some(receiver.span()),
+    FnArg::Typed(pat) => match &*pat.ty {
+      syn::Type::Reference(_) => Some(pat.span()),
+      syn::Type::Path(path) => {
+        // Check if the type contains a lifetime argument
+        let last = path.path.segments.last().unwrap();
+        match &last.arguments {
+          syn::PathArguments::AngleBracketed(args)
+            if args
+              .args
+              .iter()
+              .any(|arg| matches!(arg, syn::GenericArgument::Lifetime(_))) =>
+          {
+            Some(pat.span())
+          }
+          _ => None,
+        }
+      }
+      _ => None,
+    },
+  });
+
+  match (ref_argument_span, &sig.output) {
+    (None, _) => Ok(None),
+    (Some(_), syn::ReturnType::Type(_, return_type)) => Ok(Some(return_type)),
+    (Some(span), syn::ReturnType::Default) => Err(syn::Error::new(
+      span,
+      "async commands that contain references as inputs must return a `Result`",
+    )),
+  }
+}
+
+/// To check if the return type is `Result` we require it to check a trait that is
+/// only implemented by `Result`. That way we don't exclude renamed result types
+/// which we wouldn't otherwise be able to detect purely from the token stream.
+/// The "error message" displayed to the user is simply the trait name.
+pub(super) fn result_check(return_type: &Type) -> TokenStream2 {
+  quote_spanned! {return_type.span() =>
+    if false {
+      trait AsyncCommandMustReturnResult {}
+      impl<A, B> AsyncCommandMustReturnResult for ::std::result::Result<A, B> {}
+      let _check: #return_type = unreachable!();
+      let _: &dyn AsyncCommandMustReturnResult = &_check;
+    }
+  }
+}
+
+/// The tracing span entered when a command handles an invoke.
+pub(super) fn handler_span(
+  message: &Ident,
+  sig: &Signature,
+  execution_context: &ExecutionContext,
+  span: Span,
+) -> TokenStream2 {
+  if !cfg!(feature = "tracing") {
+    return quote!();
+  }
+
+  let kind = match execution_context {
+    ExecutionContext::Async if sig.asyncness.is_none() => "sync_threadpool",
+    ExecutionContext::Async => "async",
+    ExecutionContext::Blocking => "sync",
+  };
+
+  let loc = span.start();
+  let line = loc.line;
+  let col = loc.column;
+
+  quote!({
+    let _span = tracing::debug_span!(
+      "ipc::request::handler",
+      cmd = #message.command(),
+      kind = #kind,
+      loc.line = #line,
+      loc.col = #col,
+      is_internal = false,
+    )
+    .entered();
+  })
+}
+
 /// Generates an asynchronous command response from the arguments and return value of a function.
 ///
 /// See the [`tauri::command`] module for all the items and traits that make this possible.
 ///
 /// [`tauri::command`]: https://docs.rs/tauri/*/tauri/runtime/index.html
-fn body_async(
+pub(super) fn body_async(
   plugin_name: &TokenStream2,
-  function: &ItemFn,
+  sig: &Signature,
+  call: &TokenStream2,
   invoke: &Invoke,
   attributes: &WrapperAttributes,
 ) -> syn::Result<TokenStream2> {
@@ -298,14 +352,14 @@ fn body_async(
     resolver,
     acl,
   } = invoke;
-  parse_args(plugin_name, function, message, acl, attributes).map(|args| {
+  parse_args(plugin_name, sig, message, acl, attributes).map(|args| {
     #[cfg(feature = "tracing")]
     quote! {
       use tracing::Instrument;
 
       let span = tracing::debug_span!("ipc::request::run");
       #resolver.respond_async_serialized(async move {
-        let result = $path(#(#args?),*);
+        let result = #call(#(#args?),*);
         let kind = (&result).async_kind();
         kind.future(result).await
       }
@@ -316,7 +370,7 @@ fn body_async(
     #[cfg(not(feature = "tracing"))]
     quote! {
       #resolver.respond_async_serialized(async move {
-        let result = $path(#(#args?),*);
+        let result = #call(#(#args?),*);
         let kind = (&result).async_kind();
         kind.future(result).await
       });
@@ -330,9 +384,10 @@ fn body_async(
 /// See the [`tauri::command`] module for all the items and traits that make this possible.
 ///
 /// [`tauri::command`]: https://docs.rs/tauri/*/tauri/runtime/index.html
-fn body_blocking(
+pub(super) fn body_blocking(
   plugin_name: &TokenStream2,
-  function: &ItemFn,
+  sig: &Signature,
+  call: &TokenStream2,
   invoke: &Invoke,
   attributes: &WrapperAttributes,
 ) -> syn::Result<TokenStream2> {
@@ -341,7 +396,7 @@ fn body_blocking(
     resolver,
     acl,
   } = invoke;
-  let args = parse_args(plugin_name, function, message, acl, attributes)?;
+  let args = parse_args(plugin_name, sig, message, acl, attributes)?;
 
   // the body of a `match` to early return any argument that wasn't successful in parsing.
   let match_body = quote!({
@@ -357,7 +412,7 @@ fn body_blocking(
 
   Ok(quote! {
     #maybe_span
-    let result = $path(#(match #args #match_body),*);
+    let result = #call(#(match #args #match_body),*);
     let kind = (&result).blocking_kind();
     kind.block(result, #resolver);
     return true;
@@ -367,25 +422,15 @@ fn body_blocking(
 /// Parse all arguments for the command wrapper to use from the signature of the command function.
 fn parse_args(
   plugin_name: &TokenStream2,
-  function: &ItemFn,
+  sig: &Signature,
   message: &Ident,
   acl: &Ident,
   attributes: &WrapperAttributes,
 ) -> syn::Result<Vec<TokenStream2>> {
-  function
-    .sig
+  sig
     .inputs
     .iter()
-    .map(|arg| {
-      parse_arg(
-        plugin_name,
-        &function.sig.ident,
-        arg,
-        message,
-        acl,
-        attributes,
-      )
-    })
+    .map(|arg| parse_arg(plugin_name, &sig.ident, arg, message, acl, attributes))
     .collect()
 }
 
@@ -398,9 +443,25 @@ fn parse_arg(
   acl: &Ident,
   attributes: &WrapperAttributes,
 ) -> syn::Result<TokenStream2> {
-  // we have no use for self arguments
+  let root = &attributes.root;
+
   let mut arg = match arg {
     FnArg::Typed(arg) => arg.pat.as_ref().clone(),
+    // the `&self` receiver of the methods of a command `impl` block is the managed state of the type
+    FnArg::Receiver(receiver)
+      if attributes.methods && receiver.reference.is_some() && receiver.mutability.is_none() =>
+    {
+      return Ok(quote!(#root::ipc::CommandArg::from_command(
+        #root::ipc::CommandItem {
+          plugin: #plugin_name,
+          name: stringify!(#command),
+          key: "self",
+          message: &#message,
+          acl: &#acl,
+        }
+      )
+      .map(|state: #root::State<'_, Self>| state.inner())));
+    }
     FnArg::Receiver(arg) => {
       return Err(syn::Error::new(
         arg.span(),
@@ -440,8 +501,6 @@ fn parse_arg(
     }
   }
 
-  let root = &attributes.root;
-
   Ok(quote!(#root::ipc::CommandArg::from_command(
     #root::ipc::CommandItem {
       plugin: #plugin_name,
@@ -29,13 +29,48 @@ mod context;
 
 /// Mark a function as a command handler. It creates a wrapper function with the necessary glue code.
 ///
+/// On an `impl` block, the methods taking `&self` are turned into commands called on the managed state of the type,
+/// see [`Commands`].
+///
 /// # Stability
 /// The output of this macro is managed internally by Tauri,
 /// and should not be accessed directly on normal applications.
 /// It may have breaking changes in the future.
 #[proc_macro_attribute]
 pub fn command(attributes: TokenStream, item: TokenStream) -> TokenStream {
-  command::wrapper(attributes, item)
+  if syn::parse::<syn::ItemImpl>(item.clone()).is_ok() {
+    command::service(attributes, item)
+  } else {
+    command::wrapper(attributes, item)
+  }
+}
+
+/// Implements `tauri::ipc::Commands` for a type with a [`command`](macro@command) `impl` block,
+/// so its commands can be added to [`generate_handler!`] as `<Type>`.
+///
+/// # Examples
+/// ```rust,ignore
+/// use tauri_macros::{command, generate_handler, Commands};
+///
+/// #[derive(Default, Commands)]
+/// struct Counter(std::sync::atomic::AtomicUsize);
+///
+/// #[command]
+/// impl Counter {
+///   fn increment(&self, by: usize) -> usize {
+///     self.0.fetch_add(by, std::sync::atomic::Ordering::SeqCst) + by
+///   }
+/// }
+///
+/// fn main() {
+///   let _handler = generate_handler![<Counter>];
+/// }
+/// ```
+///
+/// A type can only have a single command `impl` block, either an inherent or a trait implementation.
+#[proc_macro_derive(Commands, attributes(commands))]
+pub fn derive_commands(input: TokenStream) -> TokenStream {
+  command::derive_commands(input)
 }
 
 #[proc_macro_attribute]
@@ -43,7 +78,8 @@ pub fn mobile_entry_point(attributes: TokenStream, item: TokenStream) -> TokenSt
   mobile::entry_point(attributes, item)
 }
 
-/// Accepts a list of command functions. Creates a handler that allows commands to be called from JS with invoke().
+/// Accepts a list of command functions and of types deriving [`Commands`], written as `<Type>`.
+/// Creates a handler that allows commands to be called from JS with invoke().
 ///
 /// # Examples
 /// ```rust,ignore
@@ -60,6 +96,14 @@ pub fn mobile_entry_point(attributes: TokenStream, item: TokenStream) -> TokenSt
 ///   let _handler = generate_handler![command_one, command_two];
 /// }
 /// ```
+///
+/// Registering two commands with the same name is a compile error, unless their attributes differ,
+/// e.g. `#[cfg(desktop)] desktop::open` and `#[cfg(mobile)] mobile::open`.
+///
+/// When the app defines the permissions of its commands, build with the `TAURI_CHECK_CAPABILITIES=true`
+/// environment variable to get a warning for each command that is not allowed by any capability
+/// and would be rejected at runtime.
+///
 /// # Stability
 /// The output of this macro is managed internally by Tauri,
 /// and should not be accessed directly on normal applications.
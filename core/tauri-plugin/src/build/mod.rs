@@ -33,6 +33,7 @@ pub fn plugin_config<T: DeserializeOwned>(name: &str) -> Option<T> {
 pub struct Builder<'a> {
   commands: &'a [&'static str],
   global_scope_schema: Option<schemars::schema::RootSchema>,
+  config_schema: Option<schemars::schema::RootSchema>,
   global_api_script_path: Option<PathBuf>,
   android_path: Option<PathBuf>,
   ios_path: Option<PathBuf>,
@@ -43,6 +44,7 @@ impl<'a> Builder<'a> {
     Self {
       commands,
       global_scope_schema: None,
+      config_schema: None,
       global_api_script_path: None,
       android_path: None,
       ios_path: None,
@@ -55,6 +57,14 @@ impl<'a> Builder<'a> {
     self
   }
 
+  /// Sets the JSON schema of the plugin configuration, the `plugins > <plugin-name>` object of the app configuration.
+  ///
+  /// It is added to the app configuration schema so the configuration is validated by the CLI and IDEs can autocomplete it.
+  pub fn config_schema(mut self, schema: schemars::schema::RootSchema) -> Self {
+    self.config_schema.replace(schema);
+    self
+  }
+
   /// Sets the path to the script that is injected in the webview when the `withGlobalTauri` configuration is set to true.
   ///
   /// This is usually an IIFE that injects the plugin API JavaScript bindings to `window.__TAURI__`.
@@ -137,6 +147,10 @@ impl<'a> Builder<'a> {
       acl::build::define_global_scope_schema(global_scope_schema, &name, &out_dir)?;
     }
 
+    if let Some(config_schema) = self.config_schema {
+      tauri_utils::plugin::define_config_schema(&config_schema, &out_dir)?;
+    }
+
     if let Some(path) = self.global_api_script_path {
       tauri_utils::plugin::define_global_api_script_path(path);
     }
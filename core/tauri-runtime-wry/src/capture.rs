@@ -0,0 +1,266 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Captures the composited content of the webviews with the snapshot API of the platform webview.
+//!
+//! The system webviews render in their own process and don't expose their compositor surfaces,
+//! so the frames are copied to memory instead of being shared with the GPU.
+
+use tauri_runtime::{webview::WebviewFrame, Error, Result};
+use wry::WebView;
+
+pub type CaptureCallback = Box<dyn FnOnce(Result<WebviewFrame>) + Send>;
+
+/// Converts premultiplied pixels to straight alpha.
+#[allow(dead_code)]
+fn unpremultiply(rgba: &mut [u8]) {
+  for pixel in rgba.chunks_exact_mut(4) {
+    let alpha = pixel[3] as u32;
+    if alpha != 0 && alpha != 255 {
+      for channel in &mut pixel[..3] {
+        *channel = ((*channel as u32 * 255 + alpha / 2) / alpha).min(255) as u8;
+      }
+    }
+  }
+}
+
+#[cfg(any(
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd"
+))]
+pub fn capture_frame(webview: &WebView, callback: CaptureCallback) {
+  use gtk::cairo::{Format, ImageSurface};
+  use webkit2gtk::{SnapshotOptions, SnapshotRegion, WebViewExt};
+  use wry::WebViewExtUnix;
+
+  webview.webview().snapshot(
+    SnapshotRegion::Visible,
+    SnapshotOptions::TRANSPARENT_BACKGROUND,
+    None::<&gtk::gio::Cancellable>,
+    move |result| {
+      let frame = result
+        .map_err(|e| Error::CaptureFrame(e.to_string()))
+        .and_then(|surface| {
+          let mut surface =
+            ImageSurface::try_from(surface).map_err(|e| Error::CaptureFrame(e.to_string()))?;
+          if surface.format() != Format::ARgb32 && surface.format() != Format::Rgb24 {
+            return Err(Error::CaptureFrame(format!(
+              "unsupported surface format {:?}",
+              surface.format()
+            )));
+          }
+          surface.flush();
+          let (width, height, stride) = (
+            surface.width() as usize,
+            surface.height() as usize,
+            surface.stride() as usize,
+          );
+          let has_alpha = surface.format() == Format::ARgb32;
+          let data = surface
+            .data()
+            .map_err(|e| Error::CaptureFrame(e.to_string()))?;
+
+          // the cairo pixels are native-endian 32-bit premultiplied ARGB values
+          let mut rgba = Vec::with_capacity(width * height * 4);
+          for row in data.chunks(stride).take(height) {
+            for pixel in row[..width * 4].chunks_exact(4) {
+              let argb = u32::from_ne_bytes([pixel[0], pixel[1], pixel[2], pixel[3]]);
+              rgba.extend_from_slice(&[
+                (argb >> 16) as u8,
+                (argb >> 8) as u8,
+                argb as u8,
+                if has_alpha { (argb >> 24) as u8 } else { 255 },
+              ]);
+            }
+          }
+          unpremultiply(&mut rgba);
+
+          Ok(WebviewFrame {
+            width: width as u32,
+            height: height as u32,
+            rgba,
+          })
+        });
+      callback(frame);
+    },
+  );
+}
+
+#[cfg(target_os = "macos")]
+pub fn capture_frame(webview: &WebView, callback: CaptureCallback) {
+  use cocoa::base::{id, nil};
+  use objc::{class, msg_send, sel, sel_impl};
+  use std::sync::Mutex;
+  use wry::WebViewExtMacOS;
+
+  /// Reads the pixels of the snapshot from its TIFF representation.
+  unsafe fn read_image(image: id) -> Result<WebviewFrame> {
+    let tiff: id = msg_send![image, TIFFRepresentation];
+    let rep: id = msg_send![class!(NSBitmapImageRep), imageRepWithData: tiff];
+    if rep == nil {
+      return Err(Error::CaptureFrame("failed to read the snapshot".into()));
+    }
+    let width: isize = msg_send![rep, pixelsWide];
+    let height: isize = msg_send![rep, pixelsHigh];
+    let bytes_per_row: isize = msg_send![rep, bytesPerRow];
+    let samples_per_pixel: isize = msg_send![rep, samplesPerPixel];
+    let bits_per_sample: isize = msg_send![rep, bitsPerSample];
+    let is_planar: bool = msg_send![rep, isPlanar];
+    let format: usize = msg_send![rep, bitmapFormat];
+    let data: *const u8 = msg_send![rep, bitmapData];
+
+    // NSBitmapFormatAlphaFirst and NSBitmapFormatAlphaNonpremultiplied
+    let alpha_first = format & 1 != 0;
+    let premultiplied = format & 2 == 0;
+    if data.is_null() || is_planar || bits_per_sample != 8 || !(3..=4).contains(&samples_per_pixel)
+    {
+      return Err(Error::CaptureFrame(format!(
+        "unsupported snapshot format: {samples_per_pixel} samples of {bits_per_sample} bits"
+      )));
+    }
+
+    let (width, height) = (width as usize, height as usize);
+    let samples_per_pixel = samples_per_pixel as usize;
+    let data = std::slice::from_raw_parts(data, bytes_per_row as usize * height);
+    let mut rgba = Vec::with_capacity(width * height * 4);
+    for row in data.chunks(bytes_per_row as usize).take(height) {
+      for pixel in row[..width * samples_per_pixel].chunks_exact(samples_per_pixel) {
+        match (samples_per_pixel, alpha_first) {
+          (3, _) => rgba.extend_from_slice(&[pixel[0], pixel[1], pixel[2], 255]),
+          (_, true) => rgba.extend_from_slice(&[pixel[1], pixel[2], pixel[3], pixel[0]]),
+          _ => rgba.extend_from_slice(pixel),
+        }
+      }
+    }
+    if premultiplied && samples_per_pixel == 4 {
+      unpremultiply(&mut rgba);
+    }
+
+    Ok(WebviewFrame {
+      width: width as u32,
+      height: height as u32,
+      rgba,
+    })
+  }
+
+  // the completion handler is a block that may only be called once
+  let callback = Mutex::new(Some(callback));
+  let handler = block::ConcreteBlock::new(move |image: id, error: id| {
+    let Some(callback) = callback.lock().unwrap().take() else {
+      return;
+    };
+    let frame = if image == nil {
+      let description = unsafe {
+        if error == nil {
+          "unknown error".to_string()
+        } else {
+          let description: id = msg_send![error, localizedDescription];
+          let utf8: *const std::os::raw::c_char = msg_send![description, UTF8String];
+          std::ffi::CStr::from_ptr(utf8)
+            .to_string_lossy()
+            .into_owned()
+        }
+      };
+      Err(Error::CaptureFrame(description))
+    } else {
+      unsafe { read_image(image) }
+    };
+    callback(frame);
+  })
+  .copy();
+
+  unsafe {
+    let _: () = msg_send![
+      webview.webview(),
+      takeSnapshotWithConfiguration: nil
+      completionHandler: &*handler
+    ];
+  }
+}
+
+#[cfg(windows)]
+pub fn capture_frame(webview: &WebView, callback: CaptureCallback) {
+  use webview2_com::{
+    CapturePreviewCompletedHandler,
+    Microsoft::Web::WebView2::Win32::COREWEBVIEW2_CAPTURE_PREVIEW_IMAGE_FORMAT_PNG,
+  };
+  use windows::Win32::{
+    Graphics::Imaging::{
+      CLSID_WICImagingFactory, GUID_WICPixelFormat32bppRGBA, IWICImagingFactory,
+      WICConvertBitmapSource, WICDecodeMetadataCacheOnDemand,
+    },
+    System::Com::{CoCreateInstance, IStream, CLSCTX_INPROC_SERVER, STREAM_SEEK_SET},
+    UI::Shell::SHCreateMemStream,
+  };
+  use wry::WebViewExtWindows;
+
+  /// Decodes the PNG image written to the stream by WebView2.
+  unsafe fn read_image(stream: &IStream) -> windows::core::Result<WebviewFrame> {
+    stream.Seek(0, STREAM_SEEK_SET, None)?;
+    let factory: IWICImagingFactory =
+      CoCreateInstance(&CLSID_WICImagingFactory, None, CLSCTX_INPROC_SERVER)?;
+    let decoder =
+      factory.CreateDecoderFromStream(stream, std::ptr::null(), WICDecodeMetadataCacheOnDemand)?;
+    let source = WICConvertBitmapSource(&GUID_WICPixelFormat32bppRGBA, &decoder.GetFrame(0)?)?;
+    let (mut width, mut height) = (0, 0);
+    source.GetSize(&mut width, &mut height)?;
+    let mut rgba = vec![0; width as usize * height as usize * 4];
+    source.CopyPixels(std::ptr::null(), width * 4, &mut rgba)?;
+    Ok(WebviewFrame {
+      width,
+      height,
+      rgba,
+    })
+  }
+
+  let result = unsafe {
+    let stream = SHCreateMemStream(None);
+    match (webview.controller().CoreWebView2(), stream) {
+      (Ok(core_webview), Some(stream)) => {
+        let stream_ = stream.clone();
+        core_webview
+          .CapturePreview(
+            COREWEBVIEW2_CAPTURE_PREVIEW_IMAGE_FORMAT_PNG,
+            &stream,
+            &CapturePreviewCompletedHandler::create(Box::new(move |result| {
+              callback(
+                result
+                  .and_then(|_| read_image(&stream_))
+                  .map_err(|e| Error::CaptureFrame(e.to_string())),
+              );
+              Ok(())
+            })),
+          )
+          .map_err(|e| Error::CaptureFrame(e.to_string()))
+      }
+      (Err(e), _) => Err(Error::CaptureFrame(e.to_string())),
+      (_, None) => Err(Error::CaptureFrame(
+        "failed to create the memory stream".into(),
+      )),
+    }
+  };
+  if let Err(e) = result {
+    log::error!("{e}");
+  }
+}
+
+#[cfg(any(target_os = "ios", target_os = "android"))]
+pub fn capture_frame(_webview: &WebView, callback: CaptureCallback) {
+  callback(Err(Error::CaptureFrame(
+    "capturing the webview is not supported on this platform".into(),
+  )));
+}
+
+#[cfg(test)]
+mod tests {
+  #[test]
+  fn unpremultiply() {
+    let mut rgba = vec![128, 64, 0, 128, 10, 20, 30, 0, 1, 2, 3, 255];
+    super::unpremultiply(&mut rgba);
+    assert_eq!(rgba, vec![255, 128, 0, 128, 10, 20, 30, 0, 1, 2, 3, 255]);
+  }
+}
@@ -0,0 +1,200 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Native surfaces created beneath the webviews of a window, for external renderers to draw into.
+
+use raw_window_handle::{HandleError, RawWindowHandle};
+use tao::{dpi::PhysicalSize, window::Window};
+use tauri_runtime::{Error, Result, SurfaceId};
+
+pub struct ChildSurface {
+  pub id: SurfaceId,
+  #[cfg(windows)]
+  hwnd: windows::Win32::Foundation::HWND,
+  #[cfg(target_os = "macos")]
+  view: cocoa::base::id,
+}
+
+#[cfg(windows)]
+impl ChildSurface {
+  pub fn new(id: SurfaceId, window: &Window) -> Result<Self> {
+    use tao::platform::windows::WindowExtWindows;
+    use windows::{
+      core::{w, PCWSTR},
+      Win32::{
+        Foundation::{HINSTANCE, HWND, LPARAM, LRESULT, WPARAM},
+        System::LibraryLoader::GetModuleHandleW,
+        UI::WindowsAndMessaging::*,
+      },
+    };
+
+    const CLASS_NAME: PCWSTR = w!("TAURI_CHILD_SURFACE");
+
+    // lets the mouse events through to the parent window
+    unsafe extern "system" fn child_surface_proc(
+      hwnd: HWND,
+      msg: u32,
+      wparam: WPARAM,
+      lparam: LPARAM,
+    ) -> LRESULT {
+      if msg == WM_NCHITTEST {
+        LRESULT(HTTRANSPARENT as _)
+      } else {
+        DefWindowProcW(hwnd, msg, wparam, lparam)
+      }
+    }
+
+    let hinstance = unsafe { GetModuleHandleW(PCWSTR::null()).unwrap_or_default() };
+    let class = WNDCLASSEXW {
+      cbSize: std::mem::size_of::<WNDCLASSEXW>() as u32,
+      lpfnWndProc: Some(child_surface_proc),
+      hInstance: HINSTANCE(hinstance.0),
+      lpszClassName: CLASS_NAME,
+      ..Default::default()
+    };
+    // fails once the class is registered, which is fine
+    unsafe { RegisterClassExW(&class) };
+
+    let size = window.inner_size();
+    let hwnd = unsafe {
+      CreateWindowExW(
+        WINDOW_EX_STYLE::default(),
+        CLASS_NAME,
+        PCWSTR::null(),
+        WS_CHILD | WS_VISIBLE | WS_CLIPSIBLINGS,
+        0,
+        0,
+        size.width as i32,
+        size.height as i32,
+        HWND(window.hwnd()),
+        HMENU::default(),
+        hinstance,
+        None,
+      )
+    };
+    if hwnd == HWND::default() {
+      return Err(Error::CreateChildSurface(
+        windows::core::Error::from_win32().to_string(),
+      ));
+    }
+
+    // the webviews are created on top of the surface
+    unsafe {
+      let _ = SetWindowPos(
+        hwnd,
+        HWND_BOTTOM,
+        0,
+        0,
+        0,
+        0,
+        SWP_NOACTIVATE | SWP_NOMOVE | SWP_NOSIZE,
+      );
+    }
+
+    Ok(Self { id, hwnd })
+  }
+
+  pub fn raw_window_handle(&self) -> std::result::Result<RawWindowHandle, HandleError> {
+    std::num::NonZeroIsize::new(self.hwnd.0)
+      .map(|hwnd| raw_window_handle::Win32WindowHandle::new(hwnd).into())
+      .ok_or(HandleError::Unavailable)
+  }
+
+  pub fn resize(&self, size: PhysicalSize<u32>) {
+    use windows::Win32::UI::WindowsAndMessaging::{
+      SetWindowPos, HWND_BOTTOM, SWP_NOACTIVATE, SWP_NOMOVE,
+    };
+
+    unsafe {
+      let _ = SetWindowPos(
+        self.hwnd,
+        HWND_BOTTOM,
+        0,
+        0,
+        size.width as i32,
+        size.height as i32,
+        SWP_NOACTIVATE | SWP_NOMOVE,
+      );
+    }
+  }
+}
+
+#[cfg(windows)]
+impl Drop for ChildSurface {
+  fn drop(&mut self) {
+    unsafe {
+      let _ = windows::Win32::UI::WindowsAndMessaging::DestroyWindow(self.hwnd);
+    }
+  }
+}
+
+#[cfg(target_os = "macos")]
+impl ChildSurface {
+  pub fn new(id: SurfaceId, window: &Window) -> Result<Self> {
+    use cocoa::{
+      base::{id, nil, YES},
+      foundation::NSRect,
+    };
+    use objc::{class, msg_send, sel, sel_impl};
+    use tao::platform::macos::WindowExtMacOS;
+
+    // NSViewWidthSizable | NSViewHeightSizable
+    const AUTORESIZING_MASK: usize = 2 | 16;
+    // NSWindowBelow
+    const BELOW: isize = -1;
+
+    unsafe {
+      let content_view = window.ns_view() as id;
+      let bounds: NSRect = msg_send![content_view, bounds];
+      let view: id = msg_send![class!(NSView), alloc];
+      let view: id = msg_send![view, initWithFrame: bounds];
+      if view == nil {
+        return Err(Error::CreateChildSurface(
+          "failed to create the view".into(),
+        ));
+      }
+      let _: () = msg_send![view, setAutoresizingMask: AUTORESIZING_MASK];
+      let _: () = msg_send![view, setWantsLayer: YES];
+      // the webviews are added to the content view too, on top of the surface
+      let _: () = msg_send![content_view, addSubview: view positioned: BELOW relativeTo: nil];
+      Ok(Self { id, view })
+    }
+  }
+
+  pub fn raw_window_handle(&self) -> std::result::Result<RawWindowHandle, HandleError> {
+    std::ptr::NonNull::new(self.view as *mut std::ffi::c_void)
+      .map(|view| raw_window_handle::AppKitWindowHandle::new(view).into())
+      .ok_or(HandleError::Unavailable)
+  }
+
+  /// The view is resized by AppKit with its autoresizing mask.
+  pub fn resize(&self, _size: PhysicalSize<u32>) {}
+}
+
+#[cfg(target_os = "macos")]
+impl Drop for ChildSurface {
+  fn drop(&mut self) {
+    use objc::{msg_send, sel, sel_impl};
+
+    unsafe {
+      let _: () = msg_send![self.view, removeFromSuperview];
+      let _: () = msg_send![self.view, release];
+    }
+  }
+}
+
+#[cfg(not(any(windows, target_os = "macos")))]
+impl ChildSurface {
+  pub fn new(_id: SurfaceId, _window: &Window) -> Result<Self> {
+    Err(Error::CreateChildSurface(
+      "child surfaces are not supported on this platform".into(),
+    ))
+  }
+
+  pub fn raw_window_handle(&self) -> std::result::Result<RawWindowHandle, HandleError> {
+    Err(HandleError::NotSupported)
+  }
+
+  pub fn resize(&self, _size: PhysicalSize<u32>) {}
+}
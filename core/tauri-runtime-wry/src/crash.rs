@@ -0,0 +1,102 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Detects the termination of the processes rendering the webviews.
+
+use tauri_runtime::window::ProcessCrashReason;
+use wry::WebView;
+
+/// Calls the handler when the web process of the webview terminates.
+#[cfg(any(
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd"
+))]
+pub fn watch<F: Fn(ProcessCrashReason) + 'static>(webview: &WebView, handler: F) {
+  use webkit2gtk::{WebProcessTerminationReason, WebViewExt};
+  use wry::WebViewExtUnix;
+
+  webview
+    .webview()
+    .connect_web_process_terminated(move |_, reason| {
+      handler(match reason {
+        WebProcessTerminationReason::ExceededMemoryLimit => ProcessCrashReason::OutOfMemory,
+        WebProcessTerminationReason::TerminatedByApi => ProcessCrashReason::Terminated,
+        _ => ProcessCrashReason::Crashed,
+      })
+    });
+}
+
+/// Calls the handler when the browser or the renderer process of the webview fails.
+///
+/// The failures of the frame, GPU and utility processes are ignored since WebView2 restarts them.
+#[cfg(windows)]
+pub fn watch<F: Fn(ProcessCrashReason) + 'static>(webview: &WebView, handler: F) {
+  use webview2_com::{
+    Microsoft::Web::WebView2::Win32::{
+      ICoreWebView2ProcessFailedEventArgs2, COREWEBVIEW2_PROCESS_FAILED_KIND,
+      COREWEBVIEW2_PROCESS_FAILED_KIND_BROWSER_PROCESS_EXITED,
+      COREWEBVIEW2_PROCESS_FAILED_KIND_RENDER_PROCESS_EXITED,
+      COREWEBVIEW2_PROCESS_FAILED_KIND_RENDER_PROCESS_UNRESPONSIVE,
+      COREWEBVIEW2_PROCESS_FAILED_REASON, COREWEBVIEW2_PROCESS_FAILED_REASON_OUT_OF_MEMORY,
+      COREWEBVIEW2_PROCESS_FAILED_REASON_TERMINATED,
+    },
+    ProcessFailedEventHandler,
+  };
+  use windows::{core::Interface, Win32::System::WinRT::EventRegistrationToken};
+  use wry::WebViewExtWindows;
+
+  let Ok(core) = (unsafe { webview.controller().CoreWebView2() }) else {
+    return;
+  };
+  let mut token = EventRegistrationToken::default();
+  let result = unsafe {
+    core.add_ProcessFailed(
+      &ProcessFailedEventHandler::create(Box::new(move |_, args| {
+        let Some(args) = args else {
+          return Ok(());
+        };
+        let mut kind = COREWEBVIEW2_PROCESS_FAILED_KIND::default();
+        args.ProcessFailedKind(&mut kind)?;
+        let reason = match kind {
+          COREWEBVIEW2_PROCESS_FAILED_KIND_RENDER_PROCESS_UNRESPONSIVE => {
+            ProcessCrashReason::Unresponsive
+          }
+          COREWEBVIEW2_PROCESS_FAILED_KIND_BROWSER_PROCESS_EXITED
+          | COREWEBVIEW2_PROCESS_FAILED_KIND_RENDER_PROCESS_EXITED => {
+            let mut reason = COREWEBVIEW2_PROCESS_FAILED_REASON::default();
+            if let Ok(args) = args.cast::<ICoreWebView2ProcessFailedEventArgs2>() {
+              let _ = args.Reason(&mut reason);
+            }
+            match reason {
+              COREWEBVIEW2_PROCESS_FAILED_REASON_OUT_OF_MEMORY => ProcessCrashReason::OutOfMemory,
+              COREWEBVIEW2_PROCESS_FAILED_REASON_TERMINATED => ProcessCrashReason::Terminated,
+              _ => ProcessCrashReason::Crashed,
+            }
+          }
+          _ => return Ok(()),
+        };
+        handler(reason);
+        Ok(())
+      })),
+      &mut token,
+    )
+  };
+  if let Err(e) = result {
+    log::error!("failed to watch the webview process failures: {e}");
+  }
+}
+
+/// The WKWebView and mobile webview process terminations are not reported.
+#[cfg(not(any(
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd",
+  windows
+)))]
+pub fn watch<F: Fn(ProcessCrashReason) + 'static>(_webview: &WebView, _handler: F) {}
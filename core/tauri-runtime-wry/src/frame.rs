@@ -0,0 +1,128 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! The rounded corners and the border color of the windows on Windows 11 and macOS.
+
+use tao::window::Window;
+use tauri_utils::{config::Color, CornerPreference};
+
+#[cfg(windows)]
+mod platform {
+  use tao::{platform::windows::WindowExtWindows, window::Window};
+  use tauri_utils::{config::Color, CornerPreference};
+  use windows::Win32::{
+    Foundation::HWND,
+    Graphics::Dwm::{
+      DwmSetWindowAttribute, DWMWA_BORDER_COLOR, DWMWA_COLOR_DEFAULT,
+      DWMWA_WINDOW_CORNER_PREFERENCE, DWMWCP_DEFAULT, DWMWCP_DONOTROUND, DWMWCP_ROUND,
+      DWMWCP_ROUNDSMALL, DWMWINDOWATTRIBUTE,
+    },
+  };
+
+  /// Sets the attribute, failing before Windows 11 which doesn't know these attributes.
+  fn set_attribute<T>(window: &Window, attribute: DWMWINDOWATTRIBUTE, value: &T) {
+    let _ = unsafe {
+      DwmSetWindowAttribute(
+        HWND(window.hwnd()),
+        attribute,
+        value as *const T as *const _,
+        std::mem::size_of::<T>() as u32,
+      )
+    };
+  }
+
+  pub fn set_corner_preference(window: &Window, preference: CornerPreference) {
+    let preference = match preference {
+      CornerPreference::Default => DWMWCP_DEFAULT,
+      CornerPreference::DoNotRound => DWMWCP_DONOTROUND,
+      CornerPreference::Round => DWMWCP_ROUND,
+      CornerPreference::RoundSmall => DWMWCP_ROUNDSMALL,
+    };
+    set_attribute(window, DWMWA_WINDOW_CORNER_PREFERENCE, &preference);
+  }
+
+  pub fn set_border_color(window: &Window, color: Option<Color>) {
+    // a COLORREF, 0x00BBGGRR
+    let color = color.map_or(DWMWA_COLOR_DEFAULT, |Color(r, g, b, _)| {
+      u32::from(r) | u32::from(g) << 8 | u32::from(b) << 16
+    });
+    set_attribute(window, DWMWA_BORDER_COLOR, &color);
+  }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+  use cocoa::base::{id, nil, NO, YES};
+  use objc::{class, msg_send, sel, sel_impl};
+  use tao::{platform::macos::WindowExtMacOS, window::Window};
+  use tauri_utils::{config::Color, CornerPreference};
+
+  /// The layer of the content view, drawing the corners and the border of the window.
+  unsafe fn layer(window: &Window) -> id {
+    let ns_view: id = window.ns_view() as _;
+    let _: () = msg_send![ns_view, setWantsLayer: YES];
+    msg_send![ns_view, layer]
+  }
+
+  pub fn set_corner_preference(window: &Window, preference: CornerPreference) {
+    // the radii of the window corners on recent macOS versions
+    let radius: f64 = match preference {
+      CornerPreference::Default | CornerPreference::DoNotRound => 0.,
+      CornerPreference::Round => 10.,
+      CornerPreference::RoundSmall => 4.,
+    };
+    unsafe {
+      let layer = layer(window);
+      let _: () = msg_send![layer, setCornerRadius: radius];
+      let _: () = msg_send![layer, setMasksToBounds: if radius > 0. { YES } else { NO }];
+      // recomputes the shadow for the new shape
+      let ns_window: id = window.ns_window() as _;
+      let _: () = msg_send![ns_window, invalidateShadow];
+    }
+  }
+
+  pub fn set_border_color(window: &Window, color: Option<Color>) {
+    unsafe {
+      let layer = layer(window);
+      match color {
+        Some(Color(r, g, b, a)) => {
+          let ns_color: id = msg_send![
+            class!(NSColor),
+            colorWithSRGBRed: f64::from(r) / 255.
+            green: f64::from(g) / 255.
+            blue: f64::from(b) / 255.
+            alpha: f64::from(a) / 255.
+          ];
+          let cg_color: id = msg_send![ns_color, CGColor];
+          let _: () = msg_send![layer, setBorderColor: cg_color];
+          let _: () = msg_send![layer, setBorderWidth: 1f64];
+        }
+        None => {
+          let _: () = msg_send![layer, setBorderColor: nil];
+          let _: () = msg_send![layer, setBorderWidth: 0f64];
+        }
+      }
+    }
+  }
+}
+
+#[cfg(not(any(windows, target_os = "macos")))]
+mod platform {
+  use tao::window::Window;
+  use tauri_utils::{config::Color, CornerPreference};
+
+  pub fn set_corner_preference(_window: &Window, _preference: CornerPreference) {}
+
+  pub fn set_border_color(_window: &Window, _color: Option<Color>) {}
+}
+
+/// Sets how the corners of the window are rounded, a no-op on the unsupported platforms.
+pub fn set_corner_preference(window: &Window, preference: CornerPreference) {
+  platform::set_corner_preference(window, preference)
+}
+
+/// Sets the color of the window border, a no-op on the unsupported platforms.
+pub fn set_border_color(window: &Window, color: Option<Color>) {
+  platform::set_border_color(window, color)
+}
@@ -20,14 +20,15 @@ use raw_window_handle::{DisplayHandle, HasDisplayHandle, HasWindowHandle};
 use tauri_runtime::{
   dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize, Position, Size},
   monitor::Monitor,
-  webview::{DetachedWebview, DownloadEvent, PendingWebview, WebviewIpcHandler},
+  webview::{DetachedWebview, DownloadEvent, PendingWebview, WebviewFrame, WebviewIpcHandler},
   window::{
-    CursorIcon, DetachedWindow, DragDropEvent, PendingWindow, RawWindow, WebviewEvent,
+    CursorIcon, DetachedWindow, DragDropEvent, ImeEvent, PendingWindow, RawWindow, WebviewEvent,
     WindowBuilder, WindowBuilderBase, WindowEvent, WindowId, WindowSizeConstraints,
   },
-  DeviceEventFilter, Error, EventLoopProxy, ExitRequestedEventAction, Icon, ProgressBarState,
-  ProgressBarStatus, Result, RunEvent, Runtime, RuntimeHandle, RuntimeInitArgs, UserAttentionType,
-  UserEvent, WebviewDispatch, WebviewEventId, WindowDispatch, WindowEventId,
+  DeviceEventFilter, Error, EventLoopProxy, ExitRequestedEventAction, Icon, LifecycleEvent,
+  ProgressBarState, ProgressBarStatus, Result, RunEvent, Runtime, RuntimeHandle, RuntimeInitArgs,
+  SurfaceId, UserAttentionType, UserEvent, WebviewDispatch, WebviewEventId, WindowDispatch,
+  WindowEventId,
 };
 
 #[cfg(target_os = "macos")]
@@ -113,6 +114,7 @@ use std::{
     Arc, Mutex, Weak,
   },
   thread::{current as current_thread, ThreadId},
+  time::{Duration, Instant},
 };
 
 pub type WebviewId = u32;
@@ -128,6 +130,11 @@ type IpcHandler = dyn Fn(Request<String>) + 'static;
 ))]
 mod undecorated_resizing;
 
+mod capture;
+mod child_surface;
+mod crash;
+mod frame;
+mod throttle;
 mod webview;
 pub use webview::Webview;
 
@@ -217,6 +224,8 @@ pub struct Context<T: UserEvent> {
   next_window_event_id: Arc<AtomicU32>,
   next_webview_event_id: Arc<AtomicU32>,
   next_webcontext_id: Arc<AtomicU32>,
+  next_surface_id: Arc<AtomicU32>,
+  window_event_throttle: Option<Duration>,
 }
 
 impl<T: UserEvent> Context<T> {
@@ -250,6 +259,10 @@ impl<T: UserEvent> Context<T> {
   fn next_webcontext_id(&self) -> u32 {
     self.next_webcontext_id.fetch_add(1, Ordering::Relaxed)
   }
+
+  fn next_surface_id(&self) -> SurfaceId {
+    self.next_surface_id.fetch_add(1, Ordering::Relaxed)
+  }
 }
 
 impl<T: UserEvent> Context<T> {
@@ -498,6 +511,7 @@ impl<'a> From<&TaoWindowEvent<'a>> for WindowEventWrapper {
       #[cfg(any(target_os = "linux", target_os = "macos"))]
       TaoWindowEvent::Focused(focused) => WindowEvent::Focused(*focused),
       TaoWindowEvent::ThemeChanged(theme) => WindowEvent::ThemeChanged(map_theme(theme)),
+      TaoWindowEvent::ReceivedImeText(text) => WindowEvent::Ime(ImeEvent::Commit(text.clone())),
       _ => return Self(None),
     };
     Self(Some(event))
@@ -695,6 +709,8 @@ impl From<ProgressBarState> for ProgressBarStateWrapper {
 pub struct WindowBuilderWrapper {
   inner: TaoWindowBuilder,
   center: bool,
+  corner_preference: Option<tauri_utils::CornerPreference>,
+  border_color: Option<tauri_utils::config::Color>,
   #[cfg(target_os = "macos")]
   tabbing_identifier: Option<String>,
 }
@@ -702,7 +718,10 @@ pub struct WindowBuilderWrapper {
 impl std::fmt::Debug for WindowBuilderWrapper {
   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     let mut s = f.debug_struct("WindowBuilderWrapper");
-    s.field("inner", &self.inner).field("center", &self.center);
+    s.field("inner", &self.inner)
+      .field("center", &self.center)
+      .field("corner_preference", &self.corner_preference)
+      .field("border_color", &self.border_color);
     #[cfg(target_os = "macos")]
     {
       s.field("tabbing_identifier", &self.tabbing_identifier);
@@ -955,6 +974,16 @@ impl WindowBuilder for WindowBuilderWrapper {
     self
   }
 
+  fn corner_preference(mut self, preference: tauri_utils::CornerPreference) -> Self {
+    self.corner_preference.replace(preference);
+    self
+  }
+
+  fn border_color(mut self, color: tauri_utils::config::Color) -> Self {
+    self.border_color.replace(color);
+    self
+  }
+
   #[cfg(windows)]
   fn owner(mut self, owner: HWND) -> Self {
     self.inner = self.inner.with_owner_window(owner.0);
@@ -1161,6 +1190,12 @@ pub enum WindowMessage {
   ))]
   GtkBox(Sender<GtkBox>),
   RawWindowHandle(Sender<std::result::Result<SendRawWindowHandle, raw_window_handle::HandleError>>),
+  CreateChildSurface(SurfaceId, Sender<Result<()>>),
+  ChildSurfaceHandle(
+    SurfaceId,
+    Sender<std::result::Result<SendRawWindowHandle, raw_window_handle::HandleError>>,
+  ),
+  DestroyChildSurface(SurfaceId),
   Theme(Sender<Theme>),
   // Setters
   Center,
@@ -1180,6 +1215,8 @@ pub enum WindowMessage {
   Destroy,
   SetDecorations(bool),
   SetShadow(bool),
+  SetCornerPreference(tauri_utils::CornerPreference),
+  SetBorderColor(Option<tauri_utils::config::Color>),
   SetAlwaysOnBottom(bool),
   SetAlwaysOnTop(bool),
   SetVisibleOnAllWorkspaces(bool),
@@ -1190,6 +1227,7 @@ pub enum WindowMessage {
   SetSizeConstraints(WindowSizeConstraints),
   SetPosition(Position),
   SetFullscreen(bool),
+  SetFullscreenMode(tauri_runtime::window::FullscreenMode, Option<Monitor>),
   SetFocus,
   SetIcon(TaoWindowIcon),
   SetSkipTaskbar(bool),
@@ -1197,9 +1235,11 @@ pub enum WindowMessage {
   SetCursorVisible(bool),
   SetCursorIcon(CursorIcon),
   SetCursorPosition(Position),
+  SetImePosition(tauri_runtime::Rect),
   SetIgnoreCursorEvents(bool),
   SetProgressBar(ProgressBarState),
   SetTitleBarStyle(tauri_utils::TitleBarStyle),
+  SetTheme(Option<Theme>),
   DragWindow,
   ResizeDragWindow(tauri_runtime::ResizeDirection),
   RequestRedraw,
@@ -1231,6 +1271,7 @@ pub enum WebviewMessage {
   SynthesizedWindowEvent(SynthesizedWindowEvent),
   Navigate(Url),
   Print,
+  CaptureFrame(capture::CaptureCallback),
   Close,
   SetPosition(Position),
   SetSize(Size),
@@ -1395,6 +1436,20 @@ impl<T: UserEvent> WebviewDispatch<T> for WryWebviewDispatcher<T> {
     )
   }
 
+  fn capture_frame<F: FnOnce(Result<WebviewFrame>) + Send + 'static>(
+    &self,
+    callback: F,
+  ) -> Result<()> {
+    send_user_message(
+      &self.context,
+      Message::Webview(
+        *self.window_id.lock().unwrap(),
+        self.webview_id,
+        WebviewMessage::CaptureFrame(Box::new(callback)),
+      ),
+    )
+  }
+
   fn close(&self) -> Result<()> {
     send_user_message(
       &self.context,
@@ -1689,6 +1744,28 @@ impl<T: UserEvent> WindowDispatch<T> for WryWindowDispatcher<T> {
       .and_then(|r| r.map(|h| unsafe { raw_window_handle::WindowHandle::borrow_raw(h.0) }))
   }
 
+  fn create_child_surface(&self) -> Result<SurfaceId> {
+    let id = self.context.next_surface_id();
+    window_getter!(self, |tx| WindowMessage::CreateChildSurface(id, tx))??;
+    Ok(id)
+  }
+
+  fn child_surface_handle(
+    &self,
+    id: SurfaceId,
+  ) -> std::result::Result<raw_window_handle::WindowHandle<'_>, raw_window_handle::HandleError> {
+    window_getter!(self, |tx| WindowMessage::ChildSurfaceHandle(id, tx))
+      .map_err(|_| raw_window_handle::HandleError::Unavailable)
+      .and_then(|r| r.map(|h| unsafe { raw_window_handle::WindowHandle::borrow_raw(h.0) }))
+  }
+
+  fn destroy_child_surface(&self, id: SurfaceId) -> Result<()> {
+    send_user_message(
+      &self.context,
+      Message::Window(self.window_id, WindowMessage::DestroyChildSurface(id)),
+    )
+  }
+
   // Setters
 
   fn center(&self) -> Result<()> {
@@ -1836,6 +1913,23 @@ impl<T: UserEvent> WindowDispatch<T> for WryWindowDispatcher<T> {
     )
   }
 
+  fn set_corner_preference(&self, preference: tauri_utils::CornerPreference) -> Result<()> {
+    send_user_message(
+      &self.context,
+      Message::Window(
+        self.window_id,
+        WindowMessage::SetCornerPreference(preference),
+      ),
+    )
+  }
+
+  fn set_border_color(&self, color: Option<tauri_utils::config::Color>) -> Result<()> {
+    send_user_message(
+      &self.context,
+      Message::Window(self.window_id, WindowMessage::SetBorderColor(color)),
+    )
+  }
+
   fn set_always_on_bottom(&self, always_on_bottom: bool) -> Result<()> {
     send_user_message(
       &self.context,
@@ -1918,6 +2012,20 @@ impl<T: UserEvent> WindowDispatch<T> for WryWindowDispatcher<T> {
     )
   }
 
+  fn set_fullscreen_mode(
+    &self,
+    mode: tauri_runtime::window::FullscreenMode,
+    monitor: Option<Monitor>,
+  ) -> Result<()> {
+    send_user_message(
+      &self.context,
+      Message::Window(
+        self.window_id,
+        WindowMessage::SetFullscreenMode(mode, monitor),
+      ),
+    )
+  }
+
   fn set_focus(&self) -> Result<()> {
     send_user_message(
       &self.context,
@@ -1980,6 +2088,13 @@ impl<T: UserEvent> WindowDispatch<T> for WryWindowDispatcher<T> {
     )
   }
 
+  fn set_ime_position(&self, rect: tauri_runtime::Rect) -> crate::Result<()> {
+    send_user_message(
+      &self.context,
+      Message::Window(self.window_id, WindowMessage::SetImePosition(rect)),
+    )
+  }
+
   fn start_dragging(&self) -> Result<()> {
     send_user_message(
       &self.context,
@@ -2010,6 +2125,13 @@ impl<T: UserEvent> WindowDispatch<T> for WryWindowDispatcher<T> {
       Message::Window(self.window_id, WindowMessage::SetTitleBarStyle(style)),
     )
   }
+
+  fn set_theme(&self, theme: Option<Theme>) -> Result<()> {
+    send_user_message(
+      &self.context,
+      Message::Window(self.window_id, WindowMessage::SetTheme(theme)),
+    )
+  }
 }
 
 #[derive(Clone)]
@@ -2049,6 +2171,11 @@ pub struct WindowWrapper {
   has_children: AtomicBool,
   webviews: Vec<WebviewWrapper>,
   window_event_listeners: WindowEventListeners,
+  child_surfaces: Vec<child_surface::ChildSurface>,
+  resize_throttle: throttle::EventThrottle<PhysicalSize<u32>>,
+  move_throttle: throttle::EventThrottle<PhysicalPosition<i32>>,
+  // the fullscreen state at the last resize, to detect the changes made by the system
+  fullscreen: bool,
   #[cfg(windows)]
   is_window_transparent: bool,
   #[cfg(windows)]
@@ -2319,10 +2446,13 @@ impl<T: UserEvent> Wry<T> {
       use tao::platform::unix::EventLoopBuilderExtUnix;
       event_loop_builder.with_app_id(app_id);
     }
-    Self::init(event_loop_builder.build())
+    Self::init(event_loop_builder.build(), args.window_event_throttle)
   }
 
-  fn init(event_loop: EventLoop<Message<T>>) -> Result<Self> {
+  fn init(
+    event_loop: EventLoop<Message<T>>,
+    window_event_throttle: Option<Duration>,
+  ) -> Result<Self> {
     let main_thread_id = current_thread().id();
     let web_context = WebContextStore::default();
 
@@ -2346,6 +2476,8 @@ impl<T: UserEvent> Wry<T> {
       next_window_event_id: Default::default(),
       next_webview_event_id: Default::default(),
       next_webcontext_id: Default::default(),
+      next_surface_id: Default::default(),
+      window_event_throttle,
     };
 
     Ok(Self {
@@ -2565,26 +2697,56 @@ impl<T: UserEvent> Runtime<T> for Wry<T> {
   }
 
   #[cfg(desktop)]
-  fn run_iteration<F: FnMut(RunEvent<T>) + 'static>(&mut self, mut callback: F) {
+  fn run_iteration<F: FnMut(RunEvent<T>) + 'static>(&mut self, callback: F) {
+    self.pump_events(Some(std::time::Duration::ZERO), callback)
+  }
+
+  #[cfg(desktop)]
+  fn pump_events<F: FnMut(RunEvent<T>) + 'static>(
+    &mut self,
+    timeout: Option<std::time::Duration>,
+    mut callback: F,
+  ) {
     use tao::platform::run_return::EventLoopExtRunReturn;
     let windows = self.context.main_thread.windows.clone();
     let window_id_map = self.context.window_id_map.clone();
     let web_context = &self.context.main_thread.web_context;
     let plugins = self.context.plugins.clone();
+    let window_event_throttle = self.context.window_event_throttle;
 
     #[cfg(feature = "tracing")]
     let active_tracing_spans = self.context.main_thread.active_tracing_spans.clone();
 
     let proxy = self.event_loop.create_proxy();
 
+    let deadline = timeout.map(|timeout| std::time::Instant::now() + timeout);
+    let mut received_event = false;
+
     self
       .event_loop
       .run_return(|event, event_loop, control_flow| {
-        *control_flow = ControlFlow::Wait;
-        if let Event::MainEventsCleared = &event {
-          *control_flow = ControlFlow::Exit;
+        match &event {
+          Event::NewEvents(_)
+          | Event::MainEventsCleared
+          | Event::RedrawEventsCleared
+          | Event::LoopDestroyed => {}
+          _ => received_event = true,
         }
 
+        // once the events are cleared, exit if any was received or keep waiting until the deadline
+        let is_main_events_cleared = matches!(event, Event::MainEventsCleared);
+        let iteration_control_flow = match deadline {
+          _ if received_event => ControlFlow::Exit,
+          Some(deadline) if deadline <= std::time::Instant::now() => ControlFlow::Exit,
+          Some(deadline) => ControlFlow::WaitUntil(deadline),
+          None => ControlFlow::Wait,
+        };
+        *control_flow = if is_main_events_cleared {
+          iteration_control_flow
+        } else {
+          ControlFlow::Wait
+        };
+
         for p in plugins.lock().unwrap().iter_mut() {
           let prevent_default = p.on_event(
             &event,
@@ -2595,6 +2757,7 @@ impl<T: UserEvent> Runtime<T> for Wry<T> {
               callback: &mut callback,
               window_id_map: window_id_map.clone(),
               windows: windows.clone(),
+              window_event_throttle,
               #[cfg(feature = "tracing")]
               active_tracing_spans: active_tracing_spans.clone(),
             },
@@ -2613,10 +2776,16 @@ impl<T: UserEvent> Runtime<T> for Wry<T> {
             callback: &mut callback,
             windows: windows.clone(),
             window_id_map: window_id_map.clone(),
+            window_event_throttle,
             #[cfg(feature = "tracing")]
             active_tracing_spans: active_tracing_spans.clone(),
           },
         );
+
+        // the event handler resets the control flow unless it exits
+        if is_main_events_cleared && *control_flow != ControlFlow::Exit {
+          *control_flow = iteration_control_flow;
+        }
       });
   }
 
@@ -2625,6 +2794,7 @@ impl<T: UserEvent> Runtime<T> for Wry<T> {
     let window_id_map = self.context.window_id_map.clone();
     let web_context = self.context.main_thread.web_context;
     let plugins = self.context.plugins.clone();
+    let window_event_throttle = self.context.window_event_throttle;
 
     #[cfg(feature = "tracing")]
     let active_tracing_spans = self.context.main_thread.active_tracing_spans.clone();
@@ -2641,6 +2811,7 @@ impl<T: UserEvent> Runtime<T> for Wry<T> {
             callback: &mut callback,
             window_id_map: window_id_map.clone(),
             windows: windows.clone(),
+            window_event_throttle,
             #[cfg(feature = "tracing")]
             active_tracing_spans: active_tracing_spans.clone(),
           },
@@ -2658,6 +2829,7 @@ impl<T: UserEvent> Runtime<T> for Wry<T> {
           callback: &mut callback,
           window_id_map: window_id_map.clone(),
           windows: windows.clone(),
+          window_event_throttle,
           #[cfg(feature = "tracing")]
           active_tracing_spans: active_tracing_spans.clone(),
         },
@@ -2670,6 +2842,7 @@ pub struct EventLoopIterationContext<'a, T: UserEvent> {
   pub callback: &'a mut (dyn FnMut(RunEvent<T>) + 'static),
   pub window_id_map: WindowIdStore,
   pub windows: Arc<WindowsStore>,
+  pub window_event_throttle: Option<Duration>,
   #[cfg(feature = "tracing")]
   pub active_tracing_spans: ActiveTraceSpanStore,
 }
@@ -2787,6 +2960,25 @@ fn handle_user_message<T: UserEvent>(
                 .map(|h| SendRawWindowHandle(h.as_raw())),
             )
             .unwrap(),
+          WindowMessage::CreateChildSurface(surface_id, tx) => {
+            let result = child_surface::ChildSurface::new(surface_id, &window).map(|surface| {
+              if let Some(w) = windows.0.borrow_mut().get_mut(&id) {
+                w.child_surfaces.push(surface);
+              }
+            });
+            tx.send(result).unwrap();
+          }
+          WindowMessage::ChildSurfaceHandle(surface_id, tx) => {
+            let handle = windows
+              .0
+              .borrow()
+              .get(&id)
+              .and_then(|w| w.child_surfaces.iter().find(|s| s.id == surface_id))
+              .ok_or(raw_window_handle::HandleError::Unavailable)
+              .and_then(|s| s.raw_window_handle())
+              .map(SendRawWindowHandle);
+            tx.send(handle).unwrap();
+          }
           WindowMessage::Theme(tx) => {
             tx.send(map_theme(&window.theme())).unwrap();
           }
@@ -2868,6 +3060,10 @@ fn handle_user_message<T: UserEvent>(
             #[cfg(target_os = "macos")]
             window.set_has_shadow(_enable);
           }
+          WindowMessage::SetCornerPreference(preference) => {
+            frame::set_corner_preference(&window, preference)
+          }
+          WindowMessage::SetBorderColor(color) => frame::set_border_color(&window, color),
           WindowMessage::SetAlwaysOnBottom(always_on_bottom) => {
             window.set_always_on_bottom(always_on_bottom)
           }
@@ -2903,6 +3099,9 @@ fn handle_user_message<T: UserEvent>(
               window.set_fullscreen(None)
             }
           }
+          WindowMessage::SetFullscreenMode(mode, monitor) => {
+            window.set_fullscreen(Some(fullscreen_with_mode(&window, mode, monitor)))
+          }
           WindowMessage::SetFocus => {
             window.set_focus();
           }
@@ -2926,6 +3125,19 @@ fn handle_user_message<T: UserEvent>(
           WindowMessage::SetCursorPosition(position) => {
             let _ = window.set_cursor_position(PositionWrapper::from(position).0);
           }
+          WindowMessage::SetImePosition(rect) => {
+            // the candidate window is placed at the bottom-left corner of the area
+            let scale_factor = window.scale_factor();
+            let position = rect.position.to_logical::<f64>(scale_factor);
+            let size = rect.size.to_logical::<f64>(scale_factor);
+            window.set_ime_position(
+              PositionWrapper::from(Position::Logical(LogicalPosition::new(
+                position.x,
+                position.y + size.height,
+              )))
+              .0,
+            );
+          }
           WindowMessage::SetIgnoreCursorEvents(ignore) => {
             let _ = window.set_ignore_cursor_events(ignore);
           }
@@ -2947,6 +3159,11 @@ fn handle_user_message<T: UserEvent>(
           WindowMessage::RequestRedraw => {
             window.request_redraw();
           }
+          WindowMessage::DestroyChildSurface(surface_id) => {
+            if let Some(w) = windows.0.borrow_mut().get_mut(&id) {
+              w.child_surfaces.retain(|s| s.id != surface_id);
+            }
+          }
           WindowMessage::SetProgressBar(progress_state) => {
             window.set_progress_bar(ProgressBarStateWrapper::from(progress_state).0);
           }
@@ -2974,6 +3191,13 @@ fn handle_user_message<T: UserEvent>(
               }
             };
           }
+          WindowMessage::SetTheme(theme) => {
+            window.set_theme(match theme {
+              Some(Theme::Dark) => Some(TaoTheme::Dark),
+              Some(Theme::Light) => Some(TaoTheme::Light),
+              _ => None,
+            });
+          }
         }
       }
     }
@@ -3084,6 +3308,9 @@ fn handle_user_message<T: UserEvent>(
           WebviewMessage::Print => {
             let _ = webview.print();
           }
+          WebviewMessage::CaptureFrame(callback) => {
+            capture::capture_frame(&webview, callback);
+          }
           WebviewMessage::Close => {
             windows.0.borrow_mut().get_mut(&window_id).map(|window| {
               if let Some(i) = window.webviews.iter().position(|w| w.id == webview.id) {
@@ -3345,6 +3572,10 @@ fn handle_user_message<T: UserEvent>(
             inner: Some(window.clone()),
             window_event_listeners: Default::default(),
             webviews: Vec::new(),
+            child_surfaces: Vec::new(),
+            resize_throttle: Default::default(),
+            move_throttle: Default::default(),
+            fullscreen: window.fullscreen().is_some(),
             #[cfg(windows)]
             is_window_transparent,
             #[cfg(windows)]
@@ -3371,6 +3602,7 @@ fn handle_event_loop<T: UserEvent>(
     callback,
     window_id_map,
     windows,
+    window_event_throttle,
     #[cfg(feature = "tracing")]
     active_tracing_spans,
   } = context;
@@ -3378,6 +3610,8 @@ fn handle_event_loop<T: UserEvent>(
     *control_flow = ControlFlow::Wait;
   }
 
+  let throttled_windows = windows.clone();
+
   match event {
     Event::NewEvents(StartCause::Init) => {
       callback(RunEvent::Ready);
@@ -3391,6 +3625,14 @@ fn handle_event_loop<T: UserEvent>(
       callback(RunEvent::MainEventsCleared);
     }
 
+    Event::Suspended => {
+      callback(RunEvent::Lifecycle(LifecycleEvent::Suspended));
+    }
+
+    Event::Resumed => {
+      callback(RunEvent::Lifecycle(LifecycleEvent::Resumed));
+    }
+
     Event::LoopDestroyed => {
       callback(RunEvent::Exit);
     }
@@ -3475,22 +3717,50 @@ fn handle_event_loop<T: UserEvent>(
     } => {
       if let Some(window_id) = window_id_map.get(&window_id) {
         {
-          let windows_ref = windows.0.borrow();
-          if let Some(window) = windows_ref.get(&window_id) {
+          let mut windows_ref = windows.0.borrow_mut();
+          if let Some(window) = windows_ref.get_mut(&window_id) {
+            let mut events = Vec::new();
             if let Some(event) = WindowEventWrapper::parse(window, &event).0 {
+              // the throttled events are dispatched later by `flush_window_events`
+              let dispatch = match &event {
+                WindowEvent::Resized(size) => {
+                  window
+                    .resize_throttle
+                    .receive(*size, window_event_throttle, Instant::now())
+                }
+                WindowEvent::Moved(position) => {
+                  window
+                    .move_throttle
+                    .receive(*position, window_event_throttle, Instant::now())
+                }
+                _ => true,
+              };
+
+              if dispatch {
+                events.push(event);
+              }
+            }
+
+            // the system can change the fullscreen state, e.g. with the green button on macOS
+            if matches!(event, TaoWindowEvent::Resized(_)) {
+              let fullscreen = window
+                .inner
+                .as_ref()
+                .is_some_and(|w| w.fullscreen().is_some());
+              if fullscreen != window.fullscreen {
+                window.fullscreen = fullscreen;
+                events.push(WindowEvent::FullscreenChanged(fullscreen));
+              }
+            }
+
+            if !events.is_empty() {
               let label = window.label.clone();
               let window_event_listeners = window.window_event_listeners.clone();
 
               drop(windows_ref);
 
-              callback(RunEvent::WindowEvent {
-                label,
-                event: event.clone(),
-              });
-              let listeners = window_event_listeners.lock().unwrap();
-              let handlers = listeners.values();
-              for handler in handlers {
-                handler(&event);
+              for event in events {
+                dispatch_window_event(callback, label.clone(), &window_event_listeners, event);
               }
             }
           }
@@ -3533,6 +3803,11 @@ fn handle_event_loop<T: UserEvent>(
             }
           }
           TaoWindowEvent::Resized(size) => {
+            if let Some(w) = windows.0.borrow().get(&window_id) {
+              for surface in &w.child_surfaces {
+                surface.resize(size);
+              }
+            }
             if let Some((Some(window), webviews)) = windows
               .0
               .borrow()
@@ -3604,6 +3879,127 @@ fn handle_event_loop<T: UserEvent>(
     }),
     _ => (),
   }
+
+  // wake up the event loop when the next coalesced resize or move event is due
+  let deadline = flush_window_events(callback, &throttled_windows, window_event_throttle);
+  if let (Some(deadline), ControlFlow::Wait) = (deadline, *control_flow) {
+    *control_flow = ControlFlow::WaitUntil(deadline);
+  }
+}
+
+/// The fullscreen state of the window for the mode, on the monitor or the current monitor.
+fn fullscreen_with_mode(
+  window: &Window,
+  mode: tauri_runtime::window::FullscreenMode,
+  monitor: Option<Monitor>,
+) -> Fullscreen {
+  let monitor = monitor.and_then(|monitor| {
+    let handle = window.available_monitors().find(|m| {
+      let position = m.position();
+      position.x == monitor.position.x && position.y == monitor.position.y
+    });
+    if handle.is_none() {
+      log::warn!(
+        "monitor {:?} not found, using the current monitor",
+        monitor.name
+      );
+    }
+    handle
+  });
+
+  match mode {
+    tauri_runtime::window::FullscreenMode::Borderless => Fullscreen::Borderless(monitor),
+    tauri_runtime::window::FullscreenMode::Exclusive => {
+      #[cfg(any(windows, target_os = "macos"))]
+      {
+        let video_mode = monitor
+          .clone()
+          .or_else(|| window.current_monitor())
+          .and_then(|m| {
+            m.video_modes().max_by_key(|mode| {
+              let size = mode.size();
+              (
+                u64::from(size.width) * u64::from(size.height),
+                mode.refresh_rate(),
+                mode.bit_depth(),
+              )
+            })
+          });
+        if let Some(video_mode) = video_mode {
+          return Fullscreen::Exclusive(video_mode);
+        }
+      }
+      Fullscreen::Borderless(monitor)
+    }
+  }
+}
+
+fn dispatch_window_event<T: UserEvent>(
+  callback: &mut (dyn FnMut(RunEvent<T>) + 'static),
+  label: String,
+  window_event_listeners: &WindowEventListeners,
+  event: WindowEvent,
+) {
+  callback(RunEvent::WindowEvent {
+    label,
+    event: event.clone(),
+  });
+  let listeners = window_event_listeners.lock().unwrap();
+  let handlers = listeners.values();
+  for handler in handlers {
+    handler(&event);
+  }
+}
+
+/// Dispatches the resize and move events held back by the throttle and the ended events of the windows that settled.
+///
+/// Returns the next time an event is due.
+fn flush_window_events<T: UserEvent>(
+  callback: &mut (dyn FnMut(RunEvent<T>) + 'static),
+  windows: &WindowsStore,
+  window_event_throttle: Option<Duration>,
+) -> Option<Instant> {
+  let now = Instant::now();
+  let mut due = Vec::new();
+  let mut deadline = None;
+
+  for window in windows.0.borrow_mut().values_mut() {
+    let resize = window.resize_throttle.flush(window_event_throttle, now);
+    let moved = window.move_throttle.flush(window_event_throttle, now);
+    let events = [
+      resize.pending.map(WindowEvent::Resized),
+      moved.pending.map(WindowEvent::Moved),
+      resize.ended.map(WindowEvent::ResizeEnded),
+      moved.ended.map(WindowEvent::MoveEnded),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>();
+    if !events.is_empty() {
+      due.push((
+        window.label.clone(),
+        window.window_event_listeners.clone(),
+        events,
+      ));
+    }
+
+    deadline = [
+      deadline,
+      window.resize_throttle.deadline(window_event_throttle),
+      window.move_throttle.deadline(window_event_throttle),
+    ]
+    .into_iter()
+    .flatten()
+    .min();
+  }
+
+  for (label, window_event_listeners, events) in due {
+    for event in events {
+      dispatch_window_event(callback, label.clone(), &window_event_listeners, event);
+    }
+  }
+
+  deadline
 }
 
 fn on_close_requested<'a, T: UserEvent>(
@@ -3640,6 +4036,7 @@ fn on_close_requested<'a, T: UserEvent>(
 fn on_window_close(window_id: WindowId, windows: Arc<WindowsStore>) {
   if let Some(window_wrapper) = windows.0.borrow_mut().get_mut(&window_id) {
     window_wrapper.inner = None;
+    window_wrapper.child_surfaces.clear();
     #[cfg(windows)]
     window_wrapper.surface.take();
   }
@@ -3812,6 +4209,13 @@ fn create_window<T: UserEvent, F: Fn(RawWindow) + Send + 'static>(
     )?);
   }
 
+  if let Some(preference) = window_builder.corner_preference {
+    frame::set_corner_preference(&window, preference);
+  }
+  if let Some(color) = window_builder.border_color {
+    frame::set_border_color(&window, Some(color));
+  }
+
   let window = Arc::new(window);
 
   #[cfg(windows)]
@@ -3836,6 +4240,10 @@ fn create_window<T: UserEvent, F: Fn(RawWindow) + Send + 'static>(
     inner: Some(window),
     webviews,
     window_event_listeners,
+    child_surfaces: Vec::new(),
+    resize_throttle: Default::default(),
+    move_throttle: Default::default(),
+    fullscreen: window.fullscreen().is_some(),
     #[cfg(windows)]
     is_window_transparent,
     #[cfg(windows)]
@@ -4139,6 +4547,12 @@ fn create_webview<T: UserEvent>(
     webview_builder = webview_builder.with_devtools(true);
   }
 
+  // the WKWebView must be inspectable for the WebDriver tests of release builds
+  #[cfg(target_os = "macos")]
+  if automation_enabled {
+    webview_builder = webview_builder.with_devtools(true);
+  }
+
   #[cfg(target_os = "android")]
   {
     if let Some(on_webview_created) = pending.on_webview_created {
@@ -4157,6 +4571,18 @@ fn create_webview<T: UserEvent>(
     .build()
     .map_err(|e| Error::CreateWebview(Box::new(e)))?;
 
+  {
+    let proxy = context.proxy.clone();
+    let window_id = window_id.clone();
+    crash::watch(&webview, move |reason| {
+      let _ = proxy.send_event(Message::Webview(
+        *window_id.lock().unwrap(),
+        id,
+        WebviewMessage::WebviewEvent(WebviewEvent::ProcessCrashed { reason }),
+      ));
+    });
+  }
+
   if kind == WebviewKind::WindowContent {
     #[cfg(any(
       target_os = "linux",
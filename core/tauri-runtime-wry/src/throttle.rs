@@ -0,0 +1,157 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Coalesces the resize and move events fired continuously while a window is dragged.
+
+use std::time::{Duration, Instant};
+
+/// The delay without a new event after which a resize or move is considered finished.
+pub const SETTLE_DELAY: Duration = Duration::from_millis(100);
+
+/// The throttling state of a stream of events carrying a value, e.g. the window size.
+#[derive(Debug)]
+pub struct EventThrottle<V> {
+  /// When the last event was dispatched.
+  last_dispatched: Option<Instant>,
+  /// When the last event was received.
+  last_received: Option<Instant>,
+  /// The value of the last event held back by the throttle.
+  pending: Option<V>,
+  /// The value of the last event, dispatched with the ended event once the stream settles.
+  last: Option<V>,
+}
+
+impl<V> Default for EventThrottle<V> {
+  fn default() -> Self {
+    Self {
+      last_dispatched: None,
+      last_received: None,
+      pending: None,
+      last: None,
+    }
+  }
+}
+
+/// The events due after a call to [`EventThrottle::flush`].
+#[derive(Debug, PartialEq, Eq)]
+pub struct FlushedEvents<V> {
+  /// The value of the event held back until the throttle interval elapsed.
+  pub pending: Option<V>,
+  /// The final value once the stream settled.
+  pub ended: Option<V>,
+}
+
+impl<V: Copy> EventThrottle<V> {
+  /// Records an event, returning whether it must be dispatched now.
+  pub fn receive(&mut self, value: V, interval: Option<Duration>, now: Instant) -> bool {
+    self.last_received = Some(now);
+    self.last = Some(value);
+    let dispatch = match (interval, self.last_dispatched) {
+      (Some(interval), Some(last_dispatched)) => now.duration_since(last_dispatched) >= interval,
+      _ => true,
+    };
+    if dispatch {
+      self.last_dispatched = Some(now);
+      self.pending = None;
+    } else {
+      self.pending = Some(value);
+    }
+    dispatch
+  }
+
+  /// Takes the held back event once the interval elapsed and the ended event once the stream settled.
+  pub fn flush(&mut self, interval: Option<Duration>, now: Instant) -> FlushedEvents<V> {
+    let mut flushed = FlushedEvents {
+      pending: None,
+      ended: None,
+    };
+    if let Some(deadline) = self.pending_deadline(interval) {
+      if deadline <= now {
+        flushed.pending = self.pending.take();
+        self.last_dispatched = Some(now);
+      }
+    }
+    if let Some(deadline) = self.settle_deadline(interval) {
+      if deadline <= now {
+        flushed.pending = flushed.pending.or(self.pending.take());
+        flushed.ended = self.last.take();
+        self.last_received = None;
+      }
+    }
+    flushed
+  }
+
+  /// The next time [`Self::flush`] has an event to return.
+  pub fn deadline(&self, interval: Option<Duration>) -> Option<Instant> {
+    match (
+      self.pending_deadline(interval),
+      self.settle_deadline(interval),
+    ) {
+      (Some(a), Some(b)) => Some(a.min(b)),
+      (a, b) => a.or(b),
+    }
+  }
+
+  fn pending_deadline(&self, interval: Option<Duration>) -> Option<Instant> {
+    self.pending.as_ref()?;
+    Some(self.last_dispatched? + interval.unwrap_or_default())
+  }
+
+  fn settle_deadline(&self, interval: Option<Duration>) -> Option<Instant> {
+    Some(self.last_received? + interval.unwrap_or_default().max(SETTLE_DELAY))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::time::{Duration, Instant};
+
+  use super::{EventThrottle, FlushedEvents, SETTLE_DELAY};
+
+  #[test]
+  fn coalesces_events() {
+    let interval = Some(Duration::from_millis(50));
+    let start = Instant::now();
+    let at = |ms| start + Duration::from_millis(ms);
+    let mut throttle = EventThrottle::default();
+
+    assert!(throttle.receive(1, interval, at(0)));
+    assert!(!throttle.receive(2, interval, at(10)));
+    assert!(!throttle.receive(3, interval, at(20)));
+    assert_eq!(throttle.deadline(interval), Some(at(50)));
+    assert_eq!(
+      throttle.flush(interval, at(50)),
+      FlushedEvents {
+        pending: Some(3),
+        ended: None
+      }
+    );
+
+    assert!(throttle.receive(4, interval, at(100)));
+    assert_eq!(throttle.deadline(interval), Some(at(100) + SETTLE_DELAY));
+    assert_eq!(
+      throttle.flush(interval, at(100) + SETTLE_DELAY),
+      FlushedEvents {
+        pending: None,
+        ended: Some(4)
+      }
+    );
+    assert_eq!(throttle.deadline(interval), None);
+  }
+
+  #[test]
+  fn dispatches_every_event_without_interval() {
+    let start = Instant::now();
+    let mut throttle = EventThrottle::default();
+    assert!(throttle.receive(1, None, start));
+    assert!(throttle.receive(2, None, start));
+    assert_eq!(
+      throttle.flush(None, start + SETTLE_DELAY),
+      FlushedEvents {
+        pending: None,
+        ended: Some(2)
+      }
+    );
+  }
+}
@@ -0,0 +1,162 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! The futures of the getters running on the main thread, see [`crate::WindowDispatch::get_async`].
+
+use std::{
+  future::Future,
+  pin::Pin,
+  sync::{Arc, Mutex},
+  task::{Context, Poll, Waker},
+};
+
+use crate::{Error, Result};
+
+struct Shared<T> {
+  value: Option<Result<T>>,
+  waker: Option<Waker>,
+  closed: bool,
+}
+
+/// Creates the sender of a value and the future that resolves to it.
+pub fn channel<T>() -> (GetterSender<T>, GetterFuture<T>) {
+  let shared = Arc::new(Mutex::new(Shared {
+    value: None,
+    waker: None,
+    closed: false,
+  }));
+  (
+    GetterSender {
+      shared: shared.clone(),
+    },
+    GetterFuture { shared },
+  )
+}
+
+/// Sends the value of a [`GetterFuture`].
+///
+/// The future resolves to [`Error::FailedToReceiveMessage`] if the sender is dropped without sending a value.
+pub struct GetterSender<T> {
+  shared: Arc<Mutex<Shared<T>>>,
+}
+
+impl<T> GetterSender<T> {
+  /// Resolves the future.
+  pub fn send(self, value: Result<T>) {
+    self.shared.lock().unwrap().value.replace(value);
+  }
+}
+
+impl<T> Drop for GetterSender<T> {
+  fn drop(&mut self) {
+    let mut shared = self.shared.lock().unwrap();
+    shared.closed = true;
+    if let Some(waker) = shared.waker.take() {
+      waker.wake();
+    }
+  }
+}
+
+/// A future resolving to the value of a getter that runs on the main thread.
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct GetterFuture<T> {
+  shared: Arc<Mutex<Shared<T>>>,
+}
+
+impl<T> GetterFuture<T> {
+  /// A future that is already resolved.
+  pub fn ready(value: Result<T>) -> Self {
+    let (tx, future) = channel();
+    tx.send(value);
+    future
+  }
+}
+
+impl<T> std::fmt::Debug for GetterFuture<T> {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("GetterFuture").finish_non_exhaustive()
+  }
+}
+
+impl<T> Future for GetterFuture<T> {
+  type Output = Result<T>;
+
+  fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+    let mut shared = self.shared.lock().unwrap();
+    if let Some(value) = shared.value.take() {
+      Poll::Ready(value)
+    } else if shared.closed {
+      Poll::Ready(Err(Error::FailedToReceiveMessage))
+    } else {
+      shared.waker.replace(cx.waker().clone());
+      Poll::Pending
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::{
+    future::Future,
+    pin::pin,
+    sync::{
+      atomic::{AtomicBool, Ordering},
+      Arc,
+    },
+    task::{Context, Poll, Wake, Waker},
+  };
+
+  use super::{channel, GetterFuture};
+  use crate::Error;
+
+  #[derive(Default)]
+  struct Flag(AtomicBool);
+
+  impl Wake for Flag {
+    fn wake(self: Arc<Self>) {
+      self.0.store(true, Ordering::SeqCst);
+    }
+  }
+
+  fn poll<T>(future: &mut GetterFuture<T>, waker: &Waker) -> Poll<crate::Result<T>> {
+    pin!(future).poll(&mut Context::from_waker(waker))
+  }
+
+  #[test]
+  fn resolves_to_sent_value() {
+    let flag = Arc::new(Flag::default());
+    let waker = Waker::from(flag.clone());
+    let (tx, mut future) = channel();
+    assert!(poll(&mut future, &waker).is_pending());
+
+    tx.send(Ok(42));
+    assert!(flag.0.load(Ordering::SeqCst));
+    assert!(matches!(poll(&mut future, &waker), Poll::Ready(Ok(42))));
+  }
+
+  #[test]
+  fn fails_when_sender_dropped() {
+    let flag = Arc::new(Flag::default());
+    let waker = Waker::from(flag.clone());
+    let (tx, mut future) = channel::<u32>();
+    assert!(poll(&mut future, &waker).is_pending());
+
+    drop(tx);
+    assert!(flag.0.load(Ordering::SeqCst));
+    assert!(matches!(
+      poll(&mut future, &waker),
+      Poll::Ready(Err(Error::FailedToReceiveMessage))
+    ));
+  }
+
+  #[test]
+  fn ready() {
+    let waker = Waker::from(Arc::new(Flag::default()));
+    let mut future = GetterFuture::ready(Err::<(), _>(Error::FailedToSendMessage));
+    assert!(matches!(
+      poll(&mut future, &waker),
+      Poll::Ready(Err(Error::FailedToSendMessage))
+    ));
+  }
+}
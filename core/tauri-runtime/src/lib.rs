@@ -8,6 +8,16 @@
 //!
 //! None of the exposed API of this crate is stable, and it may break semver
 //! compatibility in the future. The major version only signifies the intended Tauri version.
+//!
+//! ## Implementing a runtime
+//!
+//! A runtime implements [`Runtime`] for its event loop, [`RuntimeHandle`] for the thread-safe handle to it,
+//! and [`WindowDispatch`] and [`WebviewDispatch`] for its windows and webviews, and is used with `tauri::Builder::<MyRuntime>::new()`.
+//! The `MockRuntime` of the `tauri::test` module is a minimal runtime without a webview
+//! that can be used as the skeleton of a new one.
+//!
+//! To drive Tauri from an event loop owned by the host application instead, such as a game using winit,
+//! call [`Runtime::pump_events`] from that loop; see the `pump-events` example.
 
 #![doc(
   html_logo_url = "https://github.com/tauri-apps/tauri/raw/dev/app-icon.png",
@@ -23,6 +33,7 @@ use url::Url;
 use webview::{DetachedWebview, PendingWebview};
 
 /// Types useful for interacting with a user's monitors.
+pub mod future;
 pub mod monitor;
 pub mod webview;
 pub mod window;
@@ -46,6 +57,8 @@ pub use dpi;
 
 pub type WindowEventId = u32;
 pub type WebviewEventId = u32;
+/// Identifier of a [child surface](WindowDispatch::create_child_surface).
+pub type SurfaceId = u32;
 
 /// A rectangular region.
 #[derive(Clone, Copy, Debug, Serialize)]
@@ -183,6 +196,12 @@ pub enum Error {
   InvalidProxyUrl,
   #[error("window not found")]
   WindowNotFound,
+  /// Failed to capture the webview content.
+  #[error("failed to capture the webview content: {0}")]
+  CaptureFrame(String),
+  /// Failed to create a child surface.
+  #[error("failed to create the child surface: {0}")]
+  CreateChildSurface(String),
 }
 
 /// Result type.
@@ -247,10 +266,35 @@ pub enum RunEvent<T: UserEvent> {
     /// Indicates whether the NSApplication object found any visible windows in your application.
     has_visible_windows: bool,
   },
+  /// The application lifecycle changed.
+  Lifecycle(LifecycleEvent),
   /// A custom event defined by the user.
   UserEvent(T),
 }
 
+/// A change in the application lifecycle, triggered by the operating system.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum LifecycleEvent {
+  /// The application was moved to the background on mobile, or the system is going to sleep on desktop.
+  Suspended,
+  /// The application was moved back to the foreground on mobile, or the system woke up on desktop.
+  Resumed,
+  /// The system is running low on memory and the application should release the resources it can.
+  MemoryWarning,
+  /// The application is in the foreground and receives the user input.
+  Foreground,
+  /// The application is visible but does not receive the user input,
+  /// e.g. while a system dialog is displayed on mobile or when no window is focused on desktop.
+  Inactive,
+  /// The application is in the background and no longer visible. Only triggered on mobile.
+  Background,
+  /// The application is about to be terminated by the operating system. Only triggered on mobile.
+  ///
+  /// The application might be killed without this event, so the state should be saved on [`Self::Background`].
+  Terminate,
+}
+
 /// Action to take when the event loop is about to exit
 #[derive(Debug)]
 pub enum ExitRequestedEventAction {
@@ -355,6 +399,10 @@ pub struct RuntimeInitArgs {
   pub app_id: Option<String>,
   #[cfg(windows)]
   pub msg_hook: Option<Box<dyn FnMut(*const std::ffi::c_void) -> bool + 'static>>,
+  /// The minimum interval between two [`window::WindowEvent::Resized`] or [`window::WindowEvent::Moved`] events of a window.
+  ///
+  /// The events received in between are coalesced, the last one being dispatched once the interval elapses.
+  pub window_event_throttle: Option<std::time::Duration>,
 }
 
 /// The webview runtime interface.
@@ -434,6 +482,24 @@ pub trait Runtime<T: UserEvent>: Debug + Sized + 'static {
   #[cfg(desktop)]
   fn run_iteration<F: FnMut(RunEvent<T>) + 'static>(&mut self, callback: F);
 
+  /// Processes the pending events of the runtime event loop, waiting up to the timeout for one if there are none,
+  /// and returns control flow to the caller.
+  ///
+  /// This drives the runtime from an event loop owned by the host application, such as the loop of a game engine.
+  /// `Some(Duration::ZERO)` only processes the pending events and `None` waits until an event is received,
+  /// which includes the tasks sent with [`RuntimeHandle::run_on_main_thread`] to wake up the loop from another thread.
+  ///
+  /// The default implementation ignores the timeout and calls [`Self::run_iteration`].
+  #[cfg(desktop)]
+  fn pump_events<F: FnMut(RunEvent<T>) + 'static>(
+    &mut self,
+    timeout: Option<std::time::Duration>,
+    callback: F,
+  ) {
+    let _ = timeout;
+    self.run_iteration(callback)
+  }
+
   /// Run the webview runtime.
   fn run<F: FnMut(RunEvent<T>) + 'static>(self, callback: F);
 }
@@ -446,6 +512,23 @@ pub trait WebviewDispatch<T: UserEvent>: Debug + Clone + Send + Sync + Sized + '
   /// Run a task on the main thread.
   fn run_on_main_thread<F: FnOnce() + Send + 'static>(&self, f: F) -> Result<()>;
 
+  /// Runs the getter with the dispatcher on the main thread and resolves to its result.
+  ///
+  /// The getters of the dispatcher block the calling thread until the event loop answers,
+  /// which deadlocks when the event loop waits for that thread, e.g. in a synchronous command.
+  /// The future never blocks: the getter runs on the main thread, where it returns right away.
+  fn get_async<V: Send + 'static, F: FnOnce(&Self) -> Result<V> + Send + 'static>(
+    &self,
+    getter: F,
+  ) -> future::GetterFuture<V> {
+    let (tx, future) = future::channel();
+    let dispatcher = self.clone();
+    match self.run_on_main_thread(move || tx.send(getter(&dispatcher))) {
+      Ok(()) => future,
+      Err(e) => future::GetterFuture::ready(Err(e)),
+    }
+  }
+
   /// Registers a webview event handler.
   fn on_webview_event<F: Fn(&WebviewEvent) + Send + 'static>(&self, f: F) -> WebviewEventId;
 
@@ -486,6 +569,16 @@ pub trait WebviewDispatch<T: UserEvent>: Debug + Clone + Send + Sync + Sized + '
   /// Opens the dialog to prints the contents of the webview.
   fn print(&self) -> Result<()>;
 
+  /// Captures the composited content of the webview, calling the callback with the frame on the main thread.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **iOS / Android:** Unsupported, the callback receives [`Error::CaptureFrame`].
+  fn capture_frame<F: FnOnce(Result<webview::WebviewFrame>) + Send + 'static>(
+    &self,
+    callback: F,
+  ) -> Result<()>;
+
   /// Closes the webview.
   fn close(&self) -> Result<()>;
 
@@ -525,6 +618,23 @@ pub trait WindowDispatch<T: UserEvent>: Debug + Clone + Send + Sync + Sized + 's
   /// Run a task on the main thread.
   fn run_on_main_thread<F: FnOnce() + Send + 'static>(&self, f: F) -> Result<()>;
 
+  /// Runs the getter with the dispatcher on the main thread and resolves to its result.
+  ///
+  /// The getters of the dispatcher block the calling thread until the event loop answers,
+  /// which deadlocks when the event loop waits for that thread, e.g. in a synchronous command.
+  /// The future never blocks: the getter runs on the main thread, where it returns right away.
+  fn get_async<V: Send + 'static, F: FnOnce(&Self) -> Result<V> + Send + 'static>(
+    &self,
+    getter: F,
+  ) -> future::GetterFuture<V> {
+    let (tx, future) = future::channel();
+    let dispatcher = self.clone();
+    match self.run_on_main_thread(move || tx.send(getter(&dispatcher))) {
+      Ok(()) => future,
+      Err(e) => future::GetterFuture::ready(Err(e)),
+    }
+  }
+
   /// Registers a window event handler.
   fn on_window_event<F: Fn(&WindowEvent) + Send + 'static>(&self, f: F) -> WindowEventId;
 
@@ -634,6 +744,25 @@ pub trait WindowDispatch<T: UserEvent>: Debug + Clone + Send + Sync + Sized + 's
     &self,
   ) -> std::result::Result<raw_window_handle::WindowHandle<'_>, raw_window_handle::HandleError>;
 
+  /// Creates a native surface filling the window content beneath its webviews,
+  /// for renderers such as wgpu or OpenGL to draw behind a transparent webview.
+  ///
+  /// The surface follows the window size, get its handle with [`Self::child_surface_handle`].
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Linux / iOS / Android:** Unsupported, returns [`Error::CreateChildSurface`].
+  fn create_child_surface(&self) -> Result<SurfaceId>;
+
+  /// Raw window handle of a surface created with [`Self::create_child_surface`].
+  fn child_surface_handle(
+    &self,
+    id: SurfaceId,
+  ) -> std::result::Result<raw_window_handle::WindowHandle<'_>, raw_window_handle::HandleError>;
+
+  /// Destroys a surface created with [`Self::create_child_surface`].
+  fn destroy_child_surface(&self, id: SurfaceId) -> Result<()>;
+
   /// Returns the current window theme.
   fn theme(&self) -> Result<Theme>;
 
@@ -720,6 +849,12 @@ pub trait WindowDispatch<T: UserEvent>: Debug + Clone + Send + Sync + Sized + 's
   /// Updates the shadow flag.
   fn set_shadow(&self, enable: bool) -> Result<()>;
 
+  /// Updates how the corners of the window are rounded.
+  fn set_corner_preference(&self, preference: tauri_utils::CornerPreference) -> Result<()>;
+
+  /// Updates the color of the window border, [`None`] restoring the system color.
+  fn set_border_color(&self, color: Option<tauri_utils::config::Color>) -> Result<()>;
+
   /// Updates the window alwaysOnBottom flag.
   fn set_always_on_bottom(&self, always_on_bottom: bool) -> Result<()>;
 
@@ -750,6 +885,13 @@ pub trait WindowDispatch<T: UserEvent>: Debug + Clone + Send + Sync + Sized + 's
   /// Updates the window fullscreen state.
   fn set_fullscreen(&self, fullscreen: bool) -> Result<()>;
 
+  /// Makes the window fullscreen with the mode, on the monitor or on the current monitor if [`None`].
+  fn set_fullscreen_mode(
+    &self,
+    mode: window::FullscreenMode,
+    monitor: Option<monitor::Monitor>,
+  ) -> Result<()>;
+
   /// Bring the window to front and focus.
   fn set_focus(&self) -> Result<()>;
 
@@ -779,6 +921,13 @@ pub trait WindowDispatch<T: UserEvent>: Debug + Clone + Send + Sync + Sized + 's
   /// Ignores the window cursor events.
   fn set_ignore_cursor_events(&self, ignore: bool) -> Result<()>;
 
+  /// Places the candidate window of the input method editor below the given area, usually the text cursor, in window coordinates.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **iOS / Android:** Unsupported.
+  fn set_ime_position(&self, rect: Rect) -> Result<()>;
+
   /// Starts dragging the window.
   fn start_dragging(&self) -> Result<()>;
 
@@ -799,4 +948,12 @@ pub trait WindowDispatch<T: UserEvent>: Debug + Clone + Send + Sync + Sized + 's
   ///
   /// - **Linux / Windows / iOS / Android:** Unsupported.
   fn set_title_bar_style(&self, style: tauri_utils::TitleBarStyle) -> Result<()>;
+
+  /// Sets the theme for this window, [`None`] to follow the system theme.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Linux / macOS**: Theme is app-wide and not specific to this window.
+  /// - **iOS / Android:** Unsupported.
+  fn set_theme(&self, theme: Option<Theme>) -> Result<()>;
 }
@@ -54,6 +54,17 @@ pub enum DownloadEvent<'a> {
   },
 }
 
+/// A frame of the composited content of a webview, captured with [`crate::WebviewDispatch::capture_frame`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebviewFrame {
+  /// The frame width in physical pixels.
+  pub width: u32,
+  /// The frame height in physical pixels.
+  pub height: u32,
+  /// The pixels, row by row, as 8-bit RGBA with straight alpha.
+  pub rgba: Vec<u8>,
+}
+
 #[cfg(target_os = "android")]
 pub struct CreationContext<'a, 'b> {
   pub env: &'a mut jni::JNIEnv<'b>,
@@ -190,6 +201,21 @@ impl<T: UserEvent, R: Runtime<T>> PartialEq for DetachedWebview<T, R> {
   }
 }
 
+/// What happens when the webview process crashes, see [`crate::window::WebviewEvent::ProcessCrashed`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CrashRecovery {
+  /// The webview is left blank, the app handles the crash.
+  #[default]
+  None,
+  /// The page is reloaded, at most `max_attempts` times during the lifetime of the webview
+  /// so a page crashing on load does not reload forever.
+  Reload {
+    /// The maximum number of reloads.
+    max_attempts: u32,
+  },
+}
+
 /// The attributes used to create an webview.
 #[derive(Debug, Clone)]
 pub struct WebviewAttributes {
@@ -208,6 +234,8 @@ pub struct WebviewAttributes {
   pub auto_resize: bool,
   pub proxy_url: Option<Url>,
   pub zoom_hotkeys_enabled: bool,
+  pub edge_to_edge: bool,
+  pub crash_recovery: CrashRecovery,
 }
 
 impl From<&WindowConfig> for WebviewAttributes {
@@ -235,6 +263,7 @@ impl From<&WindowConfig> for WebviewAttributes {
       builder = builder.proxy_url(url.to_owned());
     }
     builder = builder.zoom_hotkeys_enabled(config.zoom_hotkeys_enabled);
+    builder = builder.edge_to_edge(config.edge_to_edge);
     builder
   }
 }
@@ -258,6 +287,8 @@ impl WebviewAttributes {
       auto_resize: false,
       proxy_url: None,
       zoom_hotkeys_enabled: false,
+      edge_to_edge: false,
+      crash_recovery: CrashRecovery::None,
     }
   }
 
@@ -363,6 +394,20 @@ impl WebviewAttributes {
     self.zoom_hotkeys_enabled = enabled;
     self
   }
+
+  /// Whether the content is drawn under the system bars and display cutouts, with the body padded by the safe area insets.
+  #[must_use]
+  pub fn edge_to_edge(mut self, enabled: bool) -> Self {
+    self.edge_to_edge = enabled;
+    self
+  }
+
+  /// Sets what happens when the webview process crashes.
+  #[must_use]
+  pub fn crash_recovery(mut self, recovery: CrashRecovery) -> Self {
+    self.crash_recovery = recovery;
+    self
+  }
 }
 
 /// IPC handler.
@@ -29,6 +29,10 @@ pub enum WindowEvent {
   Resized(dpi::PhysicalSize<u32>),
   /// The position of the window has changed. Contains the window's new position.
   Moved(dpi::PhysicalPosition<i32>),
+  /// The window stopped being resized. Contains the client area's final dimensions.
+  ResizeEnded(dpi::PhysicalSize<u32>),
+  /// The window stopped being moved. Contains the window's final position.
+  MoveEnded(dpi::PhysicalPosition<i32>),
   /// The window has been requested to close.
   CloseRequested {
     /// A signal sender. If a `true` value is emitted, the window won't be closed.
@@ -59,6 +63,42 @@ pub enum WindowEvent {
   ///
   /// Applications might wish to react to this to change the theme of the content of the window when the system changes the window theme.
   ThemeChanged(Theme),
+  /// An event from the input method editor, see [`ImeEvent`].
+  Ime(ImeEvent),
+  /// The window entered or left fullscreen, e.g. with the green button on macOS.
+  ///
+  /// The parameter is true if the window is now fullscreen.
+  FullscreenChanged(bool),
+}
+
+/// How a window covers a monitor in fullscreen.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum FullscreenMode {
+  /// The window covers the monitor without changing its video mode.
+  #[default]
+  Borderless,
+  /// The monitor switches to its video mode with the highest resolution and refresh rate,
+  /// falling back to [`Self::Borderless`] where unsupported.
+  Exclusive,
+}
+
+/// The text composition state of the input method editor (IME) of a window.
+///
+/// The events are only emitted when the window itself receives the keyboard input,
+/// the webviews handle the composition of their focused elements.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ImeEvent {
+  /// The text being composed changed, an empty text ending the composition.
+  Preedit {
+    /// The text being composed.
+    text: String,
+    /// The byte range of the cursor in the text, `None` hiding the cursor.
+    cursor: Option<(usize, usize)>,
+  },
+  /// The composed text was committed.
+  Commit(String),
 }
 
 /// An event from a window.
@@ -66,6 +106,33 @@ pub enum WindowEvent {
 pub enum WebviewEvent {
   /// An event associated with the drag and drop action.
   DragDrop(DragDropEvent),
+  /// The process rendering the webview content terminated, leaving the webview blank until it is reloaded.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **macOS / Android / iOS:** Unsupported.
+  ProcessCrashed {
+    /// Why the process terminated.
+    reason: ProcessCrashReason,
+  },
+}
+
+/// Why a webview process terminated, see [`WebviewEvent::ProcessCrashed`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ProcessCrashReason {
+  /// The process crashed.
+  Crashed,
+  /// The process was terminated because it exceeded its memory limit or the system ran out of memory.
+  OutOfMemory,
+  /// The process was terminated by the system or another process.
+  Terminated,
+  /// The process stopped responding.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Linux:** Unsupported.
+  Unresponsive,
 }
 
 /// The drag drop event payload.
@@ -366,6 +433,24 @@ pub trait WindowBuilder: WindowBuilderBase {
   #[must_use]
   fn shadow(self, enable: bool) -> Self;
 
+  /// Sets how the corners of the window are rounded.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Windows:** Requires Windows 11.
+  /// - **Linux:** Unsupported.
+  #[must_use]
+  fn corner_preference(self, preference: tauri_utils::CornerPreference) -> Self;
+
+  /// Sets the color of the window border.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Windows:** Requires Windows 11.
+  /// - **Linux:** Unsupported.
+  #[must_use]
+  fn border_color(self, color: tauri_utils::config::Color) -> Self;
+
   /// Set an owner to the window to be created.
   ///
   /// From MSDN:
@@ -453,9 +538,7 @@ pub struct PendingWindow<T: UserEvent, R: Runtime<T>> {
 }
 
 pub fn is_label_valid(label: &str) -> bool {
-  label
-    .chars()
-    .all(|c| char::is_alphanumeric(c) || c == '-' || c == '/' || c == ':' || c == '_')
+  label.chars().all(tauri_utils::label::is_valid_char)
 }
 
 pub fn assert_label_is_valid(label: &str) {
@@ -193,6 +193,21 @@ pub struct Capability {
   /// `["macOS","windows"]`
   #[serde(skip_serializing_if = "Option::is_none")]
   pub platforms: Option<Vec<Target>>,
+  /// The operating system permissions required by the commands of this capability on Android and iOS.
+  ///
+  /// The CLI declares them in the `AndroidManifest.xml` file and checks the usage descriptions of the iOS `Info.plist` file.
+  /// The permissions are requested at runtime with `tauri::mobile::permissions::PermissionsExt::request_permissions`.
+  ///
+  /// ## Example
+  ///
+  /// `["camera", "notifications"]`
+  #[serde(
+    default,
+    rename = "osPermissions",
+    alias = "os-permissions",
+    skip_serializing_if = "Vec::is_empty"
+  )]
+  pub os_permissions: Vec<OsPermission>,
 }
 
 #[cfg(feature = "schema")]
@@ -214,6 +229,86 @@ fn default_capability_local() -> bool {
   true
 }
 
+/// An operating system permission the user grants to the app at runtime on Android and iOS.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum OsPermission {
+  /// Access to the camera.
+  Camera,
+  /// Access to the microphone.
+  Microphone,
+  /// Displaying notifications.
+  Notifications,
+  /// Access to the precise location while the app is in use.
+  Location,
+  /// Access to the photo library.
+  Photos,
+  /// Access to the contacts.
+  Contacts,
+}
+
+impl OsPermission {
+  /// The name of the permission, e.g. `camera`.
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      Self::Camera => "camera",
+      Self::Microphone => "microphone",
+      Self::Notifications => "notifications",
+      Self::Location => "location",
+      Self::Photos => "photos",
+      Self::Contacts => "contacts",
+    }
+  }
+
+  /// The Android permissions declared in the `AndroidManifest.xml` file.
+  pub fn android_permissions(&self) -> &'static [&'static str] {
+    match self {
+      Self::Camera => &["android.permission.CAMERA"],
+      Self::Microphone => &["android.permission.RECORD_AUDIO"],
+      Self::Notifications => &["android.permission.POST_NOTIFICATIONS"],
+      Self::Location => &[
+        "android.permission.ACCESS_COARSE_LOCATION",
+        "android.permission.ACCESS_FINE_LOCATION",
+      ],
+      Self::Photos => &[
+        "android.permission.READ_MEDIA_IMAGES",
+        "android.permission.READ_MEDIA_VIDEO",
+      ],
+      Self::Contacts => &["android.permission.READ_CONTACTS"],
+    }
+  }
+
+  /// The Android permissions replaced by [`Self::android_permissions`] in later Android versions,
+  /// declared in the `AndroidManifest.xml` file with the last API level using them as `android:maxSdkVersion`.
+  pub fn android_legacy_permissions(&self) -> &'static [(&'static str, u32)] {
+    match self {
+      // READ_MEDIA_IMAGES and READ_MEDIA_VIDEO were added in Android 13 (API level 33)
+      Self::Photos => &[("android.permission.READ_EXTERNAL_STORAGE", 32)],
+      _ => &[],
+    }
+  }
+
+  /// The `Info.plist` keys of the usage descriptions iOS displays when requesting the permission.
+  pub fn ios_usage_description_keys(&self) -> &'static [&'static str] {
+    match self {
+      Self::Camera => &["NSCameraUsageDescription"],
+      Self::Microphone => &["NSMicrophoneUsageDescription"],
+      Self::Notifications => &[],
+      Self::Location => &["NSLocationWhenInUseUsageDescription"],
+      Self::Photos => &["NSPhotoLibraryUsageDescription"],
+      Self::Contacts => &["NSContactsUsageDescription"],
+    }
+  }
+}
+
+impl std::fmt::Display for OsPermission {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.write_str(self.as_str())
+  }
+}
+
 /// Configuration for remote URLs that are associated with the capability.
 #[derive(Debug, Default, Clone, Serialize, Deserialize, Eq, PartialEq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
@@ -338,6 +433,21 @@ mod build {
     }
   }
 
+  impl ToTokens for OsPermission {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+      let prefix = quote! { ::tauri::utils::acl::capability::OsPermission };
+
+      tokens.append_all(match self {
+        Self::Camera => quote! { #prefix::Camera },
+        Self::Microphone => quote! { #prefix::Microphone },
+        Self::Notifications => quote! { #prefix::Notifications },
+        Self::Location => quote! { #prefix::Location },
+        Self::Photos => quote! { #prefix::Photos },
+        Self::Contacts => quote! { #prefix::Contacts },
+      });
+    }
+  }
+
   impl ToTokens for Capability {
     fn to_tokens(&self, tokens: &mut TokenStream) {
       let identifier = str_lit(&self.identifier);
@@ -348,6 +458,7 @@ mod build {
       let webviews = vec_lit(&self.webviews, str_lit);
       let permissions = vec_lit(&self.permissions, identity);
       let platforms = opt_vec_lit(self.platforms.as_ref(), identity);
+      let os_permissions = vec_lit(&self.os_permissions, identity);
 
       literal_struct!(
         tokens,
@@ -359,7 +470,8 @@ mod build {
         windows,
         webviews,
         permissions,
-        platforms
+        platforms,
+        os_permissions
       );
     }
   }
@@ -408,6 +520,7 @@ mod tests {
       webviews: vec![],
       permissions: vec![],
       platforms: None,
+      os_permissions: vec![],
     };
     let capability_json = serde_json::to_string(&capability).unwrap();
 
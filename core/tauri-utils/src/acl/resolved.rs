@@ -20,10 +20,14 @@ pub type ScopeKey = u64;
 const CORE_PLUGINS: &[&str] = &[
   "core:app",
   "core:event",
+  "core:fs-watch",
+  "core:i18n",
   "core:image",
+  "core:input",
   "core:menu",
   "core:path",
   "core:resources",
+  "core:sidecar",
   "core:tray",
   "core:webview",
   "core:window",
@@ -11,6 +11,14 @@ use std::{
   borrow::Cow,
   path::{Component, Path},
 };
+#[cfg(feature = "asset-packfile")]
+use std::{path::PathBuf, sync::OnceLock};
+
+/// The magic bytes starting an asset packfile, followed by its identifier.
+pub const PACKFILE_MAGIC: &[u8; 8] = b"TAURIPAK";
+
+/// The length of the asset packfile identifier, the hex-encoded checksum of its assets.
+pub const PACKFILE_ID_LEN: usize = 64;
 
 /// Represent an asset file path in a normalized way.
 ///
@@ -74,6 +82,36 @@ impl<P: AsRef<Path>> From<P> for AssetKey {
   }
 }
 
+impl AssetKey {
+  /// The key of the asset with the given content hash added to its file name, before the extension.
+  ///
+  /// e.g. `/assets/index.js` is `/assets/index.0a1b2c3d.js` for a hash starting with `0a1b2c3d`.
+  pub fn with_content_hash(&self, hash: &str) -> AssetKey {
+    let hash = &hash[..hash.len().min(CONTENT_HASH_LEN)];
+    let (dir, file_name) = self.0.rsplit_once('/').unwrap_or(("", &self.0));
+    match file_name.rsplit_once('.') {
+      Some((stem, extension)) if !stem.is_empty() => {
+        AssetKey(format!("{dir}/{stem}.{hash}.{extension}"))
+      }
+      _ => AssetKey(format!("{dir}/{file_name}.{hash}")),
+    }
+  }
+}
+
+/// Length of the content hash added to the file name of the assets by [`AssetKey::with_content_hash`].
+pub const CONTENT_HASH_LEN: usize = 8;
+
+/// Metadata of an embedded asset, computed at compile time.
+#[derive(Debug, Clone, Copy)]
+pub struct AssetMetadata<'a> {
+  /// The hash of the asset content, used as its `ETag`.
+  pub hash: &'a str,
+  /// The key of the asset with the content hash in its file name, see [`AssetKey::with_content_hash`].
+  pub hashed_key: &'a str,
+  /// The MIME type of the asset.
+  pub mime_type: &'a str,
+}
+
 /// A Content-Security-Policy hash value for a specific directive.
 /// For more information see [the MDN page](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Content-Security-Policy#directives).
 #[non_exhaustive]
@@ -112,6 +150,89 @@ pub struct EmbeddedAssets {
   global_hashes: &'static [CspHash<'static>],
   // Hashes that are associated to the CSP of the HTML file identified by the map key (the HTML asset key).
   html_hashes: phf::Map<&'static str, &'static [CspHash<'static>]>,
+  // Metadata of the assets, identified by the asset key.
+  manifest: phf::Map<&'static str, AssetMetadata<'static>>,
+  // Maps the content-hashed asset keys to the asset keys.
+  hashed_keys: phf::Map<&'static str, &'static str>,
+  // The packfile storing the assets instead of the binary.
+  #[cfg(feature = "asset-packfile")]
+  packfile: Option<Packfile>,
+}
+
+/// An asset packfile written next to the executable, memory-mapped on the first asset access.
+#[cfg(feature = "asset-packfile")]
+#[derive(Debug)]
+struct Packfile {
+  file_name: &'static str,
+  id: &'static str,
+  // (offset, length, hex-encoded SHA-256) of the assets in the packfile, identified by the asset key.
+  index: phf::Map<&'static str, (usize, usize, &'static str)>,
+  dir: OnceLock<PathBuf>,
+  data: OnceLock<Option<memmap2::Mmap>>,
+}
+
+#[cfg(feature = "asset-packfile")]
+impl Packfile {
+  fn data(&self) -> Option<&[u8]> {
+    self
+      .data
+      .get_or_init(|| {
+        let dir = match self.dir.get() {
+          Some(dir) => dir.clone(),
+          None => std::env::current_exe().ok()?.parent()?.to_path_buf(),
+        };
+        let path = dir.join(self.file_name);
+        match self.map(&path) {
+          Ok(data) => Some(data),
+          Err(e) => {
+            log::error!("failed to load the asset packfile {}: {e}", path.display());
+            None
+          }
+        }
+      })
+      .as_deref()
+  }
+
+  fn map(&self, path: &Path) -> std::io::Result<memmap2::Mmap> {
+    let file = std::fs::File::open(path)?;
+    // SAFETY: the packfile is written at build time and only read by the app,
+    // modifying it while the app is running is not supported.
+    let data = unsafe { memmap2::Mmap::map(&file)? };
+    let header_len = PACKFILE_MAGIC.len() + PACKFILE_ID_LEN;
+    if data.len() < header_len
+      || &data[..PACKFILE_MAGIC.len()] != PACKFILE_MAGIC
+      || &data[PACKFILE_MAGIC.len()..header_len] != self.id.as_bytes()
+    {
+      return Err(std::io::Error::new(
+        std::io::ErrorKind::InvalidData,
+        "the packfile does not match the assets of this build",
+      ));
+    }
+    Ok(data)
+  }
+
+  /// The bytes of the asset, if they match the hash recorded at build time.
+  ///
+  /// The assets are checked on every access since the packfile can be modified after it is mapped.
+  fn get(&self, key: &str) -> Option<&[u8]> {
+    use sha2::Digest;
+
+    let (offset, len, hash) = *self.index.get(key)?;
+    let bytes = self.data()?.get(offset..offset.checked_add(len)?)?;
+    let digest = sha2::Sha256::digest(bytes)
+      .iter()
+      .map(|byte| format!("{byte:02x}"))
+      .collect::<String>();
+    if digest == hash {
+      Some(bytes)
+    } else {
+      log::error!(
+        "the asset {key} of the packfile {} is corrupted",
+        self.file_name
+      );
+      None
+    }
+  }
 }
 
 impl EmbeddedAssets {
@@ -125,16 +246,77 @@ impl EmbeddedAssets {
       assets: map,
       global_hashes,
       html_hashes,
+      manifest: phf::Map::new(),
+      hashed_keys: phf::Map::new(),
+      #[cfg(feature = "asset-packfile")]
+      packfile: None,
     }
   }
 
+  /// Creates a new instance reading the assets from the packfile with the given name,
+  /// looked up in the directory set with [`Self::set_packfile_dir`] or next to the executable.
+  ///
+  /// The packfile is memory-mapped on the first asset access and must start with [`PACKFILE_MAGIC`] and the given identifier.
+  /// The index maps the asset keys to their offset, length and hex-encoded SHA-256 hash in the packfile,
+  /// the assets not matching their hash are not served.
+  #[cfg(feature = "asset-packfile")]
+  pub fn from_packfile(
+    file_name: &'static str,
+    id: &'static str,
+    index: phf::Map<&'static str, (usize, usize, &'static str)>,
+    global_hashes: &'static [CspHash<'static>],
+    html_hashes: phf::Map<&'static str, &'static [CspHash<'static>]>,
+  ) -> Self {
+    Self {
+      packfile: Some(Packfile {
+        file_name,
+        id,
+        index,
+        dir: OnceLock::new(),
+        data: OnceLock::new(),
+      }),
+      ..Self::new(phf::Map::new(), global_hashes, html_hashes)
+    }
+  }
+
+  /// Sets the directory of the packfile, e.g. the resource directory of a bundled app.
+  ///
+  /// Has no effect if the assets are embedded or if the packfile was already loaded.
+  #[cfg(feature = "asset-packfile")]
+  pub fn set_packfile_dir(&self, dir: PathBuf) {
+    if let Some(packfile) = &self.packfile {
+      let _ = packfile.dir.set(dir);
+    }
+  }
+
+  /// Sets the asset manifest, mapping the asset keys to their metadata, and the asset keys of their content-hashed keys.
+  pub fn with_manifest(
+    self,
+    manifest: phf::Map<&'static str, AssetMetadata<'static>>,
+    hashed_keys: phf::Map<&'static str, &'static str>,
+  ) -> Self {
+    Self {
+      manifest,
+      hashed_keys,
+      ..self
+    }
+  }
+
+  /// The stored bytes of an asset, compressed with the `compression` feature.
+  fn raw(&self, key: &str) -> Option<&[u8]> {
+    #[cfg(feature = "asset-packfile")]
+    if let Some(packfile) = &self.packfile {
+      return packfile.get(key);
+    }
+    self.assets.get(key).copied()
+  }
+
   /// Get an asset by key.
   #[cfg(feature = "compression")]
   pub fn get(&self, key: &AssetKey) -> Option<Cow<'_, [u8]>> {
     self
-      .assets
-      .get(key.as_ref())
-      .map(|&(mut asdf)| {
+      .raw(key.as_ref())
+      .map(|mut asdf| {
         // with the exception of extremely small files, output should usually be
         // at least as large as the compressed version.
         let mut buf = Vec::with_capacity(asdf.len());
@@ -147,15 +329,43 @@ impl EmbeddedAssets {
   /// Get an asset by key.
   #[cfg(not(feature = "compression"))]
   pub fn get(&self, key: &AssetKey) -> Option<Cow<'_, [u8]>> {
+    self.raw(key.as_ref()).map(|a| Cow::Owned(a.to_vec()))
+  }
+
+  /// Get an asset by key without decompressing it, if it is stored with the given [content encoding](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Content-Encoding).
+  #[allow(unused_variables)]
+  pub fn get_encoded(&self, key: &AssetKey, encoding: &str) -> Option<Cow<'_, [u8]>> {
+    #[cfg(feature = "compression")]
+    if encoding == "br" {
+      return self.raw(key.as_ref()).map(Cow::Borrowed);
+    }
+    None
+  }
+
+  /// The compile-time metadata of the asset.
+  pub fn metadata(&self, key: &AssetKey) -> Option<AssetMetadata<'_>> {
+    self.manifest.get(key.as_ref()).copied()
+  }
+
+  /// The key of the asset served on the given content-hashed key.
+  pub fn resolve_hashed(&self, hashed_key: &AssetKey) -> Option<AssetKey> {
     self
-      .assets
-      .get(key.as_ref())
-      .copied()
-      .map(|a| Cow::Owned(a.to_vec()))
+      .hashed_keys
+      .get(hashed_key.as_ref())
+      .map(|key| AssetKey(key.to_string()))
   }
 
   /// Iterate on the assets.
   pub fn iter(&self) -> Box<dyn Iterator<Item = (&str, &[u8])> + '_> {
+    #[cfg(feature = "asset-packfile")]
+    if let Some(packfile) = &self.packfile {
+      return Box::new(
+        packfile
+          .index
+          .keys()
+          .filter_map(|key| packfile.get(key).map(|bytes| (*key, bytes))),
+      );
+    }
     Box::new(self.assets.into_iter().map(|(k, b)| (*k, *b)))
   }
 
@@ -177,3 +387,82 @@ impl EmbeddedAssets {
     )
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn content_hashed_key() {
+    let hash = "0a1b2c3d4e5f";
+    assert_eq!(
+      AssetKey::from("assets/index.js").with_content_hash(hash),
+      AssetKey("/assets/index.0a1b2c3d.js".into())
+    );
+    assert_eq!(
+      AssetKey::from("LICENSE").with_content_hash(hash),
+      AssetKey("/LICENSE.0a1b2c3d".into())
+    );
+    assert_eq!(
+      AssetKey::from(".well-known/config").with_content_hash(hash),
+      AssetKey("/.well-known/config.0a1b2c3d".into())
+    );
+  }
+
+  #[cfg(feature = "asset-packfile")]
+  #[test]
+  fn packfile_assets() {
+    // SHA-256 of `<html></html>`
+    const HASH: &str = "b633a587c652d02386c4f16f8c6f6aab7352d97f16367c3c40576214372dd628";
+    let id = "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef";
+    let dir = std::env::temp_dir().join(format!("tauri-utils-packfile-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let mut packfile = PACKFILE_MAGIC.to_vec();
+    packfile.extend(id.as_bytes());
+    let offset = packfile.len();
+    packfile.extend(b"<html></html>");
+    std::fs::write(dir.join("app.pak"), &packfile).unwrap();
+
+    let assets = EmbeddedAssets::from_packfile(
+      "app.pak",
+      id,
+      phf::phf_map! { "/index.html" => (72, 13, HASH) },
+      &[],
+      phf::Map::new(),
+    );
+    assert_eq!(offset, 72);
+    assets.set_packfile_dir(dir.clone());
+    assert_eq!(
+      assets.iter().collect::<Vec<_>>(),
+      vec![("/index.html", b"<html></html>".as_slice())]
+    );
+
+    let mismatched = EmbeddedAssets::from_packfile(
+      "app.pak",
+      "outdated",
+      phf::phf_map! { "/index.html" => (72, 13, HASH) },
+      &[],
+      phf::Map::new(),
+    );
+    mismatched.set_packfile_dir(dir.clone());
+    assert_eq!(mismatched.iter().count(), 0);
+
+    // a tampered asset is not served, even with a valid header
+    let tampered_dir = dir.join("tampered");
+    std::fs::create_dir_all(&tampered_dir).unwrap();
+    packfile.truncate(offset);
+    packfile.extend(b"<html>!</html>");
+    std::fs::write(tampered_dir.join("app.pak"), &packfile).unwrap();
+    let tampered = EmbeddedAssets::from_packfile(
+      "app.pak",
+      id,
+      phf::phf_map! { "/index.html" => (72, 13, HASH) },
+      &[],
+      phf::Map::new(),
+    );
+    tampered.set_packfile_dir(tampered_dir);
+    assert!(tampered.get(&"index.html".into()).is_none());
+
+    std::fs::remove_dir_all(dir).unwrap();
+  }
+}
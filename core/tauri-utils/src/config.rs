@@ -39,15 +39,17 @@ use std::{
   collections::HashMap,
   fmt::{self, Display},
   fs::read_to_string,
-  path::PathBuf,
+  path::{Path, PathBuf},
   str::FromStr,
 };
 
+mod builder;
 /// Items to help with parsing content into a [`Config`].
 pub mod parse;
 
 use crate::{acl::capability::Capability, TitleBarStyle, WindowEffect, WindowEffectState};
 
+pub use self::builder::ConfigBuilder;
 pub use self::parse::parse;
 
 fn default_true() -> bool {
@@ -129,10 +131,24 @@ pub enum BundleType {
   App,
   /// The Apple Disk Image bundle (.dmg).
   Dmg,
+  /// The Flatpak bundle (.flatpak).
+  Flatpak,
+  /// The Snap bundle (.snap).
+  Snap,
+  /// The MSIX package (.msix).
+  Msix,
+  /// The macOS installer package (.pkg).
+  Pkg,
+  /// The Windows portable app archive (.zip).
+  Portable,
 }
 
 impl BundleType {
   /// All bundle types.
+  ///
+  /// The Flatpak, Snap, MSIX and PKG bundles are not included since they require `flatpak-builder`,
+  /// `snapcraft`, `makeappx` and `productbuild` respectively and must be explicitly requested.
+  /// The portable bundle is an alternative distribution of the NSIS and MSI installers and must also be explicitly requested.
   fn all() -> &'static [Self] {
     &[
       BundleType::Deb,
@@ -159,6 +175,11 @@ impl Display for BundleType {
         Self::Nsis => "nsis",
         Self::App => "app",
         Self::Dmg => "dmg",
+        Self::Flatpak => "flatpak",
+        Self::Snap => "snap",
+        Self::Msix => "msix",
+        Self::Pkg => "pkg",
+        Self::Portable => "portable",
       }
     )
   }
@@ -187,6 +208,11 @@ impl<'de> Deserialize<'de> for BundleType {
       "nsis" => Ok(Self::Nsis),
       "app" => Ok(Self::App),
       "dmg" => Ok(Self::Dmg),
+      "flatpak" => Ok(Self::Flatpak),
+      "snap" => Ok(Self::Snap),
+      "msix" => Ok(Self::Msix),
+      "pkg" => Ok(Self::Pkg),
+      "portable" => Ok(Self::Portable),
       _ => Err(DeError::custom(format!("unknown bundle target '{s}'"))),
     }
   }
@@ -308,6 +334,7 @@ impl BundleTarget {
 /// Configuration for AppImage bundles.
 ///
 /// See more: <https://tauri.app/v1/api/config#appimageconfig>
+#[skip_serializing_none]
 #[derive(Debug, Default, PartialEq, Eq, Clone, Deserialize, Serialize)]
 #[cfg_attr(feature = "schema", derive(JsonSchema))]
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
@@ -319,6 +346,18 @@ pub struct AppImageConfig {
   /// The files to include in the Appimage Binary.
   #[serde(default)]
   pub files: HashMap<PathBuf, PathBuf>,
+  /// The update information embedded in the AppImage, used by AppImageUpdate and the updater to find the next release.
+  ///
+  /// A `.zsync` file is generated next to the AppImage and must be published along with it, so updates only download the changed blocks.
+  /// Requires `zsyncmake` when the AppImage tooling does not generate it.
+  ///
+  /// Supported formats:
+  /// - `zsync|<url of the .zsync file>`
+  /// - `gh-releases-zsync|<user>|<repo>|<tag or latest>|<.zsync file name pattern>`
+  ///
+  /// Example: `gh-releases-zsync|tauri-apps|tauri|latest|app_*_amd64.AppImage.zsync`.
+  #[serde(alias = "update-information")]
+  pub update_information: Option<String>,
 }
 
 /// Configuration for Debian (.deb) bundles.
@@ -347,12 +386,18 @@ pub struct DebConfig {
   pub priority: Option<String>,
   /// Path of the uncompressed Changelog file, to be stored at /usr/share/doc/package-name/changelog.gz. See
   /// <https://www.debian.org/doc/debian-policy/ch-docs.html#changelog-files-and-release-notes>
+  ///
+  /// A Markdown file such as `CHANGELOG.md` is converted to the Debian changelog format,
+  /// with an entry for each `## <version>` section.
   pub changelog: Option<PathBuf>,
   /// Path to a custom desktop file Handlebars template.
   ///
   /// Available variables: `categories`, `comment` (optional), `exec`, `icon` and `name`.
   #[serde(alias = "desktop-template")]
   pub desktop_template: Option<PathBuf>,
+  /// Where the app binaries and resources are installed. Defaults to `usr`.
+  #[serde(default, alias = "install-prefix")]
+  pub install_prefix: LinuxInstallPrefix,
   /// Path to script that will be executed before the package is unpacked. See
   /// <https://www.debian.org/doc/debian-policy/ch-maintainerscripts.html>
   #[serde(alias = "pre-install-script")]
@@ -371,6 +416,22 @@ pub struct DebConfig {
   pub post_remove_script: Option<PathBuf>,
 }
 
+/// Where the binaries and resources of the Debian and RPM packages are installed.
+///
+/// The desktop entry and icons are always installed to `/usr/share`.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum LinuxInstallPrefix {
+  /// Install the binaries to `/usr/bin` and the resources to `/usr/lib/<main binary name>`.
+  #[default]
+  Usr,
+  /// Install the binaries to `/opt/<main binary name>/bin` and the resources to `/opt/<main binary name>/lib/<main binary name>`.
+  ///
+  /// The binaries are not added to the `PATH`.
+  Opt,
+}
+
 /// Configuration for Linux bundles.
 ///
 /// See more: <https://tauri.app/v1/api/config#linuxconfig>
@@ -388,6 +449,314 @@ pub struct LinuxConfig {
   /// Configuration for the RPM bundle.
   #[serde(default)]
   pub rpm: RpmConfig,
+  /// Configuration for the Flatpak bundle.
+  #[serde(default)]
+  pub flatpak: FlatpakConfig,
+  /// Configuration for the Snap bundle.
+  #[serde(default)]
+  pub snap: SnapConfig,
+  /// Cross compilation settings for Linux targets, keyed by target triple,
+  /// e.g. `aarch64-unknown-linux-gnu` or `armv7-unknown-linux-gnueabihf`.
+  ///
+  /// When building for one of these targets from a different host,
+  /// the CLI configures cargo to use the target toolchain, sysroot or container image.
+  #[serde(default, alias = "cross-compile")]
+  pub cross_compile: HashMap<String, LinuxCrossCompileConfig>,
+  /// A systemd user service installed with the Debian and RPM packages,
+  /// to run the app as a background agent of the user session, e.g. a tray daemon.
+  pub service: Option<LinuxServiceConfig>,
+}
+
+impl LinuxConfig {
+  /// Validates the package relationships of the Debian and RPM packages,
+  /// returning a message describing each invalid entry.
+  pub fn validate(&self) -> Vec<String> {
+    let mut errors = Vec::new();
+
+    let deb_relationships = [
+      ("deb > depends", &self.deb.depends),
+      ("deb > provides", &self.deb.provides),
+      ("deb > conflicts", &self.deb.conflicts),
+      ("deb > replaces", &self.deb.replaces),
+    ];
+    for (field, relationships) in deb_relationships {
+      for relationship in relationships.iter().flatten() {
+        // only `depends` accepts alternatives
+        let alternatives = relationship.split('|').collect::<Vec<_>>();
+        let is_valid = (alternatives.len() == 1 || field == "deb > depends")
+          && alternatives
+            .iter()
+            .all(|alternative| is_valid_deb_relationship(alternative.trim()));
+        if !is_valid {
+          errors.push(format!(
+            "invalid `{field}` entry `{relationship}`, expected `name` or `name (op version)` with op one of <<, <=, =, >=, >>"
+          ));
+        }
+      }
+    }
+
+    let rpm_relationships = [
+      ("rpm > depends", &self.rpm.depends),
+      ("rpm > provides", &self.rpm.provides),
+      ("rpm > conflicts", &self.rpm.conflicts),
+      ("rpm > obsoletes", &self.rpm.obsoletes),
+    ];
+    for (field, relationships) in rpm_relationships {
+      for relationship in relationships.iter().flatten() {
+        if parse_rpm_dependency(relationship).is_none() {
+          errors.push(format!(
+            "invalid `{field}` entry `{relationship}`, expected `name` or `name op version` with op one of <, <=, =, >=, >"
+          ));
+        }
+      }
+    }
+
+    errors
+  }
+}
+
+/// Whether the relationship is a valid Debian package relationship such as `libc6 (>= 2.31)`.
+fn is_valid_deb_relationship(relationship: &str) -> bool {
+  let (name, constraint) = match relationship.split_once('(') {
+    Some((name, constraint)) => (name.trim(), Some(constraint)),
+    None => (relationship, None),
+  };
+  // an architecture qualifier such as `:any` may follow the name
+  let name = name.split_once(':').map_or(name, |(name, _arch)| name);
+  let is_valid_name = name.len() >= 2
+    && name.starts_with(|c: char| c.is_ascii_lowercase() || c.is_ascii_digit())
+    && name
+      .chars()
+      .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || "+-.".contains(c));
+  if !is_valid_name {
+    return false;
+  }
+
+  match constraint {
+    None => true,
+    Some(constraint) => {
+      let Some(constraint) = constraint.trim().strip_suffix(')') else {
+        return false;
+      };
+      let op_len = constraint
+        .find(|c: char| !"<=>".contains(c))
+        .unwrap_or(constraint.len());
+      let (op, version) = constraint.split_at(op_len);
+      let version = version.trim();
+      ["<<", "<=", "=", ">=", ">>"].contains(&op)
+        && !version.is_empty()
+        && !version.contains(char::is_whitespace)
+    }
+  }
+}
+
+/// Parses an RPM dependency such as `webkit2gtk4.1 >= 2.40` into its name and optional (operator, version) constraint.
+///
+/// Returns `None` if the dependency is not valid.
+pub fn parse_rpm_dependency(dependency: &str) -> Option<(&str, Option<(&str, &str)>)> {
+  let mut parts = dependency.split_whitespace();
+  let name = parts.next()?;
+  match (parts.next(), parts.next(), parts.next()) {
+    (None, _, _) => Some((name, None)),
+    (Some(op), Some(version), None) if ["<", "<=", "=", ">=", ">"].contains(&op) => {
+      Some((name, Some((op, version))))
+    }
+    _ => None,
+  }
+}
+
+/// Cross compilation settings for a Linux target.
+///
+/// Either a local toolchain with a target sysroot or a [`cross`](https://github.com/cross-rs/cross) container image can be used.
+#[skip_serializing_none]
+#[derive(Debug, Default, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct LinuxCrossCompileConfig {
+  /// Path to the target sysroot, containing the target webkit2gtk development files.
+  ///
+  /// It is used to resolve the system libraries with `pkg-config` and to link the app.
+  pub sysroot: Option<PathBuf>,
+  /// The linker for the target.
+  /// Defaults to the GNU toolchain for the target, e.g. `aarch64-linux-gnu-gcc` or `arm-linux-gnueabihf-gcc`.
+  pub linker: Option<String>,
+  /// A container image to build the app with [`cross`](https://github.com/cross-rs/cross) instead of a local toolchain.
+  ///
+  /// The image must provide the target webkit2gtk development files.
+  pub image: Option<String>,
+}
+
+/// A systemd user service installed with the Debian and RPM packages.
+///
+/// The unit is installed to `/usr/lib/systemd/user` and runs the main binary.
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct LinuxServiceConfig {
+  /// The unit name, without the `.service` suffix. Defaults to the main binary name.
+  pub name: Option<String>,
+  /// The unit description. Defaults to the product name.
+  pub description: Option<String>,
+  /// The arguments passed to the main binary when started by the service, e.g. `--background`.
+  #[serde(default)]
+  pub args: Vec<String>,
+  /// The environment variables of the service.
+  #[serde(default)]
+  pub environment: HashMap<String, String>,
+  /// When the service is restarted after it exits. Defaults to `on-failure`.
+  #[serde(default)]
+  pub restart: LinuxServiceRestart,
+  /// Whether to start the service with the graphical session of every user.
+  ///
+  /// The unit is enabled globally by the post-installation script and disabled before the package is removed.
+  /// It starts on the next login of each user. Defaults to `true`.
+  #[serde(default = "default_true")]
+  pub autostart: bool,
+  /// The well-known D-Bus name of the app, e.g. `com.tauri.example`.
+  ///
+  /// If set, a D-Bus activation file is installed to `/usr/share/dbus-1/services`
+  /// so the service is started when this name is first requested on the session bus.
+  #[serde(alias = "dbus-name")]
+  pub dbus_name: Option<String>,
+}
+
+/// When the systemd service is restarted, see the `Restart=` option of `systemd.service`.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub enum LinuxServiceRestart {
+  /// Never restart the service.
+  No,
+  /// Restart the service when it exits with an error or is killed.
+  #[default]
+  OnFailure,
+  /// Always restart the service.
+  Always,
+}
+
+/// Configuration for Flatpak bundles.
+///
+/// The sandbox permissions (`finish-args`) are derived from the permissions in the app capabilities,
+/// e.g. `http:` permissions grant network access and `fs:` permissions grant access to the user directories.
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct FlatpakConfig {
+  /// The Flatpak runtime. Defaults to `org.gnome.Platform`, which ships webkit2gtk.
+  #[serde(default = "default_flatpak_runtime")]
+  pub runtime: String,
+  /// The Flatpak runtime version. Defaults to `46`.
+  #[serde(default = "default_flatpak_runtime_version", alias = "runtime-version")]
+  pub runtime_version: String,
+  /// The Flatpak SDK used to build the bundle. Defaults to `org.gnome.Sdk`.
+  #[serde(default = "default_flatpak_sdk")]
+  pub sdk: String,
+  /// Additional sandbox permissions, appended to the permissions derived from the app capabilities.
+  ///
+  /// See <https://docs.flatpak.org/en/latest/sandbox-permissions.html>
+  #[serde(default, alias = "finish-args")]
+  pub finish_args: Vec<String>,
+  /// The files to include in the Flatpak, with the destination relative to `/app`.
+  #[serde(default)]
+  pub files: HashMap<PathBuf, PathBuf>,
+}
+
+impl Default for FlatpakConfig {
+  fn default() -> Self {
+    Self {
+      runtime: default_flatpak_runtime(),
+      runtime_version: default_flatpak_runtime_version(),
+      sdk: default_flatpak_sdk(),
+      finish_args: Vec::new(),
+      files: Default::default(),
+    }
+  }
+}
+
+fn default_flatpak_runtime() -> String {
+  "org.gnome.Platform".into()
+}
+
+fn default_flatpak_runtime_version() -> String {
+  "46".into()
+}
+
+fn default_flatpak_sdk() -> String {
+  "org.gnome.Sdk".into()
+}
+
+/// The confinement of a Snap bundle.
+///
+/// See <https://snapcraft.io/docs/snap-confinement>
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase", deny_unknown_fields)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub enum SnapConfinement {
+  /// The app runs in a sandbox and can only access the resources granted by its plugs.
+  #[default]
+  Strict,
+  /// The app has the same access as a regular package. Requires an approval to be published on the Snap Store.
+  Classic,
+  /// The app runs with strict confinement but violations are only logged. Cannot be published to the stable channel.
+  Devmode,
+}
+
+/// The grade of a Snap bundle.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase", deny_unknown_fields)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub enum SnapGrade {
+  /// The snap can be released to all channels.
+  #[default]
+  Stable,
+  /// The snap can only be released to the `edge` and `beta` channels.
+  Devel,
+}
+
+/// Configuration for Snap bundles.
+///
+/// The interfaces the app connects to (`plugs`) are derived from the permissions in the app capabilities,
+/// e.g. `http:` permissions connect the `network` interface and `fs:` permissions the `home` interface.
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct SnapConfig {
+  /// The base snap providing the run-time environment. Defaults to `core22`.
+  #[serde(default = "default_snap_base")]
+  pub base: String,
+  /// The snap confinement. Defaults to `strict`.
+  #[serde(default)]
+  pub confinement: SnapConfinement,
+  /// The snap grade. Defaults to `stable`.
+  #[serde(default)]
+  pub grade: SnapGrade,
+  /// Additional interfaces the app connects to, appended to the interfaces derived from the app capabilities.
+  ///
+  /// See <https://snapcraft.io/docs/supported-interfaces>
+  #[serde(default)]
+  pub plugs: Vec<String>,
+  /// The files to include in the Snap, with the destination relative to the snap root.
+  #[serde(default)]
+  pub files: HashMap<PathBuf, PathBuf>,
+}
+
+impl Default for SnapConfig {
+  fn default() -> Self {
+    Self {
+      base: default_snap_base(),
+      confinement: Default::default(),
+      grade: Default::default(),
+      plugs: Vec::new(),
+      files: Default::default(),
+    }
+  }
+}
+
+fn default_snap_base() -> String {
+  "core22".into()
 }
 
 /// Configuration for RPM bundles.
@@ -420,6 +789,12 @@ pub struct RpmConfig {
   /// Available variables: `categories`, `comment` (optional), `exec`, `icon` and `name`.
   #[serde(alias = "desktop-template")]
   pub desktop_template: Option<PathBuf>,
+  /// Path to a Markdown changelog such as `CHANGELOG.md`,
+  /// converted to the package changelog with an entry for each `## <version>` section.
+  pub changelog: Option<PathBuf>,
+  /// Where the app binaries and resources are installed. Defaults to `usr`.
+  #[serde(default, alias = "install-prefix")]
+  pub install_prefix: LinuxInstallPrefix,
   /// Path to script that will be executed before the package is unpacked. See
   /// <http://ftp.rpm.org/max-rpm/s1-rpm-inside-scripts.html>
   #[serde(alias = "pre-install-script")]
@@ -449,6 +824,8 @@ impl Default for RpmConfig {
       epoch: 0,
       files: Default::default(),
       desktop_template: None,
+      changelog: None,
+      install_prefix: Default::default(),
       pre_install_script: None,
       post_install_script: None,
       pre_remove_script: None,
@@ -493,6 +870,12 @@ pub struct Size {
 pub struct DmgConfig {
   /// Image to use as the background in dmg file. Accepted formats: `png`/`jpg`/`gif`.
   pub background: Option<PathBuf>,
+  /// A map of languages and the background image in that language, e.g. `{ "en": "dmg-en.png", "de": "dmg-de.png" }`.
+  ///
+  /// Finder displays a single background per disk image, so one disk image is built for each language,
+  /// named with the language suffix, e.g. `MyApp_1.0.0_aarch64_de.dmg`. [`Self::background`] is then ignored.
+  #[serde(default, alias = "localized-backgrounds")]
+  pub localized_backgrounds: HashMap<String, PathBuf>,
   /// Position of volume window on screen.
   pub window_position: Option<Position>,
   /// Size of volume window.
@@ -507,20 +890,67 @@ pub struct DmgConfig {
     alias = "application-folder-position"
   )]
   pub application_folder_position: Position,
+  /// The volume name displayed in the Finder sidebar and window title. Defaults to the product name.
+  #[serde(alias = "volume-name")]
+  pub volume_name: Option<String>,
+  /// Path to the `.icns` volume icon. Defaults to the app icon.
+  #[serde(alias = "volume-icon")]
+  pub volume_icon: Option<PathBuf>,
+  /// Size of the icons on the window, up to `128`.
+  #[serde(alias = "icon-size")]
+  pub icon_size: Option<u32>,
+  /// Size of the icon labels on the window, from `10` to `16`.
+  #[serde(alias = "text-size")]
+  pub text_size: Option<u32>,
+  /// The license agreement displayed when the disk image is opened. Defaults to `bundle > licenseFile`.
+  ///
+  /// Plain text and RTF files are supported.
+  pub license: Option<DmgLicense>,
+  /// Identity to use for signing the disk image. Defaults to `bundle > macOS > signingIdentity`.
+  #[serde(alias = "signing-identity")]
+  pub signing_identity: Option<String>,
+  /// Notarize the disk image, so its notarization ticket is stapled to it.
+  ///
+  /// Requires the disk image to be signed and the notarization credentials to be set.
+  #[serde(default)]
+  pub notarize: bool,
 }
 
 impl Default for DmgConfig {
   fn default() -> Self {
     Self {
       background: None,
+      localized_backgrounds: HashMap::new(),
       window_position: None,
       window_size: dmg_window_size(),
       app_position: dmg_app_position(),
       application_folder_position: dmg_application_folder_position(),
+      volume_name: None,
+      volume_icon: None,
+      icon_size: None,
+      text_size: None,
+      license: None,
+      signing_identity: None,
+      notarize: false,
     }
   }
 }
 
+/// The license agreement of a DMG, stored as a Software License Agreement resource.
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(untagged)]
+pub enum DmgLicense {
+  /// Path to a license file, displayed in English.
+  Path(PathBuf),
+  /// A map of languages and the path to the license file in that language,
+  /// e.g. `{ "en": "LICENSE.txt", "de": "LICENSE.de.txt" }`.
+  ///
+  /// The user chooses the language when the disk image is opened, which defaults to English if provided.
+  /// Supported languages are `da`, `de`, `en`, `es`, `fi`, `fr`, `it`, `nb`, `nl`, `pt` and `sv`.
+  Localized(HashMap<String, PathBuf>),
+}
+
 fn dmg_window_size() -> Size {
   Size {
     width: 660,
@@ -536,6 +966,56 @@ fn dmg_application_folder_position() -> Position {
   Position { x: 480, y: 170 }
 }
 
+/// Configuration for macOS installer packages (.pkg).
+///
+/// The app bundle is wrapped in a component package built with `pkgbuild`
+/// and the installer package is assembled with `productbuild`.
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct PkgConfig {
+  /// The folder where the app bundle is installed. Defaults to `/Applications`.
+  #[serde(default = "pkg_install_location", alias = "install-location")]
+  pub install_location: PathBuf,
+  /// Path to script that will be executed before the app is installed. See
+  /// <https://developer.apple.com/documentation/xcode/customizing-the-installation-of-your-app>
+  #[serde(alias = "pre-install-script")]
+  pub pre_install_script: Option<PathBuf>,
+  /// Path to script that will be executed after the app is installed. See
+  /// <https://developer.apple.com/documentation/xcode/customizing-the-installation-of-your-app>
+  #[serde(alias = "post-install-script")]
+  pub post_install_script: Option<PathBuf>,
+  /// Additional component packages (.pkg) to include in the installer, e.g. drivers or helper tools.
+  ///
+  /// The paths are relative to the Tauri configuration directory.
+  #[serde(default, alias = "component-packages")]
+  pub component_packages: Vec<PathBuf>,
+  /// Identity to use for signing the installer package, e.g. `Developer ID Installer: Tauri Programme (TEAMID)`.
+  ///
+  /// Can be set with the `APPLE_INSTALLER_SIGNING_IDENTITY` environment variable,
+  /// and its certificate imported with the `APPLE_INSTALLER_CERTIFICATE` and `APPLE_INSTALLER_CERTIFICATE_PASSWORD` environment variables.
+  /// The installer package is notarized when the app bundle is signed and notarization credentials are set.
+  #[serde(alias = "signing-identity")]
+  pub signing_identity: Option<String>,
+}
+
+impl Default for PkgConfig {
+  fn default() -> Self {
+    Self {
+      install_location: pkg_install_location(),
+      pre_install_script: None,
+      post_install_script: None,
+      component_packages: Vec::new(),
+      signing_identity: None,
+    }
+  }
+}
+
+fn pkg_install_location() -> PathBuf {
+  "/Applications".into()
+}
+
 fn de_minimum_system_version<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
 where
   D: Deserializer<'de>,
@@ -594,6 +1074,12 @@ pub struct MacConfig {
   /// DMG-specific settings.
   #[serde(default)]
   pub dmg: DmgConfig,
+  /// PKG-specific settings.
+  #[serde(default)]
+  pub pkg: PkgConfig,
+  /// Notarization settings, used when the app is signed and the notarization credentials are set.
+  #[serde(default)]
+  pub notarization: NotarizationConfig,
 }
 
 impl Default for MacConfig {
@@ -608,10 +1094,52 @@ impl Default for MacConfig {
       provider_short_name: None,
       entitlements: None,
       dmg: Default::default(),
+      pkg: Default::default(),
+      notarization: Default::default(),
+    }
+  }
+}
+
+/// Configuration for the macOS notarization with `notarytool`.
+///
+/// The credentials are read from the `APPLE_ID`, `APPLE_PASSWORD` and `APPLE_TEAM_ID` environment variables,
+/// the `APPLE_API_KEY`, `APPLE_API_ISSUER` and `APPLE_API_KEY_PATH` environment variables
+/// or the `APPLE_KEYCHAIN_PROFILE` environment variable.
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct NotarizationConfig {
+  /// Maximum time to wait for Apple to process a submission, in seconds. Defaults to one hour.
+  #[serde(default = "notarization_timeout")]
+  pub timeout: u64,
+  /// Number of attempts to upload a submission and staple its ticket,
+  /// to recover from transient network or service errors. Defaults to `3`.
+  #[serde(default = "notarization_attempts")]
+  pub attempts: u32,
+  /// Whether to staple the notarization ticket to the notarized app, disk image or installer package,
+  /// so it can be verified offline. Defaults to `true`.
+  #[serde(default = "default_true")]
+  pub staple: bool,
+}
+
+impl Default for NotarizationConfig {
+  fn default() -> Self {
+    Self {
+      timeout: notarization_timeout(),
+      attempts: notarization_attempts(),
+      staple: true,
     }
   }
 }
 
+fn notarization_timeout() -> u64 {
+  60 * 60
+}
+
+fn notarization_attempts() -> u32 {
+  3
+}
+
 fn minimum_system_version() -> Option<String> {
   Some("10.13".into())
 }
@@ -692,6 +1220,224 @@ pub struct WixConfig {
   /// The required dimensions are 493px × 312px.
   #[serde(alias = "dialog-image-path")]
   pub dialog_image_path: Option<PathBuf>,
+  /// Whether the installation will be for all users or just the current user.
+  #[serde(default, alias = "install-mode")]
+  pub install_mode: WixInstallMode,
+  /// The major upgrade behavior when another version of the app is already installed.
+  #[serde(default)]
+  pub upgrade: WixUpgradeConfig,
+  /// Custom actions to run during the installation or uninstallation.
+  #[serde(default, alias = "custom-actions")]
+  pub custom_actions: Vec<WixCustomAction>,
+  /// Additional registry values written by the installer and removed on uninstall.
+  #[serde(default)]
+  pub registry: Vec<WixRegistryValue>,
+  /// Windows services installed from the app binaries.
+  ///
+  /// Services are installed for all users, so the `perMachine` install mode is required.
+  #[serde(default)]
+  pub services: Vec<WixService>,
+  /// Automatically reference the `ComponentGroup` elements defined in the fragments,
+  /// so their components, custom actions and sequences are merged into the installer
+  /// without listing them in `componentGroupRefs`.
+  #[serde(default, alias = "merge-fragments")]
+  pub merge_fragments: bool,
+}
+
+/// Install modes for the MSI installer.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub enum WixInstallMode {
+  /// Default mode for the installer.
+  ///
+  /// Install the app in the `Program Files` folder for all users, which requires Administrator access.
+  #[default]
+  PerMachine,
+  /// Install the app in the `%LOCALAPPDATA%\Programs` folder for the current user,
+  /// which doesn't require Administrator access.
+  CurrentUser,
+  /// Let the user choose on an installer dialog whether to install the app for all users or
+  /// for the current user only. Administrator access is only requested for all users installations.
+  ///
+  /// Silent installations are for all users unless `MSIINSTALLPERUSER=1` is passed to `msiexec`.
+  Both,
+}
+
+/// Configuration for the MSI major upgrade, see <https://wixtoolset.org/docs/v3/xsd/wix/majorupgrade/>.
+///
+/// Downgrades are configured with `bundle > windows > allowDowngrades`.
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct WixUpgradeConfig {
+  /// Whether installing the same version again replaces the installed product
+  /// instead of installing it side by side. Implied when downgrades are allowed.
+  #[serde(default = "default_true", alias = "allow-same-version-upgrades")]
+  pub allow_same_version_upgrades: bool,
+  /// When the installed product is removed during the upgrade.
+  #[serde(default)]
+  pub schedule: WixUpgradeSchedule,
+  /// The message displayed when a newer version is installed and downgrades are not allowed.
+  /// Defaults to the `DowngradeErrorMessage` locale string.
+  #[serde(alias = "downgrade-error-message")]
+  pub downgrade_error_message: Option<String>,
+}
+
+impl Default for WixUpgradeConfig {
+  fn default() -> Self {
+    Self {
+      allow_same_version_upgrades: true,
+      schedule: Default::default(),
+      downgrade_error_message: None,
+    }
+  }
+}
+
+/// When the installed product is removed during a major upgrade.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub enum WixUpgradeSchedule {
+  /// Remove the installed product before installing the new one, which is the slowest but safest option.
+  #[default]
+  AfterInstallInitialize,
+  /// Remove the installed product before the installation transaction starts.
+  AfterInstallValidate,
+  /// Remove the installed product after installing the new one, only updating the changed files.
+  ///
+  /// The component rules must be followed by the fragments for this to work.
+  AfterInstallExecute,
+  /// Remove the installed product after the installation transaction is committed.
+  ///
+  /// The component rules must be followed by the fragments for this to work.
+  AfterInstallFinalize,
+}
+
+/// A custom action running a command during the installation or uninstallation.
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct WixCustomAction {
+  /// The custom action id, which must be unique in the installer.
+  pub id: String,
+  /// The command to run from the installation directory,
+  /// which can reference installer properties such as `[INSTALLDIR]`.
+  pub command: String,
+  /// When to run the command.
+  #[serde(default)]
+  pub on: WixCustomActionStep,
+  /// A custom condition to run the command, replacing the default condition of the step.
+  pub condition: Option<String>,
+  /// Whether the command runs with the installer privileges instead of the user privileges.
+  #[serde(default)]
+  pub elevated: bool,
+  /// Whether the installation continues when the command fails.
+  #[serde(default, alias = "ignore-exit-code")]
+  pub ignore_exit_code: bool,
+}
+
+/// The installation step running a [`WixCustomAction`].
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub enum WixCustomActionStep {
+  /// After the files are installed, on installations, repairs and upgrades.
+  #[default]
+  Install,
+  /// Before the files are removed, when the app is uninstalled but not upgraded.
+  Uninstall,
+}
+
+/// A registry value written by the MSI installer.
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct WixRegistryValue {
+  /// The registry root.
+  #[serde(default)]
+  pub root: WixRegistryRoot,
+  /// The registry key, e.g. `Software\MyCompany\MyApp`.
+  pub key: String,
+  /// The value name. The default value of the key is written when not set.
+  pub name: Option<String>,
+  /// The value data, which can reference installer properties such as `[INSTALLDIR]`.
+  pub value: String,
+  /// The value type.
+  #[serde(default, rename = "type")]
+  pub value_type: WixRegistryValueType,
+}
+
+/// The root of a [`WixRegistryValue`].
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub enum WixRegistryRoot {
+  /// `HKEY_LOCAL_MACHINE` for per-machine installations and `HKEY_CURRENT_USER` for per-user installations.
+  #[default]
+  #[serde(rename = "HKMU")]
+  Hkmu,
+  /// `HKEY_LOCAL_MACHINE`.
+  #[serde(rename = "HKLM")]
+  Hklm,
+  /// `HKEY_CURRENT_USER`.
+  #[serde(rename = "HKCU")]
+  Hkcu,
+  /// `HKEY_CLASSES_ROOT`.
+  #[serde(rename = "HKCR")]
+  Hkcr,
+}
+
+/// The type of a [`WixRegistryValue`].
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub enum WixRegistryValueType {
+  /// A string value (`REG_SZ`).
+  #[default]
+  String,
+  /// A string value expanding environment variables (`REG_EXPAND_SZ`).
+  Expandable,
+  /// A 32-bit integer value (`REG_DWORD`).
+  Integer,
+}
+
+/// A Windows service installed by the MSI installer.
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct WixService {
+  /// The service name.
+  pub name: String,
+  /// The name of the service executable, which is the main binary,
+  /// an external binary or an additional project binary, without the `.exe` extension.
+  pub binary: String,
+  /// The service name displayed to users. Defaults to the service name.
+  #[serde(alias = "display-name")]
+  pub display_name: Option<String>,
+  /// The service description.
+  pub description: Option<String>,
+  /// The command line arguments passed to the service.
+  pub arguments: Option<String>,
+  /// How the service is started.
+  #[serde(default)]
+  pub start: WixServiceStart,
+  /// The account running the service. Defaults to `LocalSystem`.
+  pub account: Option<String>,
+}
+
+/// How a [`WixService`] is started.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub enum WixServiceStart {
+  /// Started at boot and after the installation.
+  #[default]
+  Auto,
+  /// Started on demand.
+  Demand,
+  /// Installed as disabled.
+  Disabled,
 }
 
 /// Compression algorithms used in the NSIS installer.
@@ -717,6 +1463,63 @@ impl Default for NsisCompression {
   }
 }
 
+/// Configuration for the MSIX package.
+///
+/// See more: <https://learn.microsoft.com/en-us/windows/msix/package/create-app-package-with-makeappx-tool>
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct MsixConfig {
+  /// The package identity name. Defaults to the bundle identifier.
+  ///
+  /// Must match the name reserved on the Microsoft Partner Center when publishing to the Microsoft Store.
+  #[serde(alias = "identity-name")]
+  pub identity_name: Option<String>,
+  /// The package publisher, e.g. `CN=Tauri Programme, O=Tauri Programme, C=NL`.
+  ///
+  /// Must match the subject of the signing certificate, or the publisher ID assigned by the Microsoft Partner Center.
+  pub publisher: String,
+  /// The publisher name displayed to users. Defaults to [`BundleConfig::publisher`] or the product name.
+  #[serde(alias = "publisher-display-name")]
+  pub publisher_display_name: Option<String>,
+  /// Additional capabilities declared by the package, e.g. `internetClient` or `webcam`.
+  ///
+  /// The `runFullTrust` restricted capability is always declared since Tauri apps are desktop apps.
+  #[serde(default)]
+  pub capabilities: Vec<String>,
+  /// The minimum Windows version required by the package. Defaults to `10.0.17763.0`.
+  #[serde(default = "default_msix_min_version", alias = "min-version")]
+  pub min_version: String,
+}
+
+fn default_msix_min_version() -> String {
+  "10.0.17763.0".into()
+}
+
+/// Configuration for the portable app archive.
+///
+/// The archive contains the app executable along its sidecars and resources,
+/// so it can be extracted and run without being installed, e.g. from a USB stick.
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct PortableConfig {
+  /// Whether the app stores its data in a `data` folder next to the executable
+  /// instead of the user directories. Defaults to `true`.
+  ///
+  /// See [`crate::platform::portable_dir`].
+  #[serde(default = "default_true", alias = "data-next-to-executable")]
+  pub data_next_to_executable: bool,
+}
+
+impl Default for PortableConfig {
+  fn default() -> Self {
+    Self {
+      data_next_to_executable: true,
+    }
+  }
+}
+
 /// Install Modes for the NSIS installer.
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
@@ -737,6 +1540,8 @@ pub enum NSISInstallerMode {
   /// whether to install for the current user or per machine. Note that this mode
   /// will require Administrator access even if the user wants to install it for the current user only.
   ///
+  /// Silent installations can choose with the `/CurrentUser` or `/AllUsers` flags.
+  ///
   /// Installer metadata will be saved under the `HKLM` or `HKCU` registry path based on the user's choice.
   Both,
 }
@@ -834,6 +1639,67 @@ pub struct NsisConfig {
   /// ```
   #[serde(alias = "installer-hooks")]
   pub installer_hooks: Option<PathBuf>,
+  /// Optional components the user can select on the installer components page.
+  ///
+  /// Each component installs a group of resources,
+  /// which are no longer installed unconditionally with the app.
+  #[serde(default)]
+  pub components: Vec<NsisComponent>,
+  /// Download the components on demand instead of embedding them in the installer.
+  ///
+  /// Each component is packaged as a `<product>_<version>_<arch>_<component id>.zip` archive
+  /// next to the installer, which must be uploaded to the configured URL.
+  #[serde(alias = "web-installer")]
+  pub web_installer: Option<NsisWebInstallerConfig>,
+  /// Paths to `.nsh` files defining custom installer pages,
+  /// included in order after the components page and before the install directory page.
+  ///
+  /// Each file must declare its page with the `Page custom` instruction along with its functions.
+  ///
+  /// ### Example
+  ///
+  /// ```nsh
+  /// Page custom TelemetryPage
+  /// Function TelemetryPage
+  ///   Call SkipIfPassive
+  ///   MessageBox MB_YESNO "Send anonymous usage statistics?" IDNO +2
+  ///   WriteRegStr SHCTX "${MANUPRODUCTKEY}" "Telemetry" "1"
+  /// FunctionEnd
+  /// ```
+  #[serde(default, alias = "custom-pages")]
+  pub custom_pages: Vec<PathBuf>,
+}
+
+/// An optional component of the NSIS installer.
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct NsisComponent {
+  /// The component identifier, used to name its web installer archive.
+  ///
+  /// Must only contain alphanumeric characters, `-` and `_`.
+  pub id: String,
+  /// The component name displayed on the components page.
+  pub name: String,
+  /// The component description displayed on the components page.
+  pub description: Option<String>,
+  /// The resources installed by this component,
+  /// as target paths relative to the resources directory or directories containing them.
+  #[serde(default)]
+  pub resources: Vec<PathBuf>,
+  /// Whether the component is selected by default. Defaults to `true`.
+  #[serde(default = "default_true")]
+  pub selected: bool,
+}
+
+/// Configuration for the NSIS web installer.
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct NsisWebInstallerConfig {
+  /// The base URL the component archives are downloaded from,
+  /// e.g. `https://releases.myapp.com/1.0.0`.
+  pub url: String,
 }
 
 /// Install modes for the Webview2 runtime.
@@ -887,6 +1753,62 @@ impl Default for WebviewInstallMode {
   }
 }
 
+/// The signer used to sign the Windows executables, libraries and installers.
+///
+/// Takes precedence over [`WindowsConfig::certificate_thumbprint`] and [`WindowsConfig::sign_command`].
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase", deny_unknown_fields)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub enum WindowsSignerConfig {
+  /// Sign with `signtool.exe`, using a certificate from the certificate store,
+  /// a certificate file or a key stored on a hardware security module (HSM).
+  #[serde(rename_all = "camelCase")]
+  Signtool {
+    /// The SHA1 hash of the signing certificate in the certificate store.
+    certificate_thumbprint: Option<String>,
+    /// The subject name of the signing certificate in the certificate store.
+    subject_name: Option<String>,
+    /// Path to the signing certificate file.
+    ///
+    /// Use the `.pfx` file when signing with a local key, its password is read from the `TAURI_WINDOWS_SIGNTOOL_PASSWORD` environment variable
+    /// and the certificate is imported into the certificate store of the user to sign with it.
+    /// Use the `.cer` file along with the `csp` and `keyContainer` options when the key is stored on a HSM.
+    certificate_file: Option<PathBuf>,
+    /// The cryptographic service provider (CSP) holding the private key, e.g. `eToken Base Cryptographic Provider`.
+    csp: Option<String>,
+    /// The name of the key container in the cryptographic service provider.
+    ///
+    /// HSM vendors commonly use the `[{{password}}]=container` syntax to unlock the key non-interactively.
+    key_container: Option<String>,
+    /// Path to an additional certificate to add to the signature block, usually the cross-signing certificate of the issuer.
+    additional_certificate: Option<PathBuf>,
+  },
+  /// Sign with [Azure Trusted Signing](https://learn.microsoft.com/en-us/azure/trusted-signing/) using `signtool.exe` and the Trusted Signing dlib.
+  ///
+  /// The Azure credentials are read from the environment, e.g. `AZURE_CLIENT_ID`, `AZURE_CLIENT_SECRET` and `AZURE_TENANT_ID`.
+  #[serde(rename_all = "camelCase")]
+  AzureTrustedSigning {
+    /// The Trusted Signing account endpoint, e.g. `https://eus.codesigning.azure.net`.
+    endpoint: String,
+    /// The Trusted Signing account name.
+    account: String,
+    /// The certificate profile name.
+    certificate_profile: String,
+    /// Path to the `Azure.CodeSigning.Dlib.dll` file, shipped with the `Microsoft.Trusted.Signing.Client` package.
+    dlib: PathBuf,
+    /// Identifier added to the signing requests, to correlate them with your own build records.
+    correlation_id: Option<String>,
+  },
+  /// Sign with an external command, such as `osslsigncode` or a vendor specific tool.
+  Command {
+    /// The program to run.
+    command: String,
+    /// The program arguments. The `%1` argument is replaced with the path of the file to sign.
+    #[serde(default)]
+    args: Vec<String>,
+  },
+}
+
 /// Windows bundler configuration.
 ///
 /// See more: <https://tauri.app/v1/api/config#windowsconfig>
@@ -930,6 +1852,11 @@ pub struct WindowsConfig {
   pub wix: Option<WixConfig>,
   /// Configuration for the installer generated with NSIS.
   pub nsis: Option<NsisConfig>,
+  /// Configuration for the MSIX package.
+  pub msix: Option<MsixConfig>,
+  /// Configuration for the portable app archive.
+  #[serde(default)]
+  pub portable: PortableConfig,
   /// Specify a custom command to sign the binaries.
   /// This command needs to have a `%1` in it which is just a placeholder for the binary path,
   /// which we will detect and replace before calling the command.
@@ -944,6 +1871,10 @@ pub struct WindowsConfig {
   /// need to use another tool like `osslsigncode`.
   #[serde(alias = "sign-command")]
   pub sign_command: Option<String>,
+  /// The signer used to sign the executables, sidecars, libraries and installers.
+  ///
+  /// Takes precedence over [`Self::certificate_thumbprint`] and [`Self::sign_command`].
+  pub signer: Option<WindowsSignerConfig>,
 }
 
 impl Default for WindowsConfig {
@@ -958,7 +1889,10 @@ impl Default for WindowsConfig {
       allow_downgrades: true,
       wix: None,
       nsis: None,
+      msix: None,
+      portable: Default::default(),
       sign_command: None,
+      signer: None,
     }
   }
 }
@@ -1167,6 +2101,12 @@ pub struct BundleConfig {
   /// so don't forget to provide binaries for all targeted platforms.
   #[serde(alias = "external-bin")]
   pub external_bin: Option<Vec<String>>,
+  /// Commands executed around the packaging steps, e.g. to use a signing service, scan or upload the artifacts.
+  #[serde(default)]
+  pub hooks: BundleHooksConfig,
+  /// The destinations of the artifacts uploaded by `tauri publish`, along with the updater manifest `latest.json`.
+  #[serde(default)]
+  pub publish: Vec<PublishTarget>,
   /// Configuration for the Windows bundles.
   #[serde(default)]
   pub windows: WindowsConfig,
@@ -1414,6 +2354,15 @@ pub struct WindowConfig {
   /// - **Android / iOS**: Unsupported.
   #[serde(default)]
   pub zoom_hotkeys_enabled: bool,
+  /// Draws the window content under the system bars and display cutouts, padding the webview body with the safe area insets.
+  ///
+  /// The insets are exposed to CSS as the `--safe-area-inset-top`, `--safe-area-inset-right`, `--safe-area-inset-bottom` and `--safe-area-inset-left` variables.
+  ///
+  /// ## Platform-specific:
+  ///
+  /// - **Linux / Windows / macOS**: The insets are always zero.
+  #[serde(default, alias = "edge-to-edge")]
+  pub edge_to_edge: bool,
 }
 
 impl Default for WindowConfig {
@@ -1460,6 +2409,7 @@ impl Default for WindowConfig {
       parent: None,
       proxy_url: None,
       zoom_hotkeys_enabled: false,
+      edge_to_edge: false,
     }
   }
 }
@@ -1819,6 +2769,18 @@ pub struct AppConfig {
   /// If set to true "identifier" will be set as GTK app ID (on systems that use GTK).
   #[serde(rename = "enableGTKAppId", alias = "enable-gtk-app-id", default)]
   pub enable_gtk_app_id: bool,
+  /// Loads plugins compiled as shared libraries at startup. Requires the `dynamic-plugins` Cargo feature.
+  #[serde(alias = "dynamic-plugins")]
+  pub dynamic_plugins: Option<DynamicPluginsConfig>,
+  /// Saves the open windows when the last window is closed or the app exits, and restores them on the next launch.
+  ///
+  /// The windows of `app > windows` are created first, the saved state is then applied to the windows with the same label.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Android / iOS:** Unsupported.
+  #[serde(default, alias = "restore-session")]
+  pub restore_session: bool,
 }
 
 impl AppConfig {
@@ -1829,6 +2791,8 @@ impl AppConfig {
       "macos-private-api",
       "protocol-asset",
       "isolation",
+      "dynamic-plugins",
+      "asset-packfile",
     ]
   }
 
@@ -1849,11 +2813,37 @@ impl AppConfig {
       features.push("isolation");
     }
 
+    if self.dynamic_plugins.is_some() {
+      features.push("dynamic-plugins");
+    }
+
     features.sort_unstable();
     features
   }
 }
 
+/// Configuration for the plugins compiled as shared libraries and loaded at startup.
+///
+/// The libraries must be built with the same Rust compiler and Tauri version as the app,
+/// export their plugin with `tauri::export_plugin!` and be signed with `tauri signer sign`.
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct DynamicPluginsConfig {
+  /// The directory of the plugin libraries, relative to the app data directory if not absolute.
+  #[serde(default = "default_dynamic_plugins_directory")]
+  pub directory: PathBuf,
+  /// The public key generated with `tauri signer generate`, verifying the `.sig` signature file next to each library.
+  ///
+  /// The libraries without a valid signature are not loaded.
+  pub pubkey: String,
+}
+
+fn default_dynamic_plugins_directory() -> PathBuf {
+  "plugins".into()
+}
+
 /// Configuration for application tray icon.
 ///
 /// See more: <https://tauri.app/v1/api/config#trayiconconfig>
@@ -1897,6 +2887,11 @@ pub struct IosConfig {
   /// The `APPLE_DEVELOPMENT_TEAM` environment variable can be set to overwrite it.
   #[serde(alias = "development-team")]
   pub development_team: Option<String>,
+  /// The associated domains of the app, e.g. `applinks:example.com` to open the app with the [universal links](https://developer.apple.com/documentation/xcode/supporting-universal-links-in-your-app) of `example.com`.
+  ///
+  /// The CLI adds them to the `com.apple.developer.associated-domains` entitlement.
+  #[serde(default, alias = "associated-domains")]
+  pub associated_domains: Vec<String>,
 }
 
 /// General configuration for the iOS target.
@@ -1918,6 +2913,28 @@ pub struct AndroidConfig {
   #[serde(alias = "version-code")]
   #[cfg_attr(feature = "schema", validate(range(min = 1, max = 2_100_000_000)))]
   pub version_code: Option<u32>,
+
+  /// The intent filters of the main activity, e.g. to open the app with deep links or [app links](https://developer.android.com/training/app-links).
+  ///
+  /// The CLI adds them to the `AndroidManifest.xml` file.
+  #[serde(default, alias = "intent-filters")]
+  pub intent_filters: Vec<AndroidIntentFilter>,
+
+  /// Registers the app as a target of the Android share sheet.
+  #[serde(alias = "share-target")]
+  pub share_target: Option<AndroidShareTarget>,
+
+  /// The signing configuration of the release APKs and AABs.
+  ///
+  /// The release builds are unsigned when it is not set.
+  pub signing: Option<AndroidSigningConfig>,
+
+  /// The [Play Asset Delivery](https://developer.android.com/guide/playcore/asset-delivery) packs,
+  /// delivering large resources separately from the base APK of the AABs.
+  ///
+  /// The packs are installed with the app, the fast-follow and on-demand deliveries are not supported.
+  #[serde(default, alias = "asset-packs")]
+  pub asset_packs: Vec<AndroidAssetPack>,
 }
 
 impl Default for AndroidConfig {
@@ -1925,6 +2942,10 @@ impl Default for AndroidConfig {
     Self {
       min_sdk_version: default_min_sdk_version(),
       version_code: None,
+      intent_filters: Vec::new(),
+      share_target: None,
+      signing: None,
+      asset_packs: Vec::new(),
     }
   }
 }
@@ -1933,6 +2954,101 @@ fn default_min_sdk_version() -> u32 {
   24
 }
 
+/// An [intent filter](https://developer.android.com/guide/components/intents-filters) of the main activity.
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct AndroidIntentFilter {
+  /// The action of the intents, `android.intent.action.VIEW` by default.
+  #[serde(default = "default_intent_filter_action")]
+  pub action: String,
+  /// The categories of the intents, `android.intent.category.DEFAULT` and `android.intent.category.BROWSABLE` by default.
+  #[serde(default = "default_intent_filter_categories")]
+  pub categories: Vec<String>,
+  /// The data the intents must match, e.g. a scheme and host.
+  #[serde(default)]
+  pub data: Vec<AndroidIntentFilterData>,
+  /// Whether the system verifies the app links of the `http` and `https` schemes with the `assetlinks.json` file of the hosts.
+  #[serde(default, alias = "auto-verify")]
+  pub auto_verify: bool,
+}
+
+fn default_intent_filter_action() -> String {
+  "android.intent.action.VIEW".into()
+}
+
+fn default_intent_filter_categories() -> Vec<String> {
+  vec![
+    "android.intent.category.DEFAULT".into(),
+    "android.intent.category.BROWSABLE".into(),
+  ]
+}
+
+/// The `<data>` element of an [`AndroidIntentFilter`].
+#[skip_serializing_none]
+#[derive(Debug, Default, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct AndroidIntentFilterData {
+  /// The URI scheme, e.g. `https` or a custom scheme.
+  pub scheme: Option<String>,
+  /// The URI host.
+  pub host: Option<String>,
+  /// The prefix of the URI path.
+  #[serde(alias = "path-prefix")]
+  pub path_prefix: Option<String>,
+  /// The pattern of the URI path.
+  #[serde(alias = "path-pattern")]
+  pub path_pattern: Option<String>,
+  /// The MIME type, e.g. `image/*`.
+  #[serde(alias = "mime-type")]
+  pub mime_type: Option<String>,
+}
+
+/// The content an app receives from the Android share sheet.
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct AndroidShareTarget {
+  /// The MIME types of the shared content, e.g. `text/plain` or `image/*`.
+  #[serde(alias = "mime-types")]
+  pub mime_types: Vec<String>,
+  /// Whether several files can be shared at once.
+  #[serde(default)]
+  pub multiple: bool,
+}
+
+/// The keystore signing the release APKs and AABs.
+///
+/// The passwords are read from the `TAURI_ANDROID_KEYSTORE_PASSWORD` and `TAURI_ANDROID_KEY_PASSWORD` environment variables,
+/// the key password defaulting to the keystore password.
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct AndroidSigningConfig {
+  /// The path of the keystore, relative to the `src-tauri` folder.
+  #[serde(alias = "key-store")]
+  pub key_store: PathBuf,
+  /// The alias of the signing key in the keystore.
+  #[serde(alias = "key-alias")]
+  pub key_alias: String,
+}
+
+/// A [Play Asset Delivery](https://developer.android.com/guide/playcore/asset-delivery) pack.
+///
+/// The packs use the install-time delivery: they are downloaded with the app,
+/// and their resources are read like the other resources of the app.
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct AndroidAssetPack {
+  /// The name of the pack, also the name of its Gradle module.
+  pub name: String,
+  /// The resources moved to the pack, each one an entry of `bundle > resources`.
+  pub resources: Vec<String>,
+}
+
 /// Defines the URL or assets to embed in the application.
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
 #[cfg_attr(feature = "schema", derive(JsonSchema))]
@@ -1976,6 +3092,91 @@ pub enum BeforeDevCommand {
   },
 }
 
+/// Commands executed by the bundler around the packaging steps.
+///
+/// The commands run with the same environment variables as the other CLI hooks.
+#[skip_serializing_none]
+#[derive(Debug, Default, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct BundleHooksConfig {
+  /// A command to run before the bundles are packaged.
+  #[serde(alias = "pre-package")]
+  pub pre_package: Option<HookCommand>,
+  /// A command to run for each generated artifact, before the updater artifacts are signed.
+  ///
+  /// The artifact path and bundle type are set in the `TAURI_BUNDLE_ARTIFACT_PATH`
+  /// and `TAURI_BUNDLE_TYPE` environment variables.
+  pub artifact: Option<HookCommand>,
+  /// A command to run after all bundles are packaged.
+  ///
+  /// The artifact paths are set in the `TAURI_BUNDLE_ARTIFACT_PATHS` environment variable, separated by the platform path separator.
+  #[serde(alias = "post-package")]
+  pub post_package: Option<HookCommand>,
+}
+
+/// A destination of the artifacts uploaded by `tauri publish`.
+///
+/// The credentials are read from environment variables so they are never stored in the configuration.
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(tag = "provider")]
+pub enum PublishTarget {
+  /// A GitHub release, created if it does not exist yet.
+  ///
+  /// The API token is read from the `GITHUB_TOKEN` environment variable.
+  #[serde(rename = "github", rename_all = "camelCase")]
+  GitHub {
+    /// The owner of the repository.
+    owner: String,
+    /// The repository name.
+    repo: String,
+    /// The release tag, where `{version}` is replaced with the app version. Defaults to `v{version}`.
+    tag: Option<String>,
+    /// Whether the release is created as a draft, to review it before publishing it.
+    #[serde(default)]
+    draft: bool,
+    /// Whether the release is created as a pre-release.
+    #[serde(default)]
+    prerelease: bool,
+  },
+  /// An S3 bucket, or a bucket of an S3-compatible storage such as Cloudflare R2 or MinIO.
+  ///
+  /// The credentials are read from the `AWS_ACCESS_KEY_ID`, `AWS_SECRET_ACCESS_KEY` and optional `AWS_SESSION_TOKEN` environment variables.
+  #[serde(rename = "s3", rename_all = "camelCase")]
+  S3 {
+    /// The bucket name.
+    bucket: String,
+    /// The bucket region. Defaults to `us-east-1`.
+    region: Option<String>,
+    /// The endpoint of the S3-compatible storage, e.g. `https://<account>.r2.cloudflarestorage.com`.
+    /// Defaults to the AWS endpoint of the region.
+    endpoint: Option<Url>,
+    /// The prefix of the object keys, where `{version}` is replaced with the app version.
+    prefix: Option<String>,
+    /// The URL the files are downloaded from, e.g. a CDN in front of the bucket. Defaults to the object URLs.
+    #[serde(alias = "public-url")]
+    public_url: Option<Url>,
+  },
+  /// A server receiving each file with an HTTP request to `<url>/<file name>`.
+  ///
+  /// The `TAURI_PUBLISH_TOKEN` environment variable is sent as bearer token if set.
+  #[serde(rename = "http", rename_all = "camelCase")]
+  Http {
+    /// The URL the files are uploaded to.
+    url: Url,
+    /// The HTTP method of the upload requests. Defaults to `PUT`.
+    method: Option<String>,
+    /// Additional headers of the upload requests.
+    #[serde(default)]
+    headers: HashMap<String, String>,
+    /// The URL the files are downloaded from. Defaults to the upload URL.
+    #[serde(alias = "public-url")]
+    public_url: Option<Url>,
+  },
+}
+
 /// Describes a shell command to be executed when a CLI hook is triggered.
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
 #[cfg_attr(feature = "schema", derive(JsonSchema))]
@@ -2043,6 +3244,169 @@ pub struct BuildConfig {
   pub before_bundle_command: Option<HookCommand>,
   /// Features passed to `cargo` commands.
   pub features: Option<Vec<String>>,
+  /// Routes forwarded by the dev server to another server such as an API backend, keyed by path prefix,
+  /// e.g. `{ "/api": "http://localhost:8080" }`. WebSocket connections are forwarded too.
+  ///
+  /// With [`devUrl`](BuildConfig::dev_url), the CLI runs its built-in dev server as a proxy in front of it,
+  /// forwarding the other requests and the HMR WebSocket connections to your dev server.
+  #[serde(default, alias = "dev-proxy")]
+  pub dev_proxy: HashMap<String, DevProxyRule>,
+  /// Serve the app over HTTPS in development, as required by APIs such as WebAuthn.
+  ///
+  /// Set it to `true` to use a self-signed certificate for `localhost` generated by the CLI,
+  /// which must be trusted by your system for the webview to load the app,
+  /// or set the `cert` and `key` paths of your own certificate, e.g. generated by `mkcert`.
+  ///
+  /// With [`devUrl`](BuildConfig::dev_url), the built-in dev server runs as an HTTPS proxy in front of it.
+  #[serde(alias = "dev-https")]
+  pub dev_https: Option<DevHttpsConfig>,
+  /// Cache the compiled Rust crates with [sccache](https://github.com/mozilla/sccache),
+  /// so the apps of a workspace and the CI runs share the dependencies compiled for every profile and target.
+  ///
+  /// Set it to `true` to use the `sccache` binary from `PATH`, storing the cache locally or remotely
+  /// as configured by the `SCCACHE_*` environment variables, e.g. `SCCACHE_GHA_ENABLED` on GitHub Actions
+  /// or `SCCACHE_BUCKET` for S3. Can also be enabled with the `TAURI_BUILD_CACHE` environment variable.
+  ///
+  /// Ignored when the `RUSTC_WRAPPER` environment variable is set.
+  pub cache: Option<BuildCacheConfig>,
+  /// The environment variables that can be referenced as `${NAME}` in the configuration string values,
+  /// e.g. `"productName": "${CUSTOMER_NAME} App"`. A name ending with `*` allows every variable with that prefix.
+  ///
+  /// `${NAME:-default}` uses a default value when the variable is not set, and `$${` writes a literal `${`.
+  /// Referencing a variable that is not allowed is an error, so secrets can't be embedded in the app by mistake.
+  #[serde(default, alias = "interpolate-env")]
+  pub interpolate_env: Vec<String>,
+  /// How the frontend assets are stored in the app, embedded in the executable by default.
+  #[serde(default, alias = "asset-storage")]
+  pub asset_storage: AssetStorage,
+}
+
+/// How the frontend assets are stored in the app.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub enum AssetStorage {
+  /// The assets are embedded in the executable data.
+  #[default]
+  Embedded,
+  /// The assets are written to a `<identifier>.pak` packfile bundled as a resource,
+  /// which is memory-mapped when an asset is first requested so large frontends don't grow the app resident memory.
+  ///
+  /// Only supported on desktop, the assets are embedded on mobile.
+  Packfile,
+}
+
+/// A route forwarded by the dev server.
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(untagged)]
+pub enum DevProxyRule {
+  /// The URL of the server the requests are forwarded to.
+  Target(Url),
+  /// The proxy options.
+  #[serde(rename_all = "camelCase")]
+  Options {
+    /// The URL of the server the requests are forwarded to.
+    target: Url,
+    /// The path replacing the matched prefix, e.g. `""` to forward `/api/users` to `<target>/users`.
+    /// Defaults to the prefix itself.
+    rewrite: Option<String>,
+    /// Whether the `Host` header is set to the target host. Defaults to `true`.
+    #[serde(default = "default_true", alias = "change-origin")]
+    change_origin: bool,
+  },
+}
+
+impl DevProxyRule {
+  /// The URL of the server the requests are forwarded to.
+  pub fn target(&self) -> &Url {
+    match self {
+      Self::Target(target) | Self::Options { target, .. } => target,
+    }
+  }
+
+  /// The path replacing the matched prefix.
+  pub fn rewrite(&self) -> Option<&str> {
+    match self {
+      Self::Target(_) => None,
+      Self::Options { rewrite, .. } => rewrite.as_deref(),
+    }
+  }
+
+  /// Whether the `Host` header is set to the target host.
+  pub fn change_origin(&self) -> bool {
+    match self {
+      Self::Target(_) => true,
+      Self::Options { change_origin, .. } => *change_origin,
+    }
+  }
+}
+
+/// The HTTPS configuration of the dev server.
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(untagged)]
+pub enum DevHttpsConfig {
+  /// Whether HTTPS is enabled, using a self-signed certificate generated by the CLI.
+  Enabled(bool),
+  /// The certificate to use.
+  Certificate {
+    /// The path to the PEM encoded certificate chain.
+    cert: PathBuf,
+    /// The path to the PEM encoded private key.
+    key: PathBuf,
+  },
+}
+
+impl DevHttpsConfig {
+  /// Whether HTTPS is enabled.
+  pub fn enabled(&self) -> bool {
+    !matches!(self, Self::Enabled(false))
+  }
+}
+
+/// The Rust build cache configuration.
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(untagged)]
+pub enum BuildCacheConfig {
+  /// Whether the build cache is enabled.
+  Enabled(bool),
+  /// The build cache options.
+  #[serde(rename_all = "camelCase")]
+  Options {
+    /// The path to the `sccache` binary, relative to the Tauri directory. Defaults to `sccache` from `PATH`.
+    ///
+    /// A bare binary name, e.g. `sccache-dist`, is looked up on `PATH`.
+    #[serde(alias = "sccache-path")]
+    sccache_path: Option<PathBuf>,
+    /// The directory of the local cache, relative to the Tauri directory.
+    /// Defaults to the `SCCACHE_DIR` environment variable or the sccache default directory.
+    dir: Option<PathBuf>,
+  },
+}
+
+impl BuildCacheConfig {
+  /// Whether the build cache is enabled.
+  pub fn enabled(&self) -> bool {
+    !matches!(self, Self::Enabled(false))
+  }
+
+  /// The path to the `sccache` binary.
+  pub fn sccache_path(&self) -> Option<&Path> {
+    match self {
+      Self::Enabled(_) => None,
+      Self::Options { sccache_path, .. } => sccache_path.as_deref(),
+    }
+  }
+
+  /// The directory of the local cache.
+  pub fn dir(&self) -> Option<&Path> {
+    match self {
+      Self::Enabled(_) => None,
+      Self::Options { dir, .. } => dir.as_deref(),
+    }
+  }
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -2225,6 +3589,11 @@ fn default_build() -> BuildConfig {
     before_build_command: None,
     before_bundle_command: None,
     features: None,
+    dev_proxy: Default::default(),
+    dev_https: None,
+    cache: None,
+    interpolate_env: Vec::new(),
+    asset_storage: AssetStorage::Embedded,
   }
 }
 
@@ -2402,6 +3771,7 @@ mod build {
       let incognito = self.incognito;
       let parent = opt_str_lit(self.parent.as_ref());
       let zoom_hotkeys_enabled = self.zoom_hotkeys_enabled;
+      let edge_to_edge = self.edge_to_edge;
 
       literal_struct!(
         tokens,
@@ -2446,7 +3816,8 @@ mod build {
         window_effects,
         incognito,
         parent,
-        zoom_hotkeys_enabled
+        zoom_hotkeys_enabled,
+        edge_to_edge
       );
     }
   }
@@ -2523,6 +3894,8 @@ mod build {
       let short_description = quote!(None);
       let long_description = quote!(None);
       let external_bin = opt_vec_lit(self.external_bin.as_ref(), str_lit);
+      let hooks = quote!(Default::default());
+      let publish = quote!(Default::default());
       let windows = &self.windows;
       let license = opt_str_lit(self.license.as_ref());
       let license_file = opt_lit(self.license_file.as_ref().map(path_buf_lit).as_ref());
@@ -2549,6 +3922,8 @@ mod build {
         short_description,
         long_description,
         external_bin,
+        hooks,
+        publish,
         windows,
         linux,
         macos,
@@ -2588,6 +3963,11 @@ mod build {
       let before_build_command = quote!(None);
       let before_bundle_command = quote!(None);
       let features = quote!(None);
+      let dev_proxy = quote!(Default::default());
+      let dev_https = quote!(None);
+      let cache = quote!(None);
+      let interpolate_env = quote!(Default::default());
+      let asset_storage = quote!(Default::default());
 
       literal_struct!(
         tokens,
@@ -2598,7 +3978,12 @@ mod build {
         before_dev_command,
         before_build_command,
         before_bundle_command,
-        features
+        features,
+        dev_proxy,
+        dev_https,
+        cache,
+        interpolate_env,
+        asset_storage
       );
     }
   }
@@ -2753,6 +4138,8 @@ mod build {
       let macos_private_api = self.macos_private_api;
       let with_global_tauri = self.with_global_tauri;
       let enable_gtk_app_id = self.enable_gtk_app_id;
+      let dynamic_plugins = opt_lit(self.dynamic_plugins.as_ref());
+      let restore_session = self.restore_session;
 
       literal_struct!(
         tokens,
@@ -2762,7 +4149,23 @@ mod build {
         tray_icon,
         macos_private_api,
         with_global_tauri,
-        enable_gtk_app_id
+        enable_gtk_app_id,
+        dynamic_plugins,
+        restore_session
+      );
+    }
+  }
+
+  impl ToTokens for DynamicPluginsConfig {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+      let directory = path_buf_lit(&self.directory);
+      let pubkey = str_lit(&self.pubkey);
+
+      literal_struct!(
+        tokens,
+        ::tauri::utils::config::DynamicPluginsConfig,
+        directory,
+        pubkey
       );
     }
   }
@@ -2840,6 +4243,8 @@ mod test {
       macos_private_api: false,
       with_global_tauri: false,
       enable_gtk_app_id: false,
+      dynamic_plugins: None,
+      restore_session: false,
     };
 
     // create a build config
@@ -2851,6 +4256,11 @@ mod test {
       before_build_command: None,
       before_bundle_command: None,
       features: None,
+      dev_proxy: Default::default(),
+      dev_https: None,
+      cache: None,
+      interpolate_env: Vec::new(),
+      asset_storage: AssetStorage::Embedded,
     };
 
     // create a bundle config
@@ -2872,6 +4282,8 @@ mod test {
       linux: Default::default(),
       macos: Default::default(),
       external_bin: None,
+      hooks: Default::default(),
+      publish: Default::default(),
       windows: Default::default(),
       ios: Default::default(),
       android: Default::default(),
@@ -2883,4 +4295,27 @@ mod test {
     assert_eq!(d_bundle, bundle);
     assert_eq!(d_windows, app.windows);
   }
+
+  #[test]
+  fn linux_package_relationships() {
+    assert!(is_valid_deb_relationship("libc6"));
+    assert!(is_valid_deb_relationship("libc6 (>= 2.31)"));
+    assert!(is_valid_deb_relationship("python3:any (>=3.8)"));
+    assert!(!is_valid_deb_relationship("libc6 >= 2.31"));
+    assert!(!is_valid_deb_relationship("libc6 (> 2.31)"));
+    assert!(!is_valid_deb_relationship("LibC6"));
+
+    assert_eq!(parse_rpm_dependency("openssl"), Some(("openssl", None)));
+    assert_eq!(
+      parse_rpm_dependency("webkit2gtk4.1 >= 2.40"),
+      Some(("webkit2gtk4.1", Some((">=", "2.40"))))
+    );
+    assert_eq!(parse_rpm_dependency("openssl >> 3"), None);
+
+    let mut linux = LinuxConfig::default();
+    linux.deb.depends = Some(vec!["libgtk-3-0 | libgtk-4-1".into()]);
+    linux.deb.provides = Some(vec!["app | other".into()]);
+    linux.rpm.conflicts = Some(vec!["app-legacy < 2.0".into(), "app >=".into()]);
+    assert_eq!(linux.validate().len(), 2);
+  }
 }
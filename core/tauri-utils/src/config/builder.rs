@@ -0,0 +1,137 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::path::{Path, PathBuf};
+
+use json_patch::merge;
+use serde_json::Value;
+
+use super::{
+  parse::{self, ConfigError},
+  Config,
+};
+use crate::platform::Target;
+
+/// Builds a [`Config`] programmatically, e.g. in a build script to patch the app configuration.
+///
+/// The configuration is kept as JSON so partial configurations can be merged with [JSON Merge Patch (RFC 7396)]
+/// the same way as the platform-specific configuration files, while [`ConfigBuilder::update`] edits it with type safety.
+///
+/// ```
+/// use tauri_utils::config::ConfigBuilder;
+///
+/// let config = ConfigBuilder::from_value(serde_json::json!({ "identifier": "com.tauri.dev" }))
+///   .merge(&serde_json::json!({ "productName": "Tauri App" }))
+///   .update(|config| config.version = Some("1.0.0".into()))
+///   .unwrap()
+///   .build()
+///   .unwrap();
+/// assert_eq!(config.product_name.as_deref(), Some("Tauri App"));
+/// assert_eq!(config.version.as_deref(), Some("1.0.0"));
+/// ```
+///
+/// [JSON Merge Patch (RFC 7396)]: https://datatracker.ietf.org/doc/html/rfc7396
+#[derive(Debug, Clone)]
+pub struct ConfigBuilder {
+  config: Value,
+}
+
+impl ConfigBuilder {
+  /// Starts from a configuration value, e.g. built with [`serde_json::json!`].
+  pub fn from_value(config: Value) -> Self {
+    Self { config }
+  }
+
+  /// Starts from an existing configuration.
+  pub fn from_config(config: &Config) -> Result<Self, ConfigError> {
+    serde_json::to_value(config)
+      .map(Self::from_value)
+      .map_err(ConfigError::Invalid)
+  }
+
+  /// Starts from the configuration files of the given directory, in any enabled format.
+  ///
+  /// See [`parse::read_from`] for the files that are read.
+  pub fn from_dir(target: Target, root_dir: impl Into<PathBuf>) -> Result<Self, ConfigError> {
+    parse::read_from(target, root_dir.into()).map(Self::from_value)
+  }
+
+  /// Merges a partial configuration.
+  #[must_use]
+  pub fn merge(mut self, patch: &Value) -> Self {
+    merge(&mut self.config, patch);
+    self
+  }
+
+  /// Merges a partial configuration file, in any enabled format based on its extension.
+  pub fn merge_file(self, path: impl AsRef<Path>) -> Result<Self, ConfigError> {
+    let patch = parse::parse_file_value(path.as_ref())?;
+    Ok(self.merge(&patch))
+  }
+
+  /// Merges the configuration of the `TAURI_CONFIG` environment variable if set, as the CLI does with its `--config` option.
+  pub fn merge_env(self) -> Result<Self, ConfigError> {
+    match std::env::var("TAURI_CONFIG") {
+      Ok(patch) => {
+        let patch = serde_json::from_str(&patch).map_err(|error| ConfigError::FormatJson {
+          path: "TAURI_CONFIG".into(),
+          error,
+        })?;
+        Ok(self.merge(&patch))
+      }
+      Err(_) => Ok(self),
+    }
+  }
+
+  /// Edits the configuration with the [`Config`] structure.
+  pub fn update<F: FnOnce(&mut Config)>(self, f: F) -> Result<Self, ConfigError> {
+    let mut config = self.build()?;
+    f(&mut config);
+    Self::from_config(&config)
+  }
+
+  /// The configuration as JSON.
+  pub fn value(&self) -> &Value {
+    &self.config
+  }
+
+  /// Deserializes the configuration.
+  ///
+  /// Note that a `version` pointing to a `package.json` file is resolved from the current directory.
+  pub fn build(&self) -> Result<Config, ConfigError> {
+    serde_json::from_value(self.config.clone()).map_err(ConfigError::Invalid)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn merge_and_update() {
+    let builder = ConfigBuilder::from_value(serde_json::json!({
+      "identifier": "com.tauri.dev",
+      "build": { "devUrl": "http://localhost:1420" },
+    }))
+    .merge(&serde_json::json!({ "build": { "devUrl": null, "features": ["custom"] } }));
+    assert_eq!(
+      builder.value()["build"],
+      serde_json::json!({ "features": ["custom"] })
+    );
+
+    let config = builder
+      .update(|config| config.identifier = "com.tauri.staging".into())
+      .unwrap()
+      .build()
+      .unwrap();
+    assert_eq!(config.identifier, "com.tauri.staging");
+    assert_eq!(config.build.features, Some(vec!["custom".into()]));
+
+    assert!(matches!(
+      ConfigBuilder::from_value(serde_json::json!({ "productName": ["not", "a", "string"] }))
+        .build(),
+      Err(ConfigError::Invalid(_))
+    ));
+  }
+}
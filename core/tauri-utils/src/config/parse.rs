@@ -18,6 +18,9 @@ pub const EXTENSIONS_SUPPORTED: &[&str] = &["json", "json5", "toml"];
 pub const SUPPORTED_FORMATS: &[ConfigFormat] =
   &[ConfigFormat::Json, ConfigFormat::Json5, ConfigFormat::Toml];
 
+/// The environment variable selecting the configuration profile merged by [`read_from`], set by the `--profile` option of the CLI.
+pub const PROFILE_ENV: &str = "TAURI_CONFIG_PROFILE";
+
 /// All configuration formats that are currently enabled.
 pub const ENABLED_FORMATS: &[ConfigFormat] = &[
   ConfigFormat::Json,
@@ -73,6 +76,14 @@ impl ConfigFormat {
       },
     }
   }
+
+  fn into_profile_file_name(self, profile: &str) -> String {
+    match self {
+      Self::Json => format!("tauri.conf.{profile}.json"),
+      Self::Json5 => format!("tauri.conf.{profile}.json5"),
+      Self::Toml => format!("Tauri.{profile}.toml"),
+    }
+  }
 }
 
 /// Represents all the errors that can happen while reading the config.
@@ -125,6 +136,24 @@ pub enum ConfigError {
     feature: String,
   },
 
+  /// The profile name can't be used in a file name.
+  #[error("invalid config profile `{0}`, it can only contain letters, digits, `-` and `_`")]
+  InvalidProfile(String),
+
+  /// Failed to replace an environment variable reference.
+  #[error("unable to interpolate `${{{variable}}}` in the Tauri config because {reason}")]
+  Interpolation {
+    /// The referenced environment variable.
+    variable: String,
+
+    /// Why the reference can't be replaced.
+    reason: String,
+  },
+
+  /// The configuration does not match the [`Config`] structure.
+  #[error("invalid Tauri config: {0}")]
+  Invalid(serde_json::Error),
+
   /// A generic IO error with context of what caused it.
   #[error("unable to read Tauri config file at {path} because {error}")]
   Io {
@@ -163,6 +192,18 @@ pub fn is_configuration_file(target: Target, path: &Path) -> bool {
     .unwrap_or_default()
 }
 
+/// Determines if the given file path represents the configuration file of the profile.
+pub fn is_profile_configuration_file(profile: &str, path: &Path) -> bool {
+  path
+    .file_name()
+    .map(|file_name| {
+      SUPPORTED_FORMATS
+        .iter()
+        .any(|format| file_name == OsStr::new(&format.into_profile_file_name(profile)))
+    })
+    .unwrap_or_default()
+}
+
 /// Reads the configuration from the given root directory.
 ///
 /// It first looks for a `tauri.conf.json[5]` or `Tauri.toml` file on the given directory. The file must exist.
@@ -172,17 +213,315 @@ pub fn is_configuration_file(target: Target, path: &Path) -> bool {
 /// - `tauri.windows.conf.json[5]` or `Tauri.windows.toml` on Windows
 /// - `tauri.android.conf.json[5]` or `Tauri.android.toml` on Android
 /// - `tauri.ios.conf.json[5]` or `Tauri.ios.toml` on iOS
-///   Merging the configurations using [JSON Merge Patch (RFC 7396)].
+///
+/// When the [`PROFILE_ENV`] environment variable is set, the `tauri.conf.<profile>.json[5]` or `Tauri.<profile>.toml`
+/// file is merged last, see [`read_profile`].
+///
+/// Merging the configurations using [JSON Merge Patch (RFC 7396)].
+/// The environment variables are then interpolated, see [`interpolate_env`].
 ///
 /// [JSON Merge Patch (RFC 7396)]: https://datatracker.ietf.org/doc/html/rfc7396.
 pub fn read_from(target: Target, root_dir: PathBuf) -> Result<Value, ConfigError> {
   let mut config: Value = parse_value(target, root_dir.join("tauri.conf.json"))?.0;
-  if let Some((platform_config, _)) = read_platform(target, root_dir)? {
+  if let Some((platform_config, _)) = read_platform(target, root_dir.clone())? {
     merge(&mut config, &platform_config);
   }
+  if let Some(profile) = std::env::var(PROFILE_ENV).ok().filter(|p| !p.is_empty()) {
+    merge(&mut config, &read_profile(&profile, &root_dir)?.0);
+  }
+  interpolate_env(&mut config)?;
   Ok(config)
 }
 
+/// Reads the configuration file of the profile from the given root directory,
+/// `tauri.conf.<profile>.json[5]` or `Tauri.<profile>.toml`. The file must exist.
+///
+/// Profiles hold the configuration of a customer or an environment, e.g. `tauri.conf.staging.json`,
+/// merged on top of the base and platform-specific configurations.
+pub fn read_profile(profile: &str, root_dir: &Path) -> Result<(Value, PathBuf), ConfigError> {
+  if profile.is_empty()
+    || !profile
+      .chars()
+      .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+  {
+    return Err(ConfigError::InvalidProfile(profile.into()));
+  }
+
+  for format in ENABLED_FORMATS {
+    let path = root_dir.join(format.into_profile_file_name(profile));
+    if path.exists() {
+      return parse_file_value(&path).map(|config| (config, path));
+    }
+  }
+
+  Err(ConfigError::Io {
+    path: root_dir.join(ConfigFormat::Json.into_profile_file_name(profile)),
+    error: std::io::ErrorKind::NotFound.into(),
+  })
+}
+
+/// Parses a configuration file of any enabled format, based on its extension, into a JSON [`Value`].
+///
+/// Unlike [`parse_value`], the file does not have to use one of the Tauri configuration file names,
+/// so it can be a partial configuration merged on top of the app configuration.
+pub fn parse_file_value(path: &Path) -> Result<Value, ConfigError> {
+  let extension = path
+    .extension()
+    .map(OsStr::to_string_lossy)
+    .unwrap_or_default();
+  match extension.as_ref() {
+    "json" => {
+      let raw = read_to_string(path)?;
+      #[allow(clippy::let_and_return)]
+      let json = do_parse_json(&raw, path);
+      // same as the app configuration, valid JSON5 is supported in the .json extension
+      #[cfg(feature = "config-json5")]
+      let json = json.or_else(|error| do_parse_json5(&raw, path).map_err(|_| error));
+      json
+    }
+    #[cfg(feature = "config-json5")]
+    "json5" => do_parse_json5(&read_to_string(path)?, path),
+    #[cfg(feature = "config-toml")]
+    "toml" => {
+      #[allow(unused_mut)]
+      let mut config = do_parse_toml(&read_to_string(path)?, path)?;
+      #[cfg(feature = "schema")]
+      normalize_keys(&mut config);
+      Ok(config)
+    }
+    #[cfg(not(feature = "config-json5"))]
+    "json5" => Err(ConfigError::DisabledFormat {
+      extension: ".json5".into(),
+      feature: "config-json5".into(),
+    }),
+    #[cfg(not(feature = "config-toml"))]
+    "toml" => Err(ConfigError::DisabledFormat {
+      extension: ".toml".into(),
+      feature: "config-toml".into(),
+    }),
+    _ => Err(ConfigError::UnsupportedFormat(extension.to_string())),
+  }
+}
+
+/// Renames the kebab-case keys of a TOML configuration to the camelCase keys of the JSON configuration,
+/// e.g. `dev-url` to `devUrl`, so the configurations of different formats can be merged and validated against the schema.
+///
+/// Only the keys of the configuration structures are renamed, the keys of maps such as `plugins` are kept.
+#[cfg(feature = "schema")]
+pub fn normalize_keys(config: &mut Value) {
+  static SCHEMA: std::sync::OnceLock<Value> = std::sync::OnceLock::new();
+  let schema =
+    SCHEMA.get_or_init(|| serde_json::to_value(schemars::schema_for!(Config)).unwrap_or_default());
+  let definitions = schema.get("definitions").unwrap_or(&Value::Null);
+  normalize_value(config, schema, definitions);
+}
+
+#[cfg(feature = "schema")]
+fn normalize_value(value: &mut Value, schema: &Value, definitions: &Value) {
+  let mut schemas = Vec::new();
+  collect_schemas(schema, definitions, &mut schemas);
+
+  match value {
+    Value::Object(map) => {
+      let property_schema = |key: &str| {
+        schemas
+          .iter()
+          .find_map(|schema| schema.get("properties")?.get(key))
+      };
+      let additional_properties = schemas.iter().find_map(|schema| {
+        schema
+          .get("additionalProperties")
+          .filter(|schema| schema.is_object())
+      });
+
+      for key in map.keys().cloned().collect::<Vec<_>>() {
+        let (key, key_schema) = match property_schema(&key) {
+          Some(key_schema) => (key, Some(key_schema)),
+          None => {
+            let camel_case = kebab_to_camel_case(&key);
+            match property_schema(&camel_case) {
+              Some(key_schema) if camel_case != key && !map.contains_key(&camel_case) => {
+                let value = map.remove(&key).unwrap();
+                map.insert(camel_case.clone(), value);
+                (camel_case, Some(key_schema))
+              }
+              _ => (key, additional_properties),
+            }
+          }
+        };
+        if let (Some(key_schema), Some(value)) = (key_schema, map.get_mut(&key)) {
+          normalize_value(value, key_schema, definitions);
+        }
+      }
+    }
+    Value::Array(values) => {
+      if let Some(items) = schemas
+        .iter()
+        .find_map(|schema| schema.get("items").filter(|items| items.is_object()))
+      {
+        for value in values {
+          normalize_value(value, items, definitions);
+        }
+      }
+    }
+    _ => {}
+  }
+}
+
+/// Collects the schema along with the schemas it references or combines.
+#[cfg(feature = "schema")]
+fn collect_schemas<'a>(schema: &'a Value, definitions: &'a Value, schemas: &mut Vec<&'a Value>) {
+  if let Some(definition) = schema
+    .get("$ref")
+    .and_then(Value::as_str)
+    .and_then(|reference| reference.strip_prefix("#/definitions/"))
+    .and_then(|name| definitions.get(name))
+  {
+    collect_schemas(definition, definitions, schemas);
+  }
+  schemas.push(schema);
+  for combinator in ["allOf", "anyOf", "oneOf"] {
+    for schema in schema
+      .get(combinator)
+      .and_then(Value::as_array)
+      .into_iter()
+      .flatten()
+    {
+      collect_schemas(schema, definitions, schemas);
+    }
+  }
+}
+
+#[cfg(feature = "schema")]
+fn kebab_to_camel_case(key: &str) -> String {
+  let mut camel_case = String::with_capacity(key.len());
+  let mut uppercase = false;
+  for c in key.chars() {
+    if c == '-' {
+      uppercase = true;
+    } else if uppercase {
+      camel_case.extend(c.to_uppercase());
+      uppercase = false;
+    } else {
+      camel_case.push(c);
+    }
+  }
+  camel_case
+}
+
+/// Replaces the `${NAME}` references in the string values of the configuration with the environment variables
+/// allowed by `build > interpolateEnv`, returning whether any reference was replaced.
+///
+/// `${NAME:-default}` falls back to `default` when the variable is not set, and `$${` is replaced with `${`.
+/// Nothing is replaced when `build > interpolateEnv` is empty.
+pub fn interpolate_env(config: &mut Value) -> Result<bool, ConfigError> {
+  let allowlist = config
+    .pointer("/build/interpolateEnv")
+    .or_else(|| config.pointer("/build/interpolate-env"))
+    .and_then(Value::as_array)
+    .map(|names| {
+      names
+        .iter()
+        .filter_map(|name| name.as_str().map(ToString::to_string))
+        .collect::<Vec<_>>()
+    })
+    .unwrap_or_default();
+  if allowlist.is_empty() {
+    return Ok(false);
+  }
+  interpolate_value(config, &allowlist, &|name| std::env::var(name).ok())
+}
+
+fn interpolate_value(
+  value: &mut Value,
+  allowlist: &[String],
+  env: &dyn Fn(&str) -> Option<String>,
+) -> Result<bool, ConfigError> {
+  let mut interpolated = false;
+  match value {
+    Value::String(s) => {
+      if let Some(replaced) = interpolate_str(s, allowlist, env)? {
+        *s = replaced;
+        interpolated = true;
+      }
+    }
+    Value::Array(values) => {
+      for value in values {
+        interpolated |= interpolate_value(value, allowlist, env)?;
+      }
+    }
+    Value::Object(values) => {
+      for value in values.values_mut() {
+        interpolated |= interpolate_value(value, allowlist, env)?;
+      }
+    }
+    _ => {}
+  }
+  Ok(interpolated)
+}
+
+fn interpolate_str(
+  raw: &str,
+  allowlist: &[String],
+  env: &dyn Fn(&str) -> Option<String>,
+) -> Result<Option<String>, ConfigError> {
+  if !raw.contains("${") {
+    return Ok(None);
+  }
+
+  let mut interpolated = String::with_capacity(raw.len());
+  let mut rest = raw;
+  while let Some(start) = rest.find("${") {
+    // escaped with `$${`
+    if rest[..start].ends_with('$') {
+      interpolated.push_str(&rest[..start - 1]);
+      interpolated.push_str("${");
+      rest = &rest[start + 2..];
+      continue;
+    }
+
+    interpolated.push_str(&rest[..start]);
+    let reference = &rest[start + 2..];
+    let end = reference
+      .find('}')
+      .ok_or_else(|| ConfigError::Interpolation {
+        variable: reference.into(),
+        reason: "the reference is missing its closing `}`".into(),
+      })?;
+    let (name, default) = match reference[..end].split_once(":-") {
+      Some((name, default)) => (name, Some(default)),
+      None => (&reference[..end], None),
+    };
+
+    let allowed = allowlist
+      .iter()
+      .any(|allowed| match allowed.strip_suffix('*') {
+        Some(prefix) => name.starts_with(prefix),
+        None => name == allowed,
+      });
+    if !allowed {
+      return Err(ConfigError::Interpolation {
+        variable: name.into(),
+        reason: "the variable is not listed in `build > interpolateEnv`".into(),
+      });
+    }
+    match env(name).or_else(|| default.map(ToString::to_string)) {
+      Some(value) => interpolated.push_str(&value),
+      None => {
+        return Err(ConfigError::Interpolation {
+          variable: name.into(),
+          reason: "the environment variable is not set".into(),
+        })
+      }
+    }
+
+    rest = &reference[end + 1..];
+  }
+  interpolated.push_str(rest);
+
+  Ok(Some(interpolated))
+}
+
 /// Reads the platform-specific configuration file from the given root directory if it exists.
 ///
 /// Check [`read_from`] for more information.
@@ -239,11 +578,19 @@ pub fn parse(target: Target, path: impl Into<PathBuf>) -> Result<(Config, PathBu
 }
 
 /// See [`parse`] for specifics, returns a JSON [`Value`] instead of [`Config`].
+///
+/// The keys of TOML files are renamed to the keys of the JSON configuration, see [`normalize_keys`].
 pub fn parse_value(
   target: Target,
   path: impl Into<PathBuf>,
 ) -> Result<(Value, PathBuf), ConfigError> {
-  do_parse(target, path.into())
+  #[allow(unused_mut)]
+  let (mut config, path): (Value, PathBuf) = do_parse(target, path.into())?;
+  #[cfg(feature = "schema")]
+  if path.extension() == Some(OsStr::new("toml")) {
+    normalize_keys(&mut config);
+  }
+  Ok((config, path))
 }
 
 fn do_parse<D: DeserializeOwned>(
@@ -392,3 +739,101 @@ fn read_to_string(path: &Path) -> Result<String, ConfigError> {
     error,
   })
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn interpolate() {
+    let allowlist = vec!["CUSTOMER_*".to_string(), "API_URL".to_string()];
+    let env = |name: &str| match name {
+      "CUSTOMER_NAME" => Some("Acme".to_string()),
+      "API_URL" => Some("https://api.acme.com".to_string()),
+      "SECRET" => Some("secret".to_string()),
+      _ => None,
+    };
+
+    let mut config = serde_json::json!({
+      "productName": "${CUSTOMER_NAME} App",
+      "app": { "windows": [{ "url": "${API_URL}/app?theme=${CUSTOMER_THEME:-dark}" }] },
+      "version": "1.0.0",
+      "identifier": "$${CUSTOMER_NAME}",
+    });
+    assert!(interpolate_value(&mut config, &allowlist, &env).unwrap());
+    assert_eq!(
+      config,
+      serde_json::json!({
+        "productName": "Acme App",
+        "app": { "windows": [{ "url": "https://api.acme.com/app?theme=dark" }] },
+        "version": "1.0.0",
+        "identifier": "${CUSTOMER_NAME}",
+      })
+    );
+
+    assert!(matches!(
+      interpolate_str("${SECRET}", &allowlist, &env),
+      Err(ConfigError::Interpolation { variable, .. }) if variable == "SECRET"
+    ));
+    assert!(interpolate_str("${CUSTOMER_ID}", &allowlist, &env).is_err());
+    assert!(interpolate_str("${API_URL", &allowlist, &env).is_err());
+    assert_eq!(
+      interpolate_str("no reference", &allowlist, &env).unwrap(),
+      None
+    );
+  }
+
+  #[cfg(feature = "schema")]
+  #[test]
+  fn normalize_toml_keys() {
+    let mut config = serde_json::json!({
+      "product-name": "app",
+      "build": {
+        "dev-url": "http://localhost:1420",
+        "dev-proxy": { "/my-api": "http://localhost:8080" },
+      },
+      "bundle": { "windows": { "wix": { "language": { "en-US": { "locale-path": "en.wxl" } } } } },
+      "plugins": { "deep-link": { "mobile-hosts": [] } },
+      "app": { "windows": [{ "title-bar-style": "Overlay" }] },
+    });
+    normalize_keys(&mut config);
+    assert_eq!(
+      config,
+      serde_json::json!({
+        "productName": "app",
+        "build": {
+          "devUrl": "http://localhost:1420",
+          "devProxy": { "/my-api": "http://localhost:8080" },
+        },
+        "bundle": { "windows": { "wix": { "language": { "en-US": { "localePath": "en.wxl" } } } } },
+        "plugins": { "deep-link": { "mobile-hosts": [] } },
+        "app": { "windows": [{ "titleBarStyle": "Overlay" }] },
+      })
+    );
+  }
+
+  #[test]
+  fn profile_file() {
+    let dir = std::env::temp_dir().join("tauri-utils-config-profile-test");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(
+      dir.join("tauri.conf.staging.json"),
+      r#"{ "identifier": "com.acme.staging" }"#,
+    )
+    .unwrap();
+
+    let (config, path) = read_profile("staging", &dir).unwrap();
+    assert_eq!(path, dir.join("tauri.conf.staging.json"));
+    assert_eq!(config["identifier"], "com.acme.staging");
+    assert!(matches!(
+      read_profile("production", &dir),
+      Err(ConfigError::Io { .. })
+    ));
+    assert!(matches!(
+      read_profile("../staging", &dir),
+      Err(ConfigError::InvalidProfile(_))
+    ));
+    std::fs::remove_dir_all(&dir).unwrap();
+  }
+}
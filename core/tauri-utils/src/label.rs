@@ -0,0 +1,130 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Validation of the window and webview labels.
+//!
+//! A label must be non-empty, include only alphanumeric characters, `-`, `/`, `:` and `_`,
+//! and not start with one of the [`RESERVED_PREFIXES`] used by the windows Tauri creates internally.
+
+use std::fmt;
+
+/// The label prefixes reserved for the windows and webviews created by Tauri.
+pub const RESERVED_PREFIXES: &[&str] = &["webview-pool-", "__tauri-"];
+
+/// The reason a label was rejected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum LabelError {
+  /// The label is empty.
+  Empty,
+  /// The label includes a character other than alphanumeric characters, `-`, `/`, `:` and `_`.
+  InvalidCharacter(char),
+  /// The label starts with one of the [`RESERVED_PREFIXES`].
+  Reserved(&'static str),
+}
+
+impl fmt::Display for LabelError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::Empty => write!(f, "labels cannot be empty"),
+      Self::InvalidCharacter(c) => write!(
+        f,
+        "invalid character `{c}`, labels must include only alphanumeric characters, `-`, `/`, `:` and `_`"
+      ),
+      Self::Reserved(prefix) => write!(f, "the `{prefix}` label prefix is reserved by Tauri"),
+    }
+  }
+}
+
+impl std::error::Error for LabelError {}
+
+/// Whether the character is allowed in labels.
+pub fn is_valid_char(c: char) -> bool {
+  c.is_alphanumeric() || c == '-' || c == '/' || c == ':' || c == '_'
+}
+
+/// The reserved prefix the label starts with, if any.
+pub fn reserved_prefix(label: &str) -> Option<&'static str> {
+  RESERVED_PREFIXES
+    .iter()
+    .find(|prefix| label.starts_with(*prefix))
+    .copied()
+}
+
+/// Checks that the label can be used by an app window or webview.
+pub fn validate(label: &str) -> Result<(), LabelError> {
+  if label.is_empty() {
+    return Err(LabelError::Empty);
+  }
+  if let Some(c) = label.chars().find(|c| !is_valid_char(*c)) {
+    return Err(LabelError::InvalidCharacter(c));
+  }
+  if let Some(prefix) = reserved_prefix(label) {
+    return Err(LabelError::Reserved(prefix));
+  }
+  Ok(())
+}
+
+/// Returns the label itself if it is free, or the first free `{label}-{n}` label starting at `n = 2`.
+pub fn suggest<F: Fn(&str) -> bool>(label: &str, is_taken: F) -> String {
+  if !is_taken(label) {
+    return label.into();
+  }
+  (2..)
+    .map(|n| format!("{label}-{n}"))
+    .find(|candidate| !is_taken(candidate))
+    .unwrap()
+}
+
+/// Whether the label matches the glob pattern, where `*` matches any sequence of characters, including `/`, and `?` a single character.
+pub fn matches(pattern: &str, label: &str) -> bool {
+  glob::Pattern::new(pattern)
+    .map(|pattern| {
+      pattern.matches_with(
+        label,
+        glob::MatchOptions {
+          case_sensitive: true,
+          require_literal_separator: false,
+          require_literal_leading_dot: false,
+        },
+      )
+    })
+    .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn validates_labels() {
+    assert_eq!(validate("main"), Ok(()));
+    assert_eq!(validate("editor/1:preview_2"), Ok(()));
+    assert_eq!(validate(""), Err(LabelError::Empty));
+    assert_eq!(
+      validate("my window"),
+      Err(LabelError::InvalidCharacter(' '))
+    );
+    assert_eq!(
+      validate("webview-pool-0"),
+      Err(LabelError::Reserved("webview-pool-"))
+    );
+  }
+
+  #[test]
+  fn suggests_free_labels() {
+    let taken = ["editor", "editor-2"];
+    assert_eq!(suggest("settings", |l| taken.contains(&l)), "settings");
+    assert_eq!(suggest("editor", |l| taken.contains(&l)), "editor-3");
+  }
+
+  #[test]
+  fn matches_globs() {
+    assert!(matches("editor-*", "editor-1"));
+    assert!(matches("docs/*", "docs/a/b"));
+    assert!(matches("tab-?", "tab-1"));
+    assert!(!matches("tab-?", "tab-10"));
+    assert!(!matches("editor-*", "settings"));
+  }
+}
@@ -27,6 +27,7 @@ pub mod assets;
 pub mod config;
 pub mod html;
 pub mod io;
+pub mod label;
 pub mod mime_type;
 pub mod platform;
 pub mod plugin;
@@ -35,6 +36,7 @@ pub mod plugin;
 pub mod resources;
 #[cfg(feature = "build")]
 pub mod tokens;
+pub mod updater;
 
 #[cfg(feature = "build")]
 pub mod build;
@@ -219,6 +221,24 @@ impl Display for TitleBarStyle {
   }
 }
 
+/// How the corners of a window are rounded on Windows 11 and macOS.
+///
+/// <https://learn.microsoft.com/en-us/windows/win32/api/dwmapi/ne-dwmapi-dwm_window_corner_preference>
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub enum CornerPreference {
+  /// Let the system decide whether to round the corners.
+  #[default]
+  Default,
+  /// Never round the corners.
+  DoNotRound,
+  /// Round the corners.
+  Round,
+  /// Round the corners with a small radius.
+  RoundSmall,
+}
+
 /// System theme.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
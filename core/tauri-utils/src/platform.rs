@@ -169,6 +169,37 @@ pub fn current_exe() -> std::io::Result<PathBuf> {
   self::starting_binary::STARTING_BINARY.cloned()
 }
 
+/// Whether the given executable path is being run from a macOS App Translocation mount.
+///
+/// macOS runs quarantined apps that were not moved by the user from a randomized read-only
+/// location, so the app bundle can't be replaced in place, e.g. by an update installer.
+pub fn is_translocated<P: AsRef<Path>>(exe: P) -> bool {
+  exe
+    .as_ref()
+    .components()
+    .any(|c| c.as_os_str() == "AppTranslocation")
+}
+
+/// The name of the file that enables the portable mode when placed along the executable.
+pub const PORTABLE_MARKER_FILE_NAME: &str = ".portable";
+
+/// Returns the directory where the app data is stored in portable mode.
+///
+/// The portable mode is enabled when a [`PORTABLE_MARKER_FILE_NAME`] file is placed along the executable,
+/// in which case the app data is stored in the `data` folder next to the executable instead of the user directories.
+pub fn portable_dir() -> Option<PathBuf> {
+  current_exe().ok().and_then(portable_dir_from)
+}
+
+fn portable_dir_from<P: AsRef<Path>>(exe: P) -> Option<PathBuf> {
+  let exe_dir = exe.as_ref().parent()?;
+  if exe_dir.join(PORTABLE_MARKER_FILE_NAME).is_file() {
+    Some(exe_dir.join("data"))
+  } else {
+    None
+  }
+}
+
 /// Try to determine the current target triple.
 ///
 /// Returns a target triple (e.g. `x86_64-unknown-linux-gnu` or `i686-pc-windows-msvc`) or an
@@ -296,6 +327,24 @@ fn resource_dir_from<P: AsRef<Path>>(
         .join(format!("../lib/{}", package_info.crate_name))
         .canonicalize()
         .map_err(Into::into)
+    } else if curr_dir.starts_with("/snap/") && curr_dir.ends_with("/usr/bin") {
+      // running in a snap
+      exe_dir
+        .join(format!("../lib/{}", package_info.crate_name))
+        .canonicalize()
+        .map_err(Into::into)
+    } else if curr_dir.starts_with("/opt/") && curr_dir.ends_with("/bin") {
+      // installed with the `opt` install prefix of the deb and rpm bundles
+      exe_dir
+        .join(format!("../lib/{}", package_info.crate_name))
+        .canonicalize()
+        .map_err(Into::into)
+    } else if curr_dir == "/app/bin" {
+      // running in a flatpak sandbox
+      Ok(PathBuf::from(format!(
+        "/app/lib/{}",
+        package_info.crate_name
+      )))
     } else if let Some(appdir) = &env.appdir {
       let appdir: &std::path::Path = appdir.as_ref();
       Ok(PathBuf::from(format!(
@@ -383,4 +432,28 @@ mod tests {
     #[cfg(windows)]
     assert_eq!(resource_dir.unwrap(), path.parent().unwrap());
   }
+
+  #[test]
+  fn resolve_portable_dir() {
+    let dir = std::env::temp_dir().join(format!("tauri-portable-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let exe = dir.join("app.exe");
+
+    assert_eq!(super::portable_dir_from(&exe), None);
+
+    std::fs::write(dir.join(super::PORTABLE_MARKER_FILE_NAME), "").unwrap();
+    assert_eq!(super::portable_dir_from(&exe), Some(dir.join("data")));
+
+    std::fs::remove_dir_all(dir).unwrap();
+  }
+
+  #[test]
+  fn detects_translocation() {
+    assert!(super::is_translocated(
+      "/private/var/folders/xy/T/AppTranslocation/1A2B3C/d/MyApp.app/Contents/MacOS/MyApp"
+    ));
+    assert!(!super::is_translocated(
+      "/Applications/MyApp.app/Contents/MacOS/MyApp"
+    ));
+  }
 }
@@ -3,17 +3,98 @@
 // SPDX-License-Identifier: MIT
 
 //! Compile-time and runtime types for Tauri plugins.
+use std::collections::BTreeMap;
+
+use serde_json::Value;
+
 #[cfg(feature = "build")]
 pub use build::*;
 
+/// Known file name of the file that contains the configuration schemas of the app plugins, keyed by plugin name.
+///
+/// It is generated next to the capability schemas by `tauri-build` so the CLI can validate the plugins configuration.
+pub const PLUGIN_CONFIG_SCHEMAS_FILE_NAME: &str = "plugin-config-schemas.json";
+
+/// Known file name of the app configuration schema including the configuration schemas of the app plugins.
+///
+/// It is generated next to the capability schemas by `tauri-build` so it can be referenced as the configuration `$schema`.
+pub const CONFIG_SCHEMA_FILE_NAME: &str = "config.schema.json";
+
+/// Adds the configuration schemas of the plugins, keyed by plugin name, to the `plugins` object of the app configuration schema.
+///
+/// The plugin schema definitions are moved to the root schema, prefixed with the plugin name to prevent conflicts.
+pub fn extend_config_schema(schema: &mut Value, plugin_schemas: &BTreeMap<String, Value>) {
+  for (name, plugin_schema) in plugin_schemas {
+    let mut plugin_schema = plugin_schema.clone();
+    prefix_definition_refs(&mut plugin_schema, name);
+
+    if let Some(plugin_schema) = plugin_schema.as_object_mut() {
+      plugin_schema.remove("$schema");
+      if let Some(Value::Object(definitions)) = plugin_schema.remove("definitions") {
+        if let Some(root_definitions) = schema
+          .as_object_mut()
+          .map(|schema| {
+            schema
+              .entry("definitions")
+              .or_insert_with(|| Value::Object(Default::default()))
+          })
+          .and_then(Value::as_object_mut)
+        {
+          for (definition_name, definition) in definitions {
+            root_definitions.insert(format!("{name}.{definition_name}"), definition);
+          }
+        }
+      }
+    }
+
+    let properties = schema
+      .pointer_mut("/definitions/PluginConfig")
+      .and_then(Value::as_object_mut)
+      .map(|plugin_config| {
+        plugin_config
+          .entry("properties")
+          .or_insert_with(|| Value::Object(Default::default()))
+      })
+      .and_then(Value::as_object_mut);
+    if let Some(properties) = properties {
+      properties.insert(name.clone(), plugin_schema);
+    }
+  }
+}
+
+fn prefix_definition_refs(value: &mut Value, prefix: &str) {
+  match value {
+    Value::Object(object) => {
+      for (key, value) in object.iter_mut() {
+        match value {
+          Value::String(reference) if key == "$ref" => {
+            if let Some(definition_name) = reference.strip_prefix("#/definitions/") {
+              *reference = format!("#/definitions/{prefix}.{definition_name}");
+            }
+          }
+          value => prefix_definition_refs(value, prefix),
+        }
+      }
+    }
+    Value::Array(array) => {
+      for value in array {
+        prefix_definition_refs(value, prefix);
+      }
+    }
+    _ => (),
+  }
+}
+
 #[cfg(feature = "build")]
 mod build {
   use std::{
+    collections::BTreeMap,
     env::vars_os,
     path::{Path, PathBuf},
   };
 
   const GLOBAL_API_SCRIPT_PATH_KEY: &str = "GLOBAL_API_SCRIPT_PATH";
+  const CONFIG_SCHEMA_PATH_KEY: &str = "CONFIG_SCHEMA_PATH";
   /// Known file name of the file that contains an array with the path of all API scripts defined with [`define_global_api_script_path`].
   pub const GLOBAL_API_SCRIPT_FILE_LIST_PATH: &str = "__global-api-script.js";
 
@@ -48,4 +129,87 @@ mod build {
     )
     .expect("failed to write global API script");
   }
+
+  /// Saves the schema of the plugin configuration to the out dir and defines its path using Cargo instructions.
+  pub fn define_config_schema(
+    schema: &schemars::schema::RootSchema,
+    out_dir: &Path,
+  ) -> std::io::Result<()> {
+    let path = out_dir.join("config-schema.json");
+    std::fs::write(&path, serde_json::to_vec(schema)?)?;
+    println!("cargo:{CONFIG_SCHEMA_PATH_KEY}={}", path.display());
+    Ok(())
+  }
+
+  /// Reads the configuration schemas defined with [`define_config_schema`] by the plugins the crate depends on,
+  /// keyed by the plugin name as used in the `plugins` configuration object.
+  pub fn read_config_schemas() -> std::io::Result<BTreeMap<String, serde_json::Value>> {
+    let mut schemas = BTreeMap::new();
+
+    for (key, value) in vars_os() {
+      let key = key.to_string_lossy();
+
+      if let Some(plugin_crate_name_var) = key
+        .strip_prefix("DEP_")
+        .and_then(|v| v.strip_suffix(&format!("_{CONFIG_SCHEMA_PATH_KEY}")))
+      {
+        let schema = serde_json::from_str(&std::fs::read_to_string(PathBuf::from(value))?)?;
+
+        let plugin_crate_name = plugin_crate_name_var.to_lowercase().replace('_', "-");
+        schemas.insert(
+          plugin_crate_name
+            .strip_prefix("tauri-plugin-")
+            .map(|n| n.to_string())
+            .unwrap_or(plugin_crate_name),
+          schema,
+        );
+      }
+    }
+
+    Ok(schemas)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn extend_config_schema_with_plugins() {
+    let mut schema = serde_json::json!({
+      "properties": { "plugins": { "$ref": "#/definitions/PluginConfig" } },
+      "definitions": {
+        "PluginConfig": { "type": "object", "additionalProperties": true },
+        "Config": { "type": "object" }
+      }
+    });
+    let plugin_schema = serde_json::json!({
+      "$schema": "http://json-schema.org/draft-07/schema#",
+      "title": "Config",
+      "type": "object",
+      "properties": { "scope": { "$ref": "#/definitions/Scope" } },
+      "definitions": { "Scope": { "type": "array", "items": { "type": "string" } } }
+    });
+    extend_config_schema(
+      &mut schema,
+      &[("fs".to_string(), plugin_schema)].into_iter().collect(),
+    );
+
+    assert_eq!(
+      schema["definitions"]["PluginConfig"]["properties"]["fs"],
+      serde_json::json!({
+        "title": "Config",
+        "type": "object",
+        "properties": { "scope": { "$ref": "#/definitions/fs.Scope" } }
+      })
+    );
+    assert_eq!(
+      schema["definitions"]["fs.Scope"],
+      serde_json::json!({ "type": "array", "items": { "type": "string" } })
+    );
+    assert_eq!(
+      schema["definitions"]["Config"],
+      serde_json::json!({ "type": "object" })
+    );
+  }
 }
@@ -0,0 +1,985 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Types shared between the bundler and the updater.
+//!
+//! # Differential updates
+//!
+//! When the bundler is given previous release artifacts, it generates a binary patch for each
+//! update artifact and writes an [`ArtifactDeltas`] file next to it (`<artifact>.deltas.json`).
+//! That information is then published in the update manifest as [`DeltaRelease`] entries
+//! so the updater can download a patch instead of the full artifact.
+//!
+//! # Channels and staged rollouts
+//!
+//! An [`UpdateManifest`] can target an [`UpdateChannel`] and be rolled out to a percentage of
+//! the installations. Each installation is assigned a stable [`RolloutBucket`] derived from an
+//! anonymous installation identifier. [`endpoint_url`] fills the [`CHANNEL_VARIABLE`] and
+//! [`ROLLOUT_BUCKET_VARIABLE`] endpoint variables so an update server can pick the release, and
+//! [`UpdateManifest::latest_available`] picks it from static manifests checked against [`UpdateManifest::rollout`].
+//!
+//! # Resumable downloads
+//!
+//! Background downloads persist a [`PartialDownload`] file next to the partially downloaded
+//! artifact (`<artifact>.partial.json`) with [`PartialDownload::save`], so they can be resumed
+//! with an HTTP range request after the app restarts: [`PartialDownload::resume`] returns the
+//! state to continue from, or discards the downloaded bytes when the release artifact changed.
+//!
+//! # Install on quit
+//!
+//! A downloaded update can be staged instead of installed right away: [`StagedUpdate::stage`] writes
+//! a [`STAGED_UPDATE_FILE_NAME`] file, and [`StagedUpdate::take`] returns the update to apply when the app
+//! exits or, if that fails, on the next launch.
+//!
+//! # Key rotation
+//!
+//! Update signatures can be verified against a set of [`TrustedKey`]s, each with an optional
+//! validity window. A compromised or expiring key is retired by publishing a [`KeyRotation`]
+//! statement signed by that key, which introduces its replacement to existing installations.
+//!
+//! # Windows install scope
+//!
+//! The NSIS and MSI installers record the [`InstallScope`] of the installation in the
+//! [`INSTALL_SCOPE_REGISTRY_VALUE`] registry value, so an update can run the new installer
+//! with the same scope and only request elevation for per-machine installations.
+//!
+//! # AppImage zsync updates
+//!
+//! AppImages built with update information embed it in the `.upd_info` section and are published
+//! with a `<artifact>.zsync` file ([`ZSYNC_FILE_EXTENSION`]). The update manifest references it in
+//! [`PlatformRelease::zsync`] so the updater can download only the blocks that changed since the
+//! installed AppImage, falling back to the full artifact otherwise.
+
+use std::{
+  collections::HashMap,
+  fmt,
+  fs::OpenOptions,
+  path::{Path, PathBuf},
+  str::FromStr,
+};
+
+use serde::{Deserialize, Serialize};
+
+/// Endpoint variable replaced with the installation's [`RolloutBucket`].
+pub const ROLLOUT_BUCKET_VARIABLE: &str = "{{rollout_bucket}}";
+
+/// Endpoint variable replaced with the installation's [`UpdateChannel`].
+pub const CHANNEL_VARIABLE: &str = "{{channel}}";
+
+/// Replaces the [`CHANNEL_VARIABLE`] and [`ROLLOUT_BUCKET_VARIABLE`] variables of an update endpoint URL.
+pub fn endpoint_url(endpoint: &str, channel: UpdateChannel, bucket: RolloutBucket) -> String {
+  endpoint
+    .replace(CHANNEL_VARIABLE, channel.as_str())
+    .replace(ROLLOUT_BUCKET_VARIABLE, &bucket.to_string())
+}
+
+/// Registry value storing the [`InstallScope`] of a Windows installation.
+///
+/// It is written under the `Software\<manufacturer>\<product name>` key of `HKEY_LOCAL_MACHINE`
+/// for per-machine installations and `HKEY_CURRENT_USER` for per-user installations.
+pub const INSTALL_SCOPE_REGISTRY_VALUE: &str = "InstallScope";
+
+/// File name of the [`StagedUpdate`] file, stored in the updater cache directory.
+pub const STAGED_UPDATE_FILE_NAME: &str = "staged-update.json";
+
+/// File extension appended to a partially downloaded artifact path for its [`PartialDownload`] file.
+pub const PARTIAL_DOWNLOAD_FILE_EXTENSION: &str = "partial.json";
+
+/// File extension appended to an update artifact path for its [`ArtifactDeltas`] file.
+pub const DELTAS_FILE_EXTENSION: &str = "deltas.json";
+
+/// File extension appended to an AppImage path for its zsync control file.
+pub const ZSYNC_FILE_EXTENSION: &str = "zsync";
+
+/// The binary diff format of a delta artifact.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum DeltaFormat {
+  /// A zstd patch generated with `zstd --patch-from=<base>`.
+  ///
+  /// Applied with the equivalent of `zstd -d --patch-from=<base> <patch>`.
+  ZstdPatch,
+}
+
+/// A delta artifact generated by the bundler.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeltaArtifact {
+  /// The version the delta applies to.
+  pub from: String,
+  /// File name of the patch, relative to the [`ArtifactDeltas`] file.
+  pub file: String,
+  /// SHA-256 hex digest of the base artifact the patch must be applied to.
+  pub base_sha256: String,
+  /// The patch format.
+  pub format: DeltaFormat,
+}
+
+/// The delta artifacts generated for an update artifact, written to `<artifact>.deltas.json`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArtifactDeltas {
+  /// The version of the update artifact.
+  pub version: String,
+  /// File name of the full update artifact.
+  pub artifact: String,
+  /// SHA-256 hex digest of the full update artifact, the expected result of applying any delta.
+  pub sha256: String,
+  /// The generated deltas.
+  #[serde(default)]
+  pub deltas: Vec<DeltaArtifact>,
+}
+
+/// A delta entry of a platform in the update manifest.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeltaRelease {
+  /// The version the delta applies to.
+  pub from: String,
+  /// Download URL of the patch.
+  pub url: String,
+  /// Signature of the patch file.
+  pub signature: String,
+  /// SHA-256 hex digest of the installed artifact the patch must be applied to.
+  pub base_sha256: String,
+  /// SHA-256 hex digest of the artifact after applying the patch.
+  pub sha256: String,
+  /// The patch format.
+  pub format: DeltaFormat,
+}
+
+/// A platform entry in the update manifest.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlatformRelease {
+  /// Download URL of the full update artifact.
+  pub url: String,
+  /// Signature of the full update artifact.
+  pub signature: String,
+  /// SHA-256 hex digest of the full update artifact.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub sha256: Option<String>,
+  /// Delta artifacts that can be used instead of the full artifact.
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub deltas: Vec<DeltaRelease>,
+  /// Download URL of the zsync control file of an AppImage artifact.
+  ///
+  /// The file lists the block checksums of the artifact, so the blocks already present
+  /// in the installed AppImage are reused and only the changed blocks are downloaded from [`Self::url`].
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub zsync: Option<String>,
+}
+
+impl PlatformRelease {
+  /// Finds the delta that updates from the given installed version, if any.
+  ///
+  /// The updater must fall back to the full artifact when there's no delta,
+  /// or when the installed artifact hash does not match [`DeltaRelease::base_sha256`].
+  pub fn delta_from(&self, version: &str) -> Option<&DeltaRelease> {
+    self.deltas.iter().find(|d| d.from == version)
+  }
+}
+
+/// The release channel of an update.
+///
+/// Channels are ordered by stability: an installation tracking a channel
+/// also accepts releases of the more stable channels.
+#[derive(
+  Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum UpdateChannel {
+  /// The stable channel.
+  #[default]
+  Stable,
+  /// The beta channel.
+  Beta,
+  /// The nightly channel.
+  Nightly,
+}
+
+impl UpdateChannel {
+  /// The channel name.
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      Self::Stable => "stable",
+      Self::Beta => "beta",
+      Self::Nightly => "nightly",
+    }
+  }
+
+  /// Whether an installation tracking this channel accepts a release published to `release`.
+  pub fn accepts(&self, release: UpdateChannel) -> bool {
+    release <= *self
+  }
+}
+
+impl fmt::Display for UpdateChannel {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str(self.as_str())
+  }
+}
+
+impl FromStr for UpdateChannel {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s.to_lowercase().as_str() {
+      "stable" => Ok(Self::Stable),
+      "beta" => Ok(Self::Beta),
+      "nightly" => Ok(Self::Nightly),
+      _ => Err(format!(
+        "unknown update channel {s}, expected one of stable, beta, nightly"
+      )),
+    }
+  }
+}
+
+/// The staged rollout bucket of an installation, in the `0..100` range.
+///
+/// The bucket is derived from an anonymous installation identifier so it is stable
+/// across app restarts and updates, but it can't be traced back to the user.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct RolloutBucket(u8);
+
+impl RolloutBucket {
+  /// Computes the bucket of the given installation identifier.
+  ///
+  /// Uses the FNV-1a hash so the result doesn't change between Rust versions or platforms.
+  pub fn from_install_id(install_id: &str) -> Self {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in install_id.as_bytes() {
+      hash ^= *byte as u64;
+      hash = hash.wrapping_mul(0x100000001b3);
+    }
+    Self((hash % 100) as u8)
+  }
+
+  /// The bucket value.
+  pub fn value(&self) -> u8 {
+    self.0
+  }
+
+  /// Whether this bucket is included in a rollout to the given percentage of installations.
+  pub fn is_included(&self, percentage: u8) -> bool {
+    self.0 < percentage
+  }
+}
+
+impl fmt::Display for RolloutBucket {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    self.0.fmt(f)
+  }
+}
+
+/// A static update manifest (`latest.json`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateManifest {
+  /// The release version.
+  pub version: String,
+  /// The release notes.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub notes: Option<String>,
+  /// The release date in RFC 3339 format.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub pub_date: Option<String>,
+  /// The release channel, defaults to [`UpdateChannel::Stable`].
+  #[serde(default)]
+  pub channel: UpdateChannel,
+  /// Percentage of installations, from 0 to 100, this release is rolled out to.
+  ///
+  /// The release is available to every installation when not set.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub rollout: Option<u8>,
+  /// The release artifacts, keyed by `<os>-<arch>` target.
+  pub platforms: HashMap<String, PlatformRelease>,
+}
+
+impl UpdateManifest {
+  /// Whether this release should be offered to an installation tracking `channel` in `bucket`.
+  pub fn is_available_for(&self, channel: UpdateChannel, bucket: RolloutBucket) -> bool {
+    channel.accepts(self.channel)
+      && self
+        .rollout
+        .map_or(true, |percentage| bucket.is_included(percentage))
+  }
+
+  /// The release with the highest version available to an installation tracking `channel` in `bucket`,
+  /// e.g. among the manifests of the stable, beta and nightly channels.
+  ///
+  /// The manifests with an invalid semver version are ignored.
+  pub fn latest_available(
+    manifests: &[UpdateManifest],
+    channel: UpdateChannel,
+    bucket: RolloutBucket,
+  ) -> Option<&UpdateManifest> {
+    manifests
+      .iter()
+      .filter(|manifest| manifest.is_available_for(channel, bucket))
+      .filter_map(|manifest| {
+        semver::Version::parse(&manifest.version)
+          .ok()
+          .map(|version| (version, manifest))
+      })
+      .max_by(|(a, _), (b, _)| a.cmp(b))
+      .map(|(_, manifest)| manifest)
+  }
+}
+
+/// The state of a partially downloaded update artifact.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PartialDownload {
+  /// The version being downloaded.
+  pub version: String,
+  /// The artifact download URL.
+  pub url: String,
+  /// Number of bytes already written to disk.
+  pub downloaded: u64,
+  /// The artifact size reported by the server, if known.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub content_length: Option<u64>,
+  /// The `ETag` response header, used to check the artifact did not change on the server.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub etag: Option<String>,
+}
+
+impl PartialDownload {
+  /// Whether the whole artifact has been downloaded.
+  pub fn is_complete(&self) -> bool {
+    self
+      .content_length
+      .map_or(false, |length| self.downloaded >= length)
+  }
+
+  /// Whether this download can be resumed for the given release artifact.
+  ///
+  /// The download must be restarted from scratch when the URL or the `ETag` changed.
+  pub fn can_resume(&self, version: &str, url: &str, etag: Option<&str>) -> bool {
+    self.version == version && self.url == url && (etag.is_none() || self.etag.as_deref() == etag)
+  }
+
+  /// The `Range` request header value to resume the download, if anything was downloaded.
+  pub fn range_header(&self) -> Option<String> {
+    (self.downloaded > 0).then(|| format!("bytes={}-", self.downloaded))
+  }
+
+  /// The download progress, from 0.0 to 1.0, if the artifact size is known.
+  pub fn progress(&self) -> Option<f64> {
+    self
+      .content_length
+      .filter(|length| *length > 0)
+      .map(|length| (self.downloaded as f64 / length as f64).min(1.0))
+  }
+
+  /// The path of the state file of a partially downloaded artifact.
+  pub fn state_path<P: AsRef<Path>>(artifact: P) -> PathBuf {
+    let mut path = artifact.as_ref().as_os_str().to_owned();
+    path.push(".");
+    path.push(PARTIAL_DOWNLOAD_FILE_EXTENSION);
+    PathBuf::from(path)
+  }
+
+  /// Reads the state file of a partially downloaded artifact, if any.
+  pub fn load<P: AsRef<Path>>(artifact: P) -> std::io::Result<Option<Self>> {
+    match std::fs::read(Self::state_path(artifact)) {
+      Ok(state) => serde_json::from_slice(&state)
+        .map(Some)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e)),
+      Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+      Err(e) => Err(e),
+    }
+  }
+
+  /// Writes the state file of the partially downloaded artifact.
+  ///
+  /// The state must be saved after the downloaded bytes are flushed to the artifact file,
+  /// so it never claims more bytes than the file has.
+  pub fn save<P: AsRef<Path>>(&self, artifact: P) -> std::io::Result<()> {
+    let state =
+      serde_json::to_vec(self).map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
+    std::fs::write(Self::state_path(artifact), state)
+  }
+
+  /// Returns the state to continue downloading the release artifact to the given path.
+  ///
+  /// When the saved state [can be resumed](Self::can_resume), the artifact file is truncated
+  /// to the saved number of downloaded bytes. Otherwise the artifact and its state file are removed
+  /// and the download starts from scratch.
+  pub fn resume<P: AsRef<Path>>(
+    artifact: P,
+    version: &str,
+    url: &str,
+    etag: Option<&str>,
+  ) -> std::io::Result<Self> {
+    let artifact = artifact.as_ref();
+    let saved = Self::load(artifact)
+      .ok()
+      .flatten()
+      .filter(|state| state.can_resume(version, url, etag));
+    if let Some(state) = saved {
+      let file = OpenOptions::new().write(true).open(artifact);
+      if let Ok(file) = file {
+        if file.metadata()?.len() >= state.downloaded {
+          file.set_len(state.downloaded)?;
+          return Ok(state);
+        }
+      }
+    }
+
+    Self::discard(artifact)?;
+    Ok(Self {
+      version: version.into(),
+      url: url.into(),
+      downloaded: 0,
+      content_length: None,
+      etag: etag.map(Into::into),
+    })
+  }
+
+  /// Removes the partially downloaded artifact and its state file.
+  pub fn discard<P: AsRef<Path>>(artifact: P) -> std::io::Result<()> {
+    let artifact = artifact.as_ref();
+    for path in [artifact.to_path_buf(), Self::state_path(artifact)] {
+      match std::fs::remove_file(path) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e),
+        _ => (),
+      }
+    }
+    Ok(())
+  }
+
+  /// Removes the state file once the artifact is completely downloaded.
+  pub fn finish<P: AsRef<Path>>(artifact: P) -> std::io::Result<()> {
+    match std::fs::remove_file(Self::state_path(artifact)) {
+      Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+      _ => Ok(()),
+    }
+  }
+}
+
+/// When a [`StagedUpdate`] is installed.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub enum InstallTrigger {
+  /// Install when the app exits, falling back to the next launch.
+  #[default]
+  OnQuit,
+  /// Install on the next app launch, before any window is created.
+  NextLaunch,
+}
+
+/// A downloaded and verified update waiting to be installed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StagedUpdate {
+  /// The version of the staged update.
+  pub version: String,
+  /// The version that was running when the update was staged.
+  ///
+  /// The staged update is discarded if the app version changed in the meantime.
+  pub current_version: String,
+  /// Path to the downloaded update artifact.
+  pub path: std::path::PathBuf,
+  /// When the update is installed.
+  #[serde(default)]
+  pub trigger: InstallTrigger,
+  /// Whether the installer must be elevated, see [`InstallScope::requires_elevation`].
+  #[serde(default)]
+  pub requires_elevation: bool,
+}
+
+impl StagedUpdate {
+  /// Whether this staged update can still be installed over the running `current_version`.
+  pub fn is_applicable(&self, current_version: &str) -> bool {
+    self.current_version == current_version && self.path.is_file()
+  }
+
+  /// Writes the [`STAGED_UPDATE_FILE_NAME`] file to the updater cache directory,
+  /// replacing the update staged previously.
+  pub fn stage<P: AsRef<Path>>(&self, cache_dir: P) -> std::io::Result<()> {
+    let state =
+      serde_json::to_vec(self).map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
+    std::fs::create_dir_all(cache_dir.as_ref())?;
+    std::fs::write(cache_dir.as_ref().join(STAGED_UPDATE_FILE_NAME), state)
+  }
+
+  /// Removes the [`STAGED_UPDATE_FILE_NAME`] file from the updater cache directory,
+  /// returning the staged update if it [is applicable](Self::is_applicable) to the running `current_version`.
+  ///
+  /// The update is taken so a failing installer doesn't run on every launch.
+  /// The artifact of an update that is no longer applicable is removed.
+  pub fn take<P: AsRef<Path>>(
+    cache_dir: P,
+    current_version: &str,
+  ) -> std::io::Result<Option<Self>> {
+    let path = cache_dir.as_ref().join(STAGED_UPDATE_FILE_NAME);
+    let state = match std::fs::read(&path) {
+      Ok(state) => state,
+      Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+      Err(e) => return Err(e),
+    };
+    std::fs::remove_file(&path)?;
+
+    let Ok(staged) = serde_json::from_slice::<Self>(&state) else {
+      return Ok(None);
+    };
+    if staged.is_applicable(current_version) {
+      Ok(Some(staged))
+    } else {
+      let _ = std::fs::remove_file(&staged.path);
+      Ok(None)
+    }
+  }
+}
+
+/// The scope of a Windows installation, stored in the [`INSTALL_SCOPE_REGISTRY_VALUE`] registry value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum InstallScope {
+  /// Installed for the current user only.
+  PerUser,
+  /// Installed for all users of the machine.
+  PerMachine,
+}
+
+impl InstallScope {
+  /// The registry value data.
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      Self::PerUser => "perUser",
+      Self::PerMachine => "perMachine",
+    }
+  }
+
+  /// Whether running the installer for this scope requires elevation.
+  pub fn requires_elevation(&self) -> bool {
+    matches!(self, Self::PerMachine)
+  }
+
+  /// The NSIS installer arguments that keep this scope,
+  /// only used by installers built with the `both` install mode.
+  pub fn nsis_args(&self) -> &'static [&'static str] {
+    match self {
+      Self::PerUser => &["/CurrentUser"],
+      Self::PerMachine => &["/AllUsers"],
+    }
+  }
+
+  /// The `msiexec` arguments that keep this scope.
+  pub fn msiexec_args(&self) -> &'static [&'static str] {
+    match self {
+      Self::PerUser => &["ALLUSERS=2", "MSIINSTALLPERUSER=1"],
+      Self::PerMachine => &["ALLUSERS=1"],
+    }
+  }
+}
+
+impl fmt::Display for InstallScope {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str(self.as_str())
+  }
+}
+
+impl FromStr for InstallScope {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s {
+      "perUser" => Ok(Self::PerUser),
+      "perMachine" => Ok(Self::PerMachine),
+      _ => Err(format!(
+        "unknown install scope {s}, expected one of perUser, perMachine"
+      )),
+    }
+  }
+}
+
+/// A public key trusted to sign updates.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrustedKey {
+  /// The base64 encoded minisign public key.
+  pub pubkey: String,
+  /// Unix timestamp, in seconds, before which signatures made with this key are rejected.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub not_before: Option<u64>,
+  /// Unix timestamp, in seconds, after which signatures made with this key are rejected.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub not_after: Option<u64>,
+}
+
+impl TrustedKey {
+  /// Whether the signatures made with this key are accepted at the given unix timestamp, in seconds.
+  ///
+  /// The timestamp must be the current time of the verifier, see [`Self::is_valid_now`].
+  /// The `timestamp` of the signature trusted comment must not be used: it is written by the key holder,
+  /// who could back-date a signature made with a retired or compromised key.
+  pub fn is_valid_at(&self, now: u64) -> bool {
+    self.not_before.map_or(true, |t| now >= t) && self.not_after.map_or(true, |t| now <= t)
+  }
+
+  /// Whether the signatures made with this key are accepted now, according to the system clock.
+  pub fn is_valid_now(&self) -> bool {
+    self.is_valid_at(unix_now())
+  }
+}
+
+/// The current unix timestamp, in seconds.
+fn unix_now() -> u64 {
+  std::time::SystemTime::now()
+    .duration_since(std::time::UNIX_EPOCH)
+    .map(|d| d.as_secs())
+    .unwrap_or_default()
+}
+
+/// A statement signed by a retiring key that introduces its replacement.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyRotation {
+  /// The base64 encoded public key being retired, which signed this statement.
+  pub from: String,
+  /// Unix timestamp, in seconds, after which signatures made with the retired key are rejected.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub retire_at: Option<u64>,
+  /// The key that replaces the retired key.
+  pub to: TrustedKey,
+  /// The base64 encoded minisign signature of [`KeyRotation::message`], made with the retired key.
+  pub signature: String,
+}
+
+impl KeyRotation {
+  /// The message that must be signed by the retired key for this statement to be accepted.
+  pub fn message(&self) -> String {
+    Self::message_for(&self.from, self.retire_at, &self.to)
+  }
+
+  /// The message to sign to retire `from` in favor of `to`.
+  pub fn message_for(from: &str, retire_at: Option<u64>, to: &TrustedKey) -> String {
+    fn timestamp(t: Option<u64>) -> String {
+      t.map(|t| t.to_string()).unwrap_or_default()
+    }
+
+    format!(
+      "tauri-key-rotation\nfrom:{from}\nretire-at:{}\nto:{}\nnot-before:{}\nnot-after:{}\n",
+      timestamp(retire_at),
+      to.pubkey,
+      timestamp(to.not_before),
+      timestamp(to.not_after),
+    )
+  }
+
+  /// Applies this rotation to a trusted key set at the given unix timestamp, in seconds,
+  /// assuming its signature was already verified.
+  ///
+  /// The retired key validity window is shortened to [`KeyRotation::retire_at`],
+  /// and the new key is added with the validity window of the statement if it is not trusted yet,
+  /// otherwise its validity window is narrowed to the one of the statement.
+  /// The keys whose validity window ended before `now`, e.g. the retired key once [`KeyRotation::retire_at`]
+  /// has passed, are removed from the set.
+  ///
+  /// Like [`TrustedKey::is_valid_at`], `now` must be the current time of the verifier.
+  pub fn apply(&self, keys: &mut Vec<TrustedKey>, now: u64) {
+    for key in keys.iter_mut().filter(|k| k.pubkey == self.from) {
+      key.not_after = earliest(key.not_after, self.retire_at);
+    }
+    match keys.iter_mut().find(|k| k.pubkey == self.to.pubkey) {
+      Some(key) => {
+        key.not_before = match (key.not_before, self.to.not_before) {
+          (Some(a), Some(b)) => Some(a.max(b)),
+          (a, b) => a.or(b),
+        };
+        key.not_after = earliest(key.not_after, self.to.not_after);
+      }
+      None => keys.push(self.to.clone()),
+    }
+    keys.retain(|key| key.not_after.map_or(true, |t| now <= t));
+  }
+}
+
+/// The earliest of two optional timestamps, where `None` is unbounded.
+fn earliest(a: Option<u64>, b: Option<u64>) -> Option<u64> {
+  match (a, b) {
+    (Some(a), Some(b)) => Some(a.min(b)),
+    (a, b) => a.or(b),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn rollout_bucket_is_stable() {
+    let bucket = RolloutBucket::from_install_id("7a3c1e7e-6f0c-4b7e-9a37-2d0f1c2b4e55");
+    assert_eq!(
+      bucket,
+      RolloutBucket::from_install_id("7a3c1e7e-6f0c-4b7e-9a37-2d0f1c2b4e55")
+    );
+    assert!(bucket.value() < 100);
+    assert!(bucket.is_included(100));
+    assert!(!bucket.is_included(0));
+  }
+
+  #[test]
+  fn manifest_channel_and_rollout() {
+    let manifest: UpdateManifest = serde_json::from_str(
+      r#"{
+        "version": "1.1.0-beta.1",
+        "channel": "beta",
+        "rollout": 50,
+        "platforms": {}
+      }"#,
+    )
+    .unwrap();
+    assert_eq!(manifest.channel, UpdateChannel::Beta);
+
+    let included = RolloutBucket(10);
+    let excluded = RolloutBucket(75);
+    assert!(!manifest.is_available_for(UpdateChannel::Stable, included));
+    assert!(manifest.is_available_for(UpdateChannel::Beta, included));
+    assert!(manifest.is_available_for(UpdateChannel::Nightly, included));
+    assert!(!manifest.is_available_for(UpdateChannel::Beta, excluded));
+
+    let stable: UpdateManifest =
+      serde_json::from_str(r#"{ "version": "1.0.0", "platforms": {} }"#).unwrap();
+    assert_eq!(stable.channel, UpdateChannel::Stable);
+    assert!(stable.is_available_for(UpdateChannel::Stable, excluded));
+  }
+
+  #[test]
+  fn latest_available_release() {
+    let manifest = |version: &str, channel, rollout| UpdateManifest {
+      version: version.into(),
+      notes: None,
+      pub_date: None,
+      channel,
+      rollout,
+      platforms: HashMap::new(),
+    };
+    let manifests = [
+      manifest("1.0.0", UpdateChannel::Stable, None),
+      manifest("1.1.0-beta.2", UpdateChannel::Beta, Some(20)),
+      manifest("1.1.0-beta.1", UpdateChannel::Beta, None),
+      manifest("1.2.0-nightly.1", UpdateChannel::Nightly, None),
+    ];
+    let version = |channel, bucket| {
+      UpdateManifest::latest_available(&manifests, channel, RolloutBucket(bucket))
+        .map(|manifest| manifest.version.as_str())
+    };
+    assert_eq!(version(UpdateChannel::Stable, 10), Some("1.0.0"));
+    assert_eq!(version(UpdateChannel::Beta, 10), Some("1.1.0-beta.2"));
+    assert_eq!(version(UpdateChannel::Beta, 50), Some("1.1.0-beta.1"));
+    assert_eq!(version(UpdateChannel::Nightly, 50), Some("1.2.0-nightly.1"));
+    assert_eq!(
+      UpdateManifest::latest_available(&[], UpdateChannel::Stable, RolloutBucket(0)),
+      None
+    );
+  }
+
+  #[test]
+  fn endpoint_variables() {
+    assert_eq!(
+      endpoint_url(
+        "https://example.com/{{channel}}/latest.json?bucket={{rollout_bucket}}",
+        UpdateChannel::Beta,
+        RolloutBucket(42)
+      ),
+      "https://example.com/beta/latest.json?bucket=42"
+    );
+  }
+
+  #[test]
+  fn platform_release_without_deltas() {
+    let release: PlatformRelease =
+      serde_json::from_str(r#"{ "url": "https://example.com/app.tar.gz", "signature": "sig" }"#)
+        .unwrap();
+    assert!(release.deltas.is_empty());
+    assert!(release.delta_from("1.0.0").is_none());
+    assert!(release.zsync.is_none());
+  }
+
+  #[test]
+  fn platform_release_delta_from() {
+    let release: PlatformRelease = serde_json::from_str(
+      r#"{
+        "url": "https://example.com/app.tar.gz",
+        "signature": "sig",
+        "sha256": "bb",
+        "deltas": [
+          {
+            "from": "1.0.0",
+            "url": "https://example.com/app.tar.gz.1.0.0.patch",
+            "signature": "patch-sig",
+            "baseSha256": "aa",
+            "sha256": "bb",
+            "format": "zstd-patch"
+          }
+        ]
+      }"#,
+    )
+    .unwrap();
+    let delta = release.delta_from("1.0.0").unwrap();
+    assert_eq!(delta.format, DeltaFormat::ZstdPatch);
+    assert_eq!(delta.base_sha256, "aa");
+    assert!(release.delta_from("0.9.0").is_none());
+  }
+
+  #[test]
+  fn partial_download_resume() {
+    let partial = PartialDownload {
+      version: "1.1.0".into(),
+      url: "https://example.com/app.tar.gz".into(),
+      downloaded: 512,
+      content_length: Some(1024),
+      etag: Some("\"abc\"".into()),
+    };
+    assert!(!partial.is_complete());
+    assert_eq!(partial.range_header().as_deref(), Some("bytes=512-"));
+    assert_eq!(partial.progress(), Some(0.5));
+    assert!(partial.can_resume("1.1.0", "https://example.com/app.tar.gz", Some("\"abc\"")));
+    assert!(!partial.can_resume("1.1.0", "https://example.com/app.tar.gz", Some("\"def\"")));
+    assert!(!partial.can_resume("1.2.0", "https://example.com/app.tar.gz", None));
+  }
+
+  #[test]
+  fn partial_download_state() {
+    let dir = std::env::temp_dir().join(format!("tauri-partial-download-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    let artifact = dir.join("app.tar.gz");
+    let url = "https://example.com/app.tar.gz";
+
+    // nothing downloaded yet
+    let mut state = PartialDownload::resume(&artifact, "1.1.0", url, Some("\"abc\"")).unwrap();
+    assert_eq!(state.downloaded, 0);
+    assert!(state.range_header().is_none());
+
+    // the bytes written after the last saved state are dropped
+    std::fs::write(&artifact, [0; 768]).unwrap();
+    state.downloaded = 512;
+    state.content_length = Some(1024);
+    state.save(&artifact).unwrap();
+    assert_eq!(
+      PartialDownload::load(&artifact).unwrap().as_ref(),
+      Some(&state)
+    );
+    let resumed = PartialDownload::resume(&artifact, "1.1.0", url, Some("\"abc\"")).unwrap();
+    assert_eq!(resumed, state);
+    assert_eq!(std::fs::metadata(&artifact).unwrap().len(), 512);
+
+    // the artifact changed on the server
+    let restarted = PartialDownload::resume(&artifact, "1.1.0", url, Some("\"def\"")).unwrap();
+    assert_eq!(restarted.downloaded, 0);
+    assert!(!artifact.exists());
+    assert!(PartialDownload::load(&artifact).unwrap().is_none());
+
+    std::fs::write(&artifact, [0; 1024]).unwrap();
+    state.downloaded = 1024;
+    state.save(&artifact).unwrap();
+    PartialDownload::finish(&artifact).unwrap();
+    assert!(PartialDownload::load(&artifact).unwrap().is_none());
+    assert!(artifact.exists());
+
+    std::fs::remove_dir_all(dir).unwrap();
+  }
+
+  #[test]
+  fn staged_update() {
+    let dir = std::env::temp_dir().join(format!("tauri-staged-update-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    let artifact = dir.join("app-setup.exe");
+    std::fs::write(&artifact, b"installer").unwrap();
+
+    let staged = StagedUpdate {
+      version: "1.1.0".into(),
+      current_version: "1.0.0".into(),
+      path: artifact.clone(),
+      trigger: InstallTrigger::OnQuit,
+      requires_elevation: InstallScope::PerMachine.requires_elevation(),
+    };
+    staged.stage(&dir).unwrap();
+    assert_eq!(
+      StagedUpdate::take(&dir, "1.0.0").unwrap(),
+      Some(staged.clone())
+    );
+    // taken only once
+    assert_eq!(StagedUpdate::take(&dir, "1.0.0").unwrap(), None);
+
+    // the app was updated by other means in the meantime
+    staged.stage(&dir).unwrap();
+    assert_eq!(StagedUpdate::take(&dir, "1.0.1").unwrap(), None);
+    assert!(!artifact.exists());
+
+    std::fs::remove_dir_all(dir).unwrap();
+  }
+
+  #[test]
+  fn install_scope() {
+    for scope in [InstallScope::PerUser, InstallScope::PerMachine] {
+      assert_eq!(scope.as_str().parse::<InstallScope>(), Ok(scope));
+    }
+    assert!(!InstallScope::PerUser.requires_elevation());
+    assert!(InstallScope::PerMachine.requires_elevation());
+    assert!("allUsers".parse::<InstallScope>().is_err());
+  }
+
+  #[test]
+  fn key_rotation() {
+    let mut keys = vec![TrustedKey {
+      pubkey: "old".into(),
+      not_before: None,
+      not_after: None,
+    }];
+    let rotation = KeyRotation {
+      from: "old".into(),
+      retire_at: Some(1000),
+      to: TrustedKey {
+        pubkey: "new".into(),
+        not_before: Some(500),
+        not_after: None,
+      },
+      signature: String::new(),
+    };
+    assert_eq!(
+      rotation.message(),
+      "tauri-key-rotation\nfrom:old\nretire-at:1000\nto:new\nnot-before:500\nnot-after:\n"
+    );
+
+    rotation.apply(&mut keys, 900);
+    assert_eq!(keys.len(), 2);
+    assert!(keys[0].is_valid_at(900));
+    assert!(!keys[0].is_valid_at(1001));
+    assert!(!keys[1].is_valid_at(400));
+    assert!(keys[1].is_valid_at(2000));
+
+    rotation.apply(&mut keys, 900);
+    assert_eq!(keys.len(), 2);
+
+    // the retired key is dropped once it is no longer valid
+    rotation.apply(&mut keys, 1001);
+    assert_eq!(keys.len(), 1);
+    assert_eq!(keys[0].pubkey, "new");
+
+    // the validity window of an already trusted key is narrowed to the one of the statement
+    let mut keys = vec![TrustedKey {
+      pubkey: "new".into(),
+      not_before: None,
+      not_after: Some(3000),
+    }];
+    rotation.apply(&mut keys, 900);
+    assert_eq!(
+      keys[0],
+      TrustedKey {
+        pubkey: "new".into(),
+        not_before: Some(500),
+        not_after: Some(3000),
+      }
+    );
+  }
+}
@@ -0,0 +1,153 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Benchmarks of the IPC layer on the `MockRuntime`: the invoke round-trip latency,
+//! the event emit throughput and the asset serving throughput of the `tauri` custom protocol.
+//!
+//! Run with `cargo bench --features test --bench ipc`.
+
+use std::{
+  borrow::Cow,
+  collections::HashMap,
+  sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+  },
+};
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use tauri::{
+  ipc::{CallbackFn, InvokeBody},
+  test::{get_ipc_response, get_protocol_response, mock_builder, mock_context, MockRuntime},
+  utils::assets::{AssetKey, CspHash},
+  webview::InvokeRequest,
+  App, Emitter, Listener, WebviewWindow, WebviewWindowBuilder,
+};
+
+const PAYLOAD_SIZES: &[usize] = &[16, 1024, 64 * 1024, 1024 * 1024];
+
+#[tauri::command]
+fn echo(payload: String) -> String {
+  payload
+}
+
+/// Serves `/asset-{size}.bin` files for each of the [`PAYLOAD_SIZES`].
+struct BenchAssets(HashMap<String, Vec<u8>>);
+
+impl BenchAssets {
+  fn new() -> Self {
+    Self(
+      PAYLOAD_SIZES
+        .iter()
+        .map(|size| (format!("asset-{size}.bin"), vec![b'a'; *size]))
+        .collect(),
+    )
+  }
+}
+
+impl tauri::Assets<MockRuntime> for BenchAssets {
+  fn get(&self, key: &AssetKey) -> Option<Cow<'_, [u8]>> {
+    self
+      .0
+      .get(key.as_ref().trim_start_matches('/'))
+      .map(|a| Cow::Borrowed(a.as_slice()))
+  }
+
+  fn iter(&self) -> Box<dyn Iterator<Item = (&str, &[u8])> + '_> {
+    Box::new(self.0.iter().map(|(k, v)| (k.as_str(), v.as_slice())))
+  }
+
+  fn csp_hashes(&self, _html_path: &AssetKey) -> Box<dyn Iterator<Item = CspHash<'_>> + '_> {
+    Box::new(std::iter::empty())
+  }
+}
+
+fn setup() -> (App<MockRuntime>, WebviewWindow<MockRuntime>) {
+  let app = mock_builder()
+    .invoke_handler(tauri::generate_handler![echo])
+    .build(mock_context(BenchAssets::new()))
+    .unwrap();
+  let webview = WebviewWindowBuilder::new(&app, "main", Default::default())
+    .build()
+    .unwrap();
+  (app, webview)
+}
+
+fn payload(size: usize) -> String {
+  "a".repeat(size)
+}
+
+fn invoke(c: &mut Criterion) {
+  let (_app, webview) = setup();
+  let mut group = c.benchmark_group("invoke");
+  for size in PAYLOAD_SIZES {
+    let body = InvokeBody::Json(serde_json::json!({ "payload": payload(*size) }));
+    group.throughput(Throughput::Bytes(*size as u64));
+    group.bench_with_input(BenchmarkId::from_parameter(size), &body, |b, body| {
+      b.iter(|| {
+        get_ipc_response(
+          &webview,
+          InvokeRequest {
+            cmd: "echo".into(),
+            callback: CallbackFn(0),
+            error: CallbackFn(1),
+            url: "http://tauri.localhost".parse().unwrap(),
+            body: body.clone(),
+            headers: Default::default(),
+            invoke_key: tauri::test::INVOKE_KEY.to_string(),
+          },
+        )
+        .unwrap()
+      })
+    });
+  }
+  group.finish();
+}
+
+fn emit(c: &mut Criterion) {
+  let (app, _webview) = setup();
+  let received = Arc::new(AtomicUsize::new(0));
+  let received_ = received.clone();
+  app.listen_any("bench", move |_| {
+    received_.fetch_add(1, Ordering::Relaxed);
+  });
+
+  let mut group = c.benchmark_group("emit");
+  for size in PAYLOAD_SIZES {
+    let payload = payload(*size);
+    group.throughput(Throughput::Bytes(*size as u64));
+    group.bench_with_input(BenchmarkId::from_parameter(size), &payload, |b, payload| {
+      b.iter(|| app.emit("bench", payload).unwrap())
+    });
+  }
+  group.finish();
+
+  assert!(received.load(Ordering::Relaxed) > 0);
+}
+
+fn serve_asset(c: &mut Criterion) {
+  let (_app, webview) = setup();
+  let mut group = c.benchmark_group("serve_asset");
+  for size in PAYLOAD_SIZES {
+    let uri = format!("tauri://localhost/asset-{size}.bin");
+    group.throughput(Throughput::Bytes(*size as u64));
+    group.bench_with_input(BenchmarkId::from_parameter(size), &uri, |b, uri| {
+      b.iter(|| {
+        let response = get_protocol_response(
+          &webview,
+          tauri::http::Request::get(uri.as_str())
+            .body(Vec::new())
+            .unwrap(),
+        )
+        .unwrap();
+        assert!(response.status().is_success());
+        response
+      })
+    });
+  }
+  group.finish();
+}
+
+criterion_group!(benches, invoke, emit, serve_asset);
+criterion_main!(benches);
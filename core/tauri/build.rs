@@ -68,6 +68,8 @@ const PLUGINS: &[(&str, &[(&str, bool)])] = &[
       ("available_monitors", true),
       ("cursor_position", true),
       ("theme", true),
+      ("workspace", true),
+      ("is_on_current_workspace", true),
       // setters
       ("center", false),
       ("request_user_attention", false),
@@ -86,10 +88,13 @@ const PLUGINS: &[(&str, &[(&str, bool)])] = &[
       ("destroy", false),
       ("set_decorations", false),
       ("set_shadow", false),
+      ("set_corner_preference", false),
+      ("set_border_color", false),
       ("set_effects", false),
       ("set_always_on_top", false),
       ("set_always_on_bottom", false),
       ("set_visible_on_all_workspaces", false),
+      ("move_to_workspace", false),
       ("set_content_protected", false),
       ("set_size", false),
       ("set_min_size", false),
@@ -97,6 +102,7 @@ const PLUGINS: &[(&str, &[(&str, bool)])] = &[
       ("set_max_size", false),
       ("set_position", false),
       ("set_fullscreen", false),
+      ("set_fullscreen_mode", false),
       ("set_focus", false),
       ("set_skip_taskbar", false),
       ("set_cursor_grab", false),
@@ -143,6 +149,7 @@ const PLUGINS: &[(&str, &[(&str, bool)])] = &[
       ("app_show", false),
       ("app_hide", false),
       ("default_window_icon", false),
+      ("open_with_default_app", false),
     ],
   ),
   (
@@ -151,11 +158,22 @@ const PLUGINS: &[(&str, &[(&str, bool)])] = &[
       ("new", true),
       ("from_bytes", true),
       ("from_path", true),
+      ("resize", false),
       ("rgba", true),
       ("size", true),
     ],
   ),
   ("core:resources", &[("close", true)]),
+  (
+    "core:i18n",
+    &[
+      ("messages", true),
+      ("translate", true),
+      ("locale", true),
+      ("available_locales", true),
+      ("set_locale", false),
+    ],
+  ),
   (
     "core:menu",
     &[
@@ -199,6 +217,12 @@ const PLUGINS: &[(&str, &[(&str, bool)])] = &[
       ("set_show_menu_on_left_click", true),
     ],
   ),
+  (
+    "core:input",
+    &[("listen_global", false), ("unlisten_global", false)],
+  ),
+  ("core:fs-watch", &[("watch", false)]),
+  ("core:sidecar", &[("proxy", false)]),
 ];
 
 // checks if the given Cargo feature is enabled.
@@ -238,6 +262,24 @@ fn main() {
   alias("desktop", !mobile);
   alias("mobile", mobile);
 
+  if has_feature("dynamic-plugins") {
+    // the dynamic plugins must be built with the same compiler as the app since they share Rust types
+    // without the version no library is loaded, see `UNKNOWN_RUSTC_VERSION`
+    let rustc = var("RUSTC").unwrap_or_else(|_| "rustc".into());
+    let version = std::process::Command::new(rustc)
+      .arg("-V")
+      .output()
+      .ok()
+      .filter(|output| output.status.success())
+      .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+      .filter(|version| !version.is_empty())
+      .unwrap_or_else(|| {
+        println!("cargo:warning=failed to get the rustc version, dynamic plugins won't be loaded");
+        "unknown".into()
+      });
+    println!("cargo:rustc-env=TAURI_RUSTC_VERSION={version}");
+  }
+
   let out_dir = PathBuf::from(var("OUT_DIR").unwrap());
 
   let checked_features_out_path = out_dir.join("checked_features");
@@ -316,6 +358,12 @@ fn main() {
         PathBuf::from(std::env::var_os("CARGO_MANIFEST_DIR").unwrap()).join("mobile/ios-api");
       tauri_utils::build::link_apple_library("Tauri", &lib_path);
       println!("cargo:ios_library_path={}", lib_path.display());
+
+      if has_feature("os-permissions") {
+        let permissions_lib_path = PathBuf::from(std::env::var_os("CARGO_MANIFEST_DIR").unwrap())
+          .join("mobile/ios-permissions");
+        tauri_utils::build::link_apple_library("TauriPermissions", &permissions_lib_path);
+      }
     }
   }
 
@@ -8,16 +8,34 @@ package {{package}}
 
 import android.os.Bundle
 import android.content.Intent
+import android.view.KeyEvent
 import app.tauri.plugin.PluginManager
 
 abstract class TauriActivity : WryActivity() {
   var pluginManager: PluginManager = PluginManager(this)
 
+  override fun onCreate(savedInstanceState: Bundle?) {
+    super.onCreate(savedInstanceState)
+    // the launch intent is delivered again when the activity is recreated
+    if (savedInstanceState == null) {
+      pluginManager.onCreate(intent)
+    }
+  }
+
   override fun onNewIntent(intent: Intent) {
     super.onNewIntent(intent)
     pluginManager.onNewIntent(intent)
   }
 
+  override fun onKeyDown(keyCode: Int, event: KeyEvent?): Boolean {
+    // the back handlers of the app take precedence over the webview history
+    if (keyCode == KeyEvent.KEYCODE_BACK && onBackPressedDispatcher.hasEnabledCallbacks()) {
+      onBackPressedDispatcher.onBackPressed()
+      return true
+    }
+    return super.onKeyDown(keyCode, event)
+  }
+
   override fun onResume() {
     super.onResume()
     pluginManager.onResume()
@@ -27,4 +45,19 @@ abstract class TauriActivity : WryActivity() {
     super.onPause()
     pluginManager.onPause()
   }
+
+  override fun onStop() {
+    super.onStop()
+    pluginManager.onStop()
+  }
+
+  override fun onDestroy() {
+    super.onDestroy()
+    pluginManager.onDestroy(isFinishing)
+  }
+
+  override fun onLowMemory() {
+    super.onLowMemory()
+    pluginManager.onLowMemory()
+  }
 }
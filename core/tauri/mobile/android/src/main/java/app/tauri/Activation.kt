@@ -0,0 +1,42 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+package app.tauri
+
+import android.content.Intent
+import android.net.Uri
+
+/**
+ * The activations of the app sent to Rust, i.e. the opened URLs and the shared content.
+ */
+object Activation {
+  fun fromIntent(intent: Intent): Map<String, Any?>? {
+    return when (intent.action) {
+      Intent.ACTION_VIEW -> intent.data?.let { mapOf("type" to "url", "url" to it.toString()) }
+      Intent.ACTION_SEND -> share(intent, listOfNotNull(stream(intent)))
+      Intent.ACTION_SEND_MULTIPLE -> share(intent, streams(intent))
+      else -> null
+    }
+  }
+
+  private fun share(intent: Intent, files: List<Uri>): Map<String, Any?> {
+    return mapOf(
+      "type" to "share",
+      "text" to intent.getStringExtra(Intent.EXTRA_TEXT),
+      "subject" to intent.getStringExtra(Intent.EXTRA_SUBJECT),
+      "mimeType" to intent.type,
+      "files" to files.map { it.toString() }
+    )
+  }
+
+  @Suppress("DEPRECATION")
+  private fun stream(intent: Intent): Uri? {
+    return intent.getParcelableExtra(Intent.EXTRA_STREAM)
+  }
+
+  @Suppress("DEPRECATION")
+  private fun streams(intent: Intent): List<Uri> {
+    return intent.getParcelableArrayListExtra<Uri>(Intent.EXTRA_STREAM) ?: listOf()
+  }
+}
@@ -0,0 +1,75 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+package app.tauri
+
+import android.Manifest
+import android.app.Activity
+import android.os.Build
+import androidx.core.app.NotificationManagerCompat
+import app.tauri.annotation.Command
+import app.tauri.annotation.Permission
+import app.tauri.annotation.TauriPlugin
+import app.tauri.plugin.Invoke
+import app.tauri.plugin.Plugin
+import app.tauri.plugin.RequestPermissionsArgs
+
+// READ_MEDIA_IMAGES and READ_MEDIA_VIDEO were added in Android 13, the photos are read with READ_EXTERNAL_STORAGE before that
+private const val PHOTOS_LEGACY = "photosLegacy"
+
+// the aliases match the names of the `OsPermission` Rust enum
+@TauriPlugin(
+  permissions = [
+    Permission(strings = [Manifest.permission.CAMERA], alias = "camera"),
+    Permission(strings = [Manifest.permission.RECORD_AUDIO], alias = "microphone"),
+    Permission(strings = [Manifest.permission.POST_NOTIFICATIONS], alias = "notifications"),
+    Permission(
+      strings = [Manifest.permission.ACCESS_COARSE_LOCATION, Manifest.permission.ACCESS_FINE_LOCATION],
+      alias = "location"
+    ),
+    Permission(
+      strings = [Manifest.permission.READ_MEDIA_IMAGES, Manifest.permission.READ_MEDIA_VIDEO],
+      alias = "photos"
+    ),
+    Permission(strings = [Manifest.permission.READ_EXTERNAL_STORAGE], alias = PHOTOS_LEGACY),
+    Permission(strings = [Manifest.permission.READ_CONTACTS], alias = "contacts")
+  ]
+)
+class PermissionsPlugin(private val activity: Activity): Plugin(activity) {
+  override fun getPermissionStates(): Map<String, PermissionState> {
+    val states = super.getPermissionStates().toMutableMap()
+    // POST_NOTIFICATIONS is a runtime permission since Android 13,
+    // before that the notifications are allowed unless the user disabled them in the settings
+    if (Build.VERSION.SDK_INT < Build.VERSION_CODES.TIRAMISU) {
+      states["notifications"] = if (NotificationManagerCompat.from(activity).areNotificationsEnabled()) {
+        PermissionState.GRANTED
+      } else {
+        PermissionState.DENIED
+      }
+    }
+    val photosLegacy = states.remove(PHOTOS_LEGACY)
+    if (Build.VERSION.SDK_INT < Build.VERSION_CODES.TIRAMISU && photosLegacy != null) {
+      states["photos"] = photosLegacy
+    }
+    return states
+  }
+
+  @Command
+  override fun requestPermissions(invoke: Invoke) {
+    if (Build.VERSION.SDK_INT >= Build.VERSION_CODES.TIRAMISU) {
+      super.requestPermissions(invoke)
+      return
+    }
+
+    val args = invoke.parseArgs(RequestPermissionsArgs::class.java)
+    val aliases = (args.permissions ?: listOf())
+      .map { if (it == "photos") PHOTOS_LEGACY else it }
+      .filter { alias -> handle?.annotation?.permissions?.any { it.alias == alias } == true }
+    if (aliases.isEmpty()) {
+      invoke.reject("No valid permission alias was requested of this plugin.")
+      return
+    }
+    requestPermissionForAliases(aliases.toTypedArray(), invoke, "checkPermissions")
+  }
+}
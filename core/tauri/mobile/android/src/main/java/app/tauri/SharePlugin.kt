@@ -0,0 +1,77 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+package app.tauri
+
+import android.app.Activity
+import android.content.ClipData
+import android.content.Intent
+import android.net.Uri
+import androidx.activity.result.ActivityResult
+import androidx.core.content.FileProvider
+import app.tauri.annotation.ActivityCallback
+import app.tauri.annotation.Command
+import app.tauri.annotation.InvokeArg
+import app.tauri.annotation.TauriPlugin
+import app.tauri.plugin.Invoke
+import app.tauri.plugin.JSObject
+import app.tauri.plugin.Plugin
+import java.io.File
+
+@InvokeArg
+internal class ShareItem {
+  lateinit var type: String
+  lateinit var value: String
+}
+
+@InvokeArg
+internal class ShareArgs {
+  var items: Array<ShareItem> = arrayOf()
+}
+
+@TauriPlugin
+class SharePlugin(private val activity: Activity): Plugin(activity) {
+  @Command
+  fun share(invoke: Invoke) {
+    val args = invoke.parseArgs(ShareArgs::class.java)
+    val text = args.items.filter { it.type == "text" || it.type == "url" }.joinToString("\n") { it.value }
+    val files = try {
+      args.items.filter { it.type == "file" }.map {
+        // the files must be in one of the directories shared by the FileProvider of the app template
+        FileProvider.getUriForFile(activity, activity.packageName + ".fileprovider", File(it.value))
+      }
+    } catch (e: IllegalArgumentException) {
+      invoke.reject("the file is not in a directory shared by the FileProvider: ${e.message}")
+      return
+    }
+
+    val intent = Intent(if (files.size > 1) Intent.ACTION_SEND_MULTIPLE else Intent.ACTION_SEND)
+    if (text.isNotEmpty()) {
+      intent.putExtra(Intent.EXTRA_TEXT, text)
+    }
+    when (files.size) {
+      0 -> intent.type = "text/plain"
+      1 -> intent.putExtra(Intent.EXTRA_STREAM, files[0])
+      else -> intent.putParcelableArrayListExtra(Intent.EXTRA_STREAM, ArrayList<Uri>(files))
+    }
+    if (files.isNotEmpty()) {
+      intent.type = files.map { activity.contentResolver.getType(it) ?: "*/*" }.distinct().singleOrNull() ?: "*/*"
+      // the receiving app is only granted access to the URIs of the clip data
+      val clip = ClipData.newRawUri(null, files[0])
+      files.drop(1).forEach { clip.addItem(ClipData.Item(it)) }
+      intent.clipData = clip
+      intent.addFlags(Intent.FLAG_GRANT_READ_URI_PERMISSION)
+    }
+
+    startActivityForResult(invoke, Intent.createChooser(intent, null), "shareResult")
+  }
+
+  // the chooser doesn't report the selected app nor whether it was dismissed
+  @ActivityCallback
+  fun shareResult(invoke: Invoke, result: ActivityResult) {
+    val outcome = JSObject()
+    outcome.put("completed", true)
+    invoke.resolve(outcome)
+  }
+}
@@ -0,0 +1,153 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+package app.tauri
+
+import android.app.Activity
+import android.view.View
+import android.view.WindowManager
+import android.webkit.WebView
+import androidx.activity.ComponentActivity
+import androidx.activity.OnBackPressedCallback
+import androidx.core.view.ViewCompat
+import androidx.core.view.WindowCompat
+import androidx.core.view.WindowInsetsCompat
+import app.tauri.annotation.Command
+import app.tauri.annotation.InvokeArg
+import app.tauri.annotation.TauriPlugin
+import app.tauri.plugin.Invoke
+import app.tauri.plugin.JSObject
+import app.tauri.plugin.Plugin
+
+@InvokeArg
+internal class EdgeToEdgeArgs {
+  var enabled: Boolean = false
+}
+
+@InvokeArg
+internal class BackHandlerArgs {
+  var enabled: Boolean = false
+}
+
+@InvokeArg
+internal class ResizeOnKeyboardArgs {
+  var enabled: Boolean = false
+}
+
+@TauriPlugin
+class WindowPlugin(private val activity: Activity): Plugin(activity) {
+  private var insets: JSObject? = null
+  private var webView: WebView? = null
+  private var edgeToEdge = false
+  private var resizeOnKeyboard = false
+  private var keyboardVisible = false
+
+  // enabled while Rust has back handlers, so the system handles the predictive back gesture otherwise
+  private val backCallback = object : OnBackPressedCallback(false) {
+    override fun handleOnBackPressed() {
+      val request = JSObject()
+      request.put("canGoBack", webView?.canGoBack() == true)
+      trigger("backRequested", request)
+    }
+  }
+
+  override fun load(webView: WebView) {
+    this.webView = webView
+    (activity as ComponentActivity).onBackPressedDispatcher.addCallback(backCallback)
+
+    ViewCompat.setOnApplyWindowInsetsListener(activity.window.decorView) { view, windowInsets ->
+      val insets = safeAreaInsets(windowInsets)
+      if (insets.toString() != this.insets?.toString()) {
+        this.insets = insets
+        trigger("insetsChanged", insets)
+      }
+      onKeyboardInsets(windowInsets)
+      ViewCompat.onApplyWindowInsets(view, windowInsets)
+    }
+  }
+
+  // the insets of the system bars and display cutouts, in density-independent pixels
+  private fun safeAreaInsets(windowInsets: WindowInsetsCompat?): JSObject {
+    val density = activity.resources.displayMetrics.density
+    val insets = windowInsets?.getInsets(WindowInsetsCompat.Type.systemBars() or WindowInsetsCompat.Type.displayCutout())
+    val obj = JSObject()
+    obj.put("top", (insets?.top ?: 0) / density)
+    obj.put("right", (insets?.right ?: 0) / density)
+    obj.put("bottom", (insets?.bottom ?: 0) / density)
+    obj.put("left", (insets?.left ?: 0) / density)
+    return obj
+  }
+
+  private fun onKeyboardInsets(windowInsets: WindowInsetsCompat) {
+    val visible = windowInsets.isVisible(WindowInsetsCompat.Type.ime())
+    val height = windowInsets.getInsets(WindowInsetsCompat.Type.ime()).bottom
+    if (visible != keyboardVisible) {
+      keyboardVisible = visible
+      val keyboard = JSObject()
+      keyboard.put("visible", visible)
+      keyboard.put("height", if (visible) height / activity.resources.displayMetrics.density else 0f)
+      trigger("keyboardChanged", keyboard)
+    }
+    // the system does not resize the edge-to-edge windows
+    if (edgeToEdge) {
+      val content = activity.findViewById<View>(android.R.id.content)
+      content.setPadding(0, 0, 0, if (resizeOnKeyboard && visible) height else 0)
+    }
+  }
+
+  @Command
+  fun getSafeAreaInsets(invoke: Invoke) {
+    invoke.resolve(safeAreaInsets(ViewCompat.getRootWindowInsets(activity.window.decorView)))
+  }
+
+  @Command
+  fun setEdgeToEdge(invoke: Invoke) {
+    val args = invoke.parseArgs(EdgeToEdgeArgs::class.java)
+    activity.runOnUiThread {
+      edgeToEdge = args.enabled
+      WindowCompat.setDecorFitsSystemWindows(activity.window, !args.enabled)
+      invoke.resolve()
+    }
+  }
+
+  @Command
+  fun setResizeOnKeyboard(invoke: Invoke) {
+    val args = invoke.parseArgs(ResizeOnKeyboardArgs::class.java)
+    activity.runOnUiThread {
+      resizeOnKeyboard = args.enabled
+      @Suppress("DEPRECATION")
+      activity.window.setSoftInputMode(
+        if (args.enabled) WindowManager.LayoutParams.SOFT_INPUT_ADJUST_RESIZE else WindowManager.LayoutParams.SOFT_INPUT_ADJUST_NOTHING
+      )
+      ViewCompat.requestApplyInsets(activity.window.decorView)
+      invoke.resolve()
+    }
+  }
+
+  @Command
+  fun setBackHandlerEnabled(invoke: Invoke) {
+    val args = invoke.parseArgs(BackHandlerArgs::class.java)
+    activity.runOnUiThread {
+      backCallback.isEnabled = args.enabled
+      invoke.resolve()
+    }
+  }
+
+  // the default behavior of a back request that was not consumed by the Rust handlers
+  @Command
+  fun goBack(invoke: Invoke) {
+    activity.runOnUiThread {
+      val webView = webView
+      if (webView != null && webView.canGoBack()) {
+        webView.goBack()
+      } else {
+        val enabled = backCallback.isEnabled
+        backCallback.isEnabled = false
+        (activity as ComponentActivity).onBackPressedDispatcher.onBackPressed()
+        backCallback.isEnabled = enabled
+      }
+      invoke.resolve()
+    }
+  }
+}
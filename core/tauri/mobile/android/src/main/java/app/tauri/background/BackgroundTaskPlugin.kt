@@ -0,0 +1,76 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+package app.tauri.background
+
+import android.app.Activity
+import androidx.work.Constraints
+import androidx.work.Data
+import androidx.work.ExistingPeriodicWorkPolicy
+import androidx.work.ExistingWorkPolicy
+import androidx.work.NetworkType
+import androidx.work.OneTimeWorkRequestBuilder
+import androidx.work.PeriodicWorkRequestBuilder
+import androidx.work.WorkManager
+import app.tauri.annotation.Command
+import app.tauri.annotation.InvokeArg
+import app.tauri.annotation.TauriPlugin
+import app.tauri.plugin.Invoke
+import app.tauri.plugin.Plugin
+import java.util.concurrent.TimeUnit
+
+@InvokeArg
+internal class ScheduleArgs {
+  lateinit var id: String
+  var interval: Long = 0
+  var periodic: Boolean = false
+  var requiresNetwork: Boolean = false
+}
+
+@InvokeArg
+internal class CancelArgs {
+  lateinit var id: String
+}
+
+@TauriPlugin
+class BackgroundTaskPlugin(private val activity: Activity): Plugin(activity) {
+  private val workManager = WorkManager.getInstance(activity.applicationContext)
+
+  @Command
+  fun schedule(invoke: Invoke) {
+    val args = invoke.parseArgs(ScheduleArgs::class.java)
+
+    val constraints = Constraints.Builder()
+      .setRequiredNetworkType(if (args.requiresNetwork) NetworkType.CONNECTED else NetworkType.NOT_REQUIRED)
+      .build()
+    val data = Data.Builder().putString(BackgroundTaskWorker.TASK_ID, args.id).build()
+
+    if (args.periodic) {
+      // WorkManager does not run periodic work more often than every 15 minutes
+      val interval = maxOf(args.interval, TimeUnit.MINUTES.toMillis(15))
+      val request = PeriodicWorkRequestBuilder<BackgroundTaskWorker>(interval, TimeUnit.MILLISECONDS)
+        .setInitialDelay(interval, TimeUnit.MILLISECONDS)
+        .setConstraints(constraints)
+        .setInputData(data)
+        .build()
+      workManager.enqueueUniquePeriodicWork(args.id, ExistingPeriodicWorkPolicy.UPDATE, request)
+    } else {
+      val request = OneTimeWorkRequestBuilder<BackgroundTaskWorker>()
+        .setInitialDelay(args.interval, TimeUnit.MILLISECONDS)
+        .setConstraints(constraints)
+        .setInputData(data)
+        .build()
+      workManager.enqueueUniqueWork(args.id, ExistingWorkPolicy.REPLACE, request)
+    }
+
+    invoke.resolve()
+  }
+
+  @Command
+  fun cancel(invoke: Invoke) {
+    val args = invoke.parseArgs(CancelArgs::class.java)
+    workManager.cancelUniqueWork(args.id)
+    invoke.resolve()
+  }
+}
@@ -0,0 +1,29 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+package app.tauri.background
+
+import android.content.Context
+import androidx.work.Worker
+import androidx.work.WorkerParameters
+import app.tauri.Logger
+
+class BackgroundTaskWorker(context: Context, params: WorkerParameters): Worker(context, params) {
+  companion object {
+    const val TASK_ID = "app.tauri.background.TASK_ID"
+  }
+
+  override fun doWork(): Result {
+    val id = inputData.getString(TASK_ID) ?: return Result.failure()
+    return try {
+      if (runBackgroundTask(id)) Result.success() else Result.retry()
+    } catch (e: UnsatisfiedLinkError) {
+      // the app library is not loaded when the system starts the process only to run the work
+      Logger.error("background task $id triggered before the app started, retrying later")
+      Result.retry()
+    }
+  }
+
+  private external fun runBackgroundTask(id: String): Boolean
+}
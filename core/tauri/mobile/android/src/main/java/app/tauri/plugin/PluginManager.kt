@@ -13,6 +13,7 @@ import androidx.activity.result.ActivityResultLauncher
 import androidx.activity.result.contract.ActivityResultContracts
 import androidx.appcompat.app.AppCompatActivity
 import app.tauri.annotation.InvokeArg
+import app.tauri.Activation
 import app.tauri.FsUtils
 import app.tauri.JniMethod
 import app.tauri.Logger
@@ -66,22 +67,47 @@ class PluginManager(val activity: AppCompatActivity) {
       .registerModule(SimpleModule().addDeserializer(Channel::class.java, channelDeserializer))
   }
 
+  fun onCreate(intent: Intent) {
+    sendActivation(intent)
+  }
+
   fun onNewIntent(intent: Intent) {
     for (plugin in plugins.values) {
       plugin.instance.onNewIntent(intent)
     }
+    sendActivation(intent)
+  }
+
+  private fun sendActivation(intent: Intent) {
+    Activation.fromIntent(intent)?.let { onActivation(jsonMapper.writeValueAsString(it)) }
   }
 
   fun onPause() {
     for (plugin in plugins.values) {
       plugin.instance.onPause()
     }
+    onLifecycleEvent(LIFECYCLE_INACTIVE)
   }
 
   fun onResume() {
     for (plugin in plugins.values) {
       plugin.instance.onResume()
     }
+    onLifecycleEvent(LIFECYCLE_FOREGROUND)
+  }
+
+  fun onStop() {
+    onLifecycleEvent(LIFECYCLE_BACKGROUND)
+  }
+
+  fun onDestroy(isFinishing: Boolean) {
+    if (isFinishing) {
+      onLifecycleEvent(LIFECYCLE_TERMINATE)
+    }
+  }
+
+  fun onLowMemory() {
+    onMemoryWarning()
   }
 
   fun startActivityForResult(intent: Intent, callback: ActivityResultCallback) {
@@ -158,6 +184,12 @@ class PluginManager(val activity: AppCompatActivity) {
   }
 
   companion object {
+    // the codes of the lifecycle events in Rust
+    private const val LIFECYCLE_FOREGROUND = 0
+    private const val LIFECYCLE_INACTIVE = 1
+    private const val LIFECYCLE_BACKGROUND = 2
+    private const val LIFECYCLE_TERMINATE = 3
+
     fun<T> loadConfig(context: Context, plugin: String, cls: Class<T>): T {
       val tauriConfigJson = FsUtils.readAsset(context.assets, "tauri.conf.json")
       val mapper = ObjectMapper()
@@ -169,6 +201,9 @@ class PluginManager(val activity: AppCompatActivity) {
 
   private external fun handlePluginResponse(id: Int, success: String?, error: String?)
   private external fun sendChannelData(id: Long, data: String)
+  private external fun onMemoryWarning()
+  private external fun onLifecycleEvent(event: Int)
+  private external fun onActivation(payload: String)
 }
 
 @InvokeArg
@@ -0,0 +1,50 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+package app.tauri.push
+
+import android.Manifest
+import android.app.Activity
+import android.content.Intent
+import android.content.pm.PackageManager
+import android.os.Build
+import app.tauri.annotation.Command
+import app.tauri.annotation.TauriPlugin
+import app.tauri.plugin.Invoke
+import app.tauri.plugin.Plugin
+import com.google.firebase.messaging.FirebaseMessaging
+
+@TauriPlugin
+class PushNotificationPlugin(private val activity: Activity): Plugin(activity) {
+  @Command
+  fun register(invoke: Invoke) {
+    if (Build.VERSION.SDK_INT >= Build.VERSION_CODES.TIRAMISU
+      && activity.checkSelfPermission(Manifest.permission.POST_NOTIFICATIONS) != PackageManager.PERMISSION_GRANTED) {
+      activity.requestPermissions(arrayOf(Manifest.permission.POST_NOTIFICATIONS), 0)
+    }
+
+    try {
+      FirebaseMessaging.getInstance().token.addOnCompleteListener { task ->
+        if (task.isSuccessful) {
+          PushNotifications.deliverToken(task.result)
+          invoke.resolve()
+        } else {
+          invoke.reject(task.exception?.message ?: "failed to get the push token")
+        }
+      }
+    } catch (e: NoClassDefFoundError) {
+      invoke.reject("the com.google.firebase:firebase-messaging dependency is required for push notifications")
+    }
+  }
+
+  @Command
+  fun deliverLaunchNotification(invoke: Invoke) {
+    PushNotifications.deliverIntent(activity, activity.intent, true)
+    invoke.resolve()
+  }
+
+  override fun onNewIntent(intent: Intent) {
+    PushNotifications.deliverIntent(activity, intent, false)
+  }
+}
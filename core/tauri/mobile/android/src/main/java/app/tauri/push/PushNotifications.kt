@@ -0,0 +1,82 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+package app.tauri.push
+
+import android.app.Activity
+import android.content.Intent
+import android.os.Build
+import app.tauri.Logger
+import app.tauri.plugin.JSObject
+
+object PushNotifications {
+  fun deliverToken(token: String) {
+    try {
+      onPushToken(token)
+    } catch (e: UnsatisfiedLinkError) {
+      Logger.error("push token delivered before the app started")
+    }
+  }
+
+  fun deliverNotification(title: String?, body: String?, data: Map<String, String>, opened: Boolean, launch: Boolean) {
+    val payload = JSObject()
+    payload.put("title", title)
+    payload.put("body", body)
+    val dataObject = JSObject()
+    for ((key, value) in data) {
+      dataObject.put(key, value)
+    }
+    payload.put("data", dataObject)
+    payload.put("opened", opened)
+    payload.put("launch", launch)
+    try {
+      onPushNotification(payload.toString())
+    } catch (e: UnsatisfiedLinkError) {
+      Logger.error("push notification delivered before the app started")
+    }
+  }
+
+  // Delivers the notification the user tapped to open the activity, if any.
+  //
+  // Must be called with the intent the activity is currently handling, i.e. its launch intent or from `onNewIntent`,
+  // since the activity referrer is the package that sent that intent.
+  fun deliverIntent(activity: Activity, intent: Intent?, launch: Boolean) {
+    val extras = intent?.extras ?: return
+    // set by Firebase Cloud Messaging on the intents of the notifications it displays
+    if (!extras.containsKey("google.message_id")) {
+      return
+    }
+    // any app can start the activity with the same extras, but the notifications are opened by this app
+    if (!isSentByApp(activity, intent)) {
+      Logger.error("ignoring a push notification intent not sent by the app")
+      return
+    }
+    val data = mutableMapOf<String, String>()
+    for (key in extras.keySet()) {
+      if (!key.startsWith("google.") && !key.startsWith("gcm.")) {
+        extras.getString(key)?.let { data[key] = it }
+      }
+    }
+    deliverNotification(null, null, data, true, launch)
+  }
+
+  // Whether the intent was sent by this app, e.g. through the pending intent of a notification.
+  private fun isSentByApp(activity: Activity, intent: Intent): Boolean {
+    if (Build.VERSION.SDK_INT < Build.VERSION_CODES.LOLLIPOP_MR1) {
+      return false
+    }
+    // the referrer extras are set by the sender, and the ones of the activity intent
+    // take precedence over the package the system reports
+    for (i in listOfNotNull(intent, activity.intent)) {
+      if (i.hasExtra(Intent.EXTRA_REFERRER) || i.hasExtra(Intent.EXTRA_REFERRER_NAME)) {
+        return false
+      }
+    }
+    val referrer = activity.referrer ?: return false
+    return referrer.scheme == "android-app" && referrer.host == activity.packageName
+  }
+
+  private external fun onPushToken(token: String)
+  private external fun onPushNotification(payload: String)
+}
@@ -0,0 +1,25 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+package app.tauri.push
+
+import com.google.firebase.messaging.FirebaseMessagingService
+import com.google.firebase.messaging.RemoteMessage
+
+// Receives the tokens and the notifications received while the app is in the foreground.
+class TauriMessagingService: FirebaseMessagingService() {
+  override fun onNewToken(token: String) {
+    PushNotifications.deliverToken(token)
+  }
+
+  override fun onMessageReceived(message: RemoteMessage) {
+    PushNotifications.deliverNotification(
+      message.notification?.title,
+      message.notification?.body,
+      message.data,
+      false,
+      false
+    )
+  }
+}
@@ -0,0 +1,42 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+import Foundation
+import SwiftRs
+import UIKit
+
+typealias ActivationCallback = @convention(c) (UnsafePointer<CChar>) -> Void
+
+/// Sends the universal links opening the app to Rust.
+///
+/// The custom URL schemes are reported by tao as opened URLs.
+@_cdecl("set_activation_callback")
+func setActivationCallback(callback: ActivationCallback) {
+  guard let delegate = UIApplication.shared.delegate else {
+    return
+  }
+
+  // the app delegate is created by tao
+  let continueUserActivity:
+    @convention(block) (AnyObject, UIApplication, NSUserActivity, AnyObject?) -> Bool = {
+      _, _, userActivity, _ in
+      guard userActivity.activityType == NSUserActivityTypeBrowsingWeb,
+        let url = userActivity.webpageURL
+      else {
+        return false
+      }
+      let payload = ["type": "url", "url": url.absoluteString]
+      guard let json = try? JSONSerialization.data(withJSONObject: payload),
+        let json = String(data: json, encoding: .utf8)
+      else {
+        return false
+      }
+      json.withCString { callback($0) }
+      return true
+    }
+  class_addMethod(
+    type(of: delegate),
+    #selector(UIApplicationDelegate.application(_:continue:restorationHandler:)),
+    imp_implementationWithBlock(continueUserActivity), "B@:@@@")
+}
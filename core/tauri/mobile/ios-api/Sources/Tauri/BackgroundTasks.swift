@@ -0,0 +1,107 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+import BackgroundTasks
+import Foundation
+import SwiftRs
+
+struct BackgroundTaskSchedule {
+  let delay: Double
+  let periodic: Bool
+  let requiresNetwork: Bool
+}
+
+/// The background tasks scheduled with BGTaskScheduler, running the handlers registered in Rust.
+class BackgroundTaskManager {
+  static let shared = BackgroundTaskManager()
+
+  private let lock = NSLock()
+  private var registered = Set<String>()
+  private var schedules = [String: BackgroundTaskSchedule]()
+
+  @available(iOS 13.0, *)
+  func register(
+    _ identifier: String, callback: @escaping @convention(c) (UnsafePointer<CChar>) -> Bool
+  ) {
+    lock.lock()
+    defer { lock.unlock() }
+    // BGTaskScheduler throws if an identifier is registered twice
+    if registered.contains(identifier) {
+      return
+    }
+    registered.insert(identifier)
+
+    BGTaskScheduler.shared.register(forTaskWithIdentifier: identifier, using: .global()) {
+      task in
+      // the next run of periodic tasks must be scheduled again
+      if let schedule = self.schedule(identifier), schedule.periodic {
+        _ = self.submit(identifier, schedule)
+      }
+      task.expirationHandler = {
+        Logger.error("background task \(identifier) expired")
+      }
+      let success = identifier.withCString { callback($0) }
+      task.setTaskCompleted(success: success)
+    }
+  }
+
+  @available(iOS 13.0, *)
+  func submit(_ identifier: String, _ schedule: BackgroundTaskSchedule) -> Bool {
+    let request = BGProcessingTaskRequest(identifier: identifier)
+    request.earliestBeginDate = Date(timeIntervalSinceNow: schedule.delay)
+    request.requiresNetworkConnectivity = schedule.requiresNetwork
+    do {
+      try BGTaskScheduler.shared.submit(request)
+    } catch {
+      Logger.error("failed to schedule background task \(identifier): \(error)")
+      return false
+    }
+    lock.lock()
+    schedules[identifier] = schedule
+    lock.unlock()
+    return true
+  }
+
+  @available(iOS 13.0, *)
+  func cancel(_ identifier: String) {
+    lock.lock()
+    schedules.removeValue(forKey: identifier)
+    lock.unlock()
+    BGTaskScheduler.shared.cancel(taskRequestWithIdentifier: identifier)
+  }
+
+  private func schedule(_ identifier: String) -> BackgroundTaskSchedule? {
+    lock.lock()
+    defer { lock.unlock() }
+    return schedules[identifier]
+  }
+}
+
+@_cdecl("register_background_task")
+func registerBackgroundTask(
+  identifier: SRString, callback: @escaping @convention(c) (UnsafePointer<CChar>) -> Bool
+) {
+  if #available(iOS 13.0, *) {
+    BackgroundTaskManager.shared.register(identifier.toString(), callback: callback)
+  }
+}
+
+@_cdecl("schedule_background_task")
+func scheduleBackgroundTask(
+  identifier: SRString, delay: Double, periodic: Bool, requiresNetwork: Bool
+) -> Bool {
+  if #available(iOS 13.0, *) {
+    return BackgroundTaskManager.shared.submit(
+      identifier.toString(),
+      BackgroundTaskSchedule(delay: delay, periodic: periodic, requiresNetwork: requiresNetwork))
+  }
+  return false
+}
+
+@_cdecl("cancel_background_task")
+func cancelBackgroundTask(identifier: SRString) {
+  if #available(iOS 13.0, *) {
+    BackgroundTaskManager.shared.cancel(identifier.toString())
+  }
+}
@@ -0,0 +1,120 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+import Foundation
+import SwiftRs
+import UIKit
+import UserNotifications
+
+typealias PushCallback = @convention(c) (UnsafePointer<CChar>) -> Void
+
+/// Delivers the APNs device token and the notifications to Rust.
+class PushNotificationManager: NSObject, UNUserNotificationCenterDelegate {
+  static let shared = PushNotificationManager()
+
+  var onToken: PushCallback?
+  var onNotification: PushCallback?
+  /// Whether the app finished launching, so a tapped notification did not launch it.
+  private var launched = false
+  private var delegateMethodsAdded = false
+
+  override init() {
+    super.init()
+    NotificationCenter.default.addObserver(
+      forName: UIApplication.didBecomeActiveNotification, object: nil, queue: .main
+    ) { [weak self] _ in
+      self?.launched = true
+    }
+  }
+
+  /// Adds the methods receiving the device token to the app delegate, which is created by tao.
+  func addDelegateMethods() {
+    guard !delegateMethodsAdded, let delegate = UIApplication.shared.delegate else {
+      return
+    }
+    delegateMethodsAdded = true
+    let delegateClass: AnyClass = type(of: delegate)
+
+    let didRegister: @convention(block) (AnyObject, UIApplication, Data) -> Void = {
+      _, _, deviceToken in
+      let token = deviceToken.map { String(format: "%02x", $0) }.joined()
+      token.withCString { self.onToken?($0) }
+    }
+    class_addMethod(
+      delegateClass,
+      #selector(
+        UIApplicationDelegate.application(_:didRegisterForRemoteNotificationsWithDeviceToken:)),
+      imp_implementationWithBlock(didRegister), "v@:@@")
+
+    let didFail: @convention(block) (AnyObject, UIApplication, Error) -> Void = { _, _, error in
+      Logger.error("failed to register for push notifications: \(error)")
+    }
+    class_addMethod(
+      delegateClass,
+      #selector(
+        UIApplicationDelegate.application(_:didFailToRegisterForRemoteNotificationsWithError:)),
+      imp_implementationWithBlock(didFail), "v@:@@")
+  }
+
+  func deliver(_ content: UNNotificationContent, opened: Bool, launch: Bool) {
+    var data = [String: Any]()
+    for (key, value) in content.userInfo {
+      if let key = key as? String, key != "aps", JSONSerialization.isValidJSONObject([value]) {
+        data[key] = value
+      }
+    }
+    let payload: [String: Any] = [
+      "title": content.title,
+      "body": content.body,
+      "data": data,
+      "opened": opened,
+      "launch": launch,
+    ]
+    guard let json = try? JSONSerialization.data(withJSONObject: payload),
+      let json = String(data: json, encoding: .utf8)
+    else {
+      return
+    }
+    json.withCString { onNotification?($0) }
+  }
+
+  func userNotificationCenter(
+    _ center: UNUserNotificationCenter, willPresent notification: UNNotification,
+    withCompletionHandler completionHandler: @escaping (UNNotificationPresentationOptions) -> Void
+  ) {
+    deliver(notification.request.content, opened: false, launch: false)
+    completionHandler([.alert, .sound, .badge])
+  }
+
+  func userNotificationCenter(
+    _ center: UNUserNotificationCenter, didReceive response: UNNotificationResponse,
+    withCompletionHandler completionHandler: @escaping () -> Void
+  ) {
+    deliver(response.notification.request.content, opened: true, launch: !launched)
+    completionHandler()
+  }
+}
+
+@_cdecl("set_push_notification_callbacks")
+func setPushNotificationCallbacks(onToken: PushCallback, onNotification: PushCallback) {
+  let manager = PushNotificationManager.shared
+  manager.onToken = onToken
+  manager.onNotification = onNotification
+  // must be set before the app finishes launching to receive the notification that launched it
+  UNUserNotificationCenter.current().delegate = manager
+}
+
+@_cdecl("register_push_notifications")
+func registerPushNotifications() {
+  UNUserNotificationCenter.current().requestAuthorization(options: [.alert, .sound, .badge]) {
+    _, error in
+    if let error = error {
+      Logger.error("failed to request the notifications permission: \(error)")
+    }
+    DispatchQueue.main.async {
+      PushNotificationManager.shared.addDelegateMethods()
+      UIApplication.shared.registerForRemoteNotifications()
+    }
+  }
+}
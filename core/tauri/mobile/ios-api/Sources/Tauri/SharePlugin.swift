@@ -0,0 +1,59 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+import SwiftRs
+import UIKit
+
+struct ShareItem: Decodable {
+  let type: String
+  let value: String
+}
+
+struct ShareArgs: Decodable {
+  let items: [ShareItem]
+}
+
+struct ShareOutcome: Encodable {
+  let completed: Bool
+  let target: String?
+}
+
+/// Presents the share sheet.
+class SharePlugin: Plugin {
+  @objc public func share(_ invoke: Invoke) throws {
+    let args = try invoke.parseArgs(ShareArgs.self)
+    let items: [Any] = args.items.compactMap { item in
+      switch item.type {
+      case "text": return item.value
+      case "url": return URL(string: item.value)
+      case "file": return URL(fileURLWithPath: item.value)
+      default: return nil
+      }
+    }
+
+    DispatchQueue.main.async {
+      guard let viewController = self.manager.viewController else {
+        invoke.reject("the share sheet requires a view controller")
+        return
+      }
+      let controller = UIActivityViewController(activityItems: items, applicationActivities: nil)
+      controller.completionWithItemsHandler = { activityType, completed, _, error in
+        if let error = error {
+          invoke.reject(error.localizedDescription, error: error)
+        } else {
+          invoke.resolve(
+            ShareOutcome(completed: completed, target: completed ? activityType?.rawValue : nil))
+        }
+      }
+      // presented as a popover on iPad
+      UIUtils.centerPopover(rootViewController: viewController, popoverController: controller)
+      viewController.present(controller, animated: true, completion: nil)
+    }
+  }
+}
+
+@_cdecl("init_plugin_share")
+func initSharePlugin() -> Plugin {
+  return SharePlugin()
+}
@@ -110,6 +110,31 @@ func onWebviewCreated(webview: WKWebView, viewController: UIViewController) {
   PluginManager.shared.onWebviewCreated(webview)
 }
 
+@_cdecl("on_memory_warning")
+func onMemoryWarning(callback: @escaping @convention(c) () -> Void) {
+  NotificationCenter.default.addObserver(
+    forName: UIApplication.didReceiveMemoryWarningNotification, object: nil, queue: .main
+  ) { _ in
+    callback()
+  }
+}
+
+@_cdecl("on_lifecycle_event")
+func onLifecycleEvent(callback: @escaping @convention(c) (Int32) -> Void) {
+  // the codes of the lifecycle events in Rust
+  let events: [(Notification.Name, Int32)] = [
+    (UIApplication.didBecomeActiveNotification, 0),
+    (UIApplication.willResignActiveNotification, 1),
+    (UIApplication.didEnterBackgroundNotification, 2),
+    (UIApplication.willTerminateNotification, 3),
+  ]
+  for (name, code) in events {
+    NotificationCenter.default.addObserver(forName: name, object: nil, queue: .main) { _ in
+      callback(code)
+    }
+  }
+}
+
 @_cdecl("run_plugin_command")
 func runCommand(
   id: Int,
@@ -0,0 +1,142 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+import SwiftRs
+import UIKit
+import WebKit
+
+struct SafeAreaInsets: Encodable, Equatable {
+  let top: Double
+  let right: Double
+  let bottom: Double
+  let left: Double
+}
+
+struct EdgeToEdgeArgs: Decodable {
+  let enabled: Bool
+}
+
+struct ResizeOnKeyboardArgs: Decodable {
+  let enabled: Bool
+}
+
+struct KeyboardState: Encodable {
+  let visible: Bool
+  let height: Double
+}
+
+/// Reports the safe area insets and the keyboard of the window of the webview.
+class WindowPlugin: Plugin {
+  private var webview: WKWebView?
+  private var edgeToEdge = false
+  private var insets: SafeAreaInsets?
+  private var resizeOnKeyboard = false
+  /// The frame of the webview before it was resized for the keyboard.
+  private var frameBeforeKeyboard: CGRect?
+
+  override func load(webview: WKWebView) {
+    self.webview = webview
+    applyEdgeToEdge()
+    // the safe area changes when the device is rotated
+    NotificationCenter.default.addObserver(
+      forName: UIDevice.orientationDidChangeNotification, object: nil, queue: .main
+    ) { [weak self] _ in
+      // wait for the layout to be updated
+      DispatchQueue.main.async {
+        self?.notifyInsetsChanged()
+      }
+    }
+    NotificationCenter.default.addObserver(
+      forName: UIResponder.keyboardWillShowNotification, object: nil, queue: .main
+    ) { [weak self] notification in
+      let frame =
+        (notification.userInfo?[UIResponder.keyboardFrameEndUserInfoKey] as? NSValue)?
+        .cgRectValue ?? .zero
+      self?.keyboardWillShow(frame)
+    }
+    NotificationCenter.default.addObserver(
+      forName: UIResponder.keyboardWillHideNotification, object: nil, queue: .main
+    ) { [weak self] _ in
+      self?.restoreFrame()
+    }
+    NotificationCenter.default.addObserver(
+      forName: UIResponder.keyboardDidHideNotification, object: nil, queue: .main
+    ) { [weak self] _ in
+      try? self?.trigger("keyboardChanged", data: KeyboardState(visible: false, height: 0))
+    }
+  }
+
+  private func keyboardWillShow(_ keyboardFrame: CGRect) {
+    try? trigger(
+      "keyboardChanged", data: KeyboardState(visible: true, height: Double(keyboardFrame.height)))
+
+    guard resizeOnKeyboard, let webview = webview, let window = webview.window else {
+      return
+    }
+    let frame = frameBeforeKeyboard ?? webview.frame
+    // the bottom of the webview in the coordinates of the window, which are the keyboard coordinates
+    let bottom = webview.superview?.convert(frame, to: window).maxY ?? frame.maxY
+    let height = max(frame.height - max(bottom - keyboardFrame.minY, 0), 0)
+    frameBeforeKeyboard = frame
+    webview.frame = CGRect(x: frame.minX, y: frame.minY, width: frame.width, height: height)
+  }
+
+  private func restoreFrame() {
+    if let frame = frameBeforeKeyboard {
+      webview?.frame = frame
+      frameBeforeKeyboard = nil
+    }
+  }
+
+  private func safeAreaInsets() -> SafeAreaInsets {
+    let insets = webview?.window?.safeAreaInsets ?? .zero
+    return SafeAreaInsets(
+      top: Double(insets.top), right: Double(insets.right), bottom: Double(insets.bottom),
+      left: Double(insets.left))
+  }
+
+  private func notifyInsetsChanged() {
+    let insets = safeAreaInsets()
+    if insets != self.insets {
+      self.insets = insets
+      try? trigger("insetsChanged", data: insets)
+    }
+  }
+
+  private func applyEdgeToEdge() {
+    webview?.scrollView.contentInsetAdjustmentBehavior = edgeToEdge ? .never : .automatic
+  }
+
+  @objc public func getSafeAreaInsets(_ invoke: Invoke) {
+    DispatchQueue.main.async {
+      invoke.resolve(self.safeAreaInsets())
+    }
+  }
+
+  @objc public func setEdgeToEdge(_ invoke: Invoke) throws {
+    let args = try invoke.parseArgs(EdgeToEdgeArgs.self)
+    DispatchQueue.main.async {
+      // applied when the webview is loaded if it is not created yet
+      self.edgeToEdge = args.enabled
+      self.applyEdgeToEdge()
+      invoke.resolve()
+    }
+  }
+
+  @objc public func setResizeOnKeyboard(_ invoke: Invoke) throws {
+    let args = try invoke.parseArgs(ResizeOnKeyboardArgs.self)
+    DispatchQueue.main.async {
+      self.resizeOnKeyboard = args.enabled
+      if !args.enabled {
+        self.restoreFrame()
+      }
+      invoke.resolve()
+    }
+  }
+}
+
+@_cdecl("init_plugin_window")
+func initWindowPlugin() -> Plugin {
+  return WindowPlugin()
+}
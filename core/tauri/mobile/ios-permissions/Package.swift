@@ -0,0 +1,34 @@
+// swift-tools-version:5.3
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+import PackageDescription
+
+// Only linked with the `os-permissions` Cargo feature: it links the Contacts, Photos, CoreLocation and AVFoundation frameworks,
+// and App Store Connect requires the usage descriptions of the frameworks an app links even if it never calls them.
+let package = Package(
+  name: "TauriPermissions",
+  platforms: [
+    .macOS(.v10_13),
+    .iOS(.v11),
+  ],
+  products: [
+    .library(
+      name: "TauriPermissions",
+      type: .static,
+      targets: ["TauriPermissions"])
+  ],
+  dependencies: [
+    .package(name: "Tauri", path: "../ios-api")
+  ],
+  targets: [
+    .target(
+      name: "TauriPermissions",
+      dependencies: [
+        .byName(name: "Tauri")
+      ],
+      path: "Sources"
+    )
+  ]
+)
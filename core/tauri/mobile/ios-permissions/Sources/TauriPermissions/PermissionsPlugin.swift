@@ -0,0 +1,158 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+import AVFoundation
+import Contacts
+import CoreLocation
+import Photos
+import SwiftRs
+import Tauri
+import UserNotifications
+
+struct RequestPermissionsArgs: Decodable {
+  let permissions: [String]
+}
+
+/// Checks and requests the permissions of the `OsPermission` Rust enum.
+class PermissionsPlugin: Plugin, CLLocationManagerDelegate {
+  private var locationManager: CLLocationManager?
+  private var locationCompletion: (() -> Void)?
+
+  @objc override func checkPermissions(_ invoke: Invoke) {
+    states { invoke.resolve($0) }
+  }
+
+  @objc override func requestPermissions(_ invoke: Invoke) {
+    do {
+      let args = try invoke.parseArgs(RequestPermissionsArgs.self)
+      request(args.permissions[...]) {
+        self.states { invoke.resolve($0) }
+      }
+    } catch {
+      invoke.reject(error.localizedDescription)
+    }
+  }
+
+  /// Requests the permissions one after the other, since iOS displays a single prompt at a time.
+  private func request(_ permissions: ArraySlice<String>, completion: @escaping () -> Void) {
+    guard let permission = permissions.first else {
+      completion()
+      return
+    }
+    let next = {
+      DispatchQueue.main.async {
+        self.request(permissions.dropFirst(), completion: completion)
+      }
+    }
+
+    switch permission {
+    case "camera":
+      AVCaptureDevice.requestAccess(for: .video) { _ in next() }
+    case "microphone":
+      AVCaptureDevice.requestAccess(for: .audio) { _ in next() }
+    case "notifications":
+      UNUserNotificationCenter.current().requestAuthorization(options: [.alert, .badge, .sound]) {
+        _, _ in next()
+      }
+    case "location":
+      if CLLocationManager.authorizationStatus() == .notDetermined {
+        let manager = CLLocationManager()
+        manager.delegate = self
+        locationManager = manager
+        locationCompletion = next
+        manager.requestWhenInUseAuthorization()
+      } else {
+        next()
+      }
+    case "photos":
+      PHPhotoLibrary.requestAuthorization { _ in next() }
+    case "contacts":
+      CNContactStore().requestAccess(for: .contacts) { _, _ in next() }
+    default:
+      next()
+    }
+  }
+
+  func locationManager(
+    _ manager: CLLocationManager, didChangeAuthorization status: CLAuthorizationStatus
+  ) {
+    // the delegate is also called with the current status when it is set
+    guard status != .notDetermined, let completion = locationCompletion else { return }
+    locationCompletion = nil
+    locationManager = nil
+    completion()
+  }
+
+  private func states(_ completion: @escaping (JsonObject) -> Void) {
+    UNUserNotificationCenter.current().getNotificationSettings { settings in
+      let notifications: String
+      switch settings.authorizationStatus {
+      case .notDetermined:
+        notifications = "prompt"
+      case .denied:
+        notifications = "denied"
+      default:
+        notifications = "granted"
+      }
+
+      let location: String
+      switch CLLocationManager.authorizationStatus() {
+      case .notDetermined:
+        location = "prompt"
+      case .authorizedAlways, .authorizedWhenInUse:
+        location = "granted"
+      default:
+        location = "denied"
+      }
+
+      let photos: String
+      let photosStatus = PHPhotoLibrary.authorizationStatus()
+      if photosStatus == .notDetermined {
+        photos = "prompt"
+      } else if photosStatus == .authorized {
+        photos = "granted"
+      } else if #available(iOS 14, *), photosStatus == .limited {
+        // the user selected the photos the app can access
+        photos = "granted"
+      } else {
+        photos = "denied"
+      }
+
+      let contacts: String
+      switch CNContactStore.authorizationStatus(for: .contacts) {
+      case .notDetermined:
+        contacts = "prompt"
+      case .authorized:
+        contacts = "granted"
+      default:
+        contacts = "denied"
+      }
+
+      completion([
+        "camera": self.captureState(.video),
+        "microphone": self.captureState(.audio),
+        "notifications": notifications,
+        "location": location,
+        "photos": photos,
+        "contacts": contacts,
+      ])
+    }
+  }
+
+  private func captureState(_ mediaType: AVMediaType) -> String {
+    switch AVCaptureDevice.authorizationStatus(for: mediaType) {
+    case .notDetermined:
+      return "prompt"
+    case .authorized:
+      return "granted"
+    default:
+      return "denied"
+    }
+  }
+}
+
+@_cdecl("init_plugin_permissions")
+func initPermissionsPlugin() -> Plugin {
+  return PermissionsPlugin()
+}
@@ -0,0 +1,33 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+;(function () {
+  let messages = {}
+
+  function format(message, args) {
+    return message.replace(/\{([^{}]+)\}/g, (placeholder, name) =>
+      args && name in args ? String(args[name]) : placeholder
+    )
+  }
+
+  const i18n = {
+    locale: null,
+    t(key, args) {
+      return key in messages ? format(messages[key], args) : key
+    },
+    // called by the app when the locale changes
+    __update(bundle) {
+      if (bundle) {
+        i18n.locale = bundle.locale
+        messages = bundle.messages
+      }
+    }
+  }
+  i18n.ready = window.__TAURI_INTERNALS__
+    .invoke('plugin:i18n|messages')
+    .then(i18n.__update)
+    .catch(() => {})
+
+  Object.defineProperty(window, '__TAURI_I18N__', { value: i18n })
+})()
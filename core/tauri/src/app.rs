@@ -12,7 +12,7 @@ use crate::{
     webview::{UriSchemeProtocol, WebviewLabelDef},
     AppManager, Asset,
   },
-  plugin::{Plugin, PluginStore},
+  plugin::{Plugin, PluginPaths, PluginService, PluginStore},
   resources::ResourceTable,
   runtime::{
     window::{WebviewEvent as RuntimeWebviewEvent, WindowEvent as RuntimeWindowEvent},
@@ -21,9 +21,10 @@ use crate::{
   sealed::{ManagerBase, RuntimeOrDispatch},
   utils::config::Config,
   utils::Env,
-  webview::PageLoadPayload,
-  Context, DeviceEventFilter, Emitter, EventLoopMessage, Listener, Manager, Monitor, Result,
-  Runtime, Scopes, StateManager, Theme, Webview, WebviewWindowBuilder, Window,
+  webview::{CspViolationReport, PageLoadPayload},
+  Context, DeviceEventFilter, Emitter, EventLoopMessage, LifecycleEvent, Listener, Manager,
+  Monitor, Result, Runtime, Scopes, StateManager, Theme, Webview, WebviewWindow,
+  WebviewWindowBuilder, Window,
 };
 
 #[cfg(desktop)]
@@ -37,7 +38,7 @@ use tauri_macros::default_runtime;
 use tauri_runtime::EventLoopProxy;
 use tauri_runtime::{
   dpi::{PhysicalPosition, PhysicalSize},
-  window::DragDropEvent,
+  window::{DragDropEvent, ImeEvent, ProcessCrashReason},
   RuntimeInitArgs,
 };
 use tauri_utils::PackageInfo;
@@ -48,6 +49,7 @@ use std::{
   collections::HashMap,
   fmt,
   sync::{mpsc::Sender, Arc, MutexGuard},
+  time::{Duration, Instant},
 };
 
 use crate::{event::EventId, runtime::RuntimeHandle, Event, EventTarget};
@@ -70,6 +72,8 @@ pub type SetupHook<R> =
   Box<dyn FnOnce(&mut App<R>) -> std::result::Result<(), Box<dyn std::error::Error>> + Send>;
 /// A closure that is run every time a page starts or finishes loading.
 pub type OnPageLoad<R> = dyn Fn(&Webview<R>, &PageLoadPayload<'_>) + Send + Sync + 'static;
+/// A closure that is run when a webview reports a Content-Security-Policy violation.
+pub type OnCspViolation<R> = dyn Fn(&Webview<R>, &CspViolationReport) + Send + Sync + 'static;
 
 /// The exit code on [`RunEvent::ExitRequested`] when [`AppHandle#method.restart`] is called.
 pub const RESTART_EXIT_CODE: i32 = i32::MAX;
@@ -106,6 +110,16 @@ pub enum WindowEvent {
   Resized(PhysicalSize<u32>),
   /// The position of the window has changed. Contains the window's new position.
   Moved(PhysicalPosition<i32>),
+  /// The window stopped being resized. Contains the client area's final dimensions.
+  ///
+  /// Emitted once no [`Self::Resized`] event was received for a short delay,
+  /// the interval set with [`Builder::window_event_throttle`] if it is longer.
+  ResizeEnded(PhysicalSize<u32>),
+  /// The window stopped being moved. Contains the window's final position.
+  ///
+  /// Emitted once no [`Self::Moved`] event was received for a short delay,
+  /// the interval set with [`Builder::window_event_throttle`] if it is longer.
+  MoveEnded(PhysicalPosition<i32>),
   /// The window has been requested to close.
   #[non_exhaustive]
   CloseRequested {
@@ -142,6 +156,31 @@ pub enum WindowEvent {
   ///
   /// - **Linux**: Not supported.
   ThemeChanged(Theme),
+  /// The [safe area insets](crate::window::Window::safe_area_insets) of the window have changed.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Linux / Windows / macOS**: Not supported.
+  SafeAreaInsetsChanged(crate::window::SafeAreaInsets),
+  /// The text composition state of the input method editor changed.
+  ///
+  /// Only emitted when the window itself receives the keyboard input, e.g. a window without webviews drawn by a native renderer,
+  /// since the webviews handle the composition of their focused elements.
+  /// Use [`Window::set_ime_position`](crate::window::Window::set_ime_position) to place the candidate window next to the text cursor.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Linux / Windows / macOS**: Only the [`ImeEvent::Commit`] event is emitted.
+  /// - **iOS / Android**: Not supported.
+  Ime(ImeEvent),
+  /// The window entered or left fullscreen, including the changes made by the system, e.g. with the green button on macOS.
+  ///
+  /// The parameter is true if the window is now fullscreen.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **iOS / Android**: Not supported.
+  FullscreenChanged(bool),
 }
 
 impl From<RuntimeWindowEvent> for WindowEvent {
@@ -149,6 +188,8 @@ impl From<RuntimeWindowEvent> for WindowEvent {
     match event {
       RuntimeWindowEvent::Resized(size) => Self::Resized(size),
       RuntimeWindowEvent::Moved(position) => Self::Moved(position),
+      RuntimeWindowEvent::ResizeEnded(size) => Self::ResizeEnded(size),
+      RuntimeWindowEvent::MoveEnded(position) => Self::MoveEnded(position),
       RuntimeWindowEvent::CloseRequested { signal_tx } => Self::CloseRequested {
         api: CloseRequestApi(signal_tx),
       },
@@ -163,6 +204,8 @@ impl From<RuntimeWindowEvent> for WindowEvent {
       },
       RuntimeWindowEvent::DragDrop(event) => Self::DragDrop(event),
       RuntimeWindowEvent::ThemeChanged(theme) => Self::ThemeChanged(theme),
+      RuntimeWindowEvent::Ime(event) => Self::Ime(event),
+      RuntimeWindowEvent::FullscreenChanged(fullscreen) => Self::FullscreenChanged(fullscreen),
     }
   }
 }
@@ -173,16 +216,46 @@ impl From<RuntimeWindowEvent> for WindowEvent {
 pub enum WebviewEvent {
   /// An event associated with the drag and drop action.
   DragDrop(DragDropEvent),
+  /// The process rendering the webview content terminated.
+  ///
+  /// The webview stays blank unless it was created with a [`CrashRecovery`](crate::webview::CrashRecovery) policy reloading it.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **macOS / Android / iOS:** Unsupported.
+  ProcessCrashed {
+    /// Why the process terminated.
+    reason: ProcessCrashReason,
+  },
 }
 
 impl From<RuntimeWebviewEvent> for WebviewEvent {
   fn from(event: RuntimeWebviewEvent) -> Self {
     match event {
       RuntimeWebviewEvent::DragDrop(e) => Self::DragDrop(e),
+      RuntimeWebviewEvent::ProcessCrashed { reason } => Self::ProcessCrashed { reason },
     }
   }
 }
 
+/// The time spent in the startup phases, reported by [`RunEvent::Ready`] to optimize the cold start of the app.
+#[derive(Debug, Clone, Default)]
+#[non_exhaustive]
+pub struct StartupTimings {
+  /// The time spent creating the runtime in [`Builder::build`].
+  pub runtime: Duration,
+  /// The time spent initializing each plugin, in initialization order.
+  ///
+  /// The plugins registered with [`Builder::plugin_lazy`] are initialized on first use, so their time is not included.
+  pub plugins: Vec<(&'static str, Duration)>,
+  /// The time spent creating the windows of the configuration.
+  pub windows: Duration,
+  /// The time spent in the [`Builder::setup`] hook.
+  pub setup: Duration,
+  /// The time from the call to [`Builder::build`] until the app is ready.
+  pub total: Duration,
+}
+
 /// An application event, triggered from the event loop.
 ///
 /// See [`App::run`](crate::App#method.run) for usage examples.
@@ -218,7 +291,11 @@ pub enum RunEvent {
     event: WebviewEvent,
   },
   /// Application ready.
-  Ready,
+  #[non_exhaustive]
+  Ready {
+    /// The time spent in each startup phase.
+    timings: StartupTimings,
+  },
   /// Sent if the event loop is being resumed.
   Resumed,
   /// Emitted when all of the event loop's input events have been processed and redraw processing is about to begin.
@@ -248,6 +325,25 @@ pub enum RunEvent {
     /// Indicates whether the NSApplication object found any visible windows in your application.
     has_visible_windows: bool,
   },
+  /// The configuration was reloaded with [`AppHandle::reload_config_subset`].
+  ///
+  /// Plugins can apply their reloaded `plugins` configuration when handling this event.
+  ConfigReloaded(Arc<Config>),
+  /// The application lifecycle changed, e.g. the app was moved to the background on mobile or the system went to sleep.
+  ///
+  /// Plugins usually handle it with [`Plugin::on_suspend`], [`Plugin::on_resume`] and [`Plugin::on_memory_warning`].
+  Lifecycle(LifecycleEvent),
+  /// A monitor was added, removed or rearranged, e.g. when a laptop is undocked,
+  /// or changed its [color profile](Monitor::color_profile) or [HDR state](Monitor::is_hdr).
+  ///
+  /// The windows that ended up on another monitor, or off-screen, can be repositioned.
+  /// The monitors are checked every 2 seconds.
+  MonitorsChanged {
+    /// The connected monitors.
+    monitors: Vec<Monitor>,
+    /// The labels of the windows that moved to another monitor.
+    windows: Vec<String>,
+  },
 }
 
 impl From<EventLoopMessage> for RunEvent {
@@ -257,10 +353,45 @@ impl From<EventLoopMessage> for RunEvent {
       EventLoopMessage::MenuEvent(e) => Self::MenuEvent(e),
       #[cfg(all(desktop, feature = "tray-icon"))]
       EventLoopMessage::TrayIconEvent(e) => Self::TrayIconEvent(e),
+      EventLoopMessage::ConfigReloaded(config) => Self::ConfigReloaded(config),
+      EventLoopMessage::Lifecycle(event) => Self::Lifecycle(event),
+      EventLoopMessage::MonitorsChanged { monitors, windows } => {
+        Self::MonitorsChanged { monitors, windows }
+      }
     }
   }
 }
 
+/// Reloads the configuration that `tauri dev` writes to the file of the `TAURI_DEV_RELOAD_CONFIG_PATH` environment variable
+/// when only the settings supported by [`AppHandle::reload_config_subset`] are edited.
+#[cfg(all(dev, desktop))]
+fn watch_dev_config<R: Runtime>(app_handle: AppHandle<R>) {
+  let Some(path) = std::env::var_os("TAURI_DEV_RELOAD_CONFIG_PATH").map(std::path::PathBuf::from)
+  else {
+    return;
+  };
+  let modified = |path: &std::path::Path| std::fs::metadata(path).and_then(|m| m.modified()).ok();
+
+  let mut last_modified = modified(&path);
+  std::thread::spawn(move || loop {
+    std::thread::sleep(std::time::Duration::from_millis(500));
+    let current_modified = modified(&path);
+    if current_modified == last_modified {
+      continue;
+    }
+    last_modified = current_modified;
+
+    let config = std::fs::read_to_string(&path)
+      .ok()
+      .and_then(|config| serde_json::from_str::<Config>(&config).ok());
+    if let Some(config) = config {
+      if let Err(e) = app_handle.reload_config_subset(&config) {
+        log::error!("failed to reload the configuration: {e}");
+      }
+    }
+  });
+}
+
 /// The asset resolver is a helper to access the [`tauri_utils::assets::Assets`] interface.
 #[derive(Debug, Clone)]
 pub struct AssetResolver<R: Runtime> {
@@ -303,6 +434,9 @@ impl<R: Runtime> AssetResolver<R> {
             bytes,
             mime_type,
             csp_header: None,
+            content_encoding: None,
+            etag: None,
+            immutable: false,
           }
         });
       }
@@ -311,6 +445,17 @@ impl<R: Runtime> AssetResolver<R> {
     self.manager.get_asset(path).ok()
   }
 
+  /// Gets the content-hashed path of the embedded asset associated with the given path, e.g. `/assets/index.0a1b2c3d.js`.
+  ///
+  /// The asset protocol serves it with an immutable cache policy, as the path changes with the asset content.
+  pub fn hashed_path(&self, path: &str) -> Option<String> {
+    self
+      .manager
+      .assets
+      .metadata(&path.into())
+      .map(|metadata| metadata.hashed_key.to_string())
+  }
+
   /// Iterate on all assets.
   pub fn iter(&self) -> Box<dyn Iterator<Item = (&str, &[u8])> + '_> {
     self.manager.assets.iter()
@@ -381,6 +526,358 @@ impl<R: Runtime> AppHandle<R> {
       .map_err(Into::into)
   }
 
+  /// Queues the given closure on the main thread scheduler, running it by priority and merging it with the queued task
+  /// that has the same coalescing key. See the [`scheduler`](crate::scheduler) module for more information.
+  ///
+  /// When called on the main thread outside of a scheduled task, the closure runs right away.
+  pub fn schedule_on_main_thread<F: FnOnce() + Send + 'static>(
+    &self,
+    options: crate::scheduler::TaskOptions,
+    f: F,
+  ) -> crate::Result<()> {
+    self.manager.scheduler.schedule(self, options, Box::new(f))
+  }
+
+  /// Runs the given closure on the main thread scheduler and resolves to its return value.
+  ///
+  /// The future must not be blocked on from the main thread, since the closure could never run.
+  ///
+  /// # Examples
+  ///
+  /// ```rust,no_run
+  /// #[tauri::command]
+  /// async fn window_count(app: tauri::AppHandle) -> Result<usize, String> {
+  ///   use tauri::Manager;
+  ///   let app_ = app.clone();
+  ///   app
+  ///     .run_on_main_thread_returning(move || app_.webview_windows().len())
+  ///     .await
+  ///     .map_err(|e| e.to_string())
+  /// }
+  /// ```
+  pub fn run_on_main_thread_returning<T: Send + 'static, F: FnOnce() -> T + Send + 'static>(
+    &self,
+    f: F,
+  ) -> impl std::future::Future<Output = crate::Result<T>> + Send + 'static {
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    let scheduled = self.schedule_on_main_thread(Default::default(), move || {
+      let _ = tx.send(f());
+    });
+    async move {
+      scheduled?;
+      rx.await.map_err(|_| crate::Error::FailedToReceiveMessage)
+    }
+  }
+
+  /// Returns a snapshot of the memory used by the app and its webviews.
+  ///
+  /// # Examples
+  ///
+  /// ```rust,no_run
+  /// #[tauri::command]
+  /// fn memory_stats(app: tauri::AppHandle) -> tauri::memory::MemoryStats {
+  ///   app.memory_stats()
+  /// }
+  /// ```
+  pub fn memory_stats(&self) -> crate::memory::MemoryStats {
+    crate::memory::stats(self)
+  }
+
+  /// Emits the [`MemoryStats`](crate::memory::MemoryStats) to all targets as the
+  /// [`MEMORY_STATS_EVENT`](crate::memory::MEMORY_STATS_EVENT) at the given interval, or stops emitting them with `None`.
+  ///
+  /// Comparing the stats over time helps detecting the leaks, e.g. the memory of the webviews growing while the window count does not.
+  pub fn set_memory_stats_interval(&self, interval: Option<std::time::Duration>) {
+    crate::memory::set_interval(self, interval)
+  }
+
+  /// Polls the connected gamepads at the given interval, emitting their input to all targets as the
+  /// [`GAMEPAD_EVENT`](crate::input::gamepad::GAMEPAD_EVENT), or stops polling them with `None`.
+  ///
+  /// The gamepads are polled on the main thread, an interval of 16ms matches the frame rate of most displays.
+  ///
+  /// # Examples
+  ///
+  /// ```rust,no_run
+  /// use tauri::{input::gamepad::{GamepadEvent, GAMEPAD_EVENT}, Listener};
+  ///
+  /// tauri::Builder::default().setup(|app| {
+  ///   app.handle().set_gamepad_poll_interval(Some(std::time::Duration::from_millis(16)));
+  ///   app.listen_any(GAMEPAD_EVENT, |event| {
+  ///     if let Ok(GamepadEvent::ButtonPressed { button, .. }) = serde_json::from_str(event.payload()) {
+  ///       println!("{button:?} pressed");
+  ///     }
+  ///   });
+  ///   Ok(())
+  /// });
+  /// ```
+  #[cfg(all(desktop, feature = "gamepad"))]
+  #[cfg_attr(docsrs, doc(cfg(all(desktop, feature = "gamepad"))))]
+  pub fn set_gamepad_poll_interval(&self, interval: Option<std::time::Duration>) {
+    crate::input::gamepad::set_interval(self, interval)
+  }
+
+  /// Keeps `count` hidden webview windows loaded with the default URL,
+  /// so [`Self::claim_prewarmed_webview`] can open a window without waiting for the webview to initialize.
+  ///
+  /// The pooled windows are labeled `webview-pool-{n}`, capabilities granted to them should target the `webview-pool-*` windows.
+  /// Lowering the count destroys the pooled windows above it.
+  ///
+  /// # Examples
+  ///
+  /// ```rust,no_run
+  /// tauri::Builder::default().setup(|app| {
+  ///   app.handle().prewarm_webviews(2)?;
+  ///   Ok(())
+  /// });
+  /// ```
+  #[cfg(desktop)]
+  pub fn prewarm_webviews(&self, count: usize) -> crate::Result<()> {
+    self.manager.webview_pool.resize(self, count)
+  }
+
+  /// Claims a hidden window kept by [`Self::prewarm_webviews`], navigating it to the URL, showing and focusing it.
+  /// Returns `None` when no window is ready, e.g. to build a new window instead.
+  ///
+  /// The pool creates a window in the background to replace the claimed one.
+  ///
+  /// The claimed window keeps its `webview-pool-{n}` label and the default window and webview attributes,
+  /// use its setters to change e.g. its title or size. It is not saved by [`Self::save_session`].
+  ///
+  /// # Examples
+  ///
+  /// ```rust,no_run
+  /// use tauri::{WebviewUrl, WebviewWindowBuilder};
+  ///
+  /// #[tauri::command]
+  /// async fn open_editor(app: tauri::AppHandle) -> Result<(), String> {
+  ///   let url = WebviewUrl::App("editor.html".into());
+  ///   match app.claim_prewarmed_webview(url.clone()).map_err(|e| e.to_string())? {
+  ///     Some(window) => window.set_title("Editor").map_err(|e| e.to_string())?,
+  ///     None => {
+  ///       WebviewWindowBuilder::new(&app, "editor", url)
+  ///         .title("Editor")
+  ///         .build()
+  ///         .map_err(|e| e.to_string())?;
+  ///     }
+  ///   }
+  ///   Ok(())
+  /// }
+  /// ```
+  #[cfg(desktop)]
+  pub fn claim_prewarmed_webview(
+    &self,
+    url: crate::WebviewUrl,
+  ) -> crate::Result<Option<crate::WebviewWindow<R>>> {
+    let Some(mut window) = self.manager.webview_pool.claim(self) else {
+      return Ok(None);
+    };
+    if url != crate::WebviewUrl::default() {
+      window.navigate(crate::manager::webview::resolve_url(&self.manager, &url))?;
+    }
+    window.show()?;
+    window.set_focus()?;
+    crate::webview::pool::WebviewPool::replenish(self);
+    Ok(Some(window))
+  }
+
+  /// Saves the label, URL, geometry and zoom level of the open webview windows
+  /// to the [`SESSION_FILE_NAME`](crate::session::SESSION_FILE_NAME) file of the app data directory.
+  ///
+  /// The session is saved automatically when the last window is closed or the app exits if `app > restoreSession` is enabled.
+  ///
+  /// # Examples
+  ///
+  /// ```rust,no_run
+  /// #[tauri::command]
+  /// fn save_workspace(app: tauri::AppHandle) -> Result<(), String> {
+  ///   app.save_session().map(|_| ()).map_err(|e| e.to_string())
+  /// }
+  /// ```
+  #[cfg(desktop)]
+  pub fn save_session(&self) -> crate::Result<crate::session::Session> {
+    crate::session::save(self)
+  }
+
+  /// Recreates the windows saved with [`Self::save_session`], applying the saved state to the open windows with the same label.
+  ///
+  /// Returns the restored windows, which is empty if no session was saved.
+  ///
+  /// The session is restored automatically after the windows of the config are created if `app > restoreSession` is enabled.
+  #[cfg(desktop)]
+  pub fn restore_session(&self) -> crate::Result<Vec<crate::WebviewWindow<R>>> {
+    crate::session::restore(self)
+  }
+
+  /// Opens the URL or absolute path with the default app of the system, if allowed by the [`Builder::opener_policy`].
+  ///
+  /// # Examples
+  ///
+  /// ```rust,no_run
+  /// #[tauri::command]
+  /// fn open_docs(app: tauri::AppHandle) -> Result<(), String> {
+  ///   app
+  ///     .open_with_default_app("https://tauri.app")
+  ///     .map_err(|e| e.to_string())
+  /// }
+  /// ```
+  #[cfg(desktop)]
+  pub fn open_with_default_app(&self, target: impl AsRef<str>) -> crate::Result<()> {
+    crate::opener::open(self, target.as_ref())
+  }
+
+  /// Starts a [local HTTP server](crate::server) on the async runtime, shut down when the app exits.
+  ///
+  /// # Examples
+  ///
+  /// ```rust,no_run
+  /// use tauri::{server::LocalServer, Manager};
+  ///
+  /// tauri::Builder::default().setup(|app| {
+  ///   let site = app.path().resource_dir()?.join("site");
+  ///   let server = app.handle().serve(LocalServer::new().serve_dir("/", site))?;
+  ///   println!("serving on {}", server.url());
+  ///   Ok(())
+  /// });
+  /// ```
+  #[cfg(feature = "server")]
+  #[cfg_attr(docsrs, doc(cfg(feature = "server")))]
+  pub fn serve(
+    &self,
+    server: crate::server::LocalServer,
+  ) -> crate::Result<crate::server::ServerHandle> {
+    crate::server::serve(self, server)
+  }
+
+  /// Spawns a [sidecar](crate::sidecar) supervised until the app exits.
+  ///
+  /// # Examples
+  ///
+  /// ```rust,no_run
+  /// use tauri::sidecar::{Backoff, RestartPolicy, Sidecar};
+  ///
+  /// tauri::Builder::default().setup(|app| {
+  ///   app
+  ///     .handle()
+  ///     .spawn_sidecar(Sidecar::new("server").restart(RestartPolicy::Always(Backoff::default())))?;
+  ///   Ok(())
+  /// });
+  /// ```
+  #[cfg(desktop)]
+  pub fn spawn_sidecar(
+    &self,
+    sidecar: crate::sidecar::Sidecar,
+  ) -> crate::Result<crate::sidecar::SidecarHandle> {
+    crate::sidecar::spawn_sidecar(self, sidecar)
+  }
+
+  /// Whether the app is the default handler of the URL scheme or file type.
+  ///
+  /// See the [`default_handler`](crate::default_handler) module for the platform differences.
+  #[cfg(desktop)]
+  pub fn is_default_handler(
+    &self,
+    target: &crate::default_handler::HandlerTarget,
+  ) -> crate::Result<bool> {
+    crate::default_handler::is_default(self, target)
+  }
+
+  /// Requests the app to become the default handler of the URL scheme or file type,
+  /// complementing the static registration of the bundle.
+  ///
+  /// Returns whether the app is now the default, or the user was asked to choose the default app.
+  ///
+  /// # Examples
+  ///
+  /// ```rust,no_run
+  /// use tauri::default_handler::{DefaultHandlerResult, HandlerTarget};
+  ///
+  /// #[tauri::command]
+  /// fn make_default(app: tauri::AppHandle) -> Result<bool, String> {
+  ///   let result = app
+  ///     .set_default_handler(&HandlerTarget::Scheme("my-app".into()))
+  ///     .map_err(|e| e.to_string())?;
+  ///   Ok(result == DefaultHandlerResult::Registered)
+  /// }
+  /// ```
+  #[cfg(desktop)]
+  pub fn set_default_handler(
+    &self,
+    target: &crate::default_handler::HandlerTarget,
+  ) -> crate::Result<crate::default_handler::DefaultHandlerResult> {
+    crate::default_handler::set_default(self, target)
+  }
+
+  /// Returns whether the app is launched for the first time or after an upgrade, and what launched it,
+  /// to route the onboarding and migration logic.
+  ///
+  /// The version of the launch is recorded in the [`LAUNCH_FILE_NAME`](crate::launch::LAUNCH_FILE_NAME) file of the app data directory
+  /// when the app starts.
+  ///
+  /// # Examples
+  ///
+  /// ```rust,no_run
+  /// tauri::Builder::default().setup(|app| {
+  ///   let launch = app.launch_info();
+  ///   if launch.first_run {
+  ///     // show the onboarding
+  ///   } else if let Some(previous) = launch.upgraded_from() {
+  ///     println!("migrating the data from {previous}");
+  ///   }
+  ///   Ok(())
+  /// });
+  /// ```
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Linux / Windows:** The source is read from the arguments: the [`AUTOSTART_ARG`](crate::launch::AUTOSTART_ARG) argument,
+  ///   which the autostart entry must pass, a URL or an existing file path.
+  /// - **macOS:** The URLs and files opening the app are delivered after the launch by [`RunEvent::Opened`], so the source is only
+  ///   the autostart argument.
+  /// - **Android / iOS:** The source is the launch activation or notification, see [`crate::mobile::activation`] and [`crate::mobile::push`].
+  pub fn launch_info(&self) -> crate::launch::LaunchInfo {
+    crate::launch::info(self)
+  }
+
+  /// Returns the time since the last keyboard or mouse input of the user in the whole system.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Linux:** Requires a desktop exposing the idle time over D-Bus, e.g. GNOME or KDE, `None` otherwise.
+  /// - **Android / iOS:** Unsupported, always `None`.
+  pub fn idle_time(&self) -> Option<Duration> {
+    crate::idle::idle_time()
+  }
+
+  /// Calls the handler with [`IdleState::Idle`](crate::idle::IdleState::Idle) when the user has been inactive for the threshold,
+  /// and with [`IdleState::Active`](crate::idle::IdleState::Active) when the user interacts with the system again.
+  ///
+  /// The idle time is checked every [`POLL_INTERVAL`](crate::idle::POLL_INTERVAL) until the returned timer is dropped.
+  ///
+  /// # Examples
+  ///
+  /// ```rust,no_run
+  /// use std::time::Duration;
+  /// use tauri::{idle::IdleState, time::Timer, Emitter, Manager};
+  ///
+  /// struct AwayStatus(Timer);
+  ///
+  /// tauri::Builder::default().setup(|app| {
+  ///   let timer = app.handle().on_idle(Duration::from_secs(5 * 60), |app, state| {
+  ///     let _ = app.emit("away", state == IdleState::Idle);
+  ///   });
+  ///   app.manage(AwayStatus(timer));
+  ///   Ok(())
+  /// });
+  /// ```
+  pub fn on_idle<F: Fn(&AppHandle<R>, crate::idle::IdleState) + Send + 'static>(
+    &self,
+    threshold: Duration,
+    handler: F,
+  ) -> crate::time::Timer {
+    crate::idle::on_idle(self, threshold, handler)
+  }
+
   /// Adds a Tauri application plugin.
   /// This function can be used to register a plugin that is loaded dynamically e.g. after login.
   /// For plugins that are created when the app is started, prefer [`Builder::plugin`].
@@ -443,7 +940,83 @@ impl<R: Runtime> AppHandle<R> {
   ///   });
   /// ```
   pub fn remove_plugin(&self, plugin: &'static str) -> bool {
-    self.manager().plugins.lock().unwrap().unregister(plugin)
+    let removed = self.manager().plugins.lock().unwrap().unregister(plugin);
+    if removed {
+      self
+        .manager()
+        .plugin_services
+        .lock()
+        .unwrap()
+        .remove_provider(plugin);
+    }
+    removed
+  }
+
+  /// Returns the directories of the plugin with the given name, e.g. to remove its data when it is uninstalled.
+  ///
+  /// Plugins get their own directories with [`PluginApi::paths`](crate::plugin::PluginApi::paths).
+  pub fn plugin_paths(&self, plugin: impl Into<String>) -> PluginPaths<R> {
+    PluginPaths::new(self.clone(), plugin)
+  }
+
+  /// Gets a service provided by a plugin with [`PluginApi::provide_service`](crate::plugin::PluginApi::provide_service).
+  ///
+  /// Fails if no plugin provides it or if the provided version is not compatible with [`PluginService::VERSION`].
+  /// Plugins should declare the services they use with [`Builder::depends_on`](crate::plugin::Builder::depends_on)
+  /// so they are initialized after the plugins providing them.
+  ///
+  /// See [`PluginService`] for an example.
+  pub fn plugin_service<S: ?Sized + PluginService>(&self) -> crate::Result<Arc<S>> {
+    self.manager().plugin_services.lock().unwrap().get::<S>()
+  }
+
+  /// The last lifecycle state of the application, i.e. the last [`LifecycleEvent::Foreground`], [`LifecycleEvent::Inactive`],
+  /// [`LifecycleEvent::Background`] or [`LifecycleEvent::Terminate`] event.
+  ///
+  /// Returns `None` until the first state change is reported.
+  pub fn last_state(&self) -> Option<LifecycleEvent> {
+    self.manager().lifecycle.last()
+  }
+
+  /// Applies the settings of the given configuration that can be changed at runtime, usually read from an edited configuration file.
+  ///
+  /// The reloaded settings are:
+  ///
+  /// - The title and theme of the windows created from the configuration, matched by label.
+  /// - The tooltip and title of the configured tray icon.
+  /// - The content security policy of the pages loaded from now on.
+  ///   The policy can't be removed at runtime, so a configuration without one keeps the current policy.
+  ///
+  /// [`RunEvent::ConfigReloaded`] is then triggered so plugins can reload their configuration.
+  /// Other settings require restarting the app, and [`Manager::config`] keeps returning the configuration the app was built with.
+  ///
+  /// `tauri dev` uses this function to apply the changes to these settings without restarting the app.
+  pub fn reload_config_subset(&self, config: &Config) -> crate::Result<()> {
+    #[cfg(desktop)]
+    for window_config in &config.app.windows {
+      if let Some(window) = self.get_window(&window_config.label) {
+        window.set_title(&window_config.title)?;
+        window.set_theme(window_config.theme)?;
+      }
+    }
+
+    #[cfg(all(desktop, feature = "tray-icon"))]
+    if let Some(tray_config) = &config.app.tray_icon {
+      if let Some(tray) = self.tray_by_id(tray_config.id.as_deref().unwrap_or("main")) {
+        tray.set_tooltip(tray_config.tooltip.as_deref())?;
+        tray.set_title(tray_config.title.as_deref())?;
+      }
+    }
+
+    if let Some(csp) = crate::manager::csp_from_config(config) {
+      self.manager.csp.lock().unwrap().replace(csp);
+    }
+
+    self
+      .runtime_handle
+      .create_proxy()
+      .send_event(EventLoopMessage::ConfigReloaded(Arc::new(config.clone())))
+      .map_err(Into::into)
   }
 
   /// Exits the app by triggering [`RunEvent::ExitRequested`] and [`RunEvent::Exit`].
@@ -515,6 +1088,9 @@ impl<R: Runtime> ManagerBase<R> for AppHandle<R> {
 pub struct App<R: Runtime> {
   runtime: Option<R>,
   setup: Option<SetupHook<R>>,
+  migrations: Option<crate::migration::Migrations<R>>,
+  #[cfg(feature = "remote-control")]
+  remote_control: Option<crate::remote_control::RemoteControl<R>>,
   manager: Arc<AppManager<R>>,
   handle: AppHandle<R>,
   ran_setup: bool,
@@ -856,6 +1432,12 @@ macro_rules! shared_app_impl {
         for (_, webview) in self.manager.webviews() {
           webview.resources_table().clear();
         }
+        #[cfg(desktop)]
+        self.manager.sidecars.shutdown();
+        #[cfg(feature = "server")]
+        for server in self.manager.servers.lock().unwrap().drain(..) {
+          server.shutdown();
+        }
       }
     }
 
@@ -997,22 +1579,41 @@ shared_app_impl!(App<R>);
 shared_app_impl!(AppHandle<R>);
 
 impl<R: Runtime> App<R> {
+  /// Registers a core plugin, failing instead of replacing an application plugin that uses the same name.
+  fn register_core_plugin<P: Plugin<R> + 'static>(&self, plugin: P) -> crate::Result<()> {
+    if self.manager.plugins.lock().unwrap().contains(plugin.name()) {
+      return Err(crate::Error::ReservedPluginName(plugin.name().into()));
+    }
+    self.handle.plugin(plugin)
+  }
+
   #[cfg_attr(
     feature = "tracing",
     tracing::instrument(name = "app::core_plugins::register")
   )]
   fn register_core_plugins(&self) -> crate::Result<()> {
-    self.handle.plugin(crate::path::plugin::init())?;
-    self.handle.plugin(crate::event::plugin::init())?;
-    self.handle.plugin(crate::window::plugin::init())?;
-    self.handle.plugin(crate::webview::plugin::init())?;
-    self.handle.plugin(crate::app::plugin::init())?;
-    self.handle.plugin(crate::resources::plugin::init())?;
-    self.handle.plugin(crate::image::plugin::init())?;
+    self.register_core_plugin(crate::path::plugin::init())?;
+    self.register_core_plugin(crate::event::plugin::init())?;
+    self.register_core_plugin(crate::window::plugin::init())?;
+    self.register_core_plugin(crate::webview::plugin::init())?;
+    self.register_core_plugin(crate::mobile::activation::init())?;
+    self.register_core_plugin(crate::mobile::background::init())?;
+    self.register_core_plugin(crate::mobile::permissions::init())?;
+    self.register_core_plugin(crate::mobile::push::init())?;
+    #[cfg(mobile)]
+    self.register_core_plugin(crate::share::init())?;
+    self.register_core_plugin(crate::app::plugin::init())?;
+    self.register_core_plugin(crate::i18n::plugin::init())?;
+    self.register_core_plugin(crate::resources::plugin::init())?;
+    self.register_core_plugin(crate::image::plugin::init())?;
     #[cfg(desktop)]
-    self.handle.plugin(crate::menu::plugin::init())?;
+    self.register_core_plugin(crate::menu::plugin::init())?;
     #[cfg(all(desktop, feature = "tray-icon"))]
-    self.handle.plugin(crate::tray::plugin::init())?;
+    self.register_core_plugin(crate::tray::plugin::init())?;
+    #[cfg(all(desktop, feature = "global-input-listener"))]
+    self.register_core_plugin(crate::input::global_listener::plugin::init())?;
+    #[cfg(all(desktop, feature = "fs-watch"))]
+    self.register_core_plugin(crate::fs::plugin::init())?;
     Ok(())
   }
 
@@ -1026,6 +1627,28 @@ impl<R: Runtime> App<R> {
     &self.handle
   }
 
+  /// Saves the open webview windows, see [`AppHandle::save_session`].
+  #[cfg(desktop)]
+  pub fn save_session(&self) -> crate::Result<crate::session::Session> {
+    self.handle.save_session()
+  }
+
+  /// Recreates the windows of the saved session, see [`AppHandle::restore_session`].
+  #[cfg(desktop)]
+  pub fn restore_session(&self) -> crate::Result<Vec<crate::WebviewWindow<R>>> {
+    self.handle.restore_session()
+  }
+
+  /// Returns how and for which version the app was launched, see [`AppHandle::launch_info`].
+  pub fn launch_info(&self) -> crate::launch::LaunchInfo {
+    self.handle.launch_info()
+  }
+
+  /// Returns the time since the last input of the user, see [`AppHandle::idle_time`].
+  pub fn idle_time(&self) -> Option<Duration> {
+    self.handle.idle_time()
+  }
+
   /// Sets the activation policy for the application. It is set to `NSApplicationActivationPolicyRegular` by default.
   ///
   /// # Examples
@@ -1152,6 +1775,65 @@ impl<R: Runtime> App<R> {
       callback(&app_handle, event);
     })
   }
+
+  /// Processes the pending events of the runtime event loop, waiting up to the timeout for one if there are none,
+  /// and returns.
+  ///
+  /// This drives Tauri from an event loop owned by the host application, such as the render loop of a game:
+  /// `Some(Duration::ZERO)` only processes the pending events, like [`Self::run_iteration`],
+  /// while a longer timeout lets the host sleep until its next frame.
+  /// `None` waits until an event is received.
+  ///
+  /// Other threads wake up a waiting loop with [`AppHandle::run_on_main_thread`].
+  ///
+  /// Like [`Self::run_iteration`], the app cleanup is not automatically done.
+  ///
+  /// # Examples
+  /// ```no_run
+  /// use std::time::{Duration, Instant};
+  /// use tauri::Manager;
+  ///
+  /// let mut app = tauri::Builder::default()
+  ///   // on an actual app, remove the string argument
+  ///   .build(tauri::generate_context!("test/fixture/src-tauri/tauri.conf.json"))
+  ///   .expect("error while building tauri application");
+  ///
+  /// let frame = Duration::from_millis(16);
+  /// loop {
+  ///   let start = Instant::now();
+  ///   // render the frame of the host application
+  ///
+  ///   app.pump_events(Some(frame.saturating_sub(start.elapsed())), |_app, _event| {});
+  ///   if app.webview_windows().is_empty() {
+  ///     app.cleanup_before_exit();
+  ///     break;
+  ///   }
+  /// }
+  /// ```
+  #[cfg(desktop)]
+  pub fn pump_events<F: FnMut(&AppHandle<R>, RunEvent) + 'static>(
+    &mut self,
+    timeout: Option<std::time::Duration>,
+    mut callback: F,
+  ) {
+    let manager = self.manager.clone();
+    let app_handle = self.handle().clone();
+
+    if !self.ran_setup {
+      if let Err(e) = setup(self) {
+        panic!("Failed to setup app: {e}");
+      }
+    }
+
+    self
+      .runtime
+      .as_mut()
+      .unwrap()
+      .pump_events(timeout, move |event| {
+        let event = on_event_loop_event(&app_handle, event, &manager);
+        callback(&app_handle, event);
+      })
+  }
 }
 
 /// Builds a Tauri application.
@@ -1184,6 +1866,12 @@ pub struct Builder<R: Runtime> {
   /// Page load hook.
   on_page_load: Option<Arc<OnPageLoad<R>>>,
 
+  /// Content-Security-Policy violation hook.
+  on_csp_violation: Option<Arc<OnCspViolation<R>>>,
+
+  /// The time source of the app timers.
+  clock: Arc<dyn crate::time::Clock>,
+
   /// All passed plugins
   plugins: PluginStore<R>,
 
@@ -1210,6 +1898,20 @@ pub struct Builder<R: Runtime> {
   /// The device event filter.
   device_event_filter: DeviceEventFilter,
 
+  /// The minimum interval between two resize or move events of a window.
+  window_event_throttle: Option<Duration>,
+
+  /// The migrations of the app data, run before the setup hook.
+  migrations: Option<crate::migration::Migrations<R>>,
+
+  /// The remote control interface, started before the setup hook.
+  #[cfg(feature = "remote-control")]
+  remote_control: Option<crate::remote_control::RemoteControl<R>>,
+
+  /// The rules of [`AppHandle::open_with_default_app`].
+  #[cfg(desktop)]
+  opener_policy: crate::opener::OpenerPolicy,
+
   pub(crate) invoke_key: String,
 }
 
@@ -1264,6 +1966,8 @@ impl<R: Runtime> Builder<R> {
       .unwrap()
       .into_string(),
       on_page_load: None,
+      on_csp_violation: None,
+      clock: Arc::new(crate::time::SystemClock),
       plugins: PluginStore::default(),
       uri_scheme_protocols: Default::default(),
       state: StateManager::new(),
@@ -1273,6 +1977,12 @@ impl<R: Runtime> Builder<R> {
       window_event_listeners: Vec::new(),
       webview_event_listeners: Vec::new(),
       device_event_filter: Default::default(),
+      window_event_throttle: None,
+      migrations: None,
+      #[cfg(feature = "remote-control")]
+      remote_control: None,
+      #[cfg(desktop)]
+      opener_policy: Default::default(),
       invoke_key,
     }
   }
@@ -1367,6 +2077,42 @@ tauri::Builder::default()
     self
   }
 
+  /// Defines a hook that receives the Content-Security-Policy violations reported by the webviews.
+  ///
+  /// When set, a `report-uri` directive pointing to the built-in `csp-report` protocol
+  /// is added to the CSP of every served HTML asset, so injection attempts can be detected in production.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// tauri::Builder::default()
+  ///   .on_csp_violation(|webview, report| {
+  ///     eprintln!(
+  ///       "webview `{}` blocked {:?} (directive {:?})",
+  ///       webview.label(),
+  ///       report.blocked_uri,
+  ///       report.effective_directive
+  ///     );
+  ///   });
+  /// ```
+  #[must_use]
+  pub fn on_csp_violation<F>(mut self, on_csp_violation: F) -> Self
+  where
+    F: Fn(&Webview<R>, &CspViolationReport) + Send + Sync + 'static,
+  {
+    self.on_csp_violation.replace(Arc::new(on_csp_violation));
+    self
+  }
+
+  /// Sets the [`Clock`](crate::time::Clock) scheduling the timers of the app, the [`SystemClock`](crate::time::SystemClock) by default.
+  ///
+  /// The `tauri::test::mock_builder` uses a `MockClock` to run the timers deterministically.
+  #[must_use]
+  pub fn clock<C: crate::time::Clock>(mut self, clock: C) -> Self {
+    self.clock = Arc::new(clock);
+    self
+  }
+
   /// Adds a Tauri application plugin.
   ///
   /// A plugin is created using the [`crate::plugin::Builder`] struct.Check its documentation for more information.
@@ -1391,8 +2137,8 @@ tauri::Builder::default()
   ///       })
   ///       .on_event(|app, event| {
   ///         match event {
-  ///           RunEvent::Ready => {
-  ///             println!("app is ready");
+  ///           RunEvent::Ready { timings, .. } => {
+  ///             println!("app is ready after {:?}", timings.total);
   ///           }
   ///           RunEvent::WindowEvent { label, event, .. } => {
   ///             println!("window {} received an event: {:?}", label, event);
@@ -1414,6 +2160,43 @@ tauri::Builder::default()
     self
   }
 
+  /// Adds a Tauri application plugin that is created and initialized on the first call to one of its commands,
+  /// so heavy plugins do not delay the startup.
+  ///
+  /// The `name` must match the name of the plugin returned by `init`, since it routes the commands to the plugin.
+  ///
+  /// Until its first command, the plugin is not notified of the window, webview and app events,
+  /// its initialization script is not injected and the state it manages is not available.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use tauri::{plugin::{Builder as PluginBuilder, TauriPlugin}, Runtime};
+  ///
+  /// fn indexer<R: Runtime>() -> TauriPlugin<R> {
+  ///   PluginBuilder::new("indexer")
+  ///     .setup(|_app, _api| {
+  ///       // load the search index
+  ///       Ok(())
+  ///     })
+  ///     .build()
+  /// }
+  ///
+  /// tauri::Builder::default()
+  ///   .plugin_lazy("indexer", indexer);
+  /// ```
+  #[must_use]
+  pub fn plugin_lazy<P: Plugin<R> + 'static, F: FnOnce() -> P + Send + 'static>(
+    mut self,
+    name: &'static str,
+    init: F,
+  ) -> Self {
+    self
+      .plugins
+      .register(Box::new(crate::plugin::LazyPlugin::new(name, init)));
+    self
+  }
+
   /// Add `state` to the state managed by the application.
   ///
   /// This method can be called any number of times as long as each call
@@ -1715,6 +2498,53 @@ tauri::Builder::default()
     self
   }
 
+  /// Coalesces the [`WindowEvent::Resized`] and [`WindowEvent::Moved`] events of a window,
+  /// dispatching at most one of each per `interval` while the window is dragged.
+  ///
+  /// The last size or position is always delivered, followed by a [`WindowEvent::ResizeEnded`]
+  /// or [`WindowEvent::MoveEnded`] event once the window settles.
+  ///
+  /// # Examples
+  /// ```,no_run
+  /// tauri::Builder::default()
+  ///   .window_event_throttle(std::time::Duration::from_millis(16));
+  /// ```
+  #[must_use]
+  pub fn window_event_throttle(mut self, interval: Duration) -> Self {
+    self.window_event_throttle.replace(interval);
+    self
+  }
+
+  /// Registers the migrations of the app data, run when the app starts before the windows are created and the setup hook runs.
+  ///
+  /// See the [`migration`](crate::migration) module for more information.
+  #[must_use]
+  pub fn migrations(mut self, migrations: crate::migration::Migrations<R>) -> Self {
+    self.migrations.replace(migrations);
+    self
+  }
+
+  /// Enables the remote control interface, a local JSON-RPC interface to control the running app from scripts.
+  ///
+  /// See the [`remote_control`](crate::remote_control) module for more information.
+  #[cfg(feature = "remote-control")]
+  #[cfg_attr(docsrs, doc(cfg(feature = "remote-control")))]
+  #[must_use]
+  pub fn remote_control(mut self, control: crate::remote_control::RemoteControl<R>) -> Self {
+    self.remote_control.replace(control);
+    self
+  }
+
+  /// Sets the policy checking the URLs and paths opened with [`AppHandle::open_with_default_app`].
+  ///
+  /// See the [`opener`](crate::opener) module for more information.
+  #[cfg(desktop)]
+  #[must_use]
+  pub fn opener_policy(mut self, policy: crate::opener::OpenerPolicy) -> Self {
+    self.opener_policy = policy;
+    self
+  }
+
   /// Builds the application.
   #[allow(clippy::type_complexity, unused_mut)]
   #[cfg_attr(
@@ -1722,6 +2552,8 @@ tauri::Builder::default()
     tracing::instrument(name = "app::build", skip_all)
   )]
   pub fn build(mut self, context: Context<R>) -> crate::Result<App<R>> {
+    let started_at = Instant::now();
+
     #[cfg(target_os = "macos")]
     if self.menu.is_none() && self.enable_macos_default_menu {
       self.menu = Some(Box::new(|app_handle| {
@@ -1729,7 +2561,7 @@ tauri::Builder::default()
       }));
     }
 
-    let manager = Arc::new(AppManager::with_handlers(
+    let mut manager = AppManager::with_handlers(
       context,
       self.plugins,
       self.invoke_handler,
@@ -1742,7 +2574,15 @@ tauri::Builder::default()
       HashMap::new(),
       (self.invoke_responder, self.invoke_initialization_script),
       self.invoke_key,
-    ));
+    );
+    manager.webview.on_csp_violation = self.on_csp_violation;
+    #[cfg(desktop)]
+    {
+      manager.opener_policy = self.opener_policy;
+    }
+    manager.clock = self.clock;
+    manager.started_at = started_at;
+    let manager = Arc::new(manager);
 
     let app_id = if manager.config.app.enable_gtk_app_id {
       Some(manager.config.identifier.clone())
@@ -1779,8 +2619,11 @@ tauri::Builder::default()
           }
         }))
       },
+
+      window_event_throttle: self.window_event_throttle,
     };
 
+    let runtime_start = Instant::now();
     #[cfg(any(windows, target_os = "linux"))]
     let mut runtime = if self.runtime_any_thread {
       R::new_any_thread(runtime_args)?
@@ -1789,6 +2632,7 @@ tauri::Builder::default()
     };
     #[cfg(not(any(windows, target_os = "linux")))]
     let mut runtime = R::new(runtime_args)?;
+    manager.startup_timings.lock().unwrap().runtime = runtime_start.elapsed();
 
     #[cfg(desktop)]
     {
@@ -1808,6 +2652,14 @@ tauri::Builder::default()
       }
     }
 
+    // forward the lifecycle events the runtime does not report
+    {
+      let proxy = runtime.create_proxy();
+      crate::lifecycle::listen(&manager.lifecycle, move |event| {
+        let _ = proxy.send_event(EventLoopMessage::Lifecycle(event));
+      });
+    }
+
     runtime.set_device_event_filter(self.device_event_filter);
 
     let runtime_handle = runtime.handle();
@@ -1816,6 +2668,9 @@ tauri::Builder::default()
     let mut app = App {
       runtime: Some(runtime),
       setup: Some(self.setup),
+      migrations: self.migrations,
+      #[cfg(feature = "remote-control")]
+      remote_control: self.remote_control,
       manager: manager.clone(),
       handle: AppHandle {
         runtime_handle,
@@ -1898,8 +2753,19 @@ tauri::Builder::default()
       }
     }
 
+    #[cfg(all(feature = "dynamic-plugins", feature = "wry"))]
+    {
+      let mut plugins = app.manager.plugins.lock().unwrap();
+      for plugin in crate::plugin::dynamic::load(handle) {
+        plugins.register(plugin);
+      }
+    }
+
     app.manager.initialize_plugins(handle)?;
 
+    #[cfg(all(dev, desktop))]
+    watch_dev_config(handle.clone());
+
     Ok(app)
   }
 
@@ -1961,6 +2827,10 @@ impl<R: Runtime> HasDisplayHandle for App<R> {
 fn setup<R: Runtime>(app: &mut App<R>) -> crate::Result<()> {
   app.ran_setup = true;
 
+  if let Some(migrations) = app.migrations.take() {
+    crate::migration::run(app.handle(), migrations)?;
+  }
+
   let window_labels = app
     .config()
     .app
@@ -1976,15 +2846,32 @@ fn setup<R: Runtime>(app: &mut App<R>) -> crate::Result<()> {
     })
     .collect::<Vec<_>>();
 
+  let windows_start = Instant::now();
   for window_config in app.config().app.windows.clone() {
     WebviewWindowBuilder::from_config(app.handle(), &window_config)?
       .build_internal(&window_labels, &webview_labels)?;
   }
+  #[cfg(desktop)]
+  if app.config().app.restore_session {
+    if let Err(e) = app.restore_session() {
+      log::error!("failed to restore the window session: {e}");
+    }
+  }
+  app.manager.startup_timings.lock().unwrap().windows = windows_start.elapsed();
+  crate::window::monitors::watch(app.handle());
 
   app.manager.assets.setup(app);
+  crate::launch::history(app.handle());
+
+  #[cfg(feature = "remote-control")]
+  if let Some(control) = app.remote_control.take() {
+    crate::remote_control::start(app.handle(), control)?;
+  }
 
   if let Some(setup) = app.setup.take() {
+    let setup_start = Instant::now();
     (setup)(app).map_err(|e| crate::Error::Setup(e.into()))?;
+    app.manager.startup_timings.lock().unwrap().setup = setup_start.elapsed();
   }
 
   Ok(())
@@ -2003,16 +2890,42 @@ fn on_event_loop_event<R: Runtime>(
     manager.on_window_close(label);
   }
 
+  // save the session while the windows still exist
+  #[cfg(desktop)]
+  if manager.config.app.restore_session
+    && matches!(
+      event,
+      RuntimeRunEvent::ExitRequested { .. }
+        | RuntimeRunEvent::WindowEvent {
+          event: RuntimeWindowEvent::CloseRequested { .. },
+          ..
+        }
+    )
+  {
+    crate::session::save_on_close(app_handle);
+  }
+
   let event = match event {
     RuntimeRunEvent::Exit => RunEvent::Exit,
     RuntimeRunEvent::ExitRequested { code, tx } => RunEvent::ExitRequested {
       code,
       api: ExitRequestApi(tx),
     },
-    RuntimeRunEvent::WindowEvent { label, event } => RunEvent::WindowEvent {
-      label,
-      event: event.into(),
-    },
+    RuntimeRunEvent::WindowEvent { label, event } => {
+      // the desktop apps are in the foreground while one of their windows is focused
+      #[cfg(desktop)]
+      match event {
+        RuntimeWindowEvent::Focused(focused) => {
+          manager.lifecycle.on_window_focus_changed(&label, focused)
+        }
+        RuntimeWindowEvent::Destroyed => manager.lifecycle.on_window_focus_changed(&label, false),
+        _ => {}
+      }
+      RunEvent::WindowEvent {
+        label,
+        event: event.into(),
+      }
+    }
     RuntimeRunEvent::WebviewEvent { label, event } => RunEvent::WebviewEvent {
       label,
       event: event.into(),
@@ -2038,10 +2951,20 @@ fn on_event_loop_event<R: Runtime>(
           let _: () = msg_send![ns_app, setApplicationIconImage: app_icon];
         }
       }
-      RunEvent::Ready
+      let mut timings = manager.startup_timings.lock().unwrap().clone();
+      timings.total = manager.started_at.elapsed();
+      RunEvent::Ready { timings }
     }
     RuntimeRunEvent::Resumed => RunEvent::Resumed,
-    RuntimeRunEvent::MainEventsCleared => RunEvent::MainEventsCleared,
+    RuntimeRunEvent::MainEventsCleared => {
+      #[cfg(desktop)]
+      manager.lifecycle.on_main_events_cleared();
+      RunEvent::MainEventsCleared
+    }
+    RuntimeRunEvent::Lifecycle(event) => {
+      crate::lifecycle::on_lifecycle_event(app_handle, event);
+      RunEvent::Lifecycle(event)
+    }
     RuntimeRunEvent::UserEvent(t) => {
       match t {
         #[cfg(desktop)]
@@ -2081,13 +3004,24 @@ fn on_event_loop_event<R: Runtime>(
             }
           }
         }
+        EventLoopMessage::Lifecycle(event) => {
+          crate::lifecycle::on_lifecycle_event(app_handle, event)
+        }
+        EventLoopMessage::ConfigReloaded(_) | EventLoopMessage::MonitorsChanged { .. } => {}
       }
 
       #[allow(unreachable_code)]
       t.into()
     }
     #[cfg(any(target_os = "macos", target_os = "ios"))]
-    RuntimeRunEvent::Opened { urls } => RunEvent::Opened { urls },
+    RuntimeRunEvent::Opened { urls } => {
+      for url in &urls {
+        crate::mobile::activation::receive(crate::mobile::activation::Activation::Url {
+          url: url.clone(),
+        });
+      }
+      RunEvent::Opened { urls }
+    }
     #[cfg(target_os = "macos")]
     RuntimeRunEvent::Reopen {
       has_visible_windows,
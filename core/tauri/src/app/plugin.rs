@@ -50,6 +50,16 @@ pub fn default_window_icon<R: Runtime>(
   })
 }
 
+// async so the confirmation handler of the opener policy, e.g. a blocking dialog, doesn't run on the main thread
+#[cfg(desktop)]
+#[command(root = "crate")]
+pub async fn open_with_default_app<R: Runtime>(
+  app: AppHandle<R>,
+  target: String,
+) -> crate::Result<()> {
+  app.open_with_default_app(target)
+}
+
 pub fn init<R: Runtime>() -> TauriPlugin<R> {
   Builder::new("app")
     .invoke_handler(crate::generate_handler![
@@ -59,6 +69,8 @@ pub fn init<R: Runtime>() -> TauriPlugin<R> {
       app_show,
       app_hide,
       default_window_icon,
+      #[cfg(desktop)]
+      open_with_default_app,
     ])
     .build()
 }
@@ -0,0 +1,365 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Querying and requesting the default app of URL schemes and file types,
+//! see [`AppHandle::is_default_handler`] and [`AppHandle::set_default_handler`].
+//!
+//! The app must also declare the schemes and file types in the `bundle > fileAssociations`
+//! and the deep link configuration so the system can open them with the app.
+//!
+//! ## Platform-specific
+//!
+//! - **Linux:** Uses `xdg-settings` and `xdg-mime` with the `<binary name>.desktop` file installed by the bundle.
+//! - **Windows:** Apps can't make themselves the default, [`AppHandle::set_default_handler`] opens the Default apps settings
+//!   for the user to choose the app.
+//! - **macOS:** Uses Launch Services with the bundle identifier, so the app must run from its `.app` bundle.
+//!   The system may ask the user to confirm the change.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{AppHandle, Runtime};
+
+/// A URL scheme or file type the app can handle.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "type", content = "value", rename_all = "camelCase")]
+#[non_exhaustive]
+pub enum HandlerTarget {
+  /// A URL scheme without `://`, e.g. `my-app`.
+  Scheme(String),
+  /// A file extension without the leading `.`, e.g. `png`.
+  FileExtension(String),
+}
+
+impl HandlerTarget {
+  fn validate(&self) -> crate::Result<&str> {
+    let value = match self {
+      Self::Scheme(scheme) => scheme.as_str(),
+      Self::FileExtension(extension) => extension.trim_start_matches('.'),
+    };
+    if value.is_empty() {
+      return Err(crate::Error::DefaultHandler(format!(
+        "invalid handler target {self:?}"
+      )));
+    }
+    Ok(value)
+  }
+}
+
+/// The result of [`AppHandle::set_default_handler`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DefaultHandlerResult {
+  /// The app is now the default handler.
+  Registered,
+  /// The system asked the user to choose the default app,
+  /// check the choice later with [`AppHandle::is_default_handler`].
+  Prompted,
+}
+
+pub(crate) fn is_default<R: Runtime>(
+  _app: &AppHandle<R>,
+  target: &HandlerTarget,
+) -> crate::Result<bool> {
+  platform::is_default(target, target.validate()?)
+}
+
+pub(crate) fn set_default<R: Runtime>(
+  app: &AppHandle<R>,
+  target: &HandlerTarget,
+) -> crate::Result<DefaultHandlerResult> {
+  if is_default(app, target)? {
+    return Ok(DefaultHandlerResult::Registered);
+  }
+  platform::set_default(target, target.validate()?)?;
+  Ok(if is_default(app, target)? {
+    DefaultHandlerResult::Registered
+  } else {
+    DefaultHandlerResult::Prompted
+  })
+}
+
+#[cfg(any(
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd"
+))]
+mod platform {
+  use std::process::Command;
+
+  use super::HandlerTarget;
+
+  /// The desktop entry installed by the bundler, named after the main binary.
+  fn desktop_file() -> crate::Result<String> {
+    let exe = tauri_utils::platform::current_exe()?;
+    let name = exe
+      .file_stem()
+      .ok_or_else(|| crate::Error::DefaultHandler("invalid executable path".into()))?;
+    Ok(format!("{}.desktop", name.to_string_lossy()))
+  }
+
+  fn mime_type(extension: &str) -> crate::Result<String> {
+    let (mime_type, _uncertain) =
+      gtk::gio::content_type_guess(Some(format!("file.{extension}")), &[]);
+    if gtk::gio::content_type_is_unknown(&mime_type) {
+      return Err(crate::Error::DefaultHandler(format!(
+        "unknown mime type for the `{extension}` extension"
+      )));
+    }
+    Ok(mime_type.into())
+  }
+
+  fn run(program: &str, args: &[&str]) -> crate::Result<String> {
+    let output = Command::new(program).args(args).output()?;
+    if !output.status.success() {
+      return Err(crate::Error::DefaultHandler(format!(
+        "`{program}` failed: {}",
+        String::from_utf8_lossy(&output.stderr).trim()
+      )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+  }
+
+  pub fn is_default(target: &HandlerTarget, value: &str) -> crate::Result<bool> {
+    let desktop_file = desktop_file()?;
+    match target {
+      HandlerTarget::Scheme(_) => run(
+        "xdg-settings",
+        &["check", "default-url-scheme-handler", value, &desktop_file],
+      )
+      .map(|answer| answer == "yes"),
+      HandlerTarget::FileExtension(_) => run("xdg-mime", &["query", "default", &mime_type(value)?])
+        .map(|handler| handler == desktop_file),
+    }
+  }
+
+  pub fn set_default(target: &HandlerTarget, value: &str) -> crate::Result<()> {
+    let desktop_file = desktop_file()?;
+    match target {
+      HandlerTarget::Scheme(_) => run(
+        "xdg-settings",
+        &["set", "default-url-scheme-handler", value, &desktop_file],
+      ),
+      HandlerTarget::FileExtension(_) => {
+        run("xdg-mime", &["default", &desktop_file, &mime_type(value)?])
+      }
+    }
+    .map(|_| ())
+  }
+}
+
+#[cfg(windows)]
+mod platform {
+  use std::path::PathBuf;
+
+  use windows::{
+    core::{w, HSTRING, PWSTR},
+    Win32::UI::{
+      Shell::{
+        AssocQueryStringW, ShellExecuteW, ASSOCF_INIT_IGNOREUNKNOWN, ASSOCF_IS_PROTOCOL,
+        ASSOCF_NONE, ASSOCSTR_EXECUTABLE,
+      },
+      WindowsAndMessaging::SW_SHOWNORMAL,
+    },
+  };
+
+  use super::HandlerTarget;
+
+  fn to_error(e: windows::core::Error) -> crate::Error {
+    crate::Error::DefaultHandler(e.message().to_string())
+  }
+
+  /// The executable opening the target, if any.
+  fn handler(target: &HandlerTarget, value: &str) -> Option<PathBuf> {
+    let (flags, assoc) = match target {
+      HandlerTarget::Scheme(_) => (ASSOCF_IS_PROTOCOL, HSTRING::from(value)),
+      HandlerTarget::FileExtension(_) => (ASSOCF_NONE, HSTRING::from(format!(".{value}"))),
+    };
+    let mut len = 0;
+    unsafe {
+      AssocQueryStringW(
+        flags | ASSOCF_INIT_IGNOREUNKNOWN,
+        ASSOCSTR_EXECUTABLE,
+        &assoc,
+        None,
+        PWSTR::null(),
+        &mut len,
+      )
+    }
+    .ok()
+    .ok()?;
+    let mut buffer = vec![0u16; len as usize];
+    unsafe {
+      AssocQueryStringW(
+        flags | ASSOCF_INIT_IGNOREUNKNOWN,
+        ASSOCSTR_EXECUTABLE,
+        &assoc,
+        None,
+        PWSTR(buffer.as_mut_ptr()),
+        &mut len,
+      )
+    }
+    .ok()
+    .ok()?;
+    Some(PathBuf::from(String::from_utf16_lossy(
+      &buffer[..(len as usize).saturating_sub(1)],
+    )))
+  }
+
+  pub fn is_default(target: &HandlerTarget, value: &str) -> crate::Result<bool> {
+    let exe = tauri_utils::platform::current_exe()?;
+    // paths are case-insensitive
+    Ok(handler(target, value).is_some_and(|handler| {
+      handler.to_string_lossy().to_lowercase() == exe.to_string_lossy().to_lowercase()
+    }))
+  }
+
+  pub fn set_default(_target: &HandlerTarget, _value: &str) -> crate::Result<()> {
+    let instance = unsafe {
+      ShellExecuteW(
+        None,
+        w!("open"),
+        w!("ms-settings:defaultapps"),
+        None,
+        None,
+        SW_SHOWNORMAL,
+      )
+    };
+    // values greater than 32 indicate success
+    if instance.0 as isize <= 32 {
+      return Err(to_error(windows::core::Error::from_win32()));
+    }
+    Ok(())
+  }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+  use cocoa::{
+    base::{id, nil},
+    foundation::NSString,
+  };
+  use objc::{class, msg_send, sel, sel_impl};
+
+  use super::HandlerTarget;
+
+  const LS_ROLES_ALL: u32 = u32::MAX;
+
+  #[link(name = "CoreServices", kind = "framework")]
+  extern "C" {
+    static kUTTagClassFilenameExtension: id;
+    fn UTTypeCreatePreferredIdentifierForTag(tag_class: id, tag: id, conforming_to: id) -> id;
+    fn LSCopyDefaultHandlerForURLScheme(scheme: id) -> id;
+    fn LSSetDefaultHandlerForURLScheme(scheme: id, bundle_id: id) -> i32;
+    fn LSCopyDefaultRoleHandlerForContentType(content_type: id, role: u32) -> id;
+    fn LSSetDefaultRoleHandlerForContentType(content_type: id, role: u32, bundle_id: id) -> i32;
+  }
+
+  /// Converts and releases a string returned by a copy function.
+  unsafe fn take_string(string: id) -> Option<String> {
+    if string == nil {
+      return None;
+    }
+    let utf8 = string.UTF8String();
+    let value = (!utf8.is_null()).then(|| {
+      std::ffi::CStr::from_ptr(utf8)
+        .to_string_lossy()
+        .into_owned()
+    });
+    let () = msg_send![string, release];
+    value
+  }
+
+  unsafe fn bundle_identifier() -> crate::Result<id> {
+    let bundle: id = msg_send![class!(NSBundle), mainBundle];
+    let identifier: id = msg_send![bundle, bundleIdentifier];
+    if identifier == nil {
+      return Err(crate::Error::DefaultHandler(
+        "the app must run from its bundle".into(),
+      ));
+    }
+    Ok(identifier)
+  }
+
+  /// The uniform type identifier of the file extension.
+  unsafe fn content_type(extension: &str) -> id {
+    let extension = NSString::alloc(nil).init_str(extension);
+    let content_type =
+      UTTypeCreatePreferredIdentifierForTag(kUTTagClassFilenameExtension, extension, nil);
+    let () = msg_send![extension, release];
+    content_type
+  }
+
+  pub fn is_default(target: &HandlerTarget, value: &str) -> crate::Result<bool> {
+    unsafe {
+      let identifier = take_string(msg_send![bundle_identifier()?, retain]);
+      let handler = match target {
+        HandlerTarget::Scheme(_) => {
+          let scheme = NSString::alloc(nil).init_str(value);
+          let handler = LSCopyDefaultHandlerForURLScheme(scheme);
+          let () = msg_send![scheme, release];
+          take_string(handler)
+        }
+        HandlerTarget::FileExtension(_) => {
+          let content_type = content_type(value);
+          let handler = LSCopyDefaultRoleHandlerForContentType(content_type, LS_ROLES_ALL);
+          let () = msg_send![content_type, release];
+          take_string(handler)
+        }
+      };
+      // bundle identifiers are case-insensitive
+      Ok(
+        matches!((handler, identifier), (Some(handler), Some(identifier)) if handler.eq_ignore_ascii_case(&identifier)),
+      )
+    }
+  }
+
+  pub fn set_default(target: &HandlerTarget, value: &str) -> crate::Result<()> {
+    let status = unsafe {
+      let identifier = bundle_identifier()?;
+      match target {
+        HandlerTarget::Scheme(_) => {
+          let scheme = NSString::alloc(nil).init_str(value);
+          let status = LSSetDefaultHandlerForURLScheme(scheme, identifier);
+          let () = msg_send![scheme, release];
+          status
+        }
+        HandlerTarget::FileExtension(_) => {
+          let content_type = content_type(value);
+          let status =
+            LSSetDefaultRoleHandlerForContentType(content_type, LS_ROLES_ALL, identifier);
+          let () = msg_send![content_type, release];
+          status
+        }
+      }
+    };
+    if status != 0 {
+      return Err(crate::Error::DefaultHandler(format!(
+        "Launch Services error {status}"
+      )));
+    }
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::HandlerTarget;
+
+  #[test]
+  fn validates_targets() {
+    assert_eq!(
+      HandlerTarget::FileExtension(".png".into())
+        .validate()
+        .unwrap(),
+      "png"
+    );
+    assert_eq!(
+      HandlerTarget::Scheme("my-app".into()).validate().unwrap(),
+      "my-app"
+    );
+    assert!(HandlerTarget::Scheme(String::new()).validate().is_err());
+  }
+}
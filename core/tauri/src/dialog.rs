@@ -0,0 +1,556 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Native message, confirmation and prompt dialogs, see [`MessageDialog`].
+//!
+//! ```rust,no_run
+//! use tauri::{dialog::MessageDialog, Manager};
+//!
+//! tauri::Builder::default()
+//!   .setup(|app| {
+//!     let window = app.get_webview_window("main").unwrap();
+//!     MessageDialog::new("Unsaved changes", "Do you want to save the changes to the document?")
+//!       .buttons(["Save", "Don't Save", "Cancel"])
+//!       .cancel_button(2)
+//!       .checkbox("Don't ask again")
+//!       .show(&window.as_ref().window(), |result| match result {
+//!         Ok(result) => println!("clicked {:?}, don't ask again: {}", result.button, result.checked),
+//!         Err(e) => eprintln!("failed to show the dialog: {e}"),
+//!       })?;
+//!     Ok(())
+//!   });
+//! ```
+//!
+//! ## Platform-specific
+//!
+//! - **Windows:** Uses a task dialog, requiring the `common-controls-v6` Cargo feature, enabled by default.
+//!   The checkbox is the verification checkbox of the task dialog. The prompts are unsupported,
+//!   [`MessageDialog::show`] returns [`Error::DialogUnsupported`](crate::Error::DialogUnsupported).
+//! - **macOS:** Uses an `NSAlert`, modal to the whole app rather than attached to the window.
+//! - **Linux:** Uses a `GtkMessageDialog`.
+//! - **Android / iOS:** Unsupported, [`MessageDialog::show`] returns [`Error::DialogUnsupported`](crate::Error::DialogUnsupported).
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Runtime, Window};
+
+/// The icon and the importance of a dialog.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum MessageLevel {
+  /// An informational message.
+  #[default]
+  Info,
+  /// A warning, e.g. before a destructive action.
+  Warning,
+  /// An error.
+  Error,
+}
+
+/// The choices of the user in a dialog.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct MessageDialogResult {
+  /// The index of the clicked button, the cancel button if the dialog was dismissed with the Escape key
+  /// or its close button, `None` if it was dismissed without a cancel button.
+  pub button: Option<usize>,
+  /// Whether the checkbox was checked.
+  pub checked: bool,
+  /// The text entered in the prompt.
+  pub input: Option<String>,
+}
+
+/// A native dialog with a message, custom buttons and optionally a checkbox or a text field.
+#[derive(Debug, Clone)]
+pub struct MessageDialog {
+  title: String,
+  message: String,
+  level: MessageLevel,
+  buttons: Vec<String>,
+  default_button: usize,
+  cancel_button: Option<usize>,
+  checkbox: Option<String>,
+  input: Option<String>,
+}
+
+impl MessageDialog {
+  /// A dialog with an OK button.
+  pub fn new(title: impl Into<String>, message: impl Into<String>) -> Self {
+    Self {
+      title: title.into(),
+      message: message.into(),
+      level: MessageLevel::default(),
+      buttons: vec!["OK".into()],
+      default_button: 0,
+      cancel_button: None,
+      checkbox: None,
+      input: None,
+    }
+  }
+
+  /// A dialog with OK and Cancel buttons.
+  pub fn confirm(title: impl Into<String>, message: impl Into<String>) -> Self {
+    Self::new(title, message)
+      .buttons(["OK", "Cancel"])
+      .cancel_button(1)
+  }
+
+  /// A dialog with a text field filled with the value, and OK and Cancel buttons.
+  /// The text is returned in [`MessageDialogResult::input`].
+  pub fn prompt(
+    title: impl Into<String>,
+    message: impl Into<String>,
+    value: impl Into<String>,
+  ) -> Self {
+    let mut dialog = Self::confirm(title, message);
+    dialog.input.replace(value.into());
+    dialog
+  }
+
+  /// Sets the icon and the importance of the dialog.
+  #[must_use]
+  pub fn level(mut self, level: MessageLevel) -> Self {
+    self.level = level;
+    self
+  }
+
+  /// Sets the labels of the buttons, replacing the default buttons, from the right to the left on macOS and Linux.
+  ///
+  /// The first button is the default button, see [`Self::default_button`].
+  #[must_use]
+  pub fn buttons<I: IntoIterator<Item = S>, S: Into<String>>(mut self, buttons: I) -> Self {
+    self.buttons = buttons.into_iter().map(Into::into).collect();
+    self.default_button = 0;
+    self.cancel_button = None;
+    self
+  }
+
+  /// Sets the index of the button activated by the Enter key.
+  #[must_use]
+  pub fn default_button(mut self, index: usize) -> Self {
+    self.default_button = index;
+    self
+  }
+
+  /// Sets the index of the button activated by the Escape key and returned when the dialog is closed.
+  #[must_use]
+  pub fn cancel_button(mut self, index: usize) -> Self {
+    self.cancel_button.replace(index);
+    self
+  }
+
+  /// Adds a checkbox with the label, e.g. "Don't ask again", its state is returned in [`MessageDialogResult::checked`].
+  #[must_use]
+  pub fn checkbox(mut self, label: impl Into<String>) -> Self {
+    self.checkbox.replace(label.into());
+    self
+  }
+
+  fn validate(&self) -> crate::Result<()> {
+    let count = self.buttons.len();
+    if count == 0 {
+      return Err(crate::Error::Dialog("a dialog needs a button".into()));
+    }
+    if self.default_button >= count || self.cancel_button.is_some_and(|i| i >= count) {
+      return Err(crate::Error::Dialog(format!(
+        "the dialog has only {count} buttons"
+      )));
+    }
+    Ok(())
+  }
+
+  /// Shows the dialog for the window, calling `on_result` once the user closes it.
+  ///
+  /// See the [module documentation](self) for the platform differences.
+  pub fn show<R: Runtime, F: FnOnce(crate::Result<MessageDialogResult>) + Send + 'static>(
+    self,
+    window: &Window<R>,
+    on_result: F,
+  ) -> crate::Result<()> {
+    self.validate()?;
+    platform::show(window, self, Box::new(on_result))
+  }
+
+  /// Shows the dialog for the window, resolving once the user closes it.
+  pub async fn response<R: Runtime>(
+    self,
+    window: &Window<R>,
+  ) -> crate::Result<MessageDialogResult> {
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    self.show(window, move |result| {
+      let _ = tx.send(result);
+    })?;
+    rx.await
+      .map_err(|_| crate::Error::Dialog("the dialog was dropped".into()))?
+  }
+}
+
+type Callback = Box<dyn FnOnce(crate::Result<MessageDialogResult>) + Send>;
+
+#[cfg(all(windows, feature = "common-controls-v6"))]
+mod platform {
+  use windows::{
+    core::{HSTRING, PCWSTR},
+    Win32::{
+      Foundation::BOOL,
+      UI::{
+        Controls::{
+          TaskDialogIndirect, TASKDIALOGCONFIG, TASKDIALOGCONFIG_0, TASKDIALOG_BUTTON,
+          TDF_ALLOW_DIALOG_CANCELLATION, TDF_POSITION_RELATIVE_TO_WINDOW, TD_ERROR_ICON,
+          TD_INFORMATION_ICON, TD_WARNING_ICON,
+        },
+        WindowsAndMessaging::IDCANCEL,
+      },
+    },
+  };
+
+  use super::{Callback, MessageDialog, MessageDialogResult, MessageLevel};
+  use crate::{Runtime, Window};
+
+  /// The identifier of the first custom button, after the identifiers of the common buttons.
+  const FIRST_BUTTON_ID: i32 = 100;
+
+  pub fn show<R: Runtime>(
+    window: &Window<R>,
+    dialog: MessageDialog,
+    on_result: Callback,
+  ) -> crate::Result<()> {
+    if dialog.input.is_some() {
+      return Err(crate::Error::DialogUnsupported(
+        "the prompts are not supported on Windows".into(),
+      ));
+    }
+    let hwnd = window.hwnd()?;
+    window.run_on_main_thread(move || {
+      let title = HSTRING::from(dialog.title.as_str());
+      let message = HSTRING::from(dialog.message.as_str());
+      let labels = dialog
+        .buttons
+        .iter()
+        .map(|label| HSTRING::from(label.as_str()))
+        .collect::<Vec<_>>();
+      let buttons = labels
+        .iter()
+        .enumerate()
+        .map(|(i, label)| TASKDIALOG_BUTTON {
+          nButtonID: FIRST_BUTTON_ID + i as i32,
+          pszButtonText: PCWSTR(label.as_ptr()),
+        })
+        .collect::<Vec<_>>();
+      let checkbox = dialog
+        .checkbox
+        .as_deref()
+        .map(HSTRING::from)
+        .unwrap_or_default();
+
+      let mut flags = TDF_POSITION_RELATIVE_TO_WINDOW;
+      // the dialog can only be closed without a cancel button by clicking a button
+      if dialog.cancel_button.is_some() {
+        flags |= TDF_ALLOW_DIALOG_CANCELLATION;
+      }
+      let config = TASKDIALOGCONFIG {
+        cbSize: std::mem::size_of::<TASKDIALOGCONFIG>() as u32,
+        hwndParent: hwnd,
+        dwFlags: flags,
+        pszWindowTitle: PCWSTR(title.as_ptr()),
+        Anonymous1: TASKDIALOGCONFIG_0 {
+          pszMainIcon: match dialog.level {
+            MessageLevel::Info => TD_INFORMATION_ICON,
+            MessageLevel::Warning => TD_WARNING_ICON,
+            MessageLevel::Error => TD_ERROR_ICON,
+          },
+        },
+        pszContent: PCWSTR(message.as_ptr()),
+        cButtons: buttons.len() as u32,
+        pButtons: buttons.as_ptr(),
+        nDefaultButton: FIRST_BUTTON_ID + dialog.default_button as i32,
+        pszVerificationText: if dialog.checkbox.is_some() {
+          PCWSTR(checkbox.as_ptr())
+        } else {
+          PCWSTR::null()
+        },
+        ..Default::default()
+      };
+
+      let mut button = 0;
+      let mut checked = BOOL(0);
+      let result =
+        unsafe { TaskDialogIndirect(&config, Some(&mut button), None, Some(&mut checked)) };
+      on_result(
+        result
+          .map(|()| MessageDialogResult {
+            button: if button == IDCANCEL.0 {
+              dialog.cancel_button
+            } else {
+              Some((button - FIRST_BUTTON_ID) as usize)
+            },
+            checked: checked.as_bool(),
+            input: None,
+          })
+          .map_err(|e| crate::Error::Dialog(e.message().to_string())),
+      );
+    })
+  }
+}
+
+#[cfg(all(windows, not(feature = "common-controls-v6")))]
+mod platform {
+  use super::{Callback, MessageDialog};
+  use crate::{Runtime, Window};
+
+  pub fn show<R: Runtime>(
+    _window: &Window<R>,
+    _dialog: MessageDialog,
+    _on_result: Callback,
+  ) -> crate::Result<()> {
+    Err(crate::Error::DialogUnsupported(
+      "the dialogs require the `common-controls-v6` Cargo feature".into(),
+    ))
+  }
+}
+
+#[cfg(mobile)]
+mod platform {
+  use super::{Callback, MessageDialog};
+  use crate::{Runtime, Window};
+
+  pub fn show<R: Runtime>(
+    _window: &Window<R>,
+    _dialog: MessageDialog,
+    _on_result: Callback,
+  ) -> crate::Result<()> {
+    Err(crate::Error::DialogUnsupported(
+      "the dialogs are not supported on mobile".into(),
+    ))
+  }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+  use cocoa::{
+    base::{id, nil, YES},
+    foundation::{NSPoint, NSRect, NSSize, NSString},
+  };
+  use objc::{class, msg_send, sel, sel_impl};
+
+  use super::{Callback, MessageDialog, MessageDialogResult, MessageLevel};
+  use crate::{Runtime, Window};
+
+  /// `NSAlertFirstButtonReturn`, the response of the following buttons are incremented.
+  const FIRST_BUTTON_RETURN: i64 = 1000;
+  /// `NSControlStateValueOn`
+  const STATE_ON: i64 = 1;
+
+  unsafe fn nsstring(string: &str) -> id {
+    let string = NSString::alloc(nil).init_str(string);
+    msg_send![string, autorelease]
+  }
+
+  unsafe fn nsstring_to_string(string: id) -> Option<String> {
+    if string == nil {
+      return None;
+    }
+    let utf8 = string.UTF8String();
+    (!utf8.is_null()).then(|| {
+      std::ffi::CStr::from_ptr(utf8)
+        .to_string_lossy()
+        .into_owned()
+    })
+  }
+
+  unsafe fn run(dialog: &MessageDialog) -> MessageDialogResult {
+    let alert: id = msg_send![class!(NSAlert), new];
+    let () = msg_send![alert, setMessageText: nsstring(&dialog.title)];
+    let () = msg_send![alert, setInformativeText: nsstring(&dialog.message)];
+    let style: u64 = match dialog.level {
+      MessageLevel::Warning => 0,
+      MessageLevel::Info => 1,
+      MessageLevel::Error => 2,
+    };
+    let () = msg_send![alert, setAlertStyle: style];
+
+    for (i, label) in dialog.buttons.iter().enumerate() {
+      let button: id = msg_send![alert, addButtonWithTitle: nsstring(label)];
+      // replaces the Return key equivalent the alert gives to its first button
+      let key = if i == dialog.default_button {
+        "\r"
+      } else if Some(i) == dialog.cancel_button {
+        "\u{1b}"
+      } else {
+        ""
+      };
+      let () = msg_send![button, setKeyEquivalent: nsstring(key)];
+    }
+
+    if let Some(label) = &dialog.checkbox {
+      let () = msg_send![alert, setShowsSuppressionButton: YES];
+      let checkbox: id = msg_send![alert, suppressionButton];
+      let () = msg_send![checkbox, setTitle: nsstring(label)];
+    }
+
+    let field = match &dialog.input {
+      Some(value) => {
+        let field: id = msg_send![class!(NSTextField), alloc];
+        let frame = NSRect::new(NSPoint::new(0., 0.), NSSize::new(260., 24.));
+        let field: id = msg_send![field, initWithFrame: frame];
+        let () = msg_send![field, setStringValue: nsstring(value)];
+        let () = msg_send![alert, setAccessoryView: field];
+        let () = msg_send![alert, layout];
+        let alert_window: id = msg_send![alert, window];
+        let () = msg_send![alert_window, setInitialFirstResponder: field];
+        field
+      }
+      None => nil,
+    };
+
+    let response: i64 = msg_send![alert, runModal];
+    let checkbox: id = msg_send![alert, suppressionButton];
+    let state: i64 = msg_send![checkbox, state];
+    let input = if field == nil {
+      None
+    } else {
+      let value: id = msg_send![field, stringValue];
+      let input = nsstring_to_string(value);
+      let () = msg_send![field, release];
+      input
+    };
+    let () = msg_send![alert, release];
+
+    let index = (response - FIRST_BUTTON_RETURN) as usize;
+    MessageDialogResult {
+      button: (index < dialog.buttons.len())
+        .then_some(index)
+        .or(dialog.cancel_button),
+      checked: dialog.checkbox.is_some() && state == STATE_ON,
+      input,
+    }
+  }
+
+  pub fn show<R: Runtime>(
+    window: &Window<R>,
+    dialog: MessageDialog,
+    on_result: Callback,
+  ) -> crate::Result<()> {
+    window.run_on_main_thread(move || {
+      let result = unsafe { run(&dialog) };
+      on_result(Ok(result));
+    })
+  }
+}
+
+#[cfg(any(
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd"
+))]
+mod platform {
+  use gtk::prelude::*;
+
+  use super::{Callback, MessageDialog, MessageDialogResult, MessageLevel};
+  use crate::{Runtime, Window};
+
+  fn run(parent: &gtk::ApplicationWindow, dialog: &MessageDialog) -> MessageDialogResult {
+    let message_dialog = gtk::MessageDialog::new(
+      Some(parent),
+      gtk::DialogFlags::MODAL | gtk::DialogFlags::DESTROY_WITH_PARENT,
+      match dialog.level {
+        MessageLevel::Info => gtk::MessageType::Info,
+        MessageLevel::Warning => gtk::MessageType::Warning,
+        MessageLevel::Error => gtk::MessageType::Error,
+      },
+      gtk::ButtonsType::None,
+      &dialog.title,
+    );
+    message_dialog.set_secondary_text(Some(&dialog.message));
+
+    // GTK lays out the buttons from the left to the right
+    for (i, label) in dialog.buttons.iter().enumerate().rev() {
+      let response = if Some(i) == dialog.cancel_button {
+        gtk::ResponseType::Cancel
+      } else {
+        gtk::ResponseType::Other(i as u16)
+      };
+      message_dialog.add_button(label, response);
+    }
+    message_dialog.set_default_response(if Some(dialog.default_button) == dialog.cancel_button {
+      gtk::ResponseType::Cancel
+    } else {
+      gtk::ResponseType::Other(dialog.default_button as u16)
+    });
+
+    let area = message_dialog
+      .message_area()
+      .downcast::<gtk::Box>()
+      .expect("the message area of a GtkMessageDialog is a GtkBox");
+    let entry = dialog.input.as_ref().map(|value| {
+      let entry = gtk::Entry::new();
+      entry.set_text(value);
+      entry.set_activates_default(true);
+      area.pack_start(&entry, false, false, 0);
+      entry
+    });
+    let checkbox = dialog.checkbox.as_ref().map(|label| {
+      let checkbox = gtk::CheckButton::with_label(label);
+      area.pack_start(&checkbox, false, false, 0);
+      checkbox
+    });
+    area.show_all();
+
+    let response = message_dialog.run();
+    let result = MessageDialogResult {
+      button: match response {
+        gtk::ResponseType::Other(i) => Some(i as usize),
+        // the Escape key and the close button
+        _ => dialog.cancel_button,
+      },
+      checked: checkbox.is_some_and(|checkbox| checkbox.is_active()),
+      input: entry.map(|entry| entry.text().to_string()),
+    };
+    message_dialog.close();
+    result
+  }
+
+  pub fn show<R: Runtime>(
+    window: &Window<R>,
+    dialog: MessageDialog,
+    on_result: Callback,
+  ) -> crate::Result<()> {
+    let window_ = window.clone();
+    window.run_on_main_thread(move || {
+      let result = window_.gtk_window().map(|parent| run(&parent, &dialog));
+      on_result(result);
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::MessageDialog;
+
+  #[test]
+  fn validates_buttons() {
+    assert!(MessageDialog::confirm("Delete", "Delete the file?")
+      .validate()
+      .is_ok());
+    assert!(MessageDialog::new("Delete", "Delete the file?")
+      .buttons(Vec::<String>::new())
+      .validate()
+      .is_err());
+    assert!(MessageDialog::new("Delete", "Delete the file?")
+      .cancel_button(1)
+      .validate()
+      .is_err());
+  }
+
+  #[test]
+  fn prompt_has_cancel_button() {
+    let dialog = MessageDialog::prompt("Rename", "New name:", "report.pdf");
+    assert_eq!(dialog.buttons, vec!["OK", "Cancel"]);
+    assert_eq!(dialog.cancel_button, Some(1));
+    assert_eq!(dialog.input.as_deref(), Some("report.pdf"));
+  }
+}
@@ -35,11 +35,23 @@ pub enum Error {
   #[error("runtime error: {0}")]
   Runtime(#[from] tauri_runtime::Error),
   /// Window label must be unique.
+  ///
+  /// A free label can be found with [`Manager::suggest_window_label`](crate::Manager::suggest_window_label).
   #[error("a window with label `{0}` already exists")]
   WindowLabelAlreadyExists(String),
   /// Webview label must be unique.
+  ///
+  /// A free label can be found with [`Manager::suggest_webview_label`](crate::Manager::suggest_webview_label).
   #[error("a webview with label `{0}` already exists")]
   WebviewLabelAlreadyExists(String),
+  /// The window or webview label is invalid or uses a prefix reserved by Tauri.
+  #[error("invalid label `{label}`: {error}")]
+  InvalidLabel {
+    /// The rejected label.
+    label: String,
+    /// The reason the label was rejected.
+    error: tauri_utils::label::LabelError,
+  },
   /// Cannot use the webview reparent function on webview windows.
   #[error("cannot reparent when using a WebviewWindow")]
   CannotReparentWebviewWindow,
@@ -64,6 +76,65 @@ pub enum Error {
   /// Error initializing plugin.
   #[error("failed to initialize plugin `{0}`: {1}")]
   PluginInitialization(String, String),
+  /// The plugin name can't be used as a directory name.
+  #[error("invalid plugin name `{0}`, it can't be used as a directory name")]
+  InvalidPluginName(String),
+  /// A plugin uses the name of a Tauri core plugin.
+  #[error("plugin name `{0}` is reserved by a Tauri core plugin")]
+  ReservedPluginName(String),
+  /// A plugin service is not available.
+  #[error("plugin service `{0}` is not available: {1}")]
+  PluginService(String, String),
+  /// Error loading a dynamic plugin library.
+  #[cfg(feature = "dynamic-plugins")]
+  #[cfg_attr(docsrs, doc(cfg(feature = "dynamic-plugins")))]
+  #[error("failed to load dynamic plugin {0}: {1}")]
+  DynamicPlugin(std::path::PathBuf, String),
+  /// A background task was scheduled without a registered handler.
+  #[error("background task `{0}` has no registered handler")]
+  UnregisteredBackgroundTask(String),
+  /// A background task could not be scheduled.
+  #[error("failed to schedule background task `{0}`: {1}")]
+  BackgroundTask(String, String),
+  /// Push notifications error.
+  #[error("push notifications error: {0}")]
+  PushNotifications(String),
+  /// The default handler of a URL scheme or file type could not be queried or set.
+  #[cfg(desktop)]
+  #[error("default handler error: {0}")]
+  DefaultHandler(String),
+  /// The target of [`AppHandle::open_with_default_app`](crate::AppHandle::open_with_default_app) was rejected by the opener policy.
+  #[cfg(desktop)]
+  #[error("cannot open `{0}`: {1}")]
+  OpenDenied(String, String),
+  /// The [workspace](crate::window::Workspace) of a window could not be read or changed.
+  #[cfg(desktop)]
+  #[error("workspace error: {0}")]
+  Workspace(String),
+  /// The opacity of a window could not be changed for a [transition](crate::window::Transition).
+  #[cfg(desktop)]
+  #[error("failed to animate the window: {0}")]
+  Transition(String),
+  /// A [sidecar](crate::sidecar) could not be spawned.
+  #[cfg(desktop)]
+  #[error("failed to spawn the `{0}` sidecar: {1}")]
+  Sidecar(String, String),
+  /// A [migration](crate::migration) of the app data failed.
+  #[error("the {0} migration failed: {1}")]
+  Migration(String, String),
+  /// The [remote control](crate::remote_control) interface could not be started.
+  #[cfg(feature = "remote-control")]
+  #[error("failed to start the remote control interface: {0}")]
+  RemoteControl(String),
+  /// The share sheet could not be opened or failed.
+  #[error("share error: {0}")]
+  Share(String),
+  /// The dialog could not be shown.
+  #[error("dialog error: {0}")]
+  Dialog(String),
+  /// The dialog is not supported on the current platform.
+  #[error("unsupported dialog: {0}")]
+  DialogUnsupported(String),
   /// A part of the URL is malformed or invalid. This may occur when parsing and combining
   /// user-provided URLs and paths.
   #[error("invalid url: {0}")]
@@ -82,9 +153,18 @@ pub enum Error {
   #[error("invalid glob pattern: {0}")]
   GlobPattern(#[from] glob::PatternError),
   /// Image error.
-  #[cfg(any(feature = "image-png", feature = "image-ico"))]
+  #[cfg(any(
+    feature = "image-png",
+    feature = "image-ico",
+    feature = "image-jpeg",
+    feature = "image-svg"
+  ))]
   #[error("failed to process image: {0}")]
   Image(#[from] image::error::ImageError),
+  /// Failed to parse or render an SVG image.
+  #[cfg(feature = "image-svg")]
+  #[error("failed to render SVG image: {0}")]
+  Svg(String),
   /// The Window's raw handle is invalid for the platform.
   #[error("Unexpected `raw_window_handle` for the current platform")]
   InvalidWindowHandle,
@@ -113,6 +193,21 @@ pub enum Error {
   #[cfg(all(desktop, feature = "tray-icon"))]
   #[cfg_attr(docsrs, doc(cfg(all(desktop, feature = "tray-icon"))))]
   BadTrayIcon(#[from] tray_icon::BadIcon),
+  /// Failed to watch the paths.
+  #[error("failed to watch the paths: {0}")]
+  #[cfg(all(desktop, feature = "fs-watch"))]
+  #[cfg_attr(docsrs, doc(cfg(all(desktop, feature = "fs-watch"))))]
+  Watch(#[from] notify::Error),
+  /// The path is not allowed by the scope.
+  #[error("path not allowed on the configured scope: {0}")]
+  #[cfg(all(desktop, feature = "fs-watch"))]
+  #[cfg_attr(docsrs, doc(cfg(all(desktop, feature = "fs-watch"))))]
+  PathNotAllowed(std::path::PathBuf),
+  /// The user didn't grant the access to the global input.
+  #[error("the access to the global input was not granted")]
+  #[cfg(all(desktop, feature = "global-input-listener"))]
+  #[cfg_attr(docsrs, doc(cfg(all(desktop, feature = "global-input-listener"))))]
+  GlobalInputAccessDenied,
   /// Path does not have a parent.
   #[error("path does not have a parent")]
   NoParent,
@@ -0,0 +1,197 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Atomic writes, see [`atomic_write`] and [`safe_replace`].
+
+use std::{
+  fs::OpenOptions,
+  io::Write,
+  path::{Path, PathBuf},
+  sync::atomic::{AtomicU32, Ordering},
+};
+
+/// The temporary file next to the path, on the same filesystem so it can be renamed over the path.
+fn temp_path(path: &Path) -> PathBuf {
+  static COUNTER: AtomicU32 = AtomicU32::new(0);
+  let name = path
+    .file_name()
+    .map(|name| name.to_string_lossy().into_owned())
+    .unwrap_or_default();
+  path.with_file_name(format!(
+    ".{name}.{}.{}.tmp",
+    std::process::id(),
+    COUNTER.fetch_add(1, Ordering::Relaxed)
+  ))
+}
+
+#[cfg(windows)]
+mod platform {
+  use std::path::Path;
+
+  use windows::{
+    core::{HSTRING, PCWSTR},
+    Win32::Storage::FileSystem::{
+      MoveFileExW, ReplaceFileW, MOVEFILE_REPLACE_EXISTING, MOVEFILE_WRITE_THROUGH,
+      REPLACEFILE_IGNORE_MERGE_ERRORS,
+    },
+  };
+
+  /// Replaces the path with the replacement, keeping the attributes and the ACLs of the replaced file.
+  pub fn replace(path: &Path, replacement: &Path) -> std::io::Result<()> {
+    let replaced = HSTRING::from(path);
+    let replacement = HSTRING::from(replacement);
+    let result = if path.exists() {
+      unsafe {
+        ReplaceFileW(
+          &replaced,
+          &replacement,
+          PCWSTR::null(),
+          REPLACEFILE_IGNORE_MERGE_ERRORS,
+          None,
+          None,
+        )
+      }
+    } else {
+      unsafe {
+        MoveFileExW(
+          &replacement,
+          &replaced,
+          MOVEFILE_REPLACE_EXISTING | MOVEFILE_WRITE_THROUGH,
+        )
+      }
+    };
+    result.map_err(Into::into)
+  }
+}
+
+#[cfg(not(windows))]
+mod platform {
+  use std::{fs::File, path::Path};
+
+  /// Renames the replacement over the path, then flushes the directory so the rename survives a power loss.
+  pub fn replace(path: &Path, replacement: &Path) -> std::io::Result<()> {
+    if let Ok(permissions) = std::fs::metadata(path).map(|metadata| metadata.permissions()) {
+      std::fs::set_permissions(replacement, permissions)?;
+    }
+    std::fs::rename(replacement, path)?;
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+      // not all the filesystems can flush a directory
+      let _ = File::open(dir).and_then(|dir| dir.sync_all());
+    }
+    Ok(())
+  }
+}
+
+/// Writes the contents to the path in a single step: the contents are written and flushed to a temporary file
+/// next to the path, which then replaces the path.
+///
+/// The permissions of the replaced file are kept.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use tauri::Manager;
+///
+/// #[tauri::command]
+/// fn save_settings(app: tauri::AppHandle, settings: serde_json::Value) -> tauri::Result<()> {
+///   let path = app.path().app_config_dir()?.join("settings.json");
+///   tauri::fs::atomic_write(path, serde_json::to_vec_pretty(&settings)?)
+/// }
+/// ```
+///
+/// ## Platform-specific
+///
+/// - **Windows:** Uses `ReplaceFileW`, which also keeps the attributes and the ACLs of the replaced file.
+pub fn atomic_write<P: AsRef<Path>, C: AsRef<[u8]>>(path: P, contents: C) -> crate::Result<()> {
+  let path = path.as_ref();
+  let temp = temp_path(path);
+  let result = OpenOptions::new()
+    .write(true)
+    .create_new(true)
+    .open(&temp)
+    .and_then(|mut file| {
+      file.write_all(contents.as_ref())?;
+      file.sync_all()
+    })
+    .and_then(|()| platform::replace(path, &temp));
+  if result.is_err() {
+    let _ = std::fs::remove_file(&temp);
+  }
+  result.map_err(Into::into)
+}
+
+/// Replaces the path with the replacement file in a single step, e.g. a file written by another process or library,
+/// removing the replacement.
+///
+/// The replacement must be on the same filesystem as the path. It is flushed before replacing the path,
+/// and the permissions of the replaced file are kept.
+///
+/// ## Platform-specific
+///
+/// - **Windows:** Uses `ReplaceFileW`, which also keeps the attributes and the ACLs of the replaced file.
+pub fn safe_replace<P: AsRef<Path>, Q: AsRef<Path>>(path: P, replacement: Q) -> crate::Result<()> {
+  let replacement = replacement.as_ref();
+  OpenOptions::new()
+    .write(true)
+    .open(replacement)
+    .and_then(|file| file.sync_all())?;
+  platform::replace(path.as_ref(), replacement).map_err(Into::into)
+}
+
+#[cfg(test)]
+mod tests {
+  use std::path::PathBuf;
+
+  use super::{atomic_write, safe_replace};
+
+  fn test_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("tauri-fs-{name}-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+  }
+
+  #[test]
+  fn writes_atomically() {
+    let dir = test_dir("atomic-write");
+    let path = dir.join("settings.json");
+    atomic_write(&path, b"{}").unwrap();
+    atomic_write(&path, br#"{"theme":"dark"}"#).unwrap();
+
+    assert_eq!(std::fs::read(&path).unwrap(), br#"{"theme":"dark"}"#);
+    // the temporary files are renamed over the path
+    assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 1);
+    std::fs::remove_dir_all(dir).unwrap();
+  }
+
+  #[cfg(unix)]
+  #[test]
+  fn keeps_permissions() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = test_dir("permissions");
+    let path = dir.join("secret");
+    atomic_write(&path, b"1").unwrap();
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600)).unwrap();
+    atomic_write(&path, b"2").unwrap();
+
+    let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+    assert_eq!(mode & 0o777, 0o600);
+    std::fs::remove_dir_all(dir).unwrap();
+  }
+
+  #[test]
+  fn replaces_file() {
+    let dir = test_dir("safe-replace");
+    let path = dir.join("library.db");
+    let replacement = dir.join("library.db.new");
+    std::fs::write(&path, b"old").unwrap();
+    std::fs::write(&replacement, b"new").unwrap();
+    safe_replace(&path, &replacement).unwrap();
+
+    assert_eq!(std::fs::read(&path).unwrap(), b"new");
+    assert!(!replacement.exists());
+    std::fs::remove_dir_all(dir).unwrap();
+  }
+}
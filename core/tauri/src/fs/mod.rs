@@ -0,0 +1,39 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Filesystem helpers for the commands and the plugins.
+//!
+//! ## Atomic writes
+//!
+//! [`atomic_write`] and [`safe_replace`] replace a file in a single step, so a crash or a power loss
+//! in the middle of a write leaves either the previous or the new content, never a truncated file.
+//! They don't check any scope, the commands writing the paths received from the webviews must check them first,
+//! e.g. with [`Scope::is_allowed`](crate::scope::fs::Scope::is_allowed).
+//!
+//! ## Watching
+//!
+//! With the `fs-watch` Cargo feature, [`watch`] reports the changes of files and directories.
+//! The changes are debounced: the changes happening within [`WatchOptions::debounce`] of each other,
+//! e.g. an editor writing a file in several steps, are reported together once the paths are quiet.
+//!
+//! The webviews can watch the paths allowed by the scope of the `core:fs-watch:allow-watch` permission,
+//! which is not part of `core:default`:
+//!
+//! ```json
+//! {
+//!   "identifier": "core:fs-watch:allow-watch",
+//!   "allow": [{ "path": "$APPDATA/**" }]
+//! }
+//! ```
+
+mod atomic;
+#[cfg(all(desktop, feature = "fs-watch"))]
+pub(crate) mod plugin;
+#[cfg(all(desktop, feature = "fs-watch"))]
+mod watch;
+
+pub use atomic::{atomic_write, safe_replace};
+#[cfg(all(desktop, feature = "fs-watch"))]
+#[cfg_attr(docsrs, doc(cfg(all(desktop, feature = "fs-watch"))))]
+pub use watch::{watch, ChangeEvent, ChangeKind, WatchOptions, Watcher};
@@ -0,0 +1,79 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::{path::PathBuf, time::Duration};
+
+use serde::Deserialize;
+
+use crate::{
+  command,
+  ipc::{Channel, CommandScope, GlobalScope},
+  plugin::{Builder, TauriPlugin},
+  scope::fs::Scope,
+  utils::config::FsScope,
+  Manager, ResourceId, Runtime, Webview,
+};
+
+use super::{ChangeEvent, WatchOptions};
+
+/// A path of the scope of the `watch` command, e.g. `"$APPDATA/**"` or `{ "path": "$APPDATA/**" }`.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum ScopeEntry {
+  Value(PathBuf),
+  Object { path: PathBuf },
+}
+
+impl ScopeEntry {
+  fn path(&self) -> PathBuf {
+    match self {
+      Self::Value(path) | Self::Object { path } => path.clone(),
+    }
+  }
+}
+
+#[command(root = "crate")]
+fn watch<R: Runtime>(
+  webview: Webview<R>,
+  paths: Vec<PathBuf>,
+  recursive: bool,
+  debounce: Option<u64>,
+  on_event: Channel<ChangeEvent>,
+  command_scope: CommandScope<ScopeEntry>,
+  global_scope: GlobalScope<ScopeEntry>,
+) -> crate::Result<ResourceId> {
+  let scope = Scope::new(
+    &webview,
+    &FsScope::Scope {
+      allow: command_scope
+        .allows()
+        .iter()
+        .chain(global_scope.allows())
+        .map(|entry| entry.path())
+        .collect(),
+      deny: command_scope
+        .denies()
+        .iter()
+        .chain(global_scope.denies())
+        .map(|entry| entry.path())
+        .collect(),
+      require_literal_leading_dot: None,
+    },
+  )?;
+
+  let mut options = WatchOptions::new().recursive(recursive).scope(scope);
+  if let Some(debounce) = debounce {
+    options = options.debounce(Duration::from_millis(debounce));
+  }
+  let watcher = super::watch(&webview, paths, options, move |event| {
+    let _ = on_event.send(event);
+  })?;
+  Ok(webview.resources_table().add(watcher))
+}
+
+pub(crate) fn init<R: Runtime>() -> TauriPlugin<R> {
+  Builder::new("fs-watch")
+    .invoke_handler(crate::generate_handler![watch])
+    .build()
+}
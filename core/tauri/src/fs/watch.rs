@@ -0,0 +1,326 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Watching files and directories for changes, see [`watch`].
+
+use std::{
+  collections::HashMap,
+  path::PathBuf,
+  sync::{Arc, Mutex},
+  time::Duration,
+};
+
+use serde::Serialize;
+
+use crate::{scope::fs::Scope, time::Timer, Manager, Resource, Runtime};
+
+/// The kind of a change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub enum ChangeKind {
+  /// The path was created.
+  Create,
+  /// The content or the metadata of the path changed.
+  Modify,
+  /// The path was renamed, to or from the watched paths.
+  Rename,
+  /// The path was removed.
+  Remove,
+  /// Another change, e.g. an unmount.
+  Other,
+}
+
+impl From<&notify::EventKind> for ChangeKind {
+  fn from(kind: &notify::EventKind) -> Self {
+    use notify::{event::ModifyKind, EventKind};
+    match kind {
+      EventKind::Create(_) => Self::Create,
+      EventKind::Modify(ModifyKind::Name(_)) => Self::Rename,
+      EventKind::Modify(_) => Self::Modify,
+      EventKind::Remove(_) => Self::Remove,
+      _ => Self::Other,
+    }
+  }
+}
+
+/// The changes of a kind reported together, see [`watch`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct ChangeEvent {
+  /// The kind of the changes.
+  pub kind: ChangeKind,
+  /// The changed paths, sorted.
+  pub paths: Vec<PathBuf>,
+}
+
+/// The options of [`watch`].
+#[derive(Debug, Clone)]
+pub struct WatchOptions {
+  recursive: bool,
+  debounce: Duration,
+  scope: Option<Scope>,
+}
+
+impl Default for WatchOptions {
+  fn default() -> Self {
+    Self {
+      recursive: false,
+      debounce: Duration::from_millis(500),
+      scope: None,
+    }
+  }
+}
+
+impl WatchOptions {
+  /// Watches the direct children of the directories, 500 milliseconds of debounce and no scope.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Watches the whole tree of the directories.
+  #[must_use]
+  pub fn recursive(mut self, recursive: bool) -> Self {
+    self.recursive = recursive;
+    self
+  }
+
+  /// Sets how long the paths must be quiet before their changes are reported.
+  #[must_use]
+  pub fn debounce(mut self, debounce: Duration) -> Self {
+    self.debounce = debounce;
+    self
+  }
+
+  /// Restricts the watcher to the paths allowed by the scope:
+  /// the watched paths must be allowed and the changes of the other paths are not reported.
+  #[must_use]
+  pub fn scope(mut self, scope: Scope) -> Self {
+    self.scope.replace(scope);
+    self
+  }
+}
+
+type Handler = Box<dyn Fn(ChangeEvent) + Send + Sync>;
+
+/// Collects the changes until the paths are quiet for the debounce duration.
+struct Debouncer {
+  clock: Arc<dyn crate::time::Clock>,
+  debounce: Duration,
+  scope: Option<Scope>,
+  handler: Handler,
+  pending: Mutex<HashMap<PathBuf, ChangeKind>>,
+  timer: Mutex<Option<Timer>>,
+}
+
+impl Debouncer {
+  fn push(self: &Arc<Self>, kind: ChangeKind, paths: Vec<PathBuf>) {
+    {
+      let mut pending = self.pending.lock().unwrap();
+      for path in paths {
+        if self
+          .scope
+          .as_ref()
+          .is_some_and(|scope| !scope.is_allowed(&path))
+        {
+          continue;
+        }
+        let kind = match (pending.get(&path), kind) {
+          // a file written after its creation is still new
+          (Some(ChangeKind::Create), ChangeKind::Modify) => ChangeKind::Create,
+          _ => kind,
+        };
+        pending.insert(path, kind);
+      }
+      if pending.is_empty() {
+        return;
+      }
+    }
+
+    // replacing the timer cancels the previous one
+    let debouncer = Arc::downgrade(self);
+    let timer = self.clock.set_timeout(
+      self.debounce,
+      Box::new(move || {
+        if let Some(debouncer) = debouncer.upgrade() {
+          debouncer.flush();
+        }
+      }),
+    );
+    self.timer.lock().unwrap().replace(timer);
+  }
+
+  fn flush(&self) {
+    let pending = std::mem::take(&mut *self.pending.lock().unwrap());
+    let mut events = HashMap::<ChangeKind, Vec<PathBuf>>::new();
+    for (path, kind) in pending {
+      events.entry(kind).or_default().push(path);
+    }
+    let mut events = events.into_iter().collect::<Vec<_>>();
+    events.sort_by_key(|(kind, _)| *kind);
+    for (kind, mut paths) in events {
+      paths.sort();
+      (self.handler)(ChangeEvent { kind, paths });
+    }
+  }
+}
+
+/// Watches the paths until the returned [`Watcher`] is dropped, calling the handler with their debounced changes.
+///
+/// See the [module documentation](crate::fs) for more information.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use tauri::{fs::WatchOptions, Manager};
+///
+/// tauri::Builder::default().setup(|app| {
+///   let config_dir = app.path().app_config_dir()?;
+///   let watcher = tauri::fs::watch(app, vec![config_dir], WatchOptions::new(), |event| {
+///     println!("{:?}: {:?}", event.kind, event.paths);
+///   })?;
+///   app.manage(watcher);
+///   Ok(())
+/// });
+/// ```
+pub fn watch<R: Runtime, M: Manager<R>, F: Fn(ChangeEvent) + Send + Sync + 'static>(
+  manager: &M,
+  paths: Vec<PathBuf>,
+  options: WatchOptions,
+  handler: F,
+) -> crate::Result<Watcher> {
+  use notify::Watcher as _;
+
+  if let Some(scope) = &options.scope {
+    if let Some(path) = paths.iter().find(|path| !scope.is_allowed(path)) {
+      return Err(crate::Error::PathNotAllowed(path.clone()));
+    }
+  }
+
+  let debouncer = Arc::new(Debouncer {
+    clock: manager.clock(),
+    debounce: options.debounce,
+    scope: options.scope,
+    handler: Box::new(handler),
+    pending: Default::default(),
+    timer: Default::default(),
+  });
+  let mut watcher =
+    notify::recommended_watcher(move |event: notify::Result<notify::Event>| match event {
+      Ok(event) => debouncer.push((&event.kind).into(), event.paths),
+      Err(e) => log::error!("failed to watch the paths: {e}"),
+    })?;
+
+  let mode = if options.recursive {
+    notify::RecursiveMode::Recursive
+  } else {
+    notify::RecursiveMode::NonRecursive
+  };
+  for path in &paths {
+    watcher.watch(path, mode)?;
+  }
+
+  Ok(Watcher {
+    paths,
+    _watcher: Mutex::new(watcher),
+  })
+}
+
+/// Watches paths until dropped, see [`watch`].
+pub struct Watcher {
+  paths: Vec<PathBuf>,
+  _watcher: Mutex<notify::RecommendedWatcher>,
+}
+
+impl Watcher {
+  /// The watched paths.
+  pub fn paths(&self) -> &[PathBuf] {
+    &self.paths
+  }
+}
+
+impl std::fmt::Debug for Watcher {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("Watcher")
+      .field("paths", &self.paths)
+      .finish()
+  }
+}
+
+impl Resource for Watcher {}
+
+#[cfg(test)]
+mod tests {
+  use std::{
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    time::Duration,
+  };
+
+  use super::{ChangeEvent, ChangeKind, Debouncer};
+  use crate::{
+    test::{mock_app, mock_clock},
+    Manager,
+  };
+
+  #[test]
+  fn debounces_changes() {
+    let app = mock_app();
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let events_ = events.clone();
+    let debouncer = Arc::new(Debouncer {
+      clock: app.clock(),
+      debounce: Duration::from_millis(500),
+      scope: None,
+      handler: Box::new(move |event| events_.lock().unwrap().push(event)),
+      pending: Default::default(),
+      timer: Default::default(),
+    });
+
+    debouncer.push(ChangeKind::Create, vec![PathBuf::from("/notes/a.md")]);
+    mock_clock(&app).advance(Duration::from_millis(300));
+    debouncer.push(ChangeKind::Modify, vec![PathBuf::from("/notes/a.md")]);
+    debouncer.push(ChangeKind::Modify, vec![PathBuf::from("/notes/b.md")]);
+    mock_clock(&app).advance(Duration::from_millis(300));
+    assert!(events.lock().unwrap().is_empty());
+
+    mock_clock(&app).advance(Duration::from_millis(200));
+    assert_eq!(
+      *events.lock().unwrap(),
+      vec![
+        ChangeEvent {
+          kind: ChangeKind::Create,
+          paths: vec![PathBuf::from("/notes/a.md")],
+        },
+        ChangeEvent {
+          kind: ChangeKind::Modify,
+          paths: vec![PathBuf::from("/notes/b.md")],
+        },
+      ]
+    );
+  }
+
+  #[test]
+  fn rejects_paths_out_of_scope() {
+    let app = mock_app();
+    let dir = std::env::temp_dir();
+    let scope = crate::scope::fs::Scope::new(
+      &app,
+      &crate::utils::config::FsScope::AllowedPaths(vec![dir.join("allowed")]),
+    )
+    .unwrap();
+    let result = super::watch(
+      &app,
+      vec![dir.clone()],
+      super::WatchOptions::new().scope(scope),
+      |_| {},
+    );
+    assert!(matches!(result, Err(crate::Error::PathNotAllowed(path)) if path == dir));
+
+    let watcher =
+      super::watch(&app, vec![dir.clone()], super::WatchOptions::new(), |_| {}).unwrap();
+    assert_eq!(watcher.paths(), [dir]);
+  }
+}
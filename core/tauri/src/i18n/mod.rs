@@ -0,0 +1,550 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Localization of the app, shared by the Rust code and the webviews, see [`I18n`].
+//!
+//! The locale bundles are loaded from the [`LOCALES_DIR`] directory of the resources, one file per locale named after
+//! its language tag, e.g. `locales/en.json` and `locales/pt-BR.ftl`:
+//!
+//! - The JSON files map the keys to the messages, the nested objects being flattened with `.`, e.g. `{ "menu": { "quit": "Quit" } }`
+//!   defines the `menu.quit` key. The arguments are written `{name}`.
+//! - The Fluent files support the simple messages and their attributes, `key = value` and `.attribute = value`
+//!   for the `key.attribute` key, with the `{ $name }` variables and the `{ "text" }` string literals.
+//!   The selectors, terms and functions are not supported.
+//!
+//! The messages missing from the current locale are looked up in its language, e.g. `pt` for `pt-BR`,
+//! then in the [fallback locale](I18n::set_fallback_locale). The key itself is returned if it is found nowhere.
+//!
+//! The webviews read the messages of the current locale with the `window.__TAURI_I18N__.t(key, args)` helper,
+//! available once its `ready` promise resolves, or with the `@tauri-apps/api/i18n` module.
+//!
+//! ```rust,no_run
+//! use tauri::{i18n::I18nExt, menu::{MenuBuilder, MenuItemBuilder}};
+//!
+//! tauri::Builder::default()
+//!   .setup(|app| {
+//!     let i18n = app.i18n();
+//!     let quit = MenuItemBuilder::with_id("quit", i18n.t("menu.quit", &[])).build(app)?;
+//!     let menu = MenuBuilder::new(app).item(&quit).build()?;
+//!     app.set_menu(menu)?;
+//!     println!("{}", i18n.t("greeting", &[("name", &"Tauri")]));
+//!     Ok(())
+//!   });
+//! ```
+
+use std::{
+  collections::HashMap,
+  fmt::Display,
+  path::Path,
+  sync::{Arc, Mutex},
+};
+
+use serde::Serialize;
+
+use crate::{AppHandle, Emitter, Manager, Runtime, State};
+
+pub(crate) mod plugin;
+
+/// The directory of the resources the locale bundles are loaded from.
+pub const LOCALES_DIR: &str = "locales";
+/// The event emitted to the webviews with the new locale when it changes.
+pub const LOCALE_CHANGED_EVENT: &str = "tauri://locale-changed";
+/// The locale used for the messages missing from the current locale, unless changed with [`I18n::set_fallback_locale`].
+pub const DEFAULT_FALLBACK_LOCALE: &str = "en";
+/// The file of the app data directory storing the locale set with [`I18n::set_locale`].
+const LOCALE_FILE_NAME: &str = ".locale";
+
+type Messages = HashMap<String, String>;
+type LocaleChangedHandler<R> = dyn Fn(&AppHandle<R>, &str) + Send + Sync;
+
+/// The messages of a locale, sent to the webviews.
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct LocaleMessages {
+  locale: String,
+  messages: Messages,
+}
+
+/// Normalizes a locale to a language tag, e.g. `en_US.UTF-8` to `en-US`.
+fn normalize(locale: &str) -> String {
+  locale
+    .split(['.', '@'])
+    .next()
+    .unwrap_or_default()
+    .trim()
+    .replace('_', "-")
+}
+
+/// The language of the locale, e.g. `pt` for `pt-BR`.
+fn language(locale: &str) -> &str {
+  locale.split('-').next().unwrap_or(locale)
+}
+
+/// Replaces the `{name}` placeholders with the arguments, keeping the unknown ones.
+fn format(message: &str, args: &[(&str, &dyn Display)]) -> String {
+  let mut formatted = String::with_capacity(message.len());
+  let mut rest = message;
+  while let Some(start) = rest.find('{') {
+    formatted.push_str(&rest[..start]);
+    rest = &rest[start..];
+    let end = rest.find('}');
+    let arg = end.and_then(|end| {
+      let name = &rest[1..end];
+      args.iter().find(|(n, _)| *n == name).map(|(_, v)| (end, v))
+    });
+    match arg {
+      Some((end, value)) => {
+        formatted.push_str(&value.to_string());
+        rest = &rest[end + 1..];
+      }
+      None => {
+        formatted.push('{');
+        rest = &rest[1..];
+      }
+    }
+  }
+  formatted.push_str(rest);
+  formatted
+}
+
+/// Flattens the nested objects of a JSON bundle.
+fn parse_json(value: serde_json::Value, prefix: &str, messages: &mut Messages) {
+  let serde_json::Value::Object(map) = value else {
+    return;
+  };
+  for (key, value) in map {
+    let key = if prefix.is_empty() {
+      key
+    } else {
+      format!("{prefix}.{key}")
+    };
+    match value {
+      serde_json::Value::String(message) => {
+        messages.insert(key, message);
+      }
+      serde_json::Value::Object(_) => parse_json(value, &key, messages),
+      serde_json::Value::Number(_) | serde_json::Value::Bool(_) => {
+        messages.insert(key, value.to_string());
+      }
+      _ => {}
+    }
+  }
+}
+
+/// Converts the `{ $name }` variables and `{ "text" }` literals of a Fluent pattern.
+fn convert_fluent_pattern(pattern: &str) -> String {
+  let mut converted = String::with_capacity(pattern.len());
+  let mut rest = pattern;
+  while let Some(start) = rest.find('{') {
+    converted.push_str(&rest[..start]);
+    let Some(end) = rest[start..].find('}').map(|end| start + end) else {
+      break;
+    };
+    let expression = rest[start + 1..end].trim();
+    if let Some(variable) = expression.strip_prefix('$') {
+      converted.push('{');
+      converted.push_str(variable.trim());
+      converted.push('}');
+    } else if let Some(literal) = expression
+      .strip_prefix('"')
+      .and_then(|e| e.strip_suffix('"'))
+    {
+      converted.push_str(literal);
+    } else {
+      converted.push_str(&rest[start..=end]);
+    }
+    rest = &rest[end + 1..];
+  }
+  converted.push_str(rest);
+  converted
+}
+
+/// Parses the simple messages and attributes of a Fluent bundle.
+fn parse_fluent(source: &str, messages: &mut Messages) {
+  let mut message: Option<String> = None;
+  let mut current: Option<(String, String)> = None;
+  let mut flush = |current: &mut Option<(String, String)>| {
+    if let Some((key, value)) = current.take() {
+      messages.insert(key, convert_fluent_pattern(value.trim()));
+    }
+  };
+
+  for line in source.lines() {
+    if line.starts_with('#') || line.trim().is_empty() {
+      continue;
+    }
+    let indented = line.starts_with([' ', '\t']);
+    let trimmed = line.trim();
+    if indented {
+      if let (Some(attribute), Some(message)) = (trimmed.strip_prefix('.'), &message) {
+        if let Some((name, value)) = attribute.split_once('=') {
+          flush(&mut current);
+          current = Some((format!("{message}.{}", name.trim()), value.trim().into()));
+          continue;
+        }
+      }
+      // a continuation line of a multiline pattern
+      if let Some((_, value)) = &mut current {
+        if !value.is_empty() {
+          value.push('\n');
+        }
+        value.push_str(trimmed);
+      }
+    } else if let Some((key, value)) = trimmed.split_once('=') {
+      flush(&mut current);
+      let key = key.trim();
+      // terms start with `-` and are not supported
+      if key.starts_with('-') {
+        message = None;
+        continue;
+      }
+      message = Some(key.into());
+      current = Some((key.into(), value.trim().into()));
+    }
+  }
+  flush(&mut current);
+}
+
+/// The locale bundles and the fallback rules.
+#[derive(Debug, Default)]
+struct Catalog {
+  bundles: HashMap<String, Messages>,
+  fallback: String,
+}
+
+impl Catalog {
+  /// The bundle locale matching the locale, case-insensitively.
+  fn bundle(&self, locale: &str) -> Option<&Messages> {
+    self
+      .bundles
+      .iter()
+      .find(|(l, _)| l.eq_ignore_ascii_case(locale))
+      .map(|(_, messages)| messages)
+  }
+
+  /// The bundles to look a message up in, by priority.
+  fn chain(&self, locale: &str) -> Vec<&Messages> {
+    [
+      locale,
+      language(locale),
+      self.fallback.as_str(),
+      language(&self.fallback),
+    ]
+    .into_iter()
+    .filter_map(|locale| self.bundle(locale))
+    .fold(Vec::new(), |mut chain, bundle| {
+      if !chain.iter().any(|b| std::ptr::eq(*b, bundle)) {
+        chain.push(bundle);
+      }
+      chain
+    })
+  }
+
+  fn message(&self, locale: &str, key: &str) -> Option<&str> {
+    self
+      .chain(locale)
+      .into_iter()
+      .find_map(|bundle| bundle.get(key))
+      .map(String::as_str)
+  }
+
+  /// All the messages available for the locale, including the fallbacks.
+  fn messages(&self, locale: &str) -> Messages {
+    let mut messages = Messages::new();
+    for bundle in self.chain(locale).into_iter().rev() {
+      messages.extend(bundle.iter().map(|(k, v)| (k.clone(), v.clone())));
+    }
+    messages
+  }
+}
+
+/// Loads the locale bundles and translates the messages, see the [module documentation](self).
+///
+/// Get it with [`I18nExt::i18n`].
+pub struct I18n<R: Runtime> {
+  app: AppHandle<R>,
+  catalog: Mutex<Catalog>,
+  /// The locale set with [`Self::set_locale`], if any.
+  locale_override: Mutex<Option<String>>,
+  handlers: Mutex<Vec<Arc<LocaleChangedHandler<R>>>>,
+}
+
+impl<R: Runtime> I18n<R> {
+  fn new(app: &AppHandle<R>) -> Self {
+    let i18n = Self {
+      app: app.clone(),
+      catalog: Mutex::new(Catalog {
+        bundles: HashMap::new(),
+        fallback: DEFAULT_FALLBACK_LOCALE.into(),
+      }),
+      locale_override: Mutex::new(None),
+      handlers: Default::default(),
+    };
+
+    if let Ok(dir) = app.path().resource_dir() {
+      if let Err(e) = i18n.load_dir(dir.join(LOCALES_DIR)) {
+        log::error!("failed to load the locale bundles: {e}");
+      }
+    }
+    if let Some(path) = i18n.locale_file() {
+      if let Ok(locale) = std::fs::read_to_string(path) {
+        let locale = normalize(&locale);
+        if !locale.is_empty() {
+          i18n.locale_override.lock().unwrap().replace(locale);
+        }
+      }
+    }
+    i18n
+  }
+
+  fn locale_file(&self) -> Option<std::path::PathBuf> {
+    self
+      .app
+      .path()
+      .app_data_dir()
+      .ok()
+      .map(|dir| dir.join(LOCALE_FILE_NAME))
+  }
+
+  /// Loads the `<locale>.json` and `<locale>.ftl` bundles of the directory, merged with the messages already loaded.
+  ///
+  /// The bundles of the [`LOCALES_DIR`] resource directory are loaded automatically. Does nothing if the directory does not exist.
+  pub fn load_dir<P: AsRef<Path>>(&self, dir: P) -> crate::Result<()> {
+    let entries = match std::fs::read_dir(dir) {
+      Ok(entries) => entries,
+      Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+      Err(e) => return Err(e.into()),
+    };
+    for entry in entries {
+      let path = entry?.path();
+      let (Some(locale), Some(extension)) = (path.file_stem(), path.extension()) else {
+        continue;
+      };
+      let locale = normalize(&locale.to_string_lossy());
+      let mut messages = Messages::new();
+      match extension.to_str() {
+        Some("json") => parse_json(
+          serde_json::from_slice(&std::fs::read(&path)?)?,
+          "",
+          &mut messages,
+        ),
+        Some("ftl") => parse_fluent(&std::fs::read_to_string(&path)?, &mut messages),
+        _ => continue,
+      }
+      self.add_messages(locale, messages);
+    }
+    Ok(())
+  }
+
+  /// Adds messages to the bundle of the locale, replacing the messages with the same key.
+  pub fn add_messages<L: Into<String>, K: Into<String>, V: Into<String>>(
+    &self,
+    locale: L,
+    messages: impl IntoIterator<Item = (K, V)>,
+  ) {
+    let locale = normalize(&locale.into());
+    let mut catalog = self.catalog.lock().unwrap();
+    let key = catalog
+      .bundles
+      .keys()
+      .find(|l| l.eq_ignore_ascii_case(&locale))
+      .cloned()
+      .unwrap_or(locale);
+    catalog
+      .bundles
+      .entry(key)
+      .or_default()
+      .extend(messages.into_iter().map(|(k, v)| (k.into(), v.into())));
+  }
+
+  /// Sets the locale used for the messages missing from the current locale, [`DEFAULT_FALLBACK_LOCALE`] by default.
+  pub fn set_fallback_locale(&self, locale: &str) {
+    self.catalog.lock().unwrap().fallback = normalize(locale);
+  }
+
+  /// The locale set with [`Self::set_locale`], or the locale of the system.
+  pub fn locale(&self) -> String {
+    if let Some(locale) = self.locale_override.lock().unwrap().clone() {
+      return locale;
+    }
+    sys_locale::get_locale()
+      .map(|locale| normalize(&locale))
+      .filter(|locale| !locale.is_empty())
+      .unwrap_or_else(|| self.catalog.lock().unwrap().fallback.clone())
+  }
+
+  /// The locales of the loaded bundles, sorted.
+  pub fn available_locales(&self) -> Vec<String> {
+    let mut locales = self
+      .catalog
+      .lock()
+      .unwrap()
+      .bundles
+      .keys()
+      .cloned()
+      .collect::<Vec<_>>();
+    locales.sort();
+    locales
+  }
+
+  /// Changes the locale, persisted in the app data directory so it is restored on the next launch.
+  /// `None` restores the locale of the system.
+  ///
+  /// Emits the [`LOCALE_CHANGED_EVENT`] event, calls the [`Self::on_locale_changed`] handlers
+  /// and updates the messages of the webviews helper.
+  pub fn set_locale(&self, locale: Option<&str>) -> crate::Result<()> {
+    let previous = self.locale();
+    let locale = locale.map(normalize).filter(|l| !l.is_empty());
+    if let Some(path) = self.locale_file() {
+      match &locale {
+        Some(locale) => {
+          if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+          }
+          std::fs::write(path, locale)?;
+        }
+        None => {
+          if let Err(e) = std::fs::remove_file(path) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+              return Err(e.into());
+            }
+          }
+        }
+      }
+    }
+    *self.locale_override.lock().unwrap() = locale;
+
+    let locale = self.locale();
+    if locale != previous {
+      self.notify(&locale);
+    }
+    Ok(())
+  }
+
+  fn notify(&self, locale: &str) {
+    let script = format!(
+      "window.__TAURI_I18N__ && window.__TAURI_I18N__.__update({})",
+      serde_json::to_string(&self.locale_messages()).unwrap_or_else(|_| "null".into())
+    );
+    for webview in self.app.webviews().values() {
+      let _ = webview.eval(&script);
+    }
+    let handlers = self.handlers.lock().unwrap().clone();
+    for handler in handlers {
+      handler(&self.app, locale);
+    }
+    let _ = self.app.emit(LOCALE_CHANGED_EVENT, locale);
+  }
+
+  /// Registers a handler called with the new locale when it changes, e.g. to translate the menus and the tray.
+  pub fn on_locale_changed<F: Fn(&AppHandle<R>, &str) + Send + Sync + 'static>(&self, handler: F) {
+    self.handlers.lock().unwrap().push(Arc::new(handler));
+  }
+
+  /// Translates the message of the key in the current locale, replacing the `{name}` arguments.
+  ///
+  /// Returns the key if the message is missing.
+  pub fn t(&self, key: &str, args: &[(&str, &dyn Display)]) -> String {
+    self.translate(&self.locale(), key, args)
+  }
+
+  /// Translates the message of the key in the locale, see [`Self::t`].
+  pub fn translate(&self, locale: &str, key: &str, args: &[(&str, &dyn Display)]) -> String {
+    match self
+      .catalog
+      .lock()
+      .unwrap()
+      .message(&normalize(locale), key)
+    {
+      Some(message) => format(message, args),
+      None => {
+        log::debug!("missing `{key}` message for the `{locale}` locale");
+        key.into()
+      }
+    }
+  }
+
+  pub(crate) fn locale_messages(&self) -> LocaleMessages {
+    let locale = self.locale();
+    let messages = self.catalog.lock().unwrap().messages(&locale);
+    LocaleMessages { locale, messages }
+  }
+}
+
+/// Extensions to [`Manager`] for the localization.
+pub trait I18nExt<R: Runtime> {
+  /// The [`I18n`] of the app.
+  fn i18n(&self) -> State<'_, I18n<R>>;
+}
+
+impl<R: Runtime, M: Manager<R>> I18nExt<R> for M {
+  fn i18n(&self) -> State<'_, I18n<R>> {
+    self.state::<I18n<R>>()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::collections::HashMap;
+
+  use super::{format, normalize, parse_fluent, parse_json, Catalog};
+
+  #[test]
+  fn parses_bundles() {
+    let mut messages = HashMap::new();
+    parse_json(
+      serde_json::json!({ "menu": { "quit": "Quit", "count": 2 }, "greeting": "Hello {name}" }),
+      "",
+      &mut messages,
+    );
+    assert_eq!(messages["menu.quit"], "Quit");
+    assert_eq!(messages["menu.count"], "2");
+    assert_eq!(messages["greeting"], "Hello {name}");
+
+    let mut messages = HashMap::new();
+    parse_fluent(
+      "# comment\n-brand = Tauri\ngreeting = Hello { $name }!\nquit = Quit { \"{\" }\n    .tooltip = Exit the app\nabout =\n    Multiline\n    message\n",
+      &mut messages,
+    );
+    assert_eq!(messages["greeting"], "Hello {name}!");
+    assert_eq!(messages["quit"], "Quit {");
+    assert_eq!(messages["quit.tooltip"], "Exit the app");
+    assert_eq!(messages["about"], "Multiline\nmessage");
+    assert!(!messages.contains_key("-brand"));
+  }
+
+  #[test]
+  fn formats_messages() {
+    assert_eq!(
+      format(
+        "Hello {name}, {count} new {unknown}",
+        &[("name", &"Ada"), ("count", &3)]
+      ),
+      "Hello Ada, 3 new {unknown}"
+    );
+    assert_eq!(normalize("pt_BR.UTF-8"), "pt-BR");
+  }
+
+  #[test]
+  fn falls_back() {
+    let bundle = |messages: &[(&str, &str)]| {
+      messages
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect::<HashMap<_, _>>()
+    };
+    let catalog = Catalog {
+      bundles: HashMap::from([
+        ("en".into(), bundle(&[("quit", "Quit"), ("save", "Save")])),
+        ("pt".into(), bundle(&[("quit", "Sair"), ("save", "Salvar")])),
+        ("pt-BR".into(), bundle(&[("save", "Gravar")])),
+      ]),
+      fallback: "en".into(),
+    };
+    assert_eq!(catalog.message("pt-br", "save"), Some("Gravar"));
+    assert_eq!(catalog.message("pt-BR", "quit"), Some("Sair"));
+    assert_eq!(catalog.message("de-DE", "quit"), Some("Quit"));
+    assert_eq!(catalog.message("pt-BR", "missing"), None);
+    assert_eq!(catalog.messages("pt-BR")["quit"], "Sair");
+  }
+}
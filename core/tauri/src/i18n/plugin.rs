@@ -0,0 +1,72 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::{collections::HashMap, fmt::Display};
+
+use crate::{
+  command,
+  plugin::{Builder, TauriPlugin},
+  AppHandle, Manager, Runtime,
+};
+
+use super::{I18n, I18nExt, LocaleMessages};
+
+#[command(root = "crate")]
+fn messages<R: Runtime>(app: AppHandle<R>) -> LocaleMessages {
+  app.i18n().locale_messages()
+}
+
+#[command(root = "crate")]
+fn translate<R: Runtime>(
+  app: AppHandle<R>,
+  key: String,
+  args: Option<HashMap<String, serde_json::Value>>,
+) -> String {
+  // the strings are inserted without their quotes
+  let args = args
+    .unwrap_or_default()
+    .into_iter()
+    .map(|(name, value)| match value {
+      serde_json::Value::String(value) => (name, value),
+      value => (name, value.to_string()),
+    })
+    .collect::<Vec<_>>();
+  let args = args
+    .iter()
+    .map(|(name, value)| (name.as_str(), value as &dyn Display))
+    .collect::<Vec<_>>();
+  app.i18n().t(&key, &args)
+}
+
+#[command(root = "crate")]
+fn locale<R: Runtime>(app: AppHandle<R>) -> String {
+  app.i18n().locale()
+}
+
+#[command(root = "crate")]
+fn available_locales<R: Runtime>(app: AppHandle<R>) -> Vec<String> {
+  app.i18n().available_locales()
+}
+
+#[command(root = "crate")]
+fn set_locale<R: Runtime>(app: AppHandle<R>, locale: Option<String>) -> crate::Result<()> {
+  app.i18n().set_locale(locale.as_deref())
+}
+
+pub(crate) fn init<R: Runtime>() -> TauriPlugin<R> {
+  Builder::new("i18n")
+    .js_init_script(include_str!("../../scripts/i18n.js").into())
+    .invoke_handler(crate::generate_handler![
+      messages,
+      translate,
+      locale,
+      available_locales,
+      set_locale
+    ])
+    .setup(|app, _api| {
+      app.manage(I18n::new(app));
+      Ok(())
+    })
+    .build()
+}
@@ -0,0 +1,206 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Detection of the user inactivity, see [`AppHandle::idle_time`] and [`AppHandle::on_idle`].
+
+use std::time::Duration;
+
+use serde::Serialize;
+
+use crate::{time::Timer, AppHandle, Manager, Runtime};
+
+/// The longest delay between the user activity and the [`IdleState::Active`] notification.
+pub const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Whether the user is inactive, passed to the [`AppHandle::on_idle`] handler.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum IdleState {
+  /// No keyboard or mouse input for at least the threshold.
+  Idle,
+  /// The user interacted with the system again after being idle.
+  Active,
+}
+
+/// Tracks the transitions between the idle and active states.
+#[derive(Debug)]
+struct IdleMonitor {
+  threshold: Duration,
+  idle: bool,
+}
+
+impl IdleMonitor {
+  fn new(threshold: Duration) -> Self {
+    Self {
+      threshold,
+      idle: false,
+    }
+  }
+
+  /// Returns the new state when the idle time crosses the threshold.
+  fn update(&mut self, idle_time: Duration) -> Option<IdleState> {
+    let idle = idle_time >= self.threshold;
+    if idle == self.idle {
+      return None;
+    }
+    self.idle = idle;
+    Some(if idle {
+      IdleState::Idle
+    } else {
+      IdleState::Active
+    })
+  }
+}
+
+pub(crate) fn idle_time() -> Option<Duration> {
+  platform::idle_time()
+}
+
+/// Polls the idle time, calling the handler each time the user becomes idle or active again.
+pub(crate) fn on_idle<R: Runtime, F: Fn(&AppHandle<R>, IdleState) + Send + 'static>(
+  app: &AppHandle<R>,
+  threshold: Duration,
+  handler: F,
+) -> Timer {
+  let mut monitor = IdleMonitor::new(threshold);
+  let app_ = app.clone();
+  app.clock().set_interval(
+    POLL_INTERVAL,
+    Box::new(move || {
+      if let Some(state) = idle_time().and_then(|idle_time| monitor.update(idle_time)) {
+        handler(&app_, state);
+      }
+    }),
+  )
+}
+
+#[cfg(any(
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd"
+))]
+mod platform {
+  use std::time::Duration;
+
+  use gtk::{
+    gio::{self, Cancellable, DBusCallFlags},
+    glib::VariantTy,
+  };
+
+  /// Asks the compositor over D-Bus, X11 and Wayland don't share an API to read the idle time.
+  pub fn idle_time() -> Option<Duration> {
+    let connection = gio::bus_get_sync(gio::BusType::Session, None::<&Cancellable>).ok()?;
+    // (bus name, object path, interface, method, reply type), the GNOME and the KDE / freedesktop APIs
+    let apis = [
+      (
+        "org.gnome.Mutter.IdleMonitor",
+        "/org/gnome/Mutter/IdleMonitor/Core",
+        "org.gnome.Mutter.IdleMonitor",
+        "GetIdletime",
+        "(t)",
+      ),
+      (
+        "org.freedesktop.ScreenSaver",
+        "/org/freedesktop/ScreenSaver",
+        "org.freedesktop.ScreenSaver",
+        "GetSessionIdleTime",
+        "(u)",
+      ),
+    ];
+    apis
+      .into_iter()
+      .find_map(|(name, path, interface, method, reply)| {
+        let reply = connection
+          .call_sync(
+            Some(name),
+            path,
+            interface,
+            method,
+            None,
+            Some(VariantTy::new(reply).unwrap()),
+            DBusCallFlags::NONE,
+            1000,
+            None::<&Cancellable>,
+          )
+          .ok()?;
+        reply
+          .get::<(u64,)>()
+          .or_else(|| reply.get::<(u32,)>().map(|(ms,)| (ms as u64,)))
+          .map(|(ms,)| Duration::from_millis(ms))
+      })
+  }
+}
+
+#[cfg(windows)]
+mod platform {
+  use std::time::Duration;
+
+  use windows::Win32::{
+    System::SystemInformation::GetTickCount,
+    UI::Input::KeyboardAndMouse::{GetLastInputInfo, LASTINPUTINFO},
+  };
+
+  pub fn idle_time() -> Option<Duration> {
+    let mut info = LASTINPUTINFO {
+      cbSize: std::mem::size_of::<LASTINPUTINFO>() as u32,
+      dwTime: 0,
+    };
+    unsafe { GetLastInputInfo(&mut info) }.ok().ok()?;
+    // the tick count wraps around every 49.7 days
+    let ms = unsafe { GetTickCount() }.wrapping_sub(info.dwTime);
+    Some(Duration::from_millis(ms as u64))
+  }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+  use std::time::Duration;
+
+  const COMBINED_SESSION_STATE: i32 = 0;
+  const ANY_INPUT_EVENT_TYPE: u32 = u32::MAX;
+
+  #[link(name = "CoreGraphics", kind = "framework")]
+  extern "C" {
+    fn CGEventSourceSecondsSinceLastEventType(state: i32, event_type: u32) -> f64;
+  }
+
+  pub fn idle_time() -> Option<Duration> {
+    let seconds = unsafe {
+      CGEventSourceSecondsSinceLastEventType(COMBINED_SESSION_STATE, ANY_INPUT_EVENT_TYPE)
+    };
+    Duration::try_from_secs_f64(seconds).ok()
+  }
+}
+
+#[cfg(mobile)]
+mod platform {
+  pub fn idle_time() -> Option<std::time::Duration> {
+    None
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::time::Duration;
+
+  use super::{IdleMonitor, IdleState};
+
+  #[test]
+  fn reports_transitions() {
+    let mut monitor = IdleMonitor::new(Duration::from_secs(60));
+    assert_eq!(monitor.update(Duration::from_secs(10)), None);
+    assert_eq!(
+      monitor.update(Duration::from_secs(60)),
+      Some(IdleState::Idle)
+    );
+    assert_eq!(monitor.update(Duration::from_secs(120)), None);
+    assert_eq!(
+      monitor.update(Duration::from_secs(1)),
+      Some(IdleState::Active)
+    );
+    assert_eq!(monitor.update(Duration::from_secs(2)), None);
+  }
+}
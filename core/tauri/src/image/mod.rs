@@ -11,6 +11,69 @@ use std::sync::Arc;
 
 use crate::{Resource, ResourceId, ResourceTable};
 
+/// The largest width and height of the images rasterized by [`Image::from_svg`] and resized by [`Image::resize`].
+pub const MAX_IMAGE_DIMENSION: u32 = 8192;
+
+/// Checks the size of an image to rasterize or resize against [`MAX_IMAGE_DIMENSION`].
+#[cfg(any(
+  feature = "image-ico",
+  feature = "image-png",
+  feature = "image-jpeg",
+  feature = "image-svg"
+))]
+fn check_size(width: u32, height: u32) -> std::io::Result<()> {
+  if width == 0 || height == 0 || width > MAX_IMAGE_DIMENSION || height > MAX_IMAGE_DIMENSION {
+    Err(std::io::Error::new(
+      std::io::ErrorKind::InvalidInput,
+      format!("invalid image size {width}x{height}, the width and height must be between 1 and {MAX_IMAGE_DIMENSION}"),
+    ))
+  } else {
+    Ok(())
+  }
+}
+
+/// The filter used to resize an image, from the fastest to the smoothest.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ResizeFilter {
+  /// Nearest neighbor, keeping the edges of pixel art sharp.
+  Nearest,
+  /// Linear filter.
+  Triangle,
+  /// Cubic filter.
+  CatmullRom,
+  /// Gaussian filter.
+  Gaussian,
+  /// Lanczos filter with window 3, the sharpest when downscaling icons.
+  #[default]
+  Lanczos3,
+}
+
+#[cfg(any(
+  feature = "image-ico",
+  feature = "image-png",
+  feature = "image-jpeg",
+  feature = "image-svg"
+))]
+impl From<ResizeFilter> for image::imageops::FilterType {
+  fn from(filter: ResizeFilter) -> Self {
+    match filter {
+      ResizeFilter::Nearest => Self::Nearest,
+      ResizeFilter::Triangle => Self::Triangle,
+      ResizeFilter::CatmullRom => Self::CatmullRom,
+      ResizeFilter::Gaussian => Self::Gaussian,
+      ResizeFilter::Lanczos3 => Self::Lanczos3,
+    }
+  }
+}
+
+/// Whether the bytes are an SVG document rather than a raster image.
+#[cfg(feature = "image-svg")]
+fn is_svg(bytes: &[u8]) -> bool {
+  let start = &bytes[..bytes.len().min(1024)];
+  image::guess_format(bytes).is_err() && String::from_utf8_lossy(start).contains("<svg")
+}
+
 /// An RGBA Image in row-major order from top to bottom.
 #[derive(Debug, Clone)]
 pub struct Image<'a> {
@@ -46,12 +109,31 @@ impl<'a> Image<'a> {
 
   /// Creates a new image using the provided bytes.
   ///
-  /// Only `ico` and `png` are supported (based on activated feature flag).
-  #[cfg(any(feature = "image-ico", feature = "image-png"))]
-  #[cfg_attr(docsrs, doc(cfg(any(feature = "image-ico", feature = "image-png"))))]
+  /// Only `ico`, `png`, `jpeg` and `svg` are supported (based on activated feature flag).
+  /// The SVG images are rasterized at their size, see [`Self::from_svg`] to rasterize them at another size.
+  #[cfg(any(
+    feature = "image-ico",
+    feature = "image-png",
+    feature = "image-jpeg",
+    feature = "image-svg"
+  ))]
+  #[cfg_attr(
+    docsrs,
+    doc(cfg(any(
+      feature = "image-ico",
+      feature = "image-png",
+      feature = "image-jpeg",
+      feature = "image-svg"
+    )))
+  )]
   pub fn from_bytes(bytes: &[u8]) -> crate::Result<Self> {
     use image::GenericImageView;
 
+    #[cfg(feature = "image-svg")]
+    if is_svg(bytes) {
+      return Self::from_svg(bytes, None);
+    }
+
     let img = image::load_from_memory(bytes)?;
     let pixels = img
       .pixels()
@@ -66,14 +148,78 @@ impl<'a> Image<'a> {
 
   /// Creates a new image using the provided path.
   ///
-  /// Only `ico` and `png` are supported (based on activated feature flag).
-  #[cfg(any(feature = "image-ico", feature = "image-png"))]
-  #[cfg_attr(docsrs, doc(cfg(any(feature = "image-ico", feature = "image-png"))))]
+  /// Only `ico`, `png`, `jpeg` and `svg` are supported (based on activated feature flag).
+  #[cfg(any(
+    feature = "image-ico",
+    feature = "image-png",
+    feature = "image-jpeg",
+    feature = "image-svg"
+  ))]
+  #[cfg_attr(
+    docsrs,
+    doc(cfg(any(
+      feature = "image-ico",
+      feature = "image-png",
+      feature = "image-jpeg",
+      feature = "image-svg"
+    )))
+  )]
   pub fn from_path<P: AsRef<std::path::Path>>(path: P) -> crate::Result<Self> {
     let bytes = std::fs::read(path)?;
     Self::from_bytes(&bytes)
   }
 
+  /// Rasterizes the SVG image, at its size or scaled to fit the given width and height,
+  /// centered if its aspect ratio differs.
+  ///
+  /// The size can't exceed [`MAX_IMAGE_DIMENSION`]. The `<image>` elements can only embed data URLs,
+  /// the files and the other external resources they reference are ignored.
+  #[cfg(feature = "image-svg")]
+  #[cfg_attr(docsrs, doc(cfg(feature = "image-svg")))]
+  pub fn from_svg(bytes: &[u8], size: Option<(u32, u32)>) -> crate::Result<Self> {
+    use resvg::{tiny_skia, usvg};
+
+    let options = usvg::Options {
+      image_href_resolver: usvg::ImageHrefResolver {
+        resolve_data: usvg::ImageHrefResolver::default_data_resolver(),
+        // never read the paths, an untrusted SVG could reference any local file
+        resolve_string: Box::new(|_, _| None),
+      },
+      ..Default::default()
+    };
+    let tree =
+      usvg::Tree::from_data(bytes, &options).map_err(|e| crate::Error::Svg(e.to_string()))?;
+    let svg_size = tree.size();
+    let (width, height) = size.unwrap_or((
+      svg_size.width().ceil() as u32,
+      svg_size.height().ceil() as u32,
+    ));
+    check_size(width, height).map_err(|e| crate::Error::Svg(e.to_string()))?;
+    let mut pixmap = tiny_skia::Pixmap::new(width, height)
+      .ok_or_else(|| crate::Error::Svg(format!("invalid image size {width}x{height}")))?;
+    let scale = (width as f32 / svg_size.width()).min(height as f32 / svg_size.height());
+    let transform = tiny_skia::Transform::from_scale(scale, scale).post_translate(
+      (width as f32 - svg_size.width() * scale) / 2.,
+      (height as f32 - svg_size.height() * scale) / 2.,
+    );
+    resvg::render(&tree, transform, &mut pixmap.as_mut());
+
+    // the pixmap is premultiplied by the alpha
+    let rgba = pixmap
+      .pixels()
+      .iter()
+      .flat_map(|pixel| {
+        let color = pixel.demultiply();
+        [color.red(), color.green(), color.blue(), color.alpha()]
+      })
+      .collect();
+    Ok(Self {
+      rgba: Cow::Owned(rgba),
+      width,
+      height,
+    })
+  }
+
   /// Returns the RGBA data for this image, in row-major order from top to bottom.
   pub fn rgba(&'a self) -> &'a [u8] {
     &self.rgba
@@ -89,6 +235,94 @@ impl<'a> Image<'a> {
     self.height
   }
 
+  /// The image as a buffer of the `image` crate.
+  #[cfg(any(
+    feature = "image-ico",
+    feature = "image-png",
+    feature = "image-jpeg",
+    feature = "image-svg"
+  ))]
+  fn buffer(&self) -> crate::Result<image::RgbaImage> {
+    image::RgbaImage::from_raw(self.width, self.height, self.rgba.to_vec()).ok_or_else(|| {
+      std::io::Error::new(
+        std::io::ErrorKind::InvalidData,
+        format!(
+          "expected {} bytes of RGBA data for a {}x{} image, found {}",
+          self.width as usize * self.height as usize * 4,
+          self.width,
+          self.height,
+          self.rgba.len()
+        ),
+      )
+      .into()
+    })
+  }
+
+  /// Resizes the image to the exact width and height with the filter, ignoring its aspect ratio.
+  ///
+  /// The width and height can't exceed [`MAX_IMAGE_DIMENSION`].
+  #[cfg(any(
+    feature = "image-ico",
+    feature = "image-png",
+    feature = "image-jpeg",
+    feature = "image-svg"
+  ))]
+  #[cfg_attr(
+    docsrs,
+    doc(cfg(any(
+      feature = "image-ico",
+      feature = "image-png",
+      feature = "image-jpeg",
+      feature = "image-svg"
+    )))
+  )]
+  pub fn resize(
+    &self,
+    width: u32,
+    height: u32,
+    filter: ResizeFilter,
+  ) -> crate::Result<Image<'static>> {
+    check_size(width, height)?;
+    let resized = image::imageops::resize(&self.buffer()?, width, height, filter.into());
+    Ok(Image::new_owned(resized.into_raw(), width, height))
+  }
+
+  /// Encodes the image as a PNG file, the format of the icons on macOS and Linux.
+  #[cfg(feature = "image-png")]
+  #[cfg_attr(docsrs, doc(cfg(feature = "image-png")))]
+  pub fn to_png(&self) -> crate::Result<Vec<u8>> {
+    use image::ImageEncoder;
+
+    let buffer = self.buffer()?;
+    let mut png = Vec::new();
+    image::codecs::png::PngEncoder::new(&mut png).write_image(
+      &buffer,
+      self.width,
+      self.height,
+      image::ColorType::Rgba8,
+    )?;
+    Ok(png)
+  }
+
+  /// Encodes the image as an ICO file, the format of the icons on Windows,
+  /// with a layer resized to each of the sizes, up to 256.
+  #[cfg(feature = "image-ico")]
+  #[cfg_attr(docsrs, doc(cfg(feature = "image-ico")))]
+  pub fn to_ico(&self, sizes: &[u32]) -> crate::Result<Vec<u8>> {
+    use image::codecs::ico::{IcoEncoder, IcoFrame};
+
+    let frames = sizes
+      .iter()
+      .map(|&size| {
+        let layer = self.resize(size, size, ResizeFilter::Lanczos3)?;
+        IcoFrame::as_png(layer.rgba(), size, size, image::ColorType::Rgba8).map_err(Into::into)
+      })
+      .collect::<crate::Result<Vec<_>>>()?;
+    let mut ico = Vec::new();
+    IcoEncoder::new(&mut ico).encode_images(&frames)?;
+    Ok(ico)
+  }
+
   /// Convert into a 'static owned [`Image`].
   /// This will allocate.
   pub fn to_owned(self) -> Image<'static> {
@@ -173,10 +407,20 @@ impl JsImage {
   pub fn into_img(self, resources_table: &ResourceTable) -> crate::Result<Arc<Image<'_>>> {
     match self {
       Self::Resource(rid) => resources_table.get::<Image<'static>>(rid),
-      #[cfg(any(feature = "image-ico", feature = "image-png"))]
+      #[cfg(any(
+        feature = "image-ico",
+        feature = "image-png",
+        feature = "image-jpeg",
+        feature = "image-svg"
+      ))]
       Self::Path(path) => Image::from_path(path).map(Arc::new).map_err(Into::into),
 
-      #[cfg(any(feature = "image-ico", feature = "image-png"))]
+      #[cfg(any(
+        feature = "image-ico",
+        feature = "image-png",
+        feature = "image-jpeg",
+        feature = "image-svg"
+      ))]
       Self::Bytes(bytes) => Image::from_bytes(&bytes).map(Arc::new).map_err(Into::into),
 
       Self::Rgba {
@@ -185,7 +429,12 @@ impl JsImage {
         height,
       } => Ok(Arc::new(Image::new_owned(rgba, width, height))),
 
-      #[cfg(not(any(feature = "image-ico", feature = "image-png")))]
+      #[cfg(not(any(
+        feature = "image-ico",
+        feature = "image-png",
+        feature = "image-jpeg",
+        feature = "image-svg"
+      )))]
       _ => Err(
         std::io::Error::new(
           std::io::ErrorKind::InvalidInput,
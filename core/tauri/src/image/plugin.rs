@@ -6,7 +6,11 @@ use serde::Serialize;
 
 use crate::plugin::{Builder, TauriPlugin};
 use crate::Manager;
-use crate::{command, image::Image, ResourceId, Runtime, Webview};
+use crate::{
+  command,
+  image::{Image, ResizeFilter},
+  ResourceId, Runtime, Webview,
+};
 
 #[command(root = "crate")]
 fn new<R: Runtime>(
@@ -21,7 +25,12 @@ fn new<R: Runtime>(
   Ok(rid)
 }
 
-#[cfg(any(feature = "image-ico", feature = "image-png"))]
+#[cfg(any(
+  feature = "image-ico",
+  feature = "image-png",
+  feature = "image-jpeg",
+  feature = "image-svg"
+))]
 #[command(root = "crate")]
 fn from_bytes<R: Runtime>(webview: Webview<R>, bytes: Vec<u8>) -> crate::Result<ResourceId> {
   let image = Image::from_bytes(&bytes)?.to_owned();
@@ -30,13 +39,23 @@ fn from_bytes<R: Runtime>(webview: Webview<R>, bytes: Vec<u8>) -> crate::Result<
   Ok(rid)
 }
 
-#[cfg(not(any(feature = "image-ico", feature = "image-png")))]
+#[cfg(not(any(
+  feature = "image-ico",
+  feature = "image-png",
+  feature = "image-jpeg",
+  feature = "image-svg"
+)))]
 #[command(root = "crate")]
 fn from_bytes() -> std::result::Result<(), &'static str> {
-  Err("from_bytes is only supported if the `image-ico` or `image-png` Cargo features are enabled")
+  Err("from_bytes is only supported if the `image-ico`, `image-png`, `image-jpeg` or `image-svg` Cargo features are enabled")
 }
 
-#[cfg(any(feature = "image-ico", feature = "image-png"))]
+#[cfg(any(
+  feature = "image-ico",
+  feature = "image-png",
+  feature = "image-jpeg",
+  feature = "image-svg"
+))]
 #[command(root = "crate")]
 fn from_path<R: Runtime>(
   webview: Webview<R>,
@@ -48,10 +67,46 @@ fn from_path<R: Runtime>(
   Ok(rid)
 }
 
-#[cfg(not(any(feature = "image-ico", feature = "image-png")))]
+#[cfg(not(any(
+  feature = "image-ico",
+  feature = "image-png",
+  feature = "image-jpeg",
+  feature = "image-svg"
+)))]
 #[command(root = "crate")]
 fn from_path() -> std::result::Result<(), &'static str> {
-  Err("from_path is only supported if the `image-ico` or `image-png` Cargo features are enabled")
+  Err("from_path is only supported if the `image-ico`, `image-png`, `image-jpeg` or `image-svg` Cargo features are enabled")
+}
+
+#[cfg(any(
+  feature = "image-ico",
+  feature = "image-png",
+  feature = "image-jpeg",
+  feature = "image-svg"
+))]
+#[command(root = "crate")]
+fn resize<R: Runtime>(
+  webview: Webview<R>,
+  rid: ResourceId,
+  width: u32,
+  height: u32,
+  filter: Option<ResizeFilter>,
+) -> crate::Result<ResourceId> {
+  let mut resources_table = webview.resources_table();
+  let image = resources_table.get::<Image<'_>>(rid)?;
+  let resized = image.resize(width, height, filter.unwrap_or_default())?;
+  Ok(resources_table.add(resized))
+}
+
+#[cfg(not(any(
+  feature = "image-ico",
+  feature = "image-png",
+  feature = "image-jpeg",
+  feature = "image-svg"
+)))]
+#[command(root = "crate")]
+fn resize() -> std::result::Result<(), &'static str> {
+  Err("resize is only supported if the `image-ico`, `image-png`, `image-jpeg` or `image-svg` Cargo features are enabled")
 }
 
 #[command(root = "crate")]
@@ -81,7 +136,7 @@ fn size<R: Runtime>(webview: Webview<R>, rid: ResourceId) -> crate::Result<Size>
 pub fn init<R: Runtime>() -> TauriPlugin<R> {
   Builder::new("image")
     .invoke_handler(crate::generate_handler![
-      new, from_bytes, from_path, rgba, size
+      new, from_bytes, from_path, resize, rgba, size
     ])
     .build()
 }
@@ -0,0 +1,317 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Gamepad input, see [`AppHandle::set_gamepad_poll_interval`].
+//!
+//! The browser Gamepad API is missing or limited in some webviews, e.g. WebKitGTK,
+//! so the gamepads are read natively and their input forwarded as the [`GAMEPAD_EVENT`]
+//! with the buttons and axes normalized to the standard gamepad layout.
+
+use std::{cell::RefCell, time::Duration};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{AppHandle, Emitter, Manager, Runtime};
+
+/// The event emitted to all targets with a [`GamepadEvent`] payload.
+pub const GAMEPAD_EVENT: &str = "tauri://gamepad";
+
+/// A button of the standard gamepad layout, named after its position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub enum Button {
+  /// The bottom action button, e.g. A on Xbox controllers and Cross on PlayStation controllers.
+  South,
+  /// The right action button.
+  East,
+  /// The top action button.
+  North,
+  /// The left action button.
+  West,
+  /// The left shoulder button.
+  LeftBumper,
+  /// The left trigger.
+  LeftTrigger,
+  /// The right shoulder button.
+  RightBumper,
+  /// The right trigger.
+  RightTrigger,
+  /// The left center button, e.g. Back or Share.
+  Select,
+  /// The right center button, e.g. Start or Options.
+  Start,
+  /// The vendor button, e.g. the Xbox or PlayStation logo.
+  Mode,
+  /// Pressing the left stick.
+  LeftStick,
+  /// Pressing the right stick.
+  RightStick,
+  /// The up button of the directional pad.
+  DPadUp,
+  /// The down button of the directional pad.
+  DPadDown,
+  /// The left button of the directional pad.
+  DPadLeft,
+  /// The right button of the directional pad.
+  DPadRight,
+  /// A button without a standard mapping.
+  Unknown,
+}
+
+/// An axis of the standard gamepad layout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub enum Axis {
+  /// The horizontal axis of the left stick, from -1 (left) to 1 (right).
+  LeftStickX,
+  /// The vertical axis of the left stick, from -1 (down) to 1 (up).
+  LeftStickY,
+  /// The horizontal axis of the right stick, from -1 (left) to 1 (right).
+  RightStickX,
+  /// The vertical axis of the right stick, from -1 (down) to 1 (up).
+  RightStickY,
+  /// An axis without a standard mapping.
+  Unknown,
+}
+
+/// The input of a gamepad, the payload of the [`GAMEPAD_EVENT`].
+///
+/// The `id` identifies the gamepad until it is disconnected, it may be reused by the next gamepad connected.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+#[non_exhaustive]
+pub enum GamepadEvent {
+  /// A gamepad was connected, or was already connected when the polling started.
+  Connected {
+    /// The gamepad identifier.
+    id: usize,
+    /// The name reported by the gamepad.
+    name: String,
+  },
+  /// A gamepad was disconnected.
+  Disconnected {
+    /// The gamepad identifier.
+    id: usize,
+  },
+  /// A button was pressed.
+  ButtonPressed {
+    /// The gamepad identifier.
+    id: usize,
+    /// The button.
+    button: Button,
+  },
+  /// A button was released.
+  ButtonReleased {
+    /// The gamepad identifier.
+    id: usize,
+    /// The button.
+    button: Button,
+  },
+  /// The pressure on an analog button, e.g. a trigger, changed.
+  ButtonChanged {
+    /// The gamepad identifier.
+    id: usize,
+    /// The button.
+    button: Button,
+    /// The pressure, from 0 to 1.
+    value: f32,
+  },
+  /// An axis moved.
+  AxisChanged {
+    /// The gamepad identifier.
+    id: usize,
+    /// The axis.
+    axis: Axis,
+    /// The position, from -1 to 1.
+    value: f32,
+  },
+}
+
+impl From<gilrs::Button> for Button {
+  fn from(button: gilrs::Button) -> Self {
+    use gilrs::Button as B;
+    match button {
+      B::South => Self::South,
+      B::East => Self::East,
+      B::North => Self::North,
+      B::West => Self::West,
+      B::LeftTrigger => Self::LeftBumper,
+      B::LeftTrigger2 => Self::LeftTrigger,
+      B::RightTrigger => Self::RightBumper,
+      B::RightTrigger2 => Self::RightTrigger,
+      B::Select => Self::Select,
+      B::Start => Self::Start,
+      B::Mode => Self::Mode,
+      B::LeftThumb => Self::LeftStick,
+      B::RightThumb => Self::RightStick,
+      B::DPadUp => Self::DPadUp,
+      B::DPadDown => Self::DPadDown,
+      B::DPadLeft => Self::DPadLeft,
+      B::DPadRight => Self::DPadRight,
+      _ => Self::Unknown,
+    }
+  }
+}
+
+impl From<gilrs::Axis> for Axis {
+  fn from(axis: gilrs::Axis) -> Self {
+    use gilrs::Axis as A;
+    match axis {
+      A::LeftStickX => Self::LeftStickX,
+      A::LeftStickY => Self::LeftStickY,
+      A::RightStickX => Self::RightStickX,
+      A::RightStickY => Self::RightStickY,
+      _ => Self::Unknown,
+    }
+  }
+}
+
+/// Normalizes a gilrs event, the repeated button presses and the unmapped inputs are dropped.
+fn convert(gilrs: &gilrs::Gilrs, event: gilrs::Event) -> Option<GamepadEvent> {
+  use gilrs::EventType;
+
+  let id = usize::from(event.id);
+  let event = match event.event {
+    EventType::Connected => GamepadEvent::Connected {
+      id,
+      name: gilrs.gamepad(event.id).name().into(),
+    },
+    EventType::Disconnected => GamepadEvent::Disconnected { id },
+    EventType::ButtonPressed(button, _) => GamepadEvent::ButtonPressed {
+      id,
+      button: button.into(),
+    },
+    EventType::ButtonReleased(button, _) => GamepadEvent::ButtonReleased {
+      id,
+      button: button.into(),
+    },
+    EventType::ButtonChanged(button, value, _) => GamepadEvent::ButtonChanged {
+      id,
+      button: button.into(),
+      value,
+    },
+    EventType::AxisChanged(axis, value, _) => GamepadEvent::AxisChanged {
+      id,
+      axis: axis.into(),
+      value,
+    },
+    _ => return None,
+  };
+  match event {
+    GamepadEvent::ButtonPressed {
+      button: Button::Unknown,
+      ..
+    }
+    | GamepadEvent::ButtonReleased {
+      button: Button::Unknown,
+      ..
+    }
+    | GamepadEvent::ButtonChanged {
+      button: Button::Unknown,
+      ..
+    }
+    | GamepadEvent::AxisChanged {
+      axis: Axis::Unknown,
+      ..
+    } => None,
+    event => Some(event),
+  }
+}
+
+thread_local! {
+  /// The gamepad reader, which is not `Send` on every platform so it lives on the main thread.
+  static GILRS: RefCell<Option<gilrs::Gilrs>> = const { RefCell::new(None) };
+}
+
+/// Reads the pending gamepad events on the main thread, starting the reader on the first call.
+fn poll<R: Runtime>(app: &AppHandle<R>) {
+  GILRS.with(|reader| {
+    let mut reader = reader.borrow_mut();
+    let mut events = Vec::new();
+    if reader.is_none() {
+      let gilrs = match gilrs::Gilrs::new() {
+        Ok(gilrs) | Err(gilrs::Error::NotImplemented(gilrs)) => gilrs,
+        Err(e) => {
+          log::error!("failed to read the gamepads: {e}");
+          return;
+        }
+      };
+      events.extend(
+        gilrs
+          .gamepads()
+          .map(|(id, gamepad)| GamepadEvent::Connected {
+            id: id.into(),
+            name: gamepad.name().into(),
+          }),
+      );
+      *reader = Some(gilrs);
+    }
+    let gilrs = reader.as_mut().unwrap();
+
+    while let Some(event) = gilrs.next_event() {
+      events.extend(convert(gilrs, event));
+    }
+    for event in events {
+      if let Err(e) = app.emit(GAMEPAD_EVENT, event) {
+        log::error!("failed to emit the gamepad event: {e}");
+      }
+    }
+  });
+}
+
+/// Starts or stops polling the gamepads.
+pub(crate) fn set_interval<R: Runtime>(app: &AppHandle<R>, interval: Option<Duration>) {
+  let timer = interval.map(|interval| {
+    let app_ = app.clone();
+    app.clock().set_interval(
+      interval,
+      Box::new(move || {
+        let app = app_.clone();
+        let _ = app_.run_on_main_thread(move || poll(&app));
+      }),
+    )
+  });
+  if timer.is_none() {
+    let _ = app.run_on_main_thread(|| {
+      GILRS.with(|reader| reader.borrow_mut().take());
+    });
+  }
+  *app.manager.gamepad_timer.lock().unwrap() = timer;
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{Axis, Button, GamepadEvent};
+
+  #[test]
+  fn serializes_events() {
+    let event = GamepadEvent::ButtonPressed {
+      id: 0,
+      button: Button::DPadUp,
+    };
+    assert_eq!(
+      serde_json::to_value(&event).unwrap(),
+      serde_json::json!({ "type": "buttonPressed", "id": 0, "button": "dPadUp" })
+    );
+
+    let event = GamepadEvent::AxisChanged {
+      id: 1,
+      axis: Axis::LeftStickX,
+      value: -0.5,
+    };
+    let json = serde_json::to_string(&event).unwrap();
+    assert_eq!(
+      json,
+      r#"{"type":"axisChanged","id":1,"axis":"leftStickX","value":-0.5}"#
+    );
+    assert_eq!(serde_json::from_str::<GamepadEvent>(&json).unwrap(), event);
+
+    assert_eq!(
+      Button::from(gilrs::Button::LeftTrigger2),
+      Button::LeftTrigger
+    );
+  }
+}
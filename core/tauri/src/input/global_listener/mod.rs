@@ -0,0 +1,295 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! System-wide keyboard and mouse events, see [`AppHandle::listen_global_input`].
+//!
+//! The events are received even when the app is not focused, e.g. for push-to-talk shortcuts
+//! or activity tracking. To limit the exposure of what the user types:
+//!
+//! - the key events carry the physical key, never the text it produces;
+//! - the webviews must be granted the `core:input:allow-listen-global` permission explicitly,
+//!   it is not part of `core:default`;
+//! - on macOS, the user must grant the app the Accessibility access, see [`request_access`].
+//!
+//! ## Platform-specific
+//!
+//! - **Linux:** Only X11 is supported, Wayland doesn't allow reading the global input.
+
+pub(crate) mod plugin;
+
+use std::{
+  collections::HashMap,
+  sync::{
+    atomic::{AtomicU32, Ordering},
+    Arc, Mutex,
+  },
+};
+
+use serde::Serialize;
+
+use crate::{AppHandle, PhysicalPosition, Runtime};
+
+/// The identifier of a listener registered with [`AppHandle::listen_global_input`].
+pub type GlobalListenerId = u32;
+
+/// A mouse button.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub enum MouseButton {
+  /// The left button.
+  Left,
+  /// The right button.
+  Right,
+  /// The middle button.
+  Middle,
+  /// Another button, identified by its platform code.
+  Other(u8),
+}
+
+/// A system-wide keyboard or mouse event.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+#[non_exhaustive]
+pub enum GlobalInputEvent {
+  /// A key was pressed.
+  KeyPressed {
+    /// The name of the physical key, e.g. `KeyA`, `ShiftLeft` or `Space`.
+    key: String,
+  },
+  /// A key was released.
+  KeyReleased {
+    /// The name of the physical key, e.g. `KeyA`, `ShiftLeft` or `Space`.
+    key: String,
+  },
+  /// A mouse button was pressed.
+  MouseButtonPressed {
+    /// The button.
+    button: MouseButton,
+  },
+  /// A mouse button was released.
+  MouseButtonReleased {
+    /// The button.
+    button: MouseButton,
+  },
+  /// The cursor moved.
+  MouseMoved {
+    /// The cursor position on the desktop.
+    position: PhysicalPosition<f64>,
+  },
+  /// The mouse wheel was scrolled.
+  #[serde(rename_all = "camelCase")]
+  MouseWheel {
+    /// The horizontal scroll, in lines.
+    delta_x: i64,
+    /// The vertical scroll, in lines.
+    delta_y: i64,
+  },
+}
+
+impl From<rdev::Button> for MouseButton {
+  fn from(button: rdev::Button) -> Self {
+    match button {
+      rdev::Button::Left => Self::Left,
+      rdev::Button::Right => Self::Right,
+      rdev::Button::Middle => Self::Middle,
+      rdev::Button::Unknown(code) => Self::Other(code),
+    }
+  }
+}
+
+impl From<rdev::EventType> for GlobalInputEvent {
+  fn from(event: rdev::EventType) -> Self {
+    use rdev::EventType;
+    match event {
+      EventType::KeyPress(key) => Self::KeyPressed {
+        key: format!("{key:?}"),
+      },
+      EventType::KeyRelease(key) => Self::KeyReleased {
+        key: format!("{key:?}"),
+      },
+      EventType::ButtonPress(button) => Self::MouseButtonPressed {
+        button: button.into(),
+      },
+      EventType::ButtonRelease(button) => Self::MouseButtonReleased {
+        button: button.into(),
+      },
+      EventType::MouseMove { x, y } => Self::MouseMoved {
+        position: PhysicalPosition::new(x, y),
+      },
+      EventType::Wheel { delta_x, delta_y } => Self::MouseWheel { delta_x, delta_y },
+    }
+  }
+}
+
+type Handler = Arc<dyn Fn(&GlobalInputEvent) + Send + Sync>;
+
+/// The listeners of the global input, the hook is installed with the first listener and kept until the app exits.
+#[derive(Default)]
+pub(crate) struct GlobalListeners {
+  started: Mutex<bool>,
+  next_id: AtomicU32,
+  handlers: Mutex<HashMap<GlobalListenerId, Handler>>,
+}
+
+impl GlobalListeners {
+  fn dispatch(&self, event: GlobalInputEvent) {
+    let handlers = self
+      .handlers
+      .lock()
+      .unwrap()
+      .values()
+      .cloned()
+      .collect::<Vec<_>>();
+    for handler in handlers {
+      handler(&event);
+    }
+  }
+
+  fn start(self: &Arc<Self>) -> crate::Result<()> {
+    let mut started = self.started.lock().unwrap();
+    if *started {
+      return Ok(());
+    }
+    if !request_access() {
+      return Err(crate::Error::GlobalInputAccessDenied);
+    }
+
+    let listeners = self.clone();
+    std::thread::Builder::new()
+      .name("tauri-global-input".into())
+      .spawn(move || {
+        let listeners_ = listeners.clone();
+        if let Err(e) = rdev::listen(move |event| listeners_.dispatch(event.event_type.into())) {
+          log::error!("failed to listen to the global input: {e:?}");
+          *listeners.started.lock().unwrap() = false;
+        }
+      })?;
+    *started = true;
+    Ok(())
+  }
+
+  pub(crate) fn listen<F: Fn(&GlobalInputEvent) + Send + Sync + 'static>(
+    self: &Arc<Self>,
+    handler: F,
+  ) -> crate::Result<GlobalListenerId> {
+    self.start()?;
+    let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+    self.handlers.lock().unwrap().insert(id, Arc::new(handler));
+    Ok(id)
+  }
+
+  pub(crate) fn unlisten(&self, id: GlobalListenerId) {
+    self.handlers.lock().unwrap().remove(&id);
+  }
+}
+
+/// Checks whether the app can read the global input, prompting the user to grant the access if needed.
+///
+/// ## Platform-specific
+///
+/// - **macOS:** Opens the Accessibility pane of the System Settings the first time the access is requested.
+///   The user must restart the app after granting it.
+/// - **Windows / Linux:** Always `true`.
+#[cfg(target_os = "macos")]
+pub fn request_access() -> bool {
+  use objc::{class, msg_send, runtime::Object, sel, sel_impl};
+
+  #[link(name = "ApplicationServices", kind = "framework")]
+  extern "C" {
+    static kAXTrustedCheckOptionPrompt: *mut Object;
+    fn AXIsProcessTrustedWithOptions(options: *mut Object) -> u8;
+  }
+
+  unsafe {
+    let prompt: *mut Object = msg_send![class!(NSNumber), numberWithBool: cocoa::base::YES];
+    let options: *mut Object = msg_send![
+      class!(NSDictionary),
+      dictionaryWithObject: prompt
+      forKey: kAXTrustedCheckOptionPrompt
+    ];
+    AXIsProcessTrustedWithOptions(options) != 0
+  }
+}
+
+/// Checks whether the app can read the global input, prompting the user to grant the access if needed.
+///
+/// ## Platform-specific
+///
+/// - **macOS:** Opens the Accessibility pane of the System Settings the first time the access is requested.
+///   The user must restart the app after granting it.
+/// - **Windows / Linux:** Always `true`.
+#[cfg(not(target_os = "macos"))]
+pub fn request_access() -> bool {
+  true
+}
+
+impl<R: Runtime> AppHandle<R> {
+  /// Calls the handler with the keyboard and mouse events of the whole system, see the [module docs](self).
+  ///
+  /// # Errors
+  ///
+  /// Returns [`Error::GlobalInputAccessDenied`](crate::Error::GlobalInputAccessDenied) when the user
+  /// didn't grant the access, see [`request_access`].
+  ///
+  /// # Examples
+  ///
+  /// ```rust,no_run
+  /// use tauri::input::global_listener::GlobalInputEvent;
+  ///
+  /// tauri::Builder::default().setup(|app| {
+  ///   app.handle().listen_global_input(|event| {
+  ///     if let GlobalInputEvent::KeyPressed { key } = event {
+  ///       if key == "F13" {
+  ///         println!("push to talk");
+  ///       }
+  ///     }
+  ///   })?;
+  ///   Ok(())
+  /// });
+  /// ```
+  pub fn listen_global_input<F: Fn(&GlobalInputEvent) + Send + Sync + 'static>(
+    &self,
+    handler: F,
+  ) -> crate::Result<GlobalListenerId> {
+    self.manager.global_input.listen(handler)
+  }
+
+  /// Removes a listener registered with [`Self::listen_global_input`].
+  pub fn unlisten_global_input(&self, id: GlobalListenerId) {
+    self.manager.global_input.unlisten(id)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{GlobalInputEvent, MouseButton};
+
+  #[test]
+  fn serializes_events() {
+    assert_eq!(
+      serde_json::to_value(GlobalInputEvent::from(rdev::EventType::KeyPress(
+        rdev::Key::KeyA
+      )))
+      .unwrap(),
+      serde_json::json!({ "type": "keyPressed", "key": "KeyA" })
+    );
+    assert_eq!(
+      serde_json::to_value(GlobalInputEvent::from(rdev::EventType::ButtonRelease(
+        rdev::Button::Unknown(4)
+      )))
+      .unwrap(),
+      serde_json::json!({ "type": "mouseButtonReleased", "button": { "other": 4 } })
+    );
+    assert_eq!(
+      serde_json::to_value(GlobalInputEvent::from(rdev::EventType::Wheel {
+        delta_x: 0,
+        delta_y: -1
+      }))
+      .unwrap(),
+      serde_json::json!({ "type": "mouseWheel", "deltaX": 0, "deltaY": -1 })
+    );
+    assert_eq!(MouseButton::from(rdev::Button::Left), MouseButton::Left);
+  }
+}
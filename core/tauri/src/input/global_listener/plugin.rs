@@ -0,0 +1,33 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use crate::{
+  command,
+  ipc::Channel,
+  plugin::{Builder, TauriPlugin},
+  AppHandle, Runtime,
+};
+
+use super::{GlobalInputEvent, GlobalListenerId};
+
+#[command(root = "crate")]
+fn listen_global<R: Runtime>(
+  app: AppHandle<R>,
+  handler: Channel<GlobalInputEvent>,
+) -> crate::Result<GlobalListenerId> {
+  app.listen_global_input(move |event| {
+    let _ = handler.send(event.clone());
+  })
+}
+
+#[command(root = "crate")]
+fn unlisten_global<R: Runtime>(app: AppHandle<R>, id: GlobalListenerId) {
+  app.unlisten_global_input(id)
+}
+
+pub(crate) fn init<R: Runtime>() -> TauriPlugin<R> {
+  Builder::new("input")
+    .invoke_handler(crate::generate_handler![listen_global, unlisten_global])
+    .build()
+}
@@ -0,0 +1,12 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! The input devices the webviews don't expose consistently.
+
+#[cfg(feature = "gamepad")]
+#[cfg_attr(docsrs, doc(cfg(feature = "gamepad")))]
+pub mod gamepad;
+#[cfg(feature = "global-input-listener")]
+#[cfg_attr(docsrs, doc(cfg(feature = "global-input-listener")))]
+pub mod global_listener;
@@ -31,6 +31,61 @@ impl<'a> SwiftArg<'a> for ChannelSendDataCallback {
   }
 }
 
+type MemoryWarningCallbackFn = unsafe extern "C" fn();
+pub struct MemoryWarningCallback(pub MemoryWarningCallbackFn);
+
+impl<'a> SwiftArg<'a> for MemoryWarningCallback {
+  type ArgType = MemoryWarningCallbackFn;
+
+  unsafe fn as_arg(&'a self) -> Self::ArgType {
+    self.0
+  }
+}
+
+type LifecycleEventCallbackFn = extern "C" fn(i32);
+pub struct LifecycleEventCallback(pub LifecycleEventCallbackFn);
+
+impl<'a> SwiftArg<'a> for LifecycleEventCallback {
+  type ArgType = LifecycleEventCallbackFn;
+
+  unsafe fn as_arg(&'a self) -> Self::ArgType {
+    self.0
+  }
+}
+
+type BackgroundTaskCallbackFn = extern "C" fn(*const c_char) -> bool;
+pub struct BackgroundTaskCallback(pub BackgroundTaskCallbackFn);
+
+impl<'a> SwiftArg<'a> for BackgroundTaskCallback {
+  type ArgType = BackgroundTaskCallbackFn;
+
+  unsafe fn as_arg(&'a self) -> Self::ArgType {
+    self.0
+  }
+}
+
+type PushCallbackFn = extern "C" fn(*const c_char);
+pub struct PushCallback(pub PushCallbackFn);
+
+impl<'a> SwiftArg<'a> for PushCallback {
+  type ArgType = PushCallbackFn;
+
+  unsafe fn as_arg(&'a self) -> Self::ArgType {
+    self.0
+  }
+}
+
+type ActivationCallbackFn = extern "C" fn(*const c_char);
+pub struct ActivationCallback(pub ActivationCallbackFn);
+
+impl<'a> SwiftArg<'a> for ActivationCallback {
+  type ArgType = ActivationCallbackFn;
+
+  unsafe fn as_arg(&'a self) -> Self::ArgType {
+    self.0
+  }
+}
+
 swift!(pub fn run_plugin_command(
   id: i32,
   name: &SRString,
@@ -46,3 +101,19 @@ swift!(pub fn register_plugin(
   webview: *const c_void
 ));
 swift!(pub fn on_webview_created(webview: *const c_void, controller: *const c_void));
+swift!(pub fn on_memory_warning(callback: MemoryWarningCallback));
+swift!(pub fn on_lifecycle_event(callback: LifecycleEventCallback));
+swift!(pub fn register_background_task(identifier: &SRString, callback: BackgroundTaskCallback));
+swift!(pub fn schedule_background_task(
+  identifier: &SRString,
+  delay: f64,
+  periodic: bool,
+  requires_network: bool
+) -> bool);
+swift!(pub fn cancel_background_task(identifier: &SRString));
+swift!(pub fn set_push_notification_callbacks(
+  on_token: PushCallback,
+  on_notification: PushCallback
+));
+swift!(pub fn register_push_notifications());
+swift!(pub fn set_activation_callback(callback: ActivationCallback));
@@ -8,7 +8,7 @@
 //! attribute macro along the way and used by [`crate::generate_handler`] macro.
 
 use crate::{
-  ipc::{InvokeBody, InvokeError, InvokeMessage},
+  ipc::{Invoke, InvokeBody, InvokeError, InvokeMessage},
   Runtime,
 };
 use serde::{
@@ -177,6 +177,48 @@ impl<'de, R: Runtime> Deserializer<'de> for CommandItem<'de, R> {
   pass!(deserialize_ignored_any, visitor: V);
 }
 
+/// A type grouping commands as the methods of a [`command`](macro@crate::command) `impl` block,
+/// called on the instance of the type in the managed state.
+///
+/// This trait is implemented with the [`Commands`](derive@crate::ipc::Commands) derive macro,
+/// and the commands are added to [`crate::generate_handler`] with the `<Type>` syntax.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use std::sync::Mutex;
+///
+/// #[derive(Default, tauri::ipc::Commands)]
+/// struct Todos(Mutex<Vec<String>>);
+///
+/// #[tauri::command]
+/// impl Todos {
+///   fn add_todo(&self, todo: String) {
+///     self.0.lock().unwrap().push(todo);
+///   }
+///
+///   fn todos(&self) -> Vec<String> {
+///     self.0.lock().unwrap().clone()
+///   }
+/// }
+///
+/// tauri::Builder::default()
+///   .manage(Todos::default())
+///   .invoke_handler(tauri::generate_handler![<Todos>]);
+/// ```
+pub trait Commands<R: Runtime> {
+  /// The names of the commands.
+  fn commands() -> &'static [&'static str];
+
+  /// Handles the invoke of one of the commands, returns `false` if the command is unknown.
+  fn handle(invoke: Invoke<R>) -> bool;
+
+  /// Whether the invoke calls one of the commands.
+  fn accepts(invoke: &Invoke<R>) -> bool {
+    Self::commands().contains(&invoke.message.command())
+  }
+}
+
 /// [Autoref-based stable specialization](https://github.com/dtolnay/case-studies/blob/master/autoref-specialization/README.md)
 ///
 /// Nothing in this module is considered stable.
@@ -26,13 +26,15 @@ pub(crate) mod channel;
 mod command;
 pub(crate) mod format_callback;
 pub(crate) mod protocol;
+pub mod record;
 
 pub use authority::{
   CapabilityBuilder, CommandScope, GlobalScope, Origin, RuntimeAuthority, RuntimeCapability,
   ScopeObject, ScopeValue,
 };
 pub use channel::{Channel, JavaScriptChannelId};
-pub use command::{private, CommandArg, CommandItem};
+pub use command::{private, CommandArg, CommandItem, Commands};
+pub use tauri_macros::Commands;
 
 /// A closure that is run every time Tauri receives a message it doesn't explicitly handle.
 pub type InvokeHandler<R> = dyn Fn(Invoke<R>) -> bool + Send + Sync + 'static;
@@ -0,0 +1,192 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Recording of the IPC traffic of a development session.
+//!
+//! When the app is built in development mode and the [`RECORD_ENV_VAR`] environment variable is set to a file path,
+//! every command invoked by the webviews and every event emitted by the app is appended to that file as a JSON line.
+//! The resulting fixture can be replayed against the command handlers in tests with `tauri::test::IpcSession`.
+
+use std::{
+  fs::File,
+  io::{BufRead, BufReader, Write},
+  path::Path,
+  sync::Mutex,
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+
+use super::{InvokeBody, InvokeError, InvokeResponse};
+
+/// The environment variable with the path of the file to record the IPC traffic to.
+pub const RECORD_ENV_VAR: &str = "TAURI_IPC_RECORD";
+
+/// The body of a recorded request or response.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RecordedBody {
+  /// A JSON body.
+  Json(JsonValue),
+  /// A raw body.
+  Raw(Vec<u8>),
+}
+
+impl From<&InvokeBody> for RecordedBody {
+  fn from(body: &InvokeBody) -> Self {
+    match body {
+      InvokeBody::Json(v) => Self::Json(v.clone()),
+      InvokeBody::Raw(v) => Self::Raw(v.clone()),
+    }
+  }
+}
+
+impl From<RecordedBody> for InvokeBody {
+  fn from(body: RecordedBody) -> Self {
+    match body {
+      RecordedBody::Json(v) => Self::Json(v),
+      RecordedBody::Raw(v) => Self::Raw(v),
+    }
+  }
+}
+
+/// The response of a recorded command.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RecordedResponse {
+  /// The command resolved with the body.
+  Ok(RecordedBody),
+  /// The command rejected with the error.
+  Err(JsonValue),
+}
+
+impl From<&InvokeResponse> for RecordedResponse {
+  fn from(response: &InvokeResponse) -> Self {
+    match response {
+      InvokeResponse::Ok(body) => Self::Ok(body.into()),
+      InvokeResponse::Err(InvokeError(e)) => Self::Err(e.clone()),
+    }
+  }
+}
+
+/// An entry of a recorded IPC session.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+#[non_exhaustive]
+pub enum IpcRecord {
+  /// A command invoked by a webview.
+  #[serde(rename_all = "camelCase")]
+  Invoke {
+    /// The label of the webview that invoked the command.
+    webview: String,
+    /// The URL of the webview when the command was invoked.
+    url: url::Url,
+    /// The command name.
+    cmd: String,
+    /// The command arguments.
+    body: RecordedBody,
+    /// The command response.
+    response: RecordedResponse,
+  },
+  /// An event emitted by the app.
+  Event {
+    /// The event name.
+    event: String,
+    /// The event payload.
+    payload: JsonValue,
+  },
+}
+
+impl IpcRecord {
+  /// Parses the records of a JSON lines fixture, ignoring the blank lines.
+  pub fn parse_lines<B: BufRead>(reader: B) -> crate::Result<Vec<Self>> {
+    let mut records = Vec::new();
+    for line in reader.lines() {
+      let line = line?;
+      if !line.trim().is_empty() {
+        records.push(serde_json::from_str(&line)?);
+      }
+    }
+    Ok(records)
+  }
+
+  /// Reads the records of the fixture file.
+  pub fn read(path: impl AsRef<Path>) -> crate::Result<Vec<Self>> {
+    Self::parse_lines(BufReader::new(File::open(path)?))
+  }
+}
+
+/// Appends the IPC traffic to the recording file.
+#[cfg_attr(not(dev), allow(dead_code))]
+pub(crate) struct IpcRecorder {
+  file: Mutex<File>,
+}
+
+#[cfg_attr(not(dev), allow(dead_code))]
+impl IpcRecorder {
+  /// Creates the recorder if the [`RECORD_ENV_VAR`] environment variable is set.
+  pub(crate) fn from_env() -> Option<Self> {
+    let path = std::env::var_os(RECORD_ENV_VAR)?;
+    match Self::new(Path::new(&path)) {
+      Ok(recorder) => Some(recorder),
+      Err(e) => {
+        log::error!(
+          "failed to create the IPC recording file {}: {e}",
+          Path::new(&path).display()
+        );
+        None
+      }
+    }
+  }
+
+  pub(crate) fn new(path: &Path) -> std::io::Result<Self> {
+    Ok(Self {
+      file: Mutex::new(File::create(path)?),
+    })
+  }
+
+  /// Writes the record as a line, flushing it so the recording survives the app being killed.
+  pub(crate) fn record(&self, record: &IpcRecord) {
+    let mut file = self.file.lock().unwrap();
+    let result = serde_json::to_writer(&mut *file, record)
+      .map_err(std::io::Error::from)
+      .and_then(|_| file.write_all(b"\n"))
+      .and_then(|_| file.flush());
+    if let Err(e) = result {
+      log::error!("failed to record IPC message: {e}");
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{IpcRecord, IpcRecorder, RecordedBody, RecordedResponse};
+
+  #[test]
+  fn record_and_read() {
+    let path = std::env::temp_dir().join("tauri-ipc-record-and-read.jsonl");
+    let records = vec![
+      IpcRecord::Invoke {
+        webview: "main".into(),
+        url: "http://tauri.localhost".parse().unwrap(),
+        cmd: "upload".into(),
+        body: RecordedBody::Raw(vec![1, 2, 3]),
+        response: RecordedResponse::Err("too large".into()),
+      },
+      IpcRecord::Event {
+        event: "uploaded".into(),
+        payload: serde_json::json!({ "size": 3 }),
+      },
+    ];
+
+    let recorder = IpcRecorder::new(&path).unwrap();
+    for record in &records {
+      recorder.record(record);
+    }
+    drop(recorder);
+
+    assert_eq!(IpcRecord::read(&path).unwrap(), records);
+    std::fs::remove_file(path).unwrap();
+  }
+}
@@ -0,0 +1,257 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! How and for which version the app was launched, see [`AppHandle::launch_info`].
+
+use std::path::{Path, PathBuf};
+
+use semver::Version;
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::{
+  mobile::{
+    activation::{Activation, Activations},
+    push::PushNotifications,
+  },
+  AppHandle, Manager, Runtime,
+};
+
+/// The file of the app data directory storing the version of the last launch.
+pub const LAUNCH_FILE_NAME: &str = ".launch.json";
+/// The argument the autostart entries must pass to the app for [`LaunchSource::Autostart`] to be reported.
+pub const AUTOSTART_ARG: &str = "--autostart";
+
+/// What launched the app.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+#[non_exhaustive]
+pub enum LaunchSource {
+  /// The user started the app, or the source is unknown.
+  User,
+  /// The app was started at login, with the [`AUTOSTART_ARG`] argument.
+  Autostart,
+  /// The app was opened with a URL, e.g. a deep link.
+  DeepLink {
+    /// The opened URL.
+    url: Url,
+  },
+  /// The app was opened with a file, e.g. through a file association.
+  File {
+    /// The opened file.
+    path: PathBuf,
+  },
+  /// Content was shared with the app.
+  Share,
+  /// The user tapped a push notification.
+  Notification,
+}
+
+/// Information about the launch of the app, see [`AppHandle::launch_info`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct LaunchInfo {
+  /// Whether the app is launched for the first time, i.e. no launch was recorded in the app data directory.
+  pub first_run: bool,
+  /// The version of the last launch when it differs from the current version.
+  pub previous_version: Option<Version>,
+  /// The current version of the app.
+  pub version: Version,
+  /// What launched the app.
+  pub source: LaunchSource,
+}
+
+impl LaunchInfo {
+  /// The version the app was upgraded from, if the last launch was an older version.
+  pub fn upgraded_from(&self) -> Option<&Version> {
+    self
+      .previous_version
+      .as_ref()
+      .filter(|previous| **previous < self.version)
+  }
+}
+
+/// The launches recorded in the app data directory.
+#[derive(Debug, Clone)]
+pub(crate) struct LaunchHistory {
+  first_run: bool,
+  previous_version: Option<Version>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct LaunchRecord {
+  version: Version,
+}
+
+/// Reads the version of the last launch and stores the current version.
+fn record_to(path: &Path, version: &Version) -> LaunchHistory {
+  let (first_run, previous_version) = match std::fs::read(path) {
+    Ok(bytes) => match serde_json::from_slice::<LaunchRecord>(&bytes) {
+      Ok(record) => (false, Some(record.version)),
+      Err(e) => {
+        log::error!("failed to read the last launch: {e}");
+        (false, None)
+      }
+    },
+    Err(e) if e.kind() == std::io::ErrorKind::NotFound => (true, None),
+    Err(e) => {
+      log::error!("failed to read the last launch: {e}");
+      (false, None)
+    }
+  };
+  let previous_version = previous_version.filter(|previous| previous != version);
+
+  if first_run || previous_version.is_some() {
+    let write = || -> crate::Result<()> {
+      if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+      }
+      std::fs::write(
+        path,
+        serde_json::to_vec(&LaunchRecord {
+          version: version.clone(),
+        })?,
+      )?;
+      Ok(())
+    };
+    if let Err(e) = write() {
+      log::error!("failed to record the launch: {e}");
+    }
+  }
+
+  LaunchHistory {
+    first_run,
+    previous_version,
+  }
+}
+
+/// Records the launch once, when the app starts.
+pub(crate) fn history<R: Runtime>(app: &AppHandle<R>) -> LaunchHistory {
+  app
+    .manager
+    .launch_history
+    .get_or_init(|| match app.path().app_data_dir() {
+      Ok(dir) => record_to(&dir.join(LAUNCH_FILE_NAME), &app.package_info().version),
+      Err(e) => {
+        log::error!("failed to record the launch: {e}");
+        LaunchHistory {
+          first_run: false,
+          previous_version: None,
+        }
+      }
+    })
+    .clone()
+}
+
+/// The source of a desktop launch, read from the arguments.
+fn source_from_args<I: IntoIterator<Item = String>>(args: I) -> LaunchSource {
+  let mut source = LaunchSource::User;
+  for arg in args {
+    if arg == AUTOSTART_ARG {
+      return LaunchSource::Autostart;
+    }
+    if arg.starts_with('-') || source != LaunchSource::User {
+      continue;
+    }
+    source = match Url::parse(&arg) {
+      Ok(url) if url.scheme() == "file" => match url.to_file_path() {
+        Ok(path) => LaunchSource::File { path },
+        Err(()) => continue,
+      },
+      // single letter schemes are Windows drive letters
+      Ok(url) if url.scheme().len() > 1 => LaunchSource::DeepLink { url },
+      _ => {
+        let path = PathBuf::from(arg);
+        if !path.exists() {
+          continue;
+        }
+        LaunchSource::File { path }
+      }
+    };
+  }
+  source
+}
+
+pub(crate) fn source<R: Runtime>(app: &AppHandle<R>) -> LaunchSource {
+  if let Some(push) = app.try_state::<PushNotifications<R>>() {
+    if push.launch_notification().is_some() {
+      return LaunchSource::Notification;
+    }
+  }
+  if let Some(activations) = app.try_state::<Activations<R>>() {
+    match activations.launch_activation() {
+      Some(Activation::Url { url }) => return LaunchSource::DeepLink { url },
+      Some(Activation::Share(_)) => return LaunchSource::Share,
+      _ => {}
+    }
+  }
+  source_from_args(std::env::args().skip(1))
+}
+
+pub(crate) fn info<R: Runtime>(app: &AppHandle<R>) -> LaunchInfo {
+  let history = history(app);
+  LaunchInfo {
+    first_run: history.first_run,
+    previous_version: history.previous_version,
+    version: app.package_info().version.clone(),
+    source: source(app),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use semver::Version;
+
+  use super::{record_to, source_from_args, LaunchSource};
+
+  #[test]
+  fn records_versions() {
+    let path = std::env::temp_dir()
+      .join(format!("tauri-launch-{}", std::process::id()))
+      .join(super::LAUNCH_FILE_NAME);
+    let v1 = Version::new(1, 0, 0);
+    let v2 = Version::new(1, 1, 0);
+
+    let history = record_to(&path, &v1);
+    assert!(history.first_run);
+    assert_eq!(history.previous_version, None);
+
+    let history = record_to(&path, &v1);
+    assert!(!history.first_run);
+    assert_eq!(history.previous_version, None);
+
+    let history = record_to(&path, &v2);
+    assert!(!history.first_run);
+    assert_eq!(history.previous_version, Some(v1));
+    assert_eq!(record_to(&path, &v2).previous_version, None);
+
+    std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+  }
+
+  #[test]
+  fn reads_source_from_args() {
+    let args = |args: &[&str]| args.iter().map(|a| a.to_string()).collect::<Vec<_>>();
+    assert_eq!(source_from_args(args(&[])), LaunchSource::User);
+    assert_eq!(
+      source_from_args(args(&["--verbose", "--autostart"])),
+      LaunchSource::Autostart
+    );
+    assert_eq!(
+      source_from_args(args(&["myapp://open/1"])),
+      LaunchSource::DeepLink {
+        url: "myapp://open/1".parse().unwrap()
+      }
+    );
+    let file = std::env::temp_dir();
+    assert_eq!(
+      source_from_args(args(&[file.to_str().unwrap()])),
+      LaunchSource::File { path: file }
+    );
+    assert_eq!(
+      source_from_args(args(&["missing-file.txt"])),
+      LaunchSource::User
+    );
+  }
+}
@@ -36,8 +36,14 @@
 //! - **config-toml**: Adds support to TOML format for the configuration `Tauri.toml`.
 //! - **image-ico**: Adds support to parse `.ico` image, see [`Image`].
 //! - **image-png**: Adds support to parse `.png` image, see [`Image`].
+//! - **image-jpeg**: Adds support to parse `.jpg` image, see [`Image`].
+//! - **image-svg**: Adds support to parse and rasterize `.svg` image, see [`Image`].
+//! - **fs-watch**: Adds the [`fs::watch`] function to watch files and directories for changes, also exposed to the webviews.
+//! - **os-permissions**: Links the iOS frameworks used by [`mobile::permissions::PermissionsExt`] to check and request the iOS permissions. Without it, the iOS permissions can't be checked.
 //! - **macos-proxy**: Adds support for [`WebviewBuilder::proxy_url`] on macOS. Requires macOS 14+.
 //! - **specta**: Add support for [`specta::specta`](https://docs.rs/specta/%5E2.0.0-rc.9/specta/attr.specta.html) with Tauri arguments such as [`State`](crate::State), [`Window`](crate::Window) and [`AppHandle`](crate::AppHandle)
+//! - **asset-packfile**: Reads the frontend assets from the packfile written by the `build > assetStorage` config set to `packfile`. Enabled by default if that config is set on the `tauri.conf.json` file.
+//! - **dynamic-plugins**: Loads the plugins compiled as shared libraries from the directory of the `app > dynamicPlugins` config, see the [`plugin::dynamic`] module. Enabled by default if the `app > dynamicPlugins` config is defined on the `tauri.conf.json` file.
 //!
 //! ## Cargo allowlist features
 //!
@@ -84,14 +90,35 @@ pub use url::Url;
 
 pub(crate) mod app;
 pub mod async_runtime;
+#[cfg(desktop)]
+pub mod default_handler;
+#[cfg(desktop)]
+pub mod dialog;
 mod error;
 mod event;
+pub mod fs;
+pub mod i18n;
+pub mod idle;
 pub mod ipc;
+pub mod launch;
+mod lifecycle;
 mod manager;
+pub mod memory;
+pub mod migration;
+pub mod mobile;
+pub mod net;
+#[cfg(desktop)]
+pub mod opener;
 mod pattern;
 pub mod plugin;
 pub(crate) mod protocol;
 mod resources;
+pub mod scheduler;
+#[cfg(desktop)]
+pub mod session;
+pub mod share;
+#[cfg(desktop)]
+pub mod sidecar;
 mod vibrancy;
 pub mod webview;
 pub mod window;
@@ -105,10 +132,21 @@ pub mod menu;
 /// Path APIs.
 pub mod path;
 pub mod process;
+#[cfg(feature = "remote-control")]
+pub mod remote_control;
 /// The allowlist scopes.
 pub mod scope;
+#[cfg(feature = "server")]
+pub mod server;
 mod state;
+pub mod time;
 
+#[cfg(all(desktop, any(feature = "gamepad", feature = "global-input-listener")))]
+#[cfg_attr(
+  docsrs,
+  doc(cfg(all(desktop, any(feature = "gamepad", feature = "global-input-listener"))))
+)]
+pub mod input;
 #[cfg(all(desktop, feature = "tray-icon"))]
 #[cfg_attr(docsrs, doc(cfg(all(desktop, feature = "tray-icon"))))]
 pub mod tray;
@@ -161,6 +199,49 @@ macro_rules! android_binding {
       sendChannelData,
       [i64, JString],
     );
+    ::tauri::tao::platform::android::prelude::android_fn!(
+      app_tauri,
+      plugin,
+      PluginManager,
+      onMemoryWarning,
+      [],
+    );
+    ::tauri::tao::platform::android::prelude::android_fn!(
+      app_tauri,
+      plugin,
+      PluginManager,
+      onLifecycleEvent,
+      [i32],
+    );
+    ::tauri::tao::platform::android::prelude::android_fn!(
+      app_tauri,
+      plugin,
+      PluginManager,
+      onActivation,
+      [JString],
+    );
+    ::tauri::tao::platform::android::prelude::android_fn!(
+      app_tauri,
+      background,
+      BackgroundTaskWorker,
+      runBackgroundTask,
+      [JString],
+      u8,
+    );
+    ::tauri::tao::platform::android::prelude::android_fn!(
+      app_tauri,
+      push,
+      PushNotifications,
+      onPushToken,
+      [JString],
+    );
+    ::tauri::tao::platform::android::prelude::android_fn!(
+      app_tauri,
+      push,
+      PushNotifications,
+      onPushNotification,
+      [JString],
+    );
 
     // this function is a glue between PluginManager.kt > handlePluginResponse and Rust
     #[allow(non_snake_case)]
@@ -179,9 +260,58 @@ macro_rules! android_binding {
     pub fn sendChannelData(mut env: JNIEnv, _: JClass, id: i64, data: JString) {
       ::tauri::send_channel_data(&mut env, id, data);
     }
+
+    // this function is a glue between PluginManager.kt > onMemoryWarning and Rust
+    #[allow(non_snake_case)]
+    pub fn onMemoryWarning(_: JNIEnv, _: JClass) {
+      ::tauri::handle_android_memory_warning();
+    }
+
+    // this function is a glue between PluginManager.kt > onLifecycleEvent and Rust
+    #[allow(non_snake_case)]
+    pub fn onLifecycleEvent(_: JNIEnv, _: JClass, event: i32) {
+      ::tauri::handle_android_lifecycle_event(event);
+    }
+
+    // this function is a glue between PluginManager.kt > onActivation and Rust
+    #[allow(non_snake_case)]
+    pub fn onActivation(mut env: JNIEnv, _: JClass, payload: JString) {
+      ::tauri::handle_android_activation(&mut env, payload);
+    }
+
+    // this function is a glue between BackgroundTaskWorker.kt > runBackgroundTask and Rust
+    // returns a jboolean
+    #[allow(non_snake_case)]
+    pub fn runBackgroundTask(mut env: JNIEnv, _: JClass, id: JString) -> u8 {
+      ::tauri::handle_android_background_task(&mut env, id) as u8
+    }
+
+    // this function is a glue between PushNotifications.kt > onPushToken and Rust
+    #[allow(non_snake_case)]
+    pub fn onPushToken(mut env: JNIEnv, _: JClass, token: JString) {
+      ::tauri::handle_android_push_token(&mut env, token);
+    }
+
+    // this function is a glue between PushNotifications.kt > onPushNotification and Rust
+    #[allow(non_snake_case)]
+    pub fn onPushNotification(mut env: JNIEnv, _: JClass, payload: JString) {
+      ::tauri::handle_android_push_notification(&mut env, payload);
+    }
   };
 }
 
+#[cfg(all(feature = "wry", target_os = "android"))]
+#[doc(hidden)]
+pub use lifecycle::{handle_android_lifecycle_event, handle_android_memory_warning};
+#[cfg(all(feature = "wry", target_os = "android"))]
+#[doc(hidden)]
+pub use mobile::activation::handle_android_activation;
+#[cfg(all(feature = "wry", target_os = "android"))]
+#[doc(hidden)]
+pub use mobile::background::handle_android_background_task;
+#[cfg(all(feature = "wry", target_os = "android"))]
+#[doc(hidden)]
+pub use mobile::push::{handle_android_push_notification, handle_android_push_token};
 #[cfg(all(feature = "wry", target_os = "android"))]
 #[doc(hidden)]
 pub use plugin::mobile::{handle_android_plugin_response, send_channel_data};
@@ -197,9 +327,9 @@ use std::{
   borrow::Cow,
   collections::HashMap,
   fmt::{self, Debug},
-  sync::MutexGuard,
+  sync::{Arc, MutexGuard},
 };
-use utils::assets::{AssetKey, CspHash, EmbeddedAssets};
+use utils::assets::{AssetKey, AssetMetadata, CspHash, EmbeddedAssets};
 
 #[cfg(feature = "wry")]
 #[cfg_attr(docsrs, doc(cfg(feature = "wry")))]
@@ -215,15 +345,15 @@ pub use self::utils::TitleBarStyle;
 pub use self::event::{Event, EventId, EventTarget};
 pub use {
   self::app::{
-    App, AppHandle, AssetResolver, Builder, CloseRequestApi, RunEvent, UriSchemeResponder,
-    WebviewEvent, WindowEvent,
+    App, AppHandle, AssetResolver, Builder, CloseRequestApi, RunEvent, StartupTimings,
+    UriSchemeResponder, WebviewEvent, WindowEvent,
   },
   self::manager::Asset,
   self::runtime::{
     dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize, Pixel, Position, Size},
     webview::WebviewAttributes,
-    window::{CursorIcon, DragDropEvent, WindowSizeConstraints},
-    DeviceEventFilter, Rect, UserAttentionType,
+    window::{CursorIcon, DragDropEvent, ImeEvent, ProcessCrashReason, WindowSizeConstraints},
+    DeviceEventFilter, LifecycleEvent, Rect, UserAttentionType,
   },
   self::state::{State, StateManager},
   self::utils::{
@@ -289,6 +419,17 @@ pub enum EventLoopMessage {
   #[cfg(all(desktop, feature = "tray-icon"))]
   #[cfg_attr(docsrs, doc(cfg(all(desktop, feature = "tray-icon"))))]
   TrayIconEvent(tray::TrayIconEvent),
+  /// The configuration was reloaded with [`AppHandle::reload_config_subset`].
+  ConfigReloaded(std::sync::Arc<Config>),
+  /// A lifecycle event the runtime does not report, such as the desktop power events.
+  Lifecycle(LifecycleEvent),
+  /// A monitor was added, removed or rearranged.
+  MonitorsChanged {
+    /// The connected monitors.
+    monitors: Vec<Monitor>,
+    /// The labels of the windows that moved to another monitor.
+    windows: Vec<String>,
+  },
 }
 
 /// The webview runtime interface. A wrapper around [`runtime::Runtime`] with the proper user event type associated.
@@ -355,9 +496,37 @@ pub trait Assets<R: Runtime>: Send + Sync + 'static {
 
   /// Gets the hashes for the CSP tag of the HTML on the given path.
   fn csp_hashes(&self, html_path: &AssetKey) -> Box<dyn Iterator<Item = CspHash<'_>> + '_>;
+
+  /// Get the content of the passed [`AssetKey`] without decompressing it, if it is stored with the given content encoding such as `br`.
+  ///
+  /// The asset protocol serves it as is to the webviews accepting the encoding.
+  fn get_encoded(&self, key: &AssetKey, encoding: &str) -> Option<Cow<'_, [u8]>> {
+    let _ = (key, encoding);
+    None
+  }
+
+  /// Gets the compile-time metadata of the passed [`AssetKey`].
+  fn metadata(&self, key: &AssetKey) -> Option<AssetMetadata<'_>> {
+    let _ = key;
+    None
+  }
+
+  /// Resolves a content-hashed asset key, see [`AssetKey::with_content_hash`], to the key of the asset.
+  fn resolve_hashed(&self, hashed_key: &AssetKey) -> Option<AssetKey> {
+    let _ = hashed_key;
+    None
+  }
 }
 
 impl<R: Runtime> Assets<R> for EmbeddedAssets {
+  #[cfg(feature = "asset-packfile")]
+  fn setup(&self, app: &App<R>) {
+    // bundled apps ship the asset packfile as a resource
+    if let Ok(resource_dir) = app.path().resource_dir() {
+      EmbeddedAssets::set_packfile_dir(self, resource_dir);
+    }
+  }
+
   fn get(&self, key: &AssetKey) -> Option<Cow<'_, [u8]>> {
     EmbeddedAssets::get(self, key)
   }
@@ -369,6 +538,18 @@ impl<R: Runtime> Assets<R> for EmbeddedAssets {
   fn csp_hashes(&self, html_path: &AssetKey) -> Box<dyn Iterator<Item = CspHash<'_>> + '_> {
     EmbeddedAssets::csp_hashes(self, html_path)
   }
+
+  fn get_encoded(&self, key: &AssetKey, encoding: &str) -> Option<Cow<'_, [u8]>> {
+    EmbeddedAssets::get_encoded(self, key, encoding)
+  }
+
+  fn metadata(&self, key: &AssetKey) -> Option<AssetMetadata<'_>> {
+    EmbeddedAssets::metadata(self, key)
+  }
+
+  fn resolve_hashed(&self, hashed_key: &AssetKey) -> Option<AssetKey> {
+    EmbeddedAssets::resolve_hashed(self, hashed_key)
+  }
 }
 
 /// User supplied data required inside of a Tauri application.
@@ -613,6 +794,77 @@ pub trait Manager<R: Runtime>: sealed::ManagerBase<R> {
       .collect::<HashMap<_, _>>()
   }
 
+  /// The sorted labels of the windows matching the glob pattern.
+  ///
+  /// `*` matches any sequence of characters, including `/`, and `?` matches a single character.
+  ///
+  /// # Examples
+  ///
+  /// ```rust,no_run
+  /// use tauri::Manager;
+  ///
+  /// tauri::Builder::default()
+  ///   .setup(|app| {
+  ///     for label in app.window_labels_matching("editor-*") {
+  ///       println!("editor window: {label}");
+  ///     }
+  ///     Ok(())
+  ///   });
+  /// ```
+  fn window_labels_matching(&self, pattern: &str) -> Vec<String> {
+    let mut labels = self
+      .manager()
+      .window
+      .windows_lock()
+      .keys()
+      .filter(|label| tauri_utils::label::matches(pattern, label))
+      .cloned()
+      .collect::<Vec<_>>();
+    labels.sort();
+    labels
+  }
+
+  /// The sorted labels of the webviews matching the glob pattern, see [`Self::window_labels_matching`].
+  fn webview_labels_matching(&self, pattern: &str) -> Vec<String> {
+    let mut labels = self
+      .manager()
+      .webview
+      .webviews_lock()
+      .keys()
+      .filter(|label| tauri_utils::label::matches(pattern, label))
+      .cloned()
+      .collect::<Vec<_>>();
+    labels.sort();
+    labels
+  }
+
+  /// A label similar to the given one that is not used by any window,
+  /// e.g. to retry after [`Error::WindowLabelAlreadyExists`].
+  ///
+  /// # Examples
+  ///
+  /// ```rust,no_run
+  /// use tauri::{Manager, WebviewWindowBuilder};
+  ///
+  /// tauri::Builder::default()
+  ///   .setup(|app| {
+  ///     let label = app.suggest_window_label("editor");
+  ///     WebviewWindowBuilder::new(app, label, Default::default()).build()?;
+  ///     Ok(())
+  ///   });
+  /// ```
+  fn suggest_window_label(&self, label: &str) -> String {
+    let windows = self.manager().window.windows_lock();
+    tauri_utils::label::suggest(label, |label| windows.contains_key(label))
+  }
+
+  /// A label similar to the given one that is not used by any webview,
+  /// e.g. to retry after [`Error::WebviewLabelAlreadyExists`].
+  fn suggest_webview_label(&self, label: &str) -> String {
+    let webviews = self.manager().webview.webviews_lock();
+    tauri_utils::label::suggest(label, |label| webviews.contains_key(label))
+  }
+
   /// Add `state` to the state managed by the application.
   ///
   /// If the state for the `T` type has previously been set, the state is unchanged and false is returned. Otherwise true is returned.
@@ -734,6 +986,11 @@ pub trait Manager<R: Runtime>: sealed::ManagerBase<R> {
   /// Get a reference to the resources table of this manager.
   fn resources_table(&self) -> MutexGuard<'_, ResourceTable>;
 
+  /// The [`time::Clock`] scheduling the timers of the app, see [`Builder::clock`].
+  fn clock(&self) -> Arc<dyn time::Clock> {
+    self.manager().clock.clone()
+  }
+
   /// Gets the managed [`Env`].
   fn env(&self) -> Env {
     self.state::<Env>().inner().clone()
@@ -0,0 +1,371 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! The lifecycle events the operating system reports outside of the event loop,
+//! such as the desktop power events and the mobile memory warnings and app state changes.
+
+use std::{
+  fmt,
+  sync::{Arc, Mutex, Once, Weak},
+};
+
+use crate::{runtime::LifecycleEvent, AppHandle, Emitter, Runtime};
+
+/// The event emitted to the webviews with the name of the lifecycle event, e.g. `background`.
+const LIFECYCLE_EVENT: &str = "tauri://lifecycle";
+
+type Listener = Box<dyn Fn(LifecycleEvent) + Send + Sync>;
+
+/// The lifecycle states of the apps notified of the platform events, which are reported to the whole process.
+static STATES: Mutex<Vec<Weak<LifecycleState>>> = Mutex::new(Vec::new());
+
+/// Forwards the lifecycle events of the platform to the listener of the app state, which sends them to the event loop.
+///
+/// The notifications are registered once per process and delivered to every app listening.
+pub(crate) fn listen<F: Fn(LifecycleEvent) + Send + Sync + 'static>(
+  state: &Arc<LifecycleState>,
+  listener: F,
+) {
+  static REGISTER: Once = Once::new();
+
+  state.listener.lock().unwrap().replace(Box::new(listener));
+  {
+    let mut states = STATES.lock().unwrap();
+    states.retain(|state| state.strong_count() > 0);
+    states.push(Arc::downgrade(state));
+  }
+  REGISTER.call_once(platform::register);
+}
+
+/// Sends the platform event to the apps.
+#[cfg_attr(not(any(windows, target_os = "macos", mobile)), allow(dead_code))]
+fn emit(event: LifecycleEvent) {
+  let states = STATES
+    .lock()
+    .unwrap()
+    .iter()
+    .filter_map(Weak::upgrade)
+    .collect::<Vec<_>>();
+  for state in states {
+    state.emit(event);
+  }
+}
+
+/// Glue between Rust and the Kotlin code that notifies the memory warnings.
+#[cfg(target_os = "android")]
+pub fn handle_android_memory_warning() {
+  emit(LifecycleEvent::MemoryWarning);
+}
+
+/// Glue between Rust and the Kotlin code that notifies the app state changes.
+#[cfg(target_os = "android")]
+pub fn handle_android_lifecycle_event(event: i32) {
+  if let Some(event) = from_code(event) {
+    emit(event);
+  }
+}
+
+/// The app state changes reported by the Kotlin and Swift code.
+#[cfg(mobile)]
+fn from_code(code: i32) -> Option<LifecycleEvent> {
+  match code {
+    0 => Some(LifecycleEvent::Foreground),
+    1 => Some(LifecycleEvent::Inactive),
+    2 => Some(LifecycleEvent::Background),
+    3 => Some(LifecycleEvent::Terminate),
+    _ => None,
+  }
+}
+
+/// The name of the event in the [`LIFECYCLE_EVENT`] payload.
+fn name(event: LifecycleEvent) -> &'static str {
+  match event {
+    LifecycleEvent::Suspended => "suspended",
+    LifecycleEvent::Resumed => "resumed",
+    LifecycleEvent::MemoryWarning => "memory-warning",
+    LifecycleEvent::Foreground => "foreground",
+    LifecycleEvent::Inactive => "inactive",
+    LifecycleEvent::Background => "background",
+    LifecycleEvent::Terminate => "terminate",
+    _ => "unknown",
+  }
+}
+
+/// The focus of the desktop app windows.
+#[cfg(desktop)]
+#[derive(Debug, Default)]
+struct FocusState {
+  /// The labels of the focused windows.
+  windows: std::collections::HashSet<String>,
+  /// Whether the last focused window lost the focus. The app is inactive only if no other window
+  /// gained the focus once the pending window events are processed.
+  losing_focus: bool,
+}
+
+/// The lifecycle state of the app.
+#[derive(Default)]
+pub(crate) struct LifecycleState {
+  last: Mutex<Option<LifecycleEvent>>,
+  /// Sends the events to the event loop of the app.
+  listener: Mutex<Option<Listener>>,
+  #[cfg(desktop)]
+  focus: Mutex<FocusState>,
+}
+
+impl fmt::Debug for LifecycleState {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let mut d = f.debug_struct("LifecycleState");
+    d.field("last", &self.last);
+    #[cfg(desktop)]
+    d.field("focus", &self.focus);
+    d.finish()
+  }
+}
+
+impl LifecycleState {
+  pub(crate) fn last(&self) -> Option<LifecycleEvent> {
+    *self.last.lock().unwrap()
+  }
+
+  fn emit(&self, event: LifecycleEvent) {
+    if let Some(listener) = &*self.listener.lock().unwrap() {
+      listener(event);
+    }
+  }
+
+  /// Reports the [`LifecycleEvent::Foreground`] event of the desktop apps,
+  /// which are in the foreground while one of their windows is focused.
+  ///
+  /// The focus moving to another window of the app is reported by the operating system as
+  /// a window losing the focus then another one gaining it, so the [`LifecycleEvent::Inactive`] event
+  /// is only reported by [`Self::on_main_events_cleared`].
+  #[cfg(desktop)]
+  pub(crate) fn on_window_focus_changed(&self, label: &str, focused: bool) {
+    let mut focus = self.focus.lock().unwrap();
+    let was_focused = !focus.windows.is_empty() || focus.losing_focus;
+    if focused {
+      focus.windows.insert(label.into());
+    } else {
+      focus.windows.remove(label);
+    }
+    if focus.windows.is_empty() {
+      focus.losing_focus = was_focused;
+    } else {
+      focus.losing_focus = false;
+      if !was_focused {
+        self.emit(LifecycleEvent::Foreground);
+      }
+    }
+  }
+
+  /// Reports the [`LifecycleEvent::Inactive`] event of the desktop apps once the pending window events are processed,
+  /// if none of their windows gained the focus.
+  #[cfg(desktop)]
+  pub(crate) fn on_main_events_cleared(&self) {
+    let losing_focus = std::mem::take(&mut self.focus.lock().unwrap().losing_focus);
+    if losing_focus {
+      self.emit(LifecycleEvent::Inactive);
+    }
+  }
+}
+
+/// Records the app state and emits the event to the webviews.
+pub(crate) fn on_lifecycle_event<R: Runtime>(app: &AppHandle<R>, event: LifecycleEvent) {
+  if matches!(
+    event,
+    LifecycleEvent::Foreground
+      | LifecycleEvent::Inactive
+      | LifecycleEvent::Background
+      | LifecycleEvent::Terminate
+  ) {
+    app.manager.lifecycle.last.lock().unwrap().replace(event);
+  }
+  let _ = app.emit(LIFECYCLE_EVENT, name(event));
+}
+
+#[cfg(windows)]
+mod platform {
+  use std::ffi::c_void;
+
+  use windows::Win32::{
+    Foundation::HANDLE,
+    System::Power::{PowerRegisterSuspendResumeNotification, DEVICE_NOTIFY_SUBSCRIBE_PARAMETERS},
+    UI::WindowsAndMessaging::{DEVICE_NOTIFY_CALLBACK, PBT_APMRESUMEAUTOMATIC, PBT_APMSUSPEND},
+  };
+
+  use super::{emit, LifecycleEvent};
+
+  unsafe extern "system" fn on_power_event(
+    _context: *const c_void,
+    kind: u32,
+    _setting: *const c_void,
+  ) -> u32 {
+    match kind {
+      PBT_APMSUSPEND => emit(LifecycleEvent::Suspended),
+      PBT_APMRESUMEAUTOMATIC => emit(LifecycleEvent::Resumed),
+      _ => {}
+    }
+    0
+  }
+
+  pub fn register() {
+    // the registration is never removed so the parameters must outlive it
+    let parameters = Box::leak(Box::new(DEVICE_NOTIFY_SUBSCRIBE_PARAMETERS {
+      Callback: Some(on_power_event),
+      Context: std::ptr::null_mut(),
+    }));
+    let mut registration = std::ptr::null_mut();
+    unsafe {
+      let _ = PowerRegisterSuspendResumeNotification(
+        DEVICE_NOTIFY_CALLBACK,
+        HANDLE(parameters as *mut DEVICE_NOTIFY_SUBSCRIBE_PARAMETERS as isize),
+        &mut registration,
+      );
+    }
+  }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+  use cocoa::{
+    base::{id, nil},
+    foundation::NSString,
+  };
+  use objc::{
+    class,
+    declare::ClassDecl,
+    msg_send,
+    runtime::{Object, Sel},
+    sel, sel_impl,
+  };
+
+  use super::{emit, LifecycleEvent};
+
+  extern "C" fn will_sleep(_this: &Object, _cmd: Sel, _notification: id) {
+    emit(LifecycleEvent::Suspended);
+  }
+
+  extern "C" fn did_wake(_this: &Object, _cmd: Sel, _notification: id) {
+    emit(LifecycleEvent::Resumed);
+  }
+
+  pub fn register() {
+    let Some(mut decl) = ClassDecl::new("TauriLifecycleObserver", class!(NSObject)) else {
+      return;
+    };
+    unsafe {
+      decl.add_method(
+        sel!(willSleep:),
+        will_sleep as extern "C" fn(&Object, Sel, id),
+      );
+      decl.add_method(sel!(didWake:), did_wake as extern "C" fn(&Object, Sel, id));
+      let observer: id = msg_send![decl.register(), new];
+
+      let workspace: id = msg_send![class!(NSWorkspace), sharedWorkspace];
+      let center: id = msg_send![workspace, notificationCenter];
+      for (selector, name) in [
+        (sel!(willSleep:), "NSWorkspaceWillSleepNotification"),
+        (sel!(didWake:), "NSWorkspaceDidWakeNotification"),
+      ] {
+        let name = NSString::alloc(nil).init_str(name);
+        let () = msg_send![center, addObserver: observer selector: selector name: name object: nil];
+      }
+    }
+  }
+}
+
+#[cfg(target_os = "ios")]
+mod platform {
+  use super::{emit, from_code, LifecycleEvent};
+
+  extern "C" fn on_memory_warning() {
+    emit(LifecycleEvent::MemoryWarning);
+  }
+
+  extern "C" fn on_lifecycle_event(code: i32) {
+    if let Some(event) = from_code(code) {
+      emit(event);
+    }
+  }
+
+  pub fn register() {
+    unsafe {
+      crate::ios::on_memory_warning(crate::ios::MemoryWarningCallback(on_memory_warning));
+      crate::ios::on_lifecycle_event(crate::ios::LifecycleEventCallback(on_lifecycle_event));
+    }
+  }
+}
+
+// the Android events are sent by the Kotlin code, and Linux has no such notifications
+#[cfg(not(any(windows, target_os = "macos", target_os = "ios")))]
+mod platform {
+  pub fn register() {}
+}
+
+#[cfg(test)]
+mod tests {
+  use std::sync::{Arc, Mutex};
+
+  use super::{listen, on_lifecycle_event, LifecycleEvent, LifecycleState};
+
+  #[test]
+  fn last_state() {
+    let app = crate::test::mock_app();
+    assert_eq!(app.handle().last_state(), None);
+
+    on_lifecycle_event(app.handle(), LifecycleEvent::Background);
+    on_lifecycle_event(app.handle(), LifecycleEvent::MemoryWarning);
+    assert_eq!(app.handle().last_state(), Some(LifecycleEvent::Background));
+
+    on_lifecycle_event(app.handle(), LifecycleEvent::Foreground);
+    assert_eq!(app.handle().last_state(), Some(LifecycleEvent::Foreground));
+  }
+
+  fn recording_state() -> (Arc<LifecycleState>, Arc<Mutex<Vec<LifecycleEvent>>>) {
+    let state = Arc::new(LifecycleState::default());
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let events_ = events.clone();
+    listen(&state, move |event| events_.lock().unwrap().push(event));
+    (state, events)
+  }
+
+  #[test]
+  fn every_app_listens() {
+    let (_first, first_events) = recording_state();
+    let (_second, second_events) = recording_state();
+    super::emit(LifecycleEvent::MemoryWarning);
+    assert!(first_events
+      .lock()
+      .unwrap()
+      .contains(&LifecycleEvent::MemoryWarning));
+    assert!(second_events
+      .lock()
+      .unwrap()
+      .contains(&LifecycleEvent::MemoryWarning));
+  }
+
+  #[cfg(desktop)]
+  #[test]
+  fn focus_moving_between_windows() {
+    let (state, events) = recording_state();
+    state.on_window_focus_changed("main", true);
+    state.on_window_focus_changed("main", false);
+    state.on_window_focus_changed("settings", true);
+    state.on_main_events_cleared();
+    state.on_window_focus_changed("settings", false);
+    state.on_main_events_cleared();
+
+    let focus_events = events
+      .lock()
+      .unwrap()
+      .iter()
+      .copied()
+      .filter(|event| matches!(event, LifecycleEvent::Foreground | LifecycleEvent::Inactive))
+      .collect::<Vec<_>>();
+    assert_eq!(
+      focus_events,
+      [LifecycleEvent::Foreground, LifecycleEvent::Inactive]
+    );
+  }
+}
@@ -4,7 +4,7 @@
 
 use std::{
   borrow::Cow,
-  collections::HashMap,
+  collections::{HashMap, HashSet},
   fmt,
   sync::{Arc, Mutex, MutexGuard},
 };
@@ -23,7 +23,7 @@ use crate::{
   app::{AppHandle, GlobalWebviewEventListener, GlobalWindowEventListener, OnPageLoad},
   event::{assert_event_name_is_valid, Event, EventId, EventTarget, Listeners},
   ipc::{Invoke, InvokeHandler, InvokeResponder, RuntimeAuthority},
-  plugin::PluginStore,
+  plugin::{PluginStore, ServiceRegistry},
   utils::{config::Config, PackageInfo},
   Assets, Context, Pattern, Runtime, StateManager, Window,
 };
@@ -164,6 +164,12 @@ pub struct Asset {
   pub mime_type: String,
   /// The `Content-Security-Policy` header value.
   pub csp_header: Option<String>,
+  /// The `Content-Encoding` of the bytes, when the asset is served compressed.
+  pub content_encoding: Option<String>,
+  /// The `ETag` header value, set for the embedded assets that are not modified at runtime.
+  pub etag: Option<String>,
+  /// Whether the asset was requested on its content-hashed path so it never changes.
+  pub immutable: bool,
 }
 
 impl Asset {
@@ -181,6 +187,35 @@ impl Asset {
   pub fn csp_header(&self) -> Option<&str> {
     self.csp_header.as_deref()
   }
+
+  /// The `Content-Encoding` of the bytes, when the asset is served compressed.
+  pub fn content_encoding(&self) -> Option<&str> {
+    self.content_encoding.as_deref()
+  }
+
+  /// The `ETag` header value.
+  pub fn etag(&self) -> Option<&str> {
+    self.etag.as_deref()
+  }
+
+  /// Whether the asset was requested on its content-hashed path so it never changes.
+  pub fn immutable(&self) -> bool {
+    self.immutable
+  }
+}
+
+/// Whether the `Accept-Encoding` header value accepts the given content encoding.
+fn accepts_encoding(accept_encoding: &str, encoding: &str) -> bool {
+  accept_encoding.split(',').any(|accepted| {
+    let mut parts = accepted.split(';').map(str::trim);
+    parts.next() == Some(encoding)
+      && !parts.any(|param| {
+        param
+          .strip_prefix("q=")
+          .and_then(|q| q.parse::<f32>().ok())
+          .map_or(false, |q| q == 0.)
+      })
+  })
 }
 
 #[default_runtime(crate::Wry, wry)]
@@ -194,9 +229,14 @@ pub struct AppManager<R: Runtime> {
   pub menu: menu::MenuManager<R>,
 
   pub(crate) plugins: Mutex<PluginStore<R>>,
+  /// The services the plugins provide to each other.
+  pub(crate) plugin_services: Mutex<ServiceRegistry>,
+  pub(crate) lifecycle: Arc<crate::lifecycle::LifecycleState>,
   pub listeners: Listeners,
   pub state: Arc<StateManager>,
   pub config: Config,
+  /// The content security policy, replaced when the configuration is reloaded with [`crate::AppHandle::reload_config_subset`].
+  pub(crate) csp: Mutex<Option<Csp>>,
   #[cfg(dev)]
   pub config_parent: Option<std::path::PathBuf>,
   pub assets: Box<dyn Assets<R>>,
@@ -216,6 +256,48 @@ pub struct AppManager<R: Runtime> {
 
   /// Runtime-generated invoke key.
   pub(crate) invoke_key: String,
+
+  /// The time source of the app timers.
+  pub(crate) clock: Arc<dyn crate::time::Clock>,
+
+  /// The time [`crate::Builder::build`] was called.
+  pub(crate) started_at: std::time::Instant,
+  /// The time spent in the startup phases, reported by [`crate::RunEvent::Ready`].
+  pub(crate) startup_timings: Mutex<crate::StartupTimings>,
+
+  /// Emits the memory stats at the interval set with [`crate::AppHandle::set_memory_stats_interval`].
+  pub(crate) memory_stats_timer: Mutex<Option<crate::time::Timer>>,
+  /// Polls the gamepads at the interval set with [`crate::AppHandle::set_gamepad_poll_interval`].
+  #[cfg(all(desktop, feature = "gamepad"))]
+  pub(crate) gamepad_timer: Mutex<Option<crate::time::Timer>>,
+  /// The listeners registered with [`crate::AppHandle::listen_global_input`].
+  #[cfg(all(desktop, feature = "global-input-listener"))]
+  pub(crate) global_input: Arc<crate::input::global_listener::GlobalListeners>,
+
+  /// The policy set with [`crate::Builder::opener_policy`].
+  #[cfg(desktop)]
+  pub(crate) opener_policy: crate::opener::OpenerPolicy,
+
+  /// The launches recorded when the app started, see [`crate::AppHandle::launch_info`].
+  pub(crate) launch_history: std::sync::OnceLock<crate::launch::LaunchHistory>,
+  /// The sidecars spawned with [`crate::AppHandle::spawn_sidecar`].
+  #[cfg(desktop)]
+  pub(crate) sidecars: crate::sidecar::Sidecars,
+  /// The servers started with [`crate::AppHandle::serve`].
+  #[cfg(feature = "server")]
+  pub(crate) servers: Mutex<Vec<crate::server::ServerHandle>>,
+
+  /// The queue of the tasks scheduled with [`crate::AppHandle::schedule_on_main_thread`].
+  pub(crate) scheduler: Arc<crate::scheduler::MainThreadScheduler>,
+  /// The hidden webview windows created with [`crate::AppHandle::prewarm_webviews`].
+  #[cfg(desktop)]
+  pub(crate) webview_pool: crate::webview::pool::WebviewPool,
+  /// The labels of the windows and webviews created by Tauri, allowed to use a reserved prefix.
+  pub(crate) internal_labels: Mutex<HashSet<String>>,
+
+  /// Records the IPC traffic when [`crate::ipc::record::RECORD_ENV_VAR`] is set.
+  #[cfg(dev)]
+  pub(crate) ipc_recorder: Option<crate::ipc::record::IpcRecorder>,
 }
 
 impl<R: Runtime> fmt::Debug for AppManager<R> {
@@ -224,6 +306,8 @@ impl<R: Runtime> fmt::Debug for AppManager<R> {
 
     d.field("window", &self.window)
       .field("plugins", &self.plugins)
+      .field("plugin_services", &self.plugin_services)
+      .field("lifecycle", &self.lifecycle)
       .field("state", &self.state)
       .field("config", &self.config)
       .field("app_icon", &self.app_icon)
@@ -239,6 +323,20 @@ impl<R: Runtime> fmt::Debug for AppManager<R> {
   }
 }
 
+/// The content security policy of the configuration, using the `devCsp` in dev mode if set.
+pub(crate) fn csp_from_config(config: &Config) -> Option<Csp> {
+  if !crate::is_dev() {
+    config.app.security.csp.clone()
+  } else {
+    config
+      .app
+      .security
+      .dev_csp
+      .clone()
+      .or_else(|| config.app.security.csp.clone())
+  }
+}
+
 impl<R: Runtime> AppManager<R> {
   #[allow(clippy::too_many_arguments, clippy::type_complexity)]
   pub(crate) fn with_handlers(
@@ -269,16 +367,23 @@ impl<R: Runtime> AppManager<R> {
         windows: Mutex::default(),
         default_icon: context.default_window_icon,
         event_listeners: Arc::new(window_event_listeners),
+        monitors: Default::default(),
+        monitors_timer: Default::default(),
+        #[cfg(all(desktop, feature = "tray-icon"))]
+        popovers: Default::default(),
       },
       webview: webview::WebviewManager {
         webviews: Mutex::default(),
         invoke_handler,
         on_page_load,
+        on_csp_violation: None,
         uri_scheme_protocols: Mutex::new(uri_scheme_protocols),
         event_listeners: Arc::new(webiew_event_listeners),
         invoke_responder,
         invoke_initialization_script,
         invoke_key: invoke_key.clone(),
+        crash_recovery: Default::default(),
+        zoom: Default::default(),
       },
       #[cfg(all(desktop, feature = "tray-icon"))]
       tray: tray::TrayManager {
@@ -295,8 +400,11 @@ impl<R: Runtime> AppManager<R> {
         event_listeners: Mutex::new(window_menu_event_listeners),
       },
       plugins: Mutex::new(plugins),
+      plugin_services: Mutex::default(),
+      lifecycle: Default::default(),
       listeners: Listeners::default(),
       state: Arc::new(state),
+      csp: Mutex::new(csp_from_config(&context.config)),
       config: context.config,
       #[cfg(dev)]
       config_parent: context.config_parent,
@@ -307,6 +415,27 @@ impl<R: Runtime> AppManager<R> {
       plugin_global_api_scripts: Arc::new(context.plugin_global_api_scripts),
       resources_table: Arc::default(),
       invoke_key,
+      clock: Arc::new(crate::time::SystemClock),
+      started_at: std::time::Instant::now(),
+      startup_timings: Default::default(),
+      memory_stats_timer: Default::default(),
+      #[cfg(all(desktop, feature = "gamepad"))]
+      gamepad_timer: Default::default(),
+      #[cfg(all(desktop, feature = "global-input-listener"))]
+      global_input: Default::default(),
+      #[cfg(desktop)]
+      opener_policy: Default::default(),
+      launch_history: Default::default(),
+      #[cfg(desktop)]
+      sidecars: Default::default(),
+      #[cfg(feature = "server")]
+      servers: Default::default(),
+      scheduler: Default::default(),
+      #[cfg(desktop)]
+      webview_pool: Default::default(),
+      internal_labels: Default::default(),
+      #[cfg(dev)]
+      ipc_recorder: crate::ipc::record::IpcRecorder::from_env(),
     }
   }
 
@@ -352,20 +481,19 @@ impl<R: Runtime> AppManager<R> {
   }
 
   fn csp(&self) -> Option<Csp> {
-    if !crate::is_dev() {
-      self.config.app.security.csp.clone()
-    } else {
-      self
-        .config
-        .app
-        .security
-        .dev_csp
-        .clone()
-        .or_else(|| self.config.app.security.csp.clone())
-    }
+    self.csp.lock().unwrap().clone()
+  }
+
+  pub fn get_asset(&self, path: String) -> Result<Asset, Box<dyn std::error::Error>> {
+    self.get_negotiated_asset(path, None)
   }
 
-  pub fn get_asset(&self, mut path: String) -> Result<Asset, Box<dyn std::error::Error>> {
+  /// Gets the asset, with its stored compression if the `Accept-Encoding` request header value accepts it.
+  pub(crate) fn get_negotiated_asset(
+    &self,
+    mut path: String,
+    accept_encoding: Option<&str>,
+  ) -> Result<Asset, Box<dyn std::error::Error>> {
     let assets = &self.assets;
     if path.ends_with('/') {
       path.pop();
@@ -381,6 +509,32 @@ impl<R: Runtime> AppManager<R> {
       path.chars().skip(1).collect::<String>()
     };
 
+    // the content-hashed keys serve the asset with the matching content only
+    let (path, immutable) = match assets.resolve_hashed(&path.as_str().into()) {
+      Some(key) => (key.as_ref().trim_start_matches('/').to_string(), true),
+      None => (path, false),
+    };
+
+    // HTML files are modified at runtime so they can't be served precompressed
+    if !path.ends_with(".html") && accept_encoding.map_or(false, |a| accepts_encoding(a, "br")) {
+      let key = AssetKey::from(path.as_str());
+      if let Some(bytes) = assets.get_encoded(&key, "br") {
+        let metadata = assets.metadata(&key);
+        return Ok(Asset {
+          bytes: bytes.into_owned(),
+          mime_type: metadata.map_or_else(
+            || tauri_utils::mime_type::MimeType::parse_from_uri(&path).to_string(),
+            |metadata| metadata.mime_type.to_string(),
+          ),
+          csp_header: None,
+          content_encoding: Some("br".into()),
+          // the compressed bytes differ from the identity bytes so they need their own tag
+          etag: metadata.map(|metadata| format!("\"{}-br\"", metadata.hash)),
+          immutable,
+        });
+      }
+    }
+
     let mut asset_path = AssetKey::from(path.as_str());
 
     let asset_response = assets
@@ -421,7 +575,6 @@ impl<R: Runtime> AppManager<R> {
         let final_data = if is_html {
           let mut asset = String::from_utf8_lossy(&asset).into_owned();
           if let Some(csp) = self.csp() {
-            #[allow(unused_mut)]
             let mut csp_map = set_csp(&mut asset, &self.assets, &asset_path, self, csp);
             #[cfg(feature = "isolation")]
             if let Pattern::Isolation { schema, .. } = &*self.pattern {
@@ -431,6 +584,13 @@ impl<R: Runtime> AppManager<R> {
               default_src.push(crate::pattern::format_real_schema(schema));
             }
 
+            if self.webview.on_csp_violation.is_some() {
+              csp_map
+                .entry("report-uri".into())
+                .or_insert_with(Default::default)
+                .push(crate::protocol::csp_report::report_uri());
+            }
+
             csp_header.replace(Csp::DirectiveMap(csp_map).to_string());
           }
 
@@ -439,10 +599,20 @@ impl<R: Runtime> AppManager<R> {
           asset
         };
         let mime_type = tauri_utils::mime_type::MimeType::parse(&final_data, &path);
+        let etag = if is_html {
+          None
+        } else {
+          assets
+            .metadata(&asset_path)
+            .map(|metadata| format!("\"{}\"", metadata.hash))
+        };
         Ok(Asset {
           bytes: final_data.to_vec(),
           mime_type,
           csp_header,
+          content_encoding: None,
+          etag,
+          immutable: immutable && !is_html,
         })
       }
       Err(e) => {
@@ -469,11 +639,13 @@ impl<R: Runtime> AppManager<R> {
   }
 
   pub fn initialize_plugins(&self, app: &AppHandle<R>) -> crate::Result<()> {
-    self
+    let timings = self
       .plugins
       .lock()
       .expect("poisoned plugin store")
-      .initialize_all(app, &self.config.plugins)
+      .initialize_all(app, &self.config.plugins)?;
+    self.startup_timings.lock().unwrap().plugins = timings;
+    Ok(())
   }
 
   pub fn config(&self) -> &Config {
@@ -513,6 +685,20 @@ impl<R: Runtime> AppManager<R> {
     self.listeners().unlisten(id)
   }
 
+  #[cfg(dev)]
+  fn record_event(&self, emit_args: &EmitArgs) {
+    // the window and webview events are not emitted again when the session is replayed
+    if emit_args.event_name.starts_with("tauri://") {
+      return;
+    }
+    if let Some(recorder) = &self.ipc_recorder {
+      recorder.record(&crate::ipc::record::IpcRecord::Event {
+        event: emit_args.event_name.clone(),
+        payload: serde_json::from_str(&emit_args.payload).unwrap_or_default(),
+      });
+    }
+  }
+
   #[cfg_attr(
     feature = "tracing",
     tracing::instrument("app::emit", skip(self, payload))
@@ -523,6 +709,8 @@ impl<R: Runtime> AppManager<R> {
     #[cfg(feature = "tracing")]
     let _span = tracing::debug_span!("emit::run").entered();
     let emit_args = EmitArgs::new(event, payload)?;
+    #[cfg(dev)]
+    self.record_event(&emit_args);
 
     let listeners = self.listeners();
 
@@ -546,6 +734,8 @@ impl<R: Runtime> AppManager<R> {
     #[cfg(feature = "tracing")]
     let _span = tracing::debug_span!("emit::run").entered();
     let emit_args = EmitArgs::new(event, payload)?;
+    #[cfg(dev)]
+    self.record_event(&emit_args);
 
     let listeners = self.listeners();
 
@@ -606,17 +796,44 @@ impl<R: Runtime> AppManager<R> {
       .map(|w| w.1.clone())
   }
 
+  /// Checks the label of a new window or webview, see [`tauri_utils::label::validate`].
+  pub(crate) fn check_label(&self, label: &str) -> crate::Result<()> {
+    if self.internal_labels.lock().unwrap().contains(label) {
+      return Ok(());
+    }
+    tauri_utils::label::validate(label).map_err(|error| crate::Error::InvalidLabel {
+      label: label.into(),
+      error,
+    })
+  }
+
+  /// Allows a window or webview created by Tauri to use a reserved label prefix until it is closed.
+  pub(crate) fn add_internal_label(&self, label: String) {
+    self.internal_labels.lock().unwrap().insert(label);
+  }
+
   pub(crate) fn on_window_close(&self, label: &str) {
+    self.internal_labels.lock().unwrap().remove(label);
+    self.window.monitors.lock().unwrap().remove_window(label);
     let window = self.window.windows_lock().remove(label);
     if let Some(window) = window {
       for webview in window.webviews() {
         self.webview.webviews_lock().remove(webview.label());
+        self
+          .webview
+          .crash_recovery
+          .lock()
+          .unwrap()
+          .remove(webview.label());
+        self.webview.zoom.lock().unwrap().remove(webview.label());
       }
     }
   }
 
   pub(crate) fn on_webview_close(&self, label: &str) {
     self.webview.webviews_lock().remove(label);
+    self.webview.crash_recovery.lock().unwrap().remove(label);
+    self.webview.zoom.lock().unwrap().remove(label);
 
     if let Ok(webview_labels_array) = serde_json::to_string(&self.webview.labels()) {
       let _ = self.webview.eval_script_all(format!(
@@ -667,7 +884,15 @@ impl<R: Runtime> AppManager<R> {
 
 #[cfg(test)]
 mod tests {
-  use super::replace_with_callback;
+  use super::{accepts_encoding, replace_with_callback};
+
+  #[test]
+  fn accept_encoding() {
+    assert!(accepts_encoding("gzip, deflate, br", "br"));
+    assert!(accepts_encoding("br;q=1.0, gzip;q=0.8", "br"));
+    assert!(!accepts_encoding("br;q=0, gzip", "br"));
+    assert!(!accepts_encoding("gzip, deflate", "br"));
+  }
 
   #[test]
   fn string_replace_with_callback() {
@@ -979,4 +1204,34 @@ mod test {
     }
     assert_events("emit_to", &received, &[other_webview_listen_id]);
   }
+
+  #[test]
+  fn window_labels() {
+    let app = mock_app();
+    for label in ["editor-1", "editor-2", "settings"] {
+      WebviewWindowBuilder::new(&app, label, Default::default())
+        .build()
+        .unwrap();
+    }
+
+    match WindowBuilder::new(&app, "editor-1").build() {
+      Err(crate::Error::WindowLabelAlreadyExists(label)) => {
+        assert_eq!(label, "editor-1");
+        assert_eq!(app.suggest_window_label(&label), "editor-1-2");
+      }
+      _ => panic!("expected a label collision"),
+    }
+    assert_eq!(app.suggest_webview_label("settings"), "settings-2");
+    assert_eq!(app.suggest_window_label("about"), "about");
+    assert!(matches!(
+      WindowBuilder::new(&app, "webview-pool-9").build(),
+      Err(crate::Error::InvalidLabel { .. })
+    ));
+
+    assert_eq!(
+      app.window_labels_matching("editor-*"),
+      vec!["editor-1".to_string(), "editor-2".to_string()]
+    );
+    assert_eq!(app.webview_labels_matching("set*"), vec!["settings"]);
+  }
 }
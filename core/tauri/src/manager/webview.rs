@@ -13,18 +13,18 @@ use std::{
 use serde::Serialize;
 use serialize_to_javascript::{default_template, DefaultTemplate, Template};
 use tauri_runtime::{
-  webview::{DetachedWebview, PendingWebview},
+  webview::{CrashRecovery, DetachedWebview, PendingWebview},
   window::DragDropEvent,
 };
 use tauri_utils::config::WebviewUrl;
 use url::Url;
 
 use crate::{
-  app::{GlobalWebviewEventListener, OnPageLoad, UriSchemeResponder, WebviewEvent},
+  app::{GlobalWebviewEventListener, OnCspViolation, OnPageLoad, UriSchemeResponder, WebviewEvent},
   ipc::{InvokeHandler, InvokeResponder},
   pattern::PatternJavascript,
   sealed::ManagerBase,
-  webview::PageLoadPayload,
+  webview::{PageLoadEvent, PageLoadPayload},
   AppHandle, Emitter, EventLoopMessage, EventTarget, Manager, Runtime, Scopes, Webview, Window,
 };
 
@@ -77,6 +77,8 @@ pub struct WebviewManager<R: Runtime> {
   pub invoke_handler: Box<InvokeHandler<R>>,
   /// The page load hook, invoked when the webview performs a navigation.
   pub on_page_load: Option<Arc<OnPageLoad<R>>>,
+  /// The Content-Security-Policy violation hook.
+  pub on_csp_violation: Option<Arc<OnCspViolation<R>>>,
   /// The webview protocols available to all webviews.
   pub uri_scheme_protocols: Mutex<HashMap<String, Arc<UriSchemeProtocol<R>>>>,
   /// Webview event listeners to all webviews.
@@ -89,6 +91,12 @@ pub struct WebviewManager<R: Runtime> {
 
   /// A runtime generated invoke key.
   pub(crate) invoke_key: String,
+
+  /// The crash recovery policy of the webviews and the number of times they were reloaded after a crash.
+  pub(crate) crash_recovery: Mutex<HashMap<String, (CrashRecovery, u32)>>,
+
+  /// The zoom level of the webviews set with [`Webview::set_zoom`].
+  pub(crate) zoom: Mutex<HashMap<String, f64>>,
 }
 
 impl<R: Runtime> fmt::Debug for WebviewManager<R> {
@@ -285,15 +293,46 @@ impl<R: Runtime> WebviewManager<R> {
       registered_scheme_protocols.push("ipc".into());
     }
 
+    if app_manager.webview.on_csp_violation.is_some()
+      && !registered_scheme_protocols.contains(&"csp-report".into())
+    {
+      let protocol =
+        crate::protocol::csp_report::get(manager.manager_owned(), pending.label.clone());
+      pending.register_uri_scheme_protocol("csp-report", move |request, responder| {
+        protocol(request, UriSchemeResponder(responder))
+      });
+      registered_scheme_protocols.push("csp-report".into());
+    }
+
+    #[cfg(desktop)]
+    if !registered_scheme_protocols.contains(&"sidecar".into()) {
+      let protocol = crate::protocol::sidecar::get(
+        manager.manager_owned(),
+        &window_origin,
+        pending.label.clone(),
+      );
+      pending.register_uri_scheme_protocol("sidecar", move |request, responder| {
+        protocol(request, UriSchemeResponder(responder))
+      });
+      registered_scheme_protocols.push("sidecar".into());
+    }
+
     let label = pending.label.clone();
     let app_manager_ = manager.manager_owned();
     let on_page_load_handler = pending.on_page_load_handler.take();
+    let edge_to_edge = pending.webview_attributes.edge_to_edge;
     pending
       .on_page_load_handler
       .replace(Box::new(move |url, event| {
         let payload = PageLoadPayload { url: &url, event };
 
         if let Some(w) = app_manager_.get_webview(&label) {
+          if edge_to_edge && event == PageLoadEvent::Finished {
+            if let Ok(insets) = w.window().safe_area_insets() {
+              let _ = w.eval(&crate::window::safe_area::css_variables_script(&insets));
+            }
+          }
+
           if let Some(on_page_load) = &app_manager_.webview.on_page_load {
             on_page_load(&w, &payload);
           }
@@ -418,45 +457,24 @@ impl<R: Runtime> WebviewManager<R> {
     window_labels: &[String],
     webview_labels: &[WebviewLabelDef],
   ) -> crate::Result<PendingWebview<EventLoopMessage, R>> {
-    if self.webviews_lock().contains_key(&pending.label) {
-      return Err(crate::Error::WebviewLabelAlreadyExists(pending.label));
+    manager.manager().check_label(&pending.label)?;
+    {
+      if self.webviews_lock().contains_key(&pending.label) {
+        return Err(crate::Error::WebviewLabelAlreadyExists(pending.label));
+      }
+    }
+
+    if pending.webview_attributes.crash_recovery != CrashRecovery::None {
+      self.crash_recovery.lock().unwrap().insert(
+        pending.label.clone(),
+        (pending.webview_attributes.crash_recovery, 0),
+      );
     }
 
     let app_manager = manager.manager();
 
     #[allow(unused_mut)] // mut url only for the data-url parsing
-    let mut url = match &pending.webview_attributes.url {
-      WebviewUrl::App(path) => {
-        let url = if PROXY_DEV_SERVER {
-          Cow::Owned(Url::parse("tauri://localhost").unwrap())
-        } else {
-          app_manager.get_url()
-        };
-        // ignore "index.html" just to simplify the url
-        if path.to_str() != Some("index.html") {
-          url
-            .join(&path.to_string_lossy())
-            .map_err(crate::Error::InvalidUrl)
-            // this will never fail
-            .unwrap()
-        } else {
-          url.into_owned()
-        }
-      }
-      WebviewUrl::External(url) => {
-        let config_url = app_manager.get_url();
-        let is_local = config_url.make_relative(url).is_some();
-        let mut url = url.clone();
-        if is_local && PROXY_DEV_SERVER {
-          url.set_scheme("tauri").unwrap();
-          url.set_host(Some("localhost")).unwrap();
-        }
-        url
-      }
-
-      WebviewUrl::CustomProtocol(url) => url.clone(),
-      _ => unimplemented!(),
-    };
+    let mut url = resolve_url(app_manager, &pending.webview_attributes.url);
 
     #[cfg(not(feature = "webview-data-url"))]
     if url.scheme() == "data" {
@@ -526,11 +544,7 @@ impl<R: Runtime> WebviewManager<R> {
     // but we do respect user-specification
     #[cfg(any(target_os = "linux", target_os = "windows"))]
     if pending.webview_attributes.data_directory.is_none() {
-      let local_app_data = manager.path().resolve(
-        &app_manager.config.identifier,
-        crate::path::BaseDirectory::LocalData,
-      );
-      if let Ok(user_data_dir) = local_app_data {
+      if let Ok(user_data_dir) = manager.path().app_local_data_dir() {
         pending.webview_attributes.data_directory = Some(user_data_dir);
       }
     }
@@ -559,6 +573,14 @@ impl<R: Runtime> WebviewManager<R> {
       )
     }
 
+    if pending.webview_attributes.edge_to_edge {
+      pending
+        .webview_attributes
+        .initialization_scripts
+        .push(include_str!("../webview/scripts/safe-area.js").into());
+      crate::window::safe_area::set_edge_to_edge(manager.app_handle())?;
+    }
+
     #[cfg(feature = "isolation")]
     let pattern = app_manager.pattern.clone();
     let navigation_handler = pending.navigation_handler.take();
@@ -684,6 +706,42 @@ impl<R: Runtime> Webview<R> {
   }
 }
 
+/// Resolves the URL a webview loads, the app URLs are relative to the dev server or the frontend dist.
+pub(crate) fn resolve_url<R: Runtime>(app_manager: &AppManager<R>, url: &WebviewUrl) -> Url {
+  match url {
+    WebviewUrl::App(path) => {
+      let url = if PROXY_DEV_SERVER {
+        Cow::Owned(Url::parse("tauri://localhost").unwrap())
+      } else {
+        app_manager.get_url()
+      };
+      // ignore "index.html" just to simplify the url
+      if path.to_str() != Some("index.html") {
+        url
+          .join(&path.to_string_lossy())
+          .map_err(crate::Error::InvalidUrl)
+          // this will never fail
+          .unwrap()
+      } else {
+        url.into_owned()
+      }
+    }
+    WebviewUrl::External(url) => {
+      let config_url = app_manager.get_url();
+      let is_local = config_url.make_relative(url).is_some();
+      let mut url = url.clone();
+      if is_local && PROXY_DEV_SERVER {
+        url.set_scheme("tauri").unwrap();
+        url.set_host(Some("localhost")).unwrap();
+      }
+      url
+    }
+
+    WebviewUrl::CustomProtocol(url) => url.clone(),
+    _ => unimplemented!(),
+  }
+}
+
 fn on_webview_event<R: Runtime>(webview: &Webview<R>, event: &WebviewEvent) -> crate::Result<()> {
   match event {
     WebviewEvent::DragDrop(event) => match event {
@@ -719,6 +777,30 @@ fn on_webview_event<R: Runtime>(webview: &Webview<R>, event: &WebviewEvent) -> c
       DragDropEvent::Leave => webview.emit_to_webview(DRAG_LEAVE_EVENT, ())?,
       _ => unimplemented!(),
     },
+    WebviewEvent::ProcessCrashed { reason } => {
+      log::error!(
+        "the process of the webview `{}` terminated: {reason:?}",
+        webview.label()
+      );
+      let reload = match webview
+        .manager
+        .webview
+        .crash_recovery
+        .lock()
+        .unwrap()
+        .get_mut(webview.label())
+      {
+        Some((CrashRecovery::Reload { max_attempts }, reloads)) if reloads < max_attempts => {
+          *reloads += 1;
+          true
+        }
+        _ => false,
+      };
+      if reload {
+        let url = webview.url()?;
+        webview.clone().navigate(url)?;
+      }
+    }
   }
 
   Ok(())
@@ -13,7 +13,7 @@ use serde::Serialize;
 use tauri_runtime::{
   dpi::{PhysicalPosition, PhysicalSize},
   window::WindowBuilder,
-  window::{DetachedWindow, DragDropEvent, PendingWindow},
+  window::{DetachedWindow, DragDropEvent, ImeEvent, PendingWindow},
 };
 
 use crate::{
@@ -23,12 +23,17 @@ use crate::{
 
 const WINDOW_RESIZED_EVENT: &str = "tauri://resize";
 const WINDOW_MOVED_EVENT: &str = "tauri://move";
+const WINDOW_RESIZE_ENDED_EVENT: &str = "tauri://resize-ended";
+const WINDOW_MOVE_ENDED_EVENT: &str = "tauri://move-ended";
 const WINDOW_CLOSE_REQUESTED_EVENT: &str = "tauri://close-requested";
 const WINDOW_DESTROYED_EVENT: &str = "tauri://destroyed";
+const WINDOW_SAFE_AREA_INSETS_CHANGED_EVENT: &str = "tauri://safe-area-insets-changed";
 const WINDOW_FOCUS_EVENT: &str = "tauri://focus";
 const WINDOW_BLUR_EVENT: &str = "tauri://blur";
 const WINDOW_SCALE_FACTOR_CHANGED_EVENT: &str = "tauri://scale-change";
 const WINDOW_THEME_CHANGED: &str = "tauri://theme-changed";
+const WINDOW_IME_EVENT: &str = "tauri://ime";
+const WINDOW_FULLSCREEN_CHANGED_EVENT: &str = "tauri://fullscreen-changed";
 pub(crate) const DRAG_ENTER_EVENT: &str = "tauri://drag-enter";
 pub(crate) const DRAG_OVER_EVENT: &str = "tauri://drag-over";
 pub(crate) const DRAG_DROP_EVENT: &str = "tauri://drag-drop";
@@ -39,6 +44,13 @@ pub struct WindowManager<R: Runtime> {
   pub default_icon: Option<Image<'static>>,
   /// Window event listeners to all windows.
   pub event_listeners: Arc<Vec<GlobalWindowEventListener<R>>>,
+  /// The monitors and the monitor of each window at the last check.
+  pub monitors: Mutex<crate::window::monitors::MonitorsState>,
+  /// Checks the monitors periodically.
+  pub monitors_timer: Mutex<Option<crate::time::Timer>>,
+  /// The windows shown with [`Window::show_at_tray`], hidden when they lose the focus.
+  #[cfg(all(desktop, feature = "tray-icon"))]
+  pub popovers: Mutex<HashSet<String>>,
 }
 
 impl<R: Runtime> fmt::Debug for WindowManager<R> {
@@ -59,8 +71,10 @@ impl<R: Runtime> WindowManager<R> {
     &self,
     mut pending: PendingWindow<EventLoopMessage, R>,
   ) -> crate::Result<PendingWindow<EventLoopMessage, R>> {
-    if self.windows_lock().contains_key(&pending.label) {
-      return Err(crate::Error::WindowLabelAlreadyExists(pending.label));
+    {
+      if self.windows_lock().contains_key(&pending.label) {
+        return Err(crate::Error::WindowLabelAlreadyExists(pending.label));
+      }
     }
 
     if !pending.window_builder.has_icon() {
@@ -154,6 +168,8 @@ fn on_window_event<R: Runtime>(window: &Window<R>, event: &WindowEvent) -> crate
   match event {
     WindowEvent::Resized(size) => window.emit_to_window(WINDOW_RESIZED_EVENT, size)?,
     WindowEvent::Moved(position) => window.emit_to_window(WINDOW_MOVED_EVENT, position)?,
+    WindowEvent::ResizeEnded(size) => window.emit_to_window(WINDOW_RESIZE_ENDED_EVENT, size)?,
+    WindowEvent::MoveEnded(position) => window.emit_to_window(WINDOW_MOVE_ENDED_EVENT, position)?,
     WindowEvent::CloseRequested { api } => {
       if window.has_js_listener(WINDOW_CLOSE_REQUESTED_EVENT) {
         api.prevent_close();
@@ -257,10 +273,45 @@ fn on_window_event<R: Runtime>(window: &Window<R>, event: &WindowEvent) -> crate
     WindowEvent::ThemeChanged(theme) => {
       window.emit_to_window(WINDOW_THEME_CHANGED, theme.to_string())?
     }
+    WindowEvent::SafeAreaInsetsChanged(insets) => {
+      // updates the CSS variables of the edge-to-edge webviews
+      let script = crate::window::safe_area::css_variables_script(insets);
+      for webview in window.webviews() {
+        let _ = webview.eval(&script);
+      }
+      window.emit_to_window(WINDOW_SAFE_AREA_INSETS_CHANGED_EVENT, insets)?
+    }
+    WindowEvent::Ime(event) => {
+      let payload = match event {
+        ImeEvent::Preedit { text, cursor } => ImePayload {
+          kind: "preedit",
+          text,
+          cursor: *cursor,
+        },
+        ImeEvent::Commit(text) => ImePayload {
+          kind: "commit",
+          text,
+          cursor: None,
+        },
+        _ => return Ok(()),
+      };
+      window.emit_to_window(WINDOW_IME_EVENT, payload)?
+    }
+    WindowEvent::FullscreenChanged(fullscreen) => {
+      window.emit_to_window(WINDOW_FULLSCREEN_CHANGED_EVENT, fullscreen)?
+    }
   }
   Ok(())
 }
 
+#[derive(Serialize)]
+struct ImePayload<'a> {
+  #[serde(rename = "type")]
+  kind: &'static str,
+  text: &'a str,
+  cursor: Option<(usize, usize)>,
+}
+
 #[derive(Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 struct ScaleFactorChanged {
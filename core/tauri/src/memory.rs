@@ -0,0 +1,278 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Memory usage diagnostics, see [`AppHandle::memory_stats`].
+
+use std::time::Duration;
+
+use serde::Serialize;
+
+use crate::{AppHandle, Emitter, Manager, Runtime};
+
+/// The event emitted to all targets with the [`MemoryStats`] at the interval set with [`AppHandle::set_memory_stats_interval`].
+pub const MEMORY_STATS_EVENT: &str = "tauri://memory-stats";
+
+/// A snapshot of the memory used by the app, returned by [`AppHandle::memory_stats`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct MemoryStats {
+  /// The resident memory of the app process in bytes.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Android / iOS:** Unsupported, always `None`.
+  pub process_rss: Option<u64>,
+  /// The resident memory of the webview processes started by the app in bytes,
+  /// i.e. the WebKitGTK web processes or the WebView2 browser and renderer processes.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **macOS / Android / iOS:** Unsupported, always `None`. The WKWebView processes are not children of the app process.
+  pub webview_rss: Option<u64>,
+  /// The size of the assets embedded in the binary in bytes.
+  pub assets: u64,
+  /// The number of open windows.
+  pub windows: usize,
+  /// The number of webviews in the open windows.
+  pub webviews: usize,
+}
+
+pub(crate) fn stats<R: Runtime>(app: &AppHandle<R>) -> MemoryStats {
+  let (process_rss, webview_rss) = match platform::process_memory() {
+    Some((process, children)) => (Some(process), children),
+    None => (None, None),
+  };
+  MemoryStats {
+    process_rss,
+    webview_rss,
+    assets: app
+      .manager
+      .assets
+      .iter()
+      .map(|(_, bytes)| bytes.len() as u64)
+      .sum(),
+    windows: app.manager.window.windows_lock().len(),
+    webviews: app.manager.webview.webviews_lock().len(),
+  }
+}
+
+/// Starts or stops emitting the [`MEMORY_STATS_EVENT`].
+pub(crate) fn set_interval<R: Runtime>(app: &AppHandle<R>, interval: Option<Duration>) {
+  let timer = interval.map(|interval| {
+    let app_ = app.clone();
+    app.clock().set_interval(
+      interval,
+      Box::new(move || {
+        if let Err(e) = app_.emit(MEMORY_STATS_EVENT, stats(&app_)) {
+          log::error!("failed to emit the memory stats: {e}");
+        }
+      }),
+    )
+  });
+  *app.manager.memory_stats_timer.lock().unwrap() = timer;
+}
+
+#[cfg(target_os = "linux")]
+mod platform {
+  use std::{collections::HashMap, fs};
+
+  /// Reads the `VmRSS` line of `/proc/<pid>/status`.
+  fn rss(pid: &str) -> Option<u64> {
+    let status = fs::read_to_string(format!("/proc/{pid}/status")).ok()?;
+    let line = status.lines().find(|line| line.starts_with("VmRSS:"))?;
+    let kb = line
+      .trim_start_matches("VmRSS:")
+      .trim()
+      .trim_end_matches("kB")
+      .trim()
+      .parse::<u64>()
+      .ok()?;
+    Some(kb * 1024)
+  }
+
+  /// Reads the parent of the process from `/proc/<pid>/stat`, the process name may contain spaces and parentheses.
+  fn parent(pid: &str) -> Option<u32> {
+    let stat = fs::read_to_string(format!("/proc/{pid}/stat")).ok()?;
+    stat[stat.rfind(')')? + 1..]
+      .split_whitespace()
+      .nth(1)?
+      .parse()
+      .ok()
+  }
+
+  pub fn process_memory() -> Option<(u64, Option<u64>)> {
+    let process = rss("self")?;
+
+    let mut children: HashMap<u32, Vec<u32>> = HashMap::new();
+    for entry in fs::read_dir("/proc").ok()?.flatten() {
+      let name = entry.file_name();
+      let Some(pid) = name.to_str().and_then(|name| name.parse::<u32>().ok()) else {
+        continue;
+      };
+      if let Some(parent) = parent(&pid.to_string()) {
+        children.entry(parent).or_default().push(pid);
+      }
+    }
+
+    let mut total = 0;
+    let mut pending = children
+      .get(&std::process::id())
+      .cloned()
+      .unwrap_or_default();
+    while let Some(pid) = pending.pop() {
+      total += rss(&pid.to_string()).unwrap_or_default();
+      if let Some(grandchildren) = children.get(&pid) {
+        pending.extend(grandchildren);
+      }
+    }
+    Some((process, Some(total)))
+  }
+}
+
+#[cfg(windows)]
+mod platform {
+  use std::collections::HashMap;
+
+  use windows::Win32::{
+    Foundation::{CloseHandle, HANDLE},
+    System::{
+      Diagnostics::ToolHelp::{
+        CreateToolhelp32Snapshot, Process32FirstW, Process32NextW, PROCESSENTRY32W,
+        TH32CS_SNAPPROCESS,
+      },
+      ProcessStatus::{GetProcessMemoryInfo, PROCESS_MEMORY_COUNTERS},
+      Threading::{
+        GetCurrentProcess, OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION, PROCESS_VM_READ,
+      },
+    },
+  };
+
+  fn working_set(process: HANDLE) -> Option<u64> {
+    let mut counters = PROCESS_MEMORY_COUNTERS::default();
+    unsafe {
+      GetProcessMemoryInfo(
+        process,
+        &mut counters,
+        std::mem::size_of::<PROCESS_MEMORY_COUNTERS>() as u32,
+      )
+      .ok()?;
+    }
+    Some(counters.WorkingSetSize as u64)
+  }
+
+  pub fn process_memory() -> Option<(u64, Option<u64>)> {
+    let process = working_set(unsafe { GetCurrentProcess() })?;
+
+    let mut children: HashMap<u32, Vec<u32>> = HashMap::new();
+    unsafe {
+      let snapshot = CreateToolhelp32Snapshot(TH32CS_SNAPPROCESS, 0).ok()?;
+      let mut entry = PROCESSENTRY32W {
+        dwSize: std::mem::size_of::<PROCESSENTRY32W>() as u32,
+        ..Default::default()
+      };
+      let mut next = Process32FirstW(snapshot, &mut entry);
+      while next.is_ok() {
+        children
+          .entry(entry.th32ParentProcessID)
+          .or_default()
+          .push(entry.th32ProcessID);
+        next = Process32NextW(snapshot, &mut entry);
+      }
+      let _ = CloseHandle(snapshot);
+    }
+
+    let mut total = 0;
+    let mut pending = children
+      .get(&std::process::id())
+      .cloned()
+      .unwrap_or_default();
+    while let Some(pid) = pending.pop() {
+      if let Ok(child) = unsafe {
+        OpenProcess(
+          PROCESS_QUERY_LIMITED_INFORMATION | PROCESS_VM_READ,
+          false,
+          pid,
+        )
+      } {
+        total += working_set(child).unwrap_or_default();
+        let _ = unsafe { CloseHandle(child) };
+      }
+      if let Some(grandchildren) = children.get(&pid) {
+        pending.extend(grandchildren);
+      }
+    }
+    Some((process, Some(total)))
+  }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+  pub fn process_memory() -> Option<(u64, Option<u64>)> {
+    let mut info = std::mem::MaybeUninit::<libc::mach_task_basic_info>::uninit();
+    let mut count = libc::MACH_TASK_BASIC_INFO_COUNT;
+    let result = unsafe {
+      #[allow(deprecated)]
+      libc::task_info(
+        libc::mach_task_self(),
+        libc::MACH_TASK_BASIC_INFO,
+        info.as_mut_ptr() as libc::task_info_t,
+        &mut count,
+      )
+    };
+    if result != libc::KERN_SUCCESS {
+      return None;
+    }
+    let info = unsafe { info.assume_init() };
+    Some((info.resident_size, None))
+  }
+}
+
+#[cfg(not(any(target_os = "linux", windows, target_os = "macos")))]
+mod platform {
+  pub fn process_memory() -> Option<(u64, Option<u64>)> {
+    None
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+  };
+
+  use super::MEMORY_STATS_EVENT;
+  use crate::{
+    test::{mock_app, mock_clock},
+    Listener,
+  };
+
+  #[test]
+  fn emits_stats_periodically() {
+    let app = mock_app();
+    let stats = app.handle().memory_stats();
+    assert_eq!(stats.windows, 0);
+    #[cfg(target_os = "linux")]
+    assert!(stats.process_rss.unwrap() > 0);
+
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let events_ = events.clone();
+    app.listen_any(MEMORY_STATS_EVENT, move |event| {
+      let stats: serde_json::Value = serde_json::from_str(event.payload()).unwrap();
+      events_.lock().unwrap().push(stats);
+    });
+
+    app
+      .handle()
+      .set_memory_stats_interval(Some(Duration::from_secs(10)));
+    mock_clock(&app).advance(Duration::from_secs(25));
+    app.handle().set_memory_stats_interval(None);
+    mock_clock(&app).advance(Duration::from_secs(25));
+
+    let events = events.lock().unwrap();
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0]["windows"], 0);
+  }
+}
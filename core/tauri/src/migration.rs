@@ -0,0 +1,375 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Versioned migrations of the app data, registered with [`Builder::migrations`](crate::Builder::migrations).
+//!
+//! The pending migrations run in version order when the app starts, before the windows are created and the setup hook runs.
+//! The migrations of versions greater than the [app version](crate::PackageInfo::version) are skipped until the app is updated to them.
+//! The applied versions are recorded in the [`MIGRATIONS_FILE_NAME`] file of the app data directory, so each migration runs once.
+//! The migrations also run on a fresh install, where [`MigrationContext::first_run`] is `true` and the data may not exist yet.
+//!
+//! ```rust,no_run
+//! use tauri::migration::{Migration, Migrations, Version};
+//!
+//! tauri::Builder::default().migrations(
+//!   Migrations::new()
+//!     .backup(true)
+//!     .add(Migration::new(Version::new(1, 1, 0), "rename the settings file", |ctx| {
+//!       let old = ctx.data_dir.join("settings.json");
+//!       if old.exists() && !ctx.dry_run {
+//!         std::fs::rename(old, ctx.data_dir.join("preferences.json"))?;
+//!       }
+//!       Ok(())
+//!     })),
+//! );
+//! ```
+
+use std::{
+  collections::BTreeSet,
+  path::{Path, PathBuf},
+};
+
+pub use semver::Version;
+use serde::{Deserialize, Serialize};
+
+use crate::{AppHandle, Manager, Runtime};
+
+/// The file of the app data directory recording the applied migrations.
+pub const MIGRATIONS_FILE_NAME: &str = ".migrations.json";
+
+type MigrationFn<R> =
+  dyn FnOnce(&MigrationContext<R>) -> Result<(), Box<dyn std::error::Error + Send + Sync>> + Send;
+
+/// The data available to a [`Migration`].
+#[non_exhaustive]
+pub struct MigrationContext<R: Runtime> {
+  /// The app handle.
+  pub app: AppHandle<R>,
+  /// The [app data directory](crate::path::PathResolver::app_data_dir).
+  pub data_dir: PathBuf,
+  /// Whether the migrations run in [dry-run](Migrations::dry_run) mode, where they must not change the data.
+  pub dry_run: bool,
+  /// Whether the app is launched for the first time, see [`LaunchInfo::first_run`](crate::launch::LaunchInfo::first_run).
+  pub first_run: bool,
+}
+
+/// A change of the app data introduced by a version of the app.
+pub struct Migration<R: Runtime> {
+  version: Version,
+  description: String,
+  run: Box<MigrationFn<R>>,
+}
+
+impl<R: Runtime> Migration<R> {
+  /// Creates a migration run once when a version greater or equal to `version` starts.
+  ///
+  /// It doesn't run while the app version is lower than `version`.
+  pub fn new<
+    D: Into<String>,
+    F: FnOnce(&MigrationContext<R>) -> Result<(), Box<dyn std::error::Error + Send + Sync>>
+      + Send
+      + 'static,
+  >(
+    version: Version,
+    description: D,
+    run: F,
+  ) -> Self {
+    Self {
+      version,
+      description: description.into(),
+      run: Box::new(run),
+    }
+  }
+}
+
+/// The migrations of the app, see the [module documentation](self).
+pub struct Migrations<R: Runtime> {
+  migrations: Vec<Migration<R>>,
+  dry_run: bool,
+  backup: bool,
+}
+
+impl<R: Runtime> Default for Migrations<R> {
+  fn default() -> Self {
+    Self {
+      migrations: Vec::new(),
+      dry_run: false,
+      backup: false,
+    }
+  }
+}
+
+impl<R: Runtime> Migrations<R> {
+  /// Creates an empty list of migrations.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Adds a migration.
+  #[must_use]
+  pub fn add(mut self, migration: Migration<R>) -> Self {
+    self.migrations.push(migration);
+    self
+  }
+
+  /// Runs the pending migrations with [`MigrationContext::dry_run`] set, without recording them, to check them.
+  #[must_use]
+  pub fn dry_run(mut self, dry_run: bool) -> Self {
+    self.dry_run = dry_run;
+    self
+  }
+
+  /// Copies the app data directory before running the pending migrations, and restores it if a migration fails.
+  ///
+  /// The backup is the `<app data directory>.backup-<version>` directory, named after the first pending migration.
+  /// It is removed once the migrations succeeded or the app data directory was restored,
+  /// and only kept if restoring the app data directory failed.
+  #[must_use]
+  pub fn backup(mut self, backup: bool) -> Self {
+    self.backup = backup;
+    self
+  }
+}
+
+/// The migrations run when the app started, available with `app.state::<MigrationReport>()`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct MigrationReport {
+  /// The versions of the migrations that ran, in order.
+  pub applied: Vec<Version>,
+  /// Whether the migrations ran in dry-run mode.
+  pub dry_run: bool,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct MigrationsRecord {
+  applied: BTreeSet<Version>,
+}
+
+fn read_record(path: &Path) -> crate::Result<MigrationsRecord> {
+  match std::fs::read(path) {
+    Ok(bytes) => Ok(serde_json::from_slice(&bytes)?),
+    Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(MigrationsRecord::default()),
+    Err(e) => Err(e.into()),
+  }
+}
+
+fn write_record(path: &Path, record: &MigrationsRecord) -> crate::Result<()> {
+  if let Some(parent) = path.parent() {
+    std::fs::create_dir_all(parent)?;
+  }
+  std::fs::write(path, serde_json::to_vec_pretty(record)?)?;
+  Ok(())
+}
+
+fn copy_dir(from: &Path, to: &Path) -> std::io::Result<()> {
+  std::fs::create_dir_all(to)?;
+  for entry in std::fs::read_dir(from)? {
+    let entry = entry?;
+    let target = to.join(entry.file_name());
+    if entry.file_type()?.is_dir() {
+      copy_dir(&entry.path(), &target)?;
+    } else {
+      std::fs::copy(entry.path(), target)?;
+    }
+  }
+  Ok(())
+}
+
+fn restore(backup: &Path, data_dir: &Path) -> std::io::Result<()> {
+  if data_dir.exists() {
+    std::fs::remove_dir_all(data_dir)?;
+  }
+  copy_dir(backup, data_dir)
+}
+
+fn remove_backup(backup: &Path) {
+  if let Err(e) = std::fs::remove_dir_all(backup) {
+    log::warn!(
+      "failed to remove the app data backup {}: {e}",
+      backup.display()
+    );
+  }
+}
+
+/// Runs the pending migrations of the data directory up to the app version, in version order.
+fn run_in<R: Runtime>(
+  app: &AppHandle<R>,
+  migrations: Migrations<R>,
+  data_dir: &Path,
+  app_version: &Version,
+  first_run: bool,
+) -> crate::Result<MigrationReport> {
+  let record_path = data_dir.join(MIGRATIONS_FILE_NAME);
+  let mut record = read_record(&record_path)?;
+  let mut pending = migrations
+    .migrations
+    .into_iter()
+    .filter(|migration| {
+      migration.version <= *app_version && !record.applied.contains(&migration.version)
+    })
+    .collect::<Vec<_>>();
+  pending.sort_by(|a, b| a.version.cmp(&b.version));
+
+  let mut report = MigrationReport {
+    dry_run: migrations.dry_run,
+    ..Default::default()
+  };
+  let Some(first) = pending.first() else {
+    return Ok(report);
+  };
+
+  let mut backup = None;
+  if migrations.backup && !migrations.dry_run && data_dir.exists() {
+    let mut name = data_dir.file_name().unwrap_or_default().to_os_string();
+    name.push(format!(".backup-{}", first.version));
+    let path = data_dir.with_file_name(name);
+    if path.exists() {
+      std::fs::remove_dir_all(&path)?;
+    }
+    copy_dir(data_dir, &path)?;
+    backup = Some(path);
+  }
+
+  let ctx = MigrationContext {
+    app: app.clone(),
+    data_dir: data_dir.to_path_buf(),
+    dry_run: migrations.dry_run,
+    first_run,
+  };
+  for migration in pending {
+    log::info!(
+      "running the {} migration: {}",
+      migration.version,
+      migration.description
+    );
+    if let Err(e) = (migration.run)(&ctx) {
+      if let Some(backup) = &backup {
+        match restore(backup, data_dir) {
+          Ok(()) => remove_backup(backup),
+          Err(e) => log::error!(
+            "failed to restore the app data backup, it is kept in {}: {e}",
+            backup.display()
+          ),
+        }
+      }
+      return Err(crate::Error::Migration(
+        migration.version.to_string(),
+        e.to_string(),
+      ));
+    }
+    report.applied.push(migration.version.clone());
+    if !migrations.dry_run {
+      // recorded after each migration so a failure without backup does not run the previous ones again
+      record.applied.insert(migration.version);
+      write_record(&record_path, &record)?;
+    }
+  }
+  if let Some(backup) = &backup {
+    remove_backup(backup);
+  }
+  Ok(report)
+}
+
+/// Runs the pending migrations and manages the [`MigrationReport`].
+pub(crate) fn run<R: Runtime>(app: &AppHandle<R>, migrations: Migrations<R>) -> crate::Result<()> {
+  let data_dir = app.path().app_data_dir()?;
+  let first_run = app.launch_info().first_run;
+  let app_version = app.package_info().version.clone();
+  let report = run_in(app, migrations, &data_dir, &app_version, first_run)?;
+  app.manage(report);
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use std::sync::{Arc, Mutex};
+
+  use super::{run_in, Migration, Migrations, Version};
+  use crate::test::MockRuntime;
+
+  #[test]
+  fn runs_pending_migrations() {
+    let app = crate::test::mock_app();
+    let data_dir = std::env::temp_dir().join(format!("tauri-migrations-{}", std::process::id()));
+    std::fs::create_dir_all(&data_dir).unwrap();
+    std::fs::write(data_dir.join("settings.json"), "{}").unwrap();
+
+    let ran = Arc::new(Mutex::new(Vec::new()));
+    let migrations = |ran: &Arc<Mutex<Vec<Version>>>| {
+      let migration = |version: Version| {
+        let ran = ran.clone();
+        Migration::<MockRuntime>::new(version.clone(), "test", move |ctx| {
+          assert!(!ctx.dry_run);
+          ran.lock().unwrap().push(version);
+          Ok(())
+        })
+      };
+      Migrations::new()
+        .backup(true)
+        .add(migration(Version::new(1, 2, 0)))
+        .add(migration(Version::new(1, 1, 0)))
+    };
+
+    let backup = |version: &str| {
+      let mut name = data_dir.file_name().unwrap().to_os_string();
+      name.push(format!(".backup-{version}"));
+      data_dir.with_file_name(name)
+    };
+
+    // the migrations of the next versions don't run yet
+    let report = run_in(
+      app.handle(),
+      migrations(&ran),
+      &data_dir,
+      &Version::new(1, 1, 5),
+      false,
+    )
+    .unwrap();
+    assert_eq!(*ran.lock().unwrap(), vec![Version::new(1, 1, 0)]);
+    assert_eq!(report.applied, *ran.lock().unwrap());
+    // the backup is removed once the migrations succeeded
+    assert!(!backup("1.1.0").exists());
+
+    // the applied migrations don't run again
+    let report = run_in(
+      app.handle(),
+      migrations(&ran),
+      &data_dir,
+      &Version::new(1, 2, 0),
+      false,
+    )
+    .unwrap();
+    assert_eq!(report.applied, vec![Version::new(1, 2, 0)]);
+    assert_eq!(
+      *ran.lock().unwrap(),
+      vec![Version::new(1, 1, 0), Version::new(1, 2, 0)]
+    );
+
+    // a failed migration restores the backup
+    let failing = Migrations::new()
+      .backup(true)
+      .add(Migration::<MockRuntime>::new(
+        Version::new(2, 0, 0),
+        "failing",
+        |ctx| {
+          std::fs::remove_file(ctx.data_dir.join("settings.json"))?;
+          Err("failed".into())
+        },
+      ));
+    assert!(run_in(
+      app.handle(),
+      failing,
+      &data_dir,
+      &Version::new(2, 0, 0),
+      false
+    )
+    .is_err());
+    assert!(data_dir.join("settings.json").exists());
+    assert!(!backup("2.0.0").exists());
+
+    std::fs::remove_dir_all(&data_dir).unwrap();
+  }
+}
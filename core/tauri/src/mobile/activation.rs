@@ -0,0 +1,238 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! The activations of the app by the operating system, i.e. the URLs opening the app and the content shared with it.
+//!
+//! The activations are delivered to the handlers registered on [`Activations`]
+//! and emitted to the webviews as the [`ACTIVATION_EVENT`] event.
+//!
+//! ```rust,no_run
+//! use tauri::mobile::activation::{Activation, ActivationExt};
+//!
+//! tauri::Builder::default()
+//!   .setup(|app| {
+//!     let activations = app.activations();
+//!     if let Some(activation) = activations.launch_activation() {
+//!       println!("launched with {activation:?}");
+//!     }
+//!     activations.on_activation(|_app, activation| match activation {
+//!       Activation::Url { url } => println!("opened {url}"),
+//!       Activation::Share(content) => println!("received {:?}", content.files),
+//!       _ => {}
+//!     });
+//!     Ok(())
+//!   });
+//! ```
+//!
+//! ## Platform-specific
+//!
+//! - **Android:** The URLs match the `bundle > android > intentFilters` configuration,
+//!   and the content is shared with the `bundle > android > shareTarget` configuration. The shared files are `content://` URIs.
+//! - **iOS:** The URLs are the custom schemes of the `CFBundleURLTypes` of the `Info.plist` file
+//!   and the universal links of the `bundle > iOS > associatedDomains` configuration. Receiving shared content is unsupported.
+//! - **macOS:** The URLs and files of [`RunEvent::Opened`](crate::RunEvent::Opened).
+//! - **Linux / Windows:** Unsupported.
+
+use std::sync::{Arc, Mutex};
+
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::{AppHandle, Emitter, Manager, Runtime, State};
+
+/// The event emitted to the webviews with an [`Activation`].
+pub const ACTIVATION_EVENT: &str = "tauri://activation";
+
+type ActivationHandler<R> = dyn Fn(&AppHandle<R>, &Activation) + Send + Sync;
+
+/// How the operating system activated the app.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+#[non_exhaustive]
+pub enum Activation {
+  /// The app was opened with a URL, e.g. a deep link, an app link or a universal link.
+  Url {
+    /// The opened URL.
+    url: Url,
+  },
+  /// Content was shared with the app.
+  Share(SharedContent),
+}
+
+/// The content shared with the app.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct SharedContent {
+  /// The shared text.
+  pub text: Option<String>,
+  /// The subject of the shared text.
+  pub subject: Option<String>,
+  /// The MIME type of the shared content.
+  pub mime_type: Option<String>,
+  /// The URIs of the shared files.
+  #[serde(default)]
+  pub files: Vec<Url>,
+}
+
+/// Delivers the activations of the app, see the [module documentation](self).
+///
+/// Get it with [`ActivationExt::activations`].
+pub struct Activations<R: Runtime> {
+  launch_activation: Mutex<Option<Activation>>,
+  handlers: Mutex<Vec<Arc<ActivationHandler<R>>>>,
+}
+
+impl<R: Runtime> Activations<R> {
+  /// The activation that launched the app, if any.
+  pub fn launch_activation(&self) -> Option<Activation> {
+    self.launch_activation.lock().unwrap().clone()
+  }
+
+  /// Registers a handler called with the activations received while the app is running.
+  pub fn on_activation<F: Fn(&AppHandle<R>, &Activation) + Send + Sync + 'static>(
+    &self,
+    handler: F,
+  ) {
+    self.handlers.lock().unwrap().push(Arc::new(handler));
+  }
+}
+
+/// Extensions to [`Manager`] for the activations.
+pub trait ActivationExt<R: Runtime> {
+  /// The [`Activations`] of the app.
+  fn activations(&self) -> State<'_, Activations<R>>;
+}
+
+impl<R: Runtime, M: Manager<R>> ActivationExt<R> for M {
+  fn activations(&self) -> State<'_, Activations<R>> {
+    self.state::<Activations<R>>()
+  }
+}
+
+fn deliver<R: Runtime>(app: &AppHandle<R>, activation: Activation) {
+  let handlers = app.activations().handlers.lock().unwrap().clone();
+  for handler in handlers {
+    handler(app, &activation);
+  }
+  let _ = app.emit(ACTIVATION_EVENT, activation);
+}
+
+enum Receiver {
+  /// The activations received before the app started, i.e. the launch activation.
+  Pending(Vec<Activation>),
+  Ready(Arc<dyn Fn(Activation) + Send + Sync>),
+}
+
+/// The receiver of the activations of the platforms, which don't have access to the app.
+static RECEIVER: Mutex<Receiver> = Mutex::new(Receiver::Pending(Vec::new()));
+
+#[cfg_attr(not(any(mobile, target_os = "macos")), allow(dead_code))]
+pub(crate) fn receive(activation: Activation) {
+  let receiver = match &mut *RECEIVER.lock().unwrap() {
+    Receiver::Pending(pending) => {
+      pending.push(activation);
+      return;
+    }
+    Receiver::Ready(receiver) => receiver.clone(),
+  };
+  receiver(activation);
+}
+
+#[cfg(mobile)]
+fn receive_payload(payload: &str) {
+  match serde_json::from_str(payload) {
+    Ok(activation) => receive(activation),
+    Err(e) => log::error!("failed to deserialize activation: {e}"),
+  }
+}
+
+/// Glue between Rust and the Kotlin code that delivers the intents of the main activity.
+#[cfg(target_os = "android")]
+pub fn handle_android_activation(env: &mut jni::JNIEnv<'_>, payload: jni::objects::JString<'_>) {
+  if let Ok(payload) = env.get_string(&payload) {
+    receive_payload(&String::from(payload));
+  }
+}
+
+#[cfg(target_os = "ios")]
+extern "C" fn on_ios_activation(payload: *const std::os::raw::c_char) {
+  let payload = unsafe { std::ffi::CStr::from_ptr(payload) }.to_string_lossy();
+  receive_payload(&payload);
+}
+
+/// Initializes the plugin.
+pub(crate) fn init<R: Runtime>() -> crate::plugin::TauriPlugin<R> {
+  crate::plugin::Builder::new("activation")
+    .setup(|app, _api| {
+      app.manage(Activations::<R> {
+        launch_activation: Default::default(),
+        handlers: Default::default(),
+      });
+
+      let receiver = {
+        let app = app.clone();
+        Receiver::Ready(Arc::new(move |activation| deliver(&app, activation)))
+      };
+      let pending = match std::mem::replace(&mut *RECEIVER.lock().unwrap(), receiver) {
+        Receiver::Pending(pending) => pending,
+        // the receiver of a previous app, e.g. in the tests
+        Receiver::Ready(_) => Vec::new(),
+      };
+      let mut pending = pending.into_iter();
+      *app.activations().launch_activation.lock().unwrap() = pending.next();
+      // the activations received while the app was starting
+      for activation in pending {
+        deliver(app, activation);
+      }
+
+      #[cfg(target_os = "ios")]
+      unsafe {
+        crate::ios::set_activation_callback(crate::ios::ActivationCallback(on_ios_activation));
+      }
+
+      Ok(())
+    })
+    .build()
+}
+
+#[cfg(test)]
+mod tests {
+  use std::sync::{Arc, Mutex};
+
+  use super::{deliver, Activation, ActivationExt, SharedContent};
+
+  #[test]
+  fn deliver_to_handlers() {
+    let app = crate::test::mock_app();
+    let activations = app.activations();
+
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let received_ = received.clone();
+    activations.on_activation(move |_app, activation| {
+      received_.lock().unwrap().push(activation.clone());
+    });
+
+    for payload in [
+      r#"{ "type": "url", "url": "myapp://open/1" }"#,
+      r#"{ "type": "share", "text": "hello", "mimeType": "image/png", "files": ["content://media/1"] }"#,
+    ] {
+      deliver(app.handle(), serde_json::from_str(payload).unwrap());
+    }
+    assert_eq!(
+      *received.lock().unwrap(),
+      vec![
+        Activation::Url {
+          url: "myapp://open/1".parse().unwrap()
+        },
+        Activation::Share(SharedContent {
+          text: Some("hello".into()),
+          subject: None,
+          mime_type: Some("image/png".into()),
+          files: vec!["content://media/1".parse().unwrap()],
+        }),
+      ]
+    );
+  }
+}
@@ -0,0 +1,360 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Background work scheduling, so jobs such as data synchronization run while the app is in the background.
+//!
+//! The tasks are scheduled with WorkManager on Android and BGTaskScheduler on iOS,
+//! and run in the app process with a timer on desktop.
+//!
+//! ```rust,no_run
+//! use std::time::Duration;
+//! use tauri::mobile::background::{BackgroundExt, BackgroundTask};
+//!
+//! tauri::Builder::default()
+//!   .setup(|app| {
+//!     app.background_tasks().register("sync", |_app| async move {
+//!       // synchronize the app data
+//!       Ok(())
+//!     });
+//!     app
+//!       .background_tasks()
+//!       .schedule(BackgroundTask::periodic("sync", Duration::from_secs(60 * 60)).requires_network(true))?;
+//!     Ok(())
+//!   });
+//! ```
+//!
+//! ## Platform-specific
+//!
+//! - **Android:** Periodic tasks run at most every 15 minutes. The tasks only run while the app process is alive,
+//!   a task triggered after the system stopped the app is retried once the app is opened again.
+//! - **iOS:** The handlers must be registered in the setup hook and their identifiers,
+//!   prefixed with the app identifier such as `com.tauri.dev.sync`, listed in the `BGTaskSchedulerPermittedIdentifiers` key of the `Info.plist` file.
+//!   The system decides when the tasks run, the delay and interval are only the earliest date. Requires iOS 13+.
+
+use std::{
+  collections::HashMap,
+  future::Future,
+  pin::Pin,
+  sync::{Arc, Mutex},
+  time::Duration,
+};
+
+use serde::Serialize;
+
+use crate::{
+  plugin::{Builder, TauriPlugin},
+  AppHandle, Manager, Runtime, State,
+};
+
+type HandlerFuture = Pin<Box<dyn Future<Output = crate::Result<()>> + Send>>;
+type Handler<R> = dyn Fn(AppHandle<R>) -> HandlerFuture + Send + Sync;
+
+/// A background task to schedule with [`BackgroundTasks::schedule`].
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackgroundTask {
+  id: String,
+  /// The delay or interval in milliseconds.
+  interval: u64,
+  periodic: bool,
+  requires_network: bool,
+}
+
+impl BackgroundTask {
+  /// A task running once after the given delay.
+  pub fn once(id: impl Into<String>, delay: Duration) -> Self {
+    Self {
+      id: id.into(),
+      interval: delay.as_millis() as u64,
+      periodic: false,
+      requires_network: false,
+    }
+  }
+
+  /// A task running repeatedly with the given interval.
+  pub fn periodic(id: impl Into<String>, interval: Duration) -> Self {
+    Self {
+      id: id.into(),
+      interval: interval.as_millis() as u64,
+      periodic: true,
+      requires_network: false,
+    }
+  }
+
+  /// Only runs the task when the device is connected to a network. Ignored on desktop.
+  #[must_use]
+  pub fn requires_network(mut self, requires_network: bool) -> Self {
+    self.requires_network = requires_network;
+    self
+  }
+
+  /// The task identifier.
+  pub fn id(&self) -> &str {
+    &self.id
+  }
+}
+
+/// Registers the background task handlers and schedules the tasks, see the [module documentation](self).
+///
+/// Get it with [`BackgroundExt::background_tasks`].
+pub struct BackgroundTasks<R: Runtime> {
+  #[cfg_attr(target_os = "android", allow(dead_code))]
+  app: AppHandle<R>,
+  handlers: Mutex<HashMap<String, Arc<Handler<R>>>>,
+  /// The timers of the scheduled tasks, canceled when dropped.
+  #[cfg(desktop)]
+  scheduled: Mutex<HashMap<String, crate::time::Timer>>,
+  #[cfg(target_os = "android")]
+  plugin: crate::plugin::PluginHandle<R>,
+}
+
+impl<R: Runtime> BackgroundTasks<R> {
+  /// Registers the handler of the task with the given identifier, replacing the previous one.
+  pub fn register<F, Fut>(&self, id: impl Into<String>, handler: F)
+  where
+    F: Fn(AppHandle<R>) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = crate::Result<()>> + Send + 'static,
+  {
+    let id = id.into();
+    #[cfg(target_os = "ios")]
+    unsafe {
+      crate::ios::register_background_task(
+        &task_identifier(&self.app, &id).as_str().into(),
+        crate::ios::BackgroundTaskCallback(ios::run),
+      );
+    }
+    self.handlers.lock().unwrap().insert(
+      id,
+      Arc::new(move |app| -> HandlerFuture { Box::pin(handler(app)) }),
+    );
+  }
+
+  /// Schedules a task, replacing the scheduled task with the same identifier.
+  ///
+  /// Fails if no handler is registered for the task.
+  pub fn schedule(&self, task: BackgroundTask) -> crate::Result<()> {
+    if !self.handlers.lock().unwrap().contains_key(&task.id) {
+      return Err(crate::Error::UnregisteredBackgroundTask(task.id));
+    }
+
+    #[cfg(desktop)]
+    {
+      let app = self.app.clone();
+      let id = task.id.clone();
+      let interval = Duration::from_millis(task.interval);
+      // the timer is canceled when the task is canceled or rescheduled
+      let timer = if task.periodic {
+        self.app.clock().set_interval(
+          interval,
+          Box::new(move || {
+            run(&app, &id);
+          }),
+        )
+      } else {
+        self.app.clock().set_timeout(
+          interval,
+          Box::new(move || {
+            run(&app, &id);
+          }),
+        )
+      };
+      self.scheduled.lock().unwrap().insert(task.id, timer);
+    }
+
+    #[cfg(target_os = "android")]
+    self
+      .plugin
+      .run_mobile_plugin::<()>("schedule", &task)
+      .map_err(|e| crate::Error::BackgroundTask(task.id.clone(), e.to_string()))?;
+
+    #[cfg(target_os = "ios")]
+    {
+      let scheduled = unsafe {
+        crate::ios::schedule_background_task(
+          &task_identifier(&self.app, &task.id).as_str().into(),
+          task.interval as f64 / 1000.0,
+          task.periodic,
+          task.requires_network,
+        )
+      };
+      if !scheduled {
+        return Err(crate::Error::BackgroundTask(
+          task.id,
+          "the task could not be submitted to BGTaskScheduler".into(),
+        ));
+      }
+    }
+
+    Ok(())
+  }
+
+  /// Cancels the scheduled task with the given identifier.
+  pub fn cancel(&self, id: &str) -> crate::Result<()> {
+    #[cfg(desktop)]
+    self.scheduled.lock().unwrap().remove(id);
+
+    #[cfg(target_os = "android")]
+    self
+      .plugin
+      .run_mobile_plugin::<()>("cancel", serde_json::json!({ "id": id }))
+      .map_err(|e| crate::Error::BackgroundTask(id.into(), e.to_string()))?;
+
+    #[cfg(target_os = "ios")]
+    unsafe {
+      crate::ios::cancel_background_task(&task_identifier(&self.app, id).as_str().into());
+    }
+
+    Ok(())
+  }
+}
+
+/// Runs the handler of the task, returning whether it succeeded.
+fn run<R: Runtime>(app: &AppHandle<R>, id: &str) -> bool {
+  let handler = app
+    .background_tasks()
+    .handlers
+    .lock()
+    .unwrap()
+    .get(id)
+    .cloned();
+  let Some(handler) = handler else {
+    log::error!("background task `{id}` has no registered handler");
+    return false;
+  };
+  match crate::async_runtime::block_on(handler(app.clone())) {
+    Ok(()) => true,
+    Err(e) => {
+      log::error!("background task `{id}` failed: {e}");
+      false
+    }
+  }
+}
+
+/// Extensions to [`Manager`] for the background tasks.
+pub trait BackgroundExt<R: Runtime> {
+  /// The [`BackgroundTasks`] of the app.
+  fn background_tasks(&self) -> State<'_, BackgroundTasks<R>>;
+}
+
+impl<R: Runtime, M: Manager<R>> BackgroundExt<R> for M {
+  fn background_tasks(&self) -> State<'_, BackgroundTasks<R>> {
+    self.state::<BackgroundTasks<R>>()
+  }
+}
+
+/// The runner of the tasks triggered by the mobile schedulers, which don't have access to the app.
+#[cfg(mobile)]
+static RUNNER: std::sync::OnceLock<Box<dyn Fn(&str) -> bool + Send + Sync>> =
+  std::sync::OnceLock::new();
+
+/// Glue between Rust and the Kotlin worker that runs a background task.
+#[cfg(target_os = "android")]
+pub fn handle_android_background_task(
+  env: &mut jni::JNIEnv<'_>,
+  id: jni::objects::JString<'_>,
+) -> bool {
+  let Ok(id) = env.get_string(&id) else {
+    return false;
+  };
+  let id = String::from(id);
+  RUNNER.get().map_or(false, |run| run(&id))
+}
+
+/// The BGTaskScheduler identifier of a task, which must be prefixed with the app identifier.
+#[cfg(target_os = "ios")]
+fn task_identifier<R: Runtime>(app: &AppHandle<R>, id: &str) -> String {
+  format!("{}.{id}", app.config().identifier)
+}
+
+#[cfg(target_os = "ios")]
+mod ios {
+  use std::{ffi::CStr, os::raw::c_char};
+
+  pub extern "C" fn run(identifier: *const c_char) -> bool {
+    let identifier = unsafe { CStr::from_ptr(identifier) }.to_string_lossy();
+    super::RUNNER
+      .get()
+      .map_or(false, |run| run(identifier.as_ref()))
+  }
+}
+
+/// Initializes the plugin.
+pub(crate) fn init<R: Runtime>() -> TauriPlugin<R> {
+  Builder::new("background")
+    .setup(|app, _api| {
+      #[cfg(mobile)]
+      {
+        let app = app.clone();
+        let _ = RUNNER.set(Box::new(move |id| {
+          // the iOS identifiers are prefixed with the app identifier
+          #[cfg(target_os = "ios")]
+          let id = id
+            .strip_prefix(&format!("{}.", app.config().identifier))
+            .unwrap_or(id);
+          run(&app, id)
+        }));
+      }
+
+      app.manage(BackgroundTasks {
+        app: app.clone(),
+        handlers: Default::default(),
+        #[cfg(desktop)]
+        scheduled: Default::default(),
+        #[cfg(target_os = "android")]
+        plugin: _api.register_android_plugin("app.tauri", "BackgroundTaskPlugin")?,
+      });
+      Ok(())
+    })
+    .build()
+}
+
+#[cfg(test)]
+mod tests {
+  use std::{
+    sync::{
+      atomic::{AtomicUsize, Ordering},
+      Arc,
+    },
+    time::Duration,
+  };
+
+  use super::{BackgroundExt, BackgroundTask};
+
+  #[test]
+  fn schedule_registered_tasks() {
+    let app = crate::test::mock_app();
+    let clock = crate::test::mock_clock(&app);
+    let tasks = app.background_tasks();
+
+    assert!(matches!(
+      tasks.schedule(BackgroundTask::once("sync", Duration::ZERO)),
+      Err(crate::Error::UnregisteredBackgroundTask(id)) if id == "sync"
+    ));
+
+    let runs = Arc::new(AtomicUsize::new(0));
+    let runs_ = runs.clone();
+    tasks.register("sync", move |_app| {
+      runs_.fetch_add(1, Ordering::Relaxed);
+      async { Ok(()) }
+    });
+    tasks
+      .schedule(BackgroundTask::periodic("sync", Duration::from_secs(60)))
+      .unwrap();
+    clock.advance(Duration::from_secs(59));
+    assert_eq!(runs.load(Ordering::Relaxed), 0);
+    clock.advance(Duration::from_secs(61));
+    assert_eq!(runs.load(Ordering::Relaxed), 2);
+
+    tasks.cancel("sync").unwrap();
+    clock.advance(Duration::from_secs(600));
+    assert_eq!(runs.load(Ordering::Relaxed), 2);
+
+    tasks
+      .schedule(BackgroundTask::once("sync", Duration::from_secs(10)))
+      .unwrap();
+    clock.advance(Duration::from_secs(600));
+    assert_eq!(runs.load(Ordering::Relaxed), 3);
+    assert_eq!(clock.pending_timers(), 0);
+  }
+}
@@ -0,0 +1,10 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Mobile APIs, with a fallback implementation on desktop.
+
+pub mod activation;
+pub mod background;
+pub mod permissions;
+pub mod push;
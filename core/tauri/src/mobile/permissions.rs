@@ -0,0 +1,231 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! The operating system permissions the user grants to the app at runtime.
+//!
+//! The permissions required by the commands of a capability are declared in its `osPermissions` list,
+//! so the CLI can add them to the `AndroidManifest.xml` file and check the usage descriptions of the iOS `Info.plist` file.
+//!
+//! ```rust,no_run
+//! use tauri::mobile::permissions::{OsPermission, PermissionState, PermissionsExt};
+//!
+//! #[tauri::command]
+//! fn take_picture<R: tauri::Runtime>(app: tauri::AppHandle<R>) -> Result<(), String> {
+//!   let states = app
+//!     .request_permissions(&[OsPermission::Camera])
+//!     .map_err(|e| e.to_string())?;
+//!   if states[&OsPermission::Camera] != PermissionState::Granted {
+//!     return Err("the camera permission was denied".into());
+//!   }
+//!   // ...
+//!   Ok(())
+//! }
+//! ```
+//!
+//! ## Platform-specific
+//!
+//! - **Android:** The permissions must be declared in the `AndroidManifest.xml` file.
+//!   [`OsPermission::Notifications`] is granted without prompting before Android 13 unless the user disabled the notifications.
+//! - **iOS:** Requires the `os-permissions` Cargo feature, which links the frameworks of the permissions:
+//!   App Store Connect rejects the apps linking them without the usage descriptions.
+//!   The `Info.plist` file must have the [`OsPermission::ios_usage_description_keys`] of the requested permissions.
+//! - **Linux / Windows / macOS:** All permissions are [`PermissionState::Granted`].
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Manager, Runtime};
+
+pub use crate::utils::acl::capability::OsPermission;
+
+/// The state of an [`OsPermission`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum PermissionState {
+  /// The user granted the permission.
+  Granted,
+  /// The user denied the permission, or the platform doesn't allow requesting it.
+  Denied,
+  /// The permission must be requested.
+  Prompt,
+  /// The permission must be requested, and the app should explain why it needs it first.
+  ///
+  /// Only reported on Android after the user denied the permission once.
+  PromptWithRationale,
+}
+
+/// The permission states reported by the native plugin, keyed by permission name.
+#[cfg_attr(desktop, allow(dead_code))]
+type NativeStates = HashMap<String, PermissionState>;
+
+/// Picks the states of the given permissions, treating the missing ones as [`PermissionState::Prompt`].
+#[cfg_attr(desktop, allow(dead_code))]
+fn select(
+  states: &NativeStates,
+  permissions: &[OsPermission],
+) -> HashMap<OsPermission, PermissionState> {
+  permissions
+    .iter()
+    .map(|p| {
+      (
+        *p,
+        states
+          .get(p.as_str())
+          .copied()
+          .unwrap_or(PermissionState::Prompt),
+      )
+    })
+    .collect()
+}
+
+/// Extensions to [`Manager`] to query and request the operating system permissions, see the [module documentation](self).
+pub trait PermissionsExt<R: Runtime> {
+  /// The current state of the given permissions.
+  fn check_permissions(
+    &self,
+    permissions: &[OsPermission],
+  ) -> crate::Result<HashMap<OsPermission, PermissionState>>;
+
+  /// Prompts the user for the given permissions that were not granted yet, and returns their resulting state.
+  ///
+  /// Blocks until the user answers the prompt, so it must not be called on the main thread.
+  fn request_permissions(
+    &self,
+    permissions: &[OsPermission],
+  ) -> crate::Result<HashMap<OsPermission, PermissionState>>;
+}
+
+#[cfg(desktop)]
+impl<R: Runtime, M: Manager<R>> PermissionsExt<R> for M {
+  fn check_permissions(
+    &self,
+    permissions: &[OsPermission],
+  ) -> crate::Result<HashMap<OsPermission, PermissionState>> {
+    Ok(
+      permissions
+        .iter()
+        .map(|p| (*p, PermissionState::Granted))
+        .collect(),
+    )
+  }
+
+  fn request_permissions(
+    &self,
+    permissions: &[OsPermission],
+  ) -> crate::Result<HashMap<OsPermission, PermissionState>> {
+    self.check_permissions(permissions)
+  }
+}
+
+#[cfg(mobile)]
+impl<R: Runtime, M: Manager<R>> PermissionsExt<R> for M {
+  fn check_permissions(
+    &self,
+    permissions: &[OsPermission],
+  ) -> crate::Result<HashMap<OsPermission, PermissionState>> {
+    let states = mobile::handle(self)?
+      .0
+      .run_mobile_plugin::<NativeStates>("checkPermissions", ())?;
+    Ok(select(&states, permissions))
+  }
+
+  fn request_permissions(
+    &self,
+    permissions: &[OsPermission],
+  ) -> crate::Result<HashMap<OsPermission, PermissionState>> {
+    if permissions.is_empty() {
+      return Ok(HashMap::new());
+    }
+    let states = mobile::handle(self)?.0.run_mobile_plugin::<NativeStates>(
+      "requestPermissions",
+      serde_json::json!({
+        "permissions": permissions.iter().map(OsPermission::as_str).collect::<Vec<_>>()
+      }),
+    )?;
+    Ok(select(&states, permissions))
+  }
+}
+
+#[cfg(mobile)]
+mod mobile {
+  use crate::{plugin::PluginHandle, Manager, Runtime, State};
+
+  #[cfg(all(target_os = "ios", feature = "os-permissions"))]
+  crate::swift_rs::swift!(fn init_plugin_permissions() -> *const std::ffi::c_void);
+
+  /// The native plugin that checks and requests the permissions.
+  pub(super) struct Permissions<R: Runtime>(pub(super) PluginHandle<R>);
+
+  #[cfg(any(target_os = "android", feature = "os-permissions"))]
+  pub(super) fn register<R: Runtime, C: serde::de::DeserializeOwned>(
+    api: &crate::plugin::PluginApi<R, C>,
+  ) -> crate::Result<Permissions<R>> {
+    #[cfg(target_os = "android")]
+    let handle = api.register_android_plugin("app.tauri", "PermissionsPlugin")?;
+    #[cfg(target_os = "ios")]
+    let handle = api.register_ios_plugin(init_plugin_permissions)?;
+    Ok(Permissions(handle))
+  }
+
+  /// The handle of the native plugin, which is not registered on iOS without the `os-permissions` Cargo feature.
+  pub(super) fn handle<R: Runtime, M: Manager<R>>(
+    manager: &M,
+  ) -> crate::Result<State<'_, Permissions<R>>> {
+    manager.try_state::<Permissions<R>>().ok_or_else(|| {
+      crate::Error::PluginInitialization(
+        "permissions".into(),
+        "the `os-permissions` Cargo feature is required on iOS".into(),
+      )
+    })
+  }
+}
+
+/// Initializes the plugin.
+pub(crate) fn init<R: Runtime>() -> crate::plugin::TauriPlugin<R> {
+  crate::plugin::Builder::new("permissions")
+    .setup(|_app, _api| {
+      #[cfg(all(mobile, any(target_os = "android", feature = "os-permissions")))]
+      _app.manage(mobile::register(&_api)?);
+      Ok(())
+    })
+    .build()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{select, NativeStates, OsPermission, PermissionState, PermissionsExt};
+
+  #[test]
+  fn select_states() {
+    let states: NativeStates =
+      serde_json::from_str(r#"{ "camera": "granted", "microphone": "prompt-with-rationale" }"#)
+        .unwrap();
+    let selected = select(
+      &states,
+      &[
+        OsPermission::Camera,
+        OsPermission::Microphone,
+        OsPermission::Contacts,
+      ],
+    );
+    assert_eq!(selected.len(), 3);
+    assert_eq!(selected[&OsPermission::Camera], PermissionState::Granted);
+    assert_eq!(
+      selected[&OsPermission::Microphone],
+      PermissionState::PromptWithRationale
+    );
+    assert_eq!(selected[&OsPermission::Contacts], PermissionState::Prompt);
+  }
+
+  #[test]
+  fn desktop_grants_all() {
+    let app = crate::test::mock_app();
+    let states = app
+      .request_permissions(&[OsPermission::Camera, OsPermission::Location])
+      .unwrap();
+    assert!(states.values().all(|s| *s == PermissionState::Granted));
+  }
+}
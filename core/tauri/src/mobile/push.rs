@@ -0,0 +1,337 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Push notifications, with Firebase Cloud Messaging on Android and the Apple Push Notification service on iOS.
+//!
+//! The device token and the received notifications are delivered to the handlers registered on [`PushNotifications`]
+//! and emitted to the webviews as the [`TOKEN_EVENT`] and [`NOTIFICATION_EVENT`] events.
+//!
+//! ```rust,no_run
+//! use tauri::mobile::push::PushExt;
+//!
+//! tauri::Builder::default()
+//!   .setup(|app| {
+//!     let push = app.push_notifications();
+//!     push.on_token(|_app, token| {
+//!       // send the token to the server
+//!       println!("device token: {token}");
+//!     });
+//!     push.on_notification(|_app, notification| {
+//!       println!("received {:?}", notification.title);
+//!     });
+//!     if let Some(notification) = push.launch_notification() {
+//!       println!("opened from {:?}", notification.data);
+//!     }
+//!     push.register()?;
+//!     Ok(())
+//!   });
+//! ```
+//!
+//! ## Platform-specific
+//!
+//! - **Android:** The app must depend on `com.google.firebase:firebase-messaging` and apply the Google services Gradle plugin
+//!   with its `google-services.json` file. Requires the `POST_NOTIFICATIONS` permission on Android 13+ to display the notifications.
+//!   The opened notifications are only delivered when the activity was started by the app itself, i.e. by the notification,
+//!   on Android 5.1+.
+//! - **iOS:** The app must have the Push Notifications capability. The token is the hex-encoded APNs device token.
+//! - **Linux / Windows / macOS:** Unsupported, [`PushNotifications::register`] returns an error.
+
+use std::sync::{Arc, Mutex};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{AppHandle, Emitter, Manager, Runtime, State};
+
+/// The event emitted to the webviews with the device token.
+pub const TOKEN_EVENT: &str = "tauri://push-token";
+/// The event emitted to the webviews with a received [`PushNotification`].
+pub const NOTIFICATION_EVENT: &str = "tauri://push-notification";
+
+type TokenHandler<R> = dyn Fn(&AppHandle<R>, &str) + Send + Sync;
+type NotificationHandler<R> = dyn Fn(&AppHandle<R>, &PushNotification) + Send + Sync;
+
+/// A received push notification.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct PushNotification {
+  /// The notification title.
+  pub title: Option<String>,
+  /// The notification body.
+  pub body: Option<String>,
+  /// The custom data of the notification.
+  #[serde(default)]
+  pub data: serde_json::Map<String, serde_json::Value>,
+  /// Whether the user opened the app by tapping the notification,
+  /// or the notification was received while the app was in the foreground.
+  #[serde(default)]
+  pub opened: bool,
+}
+
+#[cfg_attr(desktop, allow(dead_code))]
+/// A notification delivered by the platform.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Delivery {
+  #[serde(flatten)]
+  notification: PushNotification,
+  /// Whether the app was launched by the notification.
+  #[serde(default)]
+  launch: bool,
+}
+
+/// Registers the device for push notifications and delivers them, see the [module documentation](self).
+///
+/// Get it with [`PushExt::push_notifications`].
+pub struct PushNotifications<R: Runtime> {
+  token: Mutex<Option<String>>,
+  launch_notification: Mutex<Option<PushNotification>>,
+  token_handlers: Mutex<Vec<Arc<TokenHandler<R>>>>,
+  notification_handlers: Mutex<Vec<Arc<NotificationHandler<R>>>>,
+  #[cfg(target_os = "android")]
+  plugin: crate::plugin::PluginHandle<R>,
+}
+
+impl<R: Runtime> PushNotifications<R> {
+  /// Requests the permission to display notifications if needed, and registers the device.
+  ///
+  /// The token is delivered to the [`Self::on_token`] handlers, again whenever it changes.
+  #[allow(clippy::needless_return)]
+  pub fn register(&self) -> crate::Result<()> {
+    #[cfg(desktop)]
+    return Err(crate::Error::PushNotifications(
+      "push notifications are not supported on desktop".into(),
+    ));
+
+    #[cfg(target_os = "android")]
+    return self
+      .plugin
+      .run_mobile_plugin::<()>("register", ())
+      .map_err(|e| crate::Error::PushNotifications(e.to_string()));
+
+    #[cfg(target_os = "ios")]
+    {
+      unsafe { crate::ios::register_push_notifications() };
+      Ok(())
+    }
+  }
+
+  /// The device token, if the device is registered.
+  pub fn token(&self) -> Option<String> {
+    self.token.lock().unwrap().clone()
+  }
+
+  /// The notification the user tapped to launch the app, if any.
+  pub fn launch_notification(&self) -> Option<PushNotification> {
+    self.launch_notification.lock().unwrap().clone()
+  }
+
+  /// Registers a handler called with the device token.
+  pub fn on_token<F: Fn(&AppHandle<R>, &str) + Send + Sync + 'static>(&self, handler: F) {
+    self.token_handlers.lock().unwrap().push(Arc::new(handler));
+  }
+
+  /// Registers a handler called with the received notifications.
+  pub fn on_notification<F: Fn(&AppHandle<R>, &PushNotification) + Send + Sync + 'static>(
+    &self,
+    handler: F,
+  ) {
+    self
+      .notification_handlers
+      .lock()
+      .unwrap()
+      .push(Arc::new(handler));
+  }
+}
+
+/// Extensions to [`Manager`] for the push notifications.
+pub trait PushExt<R: Runtime> {
+  /// The [`PushNotifications`] of the app.
+  fn push_notifications(&self) -> State<'_, PushNotifications<R>>;
+}
+
+impl<R: Runtime, M: Manager<R>> PushExt<R> for M {
+  fn push_notifications(&self) -> State<'_, PushNotifications<R>> {
+    self.state::<PushNotifications<R>>()
+  }
+}
+
+#[cfg_attr(desktop, allow(dead_code))]
+fn deliver_token<R: Runtime>(app: &AppHandle<R>, token: String) {
+  let push = app.push_notifications();
+  push.token.lock().unwrap().replace(token.clone());
+  let handlers = push.token_handlers.lock().unwrap().clone();
+  for handler in handlers {
+    handler(app, &token);
+  }
+  let _ = app.emit(TOKEN_EVENT, token);
+}
+
+#[cfg_attr(desktop, allow(dead_code))]
+fn deliver_notification<R: Runtime>(app: &AppHandle<R>, payload: &str) {
+  let Delivery {
+    notification,
+    launch,
+  } = match serde_json::from_str(payload) {
+    Ok(delivery) => delivery,
+    Err(e) => {
+      log::error!("failed to deserialize push notification: {e}");
+      return;
+    }
+  };
+
+  let push = app.push_notifications();
+  if launch {
+    push
+      .launch_notification
+      .lock()
+      .unwrap()
+      .replace(notification.clone());
+  }
+  let handlers = push.notification_handlers.lock().unwrap().clone();
+  for handler in handlers {
+    handler(app, &notification);
+  }
+  let _ = app.emit(NOTIFICATION_EVENT, notification);
+}
+
+#[cfg_attr(desktop, allow(dead_code))]
+enum Delivered<'a> {
+  Token(String),
+  Notification(&'a str),
+}
+
+/// The receiver of the tokens and notifications of the mobile platforms, which don't have access to the app.
+#[cfg(mobile)]
+static RECEIVER: std::sync::OnceLock<Box<dyn Fn(Delivered<'_>) + Send + Sync>> =
+  std::sync::OnceLock::new();
+
+#[cfg(mobile)]
+fn receive(delivered: Delivered<'_>) {
+  match RECEIVER.get() {
+    Some(receive) => receive(delivered),
+    None => log::warn!("push notification delivered before the app started"),
+  }
+}
+
+/// Glue between Rust and the Kotlin push notifications that delivers a token.
+#[cfg(target_os = "android")]
+pub fn handle_android_push_token(env: &mut jni::JNIEnv<'_>, token: jni::objects::JString<'_>) {
+  if let Ok(token) = env.get_string(&token) {
+    receive(Delivered::Token(token.into()));
+  }
+}
+
+/// Glue between Rust and the Kotlin push notifications that delivers a notification.
+#[cfg(target_os = "android")]
+pub fn handle_android_push_notification(
+  env: &mut jni::JNIEnv<'_>,
+  payload: jni::objects::JString<'_>,
+) {
+  if let Ok(payload) = env.get_string(&payload) {
+    receive(Delivered::Notification(&String::from(payload)));
+  }
+}
+
+#[cfg(target_os = "ios")]
+mod ios {
+  use std::{ffi::CStr, os::raw::c_char};
+
+  use super::{receive, Delivered};
+
+  pub extern "C" fn on_token(token: *const c_char) {
+    let token = unsafe { CStr::from_ptr(token) }.to_string_lossy();
+    receive(Delivered::Token(token.into_owned()));
+  }
+
+  pub extern "C" fn on_notification(payload: *const c_char) {
+    let payload = unsafe { CStr::from_ptr(payload) }.to_string_lossy();
+    receive(Delivered::Notification(&payload));
+  }
+}
+
+#[cfg_attr(desktop, allow(dead_code))]
+fn deliver<R: Runtime>(app: &AppHandle<R>, delivered: Delivered<'_>) {
+  match delivered {
+    Delivered::Token(token) => deliver_token(app, token),
+    Delivered::Notification(payload) => deliver_notification(app, payload),
+  }
+}
+
+/// Initializes the plugin.
+pub(crate) fn init<R: Runtime>() -> crate::plugin::TauriPlugin<R> {
+  crate::plugin::Builder::new("push")
+    .setup(|app, _api| {
+      app.manage(PushNotifications {
+        token: Default::default(),
+        launch_notification: Default::default(),
+        token_handlers: Default::default(),
+        notification_handlers: Default::default(),
+        #[cfg(target_os = "android")]
+        plugin: _api.register_android_plugin("app.tauri.push", "PushNotificationPlugin")?,
+      });
+
+      #[cfg(mobile)]
+      {
+        let app = app.clone();
+        let _ = RECEIVER.set(Box::new(move |delivered| deliver(&app, delivered)));
+      }
+
+      // the launch notification is delivered once the callbacks are set
+      #[cfg(target_os = "android")]
+      app
+        .push_notifications()
+        .plugin
+        .run_mobile_plugin::<()>("deliverLaunchNotification", ())?;
+      #[cfg(target_os = "ios")]
+      unsafe {
+        crate::ios::set_push_notification_callbacks(
+          crate::ios::PushCallback(ios::on_token),
+          crate::ios::PushCallback(ios::on_notification),
+        );
+      }
+
+      Ok(())
+    })
+    .build()
+}
+
+#[cfg(test)]
+mod tests {
+  use std::sync::{Arc, Mutex};
+
+  use super::{deliver, Delivered, PushExt};
+
+  #[test]
+  fn deliver_to_handlers() {
+    let app = crate::test::mock_app();
+    let push = app.push_notifications();
+    assert!(push.register().is_err());
+
+    let titles = Arc::new(Mutex::new(Vec::new()));
+    let titles_ = titles.clone();
+    push.on_notification(move |_app, notification| {
+      titles_.lock().unwrap().push(notification.title.clone());
+    });
+
+    deliver(app.handle(), Delivered::Token("token".into()));
+    assert_eq!(push.token().as_deref(), Some("token"));
+
+    deliver(
+      app.handle(),
+      Delivered::Notification(r#"{ "title": "Sync", "data": { "id": 1 }, "launch": true }"#),
+    );
+    deliver(
+      app.handle(),
+      Delivered::Notification(r#"{ "title": "Update", "opened": true }"#),
+    );
+    assert_eq!(
+      *titles.lock().unwrap(),
+      vec![Some("Sync".to_string()), Some("Update".to_string())]
+    );
+    let launch = push.launch_notification().unwrap();
+    assert_eq!(launch.title.as_deref(), Some("Sync"));
+    assert_eq!(launch.data["id"], 1);
+  }
+}
@@ -0,0 +1,57 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Networking helpers for the local servers of the app, e.g. the [sidecars](crate::sidecar).
+
+use std::{
+  collections::BTreeSet,
+  io,
+  net::{Ipv4Addr, TcpListener},
+  sync::Mutex,
+};
+
+/// The ports returned by [`reserve_port`].
+static RESERVED_PORTS: Mutex<BTreeSet<u16>> = Mutex::new(BTreeSet::new());
+
+/// How many ports are picked by the system before giving up.
+const MAX_ATTEMPTS: usize = 16;
+
+/// Returns a free TCP port of the loopback interface, never returned twice by the app.
+///
+/// The port is picked by the system, so it is free when returned, but another process can use it
+/// before the local server listens on it.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// let port = tauri::net::reserve_port().expect("no free port");
+/// println!("listening on 127.0.0.1:{port}");
+/// ```
+pub fn reserve_port() -> io::Result<u16> {
+  let mut reserved = RESERVED_PORTS.lock().unwrap();
+  for _ in 0..MAX_ATTEMPTS {
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))?;
+    let port = listener.local_addr()?.port();
+    if reserved.insert(port) {
+      return Ok(port);
+    }
+  }
+  Err(io::Error::new(
+    io::ErrorKind::AddrInUse,
+    "no free port could be reserved",
+  ))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::reserve_port;
+
+  #[test]
+  fn reserves_distinct_ports() {
+    let first = reserve_port().unwrap();
+    let second = reserve_port().unwrap();
+    assert_ne!(first, 0);
+    assert_ne!(first, second);
+  }
+}
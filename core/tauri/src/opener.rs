@@ -0,0 +1,297 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Opening URLs and files with the default app of the system, see [`AppHandle::open_with_default_app`].
+//!
+//! The targets are checked against the [`OpenerPolicy`] set with [`Builder::opener_policy`](crate::Builder::opener_policy)
+//! so a crafted URL can't be used to run a local program, e.g. with a `file:` URL to an executable,
+//! a scheme registered by another app or an argument passed to the opener command.
+//!
+//! ```rust,no_run
+//! use tauri::opener::OpenerPolicy;
+//!
+//! tauri::Builder::default().opener_policy(
+//!   OpenerPolicy::default()
+//!     .allow_scheme("zoommtg")
+//!     .on_confirm(|target, reason| {
+//!       // e.g. ask the user with a dialog
+//!       println!("refusing to open {target}: {reason}");
+//!       false
+//!     }),
+//! );
+//! ```
+
+use std::{
+  fmt,
+  path::{Path, PathBuf},
+  sync::Arc,
+};
+
+use url::Url;
+
+use crate::{AppHandle, Runtime};
+
+/// The schemes allowed by the default policy.
+pub const DEFAULT_SCHEMES: &[&str] = &["http", "https", "mailto", "tel"];
+
+/// The extensions of the files run as programs by the default app of Windows, macOS or the Linux desktops.
+const EXECUTABLE_EXTENSIONS: &[&str] = &[
+  "app", "appimage", "bat", "cmd", "com", "command", "cpl", "desktop", "exe", "hta", "jar", "js",
+  "jse", "lnk", "msc", "msi", "msp", "pif", "ps1", "reg", "scr", "sh", "terminal", "url", "vbe",
+  "vbs", "ws", "wsf", "wsh",
+];
+
+type ConfirmHandler = dyn Fn(&OpenTarget, &str) -> bool + Send + Sync;
+
+/// A URL or local path to open.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OpenTarget {
+  /// A URL, with a scheme other than `file`.
+  Url(Url),
+  /// An absolute path, also used for the `file:` URLs.
+  Path(PathBuf),
+}
+
+impl fmt::Display for OpenTarget {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::Url(url) => write!(f, "{url}"),
+      Self::Path(path) => write!(f, "{}", path.display()),
+    }
+  }
+}
+
+/// Whether a target can be opened.
+#[derive(Debug, PartialEq, Eq)]
+enum Verdict {
+  Allow(OpenTarget),
+  /// Opened only if the confirmation handler accepts it.
+  Confirm(OpenTarget, String),
+  /// Never opened.
+  Deny(String),
+}
+
+/// The rules checked by [`AppHandle::open_with_default_app`].
+///
+/// The [default](OpenerPolicy::default) policy opens the [`DEFAULT_SCHEMES`] URLs and rejects the local paths.
+/// The targets that are not allowed are passed to the [confirmation handler](OpenerPolicy::on_confirm) if any, or rejected.
+///
+/// The targets starting with `-`, which the opener command would read as an option, and those including control characters
+/// are always rejected.
+#[derive(Clone)]
+pub struct OpenerPolicy {
+  schemes: Vec<String>,
+  allow_paths: bool,
+  confirm: Option<Arc<ConfirmHandler>>,
+}
+
+impl fmt::Debug for OpenerPolicy {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("OpenerPolicy")
+      .field("schemes", &self.schemes)
+      .field("allow_paths", &self.allow_paths)
+      .field("confirm", &self.confirm.is_some())
+      .finish()
+  }
+}
+
+impl Default for OpenerPolicy {
+  fn default() -> Self {
+    Self {
+      schemes: DEFAULT_SCHEMES.iter().map(|s| s.to_string()).collect(),
+      allow_paths: false,
+      confirm: None,
+    }
+  }
+}
+
+impl OpenerPolicy {
+  /// A policy allowing no scheme nor path.
+  pub fn deny_all() -> Self {
+    Self {
+      schemes: Vec::new(),
+      allow_paths: false,
+      confirm: None,
+    }
+  }
+
+  /// Allows the URLs of the scheme, without `:`.
+  #[must_use]
+  pub fn allow_scheme(mut self, scheme: impl Into<String>) -> Self {
+    self.schemes.push(scheme.into().to_ascii_lowercase());
+    self
+  }
+
+  /// Allows the absolute paths and `file:` URLs, except the executables which always need a confirmation.
+  #[must_use]
+  pub fn allow_paths(mut self, allow: bool) -> Self {
+    self.allow_paths = allow;
+    self
+  }
+
+  /// Sets the handler deciding whether the targets that are not allowed are opened, called with the target and the reason
+  /// it is not allowed. It is called on the thread calling [`AppHandle::open_with_default_app`],
+  /// which is an async runtime thread for the webview calls, so it can block on a dialog.
+  #[must_use]
+  pub fn on_confirm<F: Fn(&OpenTarget, &str) -> bool + Send + Sync + 'static>(
+    mut self,
+    handler: F,
+  ) -> Self {
+    self.confirm.replace(Arc::new(handler));
+    self
+  }
+
+  fn check_path(&self, path: PathBuf) -> Verdict {
+    if !path.is_absolute() {
+      return Verdict::Deny("relative paths are not allowed".into());
+    }
+    if is_executable(&path) {
+      return Verdict::Confirm(OpenTarget::Path(path), "the file is a program".into());
+    }
+    if self.allow_paths {
+      Verdict::Allow(OpenTarget::Path(path))
+    } else {
+      Verdict::Confirm(OpenTarget::Path(path), "local paths are not allowed".into())
+    }
+  }
+
+  fn check(&self, target: &str) -> Verdict {
+    if target.is_empty() {
+      return Verdict::Deny("the target is empty".into());
+    }
+    if target.starts_with('-') {
+      return Verdict::Deny("the target starts with `-`".into());
+    }
+    if target.chars().any(char::is_control) {
+      return Verdict::Deny("the target includes control characters".into());
+    }
+
+    match Url::parse(target) {
+      // single letter schemes are Windows drive letters
+      Ok(url) if url.scheme().len() > 1 => {
+        if url.scheme() == "file" {
+          match url.to_file_path() {
+            Ok(path) => self.check_path(path),
+            Err(()) => Verdict::Deny("invalid file URL".into()),
+          }
+        } else if self.schemes.iter().any(|s| s == url.scheme()) {
+          Verdict::Allow(OpenTarget::Url(url))
+        } else {
+          let reason = format!("the `{}` scheme is not allowed", url.scheme());
+          Verdict::Confirm(OpenTarget::Url(url), reason)
+        }
+      }
+      _ => self.check_path(PathBuf::from(target)),
+    }
+  }
+
+  /// The target to open, if it is allowed or confirmed.
+  fn resolve(&self, target: &str) -> crate::Result<OpenTarget> {
+    match self.check(target) {
+      Verdict::Allow(target) => Ok(target),
+      Verdict::Confirm(target, reason) => match &self.confirm {
+        Some(confirm) if confirm(&target, &reason) => Ok(target),
+        _ => Err(crate::Error::OpenDenied(target.to_string(), reason)),
+      },
+      Verdict::Deny(reason) => Err(crate::Error::OpenDenied(target.into(), reason)),
+    }
+  }
+}
+
+fn is_executable(path: &Path) -> bool {
+  let extension = path
+    .extension()
+    .map(|e| e.to_string_lossy().to_ascii_lowercase());
+  if extension.is_some_and(|e| EXECUTABLE_EXTENSIONS.contains(&e.as_str())) {
+    return true;
+  }
+  #[cfg(unix)]
+  {
+    use std::os::unix::fs::PermissionsExt;
+    if let Ok(metadata) = std::fs::metadata(path) {
+      return metadata.is_file() && metadata.permissions().mode() & 0o111 != 0;
+    }
+  }
+  false
+}
+
+pub(crate) fn open<R: Runtime>(app: &AppHandle<R>, target: &str) -> crate::Result<()> {
+  let target = app.manager.opener_policy.resolve(target)?;
+  match target {
+    OpenTarget::Url(url) => open::that_detached(url.as_str()),
+    OpenTarget::Path(path) => open::that_detached(path),
+  }
+  .map_err(Into::into)
+}
+
+#[cfg(test)]
+mod tests {
+  use std::path::PathBuf;
+
+  use super::{OpenTarget, OpenerPolicy, Verdict};
+
+  #[test]
+  fn allows_default_schemes() {
+    let policy = OpenerPolicy::default();
+    assert_eq!(
+      policy.check("https://tauri.app"),
+      Verdict::Allow(OpenTarget::Url("https://tauri.app".parse().unwrap()))
+    );
+    assert!(matches!(
+      policy.check("ms-msdt:/id PCWDiagnostic"),
+      Verdict::Confirm(..)
+    ));
+    assert!(matches!(
+      OpenerPolicy::default()
+        .allow_scheme("ZoomMtg")
+        .check("zoommtg://join"),
+      Verdict::Allow(_)
+    ));
+  }
+
+  #[test]
+  fn rejects_crafted_targets() {
+    let policy = OpenerPolicy::default().allow_paths(true);
+    assert!(matches!(policy.check("--help"), Verdict::Deny(_)));
+    assert!(matches!(
+      policy.check("https://tauri.app\n/bin/sh"),
+      Verdict::Deny(_)
+    ));
+    assert!(matches!(
+      policy.check("relative/file.txt"),
+      Verdict::Deny(_)
+    ));
+    let installer = url::Url::from_file_path(std::env::temp_dir().join("installer.EXE")).unwrap();
+    assert!(matches!(
+      policy.check(installer.as_str()),
+      Verdict::Confirm(OpenTarget::Path(_), _)
+    ));
+    let script = std::env::temp_dir().join("run.command");
+    assert!(matches!(
+      policy.check(script.to_str().unwrap()),
+      Verdict::Confirm(OpenTarget::Path(_), _)
+    ));
+  }
+
+  #[test]
+  fn confirms_paths() {
+    let path = std::env::temp_dir().join("report.pdf");
+    let target = path.to_str().unwrap();
+    assert!(OpenerPolicy::default().resolve(target).is_err());
+    assert_eq!(
+      OpenerPolicy::default()
+        .on_confirm(|_, _| true)
+        .resolve(target)
+        .unwrap(),
+      OpenTarget::Path(PathBuf::from(target))
+    );
+    assert_eq!(
+      OpenerPolicy::default()
+        .allow_paths(true)
+        .resolve(target)
+        .unwrap(),
+      OpenTarget::Path(path)
+    );
+  }
+}
@@ -194,8 +194,12 @@ impl<R: Runtime> PathResolver<R> {
 
   /// Returns the path to the suggested directory for your app's config files.
   ///
-  /// Resolves to [`config_dir`](self.config_dir)`/${bundle_identifier}`.
+  /// Resolves to [`config_dir`](self.config_dir)`/${bundle_identifier}`,
+  /// or `${exe_dir}/data/config` in [portable mode](crate::utils::platform::portable_dir).
   pub fn app_config_dir(&self) -> Result<PathBuf> {
+    if let Some(dir) = portable_dir("config") {
+      return Ok(dir);
+    }
     dirs::config_dir()
       .ok_or(Error::UnknownPath)
       .map(|dir| dir.join(&self.0.config().identifier))
@@ -203,8 +207,12 @@ impl<R: Runtime> PathResolver<R> {
 
   /// Returns the path to the suggested directory for your app's data files.
   ///
-  /// Resolves to [`data_dir`](self.data_dir)`/${bundle_identifier}`.
+  /// Resolves to [`data_dir`](self.data_dir)`/${bundle_identifier}`,
+  /// or `${exe_dir}/data/data` in [portable mode](crate::utils::platform::portable_dir).
   pub fn app_data_dir(&self) -> Result<PathBuf> {
+    if let Some(dir) = portable_dir("data") {
+      return Ok(dir);
+    }
     dirs::data_dir()
       .ok_or(Error::UnknownPath)
       .map(|dir| dir.join(&self.0.config().identifier))
@@ -212,8 +220,12 @@ impl<R: Runtime> PathResolver<R> {
 
   /// Returns the path to the suggested directory for your app's local data files.
   ///
-  /// Resolves to [`local_data_dir`](self.local_data_dir)`/${bundle_identifier}`.
+  /// Resolves to [`local_data_dir`](self.local_data_dir)`/${bundle_identifier}`,
+  /// or `${exe_dir}/data/local-data` in [portable mode](crate::utils::platform::portable_dir).
   pub fn app_local_data_dir(&self) -> Result<PathBuf> {
+    if let Some(dir) = portable_dir("local-data") {
+      return Ok(dir);
+    }
     dirs::data_local_dir()
       .ok_or(Error::UnknownPath)
       .map(|dir| dir.join(&self.0.config().identifier))
@@ -221,8 +233,12 @@ impl<R: Runtime> PathResolver<R> {
 
   /// Returns the path to the suggested directory for your app's cache files.
   ///
-  /// Resolves to [`cache_dir`](self.cache_dir)`/${bundle_identifier}`.
+  /// Resolves to [`cache_dir`](self.cache_dir)`/${bundle_identifier}`,
+  /// or `${exe_dir}/data/cache` in [portable mode](crate::utils::platform::portable_dir).
   pub fn app_cache_dir(&self) -> Result<PathBuf> {
+    if let Some(dir) = portable_dir("cache") {
+      return Ok(dir);
+    }
     dirs::cache_dir()
       .ok_or(Error::UnknownPath)
       .map(|dir| dir.join(&self.0.config().identifier))
@@ -235,7 +251,13 @@ impl<R: Runtime> PathResolver<R> {
   /// - **Linux:** Resolves to [`data_local_dir`](self.data_local_dir)`/${bundle_identifier}/logs`.
   /// - **macOS:** Resolves to [`home_dir`](self.home_dir)`/Library/Logs/${bundle_identifier}`
   /// - **Windows:** Resolves to [`data_local_dir`](self.data_local_dir)`/${bundle_identifier}/logs`.
+  ///
+  /// In [portable mode](crate::utils::platform::portable_dir), resolves to `${exe_dir}/data/logs` on all platforms.
   pub fn app_log_dir(&self) -> Result<PathBuf> {
+    if let Some(dir) = portable_dir("logs") {
+      return Ok(dir);
+    }
+
     #[cfg(target_os = "macos")]
     let path = dirs::home_dir()
       .ok_or(Error::UnknownPath)
@@ -254,3 +276,8 @@ impl<R: Runtime> PathResolver<R> {
     Ok(std::env::temp_dir())
   }
 }
+
+/// Resolves the given folder of the portable app data directory, if the portable mode is enabled.
+fn portable_dir(name: &str) -> Option<PathBuf> {
+  crate::utils::platform::portable_dir().map(|dir| dir.join(name))
+}
@@ -10,7 +10,7 @@ use crate::{
   manager::webview::UriSchemeProtocol,
   utils::config::PluginConfig,
   webview::PageLoadPayload,
-  AppHandle, Error, RunEvent, Runtime, Webview, Window,
+  AppHandle, Error, LifecycleEvent, RunEvent, Runtime, Webview, Window,
 };
 use serde::de::DeserializeOwned;
 use serde_json::Value as JsonValue;
@@ -19,21 +19,40 @@ use thiserror::Error;
 use url::Url;
 
 use std::{
+  any::Any,
   borrow::Cow,
   collections::HashMap,
   fmt::{self, Debug},
   sync::Arc,
+  time::{Duration, Instant},
 };
 
 /// Mobile APIs.
 #[cfg(mobile)]
 pub mod mobile;
 
+#[cfg(all(feature = "dynamic-plugins", feature = "wry"))]
+#[cfg_attr(docsrs, doc(cfg(feature = "dynamic-plugins")))]
+pub mod dynamic;
+mod paths;
+
+pub use paths::PluginPaths;
+
 /// The plugin interface.
 pub trait Plugin<R: Runtime>: Send {
   /// The plugin name. Used as key on the plugin config object.
   fn name(&self) -> &'static str;
 
+  /// The services of other plugins this plugin uses, initialized before this plugin.
+  fn dependencies(&self) -> &[ServiceDescriptor] {
+    &[]
+  }
+
+  /// The services this plugin provides to other plugins with [`PluginApi::provide_service`].
+  fn services(&self) -> &[ServiceDescriptor] {
+    &[]
+  }
+
   /// Initializes the plugin.
   #[allow(unused_variables)]
   fn initialize(
@@ -78,6 +97,34 @@ pub trait Plugin<R: Runtime>: Send {
   #[allow(unused_variables)]
   fn on_event(&mut self, app: &AppHandle<R>, event: &RunEvent) {}
 
+  /// Callback invoked when the app is suspended, so the resources it holds such as the camera or sockets can be released.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Android / iOS:** Triggered when the app is moved to the background.
+  /// - **Windows / macOS:** Triggered when the system goes to sleep.
+  /// - **Linux:** Unsupported.
+  #[allow(unused_variables)]
+  fn on_suspend(&mut self, app: &AppHandle<R>) {}
+
+  /// Callback invoked when the app is resumed after [`Self::on_suspend`], so the released resources can be reacquired.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Android / iOS:** Triggered when the app is moved back to the foreground.
+  /// - **Windows / macOS:** Triggered when the system wakes up.
+  /// - **Linux:** Unsupported.
+  #[allow(unused_variables)]
+  fn on_resume(&mut self, app: &AppHandle<R>) {}
+
+  /// Callback invoked when the system is running low on memory, so caches and other resources that can be recreated can be released.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Windows / macOS / Linux:** Unsupported.
+  #[allow(unused_variables)]
+  fn on_memory_warning(&mut self, app: &AppHandle<R>) {}
+
   /// Extend commands to [`crate::Builder::invoke_handler`].
   #[allow(unused_variables)]
   fn extend_api(&mut self, invoke: Invoke<R>) -> bool {
@@ -90,6 +137,7 @@ type SetupHook<R, C> =
 type OnWindowReady<R> = dyn FnMut(Window<R>) + Send;
 type OnWebviewReady<R> = dyn FnMut(Webview<R>) + Send;
 type OnEvent<R> = dyn FnMut(&AppHandle<R>, &RunEvent) + Send;
+type OnLifecycle<R> = dyn FnMut(&AppHandle<R>) + Send;
 type OnNavigation<R> = dyn Fn(&Webview<R>, &Url) -> bool + Send;
 type OnPageLoad<R> = dyn FnMut(&Webview<R>, &PageLoadPayload<'_>) + Send;
 type OnDrop<R> = dyn FnOnce(AppHandle<R>) + Send;
@@ -116,6 +164,208 @@ impl<R: Runtime> PluginHandle<R> {
   pub fn app(&self) -> &AppHandle<R> {
     &self.handle
   }
+
+  /// Returns the directories of the plugin.
+  pub fn paths(&self) -> PluginPaths<R> {
+    PluginPaths::new(self.handle.clone(), self.name)
+  }
+}
+
+/// A versioned interface a plugin provides to other plugins, usually implemented for a `dyn Trait`.
+///
+/// A plugin declares the services it uses with [`Builder::depends_on`] and the ones it provides with [`Builder::provides`],
+/// so the plugins are initialized after the plugins they depend on,
+/// and gets them with [`AppHandle::plugin_service`].
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Arc;
+/// use tauri::{plugin::{Builder, PluginService, TauriPlugin}, Runtime};
+///
+/// pub trait Dialog: Send + Sync {
+///   fn confirm(&self, message: &str) -> bool;
+/// }
+///
+/// impl PluginService for dyn Dialog {
+///   const NAME: &'static str = "dialog";
+///   const VERSION: &'static str = "1.2.0";
+/// }
+///
+/// struct NativeDialog;
+///
+/// impl Dialog for NativeDialog {
+///   fn confirm(&self, _message: &str) -> bool {
+///     true
+///   }
+/// }
+///
+/// fn dialog<R: Runtime>() -> TauriPlugin<R> {
+///   Builder::new("dialog")
+///     .provides::<dyn Dialog>()
+///     .setup(|_app, api| {
+///       api.provide_service::<dyn Dialog>(Arc::new(NativeDialog))?;
+///       Ok(())
+///     })
+///     .build()
+/// }
+///
+/// fn updater<R: Runtime>() -> TauriPlugin<R> {
+///   Builder::new("updater")
+///     .depends_on::<dyn Dialog>()
+///     .setup(|app, _api| {
+///       let dialog = app.plugin_service::<dyn Dialog>()?;
+///       dialog.confirm("An update is available, install it?");
+///       Ok(())
+///     })
+///     .build()
+/// }
+/// ```
+pub trait PluginService: Send + Sync + 'static {
+  /// The unique name of the service.
+  const NAME: &'static str;
+  /// The version of the service interface, following semantic versioning.
+  ///
+  /// A dependency on the service accepts any compatible version, as Cargo does with `^version` requirements.
+  const VERSION: &'static str;
+}
+
+/// The name and version of a [`PluginService`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ServiceDescriptor {
+  /// The [`PluginService::NAME`].
+  pub name: &'static str,
+  /// The [`PluginService::VERSION`].
+  pub version: &'static str,
+}
+
+impl ServiceDescriptor {
+  /// The descriptor of the given service.
+  pub fn of<S: ?Sized + PluginService>() -> Self {
+    Self {
+      name: S::NAME,
+      version: S::VERSION,
+    }
+  }
+
+  /// Whether a provided service satisfies this requirement.
+  fn is_satisfied_by(&self, version: &str) -> bool {
+    let requirement = semver::VersionReq::parse(&format!("^{}", self.version));
+    let version = semver::Version::parse(version);
+    matches!((requirement, version), (Ok(requirement), Ok(version)) if requirement.matches(&version))
+  }
+}
+
+struct RegisteredService {
+  provider: &'static str,
+  version: &'static str,
+  /// The `Arc<S>` of the service.
+  value: Box<dyn Any + Send + Sync>,
+}
+
+/// The services provided by the plugins.
+#[derive(Default)]
+pub(crate) struct ServiceRegistry {
+  services: HashMap<&'static str, RegisteredService>,
+}
+
+impl fmt::Debug for ServiceRegistry {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_map()
+      .entries(
+        self
+          .services
+          .iter()
+          .map(|(name, service)| (name, service.provider)),
+      )
+      .finish()
+  }
+}
+
+impl ServiceRegistry {
+  fn provide<S: ?Sized + PluginService>(
+    &mut self,
+    provider: &'static str,
+    service: Arc<S>,
+  ) -> crate::Result<()> {
+    if let Some(registered) = self.services.get(S::NAME) {
+      if registered.provider != provider {
+        return Err(Error::PluginService(
+          S::NAME.into(),
+          format!("already provided by plugin `{}`", registered.provider),
+        ));
+      }
+    }
+    self.services.insert(
+      S::NAME,
+      RegisteredService {
+        provider,
+        version: S::VERSION,
+        value: Box::new(service),
+      },
+    );
+    Ok(())
+  }
+
+  pub(crate) fn get<S: ?Sized + PluginService>(&self) -> crate::Result<Arc<S>> {
+    let registered = self
+      .services
+      .get(S::NAME)
+      .ok_or_else(|| Error::PluginService(S::NAME.into(), "no plugin provides it".into()))?;
+    if !ServiceDescriptor::of::<S>().is_satisfied_by(registered.version) {
+      return Err(Error::PluginService(
+        S::NAME.into(),
+        format!(
+          "plugin `{}` provides version {}, incompatible with {}",
+          registered.provider,
+          registered.version,
+          S::VERSION
+        ),
+      ));
+    }
+    registered
+      .value
+      .downcast_ref::<Arc<S>>()
+      .cloned()
+      .ok_or_else(|| {
+        Error::PluginService(
+          S::NAME.into(),
+          format!(
+            "plugin `{}` provides another type with the same name",
+            registered.provider
+          ),
+        )
+      })
+  }
+
+  /// Checks that the required service is provided with a compatible version.
+  fn check(&self, requirement: &ServiceDescriptor) -> Result<(), String> {
+    match self.services.get(requirement.name) {
+      Some(registered) if requirement.is_satisfied_by(registered.version) => Ok(()),
+      Some(registered) => Err(format!(
+        "depends on service `{}` {} but plugin `{}` provides version {}",
+        requirement.name, requirement.version, registered.provider, registered.version
+      )),
+      None => Err(format!(
+        "depends on service `{}` {} which no plugin provides",
+        requirement.name, requirement.version
+      )),
+    }
+  }
+
+  fn is_provided_by(&self, service: &str, provider: &str) -> bool {
+    self
+      .services
+      .get(service)
+      .map_or(false, |registered| registered.provider == provider)
+  }
+
+  /// Removes the services provided by the given plugin.
+  pub(crate) fn remove_provider(&mut self, provider: &str) {
+    self
+      .services
+      .retain(|_, registered| registered.provider != provider);
+  }
 }
 
 /// Api exposed to the plugin setup hook.
@@ -139,6 +389,11 @@ impl<R: Runtime, C: DeserializeOwned> PluginApi<R, C> {
     &self.handle
   }
 
+  /// Returns the directories of the plugin.
+  pub fn paths(&self) -> PluginPaths<R> {
+    PluginPaths::new(self.handle.clone(), self.name)
+  }
+
   /// Gets the global scope defined on the permissions that are part of the app ACL.
   pub fn scope<T: ScopeObject>(&self) -> crate::Result<ScopeValue<T>> {
     self
@@ -150,6 +405,19 @@ impl<R: Runtime, C: DeserializeOwned> PluginApi<R, C> {
       .scope_manager
       .get_global_scope_typed(&self.handle, self.name)
   }
+
+  /// Provides a service to the other plugins, which get it with [`AppHandle::plugin_service`].
+  ///
+  /// The service should be declared with [`Builder::provides`] so the plugins depending on it are initialized afterwards.
+  pub fn provide_service<S: ?Sized + PluginService>(&self, service: Arc<S>) -> crate::Result<()> {
+    self
+      .handle
+      .manager
+      .plugin_services
+      .lock()
+      .unwrap()
+      .provide(self.name, service)
+  }
 }
 
 /// Errors that can happen during [`Builder`].
@@ -245,8 +513,13 @@ pub struct Builder<R: Runtime, C: DeserializeOwned = ()> {
   on_window_ready: Box<OnWindowReady<R>>,
   on_webview_ready: Box<OnWebviewReady<R>>,
   on_event: Box<OnEvent<R>>,
+  on_suspend: Box<OnLifecycle<R>>,
+  on_resume: Box<OnLifecycle<R>>,
+  on_memory_warning: Box<OnLifecycle<R>>,
   on_drop: Option<Box<OnDrop<R>>>,
   uri_scheme_protocols: HashMap<String, Arc<UriSchemeProtocol<R>>>,
+  dependencies: Vec<ServiceDescriptor>,
+  services: Vec<ServiceDescriptor>,
 }
 
 impl<R: Runtime, C: DeserializeOwned> Builder<R, C> {
@@ -262,8 +535,13 @@ impl<R: Runtime, C: DeserializeOwned> Builder<R, C> {
       on_window_ready: Box::new(|_| ()),
       on_webview_ready: Box::new(|_| ()),
       on_event: Box::new(|_, _| ()),
+      on_suspend: Box::new(|_| ()),
+      on_resume: Box::new(|_| ()),
+      on_memory_warning: Box::new(|_| ()),
       on_drop: None,
       uri_scheme_protocols: Default::default(),
+      dependencies: Vec::new(),
+      services: Vec::new(),
     }
   }
 
@@ -498,6 +776,64 @@ impl<R: Runtime, C: DeserializeOwned> Builder<R, C> {
     self
   }
 
+  /// Callback invoked when the app is suspended. See [`Plugin::on_suspend`] for the platforms that support it.
+  ///
+  /// # Examples
+  ///
+  /// ```rust
+  /// use std::sync::Mutex;
+  /// use tauri::{plugin::{Builder, TauriPlugin}, Manager, Runtime};
+  ///
+  /// struct Connection;
+  ///
+  /// #[derive(Default)]
+  /// struct Socket(Mutex<Option<Connection>>);
+  ///
+  /// fn init<R: Runtime>() -> TauriPlugin<R> {
+  ///   Builder::new("example")
+  ///     .setup(|app, _api| {
+  ///       app.manage(Socket::default());
+  ///       Ok(())
+  ///     })
+  ///     .on_suspend(|app| {
+  ///       // close the connection while the app is in the background
+  ///       app.state::<Socket>().0.lock().unwrap().take();
+  ///     })
+  ///     .on_resume(|app| {
+  ///       app.state::<Socket>().0.lock().unwrap().replace(Connection);
+  ///     })
+  ///     .build()
+  /// }
+  /// ```
+  #[must_use]
+  pub fn on_suspend<F>(mut self, on_suspend: F) -> Self
+  where
+    F: FnMut(&AppHandle<R>) + Send + 'static,
+  {
+    self.on_suspend = Box::new(on_suspend);
+    self
+  }
+
+  /// Callback invoked when the app is resumed after being suspended. See [`Plugin::on_resume`] for the platforms that support it.
+  #[must_use]
+  pub fn on_resume<F>(mut self, on_resume: F) -> Self
+  where
+    F: FnMut(&AppHandle<R>) + Send + 'static,
+  {
+    self.on_resume = Box::new(on_resume);
+    self
+  }
+
+  /// Callback invoked when the system is running low on memory. See [`Plugin::on_memory_warning`] for the platforms that support it.
+  #[must_use]
+  pub fn on_memory_warning<F>(mut self, on_memory_warning: F) -> Self
+  where
+    F: FnMut(&AppHandle<R>) + Send + 'static,
+  {
+    self.on_memory_warning = Box::new(on_memory_warning);
+    self
+  }
+
   /// Callback invoked when the plugin is dropped.
   ///
   /// # Examples
@@ -628,6 +964,23 @@ impl<R: Runtime, C: DeserializeOwned> Builder<R, C> {
     self
   }
 
+  /// Declares a dependency on a service provided by another plugin,
+  /// so this plugin is initialized after it and can get it with [`AppHandle::plugin_service`].
+  ///
+  /// The initialization fails if no plugin provides a compatible version of the service.
+  #[must_use]
+  pub fn depends_on<S: ?Sized + PluginService>(mut self) -> Self {
+    self.dependencies.push(ServiceDescriptor::of::<S>());
+    self
+  }
+
+  /// Declares a service this plugin provides with [`PluginApi::provide_service`] in its setup hook.
+  #[must_use]
+  pub fn provides<S: ?Sized + PluginService>(mut self) -> Self {
+    self.services.push(ServiceDescriptor::of::<S>());
+    self
+  }
+
   /// Builds the [`TauriPlugin`].
   pub fn try_build(self) -> Result<TauriPlugin<R, C>, BuilderError> {
     if let Some(&reserved) = RESERVED_PLUGIN_NAMES.iter().find(|&r| r == &self.name) {
@@ -645,8 +998,13 @@ impl<R: Runtime, C: DeserializeOwned> Builder<R, C> {
       on_window_ready: self.on_window_ready,
       on_webview_ready: self.on_webview_ready,
       on_event: self.on_event,
+      on_suspend: self.on_suspend,
+      on_resume: self.on_resume,
+      on_memory_warning: self.on_memory_warning,
       on_drop: self.on_drop,
       uri_scheme_protocols: self.uri_scheme_protocols,
+      dependencies: self.dependencies,
+      services: self.services,
     })
   }
 
@@ -672,8 +1030,13 @@ pub struct TauriPlugin<R: Runtime, C: DeserializeOwned = ()> {
   on_window_ready: Box<OnWindowReady<R>>,
   on_webview_ready: Box<OnWebviewReady<R>>,
   on_event: Box<OnEvent<R>>,
+  on_suspend: Box<OnLifecycle<R>>,
+  on_resume: Box<OnLifecycle<R>>,
+  on_memory_warning: Box<OnLifecycle<R>>,
   on_drop: Option<Box<OnDrop<R>>>,
   uri_scheme_protocols: HashMap<String, Arc<UriSchemeProtocol<R>>>,
+  dependencies: Vec<ServiceDescriptor>,
+  services: Vec<ServiceDescriptor>,
 }
 
 impl<R: Runtime, C: DeserializeOwned> Drop for TauriPlugin<R, C> {
@@ -689,6 +1052,14 @@ impl<R: Runtime, C: DeserializeOwned> Plugin<R> for TauriPlugin<R, C> {
     self.name
   }
 
+  fn dependencies(&self) -> &[ServiceDescriptor] {
+    &self.dependencies
+  }
+
+  fn services(&self) -> &[ServiceDescriptor] {
+    &self.services
+  }
+
   fn initialize(
     &mut self,
     app: &AppHandle<R>,
@@ -745,11 +1116,150 @@ impl<R: Runtime, C: DeserializeOwned> Plugin<R> for TauriPlugin<R, C> {
     (self.on_event)(app, event)
   }
 
+  fn on_suspend(&mut self, app: &AppHandle<R>) {
+    (self.on_suspend)(app)
+  }
+
+  fn on_resume(&mut self, app: &AppHandle<R>) {
+    (self.on_resume)(app)
+  }
+
+  fn on_memory_warning(&mut self, app: &AppHandle<R>) {
+    (self.on_memory_warning)(app)
+  }
+
   fn extend_api(&mut self, invoke: Invoke<R>) -> bool {
     (self.invoke_handler)(invoke)
   }
 }
 
+type LazyInit<R> = dyn FnOnce() -> Box<dyn Plugin<R>> + Send;
+
+/// A plugin registered with [`crate::Builder::plugin_lazy`], created and initialized on the first call to one of its commands.
+pub(crate) struct LazyPlugin<R: Runtime> {
+  name: &'static str,
+  init: Option<Box<LazyInit<R>>>,
+  plugin: Option<Box<dyn Plugin<R>>>,
+  app: Option<AppHandle<R>>,
+  config: JsonValue,
+}
+
+impl<R: Runtime> LazyPlugin<R> {
+  pub(crate) fn new<P: Plugin<R> + 'static, F: FnOnce() -> P + Send + 'static>(
+    name: &'static str,
+    init: F,
+  ) -> Self {
+    Self {
+      name,
+      init: Some(Box::new(move || Box::new(init()) as Box<dyn Plugin<R>>)),
+      plugin: None,
+      app: None,
+      config: JsonValue::Null,
+    }
+  }
+
+  /// Creates and initializes the plugin if it is not initialized yet.
+  fn get(&mut self) -> crate::Result<&mut Box<dyn Plugin<R>>> {
+    if self.plugin.is_none() {
+      let (Some(init), Some(app)) = (self.init.take(), self.app.as_ref()) else {
+        return Err(Error::PluginInitialization(
+          self.name.into(),
+          "the lazy plugin failed to initialize or the app is not built yet".into(),
+        ));
+      };
+      let start = Instant::now();
+      let mut plugin = init();
+      initialize(&mut plugin, app, self.config.take())?;
+      log::debug!(
+        "initialized the lazy plugin `{}` in {:?}",
+        self.name,
+        start.elapsed()
+      );
+      self.plugin.replace(plugin);
+    }
+    Ok(self.plugin.as_mut().unwrap())
+  }
+}
+
+impl<R: Runtime> Plugin<R> for LazyPlugin<R> {
+  fn name(&self) -> &'static str {
+    self.name
+  }
+
+  fn initialize(
+    &mut self,
+    app: &AppHandle<R>,
+    config: JsonValue,
+  ) -> Result<(), Box<dyn std::error::Error>> {
+    self.app.replace(app.clone());
+    self.config = config;
+    Ok(())
+  }
+
+  fn initialization_script(&self) -> Option<String> {
+    self.plugin.as_ref()?.initialization_script()
+  }
+
+  fn window_created(&mut self, window: Window<R>) {
+    if let Some(plugin) = &mut self.plugin {
+      plugin.window_created(window)
+    }
+  }
+
+  fn webview_created(&mut self, webview: Webview<R>) {
+    if let Some(plugin) = &mut self.plugin {
+      plugin.webview_created(webview)
+    }
+  }
+
+  fn on_navigation(&mut self, webview: &Webview<R>, url: &Url) -> bool {
+    match &mut self.plugin {
+      Some(plugin) => plugin.on_navigation(webview, url),
+      None => true,
+    }
+  }
+
+  fn on_page_load(&mut self, webview: &Webview<R>, payload: &PageLoadPayload<'_>) {
+    if let Some(plugin) = &mut self.plugin {
+      plugin.on_page_load(webview, payload)
+    }
+  }
+
+  fn on_event(&mut self, app: &AppHandle<R>, event: &RunEvent) {
+    if let Some(plugin) = &mut self.plugin {
+      plugin.on_event(app, event)
+    }
+  }
+
+  fn on_suspend(&mut self, app: &AppHandle<R>) {
+    if let Some(plugin) = &mut self.plugin {
+      plugin.on_suspend(app)
+    }
+  }
+
+  fn on_resume(&mut self, app: &AppHandle<R>) {
+    if let Some(plugin) = &mut self.plugin {
+      plugin.on_resume(app)
+    }
+  }
+
+  fn on_memory_warning(&mut self, app: &AppHandle<R>) {
+    if let Some(plugin) = &mut self.plugin {
+      plugin.on_memory_warning(app)
+    }
+  }
+
+  fn extend_api(&mut self, invoke: Invoke<R>) -> bool {
+    match self.get() {
+      Ok(plugin) => plugin.extend_api(invoke),
+      Err(e) => {
+        invoke.resolver.reject(e.to_string());
+        true
+      }
+    }
+  }
+}
+
 /// Plugin collection type.
 #[default_runtime(crate::Wry, wry)]
 pub(crate) struct PluginStore<R: Runtime> {
@@ -783,6 +1293,11 @@ impl<R: Runtime> PluginStore<R> {
     result
   }
 
+  /// Whether a plugin with the given name is in the store.
+  pub(crate) fn contains(&self, plugin: &str) -> bool {
+    self.store.iter().any(|p| p.name() == plugin)
+  }
+
   /// Removes the plugin with the given name from the store.
   pub fn unregister(&mut self, plugin: &'static str) -> bool {
     let len = self.store.len();
@@ -797,19 +1312,83 @@ impl<R: Runtime> PluginStore<R> {
     app: &AppHandle<R>,
     config: &PluginConfig,
   ) -> crate::Result<()> {
-    initialize(plugin, app, config)
+    initialize(plugin, app, plugin_config(config, plugin.name()))
   }
 
-  /// Initializes all plugins in the store.
+  /// Initializes all plugins in the store, returning the time each plugin took in initialization order.
   pub(crate) fn initialize_all(
     &mut self,
     app: &AppHandle<R>,
     config: &PluginConfig,
-  ) -> crate::Result<()> {
-    self
-      .store
-      .iter_mut()
-      .try_for_each(|plugin| initialize(plugin, app, config))
+  ) -> crate::Result<Vec<(&'static str, Duration)>> {
+    let mut timings = Vec::with_capacity(self.store.len());
+    for index in self.initialization_order()? {
+      let plugin = &mut self.store[index];
+      let start = Instant::now();
+      initialize(plugin, app, plugin_config(config, plugin.name()))?;
+      timings.push((plugin.name(), start.elapsed()));
+    }
+    Ok(timings)
+  }
+
+  /// Sorts the plugins so they are initialized after the providers of their dependencies,
+  /// keeping the registration order otherwise.
+  fn initialization_order(&self) -> crate::Result<Vec<usize>> {
+    let mut providers = HashMap::new();
+    for (index, plugin) in self.store.iter().enumerate() {
+      for service in plugin.services() {
+        providers.insert(service.name, index);
+      }
+    }
+
+    let mut dependencies = Vec::with_capacity(self.store.len());
+    for plugin in &self.store {
+      let mut plugin_dependencies = Vec::new();
+      for dependency in plugin.dependencies() {
+        let Some(&provider) = providers.get(dependency.name) else {
+          return Err(Error::PluginInitialization(
+            plugin.name().into(),
+            format!(
+              "depends on service `{}` {} which no plugin provides",
+              dependency.name, dependency.version
+            ),
+          ));
+        };
+        plugin_dependencies.push(provider);
+      }
+      dependencies.push(plugin_dependencies);
+    }
+
+    let mut order = Vec::with_capacity(self.store.len());
+    let mut initialized = vec![false; self.store.len()];
+    while order.len() < self.store.len() {
+      let next = (0..self.store.len()).find(|&index| {
+        !initialized[index]
+          && dependencies[index]
+            .iter()
+            .all(|&provider| provider == index || initialized[provider])
+      });
+      match next {
+        Some(index) => {
+          initialized[index] = true;
+          order.push(index);
+        }
+        None => {
+          let remaining = (0..self.store.len())
+            .filter(|&index| !initialized[index])
+            .map(|index| self.store[index].name())
+            .collect::<Vec<_>>();
+          return Err(Error::PluginInitialization(
+            remaining[0].into(),
+            format!(
+              "dependency cycle between plugins `{}`",
+              remaining.join("`, `")
+            ),
+          ));
+        }
+      }
+    }
+    Ok(order)
   }
 
   /// Generates an initialization script from all plugins in the store.
@@ -861,12 +1440,19 @@ impl<R: Runtime> PluginStore<R> {
     })
   }
 
-  /// Runs the on_event hook for all plugins in the store.
+  /// Runs the on_event hook for all plugins in the store, and the lifecycle hooks for the lifecycle events.
   pub(crate) fn on_event(&mut self, app: &AppHandle<R>, event: &RunEvent) {
-    self
-      .store
-      .iter_mut()
-      .for_each(|plugin| plugin.on_event(app, event))
+    self.store.iter_mut().for_each(|plugin| {
+      plugin.on_event(app, event);
+      if let RunEvent::Lifecycle(lifecycle) = event {
+        match lifecycle {
+          LifecycleEvent::Suspended => plugin.on_suspend(app),
+          LifecycleEvent::Resumed => plugin.on_resume(app),
+          LifecycleEvent::MemoryWarning => plugin.on_memory_warning(app),
+          _ => {}
+        }
+      }
+    })
   }
 
   /// Runs the plugin `extend_api` hook if it exists. Returns whether the invoke message was handled or not.
@@ -885,16 +1471,195 @@ impl<R: Runtime> PluginStore<R> {
   }
 }
 
-#[cfg_attr(feature = "tracing", tracing::instrument(name = "plugin::hooks::initialize", skip(plugin, app), fields(name = plugin.name())))]
+fn plugin_config(config: &PluginConfig, name: &str) -> JsonValue {
+  config.0.get(name).cloned().unwrap_or_default()
+}
+
+#[cfg_attr(feature = "tracing", tracing::instrument(name = "plugin::hooks::initialize", skip(plugin, app, config), fields(name = plugin.name())))]
 fn initialize<R: Runtime>(
   plugin: &mut Box<dyn Plugin<R>>,
   app: &AppHandle<R>,
-  config: &PluginConfig,
+  config: JsonValue,
 ) -> crate::Result<()> {
+  {
+    let services = app.manager.plugin_services.lock().unwrap();
+    for dependency in plugin.dependencies() {
+      services
+        .check(dependency)
+        .map_err(|e| Error::PluginInitialization(plugin.name().to_string(), e))?;
+    }
+  }
+
   plugin
-    .initialize(
-      app,
-      config.0.get(plugin.name()).cloned().unwrap_or_default(),
-    )
-    .map_err(|e| Error::PluginInitialization(plugin.name().to_string(), e.to_string()))
+    .initialize(app, config)
+    .map_err(|e| Error::PluginInitialization(plugin.name().to_string(), e.to_string()))?;
+
+  let services = app.manager.plugin_services.lock().unwrap();
+  match plugin
+    .services()
+    .iter()
+    .find(|service| !services.is_provided_by(service.name, plugin.name()))
+  {
+    Some(service) => Err(Error::PluginInitialization(
+      plugin.name().to_string(),
+      format!(
+        "declares service `{}` but did not provide it during initialization",
+        service.name
+      ),
+    )),
+    None => Ok(()),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::sync::Arc;
+
+  use super::{Builder, PluginService, TauriPlugin};
+  use crate::{
+    test::{mock_builder, mock_context, noop_assets, MockRuntime},
+    LifecycleEvent, RunEvent,
+  };
+
+  trait Greeter: Send + Sync {
+    fn greet(&self) -> String;
+  }
+
+  impl PluginService for dyn Greeter {
+    const NAME: &'static str = "greeter";
+    const VERSION: &'static str = "1.1.0";
+  }
+
+  struct Hello;
+
+  impl Greeter for Hello {
+    fn greet(&self) -> String {
+      "hello".into()
+    }
+  }
+
+  fn greeter(provide: bool) -> TauriPlugin<MockRuntime> {
+    Builder::new("greeter")
+      .provides::<dyn Greeter>()
+      .setup(move |_app, api| {
+        if provide {
+          api.provide_service::<dyn Greeter>(Arc::new(Hello))?;
+        }
+        Ok(())
+      })
+      .build()
+  }
+
+  fn consumer() -> TauriPlugin<MockRuntime> {
+    Builder::new("consumer")
+      .depends_on::<dyn Greeter>()
+      .setup(|app, _api| {
+        assert_eq!(app.plugin_service::<dyn Greeter>()?.greet(), "hello");
+        Ok(())
+      })
+      .build()
+  }
+
+  #[test]
+  fn initializes_providers_first() {
+    let app = mock_builder()
+      .plugin(consumer())
+      .plugin(greeter(true))
+      .build(mock_context(noop_assets()))
+      .unwrap();
+    assert_eq!(
+      app
+        .handle()
+        .plugin_service::<dyn Greeter>()
+        .unwrap()
+        .greet(),
+      "hello"
+    );
+
+    assert!(app.handle().remove_plugin("greeter"));
+    assert!(app.handle().plugin_service::<dyn Greeter>().is_err());
+  }
+
+  #[test]
+  fn missing_services() {
+    assert!(mock_builder()
+      .plugin(consumer())
+      .build(mock_context(noop_assets()))
+      .is_err());
+
+    assert!(mock_builder()
+      .plugin(greeter(false))
+      .build(mock_context(noop_assets()))
+      .is_err());
+  }
+
+  #[test]
+  fn lifecycle_hooks() {
+    let (tx, rx) = std::sync::mpsc::channel();
+    let resume_tx = tx.clone();
+    let app = mock_builder()
+      .plugin(
+        Builder::new("lifecycle")
+          .on_suspend(move |_app| tx.send("suspend").unwrap())
+          .on_resume(move |_app| resume_tx.send("resume").unwrap())
+          .build(),
+      )
+      .build(mock_context(noop_assets()))
+      .unwrap();
+
+    let mut plugins = app.manager.plugins.lock().unwrap();
+    for event in [
+      LifecycleEvent::Suspended,
+      LifecycleEvent::MemoryWarning,
+      LifecycleEvent::Resumed,
+    ] {
+      plugins.on_event(app.handle(), &RunEvent::Lifecycle(event));
+    }
+    assert_eq!(rx.try_iter().collect::<Vec<_>>(), ["suspend", "resume"]);
+  }
+
+  #[test]
+  fn lazy_plugins() {
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    let app = mock_builder()
+      .plugin(greeter(true))
+      .plugin_lazy("lazy", || Builder::new("lazy").build())
+      .build(mock_context(noop_assets()))
+      .unwrap();
+    let timings = app.manager.startup_timings.lock().unwrap().clone();
+    assert_eq!(
+      timings
+        .plugins
+        .iter()
+        .map(|(name, _)| *name)
+        .collect::<Vec<_>>(),
+      ["greeter", "lazy"]
+    );
+
+    let initialized = Arc::new(AtomicBool::new(false));
+    let initialized_ = initialized.clone();
+    let mut lazy = super::LazyPlugin::new("lazy", move || {
+      Builder::<MockRuntime>::new("lazy")
+        .setup(move |_app, _api| {
+          initialized_.store(true, Ordering::Relaxed);
+          Ok(())
+        })
+        .build()
+    });
+    super::Plugin::initialize(&mut lazy, app.handle(), Default::default()).unwrap();
+    assert!(!initialized.load(Ordering::Relaxed));
+
+    lazy.get().unwrap();
+    assert!(initialized.load(Ordering::Relaxed));
+  }
+
+  #[test]
+  fn service_versions() {
+    let requirement = super::ServiceDescriptor::of::<dyn Greeter>();
+    assert!(requirement.is_satisfied_by("1.1.0"));
+    assert!(requirement.is_satisfied_by("1.4.2"));
+    assert!(!requirement.is_satisfied_by("1.0.9"));
+    assert!(!requirement.is_satisfied_by("2.0.0"));
+  }
 }
@@ -0,0 +1,403 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Plugins compiled as shared libraries and loaded at startup from the directory of the [`DynamicPluginsConfig`].
+//!
+//! A dynamic plugin is a `cdylib` crate depending on the same Tauri version as the app and built with the same Rust compiler,
+//! since the plugins are passed as Rust trait objects. It exports its plugin with [`export_plugin!`](crate::export_plugin):
+//!
+//! ```ignore
+//! use tauri::{plugin::{Builder, TauriPlugin}, Wry};
+//!
+//! fn init() -> TauriPlugin<Wry> {
+//!   Builder::new("extension").build()
+//! }
+//!
+//! tauri::export_plugin!(init);
+//! ```
+//!
+//! Each library must be signed with `tauri signer sign` using the private key of the configured public key,
+//! the library is only loaded if the `.sig` file next to it is valid.
+//!
+//! Dynamic plugins are only supported with the [`Wry`] runtime, and the libraries are never unloaded.
+
+use std::{
+  any::{Any, TypeId},
+  ffi::{c_void, CStr, OsString},
+  os::raw::c_char,
+  path::{Path, PathBuf},
+};
+
+use base64::Engine;
+
+use crate::{
+  plugin::Plugin, utils::config::DynamicPluginsConfig, AppHandle, Error, Manager, Runtime, Wry,
+};
+
+/// The version of the [`PluginDeclaration`] layout.
+pub const ABI_VERSION: u32 = 1;
+/// The Tauri version, as a NUL-terminated string.
+pub const TAURI_VERSION: &str = concat!(env!("CARGO_PKG_VERSION"), "\0");
+/// The version of the Rust compiler that built Tauri, as a NUL-terminated string.
+pub const RUSTC_VERSION: &str = concat!(env!("TAURI_RUSTC_VERSION"), "\0");
+/// The [`RUSTC_VERSION`] when the version of the compiler can't be read, no library is loaded then.
+const UNKNOWN_RUSTC_VERSION: &str = "unknown\0";
+/// The symbol of the [`PluginDeclaration`] exported by [`export_plugin!`](crate::export_plugin).
+pub const DECLARATION_SYMBOL: &[u8] = b"TAURI_PLUGIN_DECLARATION\0";
+
+/// The declaration a dynamic plugin library exports, checked before its plugins are registered.
+///
+/// It only has C types so it can be read regardless of the compiler that built the library.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct PluginDeclaration {
+  /// The [`ABI_VERSION`] of the library.
+  pub abi_version: u32,
+  /// The [`RUSTC_VERSION`] of the library.
+  pub rustc_version: *const c_char,
+  /// The [`TAURI_VERSION`] of the library.
+  pub tauri_version: *const c_char,
+  /// Registers the plugins of the library, called with a `*mut PluginRegistrar`.
+  pub register: unsafe extern "C" fn(*mut c_void),
+}
+
+// the declaration is a static of the library pointing to its static strings
+unsafe impl Sync for PluginDeclaration {}
+
+/// Collects the plugins of a dynamic plugin library.
+#[derive(Default)]
+pub struct PluginRegistrar {
+  plugins: Vec<Box<dyn Plugin<Wry>>>,
+}
+
+impl PluginRegistrar {
+  /// Registers a plugin of the library.
+  pub fn register<P: Plugin<Wry> + 'static>(&mut self, plugin: P) {
+    self.plugins.push(Box::new(plugin));
+  }
+}
+
+/// Exports the plugin returned by the given function from a dynamic plugin library.
+///
+/// See the [`plugin::dynamic`](crate::plugin::dynamic) module.
+#[macro_export]
+macro_rules! export_plugin {
+  ($init:path) => {
+    #[doc(hidden)]
+    #[no_mangle]
+    pub static TAURI_PLUGIN_DECLARATION: $crate::plugin::dynamic::PluginDeclaration =
+      $crate::plugin::dynamic::PluginDeclaration {
+        abi_version: $crate::plugin::dynamic::ABI_VERSION,
+        rustc_version: $crate::plugin::dynamic::RUSTC_VERSION.as_ptr()
+          as *const ::std::os::raw::c_char,
+        tauri_version: $crate::plugin::dynamic::TAURI_VERSION.as_ptr()
+          as *const ::std::os::raw::c_char,
+        register: {
+          unsafe extern "C" fn register(registrar: *mut ::std::ffi::c_void) {
+            let registrar = &mut *(registrar as *mut $crate::plugin::dynamic::PluginRegistrar);
+            registrar.register($init());
+          }
+          register
+        },
+      };
+  };
+}
+
+/// Loads the plugins of the configured directory, skipping the libraries that can't be verified or loaded.
+pub(crate) fn load<R: Runtime>(app: &AppHandle<R>) -> Vec<Box<dyn Plugin<R>>> {
+  let Some(config) = &app.config().app.dynamic_plugins else {
+    return Vec::new();
+  };
+  // the plugins are only built for the Wry runtime
+  if TypeId::of::<R>() != TypeId::of::<Wry>() {
+    log::error!("dynamic plugins are only supported with the Wry runtime");
+    return Vec::new();
+  }
+
+  let directory = if config.directory.is_absolute() {
+    config.directory.clone()
+  } else {
+    match app.path().app_data_dir() {
+      Ok(app_data_dir) => app_data_dir.join(&config.directory),
+      Err(e) => {
+        log::error!("failed to resolve the dynamic plugins directory: {e}");
+        return Vec::new();
+      }
+    }
+  };
+  let Ok(entries) = std::fs::read_dir(&directory) else {
+    return Vec::new();
+  };
+
+  let mut libraries = entries
+    .flatten()
+    .map(|entry| entry.path())
+    .filter(|path| {
+      path.extension().and_then(|e| e.to_str()) == Some(std::env::consts::DLL_EXTENSION)
+    })
+    .collect::<Vec<_>>();
+  // load the libraries in a stable order
+  libraries.sort();
+
+  let mut plugins = Vec::new();
+  for path in libraries {
+    match load_library(config, &path) {
+      Ok(library_plugins) => plugins.extend(library_plugins.into_iter().filter_map(|plugin| {
+        (Box::new(plugin) as Box<dyn Any>)
+          .downcast::<Box<dyn Plugin<R>>>()
+          .ok()
+          .map(|plugin| *plugin)
+      })),
+      Err(e) => log::error!("{e}"),
+    }
+  }
+  plugins
+}
+
+fn load_library(
+  config: &DynamicPluginsConfig,
+  path: &Path,
+) -> crate::Result<Vec<Box<dyn Plugin<Wry>>>> {
+  let error = |message: String| Error::DynamicPlugin(path.to_path_buf(), message);
+
+  if RUSTC_VERSION == UNKNOWN_RUSTC_VERSION {
+    return Err(error(
+      "the version of the Rust compiler that built the app is unknown".into(),
+    ));
+  }
+
+  let data = std::fs::read(path).map_err(|e| error(e.to_string()))?;
+  verify_signature(&config.pubkey, &data, &signature_path(path)).map_err(error)?;
+
+  // the directory is writable by the user so the verified bytes are loaded from a private copy,
+  // otherwise the library could be replaced between its verification and its loading
+  let file_name = path.file_name().unwrap_or_default();
+  let copy = private_copy::PrivateCopy::new(&data, file_name).map_err(|e| error(e.to_string()))?;
+
+  // the library is never unloaded since the plugin code and values can't outlive it
+  let library =
+    unsafe { libloading::Library::new(copy.path()) }.map_err(|e| error(e.to_string()))?;
+  let library: &'static libloading::Library = Box::leak(Box::new(library));
+  drop(copy);
+
+  let declaration = unsafe {
+    let declaration = library
+      .get::<*const PluginDeclaration>(DECLARATION_SYMBOL)
+      .map_err(|e| error(format!("missing `tauri::export_plugin!` declaration: {e}")))?;
+    let declaration = *declaration;
+
+    // the rest of the declaration can only be read if the layout matches
+    let abi_version = std::ptr::addr_of!((*declaration).abi_version).read();
+    if abi_version != ABI_VERSION {
+      return Err(error(format!(
+        "unsupported ABI version {abi_version}, expected {ABI_VERSION}"
+      )));
+    }
+    declaration.read()
+  };
+
+  for (name, version, expected) in [
+    ("Rust compiler", declaration.rustc_version, RUSTC_VERSION),
+    ("Tauri", declaration.tauri_version, TAURI_VERSION),
+  ] {
+    let version = unsafe { CStr::from_ptr(version) }.to_string_lossy();
+    let expected = expected.trim_end_matches('\0');
+    if version != expected {
+      return Err(error(format!(
+        "built with {name} {version} but the app uses {expected}"
+      )));
+    }
+  }
+
+  let mut registrar = PluginRegistrar::default();
+  unsafe { (declaration.register)(&mut registrar as *mut PluginRegistrar as *mut c_void) };
+  Ok(registrar.plugins)
+}
+
+#[cfg(target_os = "linux")]
+mod private_copy {
+  use std::{
+    ffi::OsStr,
+    fs::File,
+    io::Write,
+    os::{
+      fd::{AsRawFd, FromRawFd},
+      raw::c_char,
+    },
+    path::PathBuf,
+  };
+
+  /// A sealed anonymous memory file, that can't be modified once written, not even by the user.
+  pub struct PrivateCopy(File);
+
+  impl PrivateCopy {
+    pub fn new(data: &[u8], _file_name: &OsStr) -> std::io::Result<Self> {
+      let fd = unsafe {
+        libc::memfd_create(
+          b"tauri-dynamic-plugin\0".as_ptr() as *const c_char,
+          libc::MFD_CLOEXEC | libc::MFD_ALLOW_SEALING,
+        )
+      };
+      if fd < 0 {
+        return Err(std::io::Error::last_os_error());
+      }
+      let mut file = unsafe { File::from_raw_fd(fd) };
+      file.write_all(data)?;
+      let seals = libc::F_SEAL_SEAL | libc::F_SEAL_SHRINK | libc::F_SEAL_GROW | libc::F_SEAL_WRITE;
+      if unsafe { libc::fcntl(fd, libc::F_ADD_SEALS, seals) } < 0 {
+        return Err(std::io::Error::last_os_error());
+      }
+      Ok(Self(file))
+    }
+
+    pub fn path(&self) -> PathBuf {
+      PathBuf::from(format!("/proc/self/fd/{}", self.0.as_raw_fd()))
+    }
+  }
+}
+
+#[cfg(not(target_os = "linux"))]
+mod private_copy {
+  use std::{
+    ffi::OsStr,
+    fs::{DirBuilder, OpenOptions},
+    io::Write,
+    path::PathBuf,
+  };
+
+  /// A read-only copy in a new directory only the user can access, removed once the library is loaded.
+  ///
+  /// On Windows the copy is kept open without sharing its write access, so it can't be modified while it is loaded,
+  /// and it is only removed from the temporary directory by the system since a loaded library can't be deleted.
+  pub struct PrivateCopy {
+    dir: PathBuf,
+    path: PathBuf,
+    #[cfg(windows)]
+    _file: std::fs::File,
+  }
+
+  impl PrivateCopy {
+    pub fn new(data: &[u8], file_name: &OsStr) -> std::io::Result<Self> {
+      let mut random = [0; 8];
+      getrandom::getrandom(&mut random)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e.to_string()))?;
+      let dir = std::env::temp_dir().join(format!(
+        "tauri-dynamic-plugin-{}",
+        u64::from_ne_bytes(random)
+      ));
+
+      let mut builder = DirBuilder::new();
+      #[cfg(unix)]
+      std::os::unix::fs::DirBuilderExt::mode(&mut builder, 0o700);
+      // fails if the directory exists, so it can't have been prepared by another process
+      builder.create(&dir)?;
+
+      let path = dir.join(file_name);
+      let result = OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&path)
+        .and_then(|mut file| {
+          file.write_all(data)?;
+          file.sync_all()?;
+          let mut permissions = file.metadata()?.permissions();
+          permissions.set_readonly(true);
+          file.set_permissions(permissions)
+        });
+      if let Err(e) = result {
+        let _ = std::fs::remove_dir_all(&dir);
+        return Err(e);
+      }
+
+      #[cfg(windows)]
+      let _file = {
+        use std::os::windows::fs::OpenOptionsExt;
+        /// `FILE_SHARE_READ`
+        const FILE_SHARE_READ: u32 = 1;
+        OpenOptions::new()
+          .read(true)
+          .share_mode(FILE_SHARE_READ)
+          .open(&path)?
+      };
+
+      Ok(Self {
+        dir,
+        path,
+        #[cfg(windows)]
+        _file,
+      })
+    }
+
+    pub fn path(&self) -> PathBuf {
+      self.path.clone()
+    }
+  }
+
+  impl Drop for PrivateCopy {
+    fn drop(&mut self) {
+      let _ = std::fs::remove_dir_all(&self.dir);
+    }
+  }
+}
+
+/// The `tauri signer sign` signature file, next to the signed file.
+fn signature_path(path: &Path) -> PathBuf {
+  let mut signature_path = OsString::from(path.as_os_str());
+  signature_path.push(".sig");
+  signature_path.into()
+}
+
+/// Verifies the base64 encoded minisign signature of the data, as created by `tauri signer sign`.
+fn verify_signature(pubkey: &str, data: &[u8], signature_path: &Path) -> Result<(), String> {
+  let decode = |value: &str| {
+    base64::engine::general_purpose::STANDARD
+      .decode(value.trim())
+      .ok()
+      .and_then(|decoded| String::from_utf8(decoded).ok())
+  };
+
+  let pubkey = decode(pubkey)
+    .and_then(|pubkey| minisign_verify::PublicKey::decode(&pubkey).ok())
+    .ok_or_else(|| "invalid dynamic plugins public key".to_string())?;
+  let signature = std::fs::read_to_string(signature_path)
+    .map_err(|e| format!("failed to read signature {}: {e}", signature_path.display()))?;
+  let signature = decode(&signature)
+    .and_then(|signature| minisign_verify::Signature::decode(&signature).ok())
+    .ok_or_else(|| format!("invalid signature {}", signature_path.display()))?;
+
+  pubkey
+    .verify(data, &signature, true)
+    .map_err(|e| format!("signature verification failed: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+  use std::path::Path;
+
+  use super::{private_copy::PrivateCopy, signature_path, verify_signature};
+
+  #[test]
+  fn signatures() {
+    let library = Path::new("plugins").join("libextension.so");
+    assert_eq!(
+      signature_path(&library),
+      Path::new("plugins").join("libextension.so.sig")
+    );
+
+    let error = verify_signature("not a key", b"library", &signature_path(&library)).unwrap_err();
+    assert_eq!(error, "invalid dynamic plugins public key");
+  }
+
+  #[test]
+  fn private_copy() {
+    let copy = PrivateCopy::new(b"library", "libextension.so".as_ref()).unwrap();
+    assert_eq!(std::fs::read(copy.path()).unwrap(), b"library");
+    // the verified bytes can't be modified
+    assert!(std::fs::OpenOptions::new()
+      .write(true)
+      .open(copy.path())
+      .and_then(|mut file| std::io::Write::write_all(&mut file, b"tampered"))
+      .is_err());
+  }
+}
@@ -0,0 +1,165 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::path::{Component, Path, PathBuf};
+
+use tauri_utils::config::FsScope;
+
+use crate::{scope::fs::Scope, AppHandle, Manager, Runtime};
+
+/// The directory of the app directories containing the directory of each plugin,
+/// distinct from the `plugins` directory of the dynamic plugins.
+const PLUGINS_DIR: &str = "plugin-data";
+
+/// The directories of a plugin, namespaced by the plugin name inside the app directories
+/// so the plugins can't clobber each other's files.
+///
+/// Each directory resolves to the app directory followed by `plugin-data/${plugin_name}`,
+/// e.g. [`Self::data_dir`] resolves to [`PathResolver::app_data_dir`](crate::path::PathResolver::app_data_dir)`/plugin-data/${plugin_name}`.
+/// The directories are not created, and resolving them fails with [`Error::InvalidPluginName`](crate::Error::InvalidPluginName)
+/// if the plugin name is not a single directory name, e.g. `..` or `store/settings`.
+pub struct PluginPaths<R: Runtime> {
+  name: String,
+  app: AppHandle<R>,
+}
+
+impl<R: Runtime> Clone for PluginPaths<R> {
+  fn clone(&self) -> Self {
+    Self {
+      name: self.name.clone(),
+      app: self.app.clone(),
+    }
+  }
+}
+
+impl<R: Runtime> PluginPaths<R> {
+  pub(crate) fn new(app: AppHandle<R>, name: impl Into<String>) -> Self {
+    Self {
+      name: name.into(),
+      app,
+    }
+  }
+
+  fn namespaced(&self, dir: crate::Result<PathBuf>) -> crate::Result<PathBuf> {
+    let mut components = Path::new(&self.name).components();
+    match (components.next(), components.next()) {
+      (Some(Component::Normal(name)), None) if name == self.name.as_str() => {
+        dir.map(|dir| dir.join(PLUGINS_DIR).join(name))
+      }
+      _ => Err(crate::Error::InvalidPluginName(self.name.clone())),
+    }
+  }
+
+  /// Returns the path to the directory for the plugin's config files.
+  pub fn config_dir(&self) -> crate::Result<PathBuf> {
+    self.namespaced(self.app.path().app_config_dir())
+  }
+
+  /// Returns the path to the directory for the plugin's data files.
+  pub fn data_dir(&self) -> crate::Result<PathBuf> {
+    self.namespaced(self.app.path().app_data_dir())
+  }
+
+  /// Returns the path to the directory for the plugin's local data files.
+  pub fn local_data_dir(&self) -> crate::Result<PathBuf> {
+    self.namespaced(self.app.path().app_local_data_dir())
+  }
+
+  /// Returns the path to the directory for the plugin's cache files.
+  pub fn cache_dir(&self) -> crate::Result<PathBuf> {
+    self.namespaced(self.app.path().app_cache_dir())
+  }
+
+  /// Returns the path to the directory for the plugin's log files.
+  pub fn log_dir(&self) -> crate::Result<PathBuf> {
+    self.namespaced(self.app.path().app_log_dir())
+  }
+
+  fn dirs(&self) -> crate::Result<Vec<PathBuf>> {
+    Ok(vec![
+      self.config_dir()?,
+      self.data_dir()?,
+      self.local_data_dir()?,
+      self.cache_dir()?,
+      self.log_dir()?,
+    ])
+  }
+
+  /// A filesystem scope only allowing the plugin directories,
+  /// to check the paths the plugin commands receive before accessing them.
+  pub fn scope(&self) -> crate::Result<Scope> {
+    let scope = Scope::new(&self.app, &FsScope::default())?;
+    for dir in self.dirs()? {
+      scope.allow_directory(dir, true)?;
+    }
+    Ok(scope)
+  }
+
+  /// Removes the plugin directories and their content, e.g. when the plugin is uninstalled.
+  pub fn remove_all(&self) -> crate::Result<()> {
+    let mut dirs = self.dirs()?;
+    // the app directories can be the same, e.g. the data and local data directories on Linux
+    dirs.sort();
+    dirs.dedup();
+    for dir in dirs {
+      if dir.exists() {
+        std::fs::remove_dir_all(dir)?;
+      }
+    }
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{test::mock_app, Manager};
+
+  #[test]
+  fn namespaced_dirs() {
+    let app = mock_app();
+    let paths = app.handle().plugin_paths("store");
+
+    let data_dir = paths.data_dir().unwrap();
+    assert_eq!(
+      data_dir,
+      app
+        .path()
+        .app_data_dir()
+        .unwrap()
+        .join("plugin-data")
+        .join("store")
+    );
+
+    let scope = paths.scope().unwrap();
+    assert!(scope.is_allowed(data_dir.join("settings.json")));
+    assert!(scope.is_allowed(paths.cache_dir().unwrap().join("images").join("1.png")));
+    assert!(!scope.is_allowed(
+      app
+        .handle()
+        .plugin_paths("updater")
+        .data_dir()
+        .unwrap()
+        .join("settings.json")
+    ));
+    assert!(!scope.is_allowed(app.path().app_data_dir().unwrap().join("settings.json")));
+  }
+
+  #[test]
+  fn rejects_invalid_names() {
+    let app = mock_app();
+    for name in [
+      "",
+      ".",
+      "..",
+      "store/..",
+      "../store",
+      "/store",
+      "store/settings",
+    ] {
+      let paths = app.handle().plugin_paths(name);
+      assert!(paths.data_dir().is_err(), "{name}");
+      assert!(paths.remove_all().is_err(), "{name}");
+    }
+  }
+}
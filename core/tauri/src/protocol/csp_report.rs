@@ -0,0 +1,158 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::sync::Arc;
+
+use http::{header::ACCESS_CONTROL_ALLOW_ORIGIN, Method, Response as HttpResponse, StatusCode};
+use serde::Deserialize;
+
+use crate::{
+  manager::AppManager,
+  webview::{CspViolationReport, UriSchemeProtocolHandler},
+  Runtime,
+};
+
+/// The URI scheme that receives Content-Security-Policy violation reports.
+pub const CSP_REPORT_PROTOCOL: &str = "csp-report";
+
+/// The `report-uri` value injected in the Content-Security-Policy.
+pub fn report_uri() -> String {
+  if cfg!(windows) || cfg!(target_os = "android") {
+    format!("http://{CSP_REPORT_PROTOCOL}.localhost/")
+  } else {
+    format!("{CSP_REPORT_PROTOCOL}://localhost/")
+  }
+}
+
+pub fn get<R: Runtime>(manager: Arc<AppManager<R>>, label: String) -> UriSchemeProtocolHandler {
+  Box::new(move |request, responder| {
+    let status = if request.method() == Method::POST {
+      match parse_reports(request.body()) {
+        Ok(reports) => {
+          if let (Some(handler), Some(webview)) = (
+            &manager.webview.on_csp_violation,
+            manager.get_webview(&label),
+          ) {
+            for report in &reports {
+              handler(&webview, report);
+            }
+          }
+          StatusCode::NO_CONTENT
+        }
+        Err(e) => {
+          log::warn!("failed to parse CSP violation report: {e}");
+          StatusCode::BAD_REQUEST
+        }
+      }
+    } else {
+      StatusCode::METHOD_NOT_ALLOWED
+    };
+
+    responder.respond(
+      HttpResponse::builder()
+        .status(status)
+        .header(ACCESS_CONTROL_ALLOW_ORIGIN, "*")
+        .body(Vec::new())
+        .unwrap(),
+    );
+  })
+}
+
+/// Parses both the legacy `application/csp-report` body
+/// and the Reporting API `application/reports+json` body.
+fn parse_reports(body: &[u8]) -> serde_json::Result<Vec<CspViolationReport>> {
+  #[derive(Deserialize)]
+  struct LegacyReport {
+    #[serde(rename = "csp-report")]
+    report: CspViolationReport,
+  }
+
+  #[derive(Deserialize)]
+  struct Report {
+    #[serde(rename = "type")]
+    kind: String,
+    body: CspViolationReport,
+  }
+
+  if let Ok(legacy) = serde_json::from_slice::<LegacyReport>(body) {
+    return Ok(vec![legacy.report]);
+  }
+
+  let reports: Vec<Report> = serde_json::from_slice(body)?;
+  Ok(
+    reports
+      .into_iter()
+      .filter(|r| r.kind == "csp-violation")
+      .map(|r| r.body)
+      .collect(),
+  )
+}
+
+#[cfg(test)]
+mod tests {
+  use super::parse_reports;
+
+  #[test]
+  fn parses_legacy_report() {
+    let body = br#"{
+      "csp-report": {
+        "document-uri": "tauri://localhost/",
+        "violated-directive": "script-src-elem",
+        "effective-directive": "script-src-elem",
+        "original-policy": "script-src 'self'",
+        "disposition": "enforce",
+        "blocked-uri": "https://evil.example/x.js",
+        "line-number": 10,
+        "status-code": 200
+      }
+    }"#;
+    let reports = parse_reports(body).unwrap();
+    assert_eq!(reports.len(), 1);
+    let report = &reports[0];
+    assert_eq!(report.document_uri.as_deref(), Some("tauri://localhost/"));
+    assert_eq!(
+      report.blocked_uri.as_deref(),
+      Some("https://evil.example/x.js")
+    );
+    assert_eq!(report.line_number, Some(10));
+  }
+
+  #[test]
+  fn parses_reporting_api_report() {
+    let body = br#"[
+      {
+        "type": "csp-violation",
+        "age": 10,
+        "url": "tauri://localhost/",
+        "body": {
+          "documentURL": "tauri://localhost/",
+          "blockedURL": "inline",
+          "effectiveDirective": "script-src-elem",
+          "originalPolicy": "script-src 'self'",
+          "disposition": "enforce",
+          "sample": "alert(1)",
+          "statusCode": 200
+        }
+      },
+      {
+        "type": "deprecation",
+        "body": {}
+      }
+    ]"#;
+    let reports = parse_reports(body).unwrap();
+    assert_eq!(reports.len(), 1);
+    let report = &reports[0];
+    assert_eq!(report.blocked_uri.as_deref(), Some("inline"));
+    assert_eq!(report.script_sample.as_deref(), Some("alert(1)"));
+    assert_eq!(
+      report.effective_directive.as_deref(),
+      Some("script-src-elem")
+    );
+  }
+
+  #[test]
+  fn rejects_invalid_report() {
+    assert!(parse_reports(b"not json").is_err());
+  }
+}
@@ -4,6 +4,9 @@
 
 #[cfg(feature = "protocol-asset")]
 pub mod asset;
+pub mod csp_report;
 #[cfg(feature = "isolation")]
 pub mod isolation;
+#[cfg(desktop)]
+pub mod sidecar;
 pub mod tauri;
@@ -0,0 +1,224 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::sync::Arc;
+
+use http::{
+  header::{ACCESS_CONTROL_ALLOW_ORIGIN, CONTENT_TYPE, HOST, SET_COOKIE},
+  HeaderName, Response as HttpResponse, StatusCode,
+};
+
+use crate::{ipc::Origin, manager::AppManager, webview::UriSchemeProtocolHandler, Runtime};
+
+/// The URI scheme forwarding the requests to the [proxied](crate::sidecar::Sidecar::proxy) sidecars.
+pub const SIDECAR_PROTOCOL: &str = "sidecar";
+
+/// The URL of the protocol, followed by the name of the sidecar.
+pub fn base_url() -> String {
+  if cfg!(windows) || cfg!(target_os = "android") {
+    format!("http://{SIDECAR_PROTOCOL}.localhost/")
+  } else {
+    format!("{SIDECAR_PROTOCOL}://localhost/")
+  }
+}
+
+/// The command of the permission granting the webviews access to the protocol, `core:sidecar:allow-proxy`.
+const PROXY_COMMAND: &str = "plugin:sidecar|proxy";
+
+/// The headers of a single connection, which can't be forwarded.
+const HOP_BY_HOP_HEADERS: &[&str] = &[
+  "connection",
+  "keep-alive",
+  "proxy-authenticate",
+  "proxy-authorization",
+  "proxy-connection",
+  "te",
+  "trailer",
+  "transfer-encoding",
+  "upgrade",
+];
+
+/// Whether the request header is forwarded to the sidecar.
+fn is_forwarded_request_header(name: &HeaderName) -> bool {
+  name != HOST && !HOP_BY_HOP_HEADERS.contains(&name.as_str())
+}
+
+/// Whether the response header of the sidecar is forwarded to the webview.
+///
+/// The sidecar can't set cookies nor relax the CORS policy of the protocol, which is set by the app.
+fn is_forwarded_response_header(name: &HeaderName) -> bool {
+  name != SET_COOKIE
+    && !name.as_str().starts_with("access-control-")
+    && !HOP_BY_HOP_HEADERS.contains(&name.as_str())
+}
+
+/// Whether the webview shows a local page and is granted the `core:sidecar:allow-proxy` permission.
+fn is_allowed<R: Runtime>(manager: &AppManager<R>, label: &str) -> bool {
+  let Some(webview) = manager.get_webview(label) else {
+    return false;
+  };
+  // the remote pages can't reach the sidecars, regardless of their capabilities
+  let is_local = webview
+    .url()
+    .map_or(false, |url| webview.is_local_url(&url));
+  is_local
+    && manager
+      .runtime_authority
+      .lock()
+      .unwrap()
+      .resolve_access(
+        PROXY_COMMAND,
+        webview.window().label(),
+        webview.label(),
+        &Origin::Local,
+      )
+      .is_some()
+}
+
+/// Splits the path of the request in the sidecar name and the path forwarded to the sidecar.
+fn split_path(path: &str) -> Option<(String, &str)> {
+  let path = path.strip_prefix('/')?;
+  let (name, rest) = match path.find('/') {
+    Some(i) => (&path[..i], &path[i..]),
+    None => (path, "/"),
+  };
+  let name = percent_encoding::percent_decode_str(name)
+    .decode_utf8()
+    .ok()?
+    .into_owned();
+  (!name.is_empty()).then_some((name, rest))
+}
+
+pub fn get<R: Runtime>(
+  manager: Arc<AppManager<R>>,
+  window_origin: &str,
+  label: String,
+) -> UriSchemeProtocolHandler {
+  let window_origin = window_origin.to_string();
+  // redirects are returned to the webview instead of being followed by the proxy,
+  // otherwise a sidecar could make the app request any URL on behalf of the webview
+  let client = reqwest::Client::builder()
+    .redirect(reqwest::redirect::Policy::none())
+    .build()
+    .expect("failed to build the sidecar proxy client");
+  Box::new(move |request, responder| {
+    if !is_allowed(&manager, &label) {
+      responder.respond(error_response(
+        StatusCode::FORBIDDEN,
+        &window_origin,
+        "the webview is not allowed to use the sidecar proxy, see the `core:sidecar:allow-proxy` permission"
+          .into(),
+      ));
+      return;
+    }
+    let Some((name, path)) = split_path(request.uri().path()) else {
+      responder.respond(error_response(
+        StatusCode::NOT_FOUND,
+        &window_origin,
+        "missing sidecar name".into(),
+      ));
+      return;
+    };
+    let Some(port) = manager.sidecars.proxy_port(&name) else {
+      responder.respond(error_response(
+        StatusCode::BAD_GATEWAY,
+        &window_origin,
+        format!("the `{name}` sidecar is not running or not proxied"),
+      ));
+      return;
+    };
+    let url = match request.uri().query() {
+      Some(query) => format!("http://127.0.0.1:{port}{path}?{query}"),
+      None => format!("http://127.0.0.1:{port}{path}"),
+    };
+
+    let (parts, body) = request.into_parts();
+    let mut builder = client.request(parts.method, &url).body(body);
+    for (name, value) in &parts.headers {
+      if is_forwarded_request_header(name) {
+        builder = builder.header(name, value);
+      }
+    }
+    let window_origin = window_origin.clone();
+    // forwarded on the async runtime as the protocols are called on the main thread on some platforms
+    crate::async_runtime::spawn(async move {
+      let response = async {
+        let response = builder.send().await?;
+        let mut http_response = HttpResponse::builder().status(response.status());
+        for (name, value) in response.headers() {
+          if is_forwarded_response_header(name) {
+            http_response = http_response.header(name, value);
+          }
+        }
+        let body = response.bytes().await?;
+        Ok::<_, reqwest::Error>(
+          http_response
+            .header(ACCESS_CONTROL_ALLOW_ORIGIN, &window_origin)
+            .body(body.to_vec()),
+        )
+      };
+      match response.await {
+        Ok(Ok(response)) => responder.respond(response),
+        Ok(Err(e)) => responder.respond(error_response(
+          StatusCode::BAD_GATEWAY,
+          &window_origin,
+          e.to_string(),
+        )),
+        Err(e) => {
+          log::error!("failed to request {url}: {e}");
+          responder.respond(error_response(
+            StatusCode::BAD_GATEWAY,
+            &window_origin,
+            e.to_string(),
+          ))
+        }
+      }
+    });
+  })
+}
+
+fn error_response(
+  status: StatusCode,
+  window_origin: &str,
+  message: String,
+) -> HttpResponse<Vec<u8>> {
+  HttpResponse::builder()
+    .status(status)
+    .header(CONTENT_TYPE, mime::TEXT_PLAIN.essence_str())
+    .header(ACCESS_CONTROL_ALLOW_ORIGIN, window_origin)
+    .body(message.into_bytes())
+    .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+  use http::HeaderName;
+
+  use super::{is_forwarded_request_header, is_forwarded_response_header, split_path};
+
+  #[test]
+  fn splits_sidecar_path() {
+    assert_eq!(
+      split_path("/backend/api/items"),
+      Some(("backend".into(), "/api/items"))
+    );
+    assert_eq!(split_path("/my%20server"), Some(("my server".into(), "/")));
+    assert_eq!(split_path("/"), None);
+  }
+
+  #[test]
+  fn filters_headers() {
+    let header = HeaderName::from_static;
+    assert!(is_forwarded_request_header(&header("accept")));
+    assert!(!is_forwarded_request_header(&header("host")));
+    assert!(!is_forwarded_request_header(&header("connection")));
+
+    assert!(is_forwarded_response_header(&header("content-type")));
+    assert!(!is_forwarded_response_header(&header("set-cookie")));
+    assert!(!is_forwarded_response_header(&header(
+      "access-control-allow-origin"
+    )));
+    assert!(!is_forwarded_response_header(&header("transfer-encoding")));
+  }
+}
@@ -4,7 +4,12 @@
 
 use std::{borrow::Cow, sync::Arc};
 
-use http::{header::CONTENT_TYPE, Request, Response as HttpResponse, StatusCode};
+use http::{
+  header::{
+    ACCEPT_ENCODING, CACHE_CONTROL, CONTENT_ENCODING, CONTENT_TYPE, ETAG, IF_NONE_MATCH, VARY,
+  },
+  Request, Response as HttpResponse, StatusCode,
+};
 
 use crate::{
   manager::{webview::PROXY_DEV_SERVER, AppManager},
@@ -148,12 +153,47 @@ fn get_response<R: Runtime>(
 
   #[cfg(not(all(dev, mobile)))]
   let mut response = {
-    let asset = manager.get_asset(path)?;
-    builder = builder.header(CONTENT_TYPE, &asset.mime_type);
-    if let Some(csp) = &asset.csp_header {
-      builder = builder.header("Content-Security-Policy", csp);
+    let accept_encoding = request
+      .headers()
+      .get(ACCEPT_ENCODING)
+      .and_then(|value| value.to_str().ok());
+    let asset = manager.get_negotiated_asset(path, accept_encoding)?;
+
+    // the body depends on the `Accept-Encoding` header whenever the asset has a compressed variant
+    builder = builder.header(VARY, ACCEPT_ENCODING.as_str());
+    if asset.immutable {
+      builder = builder.header(CACHE_CONTROL, "public, max-age=31536000, immutable");
+    }
+    if let Some(etag) = &asset.etag {
+      builder = builder.header(ETAG, etag);
+      if !asset.immutable {
+        builder = builder.header(CACHE_CONTROL, "no-cache");
+      }
+    }
+
+    let not_modified = asset.etag.as_deref().map_or(false, |etag| {
+      request
+        .headers()
+        .get(IF_NONE_MATCH)
+        .and_then(|value| value.to_str().ok())
+        .map_or(false, |if_none_match| {
+          if_none_match.split(',').any(|tag| tag.trim() == etag)
+        })
+    });
+    if not_modified {
+      builder
+        .status(StatusCode::NOT_MODIFIED)
+        .body(Vec::new().into())?
+    } else {
+      builder = builder.header(CONTENT_TYPE, &asset.mime_type);
+      if let Some(encoding) = &asset.content_encoding {
+        builder = builder.header(CONTENT_ENCODING, encoding);
+      }
+      if let Some(csp) = &asset.csp_header {
+        builder = builder.header("Content-Security-Policy", csp);
+      }
+      builder.body(asset.bytes.into())?
     }
-    builder.body(asset.bytes.into())?
   };
   if let Some(handler) = &web_resource_request_handler {
     handler(request, &mut response);
@@ -0,0 +1,565 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! An opt-in JSON-RPC 2.0 interface to control the running app from scripts, enabled with [`Builder::remote_control`](crate::Builder::remote_control).
+//!
+//! The interface is only reachable locally: on a Unix socket with the `0600` permissions, a named pipe rejecting the remote clients,
+//! or a loopback TCP port requiring a client certificate signed by the configured certificate authority (mTLS).
+//!
+//! The requests and responses are JSON objects, one per line. A request longer than [`MAX_REQUEST_LENGTH`] closes the connection.
+//! The methods are:
+//!
+//! - `window.list`: returns the labels of the windows.
+//! - `window.open`: opens a window, with the `label`, the `url` of an app page or an external site, and the optional `title`.
+//! - `window.close`, `window.show`, `window.focus`: takes the `label` of the window.
+//! - `event.emit`: emits the `event` with the `payload` to all targets.
+//! - `command.invoke`: runs a [command](RemoteControl::command) of the interface, with its `command` name and `args`.
+//!
+//! ```rust,no_run
+//! use tauri::remote_control::RemoteControl;
+//!
+//! tauri::Builder::default().remote_control(
+//!   RemoteControl::unix_socket("/run/user/1000/my-app.sock")
+//!     .command("reload-config", |_app, _args| Ok(serde_json::Value::Null)),
+//! );
+//! ```
+//!
+//! ```sh
+//! echo '{"jsonrpc":"2.0","id":1,"method":"window.open","params":{"label":"logs","url":"logs.html"}}' \
+//!   | socat - UNIX-CONNECT:/run/user/1000/my-app.sock
+//! ```
+
+use std::{collections::HashMap, io, path::PathBuf, sync::Arc};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader};
+
+use crate::{AppHandle, Emitter, Manager, Runtime, WebviewUrl, WebviewWindowBuilder};
+
+type CommandHandler<R> = dyn Fn(&AppHandle<R>, Value) -> Result<Value, String> + Send + Sync;
+
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const APP_ERROR: i64 = -32000;
+
+/// The maximum length of a request line, in bytes.
+pub const MAX_REQUEST_LENGTH: usize = 1024 * 1024;
+
+/// Where the interface listens.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum RemoteControlTransport {
+  /// A Unix socket, created with the `0600` permissions in a private directory and then moved to the path,
+  /// so it is never reachable by the other users.
+  #[cfg(unix)]
+  UnixSocket(PathBuf),
+  /// A named pipe, e.g. `\\.\pipe\my-app`, rejecting the remote clients.
+  #[cfg(windows)]
+  NamedPipe(String),
+  /// A TCP port of the loopback interface, with TLS and client certificates.
+  Mtls {
+    /// The port.
+    port: u16,
+    /// The PEM certificate chain of the server.
+    certificate: Vec<u8>,
+    /// The PEM private key of the server.
+    private_key: Vec<u8>,
+    /// The PEM certificate authorities of the clients.
+    client_ca: Vec<u8>,
+  },
+}
+
+/// The configuration of the remote control interface, see the [module documentation](self).
+pub struct RemoteControl<R: Runtime> {
+  transport: RemoteControlTransport,
+  commands: HashMap<String, Box<CommandHandler<R>>>,
+}
+
+impl<R: Runtime> RemoteControl<R> {
+  /// Listens on the transport.
+  pub fn new(transport: RemoteControlTransport) -> Self {
+    Self {
+      transport,
+      commands: HashMap::new(),
+    }
+  }
+
+  /// Listens on a Unix socket, replaced if it exists.
+  #[cfg(unix)]
+  pub fn unix_socket(path: impl Into<PathBuf>) -> Self {
+    Self::new(RemoteControlTransport::UnixSocket(path.into()))
+  }
+
+  /// Listens on a named pipe.
+  #[cfg(windows)]
+  pub fn named_pipe(name: impl Into<String>) -> Self {
+    Self::new(RemoteControlTransport::NamedPipe(name.into()))
+  }
+
+  /// Listens on a loopback TCP port, accepting the TLS clients with a certificate signed by the `client_ca` certificates.
+  pub fn mtls(
+    port: u16,
+    certificate: impl Into<Vec<u8>>,
+    private_key: impl Into<Vec<u8>>,
+    client_ca: impl Into<Vec<u8>>,
+  ) -> Self {
+    Self::new(RemoteControlTransport::Mtls {
+      port,
+      certificate: certificate.into(),
+      private_key: private_key.into(),
+      client_ca: client_ca.into(),
+    })
+  }
+
+  /// Allows the command to be run with the `command.invoke` method, called on the async runtime with the `args` parameter.
+  #[must_use]
+  pub fn command<F: Fn(&AppHandle<R>, Value) -> Result<Value, String> + Send + Sync + 'static>(
+    mut self,
+    name: impl Into<String>,
+    handler: F,
+  ) -> Self {
+    self.commands.insert(name.into(), Box::new(handler));
+    self
+  }
+}
+
+#[derive(Deserialize)]
+struct RpcRequest {
+  jsonrpc: String,
+  #[serde(default)]
+  id: Option<Value>,
+  method: String,
+  #[serde(default)]
+  params: Value,
+}
+
+#[derive(Debug, Serialize)]
+struct RpcError {
+  code: i64,
+  message: String,
+}
+
+impl RpcError {
+  fn new(code: i64, message: impl Into<String>) -> Self {
+    Self {
+      code,
+      message: message.into(),
+    }
+  }
+}
+
+impl From<crate::Error> for RpcError {
+  fn from(e: crate::Error) -> Self {
+    Self::new(APP_ERROR, e.to_string())
+  }
+}
+
+#[derive(Serialize)]
+struct RpcResponse {
+  jsonrpc: &'static str,
+  id: Value,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  result: Option<Value>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  error: Option<RpcError>,
+}
+
+#[derive(Deserialize)]
+struct WindowParams {
+  label: String,
+}
+
+#[derive(Deserialize)]
+struct OpenParams {
+  label: String,
+  #[serde(default)]
+  url: Option<String>,
+  #[serde(default)]
+  title: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct EmitParams {
+  event: String,
+  #[serde(default)]
+  payload: Value,
+}
+
+#[derive(Deserialize)]
+struct InvokeParams {
+  command: String,
+  #[serde(default)]
+  args: Value,
+}
+
+fn params<T: serde::de::DeserializeOwned>(params: Value) -> Result<T, RpcError> {
+  serde_json::from_value(params).map_err(|e| RpcError::new(INVALID_PARAMS, e.to_string()))
+}
+
+fn window<R: Runtime>(
+  app: &AppHandle<R>,
+  params_: Value,
+) -> Result<crate::WebviewWindow<R>, RpcError> {
+  let WindowParams { label } = params(params_)?;
+  app
+    .get_webview_window(&label)
+    .ok_or_else(|| RpcError::new(INVALID_PARAMS, format!("window `{label}` not found")))
+}
+
+fn dispatch<R: Runtime>(
+  app: &AppHandle<R>,
+  control: &RemoteControl<R>,
+  method: &str,
+  params_: Value,
+) -> Result<Value, RpcError> {
+  match method {
+    "window.list" => Ok(
+      app
+        .webview_windows()
+        .into_keys()
+        .map(Value::String)
+        .collect(),
+    ),
+    "window.open" => {
+      let OpenParams { label, url, title } = params(params_)?;
+      let url = match url {
+        Some(url) => match url::Url::parse(&url) {
+          Ok(url) => WebviewUrl::External(url),
+          Err(_) => WebviewUrl::App(url.into()),
+        },
+        None => WebviewUrl::default(),
+      };
+      let mut builder = WebviewWindowBuilder::new(app, label, url);
+      if let Some(title) = title {
+        builder = builder.title(title);
+      }
+      builder.build()?;
+      Ok(Value::Null)
+    }
+    "window.close" => {
+      window(app, params_)?.close()?;
+      Ok(Value::Null)
+    }
+    "window.show" => {
+      window(app, params_)?.show()?;
+      Ok(Value::Null)
+    }
+    "window.focus" => {
+      window(app, params_)?.set_focus()?;
+      Ok(Value::Null)
+    }
+    "event.emit" => {
+      let EmitParams { event, payload } = params(params_)?;
+      app.emit(&event, payload)?;
+      Ok(Value::Null)
+    }
+    "command.invoke" => {
+      let InvokeParams { command, args } = params(params_)?;
+      let handler = control.commands.get(&command).ok_or_else(|| {
+        RpcError::new(
+          METHOD_NOT_FOUND,
+          format!("command `{command}` is not allowed"),
+        )
+      })?;
+      handler(app, args).map_err(|e| RpcError::new(APP_ERROR, e))
+    }
+    _ => Err(RpcError::new(
+      METHOD_NOT_FOUND,
+      format!("unknown method `{method}`"),
+    )),
+  }
+}
+
+/// Handles a request line, returning the response line if any.
+fn handle_line<R: Runtime>(
+  app: &AppHandle<R>,
+  control: &RemoteControl<R>,
+  line: &str,
+) -> Option<String> {
+  let (id, result) = match serde_json::from_str::<RpcRequest>(line) {
+    Ok(request) if request.jsonrpc == "2.0" => {
+      let result = dispatch(app, control, &request.method, request.params);
+      // notifications don't have responses
+      (request.id?, result)
+    }
+    Ok(request) => (
+      request.id.unwrap_or_default(),
+      Err(RpcError::new(
+        INVALID_REQUEST,
+        "the `jsonrpc` version must be `2.0`",
+      )),
+    ),
+    Err(e) => (Value::Null, Err(RpcError::new(PARSE_ERROR, e.to_string()))),
+  };
+  let (result, error) = match result {
+    Ok(result) => (Some(result), None),
+    Err(error) => (None, Some(error)),
+  };
+  serde_json::to_string(&RpcResponse {
+    jsonrpc: "2.0",
+    id,
+    result,
+    error,
+  })
+  .ok()
+}
+
+async fn serve_connection<R: Runtime, S: AsyncRead + AsyncWrite + Unpin>(
+  app: AppHandle<R>,
+  control: Arc<RemoteControl<R>>,
+  stream: S,
+) -> io::Result<()> {
+  let (reader, mut writer) = tokio::io::split(stream);
+  let mut reader = BufReader::new(reader);
+  let mut line = Vec::new();
+  loop {
+    line.clear();
+    // the line is read up to the maximum length so a client can't make the app buffer an endless line
+    let read = (&mut reader)
+      .take(MAX_REQUEST_LENGTH as u64 + 1)
+      .read_until(b'\n', &mut line)
+      .await?;
+    if read == 0 {
+      break;
+    }
+    if line.last() == Some(&b'\n') {
+      line.pop();
+    } else if line.len() > MAX_REQUEST_LENGTH {
+      return Err(io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("the request is longer than {MAX_REQUEST_LENGTH} bytes"),
+      ));
+    }
+    let line = String::from_utf8_lossy(&line);
+    if line.trim().is_empty() {
+      continue;
+    }
+    if let Some(mut response) = handle_line(&app, &control, &line) {
+      response.push('\n');
+      writer.write_all(response.as_bytes()).await?;
+      writer.flush().await?;
+    }
+  }
+  Ok(())
+}
+
+fn spawn_connection<R: Runtime, S: AsyncRead + AsyncWrite + Unpin + Send + 'static>(
+  app: &AppHandle<R>,
+  control: &Arc<RemoteControl<R>>,
+  stream: S,
+) {
+  let app = app.clone();
+  let control = control.clone();
+  crate::async_runtime::spawn(async move {
+    if let Err(e) = serve_connection(app, control, stream).await {
+      log::debug!("remote control connection error: {e}");
+    }
+  });
+}
+
+fn tls_acceptor(
+  certificate: &[u8],
+  private_key: &[u8],
+  client_ca: &[u8],
+) -> Result<tokio_rustls::TlsAcceptor, Box<dyn std::error::Error>> {
+  use tokio_rustls::rustls::{server::WebPkiClientVerifier, RootCertStore, ServerConfig};
+
+  let mut roots = RootCertStore::empty();
+  for cert in rustls_pemfile::certs(&mut &*client_ca) {
+    roots.add(cert?)?;
+  }
+  let verifier = WebPkiClientVerifier::builder(Arc::new(roots)).build()?;
+  let certificates = rustls_pemfile::certs(&mut &*certificate).collect::<Result<Vec<_>, _>>()?;
+  let key = rustls_pemfile::private_key(&mut &*private_key)?.ok_or("no private key found")?;
+  let config = ServerConfig::builder()
+    .with_client_cert_verifier(verifier)
+    .with_single_cert(certificates, key)?;
+  Ok(tokio_rustls::TlsAcceptor::from(Arc::new(config)))
+}
+
+async fn listen<R: Runtime>(
+  app: AppHandle<R>,
+  control: Arc<RemoteControl<R>>,
+  listener: Listener,
+) -> io::Result<()> {
+  match listener {
+    #[cfg(unix)]
+    Listener::Unix(listener) => {
+      let listener = tokio::net::UnixListener::from_std(listener)?;
+      loop {
+        let (stream, _) = listener.accept().await?;
+        spawn_connection(&app, &control, stream);
+      }
+    }
+    #[cfg(windows)]
+    Listener::NamedPipe(name) => {
+      use tokio::net::windows::named_pipe::ServerOptions;
+      let mut server = ServerOptions::new()
+        .first_pipe_instance(true)
+        .reject_remote_clients(true)
+        .create(&name)?;
+      loop {
+        server.connect().await?;
+        let connected = std::mem::replace(
+          &mut server,
+          ServerOptions::new()
+            .reject_remote_clients(true)
+            .create(&name)?,
+        );
+        spawn_connection(&app, &control, connected);
+      }
+    }
+    Listener::Tls(listener, acceptor) => {
+      let listener = tokio::net::TcpListener::from_std(listener)?;
+      loop {
+        let (stream, _) = listener.accept().await?;
+        let acceptor = acceptor.clone();
+        let app = app.clone();
+        let control = control.clone();
+        crate::async_runtime::spawn(async move {
+          match acceptor.accept(stream).await {
+            Ok(stream) => spawn_connection(&app, &control, stream),
+            Err(e) => log::warn!("rejected a remote control client: {e}"),
+          }
+        });
+      }
+    }
+  }
+}
+
+/// The listener, bound synchronously so the errors are reported when the app starts.
+enum Listener {
+  #[cfg(unix)]
+  Unix(std::os::unix::net::UnixListener),
+  #[cfg(windows)]
+  NamedPipe(String),
+  Tls(std::net::TcpListener, tokio_rustls::TlsAcceptor),
+}
+
+fn bind(transport: &RemoteControlTransport) -> Result<Listener, Box<dyn std::error::Error>> {
+  match transport {
+    #[cfg(unix)]
+    RemoteControlTransport::UnixSocket(path) => {
+      use std::os::unix::fs::{DirBuilderExt, PermissionsExt};
+
+      // the socket is bound in a directory only accessible by the user before its permissions are restricted,
+      // since binding creates it with the permissions allowed by the umask
+      let mut private_dir = path.file_name().unwrap_or_default().to_os_string();
+      private_dir.push(format!(".{}.tmp", std::process::id()));
+      let private_dir = path.with_file_name(private_dir);
+      if private_dir.exists() {
+        std::fs::remove_dir_all(&private_dir)?;
+      }
+      std::fs::DirBuilder::new()
+        .mode(0o700)
+        .create(&private_dir)?;
+      let bound = (|| {
+        let socket = private_dir.join("socket");
+        let listener = std::os::unix::net::UnixListener::bind(&socket)?;
+        std::fs::set_permissions(&socket, std::fs::Permissions::from_mode(0o600))?;
+        if path.exists() {
+          std::fs::remove_file(path)?;
+        }
+        std::fs::rename(&socket, path)?;
+        listener.set_nonblocking(true)?;
+        Ok::<_, io::Error>(listener)
+      })();
+      let _ = std::fs::remove_dir_all(&private_dir);
+      Ok(Listener::Unix(bound?))
+    }
+    #[cfg(windows)]
+    RemoteControlTransport::NamedPipe(name) => Ok(Listener::NamedPipe(name.clone())),
+    RemoteControlTransport::Mtls {
+      port,
+      certificate,
+      private_key,
+      client_ca,
+    } => {
+      let acceptor = tls_acceptor(certificate, private_key, client_ca)?;
+      let listener = std::net::TcpListener::bind((std::net::Ipv4Addr::LOCALHOST, *port))?;
+      listener.set_nonblocking(true)?;
+      Ok(Listener::Tls(listener, acceptor))
+    }
+  }
+}
+
+/// Starts the interface on the async runtime.
+pub(crate) fn start<R: Runtime>(
+  app: &AppHandle<R>,
+  control: RemoteControl<R>,
+) -> crate::Result<()> {
+  let listener =
+    bind(&control.transport).map_err(|e| crate::Error::RemoteControl(e.to_string()))?;
+  let app = app.clone();
+  crate::async_runtime::spawn(async move {
+    if let Err(e) = listen(app, Arc::new(control), listener).await {
+      log::error!("the remote control interface stopped: {e}");
+    }
+  });
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use serde_json::{json, Value};
+
+  use super::{handle_line, RemoteControl, RemoteControlTransport};
+  use crate::test::MockRuntime;
+
+  fn control() -> RemoteControl<MockRuntime> {
+    RemoteControl::new(RemoteControlTransport::Mtls {
+      port: 0,
+      certificate: Vec::new(),
+      private_key: Vec::new(),
+      client_ca: Vec::new(),
+    })
+    .command("sum", |_app, args| {
+      let numbers: Vec<i64> = serde_json::from_value(args).map_err(|e| e.to_string())?;
+      Ok(numbers.iter().sum::<i64>().into())
+    })
+  }
+
+  fn call(line: &str) -> Value {
+    let app = crate::test::mock_app();
+    serde_json::from_str(&handle_line(app.handle(), &control(), line).unwrap()).unwrap()
+  }
+
+  #[test]
+  fn invokes_allowed_commands() {
+    assert_eq!(
+      call(
+        r#"{"jsonrpc":"2.0","id":1,"method":"command.invoke","params":{"command":"sum","args":[1,2]}}"#
+      ),
+      json!({ "jsonrpc": "2.0", "id": 1, "result": 3 })
+    );
+    assert_eq!(
+      call(r#"{"jsonrpc":"2.0","id":2,"method":"command.invoke","params":{"command":"exit"}}"#)
+        ["error"]["code"],
+      super::METHOD_NOT_FOUND
+    );
+  }
+
+  #[test]
+  fn rejects_invalid_requests() {
+    assert_eq!(call("not json")["error"]["code"], super::PARSE_ERROR);
+    assert_eq!(
+      call(r#"{"jsonrpc":"1.0","id":1,"method":"window.list"}"#)["error"]["code"],
+      super::INVALID_REQUEST
+    );
+    assert_eq!(
+      call(r#"{"jsonrpc":"2.0","id":1,"method":"window.close","params":{}}"#)["error"]["code"],
+      super::INVALID_PARAMS
+    );
+    let app = crate::test::mock_app();
+    assert!(handle_line(
+      app.handle(),
+      &control(),
+      r#"{"jsonrpc":"2.0","method":"window.list"}"#
+    )
+    .is_none());
+  }
+}
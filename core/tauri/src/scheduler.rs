@@ -0,0 +1,260 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! The scheduler of the main thread tasks.
+//!
+//! [`AppHandle::run_on_main_thread`] hands each closure to the event loop in order.
+//! The tasks scheduled with [`AppHandle::schedule_on_main_thread`] are queued instead, and run in batches
+//! by priority, yielding to the event loop when a batch exceeds the [`FRAME_BUDGET`] so the UI doesn't stutter.
+//! The queued tasks sharing a [coalescing key](TaskOptions::coalesce) are merged, only the latest one runs.
+//!
+//! ```rust,no_run
+//! use tauri::scheduler::{Priority, TaskOptions};
+//!
+//! tauri::Builder::default().setup(|app| {
+//!   let handle = app.handle().clone();
+//!   std::thread::spawn(move || {
+//!     for progress in 0..100 {
+//!       // only the last progress update queued before the next batch is rendered
+//!       let _ = handle.schedule_on_main_thread(TaskOptions::new().coalesce("progress"), move || {
+//!         println!("progress: {progress}");
+//!       });
+//!     }
+//!     let _ = handle.schedule_on_main_thread(TaskOptions::new().priority(Priority::High), || {
+//!       println!("done");
+//!     });
+//!   });
+//!   Ok(())
+//! });
+//! ```
+
+use std::{
+  borrow::Cow,
+  sync::{Arc, Mutex},
+  time::{Duration, Instant},
+};
+
+use crate::{AppHandle, Runtime};
+
+/// The time a batch of tasks may take before the scheduler yields to the event loop.
+pub const FRAME_BUDGET: Duration = Duration::from_millis(8);
+
+/// The priority of a main thread task, the tasks with a higher priority run first.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Priority {
+  /// Work that can wait for the other tasks, e.g. prefetching.
+  Low,
+  /// The default priority.
+  #[default]
+  Normal,
+  /// Work that affects what the user sees right away, e.g. applying an input.
+  High,
+}
+
+/// The options of a task scheduled with [`AppHandle::schedule_on_main_thread`].
+#[derive(Debug, Default, Clone)]
+pub struct TaskOptions {
+  priority: Priority,
+  key: Option<Cow<'static, str>>,
+}
+
+impl TaskOptions {
+  /// Options with the [`Priority::Normal`] priority and no coalescing key.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Sets the priority of the task.
+  #[must_use]
+  pub fn priority(mut self, priority: Priority) -> Self {
+    self.priority = priority;
+    self
+  }
+
+  /// Sets the coalescing key of the task.
+  ///
+  /// A task replaces the queued task with the same key, keeping its place in the queue and the highest of their priorities.
+  #[must_use]
+  pub fn coalesce(mut self, key: impl Into<Cow<'static, str>>) -> Self {
+    self.key.replace(key.into());
+    self
+  }
+}
+
+struct QueuedTask {
+  priority: Priority,
+  /// Orders the tasks with the same priority by submission.
+  seq: u64,
+  key: Option<Cow<'static, str>>,
+  task: Box<dyn FnOnce() + Send>,
+}
+
+#[derive(Default)]
+struct SchedulerState {
+  queue: Vec<QueuedTask>,
+  next_seq: u64,
+  /// Whether a batch is running or was handed to the event loop.
+  draining: bool,
+}
+
+impl SchedulerState {
+  fn pop(&mut self) -> Option<QueuedTask> {
+    let index = self
+      .queue
+      .iter()
+      .enumerate()
+      .max_by(|(_, a), (_, b)| a.priority.cmp(&b.priority).then(b.seq.cmp(&a.seq)))
+      .map(|(i, _)| i)?;
+    Some(self.queue.remove(index))
+  }
+}
+
+/// The queue of the tasks scheduled with [`AppHandle::schedule_on_main_thread`].
+#[derive(Default)]
+pub(crate) struct MainThreadScheduler {
+  state: Mutex<SchedulerState>,
+}
+
+impl std::fmt::Debug for MainThreadScheduler {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    let state = self.state.lock().unwrap();
+    f.debug_struct("MainThreadScheduler")
+      .field("queued", &state.queue.len())
+      .field("draining", &state.draining)
+      .finish()
+  }
+}
+
+/// Resets the draining flag if a task panics, so the next task restarts the batches.
+struct DrainGuard<'a>(&'a MainThreadScheduler);
+
+impl Drop for DrainGuard<'_> {
+  fn drop(&mut self) {
+    if std::thread::panicking() {
+      self.0.state.lock().unwrap().draining = false;
+    }
+  }
+}
+
+impl MainThreadScheduler {
+  pub(crate) fn schedule<R: Runtime>(
+    self: &Arc<Self>,
+    app: &AppHandle<R>,
+    options: TaskOptions,
+    task: Box<dyn FnOnce() + Send>,
+  ) -> crate::Result<()> {
+    let mut state = self.state.lock().unwrap();
+    let queued = options
+      .key
+      .as_ref()
+      .and_then(|key| state.queue.iter_mut().find(|t| t.key.as_ref() == Some(key)));
+    if let Some(queued) = queued {
+      queued.priority = queued.priority.max(options.priority);
+      queued.task = task;
+    } else {
+      let seq = state.next_seq;
+      state.next_seq += 1;
+      state.queue.push(QueuedTask {
+        priority: options.priority,
+        seq,
+        key: options.key,
+        task,
+      });
+    }
+
+    if state.draining {
+      return Ok(());
+    }
+    state.draining = true;
+    drop(state);
+
+    let scheduler = self.clone();
+    let app_ = app.clone();
+    let result = app.run_on_main_thread(move || scheduler.drain(&app_));
+    if result.is_err() {
+      self.state.lock().unwrap().draining = false;
+    }
+    result
+  }
+
+  /// Runs the queued tasks until the queue is empty or the batch exceeds the [`FRAME_BUDGET`].
+  fn drain<R: Runtime>(self: &Arc<Self>, app: &AppHandle<R>) {
+    let _guard = DrainGuard(self);
+    let start = Instant::now();
+    loop {
+      let task = {
+        let mut state = self.state.lock().unwrap();
+        match state.pop() {
+          Some(task) => task,
+          None => {
+            state.draining = false;
+            return;
+          }
+        }
+      };
+      (task.task)();
+
+      if start.elapsed() >= FRAME_BUDGET {
+        // the main thread would run the next batch right away, so it is handed to the event loop from another thread
+        let scheduler = self.clone();
+        let app = app.clone();
+        crate::async_runtime::spawn(async move {
+          let app_ = app.clone();
+          let scheduler_ = scheduler.clone();
+          if let Err(e) = app.run_on_main_thread(move || scheduler_.drain(&app_)) {
+            log::error!("failed to schedule the main thread tasks: {e}");
+            scheduler.state.lock().unwrap().draining = false;
+          }
+        });
+        return;
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::sync::{Arc, Mutex};
+
+  use super::{Priority, TaskOptions};
+  use crate::test::mock_app;
+
+  #[test]
+  fn runs_batches_by_priority() {
+    let app = mock_app();
+    let handle = app.handle().clone();
+    let order = Arc::new(Mutex::new(Vec::new()));
+
+    let order_ = order.clone();
+    app
+      .handle()
+      .schedule_on_main_thread(TaskOptions::new(), move || {
+        // queued while the batch runs
+        let schedule = |options: TaskOptions, name: &'static str| {
+          let order = order_.clone();
+          handle
+            .schedule_on_main_thread(options, move || order.lock().unwrap().push(name))
+            .unwrap();
+        };
+        schedule(TaskOptions::new().priority(Priority::Low), "low");
+        schedule(TaskOptions::new().coalesce("progress"), "progress 1");
+        schedule(TaskOptions::new(), "normal");
+        schedule(TaskOptions::new().priority(Priority::High), "high");
+        schedule(TaskOptions::new().coalesce("progress"), "progress 2");
+      })
+      .unwrap();
+
+    assert_eq!(
+      *order.lock().unwrap(),
+      vec!["high", "progress 2", "normal", "low"]
+    );
+  }
+
+  #[test]
+  fn returns_value() {
+    let app = mock_app();
+    let value = crate::async_runtime::block_on(app.handle().run_on_main_thread_returning(|| 42));
+    assert_eq!(value.unwrap(), 42);
+  }
+}
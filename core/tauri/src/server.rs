@@ -0,0 +1,496 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! A local HTTP server, for the pages that need an origin with real HTTP semantics, e.g. with `EventSource` or service workers.
+//!
+//! The server runs on the [async runtime](crate::async_runtime) of the app and is shut down when the app exits.
+//! It listens on the loopback interface only, on a port chosen by the system unless [set](LocalServer::port).
+//!
+//! The requests must target the server as `localhost:<port>` or `127.0.0.1:<port>`, so websites can't reach it
+//! through a domain resolving to the loopback address, and the cross-origin requests are rejected
+//! unless their origin is [allowed](LocalServer::allow_origin).
+//! The [routes](LocalServer::route) also reject the requests that are not proven to come from an allowed origin,
+//! i.e. without an `Origin` header or a `Sec-Fetch-Site: same-origin` header.
+//!
+//! ```rust,no_run
+//! use tauri::{
+//!   http::{Method, Response},
+//!   server::{Body, LocalServer},
+//!   Manager,
+//! };
+//!
+//! tauri::Builder::default().setup(|app| {
+//!   let server = app.handle().serve(
+//!     LocalServer::new()
+//!       .serve_dir("/", app.path().resource_dir()?.join("site"))
+//!       .route(Method::GET, "/api/version", |_request| async {
+//!         Response::new(Body::from("1.0.0"))
+//!       }),
+//!   )?;
+//!   println!("serving on {}", server.url());
+//!   Ok(())
+//! });
+//! ```
+
+use std::{
+  convert::Infallible,
+  future::Future,
+  io,
+  net::{Ipv4Addr, SocketAddr, TcpListener as StdTcpListener},
+  path::{Component, Path, PathBuf},
+  pin::Pin,
+  sync::Arc,
+};
+
+use bytes::Bytes;
+use futures_util::{Stream, TryStreamExt};
+use http::{
+  header::{CONTENT_TYPE, HOST, ORIGIN},
+  HeaderMap, Method, Request, Response, StatusCode,
+};
+use http_body_util::{combinators::UnsyncBoxBody, BodyExt, Full, StreamBody};
+use hyper::{body::Frame, service::service_fn};
+use hyper_util::rt::TokioIo;
+use tauri_utils::mime_type::MimeType;
+use tokio::{net::TcpListener, sync::watch};
+use url::Url;
+
+use crate::{AppHandle, Runtime};
+
+type HandlerFuture = Pin<Box<dyn Future<Output = Response<Body>> + Send>>;
+type Handler = dyn Fn(Request<Bytes>) -> HandlerFuture + Send + Sync;
+
+/// The body of a response of a [`LocalServer`] route.
+pub struct Body(UnsyncBoxBody<Bytes, io::Error>);
+
+impl Body {
+  /// An empty body.
+  pub fn empty() -> Self {
+    Self::from(Bytes::new())
+  }
+
+  /// A body streamed as the chunks are produced, e.g. the events of an `EventSource`.
+  pub fn from_stream<S: Stream<Item = io::Result<Bytes>> + Send + 'static>(stream: S) -> Self {
+    Self(StreamBody::new(stream.map_ok(Frame::data)).boxed_unsync())
+  }
+}
+
+impl From<Bytes> for Body {
+  fn from(bytes: Bytes) -> Self {
+    Self(Full::new(bytes).map_err(|e| match e {}).boxed_unsync())
+  }
+}
+
+impl From<Vec<u8>> for Body {
+  fn from(bytes: Vec<u8>) -> Self {
+    Self::from(Bytes::from(bytes))
+  }
+}
+
+impl From<String> for Body {
+  fn from(text: String) -> Self {
+    Self::from(Bytes::from(text))
+  }
+}
+
+impl From<&'static str> for Body {
+  fn from(text: &'static str) -> Self {
+    Self::from(Bytes::from_static(text.as_bytes()))
+  }
+}
+
+struct Route {
+  method: Method,
+  path: String,
+  handler: Arc<Handler>,
+}
+
+impl Route {
+  /// Whether the route matches, the paths ending with `/*` matching their sub paths.
+  fn matches(&self, method: &Method, path: &str) -> bool {
+    if self.method != method {
+      return false;
+    }
+    match self.path.strip_suffix("/*") {
+      Some(prefix) => strip_mount(prefix, path).is_some(),
+      None => self.path == path,
+    }
+  }
+}
+
+/// The configuration of a local HTTP server, started with [`AppHandle::serve`].
+#[derive(Default)]
+pub struct LocalServer {
+  port: Option<u16>,
+  origins: Vec<String>,
+  dirs: Vec<(String, PathBuf)>,
+  routes: Vec<Route>,
+}
+
+impl LocalServer {
+  /// Configures a server without routes.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Sets the port to listen on, instead of a port chosen by the system.
+  #[must_use]
+  pub fn port(mut self, port: u16) -> Self {
+    self.port.replace(port);
+    self
+  }
+
+  /// Allows the cross-origin requests from the origin, e.g. `tauri://localhost` for the app pages.
+  ///
+  /// The requests of the pages served by the server are always allowed.
+  #[must_use]
+  pub fn allow_origin(mut self, origin: impl Into<String>) -> Self {
+    self
+      .origins
+      .push(origin.into().trim_end_matches('/').to_string());
+    self
+  }
+
+  /// Serves the files of the directory under the path prefix, with the `index.html` file for the directories.
+  ///
+  /// The routes are matched before the directories, and the directories in the order they were added.
+  #[must_use]
+  pub fn serve_dir(mut self, prefix: impl Into<String>, dir: impl Into<PathBuf>) -> Self {
+    let prefix = prefix.into();
+    self
+      .dirs
+      .push((prefix.trim_end_matches('/').to_string(), dir.into()));
+    self
+  }
+
+  /// Adds a route handled by the function, for the exact path or the sub paths of a path ending with `/*`.
+  ///
+  /// The handler receives the request with its whole body.
+  /// The requests must have an allowed `Origin` header, or a `Sec-Fetch-Site: same-origin` header
+  /// for the requests of the pages served by the server that browsers send without an origin.
+  #[must_use]
+  pub fn route<F, Fut>(mut self, method: Method, path: impl Into<String>, handler: F) -> Self
+  where
+    F: Fn(Request<Bytes>) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Response<Body>> + Send + 'static,
+  {
+    self.routes.push(Route {
+      method,
+      path: path.into(),
+      handler: Arc::new(move |request| Box::pin(handler(request))),
+    });
+    self
+  }
+}
+
+/// A handle to a running [`LocalServer`].
+#[derive(Debug, Clone)]
+pub struct ServerHandle {
+  address: SocketAddr,
+  shutdown: Arc<watch::Sender<bool>>,
+}
+
+impl ServerHandle {
+  /// The address the server listens on.
+  pub fn local_addr(&self) -> SocketAddr {
+    self.address
+  }
+
+  /// The URL of the server, ending with `/`.
+  pub fn url(&self) -> Url {
+    format!("http://{}/", self.address).parse().unwrap()
+  }
+
+  /// Stops accepting connections and closes the open connections once their current response is sent.
+  pub fn shutdown(&self) {
+    let _ = self.shutdown.send(true);
+  }
+}
+
+/// The rest of the path if it is under the prefix, empty or starting with `/`.
+fn strip_mount<'a>(prefix: &str, path: &'a str) -> Option<&'a str> {
+  let rest = path.strip_prefix(prefix)?;
+  (rest.is_empty() || rest.starts_with('/')).then_some(rest)
+}
+
+/// The file of the directory at the URL path, rejecting the paths escaping the directory.
+fn resolve_file(dir: &Path, path: &str) -> Option<PathBuf> {
+  let path = percent_encoding::percent_decode_str(path)
+    .decode_utf8()
+    .ok()?;
+  let mut file = dir.to_path_buf();
+  for segment in path.split('/').filter(|s| !s.is_empty()) {
+    let mut components = Path::new(segment).components();
+    match (components.next(), components.next()) {
+      (Some(Component::Normal(name)), None) => file.push(name),
+      _ => return None,
+    }
+  }
+  Some(file)
+}
+
+/// Whether the request targets the server by its loopback name and comes from its own pages or an allowed origin.
+///
+/// The requests without an origin are only allowed for the static files, unless they are same-origin requests.
+fn is_allowed(server: &LocalServer, port: u16, headers: &HeaderMap, is_route: bool) -> bool {
+  let hosts = [format!("localhost:{port}"), format!("127.0.0.1:{port}")];
+  let host_allowed = headers
+    .get(HOST)
+    .and_then(|host| host.to_str().ok())
+    .map_or(false, |host| hosts.iter().any(|allowed| allowed == host));
+  let origin_allowed = match headers.get(ORIGIN) {
+    Some(origin) => origin.to_str().map_or(false, |origin| {
+      hosts
+        .iter()
+        .any(|host| origin.strip_prefix("http://") == Some(host))
+        || server.origins.iter().any(|allowed| allowed == origin)
+    }),
+    // browsers omit the origin of the same-origin GET and HEAD requests
+    None => {
+      !is_route
+        || headers
+          .get(SEC_FETCH_SITE)
+          .map_or(false, |site| site == "same-origin")
+    }
+  };
+  host_allowed && origin_allowed
+}
+
+const SEC_FETCH_SITE: &str = "sec-fetch-site";
+
+fn status(status: StatusCode) -> Response<Body> {
+  let mut response = Response::new(Body::empty());
+  *response.status_mut() = status;
+  response
+}
+
+async fn serve_file(dir: &Path, path: &str) -> Response<Body> {
+  let Some(mut file) = resolve_file(dir, path) else {
+    return status(StatusCode::NOT_FOUND);
+  };
+  if tokio::fs::metadata(&file).await.is_ok_and(|m| m.is_dir()) {
+    file.push("index.html");
+  }
+  match tokio::fs::read(&file).await {
+    Ok(bytes) => {
+      let mime_type = MimeType::parse(&bytes, &file.to_string_lossy());
+      let mut response = Response::new(Body::from(bytes));
+      if let Ok(value) = mime_type.parse() {
+        response.headers_mut().insert(CONTENT_TYPE, value);
+      }
+      response
+    }
+    Err(e) if e.kind() == io::ErrorKind::NotFound => status(StatusCode::NOT_FOUND),
+    Err(e) => {
+      log::error!("failed to read {}: {e}", file.display());
+      status(StatusCode::INTERNAL_SERVER_ERROR)
+    }
+  }
+}
+
+async fn handle(
+  server: Arc<LocalServer>,
+  port: u16,
+  request: Request<hyper::body::Incoming>,
+) -> Result<Response<UnsyncBoxBody<Bytes, io::Error>>, Infallible> {
+  let path = request.uri().path().to_string();
+  let route = server
+    .routes
+    .iter()
+    .find(|route| route.matches(request.method(), &path));
+  let response = if !is_allowed(&server, port, request.headers(), route.is_some()) {
+    status(StatusCode::FORBIDDEN)
+  } else if let Some(route) = route {
+    let (parts, body) = request.into_parts();
+    match body.collect().await {
+      Ok(body) => (route.handler)(Request::from_parts(parts, body.to_bytes())).await,
+      Err(e) => {
+        log::error!("failed to read the request body: {e}");
+        status(StatusCode::BAD_REQUEST)
+      }
+    }
+  } else if let Some((dir, rest)) = server
+    .dirs
+    .iter()
+    .find_map(|(prefix, dir)| strip_mount(prefix, &path).map(|rest| (dir, rest)))
+  {
+    if request.method() == Method::GET || request.method() == Method::HEAD {
+      serve_file(dir, rest).await
+    } else {
+      status(StatusCode::METHOD_NOT_ALLOWED)
+    }
+  } else {
+    status(StatusCode::NOT_FOUND)
+  };
+  Ok(response.map(|body| body.0))
+}
+
+async fn run(server: Arc<LocalServer>, listener: StdTcpListener, shutdown: watch::Receiver<bool>) {
+  let port = match listener.local_addr() {
+    Ok(address) => address.port(),
+    Err(e) => {
+      log::error!("failed to start the local server: {e}");
+      return;
+    }
+  };
+  let listener = match TcpListener::from_std(listener) {
+    Ok(listener) => listener,
+    Err(e) => {
+      log::error!("failed to start the local server: {e}");
+      return;
+    }
+  };
+  let mut shutdown_ = shutdown.clone();
+  loop {
+    let stream = tokio::select! {
+      _ = shutdown_.changed() => break,
+      accepted = listener.accept() => match accepted {
+        Ok((stream, _)) => stream,
+        Err(e) => {
+          log::error!("failed to accept a connection: {e}");
+          continue;
+        }
+      },
+    };
+    let server = server.clone();
+    let mut shutdown = shutdown.clone();
+    crate::async_runtime::spawn(async move {
+      let connection = hyper::server::conn::http1::Builder::new().serve_connection(
+        TokioIo::new(stream),
+        service_fn(move |request| handle(server.clone(), port, request)),
+      );
+      tokio::pin!(connection);
+      let result = tokio::select! {
+        result = connection.as_mut() => result,
+        _ = shutdown.changed() => {
+          connection.as_mut().graceful_shutdown();
+          connection.await
+        }
+      };
+      if let Err(e) = result {
+        log::debug!("local server connection error: {e}");
+      }
+    });
+  }
+}
+
+pub(crate) fn serve<R: Runtime>(
+  app: &AppHandle<R>,
+  server: LocalServer,
+) -> crate::Result<ServerHandle> {
+  // the port 0 lets the system choose a free port, read back from the listener
+  let listener = StdTcpListener::bind((Ipv4Addr::LOCALHOST, server.port.unwrap_or(0)))?;
+  listener.set_nonblocking(true)?;
+  let (shutdown, shutdown_rx) = watch::channel(false);
+  let handle = ServerHandle {
+    address: listener.local_addr()?,
+    shutdown: Arc::new(shutdown),
+  };
+
+  crate::async_runtime::spawn(run(Arc::new(server), listener, shutdown_rx));
+  app.manager.servers.lock().unwrap().push(handle.clone());
+  Ok(handle)
+}
+
+#[cfg(test)]
+mod tests {
+  use std::path::Path;
+
+  use http::{
+    header::{HOST, ORIGIN},
+    HeaderMap,
+  };
+
+  use super::{is_allowed, resolve_file, strip_mount, LocalServer, SEC_FETCH_SITE};
+
+  #[test]
+  fn strips_mount_prefix() {
+    assert_eq!(strip_mount("/static", "/static/app.js"), Some("/app.js"));
+    assert_eq!(strip_mount("/static", "/static"), Some(""));
+    assert_eq!(strip_mount("/static", "/statics/app.js"), None);
+    assert_eq!(strip_mount("", "/index.html"), Some("/index.html"));
+  }
+
+  #[test]
+  fn rejects_escaping_paths() {
+    let dir = Path::new("site");
+    assert_eq!(
+      resolve_file(dir, "/assets/app%20main.js"),
+      Some(dir.join("assets").join("app main.js"))
+    );
+    assert_eq!(resolve_file(dir, "/"), Some(dir.to_path_buf()));
+    assert_eq!(resolve_file(dir, "/../secret"), None);
+    assert_eq!(resolve_file(dir, "/%2e%2e/secret"), None);
+    assert_eq!(resolve_file(dir, "/a%2f..%2f..%2fsecret"), None);
+  }
+
+  #[test]
+  fn checks_host_and_origin() {
+    let server = LocalServer::new().allow_origin("tauri://localhost/");
+    let headers = |host: &str, origin: Option<&str>| {
+      let mut headers = HeaderMap::new();
+      headers.insert(HOST, host.parse().unwrap());
+      if let Some(origin) = origin {
+        headers.insert(ORIGIN, origin.parse().unwrap());
+      }
+      headers
+    };
+
+    for is_route in [false, true] {
+      assert!(is_allowed(
+        &server,
+        8080,
+        &headers("127.0.0.1:8080", Some("http://127.0.0.1:8080")),
+        is_route
+      ));
+      assert!(is_allowed(
+        &server,
+        8080,
+        &headers("localhost:8080", Some("tauri://localhost")),
+        is_route
+      ));
+      // DNS rebinding
+      assert!(!is_allowed(
+        &server,
+        8080,
+        &headers("evil.com:8080", None),
+        is_route
+      ));
+      assert!(!is_allowed(
+        &server,
+        8080,
+        &headers("localhost:8081", None),
+        is_route
+      ));
+      assert!(!is_allowed(&server, 8080, &HeaderMap::new(), is_route));
+      // cross-origin requests
+      assert!(!is_allowed(
+        &server,
+        8080,
+        &headers("localhost:8080", Some("https://evil.com")),
+        is_route
+      ));
+    }
+
+    // the requests without an origin only reach the routes when they are same-origin
+    assert!(is_allowed(
+      &server,
+      8080,
+      &headers("localhost:8080", None),
+      false
+    ));
+    assert!(!is_allowed(
+      &server,
+      8080,
+      &headers("localhost:8080", None),
+      true
+    ));
+    let mut same_origin = headers("localhost:8080", None);
+    same_origin.insert(SEC_FETCH_SITE, "same-origin".parse().unwrap());
+    assert!(is_allowed(&server, 8080, &same_origin, true));
+    let mut cross_site = headers("localhost:8080", None);
+    cross_site.insert(SEC_FETCH_SITE, "cross-site".parse().unwrap());
+    assert!(!is_allowed(&server, 8080, &cross_site, true));
+  }
+}
@@ -0,0 +1,234 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Saving and restoring the open windows, see [`AppHandle::save_session`] and [`AppHandle::restore_session`].
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use tauri_utils::config::WebviewUrl;
+use url::{Position, Url};
+
+use crate::{
+  manager::webview::resolve_url, window::Workspace, AppHandle, Manager, PhysicalPosition,
+  PhysicalSize, Runtime, WebviewWindow, WebviewWindowBuilder,
+};
+
+/// The name of the file storing the session in the [`app_data_dir`](crate::path::PathResolver::app_data_dir).
+pub const SESSION_FILE_NAME: &str = ".window-session.json";
+
+/// The webview windows open when the session was saved.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct Session {
+  /// The state of the windows, sorted by label.
+  pub windows: Vec<WindowState>,
+}
+
+/// The state of a webview window saved in a [`Session`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct WindowState {
+  /// The window label.
+  pub label: String,
+  /// The URL loaded by the webview, relative to the app URL for the app pages.
+  pub url: WebviewUrl,
+  /// The position of the top-left corner of the window.
+  pub position: PhysicalPosition<i32>,
+  /// The size of the client area of the window.
+  pub size: PhysicalSize<u32>,
+  /// Whether the window is maximized.
+  pub maximized: bool,
+  /// Whether the window is fullscreen.
+  pub fullscreen: bool,
+  /// The zoom level set with [`Webview::set_zoom`](crate::Webview::set_zoom).
+  pub zoom: f64,
+  /// The virtual desktop or workspace of the window, if known.
+  #[serde(default)]
+  pub workspace: Option<Workspace>,
+}
+
+fn session_path<R: Runtime>(app: &AppHandle<R>) -> crate::Result<PathBuf> {
+  Ok(app.path().app_data_dir()?.join(SESSION_FILE_NAME))
+}
+
+/// The URL of the window relative to the app URL, so the session restores in both development and production.
+fn relative_url<R: Runtime>(app: &AppHandle<R>, url: Url) -> WebviewUrl {
+  let app_url = resolve_url(&app.manager, &WebviewUrl::default());
+  if url.origin() == app_url.origin() {
+    WebviewUrl::App(url[Position::BeforePath..].trim_start_matches('/').into())
+  } else if url.scheme() == "http" || url.scheme() == "https" {
+    WebviewUrl::External(url)
+  } else {
+    WebviewUrl::CustomProtocol(url)
+  }
+}
+
+fn window_state<R: Runtime>(window: &WebviewWindow<R>) -> crate::Result<WindowState> {
+  Ok(WindowState {
+    label: window.label().into(),
+    url: relative_url(window.app_handle(), window.url()?),
+    position: window.outer_position()?,
+    size: window.inner_size()?,
+    maximized: window.is_maximized()?,
+    fullscreen: window.is_fullscreen()?,
+    zoom: window
+      .app_handle()
+      .manager
+      .webview
+      .zoom
+      .lock()
+      .unwrap()
+      .get(window.label())
+      .copied()
+      .unwrap_or(1.0),
+    workspace: window.workspace().ok().flatten(),
+  })
+}
+
+/// The state of the webview windows, excluding the windows created by Tauri.
+pub(crate) fn current<R: Runtime>(app: &AppHandle<R>) -> crate::Result<Session> {
+  let internal_labels = app.manager.internal_labels.lock().unwrap().clone();
+  let mut windows = app
+    .webview_windows()
+    .into_values()
+    .filter(|window| !internal_labels.contains(window.label()))
+    .map(|window| window_state(&window))
+    .collect::<crate::Result<Vec<_>>>()?;
+  windows.sort_by(|a, b| a.label.cmp(&b.label));
+  Ok(Session { windows })
+}
+
+pub(crate) fn save_to<R: Runtime>(app: &AppHandle<R>, path: &Path) -> crate::Result<Session> {
+  let session = current(app)?;
+  if let Some(parent) = path.parent() {
+    std::fs::create_dir_all(parent)?;
+  }
+  // a crash in the middle of the write keeps the previous session
+  crate::fs::atomic_write(path, serde_json::to_vec_pretty(&session)?)?;
+  Ok(session)
+}
+
+pub(crate) fn save<R: Runtime>(app: &AppHandle<R>) -> crate::Result<Session> {
+  save_to(app, &session_path(app)?)
+}
+
+/// Applies the saved state to the open window with the same label or creates it.
+fn restore_window<R: Runtime>(
+  app: &AppHandle<R>,
+  state: WindowState,
+) -> crate::Result<WebviewWindow<R>> {
+  let mut window = match app.get_webview_window(&state.label) {
+    Some(window) => window,
+    None => WebviewWindowBuilder::new(app, &state.label, state.url.clone())
+      .visible(false)
+      .build()?,
+  };
+  let url = resolve_url(&app.manager, &state.url);
+  if window.url()? != url {
+    window.navigate(url)?;
+  }
+
+  window.set_size(state.size)?;
+  window.set_position(state.position)?;
+  if state.maximized {
+    window.maximize()?;
+  }
+  if state.fullscreen {
+    window.set_fullscreen(true)?;
+  }
+  if state.zoom != 1.0 {
+    window.set_zoom(state.zoom)?;
+  }
+  if let Some(workspace) = state.workspace {
+    // the workspace may not exist anymore
+    if let Err(e) = window.move_to_workspace(workspace) {
+      log::warn!(
+        "failed to restore the workspace of the `{}` window: {e}",
+        state.label
+      );
+    }
+  }
+  window.show()?;
+  Ok(window)
+}
+
+pub(crate) fn restore_from<R: Runtime>(
+  app: &AppHandle<R>,
+  path: &Path,
+) -> crate::Result<Vec<WebviewWindow<R>>> {
+  let session: Session = match std::fs::read(path) {
+    Ok(bytes) => serde_json::from_slice(&bytes)?,
+    Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+    Err(e) => return Err(e.into()),
+  };
+  let mut windows = Vec::new();
+  for state in session.windows {
+    let label = state.label.clone();
+    match restore_window(app, state) {
+      Ok(window) => windows.push(window),
+      Err(e) => log::error!("failed to restore the `{label}` window: {e}"),
+    }
+  }
+  Ok(windows)
+}
+
+pub(crate) fn restore<R: Runtime>(app: &AppHandle<R>) -> crate::Result<Vec<WebviewWindow<R>>> {
+  restore_from(app, &session_path(app)?)
+}
+
+/// Saves the session before the windows are destroyed when `app > restoreSession` is enabled.
+///
+/// Closing the last window or exiting saves all the windows, while the windows closed before are dropped from the session.
+pub(crate) fn save_on_close<R: Runtime>(app: &AppHandle<R>) {
+  if app.manager.window.windows_lock().is_empty() {
+    return;
+  }
+  if let Err(e) = save(app) {
+    log::error!("failed to save the window session: {e}");
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{test::mock_app, Manager, PhysicalPosition, PhysicalSize, WebviewUrl};
+
+  #[test]
+  fn save_and_restore() {
+    let path = std::env::temp_dir()
+      .join(format!("tauri-session-{}", std::process::id()))
+      .join(super::SESSION_FILE_NAME);
+
+    let app = mock_app();
+    let editor =
+      crate::WebviewWindowBuilder::new(&app, "editor", WebviewUrl::App("editor.html".into()))
+        .build()
+        .unwrap();
+    editor.set_size(PhysicalSize::new(640, 480)).unwrap();
+    editor.set_position(PhysicalPosition::new(10, 20)).unwrap();
+    editor.set_zoom(1.5).unwrap();
+    let session = super::save_to(app.handle(), &path).unwrap();
+    assert_eq!(session.windows.len(), 1);
+    assert_eq!(
+      session.windows[0].url,
+      WebviewUrl::App("editor.html".into())
+    );
+
+    let app = mock_app();
+    let windows = super::restore_from(app.handle(), &path).unwrap();
+    assert_eq!(windows.len(), 1);
+    let editor = app.get_webview_window("editor").unwrap();
+    assert_eq!(editor.inner_size().unwrap(), PhysicalSize::new(640, 480));
+    assert_eq!(
+      editor.outer_position().unwrap(),
+      PhysicalPosition::new(10, 20)
+    );
+    assert!(editor.url().unwrap().path().ends_with("editor.html"));
+    assert_eq!(super::current(app.handle()).unwrap(), session);
+
+    std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+  }
+}
@@ -0,0 +1,410 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! The native share sheet, see [`share`].
+//!
+//! ```rust,no_run
+//! use tauri::{share::ShareItem, Manager};
+//!
+//! tauri::Builder::default()
+//!   .setup(|app| {
+//!     let window = app.get_webview_window("main").unwrap();
+//!     tauri::share::share(
+//!       &window.as_ref().window(),
+//!       vec![
+//!         ShareItem::Text("Look at this".into()),
+//!         ShareItem::Url("https://tauri.app".parse().unwrap()),
+//!       ],
+//!       |outcome| match outcome {
+//!         Ok(outcome) if outcome.completed => println!("shared with {:?}", outcome.target),
+//!         Ok(_) => println!("sharing cancelled"),
+//!         Err(e) => eprintln!("failed to share: {e}"),
+//!       },
+//!     )?;
+//!     Ok(())
+//!   });
+//! ```
+//!
+//! ## Platform-specific
+//!
+//! - **macOS:** Uses `NSSharingServicePicker`, anchored to the center of the window.
+//! - **Windows:** Uses the Share UI of the `DataTransferManager`. Only the first text and URL items are shared.
+//! - **Android:** Uses an `ACTION_SEND` chooser. The files must be in the app cache directory or the external storage,
+//!   the directories shared by the `FileProvider` of the app. The chooser doesn't report the selected app nor whether it was cancelled,
+//!   so the outcome is always completed without target.
+//! - **iOS:** Uses `UIActivityViewController`. The target is the activity type, e.g. `com.apple.UIKit.activity.Mail`.
+//! - **Linux:** Unsupported, [`share`] returns an error.
+
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::{Runtime, Window};
+
+/// An item shared with [`share`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "value", rename_all = "camelCase")]
+#[non_exhaustive]
+pub enum ShareItem {
+  /// Plain text.
+  Text(String),
+  /// A link.
+  Url(Url),
+  /// The absolute path of a file.
+  File(PathBuf),
+}
+
+/// The outcome of a [`share`] request.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct ShareOutcome {
+  /// Whether the items were shared, `false` if the user dismissed the share sheet.
+  pub completed: bool,
+  /// The app or service the items were shared with, if reported by the platform.
+  pub target: Option<String>,
+}
+
+/// Opens the share sheet of the platform for the window, calling `on_complete` once the user shared the items or dismissed it.
+///
+/// See the [module documentation](self) for the platform differences.
+pub fn share<R: Runtime, F: FnOnce(crate::Result<ShareOutcome>) + Send + 'static>(
+  window: &Window<R>,
+  items: Vec<ShareItem>,
+  on_complete: F,
+) -> crate::Result<()> {
+  if items.is_empty() {
+    return Err(crate::Error::Share("nothing to share".into()));
+  }
+  platform::share(window, items, Box::new(on_complete))
+}
+
+type Callback = Box<dyn FnOnce(crate::Result<ShareOutcome>) + Send>;
+
+#[cfg(target_os = "macos")]
+mod platform {
+  use std::sync::Mutex;
+
+  use cocoa::{
+    base::{id, nil},
+    foundation::{NSArray, NSPoint, NSRect, NSSize, NSString},
+  };
+  use objc::{
+    class,
+    declare::ClassDecl,
+    msg_send,
+    runtime::{Object, Sel},
+    sel, sel_impl,
+  };
+
+  use super::{Callback, ShareItem, ShareOutcome};
+  use crate::{Runtime, Window};
+
+  /// The callback of the share sheet currently open.
+  static PENDING: Mutex<Option<Callback>> = Mutex::new(None);
+  /// The delegate of the pickers, which only hold a weak reference to it.
+  static DELEGATE: std::sync::OnceLock<usize> = std::sync::OnceLock::new();
+
+  const NS_MIN_Y_EDGE: u64 = 1;
+
+  extern "C" fn did_choose_service(_this: &Object, _cmd: Sel, _picker: id, service: id) {
+    let outcome = if service == nil {
+      ShareOutcome::default()
+    } else {
+      let title: id = unsafe { msg_send![service, title] };
+      ShareOutcome {
+        completed: true,
+        target: unsafe { nsstring_to_string(title) },
+      }
+    };
+    if let Some(callback) = PENDING.lock().unwrap().take() {
+      callback(Ok(outcome));
+    }
+  }
+
+  unsafe fn nsstring_to_string(string: id) -> Option<String> {
+    if string == nil {
+      return None;
+    }
+    let utf8 = string.UTF8String();
+    (!utf8.is_null()).then(|| {
+      std::ffi::CStr::from_ptr(utf8)
+        .to_string_lossy()
+        .into_owned()
+    })
+  }
+
+  fn delegate() -> id {
+    *DELEGATE.get_or_init(|| unsafe {
+      let mut decl = ClassDecl::new("TauriSharingServicePickerDelegate", class!(NSObject))
+        .expect("failed to declare the share sheet delegate");
+      decl.add_method(
+        sel!(sharingServicePicker:didChooseSharingService:),
+        did_choose_service as extern "C" fn(&Object, Sel, id, id),
+      );
+      let delegate: id = msg_send![decl.register(), new];
+      delegate as usize
+    }) as id
+  }
+
+  unsafe fn ns_item(item: &ShareItem) -> id {
+    match item {
+      ShareItem::Text(text) => NSString::alloc(nil).init_str(text),
+      ShareItem::Url(url) => {
+        let url = NSString::alloc(nil).init_str(url.as_str());
+        msg_send![class!(NSURL), URLWithString: url]
+      }
+      ShareItem::File(path) => {
+        let path = NSString::alloc(nil).init_str(&path.to_string_lossy());
+        msg_send![class!(NSURL), fileURLWithPath: path]
+      }
+    }
+  }
+
+  pub fn share<R: Runtime>(
+    window: &Window<R>,
+    items: Vec<ShareItem>,
+    on_complete: Callback,
+  ) -> crate::Result<()> {
+    let view = window.ns_view()? as usize;
+    window.run_on_main_thread(move || unsafe {
+      // a new request dismisses the callback of a share sheet that was never closed
+      if let Some(callback) = PENDING.lock().unwrap().replace(on_complete) {
+        callback(Ok(ShareOutcome::default()));
+      }
+
+      let view = view as id;
+      let items = items.iter().map(|item| ns_item(item)).collect::<Vec<_>>();
+      let items = NSArray::arrayWithObjects(nil, &items);
+      let picker: id = msg_send![class!(NSSharingServicePicker), alloc];
+      let picker: id = msg_send![picker, initWithItems: items];
+      let () = msg_send![picker, setDelegate: delegate()];
+
+      let bounds: NSRect = msg_send![view, bounds];
+      let rect = NSRect::new(
+        NSPoint::new(bounds.size.width / 2., bounds.size.height / 2.),
+        NSSize::new(1., 1.),
+      );
+      let () =
+        msg_send![picker, showRelativeToRect: rect ofView: view preferredEdge: NS_MIN_Y_EDGE];
+    })
+  }
+}
+
+#[cfg(windows)]
+mod platform {
+  use std::sync::{Arc, Mutex};
+
+  use windows::{
+    core::{IInspectable, Interface, HSTRING},
+    ApplicationModel::DataTransfer::{
+      DataPackage, DataRequestedEventArgs, DataTransferManager, ShareCompletedEventArgs,
+    },
+    Foundation::{Collections::IIterable, TypedEventHandler, Uri},
+    Storage::{IStorageItem, StorageFile},
+    Win32::{Foundation::HWND, UI::Shell::IDataTransferManagerInterop},
+  };
+
+  use super::{Callback, ShareItem, ShareOutcome};
+  use crate::{Runtime, Window};
+
+  fn fill(data: &DataPackage, title: &str, items: &[ShareItem]) -> windows::core::Result<()> {
+    // the share fails without a title
+    data.Properties()?.SetTitle(&HSTRING::from(title))?;
+    let mut files = Vec::new();
+    let (mut text, mut link) = (false, false);
+    for item in items {
+      match item {
+        ShareItem::Text(value) if !text => {
+          text = true;
+          data.SetText(&HSTRING::from(value.as_str()))?;
+        }
+        ShareItem::Url(url) if !link => {
+          link = true;
+          data.SetWebLink(&Uri::CreateUri(&HSTRING::from(url.as_str()))?)?;
+        }
+        ShareItem::File(path) => {
+          let file = StorageFile::GetFileFromPathAsync(&HSTRING::from(path.as_path()))?.get()?;
+          files.push(Some(file.cast::<IStorageItem>()?));
+        }
+        _ => {}
+      }
+    }
+    if !files.is_empty() {
+      data.SetStorageItemsReadOnly(&IIterable::<IStorageItem>::from(files))?;
+    }
+    Ok(())
+  }
+
+  fn show(
+    hwnd: HWND,
+    title: String,
+    items: Vec<ShareItem>,
+    callback: Arc<Mutex<Option<Callback>>>,
+  ) -> windows::core::Result<()> {
+    let complete = move |outcome: ShareOutcome| {
+      if let Some(callback) = callback.lock().unwrap().take() {
+        callback(Ok(outcome));
+      }
+    };
+
+    let interop = windows::core::factory::<DataTransferManager, IDataTransferManagerInterop>()?;
+    let manager: DataTransferManager = unsafe { interop.GetForWindow(hwnd)? };
+    let token = Arc::new(Mutex::new(None));
+    let token_ = token.clone();
+    let manager_ = manager.clone();
+    let registered = manager.DataRequested(&TypedEventHandler::<
+      DataTransferManager,
+      DataRequestedEventArgs,
+    >::new(move |_, args| {
+      // the handler is registered for this request only
+      if let Some(token) = token_.lock().unwrap().take() {
+        let _ = manager_.RemoveDataRequested(token);
+      }
+      let Some(args) = args.as_ref() else {
+        return Ok(());
+      };
+      let data = args.Request()?.Data()?;
+      fill(&data, &title, &items)?;
+
+      let complete_ = complete.clone();
+      data.ShareCompleted(
+        &TypedEventHandler::<DataPackage, ShareCompletedEventArgs>::new(move |_, args| {
+          let target = args
+            .as_ref()
+            .and_then(|args| args.ShareTarget().ok())
+            .and_then(|target| target.AppUserModelId().ok())
+            .map(|id| id.to_string())
+            .filter(|id| !id.is_empty());
+          complete_(ShareOutcome {
+            completed: true,
+            target,
+          });
+          Ok(())
+        }),
+      )?;
+      let complete_ = complete.clone();
+      data.ShareCanceled(&TypedEventHandler::<DataPackage, IInspectable>::new(
+        move |_, _| {
+          complete_(ShareOutcome::default());
+          Ok(())
+        },
+      ))?;
+      Ok(())
+    }))?;
+    token.lock().unwrap().replace(registered);
+
+    unsafe { interop.ShowShareUIForWindow(hwnd) }
+  }
+
+  pub fn share<R: Runtime>(
+    window: &Window<R>,
+    items: Vec<ShareItem>,
+    on_complete: Callback,
+  ) -> crate::Result<()> {
+    let hwnd = window.hwnd()?;
+    let title = window.title()?;
+    window.run_on_main_thread(move || {
+      let callback = Arc::new(Mutex::new(Some(on_complete)));
+      if let Err(e) = show(hwnd, title, items, callback.clone()) {
+        if let Some(callback) = callback.lock().unwrap().take() {
+          callback(Err(crate::Error::Share(e.message().to_string())));
+        }
+      }
+    })
+  }
+}
+
+#[cfg(mobile)]
+mod platform {
+  use serde::Serialize;
+
+  use super::{Callback, ShareItem, SharePlugin};
+  use crate::{Manager, Runtime, Window};
+
+  #[derive(Serialize)]
+  struct ShareArgs {
+    items: Vec<ShareItem>,
+  }
+
+  pub fn share<R: Runtime>(
+    window: &Window<R>,
+    items: Vec<ShareItem>,
+    on_complete: Callback,
+  ) -> crate::Result<()> {
+    let handle = window.state::<SharePlugin<R>>().0.clone();
+    // the request is resolved once the share sheet is dismissed
+    std::thread::spawn(move || {
+      on_complete(
+        handle
+          .run_mobile_plugin("share", ShareArgs { items })
+          .map_err(|e| crate::Error::Share(e.to_string())),
+      );
+    });
+    Ok(())
+  }
+}
+
+#[cfg(not(any(target_os = "macos", windows, mobile)))]
+mod platform {
+  use super::{Callback, ShareItem};
+  use crate::{Runtime, Window};
+
+  pub fn share<R: Runtime>(
+    _window: &Window<R>,
+    _items: Vec<ShareItem>,
+    _on_complete: Callback,
+  ) -> crate::Result<()> {
+    Err(crate::Error::Share(
+      "the share sheet is not supported on this platform".into(),
+    ))
+  }
+}
+
+#[cfg(target_os = "ios")]
+crate::swift_rs::swift!(fn init_plugin_share() -> *const std::ffi::c_void);
+
+/// The native share plugin.
+#[cfg(mobile)]
+pub(crate) struct SharePlugin<R: Runtime>(crate::plugin::PluginHandle<R>);
+
+/// Initializes the plugin.
+#[cfg(mobile)]
+pub(crate) fn init<R: Runtime>() -> crate::plugin::TauriPlugin<R> {
+  use crate::Manager;
+
+  crate::plugin::Builder::new("share")
+    .setup(|app, api| {
+      #[cfg(target_os = "android")]
+      let handle = api.register_android_plugin("app.tauri", "SharePlugin")?;
+      #[cfg(target_os = "ios")]
+      let handle = api.register_ios_plugin(init_plugin_share)?;
+      app.manage(SharePlugin(handle));
+      Ok(())
+    })
+    .build()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::ShareItem;
+
+  #[test]
+  fn serializes_items() {
+    let items = vec![
+      ShareItem::Text("hello".into()),
+      ShareItem::Url("https://tauri.app".parse().unwrap()),
+      ShareItem::File("/tmp/report.pdf".into()),
+    ];
+    assert_eq!(
+      serde_json::to_value(&items).unwrap(),
+      serde_json::json!([
+        { "type": "text", "value": "hello" },
+        { "type": "url", "value": "https://tauri.app/" },
+        { "type": "file", "value": "/tmp/report.pdf" },
+      ])
+    );
+  }
+}
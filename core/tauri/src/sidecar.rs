@@ -0,0 +1,803 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Supervised sidecar processes, see [`AppHandle::spawn_sidecar`].
+//!
+//! A sidecar is a binary bundled with the `bundle > externalBin` configuration, e.g. a local server.
+//! The supervisor streams its output lines, checks its health, restarts it according to its [`RestartPolicy`]
+//! and stops it when the app exits, in the reverse order the sidecars were spawned.
+//!
+//! The [`SidecarEvent`]s are passed to the [`Sidecar::on_event`] handler, and emitted as the [`SIDECAR_EVENT`] event,
+//! with the `name` of the sidecar, to the targets set with [`Sidecar::emit_to`]. They are not emitted by default
+//! since the output of a sidecar can contain secrets.
+//!
+//! ```rust,no_run
+//! use std::time::Duration;
+//! use tauri::sidecar::{Backoff, HealthCheck, RestartPolicy, Sidecar, SidecarEvent};
+//!
+//! tauri::Builder::default().setup(|app| {
+//!   app.handle().spawn_sidecar(
+//!     Sidecar::new("python-backend")
+//!       .args(["--port", "8765"])
+//!       .restart(RestartPolicy::OnFailure(Backoff::default()))
+//!       .health_check(HealthCheck::tcp(([127, 0, 0, 1], 8765)).interval(Duration::from_secs(2)))
+//!       .on_event(|event| {
+//!         if let SidecarEvent::Stderr { line } = event {
+//!           eprintln!("backend: {line}");
+//!         }
+//!       }),
+//!   )?;
+//!   Ok(())
+//! });
+//! ```
+//!
+//! The [`PORT_TEMPLATE`] in the arguments and environment values is replaced by the port of the sidecar,
+//! [reserved](crate::net::reserve_port) when the sidecar is spawned if not [set](Sidecar::port).
+//! A sidecar serving HTTP can be [proxied](Sidecar::proxy) by the app, so the webviews reach it from a secure context
+//! at the [`proxy_url`] without port conflicts nor mixed content, once granted the `core:sidecar:allow-proxy` permission:
+//!
+//! ```rust,no_run
+//! use tauri::sidecar::Sidecar;
+//!
+//! tauri::Builder::default().setup(|app| {
+//!   app.handle().spawn_sidecar(
+//!     Sidecar::new("python-backend")
+//!       .args(["--port", "{{port}}"])
+//!       .env("BACKEND_PORT", "{{port}}")
+//!       .proxy(true),
+//!   )?;
+//!   // the webviews fetch `tauri::sidecar::proxy_url("python-backend")` + "api/items"
+//!   Ok(())
+//! });
+//! ```
+//!
+//! # Platform-specific
+//!
+//! - **Windows:** the sidecars are stopped immediately, there is no termination signal.
+
+use std::{
+  ffi::{OsStr, OsString},
+  io::{BufRead, BufReader, Read},
+  net::{SocketAddr, TcpStream},
+  path::{Path, PathBuf},
+  process::{Child, Command, ExitStatus, Stdio},
+  sync::{Arc, Condvar, Mutex, OnceLock},
+  thread::ThreadId,
+  time::{Duration, Instant},
+};
+
+use serde::Serialize;
+
+use crate::{AppHandle, Emitter, EventTarget, Runtime};
+
+/// The event emitted to the [targets](Sidecar::emit_to) of a sidecar for its [`SidecarEvent`]s.
+pub const SIDECAR_EVENT: &str = "tauri://sidecar";
+
+/// The placeholder of the arguments and environment values replaced by the port of the sidecar.
+pub const PORT_TEMPLATE: &str = "{{port}}";
+
+/// The interval the supervisor checks whether the process exited.
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+/// The timeout of a [`HealthCheck::tcp`] connection.
+const TCP_CHECK_TIMEOUT: Duration = Duration::from_secs(1);
+
+type EventHandler = dyn Fn(&SidecarEvent) + Send + Sync;
+type Probe = dyn Fn() -> bool + Send + Sync;
+
+/// The delays between the restarts of a sidecar, doubled after each restart.
+///
+/// The delay is reset when the process ran longer than the maximum delay.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Backoff {
+  /// The delay before the first restart.
+  pub initial: Duration,
+  /// The maximum delay.
+  pub max: Duration,
+  /// The maximum number of consecutive restarts, unlimited if `None`.
+  pub max_restarts: Option<u32>,
+}
+
+impl Default for Backoff {
+  fn default() -> Self {
+    Self {
+      initial: Duration::from_millis(500),
+      max: Duration::from_secs(30),
+      max_restarts: None,
+    }
+  }
+}
+
+impl Backoff {
+  /// The delay before the restart following `restarts` consecutive restarts.
+  fn delay(&self, restarts: u32) -> Duration {
+    self
+      .initial
+      .checked_mul(2u32.saturating_pow(restarts))
+      .map_or(self.max, |delay| delay.min(self.max))
+  }
+}
+
+/// When a sidecar is restarted after its process exited.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RestartPolicy {
+  /// The sidecar is not restarted.
+  #[default]
+  Never,
+  /// The sidecar is restarted when it exits with an error, is killed or fails its [`HealthCheck`].
+  OnFailure(Backoff),
+  /// The sidecar is always restarted, unless it is stopped.
+  Always(Backoff),
+}
+
+/// A periodic check of a sidecar, which is killed after consecutive failures and handled as a failure by the [`RestartPolicy`].
+#[derive(Clone)]
+pub struct HealthCheck {
+  probe: Arc<Probe>,
+  interval: Duration,
+  grace_period: Duration,
+  failure_threshold: u32,
+}
+
+impl std::fmt::Debug for HealthCheck {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("HealthCheck")
+      .field("interval", &self.interval)
+      .field("grace_period", &self.grace_period)
+      .field("failure_threshold", &self.failure_threshold)
+      .finish()
+  }
+}
+
+impl HealthCheck {
+  /// Checks that the sidecar accepts TCP connections on the address.
+  pub fn tcp(address: impl Into<SocketAddr>) -> Self {
+    let address = address.into();
+    Self::custom(move || TcpStream::connect_timeout(&address, TCP_CHECK_TIMEOUT).is_ok())
+  }
+
+  /// Checks the sidecar with a function returning whether it is healthy, called on the supervisor thread.
+  pub fn custom<F: Fn() -> bool + Send + Sync + 'static>(probe: F) -> Self {
+    Self {
+      probe: Arc::new(probe),
+      interval: Duration::from_secs(5),
+      grace_period: Duration::from_secs(10),
+      failure_threshold: 3,
+    }
+  }
+
+  /// Sets the interval between two checks. Defaults to 5 seconds.
+  #[must_use]
+  pub fn interval(mut self, interval: Duration) -> Self {
+    self.interval = interval;
+    self
+  }
+
+  /// Sets the delay before the first check, for the sidecar to start. Defaults to 10 seconds.
+  #[must_use]
+  pub fn grace_period(mut self, grace_period: Duration) -> Self {
+    self.grace_period = grace_period;
+    self
+  }
+
+  /// Sets the number of consecutive failed checks after which the sidecar is killed. Defaults to 3.
+  #[must_use]
+  pub fn failure_threshold(mut self, failure_threshold: u32) -> Self {
+    self.failure_threshold = failure_threshold.max(1);
+    self
+  }
+}
+
+/// An event of a supervised sidecar.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+#[non_exhaustive]
+pub enum SidecarEvent {
+  /// The process started.
+  Started {
+    /// The process identifier.
+    pid: u32,
+  },
+  /// A line written to the standard output, without the line ending.
+  Stdout {
+    /// The line.
+    line: String,
+  },
+  /// A line written to the standard error, without the line ending.
+  Stderr {
+    /// The line.
+    line: String,
+  },
+  /// The process failed its health check and is killed.
+  Unhealthy,
+  /// The process exited.
+  Exited {
+    /// The exit code, `None` if the process was killed by a signal.
+    code: Option<i32>,
+  },
+  /// The process is restarted after a delay.
+  #[serde(rename_all = "camelCase")]
+  Restarting {
+    /// The number of consecutive restarts, including this one.
+    attempt: u32,
+    /// The delay before the restart, in milliseconds.
+    delay_ms: u64,
+  },
+  /// The sidecar is stopped and won't be restarted.
+  Stopped,
+}
+
+#[derive(Clone, Serialize)]
+struct SidecarPayload<'a> {
+  name: &'a str,
+  #[serde(flatten)]
+  event: &'a SidecarEvent,
+}
+
+/// The configuration of a supervised sidecar, spawned with [`AppHandle::spawn_sidecar`].
+pub struct Sidecar {
+  name: String,
+  args: Vec<OsString>,
+  envs: Vec<(OsString, OsString)>,
+  current_dir: Option<PathBuf>,
+  restart: RestartPolicy,
+  health_check: Option<HealthCheck>,
+  shutdown_timeout: Duration,
+  port: Option<u16>,
+  proxy: bool,
+  on_event: Option<Arc<EventHandler>>,
+  emit_to: Vec<EventTarget>,
+}
+
+impl Sidecar {
+  /// Configures the sidecar with the name of the binary in `bundle > externalBin`, without the directories nor the target triple.
+  pub fn new(name: impl Into<String>) -> Self {
+    Self {
+      name: name.into(),
+      args: Vec::new(),
+      envs: Vec::new(),
+      current_dir: None,
+      restart: RestartPolicy::Never,
+      health_check: None,
+      shutdown_timeout: Duration::from_secs(5),
+      port: None,
+      proxy: false,
+      on_event: None,
+      emit_to: Vec::new(),
+    }
+  }
+
+  /// Adds an argument.
+  #[must_use]
+  pub fn arg(mut self, arg: impl Into<OsString>) -> Self {
+    self.args.push(arg.into());
+    self
+  }
+
+  /// Adds arguments.
+  #[must_use]
+  pub fn args<I: IntoIterator<Item = S>, S: Into<OsString>>(mut self, args: I) -> Self {
+    self.args.extend(args.into_iter().map(Into::into));
+    self
+  }
+
+  /// Sets an environment variable, in addition to the environment of the app.
+  #[must_use]
+  pub fn env(mut self, key: impl Into<OsString>, value: impl Into<OsString>) -> Self {
+    self.envs.push((key.into(), value.into()));
+    self
+  }
+
+  /// Sets the working directory.
+  #[must_use]
+  pub fn current_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+    self.current_dir.replace(dir.into());
+    self
+  }
+
+  /// Sets when the sidecar is restarted. Defaults to [`RestartPolicy::Never`].
+  #[must_use]
+  pub fn restart(mut self, policy: RestartPolicy) -> Self {
+    self.restart = policy;
+    self
+  }
+
+  /// Sets the health check of the sidecar.
+  #[must_use]
+  pub fn health_check(mut self, health_check: HealthCheck) -> Self {
+    self.health_check.replace(health_check);
+    self
+  }
+
+  /// Sets how long the sidecar is given to exit after the termination signal before it is killed. Defaults to 5 seconds.
+  #[must_use]
+  pub fn shutdown_timeout(mut self, timeout: Duration) -> Self {
+    self.shutdown_timeout = timeout;
+    self
+  }
+
+  /// Sets the port replacing the [`PORT_TEMPLATE`], instead of a [reserved](crate::net::reserve_port) port.
+  #[must_use]
+  pub fn port(mut self, port: u16) -> Self {
+    self.port.replace(port);
+    self
+  }
+
+  /// Sets whether the requests to the [`proxy_url`] of the sidecar are forwarded to `http://127.0.0.1:<port>`.
+  ///
+  /// The proxy is only reachable by the local pages of the webviews granted the `core:sidecar:allow-proxy` permission,
+  /// which is not part of `core:default`. The `Set-Cookie`, CORS and hop-by-hop headers of the sidecar responses are dropped.
+  #[must_use]
+  pub fn proxy(mut self, proxy: bool) -> Self {
+    self.proxy = proxy;
+    self
+  }
+
+  /// Sets the handler of the sidecar events, called on the supervisor and output threads.
+  ///
+  /// [`SidecarHandle::stop`] only requests the sidecar to stop when called from the handler on the supervisor thread,
+  /// without waiting for the process to exit.
+  #[must_use]
+  pub fn on_event<F: Fn(&SidecarEvent) + Send + Sync + 'static>(mut self, handler: F) -> Self {
+    self.on_event.replace(Arc::new(handler));
+    self
+  }
+
+  /// Emits the sidecar events to the target as the [`SIDECAR_EVENT`] event, e.g. `EventTarget::webview_window("main")`.
+  /// Can be called several times to emit the events to several targets.
+  ///
+  /// The events are not emitted by default, only emit them to the webviews trusted with the output of the sidecar.
+  #[must_use]
+  pub fn emit_to(mut self, target: impl Into<EventTarget>) -> Self {
+    self.emit_to.push(target.into());
+    self
+  }
+
+  fn uses_port(&self) -> bool {
+    self.proxy
+      || self
+        .args
+        .iter()
+        .chain(self.envs.iter().map(|(_, v)| v))
+        .any(|value| value.to_str().is_some_and(|v| v.contains(PORT_TEMPLATE)))
+  }
+
+  fn command(&self, program: &Path) -> Command {
+    let mut command = Command::new(program);
+    command
+      .args(self.args.iter().map(|arg| expand(arg, self.port)))
+      .envs(self.envs.iter().map(|(k, v)| (k, expand(v, self.port))))
+      .stdin(Stdio::null())
+      .stdout(Stdio::piped())
+      .stderr(Stdio::piped());
+    if let Some(dir) = &self.current_dir {
+      command.current_dir(dir);
+    }
+    #[cfg(windows)]
+    {
+      use std::os::windows::process::CommandExt;
+      const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+      command.creation_flags(CREATE_NO_WINDOW);
+    }
+    command
+  }
+}
+
+/// Replaces the [`PORT_TEMPLATE`] of the value.
+fn expand(value: &OsStr, port: Option<u16>) -> OsString {
+  match (value.to_str(), port) {
+    (Some(v), Some(port)) if v.contains(PORT_TEMPLATE) => {
+      v.replace(PORT_TEMPLATE, &port.to_string()).into()
+    }
+    _ => value.to_os_string(),
+  }
+}
+
+/// The URL of the [proxy](Sidecar::proxy) of a sidecar, ending with `/`.
+pub fn proxy_url(name: &str) -> String {
+  format!(
+    "{}{}/",
+    crate::protocol::sidecar::base_url(),
+    percent_encoding::utf8_percent_encode(name, percent_encoding::NON_ALPHANUMERIC)
+  )
+}
+
+/// The path of a sidecar, next to the app binary where the bundler and the CLI copy the external binaries.
+fn sidecar_path(name: &str) -> crate::Result<PathBuf> {
+  let exe = tauri_utils::platform::current_exe()?;
+  let dir = exe
+    .parent()
+    .ok_or_else(|| crate::Error::Sidecar(name.into(), "the app binary has no parent".into()))?;
+  let path = dir.join(name);
+  #[cfg(windows)]
+  let path = if path.extension().is_none() {
+    path.with_extension("exe")
+  } else {
+    path
+  };
+  Ok(path)
+}
+
+#[derive(Default)]
+struct State {
+  pid: Option<u32>,
+  stopping: bool,
+  finished: bool,
+}
+
+/// The state shared by a supervisor thread and the [`SidecarHandle`]s.
+struct Supervised {
+  name: String,
+  port: Option<u16>,
+  proxy: bool,
+  state: Mutex<State>,
+  changed: Condvar,
+  /// The supervisor thread, which can't wait for itself to finish.
+  supervisor: OnceLock<ThreadId>,
+}
+
+impl Supervised {
+  /// Waits for the timeout or a stop request, returning whether the sidecar is stopping.
+  fn wait_stop(&self, timeout: Duration) -> bool {
+    let state = self.state.lock().unwrap();
+    let (state, _) = self
+      .changed
+      .wait_timeout_while(state, timeout, |state| !state.stopping)
+      .unwrap();
+    state.stopping
+  }
+
+  fn set_pid(&self, pid: Option<u32>) {
+    self.state.lock().unwrap().pid = pid;
+  }
+
+  fn finish(&self) {
+    let mut state = self.state.lock().unwrap();
+    state.pid = None;
+    state.finished = true;
+    self.changed.notify_all();
+  }
+
+  /// Requests the sidecar to stop and waits for the supervisor to finish, unless called by the supervisor itself.
+  fn stop(&self) {
+    let mut state = self.state.lock().unwrap();
+    state.stopping = true;
+    self.changed.notify_all();
+    if self.supervisor.get() == Some(&std::thread::current().id()) {
+      return;
+    }
+    let _state = self
+      .changed
+      .wait_while(state, |state| !state.finished)
+      .unwrap();
+  }
+}
+
+/// A handle to a supervised sidecar.
+#[derive(Clone)]
+pub struct SidecarHandle(Arc<Supervised>);
+
+impl std::fmt::Debug for SidecarHandle {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("SidecarHandle")
+      .field("name", &self.0.name)
+      .field("pid", &self.pid())
+      .finish()
+  }
+}
+
+impl SidecarHandle {
+  /// The name of the sidecar.
+  pub fn name(&self) -> &str {
+    &self.0.name
+  }
+
+  /// The identifier of the running process, `None` while the sidecar is restarting or after it stopped.
+  pub fn pid(&self) -> Option<u32> {
+    self.0.state.lock().unwrap().pid
+  }
+
+  /// The port replacing the [`PORT_TEMPLATE`], if any.
+  pub fn port(&self) -> Option<u16> {
+    self.0.port
+  }
+
+  /// Whether the sidecar is supervised, i.e. it is running or will be restarted.
+  pub fn is_supervised(&self) -> bool {
+    !self.0.state.lock().unwrap().finished
+  }
+
+  /// Stops the sidecar, waiting for the process to exit after the termination signal or killing it after the
+  /// [shutdown timeout](Sidecar::shutdown_timeout).
+  ///
+  /// When called from the [event handler](Sidecar::on_event) on the supervisor thread,
+  /// the sidecar is stopped once the handler returns, without waiting.
+  pub fn stop(&self) {
+    self.0.stop();
+  }
+}
+
+/// The sidecars supervised by the app.
+#[derive(Default)]
+pub(crate) struct Sidecars(Mutex<Vec<Arc<Supervised>>>);
+
+impl Sidecars {
+  fn add(&self, supervised: Arc<Supervised>) {
+    let mut sidecars = self.0.lock().unwrap();
+    sidecars.retain(|s| !s.state.lock().unwrap().finished);
+    sidecars.push(supervised);
+  }
+
+  /// The port of the running sidecar proxied with the name.
+  pub(crate) fn proxy_port(&self, name: &str) -> Option<u16> {
+    self
+      .0
+      .lock()
+      .unwrap()
+      .iter()
+      .rev()
+      .find(|s| s.proxy && s.name == name && !s.state.lock().unwrap().finished)
+      .and_then(|s| s.port)
+  }
+
+  /// Stops the sidecars in the reverse order they were spawned, so a sidecar stops before those it depends on.
+  pub(crate) fn shutdown(&self) {
+    let sidecars = std::mem::take(&mut *self.0.lock().unwrap());
+    for sidecar in sidecars.iter().rev() {
+      sidecar.stop();
+    }
+  }
+}
+
+/// How the process of a sidecar ended.
+enum Exit {
+  Exited(Option<ExitStatus>),
+  Unhealthy(Option<ExitStatus>),
+  Stopped,
+}
+
+/// Sends the termination signal, then kills the process after the timeout.
+fn terminate(child: &mut Child, timeout: Duration) {
+  #[cfg(unix)]
+  // SAFETY: the process is a child of the app which was not waited for, so the pid was not reused
+  unsafe {
+    libc::kill(child.id() as libc::pid_t, libc::SIGTERM);
+  }
+  #[cfg(unix)]
+  {
+    let deadline = Instant::now() + timeout;
+    while Instant::now() < deadline {
+      if let Ok(Some(_)) = child.try_wait() {
+        return;
+      }
+      std::thread::sleep(POLL_INTERVAL.min(timeout));
+    }
+  }
+  #[cfg(not(unix))]
+  let _ = timeout;
+  let _ = child.kill();
+  let _ = child.wait();
+}
+
+/// Waits for the process to exit, is unhealthy or is stopped.
+fn watch(
+  child: &mut Child,
+  sidecar: &Sidecar,
+  supervised: &Supervised,
+  emit: &EventHandler,
+) -> Exit {
+  let mut next_check = sidecar
+    .health_check
+    .as_ref()
+    .map(|check| Instant::now() + check.grace_period);
+  let mut failures = 0;
+  loop {
+    match child.try_wait() {
+      Ok(Some(status)) => return Exit::Exited(Some(status)),
+      Ok(None) => {}
+      Err(e) => {
+        log::error!("failed to wait for the `{}` sidecar: {e}", sidecar.name);
+        let _ = child.kill();
+        return Exit::Exited(child.wait().ok());
+      }
+    }
+    if supervised.wait_stop(POLL_INTERVAL) {
+      terminate(child, sidecar.shutdown_timeout);
+      return Exit::Stopped;
+    }
+    if let (Some(check), Some(next)) = (&sidecar.health_check, &mut next_check) {
+      if Instant::now() >= *next {
+        if (check.probe)() {
+          failures = 0;
+        } else {
+          failures += 1;
+          if failures >= check.failure_threshold {
+            emit(&SidecarEvent::Unhealthy);
+            let _ = child.kill();
+            return Exit::Unhealthy(child.wait().ok());
+          }
+        }
+        *next = Instant::now() + check.interval;
+      }
+    }
+  }
+}
+
+/// Streams the lines of an output of the process.
+fn stream<T: Read + Send + 'static, F: Fn(String) -> SidecarEvent + Send + 'static>(
+  output: Option<T>,
+  emit: Arc<EventHandler>,
+  event: F,
+) {
+  let Some(output) = output else {
+    return;
+  };
+  std::thread::spawn(move || {
+    let mut reader = BufReader::new(output);
+    let mut buf = Vec::new();
+    loop {
+      buf.clear();
+      match reader.read_until(b'\n', &mut buf) {
+        Ok(0) | Err(_) => break,
+        Ok(_) => {
+          let line = String::from_utf8_lossy(&buf);
+          emit(&event(line.trim_end_matches(['\n', '\r']).to_string()));
+        }
+      }
+    }
+  });
+}
+
+fn spawn(sidecar: &Sidecar, program: &Path, emit: &Arc<EventHandler>) -> std::io::Result<Child> {
+  let mut child = sidecar.command(program).spawn()?;
+  stream(child.stdout.take(), emit.clone(), |line| {
+    SidecarEvent::Stdout { line }
+  });
+  stream(child.stderr.take(), emit.clone(), |line| {
+    SidecarEvent::Stderr { line }
+  });
+  emit(&SidecarEvent::Started { pid: child.id() });
+  Ok(child)
+}
+
+/// Supervises the sidecar until it is stopped or not restarted.
+fn supervise(
+  sidecar: Sidecar,
+  program: PathBuf,
+  mut child: Child,
+  supervised: &Supervised,
+  emit: &Arc<EventHandler>,
+) {
+  let mut restarts = 0;
+  loop {
+    let started = Instant::now();
+    let (status, failed) = match watch(&mut child, &sidecar, supervised, emit) {
+      Exit::Stopped => break,
+      Exit::Exited(status) => (status, !status.is_some_and(|s| s.success())),
+      Exit::Unhealthy(status) => (status, true),
+    };
+    supervised.set_pid(None);
+    emit(&SidecarEvent::Exited {
+      code: status.and_then(|s| s.code()),
+    });
+
+    let backoff = match sidecar.restart {
+      RestartPolicy::Always(backoff) => backoff,
+      RestartPolicy::OnFailure(backoff) if failed => backoff,
+      _ => break,
+    };
+    if started.elapsed() > backoff.max {
+      restarts = 0;
+    }
+    // restarted until the process is spawned or the supervisor gives up
+    let next = loop {
+      if backoff.max_restarts.is_some_and(|max| restarts >= max) {
+        log::error!("the `{}` sidecar exceeded its restarts", sidecar.name);
+        break None;
+      }
+      let delay = backoff.delay(restarts);
+      restarts += 1;
+      emit(&SidecarEvent::Restarting {
+        attempt: restarts,
+        delay_ms: delay.as_millis() as u64,
+      });
+      if supervised.wait_stop(delay) {
+        break None;
+      }
+      match spawn(&sidecar, &program, emit) {
+        Ok(child) => break Some(child),
+        Err(e) => log::error!("failed to restart the `{}` sidecar: {e}", sidecar.name),
+      }
+    };
+    let Some(next) = next else {
+      break;
+    };
+    supervised.set_pid(Some(next.id()));
+    child = next;
+  }
+  supervised.finish();
+  emit(&SidecarEvent::Stopped);
+}
+
+pub(crate) fn spawn_sidecar<R: Runtime>(
+  app: &AppHandle<R>,
+  mut sidecar: Sidecar,
+) -> crate::Result<SidecarHandle> {
+  let program = sidecar_path(&sidecar.name)?;
+  if sidecar.port.is_none() && sidecar.uses_port() {
+    let port = crate::net::reserve_port()
+      .map_err(|e| crate::Error::Sidecar(sidecar.name.clone(), e.to_string()))?;
+    sidecar.port.replace(port);
+  }
+  let supervised = Arc::new(Supervised {
+    name: sidecar.name.clone(),
+    port: sidecar.port,
+    proxy: sidecar.proxy,
+    state: Mutex::new(State::default()),
+    changed: Condvar::new(),
+    supervisor: OnceLock::new(),
+  });
+
+  let app_ = app.clone();
+  let name = sidecar.name.clone();
+  let handler = sidecar.on_event.take();
+  let targets = std::mem::take(&mut sidecar.emit_to);
+  let emit: Arc<EventHandler> = Arc::new(move |event| {
+    if let Some(handler) = &handler {
+      handler(event);
+    }
+    for target in &targets {
+      let _ = app_.emit_to(
+        target.clone(),
+        SIDECAR_EVENT,
+        SidecarPayload { name: &name, event },
+      );
+    }
+  });
+
+  let child = spawn(&sidecar, &program, &emit)
+    .map_err(|e| crate::Error::Sidecar(sidecar.name.clone(), e.to_string()))?;
+  supervised.set_pid(Some(child.id()));
+  app.manager.sidecars.add(supervised.clone());
+
+  let supervised_ = supervised.clone();
+  std::thread::spawn(move || {
+    let _ = supervised_.supervisor.set(std::thread::current().id());
+    supervise(sidecar, program, child, &supervised_, &emit)
+  });
+
+  Ok(SidecarHandle(supervised))
+}
+
+#[cfg(test)]
+mod tests {
+  use std::time::Duration;
+
+  use std::ffi::OsStr;
+
+  use super::{expand, Backoff};
+
+  #[test]
+  fn expands_port_template() {
+    assert_eq!(
+      expand(OsStr::new("--listen=127.0.0.1:{{port}}"), Some(8765)),
+      "--listen=127.0.0.1:8765"
+    );
+    assert_eq!(expand(OsStr::new("{{port}}"), None), "{{port}}");
+    assert_eq!(expand(OsStr::new("--verbose"), Some(8765)), "--verbose");
+  }
+
+  #[test]
+  fn doubles_backoff_delay() {
+    let backoff = Backoff {
+      initial: Duration::from_millis(100),
+      max: Duration::from_secs(1),
+      max_restarts: None,
+    };
+    assert_eq!(backoff.delay(0), Duration::from_millis(100));
+    assert_eq!(backoff.delay(1), Duration::from_millis(200));
+    assert_eq!(backoff.delay(3), Duration::from_millis(800));
+    assert_eq!(backoff.delay(4), Duration::from_secs(1));
+    assert_eq!(backoff.delay(u32::MAX), Duration::from_secs(1));
+  }
+}
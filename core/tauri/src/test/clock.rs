@@ -0,0 +1,180 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::{
+  sync::{Arc, Mutex},
+  time::{Duration, Instant},
+};
+
+use super::MockRuntime;
+use crate::{
+  time::{Clock, Timer, TimerToken},
+  Manager,
+};
+
+enum Callback {
+  Once(Box<dyn FnOnce() + Send>),
+  Interval(Duration, Box<dyn FnMut() + Send>),
+}
+
+struct MockTimer {
+  /// Orders the timers with the same deadline by creation.
+  id: u64,
+  deadline: Instant,
+  token: TimerToken,
+  callback: Callback,
+}
+
+struct ClockState {
+  now: Instant,
+  next_id: u64,
+  timers: Vec<MockTimer>,
+}
+
+/// A virtual [`Clock`] that only moves forward when [advanced](Self::advance).
+///
+/// The [`mock_builder`](super::mock_builder) uses it for the app, get it with [`mock_clock`].
+///
+/// # Examples
+///
+/// ```rust
+/// use std::{sync::{Arc, atomic::{AtomicUsize, Ordering}}, time::Duration};
+/// use tauri::Manager;
+///
+/// let app = tauri::test::mock_app();
+/// let ticks = Arc::new(AtomicUsize::new(0));
+/// let ticks_ = ticks.clone();
+/// let _timer = app.clock().set_interval(
+///   Duration::from_secs(60),
+///   Box::new(move || {
+///     ticks_.fetch_add(1, Ordering::Relaxed);
+///   }),
+/// );
+///
+/// tauri::test::mock_clock(&app).advance(Duration::from_secs(150));
+/// assert_eq!(ticks.load(Ordering::Relaxed), 2);
+/// ```
+pub struct MockClock {
+  state: Mutex<ClockState>,
+}
+
+impl Default for MockClock {
+  fn default() -> Self {
+    Self {
+      state: Mutex::new(ClockState {
+        now: Instant::now(),
+        next_id: 0,
+        timers: Vec::new(),
+      }),
+    }
+  }
+}
+
+impl std::fmt::Debug for MockClock {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    let state = self.state.lock().unwrap();
+    f.debug_struct("MockClock")
+      .field("now", &state.now)
+      .field("pending_timers", &state.timers.len())
+      .finish()
+  }
+}
+
+impl MockClock {
+  /// Moves the clock forward, running the timers that expire meanwhile in the order of their deadline.
+  ///
+  /// The callbacks run on the current thread, with [`Clock::now`] returning their deadline.
+  pub fn advance(&self, duration: Duration) {
+    let target = self.state.lock().unwrap().now + duration;
+    loop {
+      let timer = {
+        let mut state = self.state.lock().unwrap();
+        state.timers.retain(|t| !t.token.is_canceled());
+        let next = state
+          .timers
+          .iter()
+          .enumerate()
+          .filter(|(_, t)| t.deadline <= target)
+          .min_by_key(|(_, t)| (t.deadline, t.id))
+          .map(|(i, _)| i);
+        match next {
+          Some(i) => {
+            let timer = state.timers.remove(i);
+            state.now = timer.deadline;
+            timer
+          }
+          None => {
+            state.now = target;
+            break;
+          }
+        }
+      };
+
+      match timer.callback {
+        Callback::Once(callback) => callback(),
+        Callback::Interval(interval, mut callback) => {
+          callback();
+          if !timer.token.is_canceled() {
+            let mut state = self.state.lock().unwrap();
+            state.timers.push(MockTimer {
+              id: timer.id,
+              // a zero interval would never let the clock reach the target
+              deadline: timer.deadline + interval.max(Duration::from_nanos(1)),
+              token: timer.token,
+              callback: Callback::Interval(interval, callback),
+            });
+          }
+        }
+      }
+    }
+  }
+
+  /// The number of timers that are neither expired nor canceled.
+  pub fn pending_timers(&self) -> usize {
+    let mut state = self.state.lock().unwrap();
+    state.timers.retain(|t| !t.token.is_canceled());
+    state.timers.len()
+  }
+
+  fn schedule(&self, delay: Duration, callback: Callback) -> Timer {
+    let (timer, token) = Timer::new();
+    let mut state = self.state.lock().unwrap();
+    let id = state.next_id;
+    state.next_id += 1;
+    let deadline = state.now + delay;
+    state.timers.push(MockTimer {
+      id,
+      deadline,
+      token,
+      callback,
+    });
+    timer
+  }
+}
+
+impl Clock for MockClock {
+  fn now(&self) -> Instant {
+    self.state.lock().unwrap().now
+  }
+
+  fn set_timeout(&self, delay: Duration, callback: Box<dyn FnOnce() + Send>) -> Timer {
+    self.schedule(delay, Callback::Once(callback))
+  }
+
+  fn set_interval(&self, interval: Duration, callback: Box<dyn FnMut() + Send>) -> Timer {
+    self.schedule(
+      interval.max(Duration::from_nanos(1)),
+      Callback::Interval(interval, callback),
+    )
+  }
+}
+
+/// The [`MockClock`] of an app created with the [`mock_builder`](super::mock_builder).
+///
+/// # Panics
+///
+/// Panics if the app was not created with the [`mock_builder`](super::mock_builder).
+pub fn mock_clock<M: Manager<MockRuntime>>(manager: &M) -> Arc<MockClock> {
+  manager.state::<Arc<MockClock>>().inner().clone()
+}
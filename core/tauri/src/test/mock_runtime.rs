@@ -8,12 +8,15 @@
 use tauri_runtime::{
   dpi::{PhysicalPosition, PhysicalSize, Position, Size},
   monitor::Monitor,
-  webview::{DetachedWebview, PendingWebview},
-  window::{CursorIcon, DetachedWindow, PendingWindow, RawWindow, WindowEvent, WindowId},
+  webview::{DetachedWebview, PageLoadEvent, PendingWebview, WebviewFrame},
+  window::{
+    CursorIcon, DetachedWindow, DragDropEvent, ImeEvent, PendingWindow, ProcessCrashReason,
+    RawWindow, WebviewEvent, WindowEvent, WindowId,
+  },
   window::{WindowBuilder, WindowBuilderBase},
-  DeviceEventFilter, Error, EventLoopProxy, ExitRequestedEventAction, Icon, ProgressBarState,
-  Result, RunEvent, Runtime, RuntimeHandle, RuntimeInitArgs, UserAttentionType, UserEvent,
-  WebviewDispatch, WindowDispatch, WindowEventId,
+  DeviceEventFilter, Error, EventLoopProxy, ExitRequestedEventAction, Icon, ProgressBarState, Rect,
+  Result, RunEvent, Runtime, RuntimeHandle, RuntimeInitArgs, SurfaceId, UserAttentionType,
+  UserEvent, WebviewDispatch, WebviewEventId, WindowDispatch, WindowEventId,
 };
 
 #[cfg(target_os = "macos")]
@@ -25,6 +28,7 @@ use url::Url;
 use windows::Win32::Foundation::HWND;
 
 use std::{
+  borrow::Cow,
   cell::RefCell,
   collections::HashMap,
   fmt,
@@ -36,11 +40,40 @@ use std::{
 };
 
 type ShortcutMap = HashMap<String, Box<dyn Fn() + Send + 'static>>;
+type WindowEventListeners = Mutex<HashMap<WindowEventId, Box<dyn Fn(&WindowEvent) + Send>>>;
+type WebviewEventListeners = Mutex<HashMap<WebviewEventId, Box<dyn Fn(&WebviewEvent) + Send>>>;
+type NavigationHandler = Mutex<Box<dyn Fn(&Url) -> bool + Send>>;
+type OnPageLoadHandler = Mutex<Box<dyn Fn(Url, PageLoadEvent) + Send>>;
+type UriSchemeProtocols = HashMap<
+  String,
+  Box<
+    dyn Fn(http::Request<Vec<u8>>, Box<dyn FnOnce(http::Response<Cow<'static, [u8]>>) + Send>)
+      + Send
+      + Sync,
+  >,
+>;
 
 enum Message {
   Task(Box<dyn FnOnce() + Send>),
   CloseWindow(WindowId),
   DestroyWindow(WindowId),
+  WindowEvent(WindowId, WindowEvent),
+  WebviewEvent(String, WebviewEvent),
+}
+
+/// Sends the simulated events to the event loop while the app is running.
+#[derive(Debug, Clone)]
+struct EventLoopSender {
+  is_running: Arc<AtomicBool>,
+  run_tx: SyncSender<Message>,
+}
+
+impl EventLoopSender {
+  fn send(&self, message: Message) {
+    if self.is_running.load(Ordering::Relaxed) {
+      let _ = self.run_tx.send(message);
+    }
+  }
 }
 
 struct Webview;
@@ -48,6 +81,194 @@ struct Webview;
 struct Window {
   label: String,
   webviews: Vec<Webview>,
+  state: Arc<WindowState>,
+}
+
+/// The monitor of the mock windows.
+fn mock_monitor() -> Monitor {
+  Monitor {
+    name: Some("mock".into()),
+    size: PhysicalSize::new(1920, 1080),
+    position: PhysicalPosition::new(0, 0),
+    scale_factor: 1.0,
+  }
+}
+
+fn monitor_from_point(monitors: &[Monitor], x: f64, y: f64) -> Option<Monitor> {
+  monitors
+    .iter()
+    .find(|monitor| {
+      x >= monitor.position.x as f64
+        && y >= monitor.position.y as f64
+        && x < (monitor.position.x + monitor.size.width as i32) as f64
+        && y < (monitor.position.y + monitor.size.height as i32) as f64
+    })
+    .cloned()
+}
+
+/// The simulated properties of a window.
+#[derive(Debug, Clone)]
+struct WindowProps {
+  title: String,
+  inner_size: PhysicalSize<u32>,
+  position: PhysicalPosition<i32>,
+  scale_factor: f64,
+  theme: Theme,
+  focused: bool,
+  visible: bool,
+  maximized: bool,
+  minimized: bool,
+  fullscreen: bool,
+  decorated: bool,
+  resizable: bool,
+  maximizable: bool,
+  minimizable: bool,
+  closable: bool,
+}
+
+impl Default for WindowProps {
+  fn default() -> Self {
+    Self {
+      title: String::new(),
+      inner_size: PhysicalSize::new(800, 600),
+      position: PhysicalPosition::new(0, 0),
+      scale_factor: 1.0,
+      theme: Theme::Light,
+      focused: true,
+      visible: true,
+      maximized: false,
+      minimized: false,
+      fullscreen: false,
+      decorated: true,
+      resizable: true,
+      maximizable: true,
+      minimizable: true,
+      closable: true,
+    }
+  }
+}
+
+/// The state of a window, shared by its dispatchers.
+struct WindowState {
+  id: WindowId,
+  props: Mutex<WindowProps>,
+  listeners: WindowEventListeners,
+  event_loop: EventLoopSender,
+  child_surfaces: Mutex<Vec<SurfaceId>>,
+}
+
+impl WindowState {
+  fn props(&self) -> WindowProps {
+    self.props.lock().unwrap().clone()
+  }
+
+  fn update(&self, f: impl FnOnce(&mut WindowProps)) {
+    f(&mut self.props.lock().unwrap());
+  }
+
+  fn emit(&self, event: &WindowEvent) {
+    for listener in self.listeners.lock().unwrap().values() {
+      listener(event);
+    }
+    self
+      .event_loop
+      .send(Message::WindowEvent(self.id, event.clone()));
+  }
+
+  /// Sets the focus state, emitting the [`WindowEvent::Focused`] event if it changed.
+  fn set_focused(&self, focused: bool) {
+    let changed = std::mem::replace(&mut self.props.lock().unwrap().focused, focused) != focused;
+    if changed {
+      self.emit(&WindowEvent::Focused(focused));
+    }
+  }
+
+  /// Resizes the window at once, so the resize ends right after the [`WindowEvent::Resized`] event.
+  fn resize(&self, size: PhysicalSize<u32>) {
+    let changed = std::mem::replace(&mut self.props.lock().unwrap().inner_size, size) != size;
+    if changed {
+      self.emit(&WindowEvent::Resized(size));
+      self.emit(&WindowEvent::ResizeEnded(size));
+    }
+  }
+
+  /// Moves the window at once, so the move ends right after the [`WindowEvent::Moved`] event.
+  fn move_to(&self, position: PhysicalPosition<i32>) {
+    let changed = std::mem::replace(&mut self.props.lock().unwrap().position, position) != position;
+    if changed {
+      self.emit(&WindowEvent::Moved(position));
+      self.emit(&WindowEvent::MoveEnded(position));
+    }
+  }
+
+  fn set_theme(&self, theme: Theme) {
+    let changed = std::mem::replace(&mut self.props.lock().unwrap().theme, theme) != theme;
+    if changed {
+      self.emit(&WindowEvent::ThemeChanged(theme));
+    }
+  }
+
+  /// Sets the fullscreen state, emitting the [`WindowEvent::FullscreenChanged`] event if it changed.
+  fn set_fullscreen(&self, fullscreen: bool) {
+    let changed =
+      std::mem::replace(&mut self.props.lock().unwrap().fullscreen, fullscreen) != fullscreen;
+    if changed {
+      self.emit(&WindowEvent::FullscreenChanged(fullscreen));
+    }
+  }
+}
+
+impl fmt::Debug for WindowState {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("WindowState")
+      .field("props", &self.props)
+      .finish_non_exhaustive()
+  }
+}
+
+/// The state of a webview, shared by its dispatchers.
+struct WebviewState {
+  label: String,
+  url: Mutex<String>,
+  bounds: Mutex<Rect>,
+  evaluated_scripts: Mutex<Vec<String>>,
+  listeners: WebviewEventListeners,
+  navigation_handler: Option<NavigationHandler>,
+  on_page_load_handler: Option<OnPageLoadHandler>,
+  uri_scheme_protocols: UriSchemeProtocols,
+  /// The window of the webview created with it, which receives its drag and drop events.
+  window: Option<Arc<WindowState>>,
+  event_loop: EventLoopSender,
+}
+
+impl WebviewState {
+  fn emit(&self, event: &WebviewEvent) {
+    for listener in self.listeners.lock().unwrap().values() {
+      listener(event);
+    }
+    self
+      .event_loop
+      .send(Message::WebviewEvent(self.label.clone(), event.clone()));
+  }
+
+  /// Loads the URL, running the page load handler.
+  fn load(&self, url: Url) {
+    *self.url.lock().unwrap() = url.to_string();
+    if let Some(handler) = &self.on_page_load_handler {
+      let handler = handler.lock().unwrap();
+      handler(url.clone(), PageLoadEvent::Started);
+      handler(url, PageLoadEvent::Finished);
+    }
+  }
+}
+
+impl fmt::Debug for WebviewState {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("WebviewState")
+      .field("url", &self.url)
+      .field("evaluated_scripts", &self.evaluated_scripts)
+      .finish_non_exhaustive()
+  }
 }
 
 #[derive(Clone)]
@@ -60,6 +281,8 @@ pub struct RuntimeContext {
   next_webview_id: Arc<AtomicU32>,
   next_window_event_id: Arc<AtomicU32>,
   next_webview_event_id: Arc<AtomicU32>,
+  next_surface_id: Arc<AtomicU32>,
+  monitors: Arc<Mutex<Vec<Monitor>>>,
 }
 
 // SAFETY: we ensure this type is only used on the main thread.
@@ -71,6 +294,14 @@ unsafe impl Send for RuntimeContext {}
 unsafe impl Sync for RuntimeContext {}
 
 impl RuntimeContext {
+  fn monitors(&self) -> Vec<Monitor> {
+    self.monitors.lock().unwrap().clone()
+  }
+
+  fn monitor_from_point(&self, x: f64, y: f64) -> Option<Monitor> {
+    monitor_from_point(&self.monitors(), x, y)
+  }
+
   fn send_message(&self, message: Message) -> Result<()> {
     if self.is_running.load(Ordering::Relaxed) {
       self
@@ -83,6 +314,7 @@ impl RuntimeContext {
         Message::CloseWindow(id) | Message::DestroyWindow(id) => {
           self.windows.borrow_mut().remove(&id);
         }
+        Message::WindowEvent(..) | Message::WebviewEvent(..) => {}
       }
       Ok(())
     }
@@ -100,9 +332,126 @@ impl RuntimeContext {
     self.next_window_event_id.fetch_add(1, Ordering::Relaxed)
   }
 
-  fn next_webview_event_id(&self) -> WindowEventId {
+  fn next_webview_event_id(&self) -> WebviewEventId {
     self.next_webview_event_id.fetch_add(1, Ordering::Relaxed)
   }
+
+  fn next_surface_id(&self) -> SurfaceId {
+    self.next_surface_id.fetch_add(1, Ordering::Relaxed)
+  }
+
+  fn event_loop(&self) -> EventLoopSender {
+    EventLoopSender {
+      is_running: self.is_running.clone(),
+      run_tx: self.run_tx.clone(),
+    }
+  }
+
+  fn create_window<T: UserEvent>(
+    &self,
+    pending: PendingWindow<T, MockRuntime>,
+  ) -> DetachedWindow<T, MockRuntime> {
+    let id = self.next_window_id();
+    let props = pending.window_builder.props;
+    let focused = props.focused;
+    let state = Arc::new(WindowState {
+      id,
+      props: Mutex::new(props),
+      listeners: Default::default(),
+      event_loop: self.event_loop(),
+      child_surfaces: Default::default(),
+    });
+
+    let webview = pending.webview.map(|webview| DetachedWebview {
+      label: webview.label.clone(),
+      dispatcher: self.webview_dispatcher(webview, Some(state.clone())),
+    });
+
+    self.windows.borrow_mut().insert(
+      id,
+      Window {
+        label: pending.label.clone(),
+        webviews: if webview.is_some() {
+          vec![Webview]
+        } else {
+          Vec::new()
+        },
+        state: state.clone(),
+      },
+    );
+
+    if focused {
+      self.focus_window(id);
+    }
+
+    DetachedWindow {
+      id,
+      label: pending.label,
+      dispatcher: MockWindowDispatcher {
+        id,
+        context: self.clone(),
+        state,
+      },
+      webview,
+    }
+  }
+
+  fn create_webview<T: UserEvent>(
+    &self,
+    window_id: WindowId,
+    pending: PendingWebview<T, MockRuntime>,
+  ) -> DetachedWebview<T, MockRuntime> {
+    if let Some(w) = self.windows.borrow_mut().get_mut(&window_id) {
+      w.webviews.push(Webview);
+    }
+
+    DetachedWebview {
+      label: pending.label.clone(),
+      dispatcher: self.webview_dispatcher(pending, None),
+    }
+  }
+
+  fn webview_dispatcher<T: UserEvent>(
+    &self,
+    pending: PendingWebview<T, MockRuntime>,
+    window: Option<Arc<WindowState>>,
+  ) -> MockWebviewDispatcher {
+    MockWebviewDispatcher {
+      id: self.next_webview_id(),
+      context: self.clone(),
+      state: Arc::new(WebviewState {
+        label: pending.label,
+        url: Mutex::new(pending.url),
+        bounds: Mutex::new(pending.webview_attributes.bounds.unwrap_or_default()),
+        evaluated_scripts: Default::default(),
+        listeners: Default::default(),
+        navigation_handler: pending.navigation_handler.map(Mutex::new),
+        on_page_load_handler: pending.on_page_load_handler.map(Mutex::new),
+        uri_scheme_protocols: pending.uri_scheme_protocols,
+        window,
+        event_loop: self.event_loop(),
+      }),
+    }
+  }
+
+  /// Focuses the window, blurring the previously focused one.
+  fn focus_window(&self, id: WindowId) {
+    let windows = self
+      .windows
+      .borrow()
+      .iter()
+      .map(|(window_id, w)| (*window_id, w.state.clone()))
+      .collect::<Vec<_>>();
+    // blur the other windows first, like the platforms do
+    for (window_id, state) in &windows {
+      if *window_id != id {
+        state.set_focused(false);
+      }
+    }
+    if let Some((_, state)) = windows.iter().find(|(window_id, _)| *window_id == id) {
+      state.set_focused(true);
+    }
+  }
 }
 
 impl fmt::Debug for RuntimeContext {
@@ -116,6 +465,13 @@ pub struct MockRuntimeHandle {
   context: RuntimeContext,
 }
 
+impl MockRuntimeHandle {
+  /// Replaces the connected monitors, the first one being the primary monitor.
+  pub fn simulate_monitors(&self, monitors: Vec<Monitor>) {
+    *self.context.monitors.lock().unwrap() = monitors;
+  }
+}
+
 impl<T: UserEvent> RuntimeHandle<T> for MockRuntimeHandle {
   type Runtime = MockRuntime;
 
@@ -142,41 +498,7 @@ impl<T: UserEvent> RuntimeHandle<T> for MockRuntimeHandle {
     pending: PendingWindow<T, Self::Runtime>,
     _after_window_creation: Option<F>,
   ) -> Result<DetachedWindow<T, Self::Runtime>> {
-    let id = self.context.next_window_id();
-
-    let (webview_id, webviews) = if let Some(w) = &pending.webview {
-      (Some(self.context.next_webview_id()), vec![Webview])
-    } else {
-      (None, Vec::new())
-    };
-
-    self.context.windows.borrow_mut().insert(
-      id,
-      Window {
-        label: pending.label.clone(),
-        webviews,
-      },
-    );
-
-    let webview = webview_id.map(|id| DetachedWebview {
-      label: pending.label.clone(),
-      dispatcher: MockWebviewDispatcher {
-        id,
-        context: self.context.clone(),
-        url: Arc::new(Mutex::new(pending.webview.unwrap().url)),
-        last_evaluated_script: Default::default(),
-      },
-    });
-
-    Ok(DetachedWindow {
-      id,
-      label: pending.label,
-      dispatcher: MockWindowDispatcher {
-        id,
-        context: self.context.clone(),
-      },
-      webview,
-    })
+    Ok(self.context.create_window(pending))
   }
 
   fn create_webview(
@@ -184,21 +506,7 @@ impl<T: UserEvent> RuntimeHandle<T> for MockRuntimeHandle {
     window_id: WindowId,
     pending: PendingWebview<T, Self::Runtime>,
   ) -> Result<DetachedWebview<T, Self::Runtime>> {
-    let id = self.context.next_webview_id();
-    let webview = Webview;
-    if let Some(w) = self.context.windows.borrow_mut().get_mut(&window_id) {
-      w.webviews.push(webview);
-    }
-
-    Ok(DetachedWebview {
-      label: pending.label,
-      dispatcher: MockWebviewDispatcher {
-        id,
-        context: self.context.clone(),
-        last_evaluated_script: Default::default(),
-        url: Arc::new(Mutex::new(pending.url)),
-      },
-    })
+    Ok(self.context.create_webview(window_id, pending))
   }
 
   /// Run a task on the main thread.
@@ -232,15 +540,15 @@ impl<T: UserEvent> RuntimeHandle<T> for MockRuntimeHandle {
   }
 
   fn primary_monitor(&self) -> Option<Monitor> {
-    unimplemented!()
+    self.context.monitors().into_iter().next()
   }
 
   fn monitor_from_point(&self, x: f64, y: f64) -> Option<Monitor> {
-    unimplemented!()
+    self.context.monitor_from_point(x, y)
   }
 
   fn available_monitors(&self) -> Vec<Monitor> {
-    unimplemented!()
+    self.context.monitors()
   }
 
   /// Shows the application, but does not automatically focus it.
@@ -282,13 +590,71 @@ impl<T: UserEvent> RuntimeHandle<T> for MockRuntimeHandle {
 pub struct MockWebviewDispatcher {
   id: u32,
   context: RuntimeContext,
-  url: Arc<Mutex<String>>,
-  last_evaluated_script: Arc<Mutex<Option<String>>>,
+  state: Arc<WebviewState>,
 }
 
 impl MockWebviewDispatcher {
   pub fn last_evaluated_script(&self) -> Option<String> {
-    self.last_evaluated_script.lock().unwrap().clone()
+    self.state.evaluated_scripts.lock().unwrap().last().cloned()
+  }
+
+  /// The scripts evaluated on the webview, in order.
+  pub fn evaluated_scripts(&self) -> Vec<String> {
+    self.state.evaluated_scripts.lock().unwrap().clone()
+  }
+
+  /// Clears the list of evaluated scripts.
+  pub fn clear_evaluated_scripts(&self) {
+    self.state.evaluated_scripts.lock().unwrap().clear();
+  }
+
+  /// Simulates the user navigating to the URL, e.g. by clicking a link.
+  ///
+  /// Returns `false` if the navigation handler of the webview blocked the navigation.
+  pub fn simulate_navigation(&self, url: Url) -> bool {
+    if let Some(handler) = &self.state.navigation_handler {
+      if !(handler.lock().unwrap())(&url) {
+        return false;
+      }
+    }
+    self.state.load(url);
+    true
+  }
+
+  /// Sends the request to the custom protocol registered for its URI scheme, returning the response.
+  ///
+  /// Returns `None` if the webview has no protocol for the scheme.
+  pub fn simulate_protocol_request(
+    &self,
+    request: http::Request<Vec<u8>>,
+  ) -> Option<http::Response<Cow<'static, [u8]>>> {
+    let protocol = self
+      .state
+      .uri_scheme_protocols
+      .get(request.uri().scheme_str()?)?;
+    let (tx, rx) = sync_channel(1);
+    protocol(
+      request,
+      Box::new(move |response| {
+        let _ = tx.send(response);
+      }),
+    );
+    rx.recv().ok()
+  }
+
+  /// Simulates a drag and drop event on the webview.
+  ///
+  /// Like on the platforms, the event is a window event when the webview was created with the window.
+  pub fn simulate_drag_drop(&self, event: DragDropEvent) {
+    match &self.state.window {
+      Some(window) => window.emit(&WindowEvent::DragDrop(event)),
+      None => self.state.emit(&WebviewEvent::DragDrop(event)),
+    }
+  }
+
+  /// Simulates a crash of the webview process.
+  pub fn simulate_process_crash(&self, reason: ProcessCrashReason) {
+    self.state.emit(&WebviewEvent::ProcessCrashed { reason });
   }
 }
 
@@ -296,31 +662,97 @@ impl MockWebviewDispatcher {
 pub struct MockWindowDispatcher {
   id: WindowId,
   context: RuntimeContext,
+  state: Arc<WindowState>,
 }
 
-#[derive(Debug, Clone)]
-pub struct MockWindowBuilder {}
+impl MockWindowDispatcher {
+  /// Simulates the window gaining or losing the focus, blurring the previously focused window.
+  pub fn simulate_focus(&self, focused: bool) {
+    if focused {
+      self.context.focus_window(self.id);
+    } else {
+      self.state.set_focused(false);
+    }
+  }
+
+  /// Simulates the user resizing the window.
+  pub fn simulate_resize(&self, size: PhysicalSize<u32>) {
+    self.state.resize(size);
+  }
+
+  /// Simulates the user moving the window.
+  pub fn simulate_move(&self, position: PhysicalPosition<i32>) {
+    self.state.move_to(position);
+  }
+
+  /// Simulates the window moving to a monitor with a different scale factor.
+  pub fn simulate_scale_factor_change(&self, scale_factor: f64) {
+    let new_inner_size = {
+      let mut props = self.state.props.lock().unwrap();
+      props.scale_factor = scale_factor;
+      props.inner_size
+    };
+    self.state.emit(&WindowEvent::ScaleFactorChanged {
+      scale_factor,
+      new_inner_size,
+    });
+  }
+
+  /// Simulates a change of the system theme.
+  pub fn simulate_theme_change(&self, theme: Theme) {
+    self.state.set_theme(theme);
+  }
+
+  /// Simulates the input method editor composing text in the window.
+  pub fn simulate_ime(&self, event: ImeEvent) {
+    self.state.emit(&WindowEvent::Ime(event));
+  }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct MockWindowBuilder {
+  props: WindowProps,
+  theme: Option<Theme>,
+}
 
 impl WindowBuilderBase for MockWindowBuilder {}
 
 impl WindowBuilder for MockWindowBuilder {
   fn new() -> Self {
-    Self {}
+    Self::default()
   }
 
   fn with_config(config: &WindowConfig) -> Self {
-    Self {}
+    let mut builder = Self::new()
+      .title(&config.title)
+      .inner_size(config.width, config.height)
+      .resizable(config.resizable)
+      .maximizable(config.maximizable)
+      .minimizable(config.minimizable)
+      .closable(config.closable)
+      .fullscreen(config.fullscreen)
+      .focused(config.focus)
+      .maximized(config.maximized)
+      .visible(config.visible)
+      .decorations(config.decorations)
+      .theme(config.theme);
+    if let (Some(x), Some(y)) = (config.x, config.y) {
+      builder = builder.position(x, y);
+    }
+    builder
   }
 
   fn center(self) -> Self {
     self
   }
 
-  fn position(self, x: f64, y: f64) -> Self {
+  fn position(mut self, x: f64, y: f64) -> Self {
+    self.props.position = PhysicalPosition::new(x as i32, y as i32);
     self
   }
 
-  fn inner_size(self, min_width: f64, min_height: f64) -> Self {
+  fn inner_size(mut self, width: f64, height: f64) -> Self {
+    self.props.inner_size = PhysicalSize::new(width as u32, height as u32);
     self
   }
 
@@ -339,39 +771,48 @@ impl WindowBuilder for MockWindowBuilder {
     self
   }
 
-  fn resizable(self, resizable: bool) -> Self {
+  fn resizable(mut self, resizable: bool) -> Self {
+    self.props.resizable = resizable;
     self
   }
 
-  fn maximizable(self, resizable: bool) -> Self {
+  fn maximizable(mut self, resizable: bool) -> Self {
+    self.props.maximizable = resizable;
     self
   }
 
-  fn minimizable(self, resizable: bool) -> Self {
+  fn minimizable(mut self, resizable: bool) -> Self {
+    self.props.minimizable = resizable;
     self
   }
 
-  fn closable(self, resizable: bool) -> Self {
+  fn closable(mut self, resizable: bool) -> Self {
+    self.props.closable = resizable;
     self
   }
 
-  fn title<S: Into<String>>(self, title: S) -> Self {
+  fn title<S: Into<String>>(mut self, title: S) -> Self {
+    self.props.title = title.into();
     self
   }
 
-  fn fullscreen(self, fullscreen: bool) -> Self {
+  fn fullscreen(mut self, fullscreen: bool) -> Self {
+    self.props.fullscreen = fullscreen;
     self
   }
 
-  fn focused(self, focused: bool) -> Self {
+  fn focused(mut self, focused: bool) -> Self {
+    self.props.focused = focused;
     self
   }
 
-  fn maximized(self, maximized: bool) -> Self {
+  fn maximized(mut self, maximized: bool) -> Self {
+    self.props.maximized = maximized;
     self
   }
 
-  fn visible(self, visible: bool) -> Self {
+  fn visible(mut self, visible: bool) -> Self {
+    self.props.visible = visible;
     self
   }
 
@@ -384,7 +825,8 @@ impl WindowBuilder for MockWindowBuilder {
     self
   }
 
-  fn decorations(self, decorations: bool) -> Self {
+  fn decorations(mut self, decorations: bool) -> Self {
+    self.props.decorated = decorations;
     self
   }
 
@@ -416,6 +858,14 @@ impl WindowBuilder for MockWindowBuilder {
     self
   }
 
+  fn corner_preference(self, preference: tauri_utils::CornerPreference) -> Self {
+    self
+  }
+
+  fn border_color(self, color: tauri_utils::config::Color) -> Self {
+    self
+  }
+
   #[cfg(windows)]
   fn owner(self, owner: HWND) -> Self {
     self
@@ -462,7 +912,9 @@ impl WindowBuilder for MockWindowBuilder {
     self
   }
 
-  fn theme(self, theme: Option<Theme>) -> Self {
+  fn theme(mut self, theme: Option<Theme>) -> Self {
+    self.props.theme = theme.unwrap_or(Theme::Light);
+    self.theme = theme;
     self
   }
 
@@ -471,7 +923,7 @@ impl WindowBuilder for MockWindowBuilder {
   }
 
   fn get_theme(&self) -> Option<Theme> {
-    None
+    self.theme
   }
 }
 
@@ -485,8 +937,10 @@ impl<T: UserEvent> WebviewDispatch<T> for MockWebviewDispatcher {
   fn on_webview_event<F: Fn(&tauri_runtime::window::WebviewEvent) + Send + 'static>(
     &self,
     f: F,
-  ) -> tauri_runtime::WebviewEventId {
-    self.context.next_window_event_id()
+  ) -> WebviewEventId {
+    let id = self.context.next_webview_event_id();
+    self.state.listeners.lock().unwrap().insert(id, Box::new(f));
+    id
   }
 
   fn with_webview<F: FnOnce(Box<dyn std::any::Any>) + Send + 'static>(&self, f: F) -> Result<()> {
@@ -510,34 +964,42 @@ impl<T: UserEvent> WebviewDispatch<T> for MockWebviewDispatcher {
 
   fn eval_script<S: Into<String>>(&self, script: S) -> Result<()> {
     self
-      .last_evaluated_script
+      .state
+      .evaluated_scripts
       .lock()
       .unwrap()
-      .replace(script.into());
+      .push(script.into());
     Ok(())
   }
 
   fn url(&self) -> Result<String> {
-    Ok(self.url.lock().unwrap().clone())
+    Ok(self.state.url.lock().unwrap().clone())
   }
 
-  fn bounds(&self) -> Result<tauri_runtime::Rect> {
-    Ok(tauri_runtime::Rect::default())
+  fn bounds(&self) -> Result<Rect> {
+    Ok(Rect {
+      position: self.position()?.into(),
+      size: self.size()?.into(),
+    })
   }
 
   fn position(&self) -> Result<PhysicalPosition<i32>> {
-    Ok(PhysicalPosition { x: 0, y: 0 })
+    if self.state.window.is_some() {
+      return Ok(PhysicalPosition::new(0, 0));
+    }
+    Ok(self.state.bounds.lock().unwrap().position.to_physical(1.0))
   }
 
   fn size(&self) -> Result<PhysicalSize<u32>> {
-    Ok(PhysicalSize {
-      width: 0,
-      height: 0,
-    })
+    // the webview created with the window fills it
+    if let Some(window) = &self.state.window {
+      return Ok(window.props().inner_size);
+    }
+    Ok(self.state.bounds.lock().unwrap().size.to_physical(1.0))
   }
 
   fn navigate(&self, url: Url) -> Result<()> {
-    *self.url.lock().unwrap() = url.to_string();
+    self.state.load(url);
     Ok(())
   }
 
@@ -545,19 +1007,41 @@ impl<T: UserEvent> WebviewDispatch<T> for MockWebviewDispatcher {
     Ok(())
   }
 
+  fn capture_frame<F: FnOnce(Result<WebviewFrame>) + Send + 'static>(
+    &self,
+    callback: F,
+  ) -> Result<()> {
+    let size = self
+      .state
+      .bounds
+      .lock()
+      .unwrap()
+      .size
+      .to_physical::<u32>(1.0);
+    callback(Ok(WebviewFrame {
+      width: size.width,
+      height: size.height,
+      rgba: vec![0; size.width as usize * size.height as usize * 4],
+    }));
+    Ok(())
+  }
+
   fn close(&self) -> Result<()> {
     Ok(())
   }
 
-  fn set_bounds(&self, bounds: tauri_runtime::Rect) -> Result<()> {
+  fn set_bounds(&self, bounds: Rect) -> Result<()> {
+    *self.state.bounds.lock().unwrap() = bounds;
     Ok(())
   }
 
-  fn set_size(&self, _size: Size) -> Result<()> {
+  fn set_size(&self, size: Size) -> Result<()> {
+    self.state.bounds.lock().unwrap().size = size;
     Ok(())
   }
 
-  fn set_position(&self, _position: Position) -> Result<()> {
+  fn set_position(&self, position: Position) -> Result<()> {
+    self.state.bounds.lock().unwrap().position = position;
     Ok(())
   }
 
@@ -584,97 +1068,98 @@ impl<T: UserEvent> WindowDispatch<T> for MockWindowDispatcher {
   }
 
   fn on_window_event<F: Fn(&WindowEvent) + Send + 'static>(&self, f: F) -> WindowEventId {
-    self.context.next_window_event_id()
+    let id = self.context.next_window_event_id();
+    self.state.listeners.lock().unwrap().insert(id, Box::new(f));
+    id
   }
 
   fn scale_factor(&self) -> Result<f64> {
-    Ok(1.0)
+    Ok(self.state.props().scale_factor)
   }
 
   fn inner_position(&self) -> Result<PhysicalPosition<i32>> {
-    Ok(PhysicalPosition { x: 0, y: 0 })
+    Ok(self.state.props().position)
   }
 
   fn outer_position(&self) -> Result<PhysicalPosition<i32>> {
-    Ok(PhysicalPosition { x: 0, y: 0 })
+    Ok(self.state.props().position)
   }
 
   fn inner_size(&self) -> Result<PhysicalSize<u32>> {
-    Ok(PhysicalSize {
-      width: 0,
-      height: 0,
-    })
+    Ok(self.state.props().inner_size)
   }
 
   fn outer_size(&self) -> Result<PhysicalSize<u32>> {
-    Ok(PhysicalSize {
-      width: 0,
-      height: 0,
-    })
+    Ok(self.state.props().inner_size)
   }
 
   fn is_fullscreen(&self) -> Result<bool> {
-    Ok(false)
+    Ok(self.state.props().fullscreen)
   }
 
   fn is_minimized(&self) -> Result<bool> {
-    Ok(false)
+    Ok(self.state.props().minimized)
   }
 
   fn is_maximized(&self) -> Result<bool> {
-    Ok(false)
+    Ok(self.state.props().maximized)
   }
 
   fn is_focused(&self) -> Result<bool> {
-    Ok(false)
+    Ok(self.state.props().focused)
   }
 
   fn is_decorated(&self) -> Result<bool> {
-    Ok(false)
+    Ok(self.state.props().decorated)
   }
 
   fn is_resizable(&self) -> Result<bool> {
-    Ok(false)
+    Ok(self.state.props().resizable)
   }
 
   fn is_maximizable(&self) -> Result<bool> {
-    Ok(true)
+    Ok(self.state.props().maximizable)
   }
 
   fn is_minimizable(&self) -> Result<bool> {
-    Ok(true)
+    Ok(self.state.props().minimizable)
   }
 
   fn is_closable(&self) -> Result<bool> {
-    Ok(true)
+    Ok(self.state.props().closable)
   }
 
   fn is_visible(&self) -> Result<bool> {
-    Ok(true)
+    Ok(self.state.props().visible)
   }
 
   fn title(&self) -> Result<String> {
-    Ok(String::new())
+    Ok(self.state.props().title)
   }
 
   fn current_monitor(&self) -> Result<Option<Monitor>> {
-    Ok(None)
+    let position = self.state.props().position;
+    Ok(
+      self
+        .context
+        .monitor_from_point(position.x as f64, position.y as f64),
+    )
   }
 
   fn primary_monitor(&self) -> Result<Option<Monitor>> {
-    Ok(None)
+    Ok(self.context.monitors().into_iter().next())
   }
 
   fn monitor_from_point(&self, x: f64, y: f64) -> Result<Option<Monitor>> {
-    Ok(None)
+    Ok(self.context.monitor_from_point(x, y))
   }
 
   fn available_monitors(&self) -> Result<Vec<Monitor>> {
-    Ok(Vec::new())
+    Ok(self.context.monitors())
   }
 
   fn theme(&self) -> Result<Theme> {
-    Ok(Theme::Light)
+    Ok(self.state.props().theme)
   }
 
   #[cfg(any(
@@ -685,7 +1170,8 @@ impl<T: UserEvent> WindowDispatch<T> for MockWindowDispatcher {
     target_os = "openbsd"
   ))]
   fn gtk_window(&self) -> Result<gtk::ApplicationWindow> {
-    unimplemented!()
+    // the mock windows have no native window
+    Err(Error::WindowNotFound)
   }
 
   #[cfg(any(
@@ -728,7 +1214,40 @@ impl<T: UserEvent> WindowDispatch<T> for MockWindowDispatcher {
     return unimplemented!();
   }
 
+  fn create_child_surface(&self) -> Result<SurfaceId> {
+    let id = self.context.next_surface_id();
+    self.state.child_surfaces.lock().unwrap().push(id);
+    Ok(id)
+  }
+
+  fn child_surface_handle(
+    &self,
+    id: SurfaceId,
+  ) -> std::result::Result<raw_window_handle::WindowHandle<'_>, raw_window_handle::HandleError> {
+    if self.state.child_surfaces.lock().unwrap().contains(&id) {
+      self.window_handle()
+    } else {
+      Err(raw_window_handle::HandleError::Unavailable)
+    }
+  }
+
+  fn destroy_child_surface(&self, id: SurfaceId) -> Result<()> {
+    self
+      .state
+      .child_surfaces
+      .lock()
+      .unwrap()
+      .retain(|s| *s != id);
+    Ok(())
+  }
+
   fn center(&self) -> Result<()> {
+    let monitor = mock_monitor();
+    let size = self.state.props().inner_size;
+    self.state.move_to(PhysicalPosition::new(
+      (monitor.size.width as i32 - size.width as i32) / 2,
+      (monitor.size.height as i32 - size.height as i32) / 2,
+    ));
     Ok(())
   }
 
@@ -741,105 +1260,69 @@ impl<T: UserEvent> WindowDispatch<T> for MockWindowDispatcher {
     pending: PendingWindow<T, Self::Runtime>,
     _after_window_creation: Option<F>,
   ) -> Result<DetachedWindow<T, Self::Runtime>> {
-    let id = self.context.next_window_id();
-
-    let (webview_id, webviews) = if let Some(w) = &pending.webview {
-      (Some(self.context.next_webview_id()), vec![Webview])
-    } else {
-      (None, Vec::new())
-    };
-
-    self.context.windows.borrow_mut().insert(
-      id,
-      Window {
-        label: pending.label.clone(),
-        webviews,
-      },
-    );
-
-    let webview = webview_id.map(|id| DetachedWebview {
-      label: pending.label.clone(),
-      dispatcher: MockWebviewDispatcher {
-        id,
-        context: self.context.clone(),
-        url: Arc::new(Mutex::new(pending.webview.unwrap().url)),
-        last_evaluated_script: Default::default(),
-      },
-    });
-
-    Ok(DetachedWindow {
-      id,
-      label: pending.label,
-      dispatcher: MockWindowDispatcher {
-        id,
-        context: self.context.clone(),
-      },
-      webview,
-    })
+    Ok(self.context.create_window(pending))
   }
 
   fn create_webview(
     &mut self,
     pending: PendingWebview<T, Self::Runtime>,
   ) -> Result<DetachedWebview<T, Self::Runtime>> {
-    let id = self.context.next_webview_id();
-    let webview = Webview;
-    if let Some(w) = self.context.windows.borrow_mut().get_mut(&self.id) {
-      w.webviews.push(webview);
-    }
-
-    Ok(DetachedWebview {
-      label: pending.label,
-      dispatcher: MockWebviewDispatcher {
-        id,
-        context: self.context.clone(),
-        last_evaluated_script: Default::default(),
-        url: Arc::new(Mutex::new(pending.url)),
-      },
-    })
+    Ok(self.context.create_webview(self.id, pending))
   }
 
   fn set_resizable(&self, resizable: bool) -> Result<()> {
+    self.state.update(|p| p.resizable = resizable);
     Ok(())
   }
 
   fn set_maximizable(&self, maximizable: bool) -> Result<()> {
+    self.state.update(|p| p.maximizable = maximizable);
     Ok(())
   }
 
   fn set_minimizable(&self, minimizable: bool) -> Result<()> {
+    self.state.update(|p| p.minimizable = minimizable);
     Ok(())
   }
 
   fn set_closable(&self, closable: bool) -> Result<()> {
+    self.state.update(|p| p.closable = closable);
     Ok(())
   }
 
   fn set_title<S: Into<String>>(&self, title: S) -> Result<()> {
+    let title = title.into();
+    self.state.update(|p| p.title = title);
     Ok(())
   }
 
   fn maximize(&self) -> Result<()> {
+    self.state.update(|p| p.maximized = true);
     Ok(())
   }
 
   fn unmaximize(&self) -> Result<()> {
+    self.state.update(|p| p.maximized = false);
     Ok(())
   }
 
   fn minimize(&self) -> Result<()> {
+    self.state.update(|p| p.minimized = true);
     Ok(())
   }
 
   fn unminimize(&self) -> Result<()> {
+    self.state.update(|p| p.minimized = false);
     Ok(())
   }
 
   fn show(&self) -> Result<()> {
+    self.state.update(|p| p.visible = true);
     Ok(())
   }
 
   fn hide(&self) -> Result<()> {
+    self.state.update(|p| p.visible = false);
     Ok(())
   }
 
@@ -854,6 +1337,7 @@ impl<T: UserEvent> WindowDispatch<T> for MockWindowDispatcher {
   }
 
   fn set_decorations(&self, decorations: bool) -> Result<()> {
+    self.state.update(|p| p.decorated = decorations);
     Ok(())
   }
 
@@ -861,6 +1345,14 @@ impl<T: UserEvent> WindowDispatch<T> for MockWindowDispatcher {
     Ok(())
   }
 
+  fn set_corner_preference(&self, preference: tauri_utils::CornerPreference) -> Result<()> {
+    Ok(())
+  }
+
+  fn set_border_color(&self, color: Option<tauri_utils::config::Color>) -> Result<()> {
+    Ok(())
+  }
+
   fn set_always_on_bottom(&self, always_on_bottom: bool) -> Result<()> {
     Ok(())
   }
@@ -878,6 +1370,8 @@ impl<T: UserEvent> WindowDispatch<T> for MockWindowDispatcher {
   }
 
   fn set_size(&self, size: Size) -> Result<()> {
+    let scale_factor = self.state.props().scale_factor;
+    self.state.resize(size.to_physical(scale_factor));
     Ok(())
   }
 
@@ -890,14 +1384,27 @@ impl<T: UserEvent> WindowDispatch<T> for MockWindowDispatcher {
   }
 
   fn set_position(&self, position: Position) -> Result<()> {
+    let scale_factor = self.state.props().scale_factor;
+    self.state.move_to(position.to_physical(scale_factor));
     Ok(())
   }
 
   fn set_fullscreen(&self, fullscreen: bool) -> Result<()> {
+    self.state.set_fullscreen(fullscreen);
+    Ok(())
+  }
+
+  fn set_fullscreen_mode(
+    &self,
+    mode: tauri_runtime::window::FullscreenMode,
+    monitor: Option<tauri_runtime::monitor::Monitor>,
+  ) -> Result<()> {
+    self.state.set_fullscreen(true);
     Ok(())
   }
 
   fn set_focus(&self) -> Result<()> {
+    self.context.focus_window(self.id);
     Ok(())
   }
 
@@ -929,6 +1436,10 @@ impl<T: UserEvent> WindowDispatch<T> for MockWindowDispatcher {
     Ok(())
   }
 
+  fn set_ime_position(&self, rect: Rect) -> Result<()> {
+    Ok(())
+  }
+
   fn start_dragging(&self) -> Result<()> {
     Ok(())
   }
@@ -945,6 +1456,11 @@ impl<T: UserEvent> WindowDispatch<T> for MockWindowDispatcher {
     Ok(())
   }
 
+  fn set_theme(&self, theme: Option<Theme>) -> Result<()> {
+    self.state.set_theme(theme.unwrap_or(Theme::Light));
+    Ok(())
+  }
+
   fn set_size_constraints(
     &self,
     constraints: tauri_runtime::window::WindowSizeConstraints,
@@ -970,6 +1486,87 @@ pub struct MockRuntime {
 }
 
 impl MockRuntime {
+  /// Removes the window, emitting the [`WindowEvent::Destroyed`] event.
+  ///
+  /// Returns `true` if it was the last window and the exit was not prevented.
+  fn destroy_window<T: UserEvent, F: FnMut(RunEvent<T>)>(
+    &self,
+    id: WindowId,
+    callback: &mut F,
+  ) -> bool {
+    let Some(window) = self.context.windows.borrow_mut().remove(&id) else {
+      return false;
+    };
+    for listener in window.state.listeners.lock().unwrap().values() {
+      listener(&WindowEvent::Destroyed);
+    }
+    callback(RunEvent::WindowEvent {
+      label: window.label,
+      event: WindowEvent::Destroyed,
+    });
+
+    let is_empty = self.context.windows.borrow().is_empty();
+    if is_empty {
+      let (tx, rx) = channel();
+      callback(RunEvent::ExitRequested { code: None, tx });
+
+      let recv = rx.try_recv();
+      !matches!(recv, Ok(ExitRequestedEventAction::Prevent))
+    } else {
+      false
+    }
+  }
+
+  /// Handles a message of the event loop, returning `true` if the app must exit.
+  fn handle_message<T: UserEvent, F: FnMut(RunEvent<T>)>(
+    &self,
+    message: Message,
+    callback: &mut F,
+  ) -> bool {
+    match message {
+      Message::Task(p) => p(),
+      Message::CloseWindow(id) => {
+        let window = self
+          .context
+          .windows
+          .borrow()
+          .get(&id)
+          .map(|w| (w.label.clone(), w.state.clone()));
+        if let Some((label, state)) = window {
+          let (tx, rx) = channel();
+          for listener in state.listeners.lock().unwrap().values() {
+            listener(&WindowEvent::CloseRequested {
+              signal_tx: tx.clone(),
+            });
+          }
+          callback(RunEvent::WindowEvent {
+            label,
+            event: WindowEvent::CloseRequested { signal_tx: tx },
+          });
+
+          let should_prevent = rx.try_iter().any(|prevent| prevent);
+          return !should_prevent && self.destroy_window(id, callback);
+        }
+      }
+      Message::DestroyWindow(id) => return self.destroy_window(id, callback),
+      Message::WindowEvent(id, event) => {
+        let label = self
+          .context
+          .windows
+          .borrow()
+          .get(&id)
+          .map(|w| w.label.clone());
+        if let Some(label) = label {
+          callback(RunEvent::WindowEvent { label, event });
+        }
+      }
+      Message::WebviewEvent(label, event) => {
+        callback(RunEvent::WebviewEvent { label, event });
+      }
+    }
+    false
+  }
+
   fn init() -> Self {
     let is_running = Arc::new(AtomicBool::new(false));
     let (tx, rx) = sync_channel(256);
@@ -982,6 +1579,8 @@ impl MockRuntime {
       next_webview_id: Default::default(),
       next_window_event_id: Default::default(),
       next_webview_event_id: Default::default(),
+      next_surface_id: Default::default(),
+      monitors: Arc::new(Mutex::new(vec![mock_monitor()])),
     };
     Self {
       is_running,
@@ -1021,41 +1620,7 @@ impl<T: UserEvent> Runtime<T> for MockRuntime {
     pending: PendingWindow<T, Self>,
     _after_window_creation: Option<F>,
   ) -> Result<DetachedWindow<T, Self>> {
-    let id = self.context.next_window_id();
-
-    let (webview_id, webviews) = if let Some(w) = &pending.webview {
-      (Some(self.context.next_webview_id()), vec![Webview])
-    } else {
-      (None, Vec::new())
-    };
-
-    self.context.windows.borrow_mut().insert(
-      id,
-      Window {
-        label: pending.label.clone(),
-        webviews,
-      },
-    );
-
-    let webview = webview_id.map(|id| DetachedWebview {
-      label: pending.label.clone(),
-      dispatcher: MockWebviewDispatcher {
-        id,
-        context: self.context.clone(),
-        url: Arc::new(Mutex::new(pending.webview.unwrap().url)),
-        last_evaluated_script: Default::default(),
-      },
-    });
-
-    Ok(DetachedWindow {
-      id,
-      label: pending.label,
-      dispatcher: MockWindowDispatcher {
-        id,
-        context: self.context.clone(),
-      },
-      webview,
-    })
+    Ok(self.context.create_window(pending))
   }
 
   fn create_webview(
@@ -1063,33 +1628,19 @@ impl<T: UserEvent> Runtime<T> for MockRuntime {
     window_id: WindowId,
     pending: PendingWebview<T, Self>,
   ) -> Result<DetachedWebview<T, Self>> {
-    let id = self.context.next_webview_id();
-    let webview = Webview;
-    if let Some(w) = self.context.windows.borrow_mut().get_mut(&window_id) {
-      w.webviews.push(webview);
-    }
-
-    Ok(DetachedWebview {
-      label: pending.label,
-      dispatcher: MockWebviewDispatcher {
-        id,
-        context: self.context.clone(),
-        last_evaluated_script: Default::default(),
-        url: Arc::new(Mutex::new(pending.url)),
-      },
-    })
+    Ok(self.context.create_webview(window_id, pending))
   }
 
   fn primary_monitor(&self) -> Option<Monitor> {
-    unimplemented!()
+    self.context.monitors().into_iter().next()
   }
 
   fn monitor_from_point(&self, x: f64, y: f64) -> Option<Monitor> {
-    unimplemented!()
+    self.context.monitor_from_point(x, y)
   }
 
   fn available_monitors(&self) -> Vec<Monitor> {
-    unimplemented!()
+    self.context.monitors()
   }
 
   #[cfg(target_os = "macos")]
@@ -1115,7 +1666,45 @@ impl<T: UserEvent> Runtime<T> for MockRuntime {
     target_os = "netbsd",
     target_os = "openbsd"
   ))]
-  fn run_iteration<F: FnMut(RunEvent<T>)>(&mut self, callback: F) {}
+  fn run_iteration<F: FnMut(RunEvent<T>)>(&mut self, callback: F) {
+    self.pump_events(Some(std::time::Duration::ZERO), callback)
+  }
+
+  #[cfg(any(
+    target_os = "macos",
+    windows,
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+  ))]
+  fn pump_events<F: FnMut(RunEvent<T>)>(
+    &mut self,
+    timeout: Option<std::time::Duration>,
+    mut callback: F,
+  ) {
+    if !self.is_running.swap(true, Ordering::Relaxed) {
+      callback(RunEvent::Ready);
+    }
+
+    let first = match timeout {
+      Some(timeout) => self.run_rx.recv_timeout(timeout).ok(),
+      None => self.run_rx.recv().ok(),
+    };
+    let messages = first
+      .into_iter()
+      .chain(self.run_rx.try_iter())
+      .collect::<Vec<_>>();
+    for message in messages {
+      if self.handle_message(message, &mut callback) {
+        callback(RunEvent::Exit);
+        return;
+      }
+    }
+
+    callback(RunEvent::MainEventsCleared);
+  }
 
   fn run<F: FnMut(RunEvent<T>) + 'static>(self, mut callback: F) {
     self.is_running.store(true, Ordering::Relaxed);
@@ -1123,58 +1712,8 @@ impl<T: UserEvent> Runtime<T> for MockRuntime {
 
     loop {
       if let Ok(m) = self.run_rx.try_recv() {
-        match m {
-          Message::Task(p) => p(),
-          Message::CloseWindow(id) => {
-            let label = self
-              .context
-              .windows
-              .borrow()
-              .get(&id)
-              .map(|w| w.label.clone());
-            if let Some(label) = label {
-              let (tx, rx) = channel();
-              callback(RunEvent::WindowEvent {
-                label,
-                event: WindowEvent::CloseRequested { signal_tx: tx },
-              });
-
-              let should_prevent = matches!(rx.try_recv(), Ok(true));
-              if !should_prevent {
-                self.context.windows.borrow_mut().remove(&id);
-
-                let is_empty = self.context.windows.borrow().is_empty();
-                if is_empty {
-                  let (tx, rx) = channel();
-                  callback(RunEvent::ExitRequested { code: None, tx });
-
-                  let recv = rx.try_recv();
-                  let should_prevent = matches!(recv, Ok(ExitRequestedEventAction::Prevent));
-
-                  if !should_prevent {
-                    break;
-                  }
-                }
-              }
-            }
-          }
-          Message::DestroyWindow(id) => {
-            let removed = self.context.windows.borrow_mut().remove(&id).is_some();
-            if removed {
-              let is_empty = self.context.windows.borrow().is_empty();
-              if is_empty {
-                let (tx, rx) = channel();
-                callback(RunEvent::ExitRequested { code: None, tx });
-
-                let recv = rx.try_recv();
-                let should_prevent = matches!(recv, Ok(ExitRequestedEventAction::Prevent));
-
-                if !should_prevent {
-                  break;
-                }
-              }
-            }
-          }
+        if self.handle_message(m, &mut callback) {
+          break;
         }
       }
 
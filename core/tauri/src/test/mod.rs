@@ -50,17 +50,27 @@
 
 #![allow(unused_variables)]
 
+mod clock;
 mod mock_runtime;
+mod replay;
+pub use clock::*;
 pub use mock_runtime::*;
+pub use replay::*;
 use serde::Serialize;
 use serialize_to_javascript::DefaultTemplate;
 
-use std::{borrow::Cow, collections::HashMap, fmt::Debug};
+use std::{
+  borrow::Cow,
+  collections::HashMap,
+  fmt::Debug,
+  sync::{Arc, Mutex},
+};
 
 use crate::{
   ipc::{InvokeBody, InvokeError, InvokeResponse, RuntimeAuthority},
   webview::InvokeRequest,
-  App, Assets, Builder, Context, Pattern, Runtime, Webview,
+  App, Assets, Builder, Context, DragDropEvent, ImeEvent, Listener, Pattern, PhysicalPosition,
+  PhysicalSize, ProcessCrashReason, Runtime, Theme, Webview, WebviewWindow, Window,
 };
 use tauri_utils::{
   acl::resolved::Resolved,
@@ -114,6 +124,8 @@ pub fn mock_context<R: Runtime, A: Assets<R>>(assets: A) -> crate::Context<R> {
         tray_icon: None,
         macos_private_api: false,
         enable_gtk_app_id: false,
+        dynamic_plugins: None,
+        restore_session: false,
       },
       bundle: Default::default(),
       build: Default::default(),
@@ -172,7 +184,8 @@ pub fn mock_builder() -> Builder<MockRuntime> {
 
   builder.invoke_key = INVOKE_KEY.to_string();
 
-  builder
+  let clock = Arc::new(MockClock::default());
+  builder.clock(clock.clone()).manage(clock)
 }
 
 /// Creates a new [`App`] for testing using the [`mock_context`] with a [`noop_assets`].
@@ -298,11 +311,307 @@ pub fn get_ipc_response<W: AsRef<Webview<MockRuntime>>>(
   }
 }
 
+/// Sends the request to the custom protocol of the webview registered for its URI scheme and returns the response,
+/// such as the `tauri` protocol serving the app assets.
+///
+/// Returns `None` if the webview has no protocol for the scheme.
+///
+/// # Examples
+///
+/// ```rust
+/// let app = tauri::test::mock_app();
+/// let webview = tauri::WebviewWindowBuilder::new(&app, "main", Default::default())
+///   .build()
+///   .unwrap();
+///
+/// let response = tauri::test::get_protocol_response(
+///   &webview,
+///   tauri::http::Request::get("tauri://localhost/index.html")
+///     .body(Vec::new())
+///     .unwrap(),
+/// )
+/// .unwrap();
+/// // the mock app has no assets
+/// assert!(!response.status().is_success());
+/// ```
+pub fn get_protocol_response<W: AsRef<Webview<MockRuntime>>>(
+  webview: &W,
+  request: http::Request<Vec<u8>>,
+) -> Option<http::Response<Cow<'static, [u8]>>> {
+  webview
+    .as_ref()
+    .webview
+    .dispatcher
+    .simulate_protocol_request(request)
+}
+
+/// Simulates user interactions and system events on the windows of the [`MockRuntime`].
+///
+/// The events are delivered to the window event handlers and emitted to the webviews like on the platforms.
+///
+/// # Examples
+///
+/// ```rust
+/// use tauri::test::MockWindowExt;
+///
+/// let app = tauri::test::mock_app();
+/// let window = tauri::WebviewWindowBuilder::new(&app, "main", Default::default())
+///   .build()
+///   .unwrap();
+/// window.on_window_event(|event| {
+///   if let tauri::WindowEvent::Resized(size) = event {
+///     println!("resized to {size:?}");
+///   }
+/// });
+/// window.simulate_resize(tauri::PhysicalSize::new(1024, 768));
+/// assert_eq!(window.inner_size().unwrap(), tauri::PhysicalSize::new(1024, 768));
+/// ```
+pub trait MockWindowExt {
+  /// Simulates the window gaining or losing the focus. Focusing a window blurs the previously focused one.
+  fn simulate_focus(&self, focused: bool);
+
+  /// Simulates the user resizing the window.
+  fn simulate_resize(&self, size: PhysicalSize<u32>);
+
+  /// Simulates the user moving the window.
+  fn simulate_move(&self, position: PhysicalPosition<i32>);
+
+  /// Simulates the window moving to a monitor with a different scale factor.
+  fn simulate_scale_factor_change(&self, scale_factor: f64);
+
+  /// Simulates a change of the system theme.
+  fn simulate_theme_change(&self, theme: Theme);
+
+  /// Simulates the input method editor composing text in the window.
+  fn simulate_ime(&self, event: ImeEvent);
+}
+
+impl MockWindowExt for Window<MockRuntime> {
+  fn simulate_focus(&self, focused: bool) {
+    self.window.dispatcher.simulate_focus(focused);
+  }
+
+  fn simulate_resize(&self, size: PhysicalSize<u32>) {
+    self.window.dispatcher.simulate_resize(size);
+  }
+
+  fn simulate_move(&self, position: PhysicalPosition<i32>) {
+    self.window.dispatcher.simulate_move(position);
+  }
+
+  fn simulate_scale_factor_change(&self, scale_factor: f64) {
+    self
+      .window
+      .dispatcher
+      .simulate_scale_factor_change(scale_factor);
+  }
+
+  fn simulate_theme_change(&self, theme: Theme) {
+    self.window.dispatcher.simulate_theme_change(theme);
+  }
+
+  fn simulate_ime(&self, event: ImeEvent) {
+    self.window.dispatcher.simulate_ime(event);
+  }
+}
+
+impl MockWindowExt for WebviewWindow<MockRuntime> {
+  fn simulate_focus(&self, focused: bool) {
+    self.webview.window().simulate_focus(focused);
+  }
+
+  fn simulate_resize(&self, size: PhysicalSize<u32>) {
+    self.webview.window().simulate_resize(size);
+  }
+
+  fn simulate_move(&self, position: PhysicalPosition<i32>) {
+    self.webview.window().simulate_move(position);
+  }
+
+  fn simulate_scale_factor_change(&self, scale_factor: f64) {
+    self
+      .webview
+      .window()
+      .simulate_scale_factor_change(scale_factor);
+  }
+
+  fn simulate_theme_change(&self, theme: Theme) {
+    self.webview.window().simulate_theme_change(theme);
+  }
+
+  fn simulate_ime(&self, event: ImeEvent) {
+    self.webview.window().simulate_ime(event);
+  }
+}
+
+/// Simulates user interactions on the webviews of the [`MockRuntime`] and intercepts the scripts they evaluate.
+///
+/// # Examples
+///
+/// ```rust
+/// use tauri::test::MockWebviewExt;
+///
+/// let app = tauri::test::mock_app();
+/// let webview = tauri::WebviewWindowBuilder::new(&app, "main", Default::default())
+///   .on_navigation(|url| url.host_str() != Some("example.com"))
+///   .build()
+///   .unwrap();
+///
+/// assert!(!webview.simulate_navigation("https://example.com".parse().unwrap()));
+/// assert!(webview.simulate_navigation("http://tauri.localhost/about".parse().unwrap()));
+///
+/// webview.clear_evaluated_scripts();
+/// webview.eval("console.log('hello')").unwrap();
+/// assert_eq!(webview.evaluated_scripts(), vec!["console.log('hello')"]);
+/// ```
+pub trait MockWebviewExt {
+  /// Simulates the user navigating to the URL, e.g. by clicking a link, running the navigation and page load handlers.
+  ///
+  /// Returns `false` if the navigation handler blocked the navigation.
+  fn simulate_navigation(&self, url: url::Url) -> bool;
+
+  /// Simulates a file drag and drop event on the webview.
+  fn simulate_drag_drop(&self, event: DragDropEvent);
+
+  /// Simulates a crash of the webview process, applying the [`CrashRecovery`](crate::webview::CrashRecovery) policy of the webview.
+  fn simulate_process_crash(&self, reason: ProcessCrashReason);
+
+  /// The scripts evaluated on the webview since it was created or [`Self::clear_evaluated_scripts`] was called, in order.
+  ///
+  /// This includes the scripts used by Tauri to emit the events to the webview.
+  fn evaluated_scripts(&self) -> Vec<String>;
+
+  /// Clears the list of [evaluated scripts](Self::evaluated_scripts).
+  fn clear_evaluated_scripts(&self);
+}
+
+impl MockWebviewExt for Webview<MockRuntime> {
+  fn simulate_navigation(&self, url: url::Url) -> bool {
+    self.webview.dispatcher.simulate_navigation(url)
+  }
+
+  fn simulate_drag_drop(&self, event: DragDropEvent) {
+    self.webview.dispatcher.simulate_drag_drop(event);
+  }
+
+  fn simulate_process_crash(&self, reason: ProcessCrashReason) {
+    self.webview.dispatcher.simulate_process_crash(reason);
+  }
+
+  fn evaluated_scripts(&self) -> Vec<String> {
+    self.webview.dispatcher.evaluated_scripts()
+  }
+
+  fn clear_evaluated_scripts(&self) {
+    self.webview.dispatcher.clear_evaluated_scripts();
+  }
+}
+
+impl MockWebviewExt for WebviewWindow<MockRuntime> {
+  fn simulate_navigation(&self, url: url::Url) -> bool {
+    self.webview.simulate_navigation(url)
+  }
+
+  fn simulate_drag_drop(&self, event: DragDropEvent) {
+    self.webview.simulate_drag_drop(event);
+  }
+
+  fn simulate_process_crash(&self, reason: ProcessCrashReason) {
+    self.webview.simulate_process_crash(reason);
+  }
+
+  fn evaluated_scripts(&self) -> Vec<String> {
+    self.webview.evaluated_scripts()
+  }
+
+  fn clear_evaluated_scripts(&self) {
+    self.webview.clear_evaluated_scripts();
+  }
+}
+
+/// Records the payloads of an event, see [`record_events`].
+#[derive(Debug, Clone)]
+pub struct EventRecorder {
+  payloads: Arc<Mutex<Vec<String>>>,
+}
+
+impl EventRecorder {
+  /// The JSON payloads of the events emitted so far.
+  pub fn payloads(&self) -> Vec<String> {
+    self.payloads.lock().unwrap().clone()
+  }
+
+  /// The payloads of the events emitted so far, deserialized.
+  pub fn deserialize<T: serde::de::DeserializeOwned>(&self) -> serde_json::Result<Vec<T>> {
+    self
+      .payloads
+      .lock()
+      .unwrap()
+      .iter()
+      .map(|p| serde_json::from_str(p))
+      .collect()
+  }
+}
+
+/// Records the payloads of the event emitted to any target, to assert on them.
+///
+/// # Examples
+///
+/// ```rust
+/// use tauri::{test::record_events, Emitter};
+///
+/// let app = tauri::test::mock_app();
+/// let recorder = record_events(&app, "synchronized");
+/// app.emit("synchronized", 42).unwrap();
+/// assert_eq!(recorder.deserialize::<u32>().unwrap(), vec![42]);
+/// ```
+pub fn record_events<R: Runtime, L: Listener<R>>(listener: &L, event: &str) -> EventRecorder {
+  let recorder = EventRecorder {
+    payloads: Default::default(),
+  };
+  let payloads = recorder.payloads.clone();
+  listener.listen_any(event, move |event| {
+    payloads.lock().unwrap().push(event.payload().to_string());
+  });
+  recorder
+}
+
+/// Replaces the monitors of the [`MockRuntime`], e.g. to simulate a laptop being undocked.
+///
+/// The change is detected by the next check of the monitors, every 2 seconds of the [`MockClock`].
+pub fn simulate_monitors(
+  app: &crate::AppHandle<MockRuntime>,
+  monitors: Vec<tauri_runtime::monitor::Monitor>,
+) {
+  app.runtime_handle.simulate_monitors(monitors);
+}
+
 #[cfg(test)]
 mod tests {
-  use std::time::Duration;
+  use std::{
+    sync::{
+      atomic::{AtomicBool, Ordering},
+      Arc,
+    },
+    time::Duration,
+  };
 
-  use super::mock_app;
+  use super::{
+    mock_app, mock_builder, mock_context, noop_assets, record_events, IpcRecord, IpcSession,
+    MockWebviewExt, MockWindowExt, RecordedBody, RecordedResponse, ReplayMismatch,
+  };
+  use crate::{
+    DragDropEvent, Emitter, PhysicalPosition, PhysicalSize, ProcessCrashReason, WebviewUrl,
+  };
+
+  #[crate::command(root = "crate")]
+  fn greet(app: crate::AppHandle<super::MockRuntime>, name: &str) -> Result<String, String> {
+    if name.is_empty() {
+      return Err("missing name".into());
+    }
+    app.emit("greeted", name).map_err(|e| e.to_string())?;
+    Ok(format!("Hello, {name}!"))
+  }
 
   #[test]
   fn run_app() {
@@ -321,4 +630,240 @@ mod tests {
       println!("{event:?}");
     });
   }
+
+  #[test]
+  fn pump_events() {
+    let mut app = mock_app();
+    let ready = Arc::new(AtomicBool::new(false));
+    let ready_ = ready.clone();
+    app.pump_events(Some(Duration::ZERO), move |_app, event| {
+      if let crate::RunEvent::Ready { .. } = event {
+        ready_.store(true, Ordering::Relaxed);
+      }
+    });
+    assert!(ready.load(Ordering::Relaxed));
+
+    // waits until another thread wakes up the loop
+    let ran = Arc::new(AtomicBool::new(false));
+    let ran_ = ran.clone();
+    let handle = app.handle().clone();
+    std::thread::spawn(move || {
+      std::thread::sleep(Duration::from_millis(50));
+      handle
+        .run_on_main_thread(move || ran_.store(true, Ordering::Relaxed))
+        .unwrap();
+    });
+    app.pump_events(None, |_app, _event| {});
+    assert!(ran.load(Ordering::Relaxed));
+  }
+
+  #[test]
+  fn simulate_window_events() {
+    let app = mock_app();
+    let first = crate::WebviewWindowBuilder::new(&app, "first", Default::default())
+      .build()
+      .unwrap();
+    let second = crate::WebviewWindowBuilder::new(&app, "second", Default::default())
+      .build()
+      .unwrap();
+
+    // the last created window has the focus
+    assert!(!first.is_focused().unwrap());
+    assert!(second.is_focused().unwrap());
+
+    let blur = record_events(&app, "tauri://blur");
+    let focus = record_events(&app, "tauri://focus");
+    first.simulate_focus(true);
+    assert!(first.is_focused().unwrap());
+    assert!(!second.is_focused().unwrap());
+    assert_eq!(blur.payloads().len(), 1);
+    assert_eq!(focus.payloads().len(), 1);
+
+    let resized = record_events(&app, "tauri://resize");
+    let resize_ended = record_events(&app, "tauri://resize-ended");
+    first.simulate_resize(PhysicalSize::new(1024, 768));
+    // resizing to the same size doesn't emit the event
+    first.set_size(PhysicalSize::<u32>::new(1024, 768)).unwrap();
+    assert_eq!(first.inner_size().unwrap(), PhysicalSize::new(1024, 768));
+    assert_eq!(
+      resized.deserialize::<PhysicalSize<u32>>().unwrap(),
+      vec![PhysicalSize::new(1024, 768)]
+    );
+    assert_eq!(
+      resize_ended.deserialize::<PhysicalSize<u32>>().unwrap(),
+      vec![PhysicalSize::new(1024, 768)]
+    );
+
+    let moved = record_events(&app, "tauri://move");
+    let move_ended = record_events(&app, "tauri://move-ended");
+    second.set_position(PhysicalPosition::new(10, 20)).unwrap();
+    assert_eq!(
+      moved.deserialize::<PhysicalPosition<i32>>().unwrap(),
+      vec![PhysicalPosition::new(10, 20)]
+    );
+    assert_eq!(
+      move_ended.deserialize::<PhysicalPosition<i32>>().unwrap(),
+      vec![PhysicalPosition::new(10, 20)]
+    );
+
+    let ime = record_events(&app, "tauri://ime");
+    first.simulate_ime(ImeEvent::Preedit {
+      text: "にほ".into(),
+      cursor: Some((6, 6)),
+    });
+    first.simulate_ime(ImeEvent::Commit("日本".into()));
+    assert_eq!(
+      ime.deserialize::<serde_json::Value>().unwrap(),
+      vec![
+        serde_json::json!({ "type": "preedit", "text": "にほ", "cursor": [6, 6] }),
+        serde_json::json!({ "type": "commit", "text": "日本", "cursor": null }),
+      ]
+    );
+  }
+
+  #[test]
+  fn simulate_webview_events() {
+    let app = mock_app();
+    let webview = crate::WebviewWindowBuilder::new(&app, "main", WebviewUrl::default())
+      .on_navigation(|url| url.scheme() != "https")
+      .build()
+      .unwrap();
+
+    assert!(!webview.simulate_navigation("https://tauri.app".parse().unwrap()));
+    assert!(webview.simulate_navigation("http://tauri.localhost/settings".parse().unwrap()));
+    assert_eq!(
+      webview.url().unwrap().as_str(),
+      "http://tauri.localhost/settings"
+    );
+
+    let dropped = record_events(&app, "tauri://drag-drop");
+    webview.simulate_drag_drop(DragDropEvent::Drop {
+      paths: vec!["file.txt".into()],
+      position: PhysicalPosition::new(1.0, 2.0),
+    });
+    assert_eq!(dropped.payloads().len(), 1);
+
+    webview.clear_evaluated_scripts();
+    webview.eval("document.title = 'test'").unwrap();
+    assert_eq!(
+      webview.evaluated_scripts(),
+      vec!["document.title = 'test'".to_string()]
+    );
+  }
+
+  #[test]
+  fn crash_recovery() {
+    let app = mock_app();
+    let loads = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+    let loads_ = loads.clone();
+    let webview = crate::WebviewWindowBuilder::new(&app, "main", WebviewUrl::default())
+      .crash_recovery(crate::webview::CrashRecovery::Reload { max_attempts: 1 })
+      .on_page_load(move |_webview, payload| {
+        if payload.event() == crate::webview::PageLoadEvent::Finished {
+          loads_.fetch_add(1, Ordering::Relaxed);
+        }
+      })
+      .build()
+      .unwrap();
+    let initial_loads = loads.load(Ordering::Relaxed);
+
+    webview.simulate_process_crash(ProcessCrashReason::Crashed);
+    assert_eq!(loads.load(Ordering::Relaxed), initial_loads + 1);
+
+    // the reload attempts are exhausted
+    webview.simulate_process_crash(ProcessCrashReason::OutOfMemory);
+    assert_eq!(loads.load(Ordering::Relaxed), initial_loads + 1);
+  }
+
+  #[test]
+  fn capture_frame() {
+    let app = mock_app();
+    let webview = crate::WebviewWindowBuilder::new(&app, "main", Default::default())
+      .build()
+      .unwrap();
+
+    let (tx, rx) = std::sync::mpsc::channel();
+    webview
+      .capture_frame(move |frame| tx.send(frame).unwrap())
+      .unwrap();
+    let frame = rx.recv().unwrap().unwrap();
+    assert_eq!(
+      frame.rgba.len(),
+      frame.width as usize * frame.height as usize * 4
+    );
+  }
+
+  #[test]
+  fn child_surface() {
+    use raw_window_handle::HasWindowHandle;
+
+    let app = mock_app();
+    let window = crate::WebviewWindowBuilder::new(&app, "main", Default::default())
+      .build()
+      .unwrap();
+
+    let surface = window.create_child_surface().unwrap();
+    assert!(surface.window_handle().is_ok());
+    assert_eq!(
+      surface.size().unwrap(),
+      crate::window::SurfaceSize {
+        size: PhysicalSize::new(800, 600),
+        scale_factor: 1.0,
+      }
+    );
+
+    let (tx, rx) = std::sync::mpsc::channel();
+    surface.on_resize(move |size| tx.send(size).unwrap());
+    window.simulate_resize(PhysicalSize::new(1024, 768));
+    assert_eq!(rx.try_recv().unwrap().size, PhysicalSize::new(1024, 768));
+  }
+
+  #[test]
+  fn replay_ipc_session() {
+    let app = mock_builder()
+      .invoke_handler(crate::generate_handler![greet])
+      .build(mock_context(noop_assets()))
+      .unwrap();
+    let webview = crate::WebviewWindowBuilder::new(&app, "main", Default::default())
+      .build()
+      .unwrap();
+
+    let session = IpcSession::parse(
+      r#"
+{"type":"invoke","webview":"main","url":"http://tauri.localhost/","cmd":"greet","body":{"json":{"name":"Tauri"}},"response":{"ok":{"json":"Hello, Tauri!"}}}
+{"type":"event","event":"greeted","payload":"Tauri"}
+{"type":"invoke","webview":"main","url":"http://tauri.localhost/","cmd":"greet","body":{"json":{"name":""}},"response":{"err":"missing name"}}
+"#
+      .as_bytes(),
+    )
+    .unwrap();
+    assert_eq!(session.records().len(), 3);
+    session.assert_replay(&webview);
+
+    let mut records = session.records().to_vec();
+    records[0] = IpcRecord::Invoke {
+      webview: "main".into(),
+      url: "http://tauri.localhost".parse().unwrap(),
+      cmd: "greet".into(),
+      body: RecordedBody::Json(serde_json::json!({ "name": "Rust" })),
+      response: RecordedResponse::Ok(RecordedBody::Json("Hello, Tauri!".into())),
+    };
+    let mismatches = IpcSession::new(records).replay(&webview);
+    assert_eq!(
+      mismatches,
+      vec![
+        ReplayMismatch::Response {
+          index: 0,
+          cmd: "greet".into(),
+          expected: RecordedResponse::Ok(RecordedBody::Json("Hello, Tauri!".into())),
+          actual: RecordedResponse::Ok(RecordedBody::Json("Hello, Rust!".into())),
+        },
+        ReplayMismatch::Event {
+          event: "greeted".into(),
+          expected: vec!["Tauri".into()],
+          actual: vec!["Rust".into()],
+        },
+      ]
+    );
+  }
 }
@@ -0,0 +1,200 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::{fmt, io::BufRead, path::Path};
+
+use serde_json::Value as JsonValue;
+
+use super::{get_ipc_response, record_events, MockRuntime, INVOKE_KEY};
+use crate::{ipc::CallbackFn, webview::InvokeRequest, Manager, Webview};
+
+pub use crate::ipc::record::{IpcRecord, RecordedBody, RecordedResponse};
+
+/// A recorded IPC session, replayed against the command handlers of a mock app.
+///
+/// Run the app with `tauri dev` and the [`RECORD_ENV_VAR`](crate::ipc::record::RECORD_ENV_VAR) environment variable set to a file path
+/// to record the commands invoked by the webviews and the events emitted by the app to that file.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use tauri::test::{mock_builder, IpcSession};
+///
+/// #[tauri::command]
+/// fn greet(name: &str) -> String {
+///     format!("Hello, {name}!")
+/// }
+///
+/// let app = mock_builder()
+///     .invoke_handler(tauri::generate_handler![greet])
+///     .build(tauri::generate_context!("test/fixture/src-tauri/tauri.conf.json"))
+///     .unwrap();
+/// let webview = tauri::WebviewWindowBuilder::new(&app, "main", Default::default())
+///     .build()
+///     .unwrap();
+///
+/// IpcSession::load("tests/fixtures/greet.jsonl")
+///     .unwrap()
+///     .assert_replay(&webview);
+/// ```
+#[derive(Debug, Clone)]
+pub struct IpcSession {
+  records: Vec<IpcRecord>,
+}
+
+/// A difference between the recorded session and its replay.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum ReplayMismatch {
+  /// A command responded differently.
+  Response {
+    /// The index of the command record in the session.
+    index: usize,
+    /// The command name.
+    cmd: String,
+    /// The recorded response.
+    expected: RecordedResponse,
+    /// The response of the replay.
+    actual: RecordedResponse,
+  },
+  /// An event was emitted with different payloads.
+  Event {
+    /// The event name.
+    event: String,
+    /// The recorded payloads, in order.
+    expected: Vec<JsonValue>,
+    /// The payloads emitted during the replay, in order.
+    actual: Vec<JsonValue>,
+  },
+}
+
+impl fmt::Display for ReplayMismatch {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::Response {
+        index,
+        cmd,
+        expected,
+        actual,
+      } => write!(
+        f,
+        "command `{cmd}` (record {index}) responded {actual:?} instead of {expected:?}"
+      ),
+      Self::Event {
+        event,
+        expected,
+        actual,
+      } => write!(
+        f,
+        "event `{event}` was emitted with {actual:?} instead of {expected:?}"
+      ),
+    }
+  }
+}
+
+impl IpcSession {
+  /// Loads a session recorded to a file.
+  pub fn load(path: impl AsRef<Path>) -> crate::Result<Self> {
+    IpcRecord::read(path).map(Self::new)
+  }
+
+  /// Parses a session from its JSON lines.
+  pub fn parse<B: BufRead>(reader: B) -> crate::Result<Self> {
+    IpcRecord::parse_lines(reader).map(Self::new)
+  }
+
+  /// Creates a session from its records.
+  pub fn new(records: Vec<IpcRecord>) -> Self {
+    Self { records }
+  }
+
+  /// The records of the session, in order.
+  pub fn records(&self) -> &[IpcRecord] {
+    &self.records
+  }
+
+  /// Invokes the recorded commands on the webview in order and compares their responses,
+  /// and the events emitted meanwhile, with the recording.
+  ///
+  /// The commands are invoked from the webview regardless of the one that invoked them during the recording.
+  pub fn replay<W: AsRef<Webview<MockRuntime>>>(&self, webview: &W) -> Vec<ReplayMismatch> {
+    let mut expected_events: Vec<(&str, Vec<JsonValue>)> = Vec::new();
+    for record in &self.records {
+      if let IpcRecord::Event { event, payload } = record {
+        match expected_events.iter_mut().find(|(e, _)| e == event) {
+          Some((_, payloads)) => payloads.push(payload.clone()),
+          None => expected_events.push((event, vec![payload.clone()])),
+        }
+      }
+    }
+    let recorders = expected_events
+      .iter()
+      .map(|(event, _)| record_events(webview.as_ref().app_handle(), event))
+      .collect::<Vec<_>>();
+
+    let mut mismatches = Vec::new();
+    for (index, record) in self.records.iter().enumerate() {
+      if let IpcRecord::Invoke {
+        url,
+        cmd,
+        body,
+        response,
+        ..
+      } = record
+      {
+        let actual = match get_ipc_response(
+          webview,
+          InvokeRequest {
+            cmd: cmd.clone(),
+            callback: CallbackFn(0),
+            error: CallbackFn(1),
+            url: url.clone(),
+            body: body.clone().into(),
+            headers: Default::default(),
+            invoke_key: INVOKE_KEY.to_string(),
+          },
+        ) {
+          Ok(body) => RecordedResponse::Ok((&body).into()),
+          Err(e) => RecordedResponse::Err(e),
+        };
+        if &actual != response {
+          mismatches.push(ReplayMismatch::Response {
+            index,
+            cmd: cmd.clone(),
+            expected: response.clone(),
+            actual,
+          });
+        }
+      }
+    }
+
+    for ((event, expected), recorder) in expected_events.into_iter().zip(recorders) {
+      let actual = recorder.deserialize::<JsonValue>().unwrap_or_default();
+      if actual != expected {
+        mismatches.push(ReplayMismatch::Event {
+          event: event.to_string(),
+          expected,
+          actual,
+        });
+      }
+    }
+
+    mismatches
+  }
+
+  /// Replays the session on the webview, see [`Self::replay`], and panics if it differs from the recording.
+  pub fn assert_replay<W: AsRef<Webview<MockRuntime>>>(&self, webview: &W) {
+    let mismatches = self.replay(webview);
+    if !mismatches.is_empty() {
+      panic!(
+        "the IPC session replay differs from the recording:\n{}",
+        mismatches
+          .iter()
+          .map(|m| format!("  - {m}"))
+          .collect::<Vec<_>>()
+          .join("\n")
+      );
+    }
+  }
+}
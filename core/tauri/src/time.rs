@@ -0,0 +1,154 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! The time source of the app timers.
+//!
+//! The timers of Tauri, such as the desktop background tasks, and of the plugins should be created with the
+//! [`Clock`] returned by [`Manager::clock`](crate::Manager::clock), so the tests can drive them deterministically
+//! with `tauri::test::MockClock` instead of sleeping.
+//!
+//! ```rust,no_run
+//! use std::time::Duration;
+//! use tauri::{time::Timer, Manager};
+//!
+//! // the timer is canceled when dropped, so it is kept in the app state
+//! struct UpdateCheck(Timer);
+//!
+//! tauri::Builder::default().setup(|app| {
+//!   let timer = app.clock().set_interval(
+//!     Duration::from_secs(60 * 60),
+//!     Box::new(|| println!("checking for updates")),
+//!   );
+//!   app.manage(UpdateCheck(timer));
+//!   Ok(())
+//! });
+//! ```
+
+use std::{
+  sync::{
+    mpsc::{channel, Receiver, RecvTimeoutError, Sender, TryRecvError},
+    Arc,
+  },
+  time::{Duration, Instant},
+};
+
+/// A time source scheduling the timers of the app, see the [module documentation](self).
+pub trait Clock: Send + Sync + 'static {
+  /// The current instant of the clock.
+  fn now(&self) -> Instant;
+
+  /// Runs the callback once after the delay, unless the returned [`Timer`] is dropped first.
+  fn set_timeout(&self, delay: Duration, callback: Box<dyn FnOnce() + Send>) -> Timer;
+
+  /// Runs the callback repeatedly with the interval until the returned [`Timer`] is dropped.
+  fn set_interval(&self, interval: Duration, callback: Box<dyn FnMut() + Send>) -> Timer;
+}
+
+impl<C: Clock> Clock for Arc<C> {
+  fn now(&self) -> Instant {
+    (**self).now()
+  }
+
+  fn set_timeout(&self, delay: Duration, callback: Box<dyn FnOnce() + Send>) -> Timer {
+    (**self).set_timeout(delay, callback)
+  }
+
+  fn set_interval(&self, interval: Duration, callback: Box<dyn FnMut() + Send>) -> Timer {
+    (**self).set_interval(interval, callback)
+  }
+}
+
+/// A timer scheduled on a [`Clock`], canceled when dropped.
+#[derive(Debug)]
+pub struct Timer {
+  _cancel: Sender<()>,
+}
+
+impl Timer {
+  /// Creates a timer and the token the [`Clock`] uses to check whether it was canceled.
+  pub fn new() -> (Self, TimerToken) {
+    let (tx, rx) = channel();
+    (Self { _cancel: tx }, TimerToken { canceled: rx })
+  }
+
+  /// Cancels the timer.
+  pub fn cancel(self) {}
+}
+
+/// Tells a [`Clock`] whether its [`Timer`] was canceled.
+#[derive(Debug)]
+pub struct TimerToken {
+  canceled: Receiver<()>,
+}
+
+impl TimerToken {
+  /// Whether the timer was canceled.
+  pub fn is_canceled(&self) -> bool {
+    matches!(self.canceled.try_recv(), Err(TryRecvError::Disconnected))
+  }
+
+  /// Blocks the current thread for the duration, returning `false` if the timer is canceled meanwhile.
+  pub fn sleep(&self, duration: Duration) -> bool {
+    matches!(
+      self.canceled.recv_timeout(duration),
+      Err(RecvTimeoutError::Timeout)
+    )
+  }
+}
+
+/// The [`Clock`] of the operating system, running each timer on its own thread.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+  fn now(&self) -> Instant {
+    Instant::now()
+  }
+
+  fn set_timeout(&self, delay: Duration, callback: Box<dyn FnOnce() + Send>) -> Timer {
+    let (timer, token) = Timer::new();
+    std::thread::spawn(move || {
+      if token.sleep(delay) {
+        callback();
+      }
+    });
+    timer
+  }
+
+  fn set_interval(&self, interval: Duration, mut callback: Box<dyn FnMut() + Send>) -> Timer {
+    let (timer, token) = Timer::new();
+    std::thread::spawn(move || {
+      while token.sleep(interval) {
+        callback();
+      }
+    });
+    timer
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::{sync::mpsc::channel, time::Duration};
+
+  use super::{Clock, SystemClock, Timer};
+
+  #[test]
+  fn cancel_timer() {
+    let (timer, token) = Timer::new();
+    assert!(!token.is_canceled());
+    timer.cancel();
+    assert!(token.is_canceled());
+    assert!(!token.sleep(Duration::from_secs(5)));
+  }
+
+  #[test]
+  fn system_clock_timeout() {
+    let (tx, rx) = channel();
+    let _timer = SystemClock.set_timeout(
+      Duration::from_millis(10),
+      Box::new(move || tx.send(()).unwrap()),
+    );
+    rx.recv_timeout(Duration::from_secs(5)).unwrap();
+  }
+}
@@ -5,14 +5,16 @@
 //! The Tauri webview types and functions.
 
 pub(crate) mod plugin;
+#[cfg(desktop)]
+pub(crate) mod pool;
 mod webview_window;
 
 pub use webview_window::{WebviewWindow, WebviewWindowBuilder};
 
 use http::HeaderMap;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use tauri_macros::default_runtime;
-pub use tauri_runtime::webview::PageLoadEvent;
+pub use tauri_runtime::webview::{CrashRecovery, PageLoadEvent, WebviewFrame};
 #[cfg(desktop)]
 use tauri_runtime::{
   dpi::{PhysicalPosition, PhysicalSize, Position, Size},
@@ -40,6 +42,7 @@ use crate::{
 
 use std::{
   borrow::Cow,
+  future::Future,
   hash::{Hash, Hasher},
   path::PathBuf,
   sync::{Arc, Mutex, MutexGuard},
@@ -110,6 +113,49 @@ impl<'a> PageLoadPayload<'a> {
   }
 }
 
+/// A Content-Security-Policy violation report sent by the webview.
+///
+/// Both the legacy `report-uri` format and the Reporting API format are normalized into this struct.
+/// See <https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Content-Security-Policy/report-uri>.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub struct CspViolationReport {
+  /// The URL of the document in which the violation occurred.
+  #[serde(alias = "documentURL")]
+  pub document_uri: Option<String>,
+  /// The referrer of the document in which the violation occurred.
+  pub referrer: Option<String>,
+  /// The URL of the resource that was blocked, or `inline`, `eval` for inline scripts and eval calls.
+  #[serde(alias = "blockedURL")]
+  pub blocked_uri: Option<String>,
+  /// The directive whose enforcement caused the violation.
+  pub violated_directive: Option<String>,
+  /// The directive whose enforcement caused the violation, normalized by the webview.
+  #[serde(alias = "effectiveDirective")]
+  pub effective_directive: Option<String>,
+  /// The original policy as specified by the Content-Security-Policy header.
+  #[serde(alias = "originalPolicy")]
+  pub original_policy: Option<String>,
+  /// Either `enforce` or `report`.
+  pub disposition: Option<String>,
+  /// The URL of the resource where the violation occurred.
+  #[serde(alias = "sourceFile")]
+  pub source_file: Option<String>,
+  /// The line number in [`Self::source_file`] where the violation occurred.
+  #[serde(alias = "lineNumber")]
+  pub line_number: Option<u64>,
+  /// The column number in [`Self::source_file`] where the violation occurred.
+  #[serde(alias = "columnNumber")]
+  pub column_number: Option<u64>,
+  /// The first characters of the inline script, event handler or style that caused the violation.
+  #[serde(alias = "sample")]
+  pub script_sample: Option<String>,
+  /// The HTTP status code of the document in which the violation occurred.
+  #[serde(alias = "statusCode")]
+  pub status_code: Option<u16>,
+}
+
 /// The IPC invoke request.
 ///
 /// # Stability
@@ -800,6 +846,34 @@ fn main() {
     self.webview_attributes.zoom_hotkeys_enabled = enabled;
     self
   }
+
+  /// Draws the content under the system bars and display cutouts, padding the webview body with the safe area insets.
+  ///
+  /// The insets are exposed to CSS as the `--safe-area-inset-top`, `--safe-area-inset-right`,
+  /// `--safe-area-inset-bottom` and `--safe-area-inset-left` variables,
+  /// see [`Window::safe_area_insets`](crate::window::Window::safe_area_insets).
+  ///
+  /// ## Platform-specific:
+  ///
+  /// - **Linux / Windows / macOS**: The insets are always zero.
+  #[must_use]
+  pub fn edge_to_edge(mut self, enabled: bool) -> Self {
+    self.webview_attributes.edge_to_edge = enabled;
+    self
+  }
+
+  /// Sets what happens when the webview process crashes, reloading the page instead of leaving the webview blank.
+  ///
+  /// The [`WebviewEvent::ProcessCrashed`](crate::WebviewEvent::ProcessCrashed) event is emitted either way.
+  ///
+  /// ## Platform-specific:
+  ///
+  /// - **macOS / Android / iOS**: Unsupported.
+  #[must_use]
+  pub fn crash_recovery(mut self, recovery: CrashRecovery) -> Self {
+    self.webview_attributes.crash_recovery = recovery;
+    self
+  }
 }
 
 /// Webview.
@@ -905,6 +979,27 @@ impl<R: Runtime> Webview<R> {
     self.webview.dispatcher.print().map_err(Into::into)
   }
 
+  /// Captures the composited content of the webview as a RGBA image,
+  /// e.g. to upload it to a texture when embedding the webview in a wgpu or OpenGL scene.
+  ///
+  /// The callback is called on the main thread once the frame is ready.
+  /// The system webviews do not expose their GPU surfaces, so the frame is copied to memory.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Linux**: Requires WebKitGTK to render the webview, only the visible region is captured.
+  /// - **Android / iOS**: Unsupported, the callback receives an error.
+  pub fn capture_frame<F: FnOnce(crate::Result<WebviewFrame>) + Send + 'static>(
+    &self,
+    callback: F,
+  ) -> crate::Result<()> {
+    self
+      .webview
+      .dispatcher
+      .capture_frame(move |frame| callback(frame.map_err(Into::into)))
+      .map_err(Into::into)
+  }
+
   /// Get the cursor position relative to the top-left hand corner of the desktop.
   ///
   /// Note that the top-left hand corner of the desktop is not necessarily the same as the screen.
@@ -997,6 +1092,25 @@ impl<R: Runtime> Webview<R> {
   pub fn size(&self) -> crate::Result<PhysicalSize<u32>> {
     self.webview.dispatcher.size().map_err(Into::into)
   }
+
+  /// Returns the value of [`Self::bounds`] without blocking the calling thread.
+  pub fn bounds_async(&self) -> impl Future<Output = crate::Result<Rect>> + Send + 'static {
+    self.get_async(|d| d.bounds())
+  }
+
+  /// Returns the value of [`Self::position`] without blocking the calling thread.
+  pub fn position_async(
+    &self,
+  ) -> impl Future<Output = crate::Result<PhysicalPosition<i32>>> + Send + 'static {
+    self.get_async(|d| d.position())
+  }
+
+  /// Returns the value of [`Self::size`] without blocking the calling thread.
+  pub fn size_async(
+    &self,
+  ) -> impl Future<Output = crate::Result<PhysicalSize<u32>>> + Send + 'static {
+    self.get_async(|d| d.size())
+  }
 }
 
 /// Webview APIs.
@@ -1091,12 +1205,27 @@ fn main() {
       .map(|url| url.parse().map_err(crate::Error::InvalidUrl))?
   }
 
+  /// Returns the value of [`Self::url`] without blocking the calling thread.
+  pub fn url_async(&self) -> impl Future<Output = crate::Result<Url>> + Send + 'static {
+    let url = self.get_async(|d| d.url());
+    async move { url.await?.parse().map_err(crate::Error::InvalidUrl) }
+  }
+
+  /// Runs the getter on the main thread, see [`WebviewDispatch::get_async`].
+  fn get_async<T: Send + 'static>(
+    &self,
+    getter: impl FnOnce(&R::WebviewDispatcher) -> crate::runtime::Result<T> + Send + 'static,
+  ) -> impl Future<Output = crate::Result<T>> + Send + 'static {
+    let future = self.webview.dispatcher.get_async(getter);
+    async move { future.await.map_err(Into::into) }
+  }
+
   /// Navigates the webview to the defined url.
   pub fn navigate(&mut self, url: Url) -> crate::Result<()> {
     self.webview.dispatcher.navigate(url).map_err(Into::into)
   }
 
-  fn is_local_url(&self, current_url: &Url) -> bool {
+  pub(crate) fn is_local_url(&self, current_url: &Url) -> bool {
     // if from `tauri://` custom protocol
     ({
       let protocol_url = self.manager().protocol_url();
@@ -1162,6 +1291,17 @@ fn main() {
 
     let custom_responder = self.manager().webview.invoke_responder.clone();
 
+    // the channel data is fetched by the frontend and cannot be replayed
+    #[cfg(dev)]
+    let record = (manager.ipc_recorder.is_some()
+      && request.cmd != crate::ipc::channel::FETCH_CHANNEL_DATA_COMMAND)
+      .then(|| {
+        (
+          request.url.clone(),
+          crate::ipc::record::RecordedBody::from(&request.body),
+        )
+      });
+
     let resolver = InvokeResolver::new(
       self.clone(),
       Arc::new(Mutex::new(Some(Box::new(
@@ -1171,6 +1311,17 @@ fn main() {
             (responder)(&webview, &cmd, &response, callback, error);
           }
 
+          #[cfg(dev)]
+          if let (Some((url, body)), Some(recorder)) = (record, &webview.manager().ipc_recorder) {
+            recorder.record(&crate::ipc::record::IpcRecord::Invoke {
+              webview: webview.label().to_string(),
+              url,
+              cmd: cmd.clone(),
+              body,
+              response: (&response).into(),
+            });
+          }
+
           responder(webview, cmd, response, callback, error);
         },
       )))),
@@ -1484,11 +1635,27 @@ tauri::Builder::default()
   /// - **macOS**: available on macOS 11+ only.
   /// - **iOS**: available on iOS 14+ only.
   pub fn set_zoom(&self, scale_factor: f64) -> crate::Result<()> {
+    self.webview.dispatcher.set_zoom(scale_factor)?;
     self
+      .manager()
       .webview
-      .dispatcher
-      .set_zoom(scale_factor)
-      .map_err(Into::into)
+      .zoom
+      .lock()
+      .unwrap()
+      .insert(self.label().into(), scale_factor);
+    Ok(())
+  }
+
+  /// Resizes the webview to the area above the soft keyboard while it is shown, instead of letting the keyboard cover the content.
+  ///
+  /// The keyboard visibility and height are emitted to the webviews as the
+  /// [`keyboard://will-show`](crate::window::KEYBOARD_WILL_SHOW_EVENT) and [`keyboard://did-hide`](crate::window::KEYBOARD_DID_HIDE_EVENT) events.
+  ///
+  /// ## Platform-specific:
+  ///
+  /// - **Linux / Windows / macOS**: Unsupported.
+  pub fn set_resize_on_keyboard(&self, enabled: bool) -> crate::Result<()> {
+    crate::window::keyboard::set_resize_on_keyboard(&self.app_handle, enabled)
   }
 }
 
@@ -1707,4 +1874,26 @@ mod tests {
     crate::test_utils::assert_send::<super::Webview>();
     crate::test_utils::assert_sync::<super::Webview>();
   }
+
+  #[test]
+  fn async_getters() {
+    use crate::Manager;
+
+    let app = crate::test::mock_app();
+    crate::WebviewWindowBuilder::new(&app, "main", Default::default())
+      .build()
+      .unwrap();
+    let webview = app.get_webview("main").unwrap();
+
+    crate::async_runtime::block_on(async {
+      assert_eq!(webview.url_async().await.unwrap(), webview.url().unwrap());
+      assert_eq!(webview.size_async().await.unwrap(), webview.size().unwrap());
+      assert_eq!(
+        webview.position_async().await.unwrap(),
+        webview.position().unwrap()
+      );
+      let bounds = webview.bounds_async().await.unwrap();
+      assert_eq!(bounds.size.to_physical::<u32>(1.), webview.size().unwrap());
+    });
+  }
 }
@@ -0,0 +1,138 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! The pool of prewarmed webview windows, see [`AppHandle::prewarm_webviews`].
+
+use std::{collections::VecDeque, sync::Mutex};
+
+use tauri_utils::config::WebviewUrl;
+
+use crate::{AppHandle, Manager, Runtime, WebviewWindow, WebviewWindowBuilder};
+
+/// The prefix of the labels of the pooled webview windows.
+pub(crate) const POOL_LABEL_PREFIX: &str = "webview-pool-";
+
+#[derive(Debug, Default)]
+struct PoolState {
+  /// The number of windows the pool keeps ready.
+  size: usize,
+  /// The labels of the hidden windows ready to be claimed, oldest first.
+  labels: VecDeque<String>,
+  /// The number of windows being created.
+  pending: usize,
+  next_id: u32,
+}
+
+/// The hidden webview windows created with [`AppHandle::prewarm_webviews`]
+/// and claimed with [`AppHandle::claim_prewarmed_webview`].
+#[derive(Debug, Default)]
+pub(crate) struct WebviewPool {
+  state: Mutex<PoolState>,
+}
+
+impl WebviewPool {
+  /// Sets the size of the pool, destroying the windows above it, and creates the missing windows.
+  pub(crate) fn resize<R: Runtime>(&self, app: &AppHandle<R>, size: usize) -> crate::Result<()> {
+    let excess = {
+      let mut state = self.state.lock().unwrap();
+      state.size = size;
+      let keep = size.min(state.labels.len());
+      state.labels.split_off(keep)
+    };
+    for label in excess {
+      if let Some(window) = app.get_webview_window(&label) {
+        window.destroy()?;
+      }
+    }
+    self.fill(app)
+  }
+
+  /// Creates hidden windows until the pool is full.
+  ///
+  /// The lock is not held while a window is created, since the main thread may claim a window in the meantime.
+  pub(crate) fn fill<R: Runtime>(&self, app: &AppHandle<R>) -> crate::Result<()> {
+    loop {
+      let label = {
+        let mut state = self.state.lock().unwrap();
+        if state.labels.len() + state.pending >= state.size {
+          return Ok(());
+        }
+        state.pending += 1;
+        let label = format!("{POOL_LABEL_PREFIX}{}", state.next_id);
+        state.next_id += 1;
+        label
+      };
+
+      app.manager.add_internal_label(label.clone());
+      let result = WebviewWindowBuilder::new(app, &label, WebviewUrl::default())
+        .visible(false)
+        .build();
+
+      let mut state = self.state.lock().unwrap();
+      state.pending -= 1;
+      result?;
+      state.labels.push_back(label);
+    }
+  }
+
+  /// Takes the oldest window of the pool, skipping the windows that were closed.
+  pub(crate) fn claim<R: Runtime>(&self, app: &AppHandle<R>) -> Option<WebviewWindow<R>> {
+    let mut state = self.state.lock().unwrap();
+    while let Some(label) = state.labels.pop_front() {
+      if let Some(window) = app.get_webview_window(&label) {
+        return Some(window);
+      }
+    }
+    None
+  }
+
+  /// Creates the windows taken from the pool in the background.
+  pub(crate) fn replenish<R: Runtime>(app: &AppHandle<R>) {
+    let app = app.clone();
+    crate::async_runtime::spawn(async move {
+      if let Err(e) = app.manager.webview_pool.fill(&app) {
+        log::error!("failed to prewarm a webview window: {e}");
+      }
+    });
+  }
+
+  #[cfg(test)]
+  pub(crate) fn ready(&self) -> usize {
+    self.state.lock().unwrap().labels.len()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{test::mock_app, Manager, WebviewUrl};
+
+  #[test]
+  fn claims_prewarmed_windows() {
+    let app = mock_app();
+    let handle = app.handle();
+
+    handle.prewarm_webviews(1).unwrap();
+    assert_eq!(handle.manager.webview_pool.ready(), 1);
+    let pooled = handle.get_webview_window("webview-pool-0").unwrap();
+    assert!(!pooled.is_visible().unwrap());
+
+    let claimed = handle
+      .claim_prewarmed_webview(WebviewUrl::App("editor.html".into()))
+      .unwrap()
+      .unwrap();
+    assert_eq!(claimed.label(), "webview-pool-0");
+    assert!(claimed.is_visible().unwrap());
+    assert!(claimed.url().unwrap().path().ends_with("editor.html"));
+  }
+
+  #[test]
+  fn empty_pool() {
+    let app = mock_app();
+    assert!(app
+      .handle()
+      .claim_prewarmed_webview(WebviewUrl::default())
+      .unwrap()
+      .is_none());
+  }
+}
@@ -0,0 +1,43 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+;(function () {
+  function injectStyle() {
+    const style = document.createElement('style')
+    // the variables are updated by Tauri when the insets change,
+    // the padding has no specificity so it is easily overridden by the app
+    style.textContent = `
+      :root {
+        --safe-area-inset-top: env(safe-area-inset-top, 0px);
+        --safe-area-inset-right: env(safe-area-inset-right, 0px);
+        --safe-area-inset-bottom: env(safe-area-inset-bottom, 0px);
+        --safe-area-inset-left: env(safe-area-inset-left, 0px);
+      }
+      :where(body) {
+        box-sizing: border-box;
+        padding: var(--safe-area-inset-top) var(--safe-area-inset-right)
+          var(--safe-area-inset-bottom) var(--safe-area-inset-left);
+      }
+    `
+    document.head.appendChild(style)
+
+    let viewport = document.querySelector('meta[name="viewport"]')
+    if (!viewport) {
+      viewport = document.createElement('meta')
+      viewport.name = 'viewport'
+      viewport.content = 'width=device-width, initial-scale=1'
+      document.head.appendChild(viewport)
+    }
+    // lets the content be drawn under the display cutouts on iOS
+    if (!viewport.content.includes('viewport-fit')) {
+      viewport.content += ', viewport-fit=cover'
+    }
+  }
+
+  if (document.readyState === 'loading') {
+    document.addEventListener('DOMContentLoaded', injectStyle)
+  } else {
+    injectStyle()
+  }
+})()
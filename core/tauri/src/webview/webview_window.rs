@@ -6,6 +6,7 @@
 
 use std::{
   borrow::Cow,
+  future::Future,
   path::PathBuf,
   sync::{Arc, MutexGuard},
 };
@@ -537,6 +538,34 @@ impl<'a, R: Runtime, M: Manager<R>> WebviewWindowBuilder<'a, R, M> {
     self
   }
 
+  /// Replaces the title bar of the window with an overlay of the caption buttons over the webview,
+  /// leaving the rest of the title bar to the page.
+  ///
+  /// See [`TitleBarOverlay`](crate::window::TitleBarOverlay) for the CSS variables of the title bar area.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **macOS:** Sets the [`TitleBarStyle::Overlay`](crate::TitleBarStyle::Overlay) title bar style and hides the title.
+  /// - **Windows / Linux:** Removes the decorations of the window.
+  #[must_use]
+  pub fn title_bar_overlay(mut self, overlay: crate::window::TitleBarOverlay) -> Self {
+    #[cfg(target_os = "macos")]
+    {
+      self.window_builder = self
+        .window_builder
+        .title_bar_style(crate::TitleBarStyle::Overlay)
+        .hidden_title(true);
+    }
+    #[cfg(not(target_os = "macos"))]
+    {
+      self.window_builder = self.window_builder.decorations(false);
+    }
+    self.webview_builder = self
+      .webview_builder
+      .initialization_script(&overlay.script());
+    self
+  }
+
   /// Whether the window should always be below other windows.
   #[must_use]
   pub fn always_on_bottom(mut self, always_on_bottom: bool) -> Self {
@@ -599,6 +628,32 @@ impl<'a, R: Runtime, M: Manager<R>> WebviewWindowBuilder<'a, R, M> {
     self
   }
 
+  /// Sets how the corners of the window are rounded.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Windows:** Requires Windows 11.
+  /// - **macOS:** Rounds the content of the window, mostly useful without decorations.
+  /// - **Linux:** Unsupported.
+  #[must_use]
+  pub fn corner_preference(mut self, preference: crate::window::CornerPreference) -> Self {
+    self.window_builder = self.window_builder.corner_preference(preference);
+    self
+  }
+
+  /// Sets the color of the window border.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Windows:** Requires Windows 11.
+  /// - **macOS:** Draws a 1px border around the content of the window.
+  /// - **Linux:** Unsupported.
+  #[must_use]
+  pub fn border_color(mut self, color: crate::window::Color) -> Self {
+    self.window_builder = self.window_builder.border_color(color);
+    self
+  }
+
   /// Sets a parent to the window to be created.
   ///
   /// ## Platform-specific
@@ -884,6 +939,34 @@ impl<'a, R: Runtime, M: Manager<R>> WebviewWindowBuilder<'a, R, M> {
     self.webview_builder = self.webview_builder.zoom_hotkeys_enabled(enabled);
     self
   }
+
+  /// Draws the content under the system bars and display cutouts, padding the webview body with the safe area insets.
+  ///
+  /// The insets are exposed to CSS as the `--safe-area-inset-top`, `--safe-area-inset-right`,
+  /// `--safe-area-inset-bottom` and `--safe-area-inset-left` variables,
+  /// see [`Window::safe_area_insets`](crate::window::Window::safe_area_insets).
+  ///
+  /// ## Platform-specific:
+  ///
+  /// - **Linux / Windows / macOS**: The insets are always zero.
+  #[must_use]
+  pub fn edge_to_edge(mut self, enabled: bool) -> Self {
+    self.webview_builder = self.webview_builder.edge_to_edge(enabled);
+    self
+  }
+
+  /// Sets what happens when the webview process crashes, reloading the page instead of leaving the webview blank.
+  ///
+  /// The [`WebviewEvent::ProcessCrashed`](crate::WebviewEvent::ProcessCrashed) event is emitted either way.
+  ///
+  /// ## Platform-specific:
+  ///
+  /// - **macOS / Android / iOS**: Unsupported.
+  #[must_use]
+  pub fn crash_recovery(mut self, recovery: super::CrashRecovery) -> Self {
+    self.webview_builder = self.webview_builder.crash_recovery(recovery);
+    self
+  }
 }
 
 /// A type that wraps a [`Window`] together with a [`Webview`].
@@ -972,6 +1055,11 @@ impl<R: Runtime> WebviewWindow<R> {
   pub fn on_window_event<F: Fn(&WindowEvent) + Send + 'static>(&self, f: F) {
     self.webview.window().on_window_event(f);
   }
+
+  /// Registers a listener called when the window moves to another monitor, see [`Window::on_monitor_changed`].
+  pub fn on_monitor_changed<F: Fn(Option<&Monitor>) + Send + Sync + 'static>(&self, f: F) {
+    self.webview.window().on_monitor_changed(f);
+  }
 }
 
 /// Menu APIs
@@ -1247,6 +1335,13 @@ impl<R: Runtime> WebviewWindow<R> {
     self.webview.window().default_vbox()
   }
 
+  /// Creates a native surface filling the window content beneath its webviews.
+  ///
+  /// See [`crate::Window::create_child_surface`] for more information.
+  pub fn create_child_surface(&self) -> crate::Result<crate::window::ChildSurface<R>> {
+    self.webview.window().create_child_surface()
+  }
+
   /// Returns the current window theme.
   ///
   /// ## Platform-specific
@@ -1255,6 +1350,144 @@ impl<R: Runtime> WebviewWindow<R> {
   pub fn theme(&self) -> crate::Result<crate::Theme> {
     self.webview.window().theme()
   }
+
+  /// Returns the virtual desktop or workspace of the window, see [`Window::workspace`](crate::Window::workspace).
+  #[cfg(desktop)]
+  pub fn workspace(&self) -> crate::Result<Option<crate::window::Workspace>> {
+    self.webview.window().workspace()
+  }
+
+  /// Whether the window is on the virtual desktop or workspace shown to the user.
+  #[cfg(desktop)]
+  pub fn is_on_current_workspace(&self) -> crate::Result<bool> {
+    self.webview.window().is_on_current_workspace()
+  }
+}
+
+/// Async window getters.
+impl<R: Runtime> WebviewWindow<R> {
+  /// Returns the value of [`Self::scale_factor`] without blocking the calling thread.
+  pub fn scale_factor_async(&self) -> impl Future<Output = crate::Result<f64>> + Send + 'static {
+    self.webview.window().scale_factor_async()
+  }
+
+  /// Returns the value of [`Self::inner_position`] without blocking the calling thread.
+  pub fn inner_position_async(
+    &self,
+  ) -> impl Future<Output = crate::Result<PhysicalPosition<i32>>> + Send + 'static {
+    self.webview.window().inner_position_async()
+  }
+
+  /// Returns the value of [`Self::outer_position`] without blocking the calling thread.
+  pub fn outer_position_async(
+    &self,
+  ) -> impl Future<Output = crate::Result<PhysicalPosition<i32>>> + Send + 'static {
+    self.webview.window().outer_position_async()
+  }
+
+  /// Returns the value of [`Self::inner_size`] without blocking the calling thread.
+  pub fn inner_size_async(
+    &self,
+  ) -> impl Future<Output = crate::Result<PhysicalSize<u32>>> + Send + 'static {
+    self.webview.window().inner_size_async()
+  }
+
+  /// Returns the value of [`Self::outer_size`] without blocking the calling thread.
+  pub fn outer_size_async(
+    &self,
+  ) -> impl Future<Output = crate::Result<PhysicalSize<u32>>> + Send + 'static {
+    self.webview.window().outer_size_async()
+  }
+
+  /// Returns the value of [`Self::is_fullscreen`] without blocking the calling thread.
+  pub fn is_fullscreen_async(&self) -> impl Future<Output = crate::Result<bool>> + Send + 'static {
+    self.webview.window().is_fullscreen_async()
+  }
+
+  /// Returns the value of [`Self::is_minimized`] without blocking the calling thread.
+  pub fn is_minimized_async(&self) -> impl Future<Output = crate::Result<bool>> + Send + 'static {
+    self.webview.window().is_minimized_async()
+  }
+
+  /// Returns the value of [`Self::is_maximized`] without blocking the calling thread.
+  pub fn is_maximized_async(&self) -> impl Future<Output = crate::Result<bool>> + Send + 'static {
+    self.webview.window().is_maximized_async()
+  }
+
+  /// Returns the value of [`Self::is_focused`] without blocking the calling thread.
+  pub fn is_focused_async(&self) -> impl Future<Output = crate::Result<bool>> + Send + 'static {
+    self.webview.window().is_focused_async()
+  }
+
+  /// Returns the value of [`Self::is_decorated`] without blocking the calling thread.
+  pub fn is_decorated_async(&self) -> impl Future<Output = crate::Result<bool>> + Send + 'static {
+    self.webview.window().is_decorated_async()
+  }
+
+  /// Returns the value of [`Self::is_resizable`] without blocking the calling thread.
+  pub fn is_resizable_async(&self) -> impl Future<Output = crate::Result<bool>> + Send + 'static {
+    self.webview.window().is_resizable_async()
+  }
+
+  /// Returns the value of [`Self::is_maximizable`] without blocking the calling thread.
+  pub fn is_maximizable_async(&self) -> impl Future<Output = crate::Result<bool>> + Send + 'static {
+    self.webview.window().is_maximizable_async()
+  }
+
+  /// Returns the value of [`Self::is_minimizable`] without blocking the calling thread.
+  pub fn is_minimizable_async(&self) -> impl Future<Output = crate::Result<bool>> + Send + 'static {
+    self.webview.window().is_minimizable_async()
+  }
+
+  /// Returns the value of [`Self::is_closable`] without blocking the calling thread.
+  pub fn is_closable_async(&self) -> impl Future<Output = crate::Result<bool>> + Send + 'static {
+    self.webview.window().is_closable_async()
+  }
+
+  /// Returns the value of [`Self::is_visible`] without blocking the calling thread.
+  pub fn is_visible_async(&self) -> impl Future<Output = crate::Result<bool>> + Send + 'static {
+    self.webview.window().is_visible_async()
+  }
+
+  /// Returns the value of [`Self::title`] without blocking the calling thread.
+  pub fn title_async(&self) -> impl Future<Output = crate::Result<String>> + Send + 'static {
+    self.webview.window().title_async()
+  }
+
+  /// Returns the value of [`Self::current_monitor`] without blocking the calling thread.
+  pub fn current_monitor_async(
+    &self,
+  ) -> impl Future<Output = crate::Result<Option<Monitor>>> + Send + 'static {
+    self.webview.window().current_monitor_async()
+  }
+
+  /// Returns the value of [`Self::monitor_from_point`] without blocking the calling thread.
+  pub fn monitor_from_point_async(
+    &self,
+    x: f64,
+    y: f64,
+  ) -> impl Future<Output = crate::Result<Option<Monitor>>> + Send + 'static {
+    self.webview.window().monitor_from_point_async(x, y)
+  }
+
+  /// Returns the value of [`Self::primary_monitor`] without blocking the calling thread.
+  pub fn primary_monitor_async(
+    &self,
+  ) -> impl Future<Output = crate::Result<Option<Monitor>>> + Send + 'static {
+    self.webview.window().primary_monitor_async()
+  }
+
+  /// Returns the value of [`Self::available_monitors`] without blocking the calling thread.
+  pub fn available_monitors_async(
+    &self,
+  ) -> impl Future<Output = crate::Result<Vec<Monitor>>> + Send + 'static {
+    self.webview.window().available_monitors_async()
+  }
+
+  /// Returns the value of [`Self::theme`] without blocking the calling thread.
+  pub fn theme_async(&self) -> impl Future<Output = crate::Result<crate::Theme>> + Send + 'static {
+    self.webview.window().theme_async()
+  }
 }
 
 /// Desktop window getters.
@@ -1371,6 +1604,53 @@ impl<R: Runtime> WebviewWindow<R> {
     self.webview.window().hide()
   }
 
+  /// Shows this window with the transition, returning once it started.
+  ///
+  /// See [`Window::show_animated`](crate::window::Window::show_animated).
+  pub fn show_animated(&self, transition: crate::window::Transition) -> crate::Result<()> {
+    self.webview.window().show_animated(transition)
+  }
+
+  /// Hides this window with the transition, returning once it started.
+  ///
+  /// See [`Window::hide_animated`](crate::window::Window::hide_animated).
+  pub fn hide_animated(&self, transition: crate::window::Transition) -> crate::Result<()> {
+    self.webview.window().hide_animated(transition)
+  }
+
+  /// Moves this window next to the tray icon, then shows and focuses it, hiding it when it loses the focus.
+  ///
+  /// See [`Window::show_at_tray`](crate::window::Window::show_at_tray).
+  ///
+  /// ```rust,no_run
+  /// use tauri::{
+  ///   tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent},
+  ///   window::TrayAlignment,
+  ///   Manager,
+  /// };
+  ///
+  /// tauri::Builder::default().setup(|app| {
+  ///   TrayIconBuilder::with_id("main")
+  ///     .on_tray_icon_event(|tray, event| {
+  ///       if let TrayIconEvent::Click { button: MouseButton::Left, button_state: MouseButtonState::Up, .. } = event {
+  ///         let popover = tray.app_handle().get_webview_window("popover").unwrap();
+  ///         let _ = popover.show_at_tray(tray, TrayAlignment::Center);
+  ///       }
+  ///     })
+  ///     .build(app)?;
+  ///   Ok(())
+  /// });
+  /// ```
+  #[cfg(feature = "tray-icon")]
+  #[cfg_attr(docsrs, doc(cfg(feature = "tray-icon")))]
+  pub fn show_at_tray(
+    &self,
+    tray: &crate::tray::TrayIcon<R>,
+    alignment: crate::window::TrayAlignment,
+  ) -> crate::Result<()> {
+    self.webview.window().show_at_tray(tray, alignment)
+  }
+
   /// Closes this window. It emits [`crate::RunEvent::CloseRequested`] first like a user-initiated close request so you can intercept it.
   pub fn close(&self) -> crate::Result<()> {
     self.webview.window().close()
@@ -1401,6 +1681,31 @@ impl<R: Runtime> WebviewWindow<R> {
     self.webview.window().set_shadow(enable)
   }
 
+  /// Updates how the corners of the window are rounded.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Windows:** Requires Windows 11.
+  /// - **macOS:** Rounds the content of the window, mostly useful without decorations.
+  /// - **Linux:** Unsupported.
+  pub fn set_corner_preference(
+    &self,
+    preference: crate::window::CornerPreference,
+  ) -> crate::Result<()> {
+    self.webview.window().set_corner_preference(preference)
+  }
+
+  /// Updates the color of the window border, [`None`] restoring the system color.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Windows:** Requires Windows 11.
+  /// - **macOS:** Draws a 1px border around the content of the window.
+  /// - **Linux:** Unsupported.
+  pub fn set_border_color(&self, color: Option<crate::window::Color>) -> crate::Result<()> {
+    self.webview.window().set_border_color(color)
+  }
+
   /// Sets window effects, pass [`None`] to clear any effects applied if possible.
   ///
   /// Requires the window to be transparent.
@@ -1457,6 +1762,12 @@ impl<R: Runtime> WebviewWindow<R> {
       .set_visible_on_all_workspaces(visible_on_all_workspaces)
   }
 
+  /// Moves the window to a virtual desktop or workspace, see [`Window::move_to_workspace`](crate::Window::move_to_workspace).
+  #[cfg(desktop)]
+  pub fn move_to_workspace(&self, workspace: crate::window::Workspace) -> crate::Result<()> {
+    self.webview.window().move_to_workspace(workspace)
+  }
+
   /// Prevents the window contents from being captured by other apps.
   pub fn set_content_protected(&self, protected: bool) -> crate::Result<()> {
     self.webview.window().set_content_protected(protected)
@@ -1492,6 +1803,21 @@ impl<R: Runtime> WebviewWindow<R> {
     self.webview.window().set_fullscreen(fullscreen)
   }
 
+  /// Makes the window fullscreen with the mode, on the monitor or on the current monitor if [`None`].
+  ///
+  /// Use [`Self::set_fullscreen`] to leave fullscreen.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Linux:** [`FullscreenMode::Exclusive`](crate::window::FullscreenMode::Exclusive) falls back to borderless.
+  pub fn set_fullscreen_mode(
+    &self,
+    mode: crate::window::FullscreenMode,
+    monitor: Option<&Monitor>,
+  ) -> crate::Result<()> {
+    self.webview.window().set_fullscreen_mode(mode, monitor)
+  }
+
   /// Bring the window to front and focus.
   pub fn set_focus(&self) -> crate::Result<()> {
     self.webview.window().set_focus()
@@ -1552,11 +1878,25 @@ impl<R: Runtime> WebviewWindow<R> {
     self.webview.window().set_ignore_cursor_events(ignore)
   }
 
+  /// Places the candidate window of the input method editor below the given area in window coordinates,
+  /// see [`Window::set_ime_position`](crate::window::Window::set_ime_position).
+  pub fn set_ime_position(&self, rect: crate::Rect) -> crate::Result<()> {
+    self.webview.window().set_ime_position(rect)
+  }
+
   /// Starts dragging the window.
   pub fn start_dragging(&self) -> crate::Result<()> {
     self.webview.window().start_dragging()
   }
 
+  /// Starts resize-dragging the window from the edge or corner in the given direction.
+  pub fn start_resize_dragging(
+    &self,
+    direction: crate::window::ResizeDirection,
+  ) -> crate::Result<()> {
+    self.webview.window().start_resize_dragging(direction)
+  }
+
   /// Sets the taskbar progress state.
   ///
   /// ## Platform-specific
@@ -1575,6 +1915,16 @@ impl<R: Runtime> WebviewWindow<R> {
   pub fn set_title_bar_style(&self, style: tauri_utils::TitleBarStyle) -> crate::Result<()> {
     self.webview.window().set_title_bar_style(style)
   }
+
+  /// Sets the theme for this window, [`None`] to follow the system theme.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Linux / macOS**: Theme is app-wide and not specific to this window.
+  /// - **iOS / Android:** Unsupported.
+  pub fn set_theme(&self, theme: Option<crate::Theme>) -> crate::Result<()> {
+    self.webview.window().set_theme(theme)
+  }
 }
 
 /// Desktop webview setters and actions.
@@ -1586,6 +1936,16 @@ impl<R: Runtime> WebviewWindow<R> {
   pub fn print(&self) -> crate::Result<()> {
     self.webview.print()
   }
+
+  /// Captures the composited content of the webview as a RGBA image.
+  ///
+  /// See [`Webview::capture_frame`] for more information.
+  pub fn capture_frame<F: FnOnce(crate::Result<crate::webview::WebviewFrame>) + Send + 'static>(
+    &self,
+    callback: F,
+  ) -> crate::Result<()> {
+    self.webview.capture_frame(callback)
+  }
 }
 
 /// Webview APIs.
@@ -1655,6 +2015,11 @@ impl<R: Runtime> WebviewWindow<R> {
     self.webview.url()
   }
 
+  /// Returns the value of [`Self::url`] without blocking the calling thread.
+  pub fn url_async(&self) -> impl Future<Output = crate::Result<Url>> + Send + 'static {
+    self.webview.url_async()
+  }
+
   /// Navigates the webview to the defined url.
   pub fn navigate(&mut self, url: Url) -> crate::Result<()> {
     self.webview.navigate(url)
@@ -1773,6 +2138,18 @@ impl<R: Runtime> WebviewWindow<R> {
   pub fn set_zoom(&self, scale_factor: f64) -> crate::Result<()> {
     self.webview.set_zoom(scale_factor)
   }
+
+  /// Resizes the webview to the area above the soft keyboard while it is shown, instead of letting the keyboard cover the content.
+  ///
+  /// The keyboard visibility and height are emitted to the webviews as the
+  /// [`keyboard://will-show`](crate::window::KEYBOARD_WILL_SHOW_EVENT) and [`keyboard://did-hide`](crate::window::KEYBOARD_DID_HIDE_EVENT) events.
+  ///
+  /// ## Platform-specific:
+  ///
+  /// - **Linux / Windows / macOS**: Unsupported.
+  pub fn set_resize_on_keyboard(&self, enabled: bool) -> crate::Result<()> {
+    self.webview.set_resize_on_keyboard(enabled)
+  }
 }
 
 impl<R: Runtime> Listener<R> for WebviewWindow<R> {
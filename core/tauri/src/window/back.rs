@@ -0,0 +1,137 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! The back navigation requests of the Android back button and back gesture.
+
+use std::sync::{Arc, Mutex};
+
+use serde::Deserialize;
+
+/// A request to navigate back, from the Android back button or back gesture.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct BackRequest {
+  /// Whether the webview can navigate back in its history, which is the default behavior of the request.
+  pub can_go_back: bool,
+}
+
+type BackHandler = dyn Fn(&BackRequest) -> bool + Send + Sync;
+
+pub(crate) type BackHandlers = Arc<Mutex<Vec<Arc<BackHandler>>>>;
+
+/// Calls the handlers, the last registered first, until one consumes the request.
+///
+/// Returns whether the request was consumed.
+#[cfg_attr(not(target_os = "android"), allow(dead_code))]
+pub(crate) fn dispatch(handlers: &BackHandlers, request: &BackRequest) -> bool {
+  let handlers = handlers.lock().unwrap().clone();
+  handlers.iter().rev().any(|handler| handler(request))
+}
+
+#[cfg(not(target_os = "android"))]
+pub(crate) fn set_enabled<R: crate::Runtime>(
+  _app: &crate::AppHandle<R>,
+  _enabled: bool,
+) -> crate::Result<()> {
+  Ok(())
+}
+
+#[cfg(target_os = "android")]
+pub(crate) use android::{init, set_enabled};
+
+#[cfg(target_os = "android")]
+mod android {
+  use super::BackRequest;
+  use crate::{
+    ipc::{Channel, InvokeBody},
+    window::safe_area::{RegisterListener, WindowPlugin},
+    AppHandle, Manager, Runtime,
+  };
+
+  pub(crate) fn init<R: Runtime>(app: &AppHandle<R>) -> crate::Result<()> {
+    let handle = app.state::<WindowPlugin<R>>().0.clone();
+
+    let app_ = app.clone();
+    let handle_ = handle.clone();
+    let channel = Channel::new(move |body| {
+      if let InvokeBody::Json(request) = body {
+        let request: BackRequest = serde_json::from_value(request)?;
+        let consumed = app_
+          .manager
+          .windows()
+          .into_values()
+          .any(|window| super::dispatch(&window.back_handlers, &request));
+        if !consumed {
+          // the plugin runs on the main thread, which is delivering this request
+          let handle = handle_.clone();
+          std::thread::spawn(move || {
+            if let Err(e) = handle.run_mobile_plugin::<()>("goBack", ()) {
+              log::error!("failed to navigate back: {e}");
+            }
+          });
+        }
+      }
+      Ok(())
+    });
+    handle.run_mobile_plugin::<()>(
+      "registerListener",
+      RegisterListener {
+        event: "backRequested",
+        handler: channel,
+      },
+    )?;
+
+    Ok(())
+  }
+
+  pub(crate) fn set_enabled<R: Runtime>(app: &AppHandle<R>, enabled: bool) -> crate::Result<()> {
+    app
+      .state::<WindowPlugin<R>>()
+      .0
+      .run_mobile_plugin::<()>(
+        "setBackHandlerEnabled",
+        serde_json::json!({ "enabled": enabled }),
+      )
+      .map_err(Into::into)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+  };
+
+  use super::{dispatch, BackRequest};
+
+  #[test]
+  fn consume_back_requests() {
+    let app = crate::test::mock_app();
+    let window = crate::window::WindowBuilder::new(&app, "main")
+      .build()
+      .unwrap();
+    let request = BackRequest { can_go_back: true };
+    assert!(!dispatch(&window.back_handlers, &request));
+
+    let calls = Arc::new(AtomicUsize::new(0));
+    let calls_ = calls.clone();
+    window
+      .on_back_requested(move |request| {
+        calls_.fetch_add(1, Ordering::Relaxed);
+        !request.can_go_back
+      })
+      .unwrap();
+    // the last registered handler is called first
+    window.on_back_requested(|_| false).unwrap();
+
+    assert!(!dispatch(&window.back_handlers, &request));
+    assert!(dispatch(
+      &window.back_handlers,
+      &BackRequest { can_go_back: false }
+    ));
+    assert_eq!(calls.load(Ordering::Relaxed), 2);
+  }
+}
@@ -0,0 +1,223 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! The color capabilities of the monitors, see [`Monitor::color_profile`] and [`Monitor::is_hdr`].
+
+use serde::Serialize;
+
+use super::Monitor;
+
+/// A range of colors, named after the values of the `color-gamut` CSS media feature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ColorGamut {
+  /// The sRGB gamut of most displays.
+  Srgb,
+  /// The Display P3 gamut of wide color displays, about 25% larger than sRGB.
+  DisplayP3,
+  /// The ITU-R BT.2020 gamut of HDR displays.
+  Rec2020,
+}
+
+/// The color profile of a monitor, see [`Monitor::color_profile`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ColorProfile {
+  name: Option<String>,
+  gamut: ColorGamut,
+}
+
+impl ColorProfile {
+  /// The localized name of the profile, e.g. `Display P3` or `sRGB IEC61966-2.1`.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Windows:** Always `None`.
+  pub fn name(&self) -> Option<&str> {
+    self.name.as_deref()
+  }
+
+  /// The largest gamut the monitor displays.
+  pub fn gamut(&self) -> ColorGamut {
+    self.gamut
+  }
+}
+
+/// The color profile and the HDR state of a monitor.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct MonitorColor {
+  pub(crate) profile: ColorProfile,
+  pub(crate) hdr: bool,
+}
+
+/// The gamut of the red primary of the monitor, its `x` chromaticity.
+#[cfg_attr(not(windows), allow(dead_code))]
+fn gamut_from_red_primary(x: f32) -> ColorGamut {
+  // sRGB is at 0.64, Display P3 at 0.68 and BT.2020 at 0.708
+  if x >= 0.7 {
+    ColorGamut::Rec2020
+  } else if x >= 0.67 {
+    ColorGamut::DisplayP3
+  } else {
+    ColorGamut::Srgb
+  }
+}
+
+#[cfg(windows)]
+mod platform {
+  use windows::{
+    core::Interface,
+    Win32::Graphics::Dxgi::{
+      Common::DXGI_COLOR_SPACE_RGB_FULL_G2084_NONE_P2020, CreateDXGIFactory1, IDXGIFactory1,
+      IDXGIOutput6,
+    },
+  };
+
+  use super::{gamut_from_red_primary, ColorProfile, Monitor, MonitorColor};
+
+  /// Finds the DXGI output at the position of the monitor.
+  pub fn query(monitor: &Monitor) -> Option<MonitorColor> {
+    let factory: IDXGIFactory1 = unsafe { CreateDXGIFactory1() }.ok()?;
+    let mut adapter_index = 0;
+    while let Ok(adapter) = unsafe { factory.EnumAdapters1(adapter_index) } {
+      let mut output_index = 0;
+      while let Ok(output) = unsafe { adapter.EnumOutputs(output_index) } {
+        if let Ok(desc) = output
+          .cast::<IDXGIOutput6>()
+          .and_then(|output| unsafe { output.GetDesc1() })
+        {
+          let coordinates = desc.DesktopCoordinates;
+          if coordinates.left == monitor.position.x && coordinates.top == monitor.position.y {
+            return Some(MonitorColor {
+              profile: ColorProfile {
+                name: None,
+                gamut: gamut_from_red_primary(desc.RedPrimary[0]),
+              },
+              hdr: desc.ColorSpace == DXGI_COLOR_SPACE_RGB_FULL_G2084_NONE_P2020,
+            });
+          }
+        }
+        output_index += 1;
+      }
+      adapter_index += 1;
+    }
+    None
+  }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+  use std::ffi::CStr;
+
+  use cocoa::{
+    base::{id, nil},
+    foundation::{NSArray, NSRect, NSString},
+  };
+  use objc::{class, msg_send, sel, sel_impl};
+
+  use super::{ColorGamut, ColorProfile, Monitor, MonitorColor};
+
+  #[link(name = "CoreGraphics", kind = "framework")]
+  extern "C" {
+    fn CGDisplayBounds(display: u32) -> NSRect;
+  }
+
+  /// `NSDisplayGamutP3`
+  const DISPLAY_GAMUT_P3: i64 = 2;
+
+  /// Finds the screen at the position of the monitor, comparing the bounds of their display
+  /// in the top-left origin coordinates of the monitors.
+  pub fn query(monitor: &Monitor) -> Option<MonitorColor> {
+    unsafe {
+      let screens: id = msg_send![class!(NSScreen), screens];
+      for i in 0..screens.count() {
+        let screen = screens.objectAtIndex(i);
+        let description: id = msg_send![screen, deviceDescription];
+        let key = NSString::alloc(nil).init_str("NSScreenNumber");
+        let number: id = msg_send![description, objectForKey: key];
+        let _: () = msg_send![key, release];
+        if number == nil {
+          continue;
+        }
+        let display: u32 = msg_send![number, unsignedIntValue];
+        let bounds = CGDisplayBounds(display);
+        let x = (bounds.origin.x * monitor.scale_factor).round() as i32;
+        let y = (bounds.origin.y * monitor.scale_factor).round() as i32;
+        if x != monitor.position.x || y != monitor.position.y {
+          continue;
+        }
+
+        let color_space: id = msg_send![screen, colorSpace];
+        let name: id = if color_space == nil {
+          nil
+        } else {
+          msg_send![color_space, localizedName]
+        };
+        let name = (name != nil).then(|| {
+          CStr::from_ptr(name.UTF8String())
+            .to_string_lossy()
+            .into_owned()
+        });
+        let p3: bool = msg_send![screen, canRepresentDisplayGamut: DISPLAY_GAMUT_P3];
+        let headroom: f64 = msg_send![
+          screen,
+          maximumPotentialExtendedDynamicRangeColorComponentValue
+        ];
+        return Some(MonitorColor {
+          profile: ColorProfile {
+            name,
+            gamut: if p3 {
+              ColorGamut::DisplayP3
+            } else {
+              ColorGamut::Srgb
+            },
+          },
+          hdr: headroom > 1.,
+        });
+      }
+    }
+    None
+  }
+}
+
+#[cfg(not(any(windows, target_os = "macos")))]
+mod platform {
+  use super::{Monitor, MonitorColor};
+
+  pub fn query(_monitor: &Monitor) -> Option<MonitorColor> {
+    None
+  }
+}
+
+/// The color profile and the HDR state of the monitor, `None` if unknown.
+pub(crate) fn query(monitor: &Monitor) -> Option<MonitorColor> {
+  platform::query(monitor)
+}
+
+/// The gamut the webviews composite their content in, the gamut of the `display-p3` CSS colors
+/// and images with a wide color profile are drawn in.
+///
+/// ## Platform-specific
+///
+/// - **macOS / iOS:** Display P3, WebKit renders in the color space of the display.
+/// - **Windows / Linux / Android:** sRGB, wide colors are clamped to sRGB.
+pub fn webview_color_gamut() -> ColorGamut {
+  if cfg!(any(target_os = "macos", target_os = "ios")) {
+    ColorGamut::DisplayP3
+  } else {
+    ColorGamut::Srgb
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{gamut_from_red_primary, ColorGamut};
+
+  #[test]
+  fn classifies_red_primaries() {
+    assert_eq!(gamut_from_red_primary(0.64), ColorGamut::Srgb);
+    assert_eq!(gamut_from_red_primary(0.68), ColorGamut::DisplayP3);
+    assert_eq!(gamut_from_red_primary(0.708), ColorGamut::Rec2020);
+  }
+}
@@ -0,0 +1,144 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! The visibility and height of the mobile soft keyboard.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{AppHandle, Emitter, Runtime};
+
+/// The event emitted to the webviews when the keyboard is about to show, with the [`KeyboardShown`] payload.
+pub const KEYBOARD_WILL_SHOW_EVENT: &str = "keyboard://will-show";
+/// The event emitted to the webviews once the keyboard is hidden.
+pub const KEYBOARD_DID_HIDE_EVENT: &str = "keyboard://did-hide";
+
+/// The payload of the [`KEYBOARD_WILL_SHOW_EVENT`] event.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[non_exhaustive]
+pub struct KeyboardShown {
+  /// The height of the keyboard in logical pixels.
+  pub height: f64,
+}
+
+/// The keyboard state reported by the native window plugin.
+#[derive(Debug, Deserialize)]
+#[cfg_attr(desktop, allow(dead_code))]
+struct KeyboardState {
+  visible: bool,
+  height: f64,
+}
+
+/// Emits the keyboard event matching the state to the webviews.
+#[cfg_attr(desktop, allow(dead_code))]
+fn emit<R: Runtime>(app: &AppHandle<R>, state: KeyboardState) -> crate::Result<()> {
+  if state.visible {
+    app.emit(
+      KEYBOARD_WILL_SHOW_EVENT,
+      KeyboardShown {
+        height: state.height,
+      },
+    )
+  } else {
+    app.emit(KEYBOARD_DID_HIDE_EVENT, ())
+  }
+}
+
+#[cfg(desktop)]
+pub(crate) fn set_resize_on_keyboard<R: Runtime>(
+  _app: &AppHandle<R>,
+  _enabled: bool,
+) -> crate::Result<()> {
+  Ok(())
+}
+
+#[cfg(mobile)]
+pub(crate) use mobile::{init, set_resize_on_keyboard};
+
+#[cfg(mobile)]
+mod mobile {
+  use super::KeyboardState;
+  use crate::{
+    ipc::{Channel, InvokeBody},
+    window::safe_area::{RegisterListener, WindowPlugin},
+    AppHandle, Manager, Runtime,
+  };
+
+  pub(crate) fn init<R: Runtime>(app: &AppHandle<R>) -> crate::Result<()> {
+    let app_ = app.clone();
+    let channel = Channel::new(move |body| {
+      if let InvokeBody::Json(state) = body {
+        let state: KeyboardState = serde_json::from_value(state)?;
+        super::emit(&app_, state)?;
+      }
+      Ok(())
+    });
+    app.state::<WindowPlugin<R>>().0.run_mobile_plugin::<()>(
+      "registerListener",
+      RegisterListener {
+        event: "keyboardChanged",
+        handler: channel,
+      },
+    )?;
+
+    Ok(())
+  }
+
+  pub(crate) fn set_resize_on_keyboard<R: Runtime>(
+    app: &AppHandle<R>,
+    enabled: bool,
+  ) -> crate::Result<()> {
+    app
+      .state::<WindowPlugin<R>>()
+      .0
+      .run_mobile_plugin::<()>(
+        "setResizeOnKeyboard",
+        serde_json::json!({ "enabled": enabled }),
+      )
+      .map_err(Into::into)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::sync::{Arc, Mutex};
+
+  use super::{emit, KeyboardState, KEYBOARD_DID_HIDE_EVENT, KEYBOARD_WILL_SHOW_EVENT};
+  use crate::{Listener, Manager};
+
+  #[test]
+  fn emit_keyboard_events() {
+    let app = crate::test::mock_app();
+    let received = Arc::new(Mutex::new(Vec::new()));
+    for event in [KEYBOARD_WILL_SHOW_EVENT, KEYBOARD_DID_HIDE_EVENT] {
+      let received_ = received.clone();
+      app.listen_any(event, move |e| {
+        received_
+          .lock()
+          .unwrap()
+          .push((event, e.payload().to_string()));
+      });
+    }
+
+    let handle = app.app_handle();
+    let state =
+      serde_json::from_str::<KeyboardState>(r#"{"visible":true,"height":291.5}"#).unwrap();
+    emit(handle, state).unwrap();
+    emit(
+      handle,
+      KeyboardState {
+        visible: false,
+        height: 0.,
+      },
+    )
+    .unwrap();
+
+    assert_eq!(
+      *received.lock().unwrap(),
+      vec![
+        (KEYBOARD_WILL_SHOW_EVENT, r#"{"height":291.5}"#.to_string()),
+        (KEYBOARD_DID_HIDE_EVENT, "null".to_string()),
+      ]
+    );
+  }
+}
@@ -4,17 +4,50 @@
 
 //! The Tauri window types and functions.
 
+mod back;
+mod color;
+pub(crate) mod keyboard;
+pub(crate) mod monitors;
 pub(crate) mod plugin;
+#[cfg(all(desktop, feature = "tray-icon"))]
+mod popover;
+pub(crate) mod safe_area;
+mod surface;
+#[cfg(desktop)]
+pub(crate) mod titlebar;
+#[cfg(desktop)]
+mod transition;
+#[cfg(desktop)]
+mod workspace;
+
+pub use back::BackRequest;
+pub use color::{webview_color_gamut, ColorGamut, ColorProfile};
+pub use keyboard::{KeyboardShown, KEYBOARD_DID_HIDE_EVENT, KEYBOARD_WILL_SHOW_EVENT};
+#[cfg(all(desktop, feature = "tray-icon"))]
+#[cfg_attr(docsrs, doc(cfg(all(desktop, feature = "tray-icon"))))]
+pub use popover::TrayAlignment;
+pub use safe_area::SafeAreaInsets;
+pub use surface::{ChildSurface, SurfaceSize};
+#[cfg(desktop)]
+pub use titlebar::{CaptionButtonPlacement, TitleBarOverlay};
+#[cfg(desktop)]
+pub use transition::Transition;
+#[cfg(desktop)]
+pub use workspace::Workspace;
 
 use tauri_runtime::{
   dpi::{PhysicalPosition, PhysicalSize},
   webview::PendingWebview,
   window::WindowSizeConstraints,
 };
-pub use tauri_utils::{config::Color, WindowEffect as Effect, WindowEffectState as EffectState};
+pub use tauri_utils::{
+  config::Color, CornerPreference, WindowEffect as Effect, WindowEffectState as EffectState,
+};
 
 #[cfg(desktop)]
 pub use crate::runtime::ProgressBarStatus;
+#[cfg(desktop)]
+pub use crate::runtime::{window::FullscreenMode, ResizeDirection};
 
 use crate::{
   app::AppHandle,
@@ -51,12 +84,13 @@ use tauri_macros::default_runtime;
 
 use std::{
   fmt,
+  future::Future,
   hash::{Hash, Hasher},
   sync::{Arc, Mutex, MutexGuard},
 };
 
 /// Monitor descriptor.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Monitor {
   pub(crate) name: Option<String>,
@@ -97,6 +131,30 @@ impl Monitor {
   pub fn scale_factor(&self) -> f64 {
     self.scale_factor
   }
+
+  /// Returns the color profile of the monitor, `None` if unknown.
+  ///
+  /// The changes, e.g. when the user picks another profile, are reported by
+  /// [`RunEvent::MonitorsChanged`](crate::RunEvent::MonitorsChanged) and [`Window::on_monitor_changed`].
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Linux / iOS / Android:** Unsupported, always returns `None`.
+  pub fn color_profile(&self) -> Option<ColorProfile> {
+    color::query(self).map(|color| color.profile)
+  }
+
+  /// Returns whether the monitor displays HDR content, with HDR turned on in the display settings on Windows
+  /// or an extended dynamic range display on macOS.
+  ///
+  /// The changes are reported like the changes of [`Self::color_profile`].
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Linux / iOS / Android:** Unsupported, always returns `false`.
+  pub fn is_hdr(&self) -> bool {
+    color::query(self).is_some_and(|color| color.hdr)
+  }
 }
 
 macro_rules! unstable_struct {
@@ -114,7 +172,7 @@ macro_rules! unstable_struct {
 unstable_struct!(
   #[doc = "A builder for a window managed by Tauri."]
   struct WindowBuilder<'a, R: Runtime, M: Manager<R>> {
-    manager: &'a M,
+    pub(crate) manager: &'a M,
     pub(crate) label: String,
     pub(crate) window_builder:
       <R::WindowDispatcher as WindowDispatch<EventLoopMessage>>::WindowBuilder,
@@ -378,6 +436,7 @@ tauri::Builder::default()
 
     let app_manager = self.manager.manager();
 
+    app_manager.check_label(&pending.label)?;
     let pending = app_manager.window.prepare_window(pending)?;
 
     #[cfg(desktop)]
@@ -688,6 +747,32 @@ impl<'a, R: Runtime, M: Manager<R>> WindowBuilder<'a, R, M> {
     self
   }
 
+  /// Sets how the corners of the window are rounded.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Windows:** Requires Windows 11.
+  /// - **macOS:** Rounds the content of the window, mostly useful without decorations.
+  /// - **Linux:** Unsupported.
+  #[must_use]
+  pub fn corner_preference(mut self, preference: CornerPreference) -> Self {
+    self.window_builder = self.window_builder.corner_preference(preference);
+    self
+  }
+
+  /// Sets the color of the window border.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Windows:** Requires Windows 11.
+  /// - **macOS:** Draws a 1px border around the content of the window.
+  /// - **Linux:** Unsupported.
+  #[must_use]
+  pub fn border_color(mut self, color: Color) -> Self {
+    self.window_builder = self.window_builder.border_color(color);
+    self
+  }
+
   /// Sets a parent to the window to be created.
   ///
   /// ## Platform-specific
@@ -880,6 +965,13 @@ pub(crate) struct WindowMenu<R: Runtime> {
 ///
 /// This type also implements [`Manager`] which allows you to manage other windows attached to
 /// the same application.
+///
+/// # Getters
+///
+/// The getters such as [`Self::outer_size`] wait for the event loop to answer, blocking the calling thread.
+/// They deadlock when the event loop waits for that thread, e.g. in a synchronous command or a `block_on` call,
+/// so prefer their `_async` variants such as [`Self::outer_size_async`] in async code.
+/// The blocking getters are kept for compatibility and will be deprecated in a future major release.
 #[default_runtime(crate::Wry, wry)]
 pub struct Window<R: Runtime> {
   /// The window created by the runtime.
@@ -891,6 +983,23 @@ pub struct Window<R: Runtime> {
   #[cfg(desktop)]
   pub(crate) menu: Arc<Mutex<Option<WindowMenu<R>>>>,
   pub(crate) resources_table: Arc<Mutex<ResourceTable>>,
+  /// The window event listeners, kept by Tauri to deliver the events that don't come from the runtime,
+  /// such as [`WindowEvent::SafeAreaInsetsChanged`].
+  event_listeners: WindowEventListeners,
+  /// The handlers of the back requests, see [`Window::on_back_requested`].
+  pub(crate) back_handlers: back::BackHandlers,
+}
+
+type WindowEventListener = Arc<Mutex<Box<dyn Fn(&WindowEvent) + Send>>>;
+type WindowEventListeners = Arc<Mutex<Vec<WindowEventListener>>>;
+
+/// Calls the window event listeners without holding the lock of the list,
+/// so the listeners can register other listeners or trigger other events.
+fn call_event_listeners(listeners: &WindowEventListeners, event: &WindowEvent) {
+  let listeners = listeners.lock().unwrap().clone();
+  for listener in listeners {
+    (*listener.lock().unwrap())(event);
+  }
 }
 
 impl<R: Runtime> std::fmt::Debug for Window<R> {
@@ -928,6 +1037,8 @@ impl<R: Runtime> Clone for Window<R> {
       #[cfg(desktop)]
       menu: self.menu.clone(),
       resources_table: self.resources_table.clone(),
+      event_listeners: self.event_listeners.clone(),
+      back_handlers: self.back_handlers.clone(),
     }
   }
 }
@@ -990,6 +1101,12 @@ impl<R: Runtime> Window<R> {
     app_handle: AppHandle<R>,
     #[cfg(desktop)] menu: Option<WindowMenu<R>>,
   ) -> Self {
+    let event_listeners = WindowEventListeners::default();
+    let event_listeners_ = event_listeners.clone();
+    window.dispatcher.on_window_event(move |event| {
+      call_event_listeners(&event_listeners_, &event.clone().into());
+    });
+
     Self {
       window,
       manager,
@@ -997,6 +1114,8 @@ impl<R: Runtime> Window<R> {
       #[cfg(desktop)]
       menu: Arc::new(std::sync::Mutex::new(menu)),
       resources_table: Default::default(),
+      event_listeners,
+      back_handlers: Default::default(),
     }
   }
 
@@ -1064,9 +1183,74 @@ impl<R: Runtime> Window<R> {
   /// Registers a window event listener.
   pub fn on_window_event<F: Fn(&WindowEvent) + Send + 'static>(&self, f: F) {
     self
+      .event_listeners
+      .lock()
+      .unwrap()
+      .push(Arc::new(Mutex::new(Box::new(f))));
+  }
+
+  /// Registers a listener called when the window moves to another monitor, e.g. when it is dragged to another screen
+  /// or when its monitor is disconnected, with `None` if the window is not on any monitor anymore.
+  /// It is also called when the [color profile](Monitor::color_profile) or the [HDR state](Monitor::is_hdr)
+  /// of its monitor changes, so media apps can adjust their rendering.
+  ///
+  /// The monitor of the window is checked every 2 seconds.
+  ///
+  /// # Examples
+  ///
+  /// ```rust,no_run
+  /// use tauri::Manager;
+  ///
+  /// tauri::Builder::default().setup(|app| {
+  ///   let window = app.get_webview_window("main").unwrap();
+  ///   let window_ = window.clone();
+  ///   window.on_monitor_changed(move |monitor| {
+  ///     // bring back the window left off-screen when a monitor is unplugged
+  ///     if monitor.is_none() {
+  ///       let _ = window_.center();
+  ///     }
+  ///   });
+  ///   Ok(())
+  /// });
+  /// ```
+  pub fn on_monitor_changed<F: Fn(Option<&Monitor>) + Send + Sync + 'static>(&self, f: F) {
+    self
+      .manager
       .window
-      .dispatcher
-      .on_window_event(move |event| f(&event.clone().into()));
+      .monitors
+      .lock()
+      .unwrap()
+      .add_listener(self.label(), Arc::new(f));
+  }
+
+  /// Delivers an event to the window event listeners.
+  pub(crate) fn dispatch_event(&self, event: WindowEvent) {
+    call_event_listeners(&self.event_listeners, &event);
+  }
+
+  /// Registers a handler of the back requests, returning `true` to consume the request
+  /// or `false` to keep the default behavior, i.e. navigating back in the webview history or leaving the app.
+  ///
+  /// The handler registered last is called first, and the next handlers are not called once the request is consumed.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Android:** The back button and back gesture, through the `OnBackPressedDispatcher`
+  ///   which supports the predictive back gesture. The system handles the gesture until a handler is registered.
+  /// - **Linux / Windows / macOS / iOS:** Unsupported, the handlers are never called.
+  pub fn on_back_requested<F: Fn(&BackRequest) -> bool + Send + Sync + 'static>(
+    &self,
+    handler: F,
+  ) -> crate::Result<()> {
+    let first = {
+      let mut handlers = self.back_handlers.lock().unwrap();
+      handlers.push(Arc::new(handler));
+      handlers.len() == 1
+    };
+    if first {
+      back::set_enabled(&self.app_handle, true)?;
+    }
+    Ok(())
   }
 }
 
@@ -1348,6 +1532,18 @@ impl<R: Runtime> Window<R> {
     self.window.dispatcher.scale_factor().map_err(Into::into)
   }
 
+  /// Returns the insets of the area covered by the system bars and display cutouts, in logical pixels.
+  ///
+  /// Listen to [`WindowEvent::SafeAreaInsetsChanged`] to be notified when they change,
+  /// e.g. when the device is rotated.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Linux / Windows / macOS:** Always zero.
+  pub fn safe_area_insets(&self) -> crate::Result<SafeAreaInsets> {
+    safe_area::get(&self.app_handle)
+  }
+
   /// Returns the position of the top-left hand corner of the window's client area relative to the top-left hand corner of the desktop.
   pub fn inner_position(&self) -> crate::Result<PhysicalPosition<i32>> {
     self.window.dispatcher.inner_position().map_err(Into::into)
@@ -1567,6 +1763,18 @@ impl<R: Runtime> Window<R> {
     self.window.dispatcher.default_vbox().map_err(Into::into)
   }
 
+  /// Creates a native surface filling the window content beneath its webviews,
+  /// for renderers such as wgpu or OpenGL to draw behind a transparent webview.
+  ///
+  /// See [`ChildSurface`] for more information.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Linux / iOS / Android:** Unsupported. On Linux the renderer can use the window handle instead.
+  pub fn create_child_surface(&self) -> crate::Result<ChildSurface<R>> {
+    ChildSurface::new(self)
+  }
+
   /// Returns the current window theme.
   ///
   /// ## Platform-specific
@@ -1575,6 +1783,165 @@ impl<R: Runtime> Window<R> {
   pub fn theme(&self) -> crate::Result<Theme> {
     self.window.dispatcher.theme().map_err(Into::into)
   }
+
+  /// Returns the virtual desktop or workspace of the window, to restore it with [`Self::move_to_workspace`].
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Linux**: Returns `None` on Wayland and when the window is visible on all workspaces.
+  /// - **macOS**: Always returns `None`, the Spaces have no public identifier.
+  #[cfg(desktop)]
+  pub fn workspace(&self) -> crate::Result<Option<Workspace>> {
+    workspace::workspace(self)
+  }
+
+  /// Whether the window is on the virtual desktop or workspace shown to the user.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Linux**: Always `true` on Wayland.
+  #[cfg(desktop)]
+  pub fn is_on_current_workspace(&self) -> crate::Result<bool> {
+    workspace::is_on_current_workspace(self)
+  }
+}
+
+/// Async window getters.
+impl<R: Runtime> Window<R> {
+  /// Runs the getter on the main thread, see [`WindowDispatch::get_async`](crate::runtime::WindowDispatch::get_async).
+  fn get_async<T: Send + 'static>(
+    &self,
+    getter: impl FnOnce(&R::WindowDispatcher) -> crate::runtime::Result<T> + Send + 'static,
+  ) -> impl Future<Output = crate::Result<T>> + Send + 'static {
+    let future = self.window.dispatcher.get_async(getter);
+    async move { future.await.map_err(Into::into) }
+  }
+
+  /// Returns the value of [`Self::scale_factor`] without blocking the calling thread.
+  pub fn scale_factor_async(&self) -> impl Future<Output = crate::Result<f64>> + Send + 'static {
+    self.get_async(|d| d.scale_factor())
+  }
+
+  /// Returns the value of [`Self::inner_position`] without blocking the calling thread.
+  pub fn inner_position_async(
+    &self,
+  ) -> impl Future<Output = crate::Result<PhysicalPosition<i32>>> + Send + 'static {
+    self.get_async(|d| d.inner_position())
+  }
+
+  /// Returns the value of [`Self::outer_position`] without blocking the calling thread.
+  pub fn outer_position_async(
+    &self,
+  ) -> impl Future<Output = crate::Result<PhysicalPosition<i32>>> + Send + 'static {
+    self.get_async(|d| d.outer_position())
+  }
+
+  /// Returns the value of [`Self::inner_size`] without blocking the calling thread.
+  pub fn inner_size_async(
+    &self,
+  ) -> impl Future<Output = crate::Result<PhysicalSize<u32>>> + Send + 'static {
+    self.get_async(|d| d.inner_size())
+  }
+
+  /// Returns the value of [`Self::outer_size`] without blocking the calling thread.
+  pub fn outer_size_async(
+    &self,
+  ) -> impl Future<Output = crate::Result<PhysicalSize<u32>>> + Send + 'static {
+    self.get_async(|d| d.outer_size())
+  }
+
+  /// Returns the value of [`Self::is_fullscreen`] without blocking the calling thread.
+  pub fn is_fullscreen_async(&self) -> impl Future<Output = crate::Result<bool>> + Send + 'static {
+    self.get_async(|d| d.is_fullscreen())
+  }
+
+  /// Returns the value of [`Self::is_minimized`] without blocking the calling thread.
+  pub fn is_minimized_async(&self) -> impl Future<Output = crate::Result<bool>> + Send + 'static {
+    self.get_async(|d| d.is_minimized())
+  }
+
+  /// Returns the value of [`Self::is_maximized`] without blocking the calling thread.
+  pub fn is_maximized_async(&self) -> impl Future<Output = crate::Result<bool>> + Send + 'static {
+    self.get_async(|d| d.is_maximized())
+  }
+
+  /// Returns the value of [`Self::is_focused`] without blocking the calling thread.
+  pub fn is_focused_async(&self) -> impl Future<Output = crate::Result<bool>> + Send + 'static {
+    self.get_async(|d| d.is_focused())
+  }
+
+  /// Returns the value of [`Self::is_decorated`] without blocking the calling thread.
+  pub fn is_decorated_async(&self) -> impl Future<Output = crate::Result<bool>> + Send + 'static {
+    self.get_async(|d| d.is_decorated())
+  }
+
+  /// Returns the value of [`Self::is_resizable`] without blocking the calling thread.
+  pub fn is_resizable_async(&self) -> impl Future<Output = crate::Result<bool>> + Send + 'static {
+    self.get_async(|d| d.is_resizable())
+  }
+
+  /// Returns the value of [`Self::is_maximizable`] without blocking the calling thread.
+  pub fn is_maximizable_async(&self) -> impl Future<Output = crate::Result<bool>> + Send + 'static {
+    self.get_async(|d| d.is_maximizable())
+  }
+
+  /// Returns the value of [`Self::is_minimizable`] without blocking the calling thread.
+  pub fn is_minimizable_async(&self) -> impl Future<Output = crate::Result<bool>> + Send + 'static {
+    self.get_async(|d| d.is_minimizable())
+  }
+
+  /// Returns the value of [`Self::is_closable`] without blocking the calling thread.
+  pub fn is_closable_async(&self) -> impl Future<Output = crate::Result<bool>> + Send + 'static {
+    self.get_async(|d| d.is_closable())
+  }
+
+  /// Returns the value of [`Self::is_visible`] without blocking the calling thread.
+  pub fn is_visible_async(&self) -> impl Future<Output = crate::Result<bool>> + Send + 'static {
+    self.get_async(|d| d.is_visible())
+  }
+
+  /// Returns the value of [`Self::title`] without blocking the calling thread.
+  pub fn title_async(&self) -> impl Future<Output = crate::Result<String>> + Send + 'static {
+    self.get_async(|d| d.title())
+  }
+
+  /// Returns the value of [`Self::current_monitor`] without blocking the calling thread.
+  pub fn current_monitor_async(
+    &self,
+  ) -> impl Future<Output = crate::Result<Option<Monitor>>> + Send + 'static {
+    self.get_async(|d| d.current_monitor().map(|m| m.map(Into::into)))
+  }
+
+  /// Returns the value of [`Self::monitor_from_point`] without blocking the calling thread.
+  pub fn monitor_from_point_async(
+    &self,
+    x: f64,
+    y: f64,
+  ) -> impl Future<Output = crate::Result<Option<Monitor>>> + Send + 'static {
+    self.get_async(move |d| d.monitor_from_point(x, y).map(|m| m.map(Into::into)))
+  }
+
+  /// Returns the value of [`Self::primary_monitor`] without blocking the calling thread.
+  pub fn primary_monitor_async(
+    &self,
+  ) -> impl Future<Output = crate::Result<Option<Monitor>>> + Send + 'static {
+    self.get_async(|d| d.primary_monitor().map(|m| m.map(Into::into)))
+  }
+
+  /// Returns the value of [`Self::available_monitors`] without blocking the calling thread.
+  pub fn available_monitors_async(
+    &self,
+  ) -> impl Future<Output = crate::Result<Vec<Monitor>>> + Send + 'static {
+    self.get_async(|d| {
+      d.available_monitors()
+        .map(|m| m.into_iter().map(Into::into).collect())
+    })
+  }
+
+  /// Returns the value of [`Self::theme`] without blocking the calling thread.
+  pub fn theme_async(&self) -> impl Future<Output = crate::Result<Theme>> + Send + 'static {
+    self.get_async(|d| d.theme())
+  }
 }
 
 /// Desktop window getters.
@@ -1715,6 +2082,58 @@ impl<R: Runtime> Window<R> {
     self.window.dispatcher.hide().map_err(Into::into)
   }
 
+  /// Shows this window with the transition, returning once it started.
+  ///
+  /// The window is transparent until the transition starts, so create it hidden
+  /// to show its content without flashing.
+  ///
+  /// ```rust,no_run
+  /// use tauri::{window::Transition, Manager, PhysicalPosition};
+  ///
+  /// tauri::Builder::default().setup(|app| {
+  ///   let popover = app.get_webview_window("popover").unwrap();
+  ///   // e.g. the position of the tray icon
+  ///   popover.show_animated(Transition::slide_from(PhysicalPosition::new(1800, 0)))?;
+  ///   Ok(())
+  /// });
+  /// ```
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Linux:** The fade requires a compositing window manager.
+  pub fn show_animated(&self, transition: Transition) -> crate::Result<()> {
+    transition::show(self, transition)
+  }
+
+  /// Hides this window with the transition, returning once it started.
+  ///
+  /// With [`Transition::Slide`], the window is moved back to its position once hidden.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Linux:** The fade requires a compositing window manager.
+  pub fn hide_animated(&self, transition: Transition) -> crate::Result<()> {
+    transition::hide(self, transition)
+  }
+
+  /// Moves this window next to the tray icon, on the side opposite to the taskbar or the menu bar, then shows and focuses it.
+  ///
+  /// The window is aligned with the icon along the taskbar and kept on the monitor of the icon.
+  /// It is hidden when it loses the focus, the usual behavior of the menu bar apps.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Linux:** The tray icon position is unknown, the window is shown at its position.
+  #[cfg(feature = "tray-icon")]
+  #[cfg_attr(docsrs, doc(cfg(feature = "tray-icon")))]
+  pub fn show_at_tray(
+    &self,
+    tray: &crate::tray::TrayIcon<R>,
+    alignment: TrayAlignment,
+  ) -> crate::Result<()> {
+    popover::show_at_tray(self, tray, alignment)
+  }
+
   /// Closes this window. It emits [`crate::RunEvent::CloseRequested`] first like a user-initiated close request so you can intercept it.
   pub fn close(&self) -> crate::Result<()> {
     self.window.dispatcher.close().map_err(Into::into)
@@ -1753,6 +2172,36 @@ impl<R: Runtime> Window<R> {
       .map_err(Into::into)
   }
 
+  /// Updates how the corners of the window are rounded.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Windows:** Requires Windows 11.
+  /// - **macOS:** Rounds the content of the window, mostly useful without decorations.
+  /// - **Linux:** Unsupported.
+  pub fn set_corner_preference(&self, preference: CornerPreference) -> crate::Result<()> {
+    self
+      .window
+      .dispatcher
+      .set_corner_preference(preference)
+      .map_err(Into::into)
+  }
+
+  /// Updates the color of the window border, [`None`] restoring the system color.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Windows:** Requires Windows 11.
+  /// - **macOS:** Draws a 1px border around the content of the window.
+  /// - **Linux:** Unsupported.
+  pub fn set_border_color(&self, color: Option<Color>) -> crate::Result<()> {
+    self
+      .window
+      .dispatcher
+      .set_border_color(color)
+      .map_err(Into::into)
+  }
+
   /// Sets window effects, pass [`None`] to clear any effects applied if possible.
   ///
   /// Requires the window to be transparent.
@@ -1827,6 +2276,17 @@ tauri::Builder::default()
       .map_err(Into::into)
   }
 
+  /// Moves the window to a virtual desktop or workspace returned by [`Self::workspace`].
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Linux**: Unsupported on Wayland.
+  /// - **macOS**: Unsupported, use [`Self::set_visible_on_all_workspaces`] to show the window on all the Spaces.
+  #[cfg(desktop)]
+  pub fn move_to_workspace(&self, workspace: Workspace) -> crate::Result<()> {
+    workspace::move_to_workspace(self, workspace)
+  }
+
   /// Prevents the window contents from being captured by other apps.
   pub fn set_content_protected(&self, protected: bool) -> crate::Result<()> {
     self
@@ -1890,6 +2350,33 @@ tauri::Builder::default()
       .map_err(Into::into)
   }
 
+  /// Makes the window fullscreen with the mode, on the monitor or on the current monitor if [`None`].
+  ///
+  /// Use [`Self::set_fullscreen`] to leave fullscreen.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Linux:** [`FullscreenMode::Exclusive`] falls back to [`FullscreenMode::Borderless`].
+  pub fn set_fullscreen_mode(
+    &self,
+    mode: FullscreenMode,
+    monitor: Option<&Monitor>,
+  ) -> crate::Result<()> {
+    self
+      .window
+      .dispatcher
+      .set_fullscreen_mode(
+        mode,
+        monitor.map(|monitor| RuntimeMonitor {
+          name: monitor.name.clone(),
+          size: monitor.size,
+          position: monitor.position,
+          scale_factor: monitor.scale_factor,
+        }),
+      )
+      .map_err(Into::into)
+  }
+
   /// Bring the window to front and focus.
   pub fn set_focus(&self) -> crate::Result<()> {
     self.window.dispatcher.set_focus().map_err(Into::into)
@@ -1978,16 +2465,45 @@ tauri::Builder::default()
       .map_err(Into::into)
   }
 
+  /// Places the candidate window of the input method editor below the given area in window coordinates,
+  /// usually the text cursor of an editor drawn by the app.
+  ///
+  /// The position is only used while the window itself receives the keyboard input, see [`WindowEvent::Ime`](crate::WindowEvent::Ime).
+  ///
+  /// # Examples
+  ///
+  /// ```rust,no_run
+  /// use tauri::{LogicalPosition, LogicalSize, Manager, Rect};
+  ///
+  /// tauri::Builder::default()
+  ///   .setup(|app| {
+  ///     let window = app.get_webview_window("main").unwrap();
+  ///     window.set_ime_position(Rect {
+  ///       position: LogicalPosition::new(120.0, 48.0).into(),
+  ///       size: LogicalSize::new(2.0, 18.0).into(),
+  ///     })?;
+  ///     Ok(())
+  ///   });
+  /// ```
+  pub fn set_ime_position(&self, rect: crate::Rect) -> crate::Result<()> {
+    self
+      .window
+      .dispatcher
+      .set_ime_position(rect)
+      .map_err(Into::into)
+  }
+
   /// Starts dragging the window.
   pub fn start_dragging(&self) -> crate::Result<()> {
     self.window.dispatcher.start_dragging().map_err(Into::into)
   }
 
-  /// Starts resize-dragging the window.
-  pub fn start_resize_dragging(
-    &self,
-    direction: tauri_runtime::ResizeDirection,
-  ) -> crate::Result<()> {
+  /// Starts resize-dragging the window from the edge or corner in the given direction.
+  ///
+  /// This is the behavior of the elements with a `data-tauri-resize-region` attribute,
+  /// e.g. `<div data-tauri-resize-region="bottom-right">`, in the windows without decorations.
+  /// On macOS these elements resize the window with [`Self::set_size`] and [`Self::set_position`] instead.
+  pub fn start_resize_dragging(&self, direction: ResizeDirection) -> crate::Result<()> {
     self
       .window
       .dispatcher
@@ -2021,6 +2537,16 @@ tauri::Builder::default()
       .set_title_bar_style(style)
       .map_err(Into::into)
   }
+
+  /// Sets the theme for this window, [`None`] to follow the system theme.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Linux / macOS**: Theme is app-wide and not specific to this window.
+  /// - **iOS / Android:** Unsupported.
+  pub fn set_theme(&self, theme: Option<Theme>) -> crate::Result<()> {
+    self.window.dispatcher.set_theme(theme).map_err(Into::into)
+  }
 }
 
 /// Progress bar state.
@@ -2273,4 +2799,43 @@ mod tests {
     crate::test_utils::assert_send::<super::Window>();
     crate::test_utils::assert_sync::<super::Window>();
   }
+
+  #[test]
+  fn fullscreen_changed_event() {
+    let app = crate::test::mock_app();
+    let window = crate::WebviewWindowBuilder::new(&app, "main", Default::default())
+      .build()
+      .unwrap();
+    let (tx, rx) = std::sync::mpsc::channel();
+    window.on_window_event(move |event| {
+      if let crate::WindowEvent::FullscreenChanged(fullscreen) = event {
+        tx.send(*fullscreen).unwrap();
+      }
+    });
+
+    window
+      .set_fullscreen_mode(super::FullscreenMode::Exclusive, None)
+      .unwrap();
+    assert!(window.is_fullscreen().unwrap());
+    window.set_fullscreen(false).unwrap();
+    assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec![true, false]);
+  }
+
+  #[test]
+  fn async_getters() {
+    let app = crate::test::mock_app();
+    let window = crate::WebviewWindowBuilder::new(&app, "main", Default::default())
+      .build()
+      .unwrap();
+    window.maximize().unwrap();
+
+    crate::async_runtime::block_on(async {
+      assert_eq!(
+        window.inner_size_async().await.unwrap(),
+        window.inner_size().unwrap()
+      );
+      assert!(window.is_maximized_async().await.unwrap());
+      assert_eq!(window.url_async().await.unwrap(), window.url().unwrap());
+    });
+  }
 }
@@ -0,0 +1,163 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Detection of the display configuration and color profile changes, reported by [`RunEvent::MonitorsChanged`](crate::RunEvent::MonitorsChanged)
+//! and [`Window::on_monitor_changed`](crate::Window::on_monitor_changed).
+
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+use crate::{AppHandle, EventLoopMessage, Manager, Runtime};
+
+use super::{color::MonitorColor, Monitor};
+
+/// The interval between the checks of the monitors and of the monitor of each window.
+pub(crate) const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+pub(crate) type MonitorChangedListener = Arc<dyn Fn(Option<&Monitor>) + Send + Sync>;
+
+/// The monitors, their colors and the monitor of each window at the last check.
+#[derive(Default)]
+pub(crate) struct MonitorsState {
+  /// `None` until the first check.
+  monitors: Option<(Vec<Monitor>, Vec<Option<MonitorColor>>)>,
+  window_monitors: HashMap<String, (Option<Monitor>, Option<MonitorColor>)>,
+  listeners: HashMap<String, Vec<MonitorChangedListener>>,
+}
+
+impl MonitorsState {
+  pub(crate) fn add_listener(&mut self, label: &str, listener: MonitorChangedListener) {
+    self
+      .listeners
+      .entry(label.into())
+      .or_default()
+      .push(listener);
+  }
+
+  pub(crate) fn remove_window(&mut self, label: &str) {
+    self.window_monitors.remove(label);
+    self.listeners.remove(label);
+  }
+}
+
+/// Compares the monitors and the monitor of each window with the last check,
+/// calling the listeners of the windows whose monitor or its colors changed and sending [`EventLoopMessage::MonitorsChanged`]
+/// if a monitor was added, removed, rearranged or changed its color profile or HDR state.
+pub(crate) fn check<R: Runtime>(app: &AppHandle<R>) {
+  let Ok(monitors) = app.available_monitors() else {
+    return;
+  };
+  let colors = monitors.iter().map(super::color::query).collect::<Vec<_>>();
+  let color_of = |monitor: &Option<Monitor>| {
+    monitor
+      .as_ref()
+      .and_then(|monitor| monitors.iter().position(|m| m == monitor))
+      .and_then(|i| colors[i].clone())
+  };
+  let windows = app
+    .manager
+    .window
+    .windows_lock()
+    .values()
+    .map(|window| {
+      let monitor = window.current_monitor().ok().flatten();
+      let color = color_of(&monitor);
+      (window.label().to_string(), monitor, color)
+    })
+    .collect::<Vec<_>>();
+
+  let mut state = app.manager.window.monitors.lock().unwrap();
+  let current = (monitors, colors);
+  let previous_monitors = state.monitors.replace(current.clone());
+  let mut affected = Vec::new();
+  let mut calls = Vec::new();
+  for (label, monitor, color) in windows {
+    let current = (monitor.clone(), color);
+    let previous = state.window_monitors.insert(label.clone(), current.clone());
+    // the windows seen for the first time are not reported
+    if matches!(previous, Some(previous) if previous != current) {
+      for listener in state.listeners.get(&label).into_iter().flatten() {
+        calls.push((listener.clone(), monitor.clone()));
+      }
+      affected.push(label);
+    }
+  }
+  drop(state);
+
+  for (listener, monitor) in calls {
+    listener(monitor.as_ref());
+  }
+  if matches!(previous_monitors, Some(ref previous) if *previous != current) {
+    let _ = app
+      .runtime_handle
+      .create_proxy()
+      .send_event(EventLoopMessage::MonitorsChanged {
+        monitors: current.0,
+        windows: affected,
+      });
+  }
+}
+
+/// Starts checking the monitors every [`POLL_INTERVAL`].
+pub(crate) fn watch<R: Runtime>(app: &AppHandle<R>) {
+  check(app);
+  let app_ = app.clone();
+  let timer = app
+    .clock()
+    .set_interval(POLL_INTERVAL, Box::new(move || check(&app_)));
+  *app.manager.window.monitors_timer.lock().unwrap() = Some(timer);
+}
+
+#[cfg(test)]
+mod tests {
+  use std::sync::{Arc, Mutex};
+
+  use crate::{
+    test::{mock_app, mock_clock},
+    PhysicalPosition, PhysicalSize, WebviewUrl, WebviewWindowBuilder,
+  };
+
+  #[test]
+  fn reports_window_monitor_changes() {
+    let app = mock_app();
+    let window = WebviewWindowBuilder::new(&app, "main", WebviewUrl::default())
+      .build()
+      .unwrap();
+    super::watch(app.handle());
+
+    let changes = Arc::new(Mutex::new(Vec::new()));
+    let changes_ = changes.clone();
+    window.on_monitor_changed(move |monitor| {
+      changes_
+        .lock()
+        .unwrap()
+        .push(monitor.and_then(|m| m.name().cloned()));
+    });
+
+    let mut external = crate::runtime::monitor::Monitor {
+      name: Some("external".into()),
+      size: PhysicalSize::new(2560, 1440),
+      position: PhysicalPosition::new(1920, 0),
+      scale_factor: 1.0,
+    };
+    crate::test::simulate_monitors(app.handle(), vec![external.clone()]);
+    window
+      .set_position(PhysicalPosition::new(2000, 100))
+      .unwrap();
+    mock_clock(&app).advance(super::POLL_INTERVAL);
+
+    // undocking moves the window back to the laptop screen
+    external.name = Some("laptop".into());
+    external.position = PhysicalPosition::new(0, 0);
+    crate::test::simulate_monitors(app.handle(), vec![external]);
+    window
+      .set_position(PhysicalPosition::new(100, 100))
+      .unwrap();
+    mock_clock(&app).advance(super::POLL_INTERVAL);
+
+    assert_eq!(
+      *changes.lock().unwrap(),
+      vec![Some("external".to_string()), Some("laptop".to_string())]
+    );
+  }
+}
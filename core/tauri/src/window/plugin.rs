@@ -12,14 +12,14 @@ use crate::{
 #[cfg(desktop)]
 mod desktop_commands {
   use tauri_runtime::{window::WindowSizeConstraints, ResizeDirection};
-  use tauri_utils::TitleBarStyle;
+  use tauri_utils::{CornerPreference, TitleBarStyle};
 
   use super::*;
   use crate::{
     command,
     sealed::ManagerBase,
     utils::config::{WindowConfig, WindowEffectsConfig},
-    window::{ProgressBarState, WindowBuilder},
+    window::{ProgressBarState, WindowBuilder, Workspace},
     AppHandle, CursorIcon, Manager, Monitor, PhysicalPosition, PhysicalSize, Position, Size, Theme,
     UserAttentionType, Webview, Window,
   };
@@ -93,6 +93,8 @@ mod desktop_commands {
   getter!(available_monitors, Vec<Monitor>);
   getter!(cursor_position, PhysicalPosition<f64>);
   getter!(theme, Theme);
+  getter!(workspace, Option<Workspace>);
+  getter!(is_on_current_workspace, bool);
 
   setter!(center);
   setter!(request_user_attention, Option<UserAttentionType>);
@@ -111,6 +113,8 @@ mod desktop_commands {
   setter!(destroy);
   setter!(set_decorations, bool);
   setter!(set_shadow, bool);
+  setter!(set_corner_preference, CornerPreference);
+  setter!(set_border_color, Option<crate::window::Color>);
   setter!(set_effects, Option<WindowEffectsConfig>);
   setter!(set_always_on_top, bool);
   setter!(set_always_on_bottom, bool);
@@ -131,6 +135,7 @@ mod desktop_commands {
   setter!(start_resize_dragging, ResizeDirection);
   setter!(set_progress_bar, ProgressBarState);
   setter!(set_visible_on_all_workspaces, bool);
+  setter!(move_to_workspace, Workspace);
   setter!(set_title_bar_style, TitleBarStyle);
   setter!(set_size_constraints, WindowSizeConstraints);
 
@@ -167,13 +172,26 @@ mod desktop_commands {
     label: Option<String>,
   ) -> crate::Result<()> {
     let window = get_window(window, label)?;
-    if window.is_resizable()? {
-      match window.is_maximized()? {
-        true => window.unmaximize()?,
-        false => window.maximize()?,
-      };
-    }
-    Ok(())
+    crate::window::titlebar::double_click(&window)
+  }
+
+  #[command(root = "crate")]
+  pub async fn set_fullscreen_mode<R: Runtime>(
+    window: Window<R>,
+    label: Option<String>,
+    mode: crate::window::FullscreenMode,
+    monitor: Option<String>,
+  ) -> crate::Result<()> {
+    let window = get_window(window, label)?;
+    // the monitors are identified by their name, falling back to the current monitor
+    let monitor = match monitor {
+      Some(name) => window
+        .available_monitors()?
+        .into_iter()
+        .find(|m| m.name() == Some(&name)),
+      None => None,
+    };
+    window.set_fullscreen_mode(mode, monitor.as_ref())
   }
 
   #[command(root = "crate")]
@@ -209,6 +227,21 @@ pub fn init<R: Runtime>() -> TauriPlugin<R> {
     .into_string(),
   );
 
+  #[derive(Template)]
+  #[default_template("./scripts/resize.js")]
+  struct Resize<'a> {
+    os_name: &'a str,
+  }
+
+  init_script.push_str(
+    &Resize {
+      os_name: std::env::consts::OS,
+    }
+    .render_default(&Default::default())
+    .unwrap()
+    .into_string(),
+  );
+
   Builder::new("window")
     .js_init_script(init_script)
     .invoke_handler(|invoke| {
@@ -240,6 +273,8 @@ pub fn init<R: Runtime>() -> TauriPlugin<R> {
             desktop_commands::available_monitors,
             desktop_commands::cursor_position,
             desktop_commands::theme,
+            desktop_commands::workspace,
+            desktop_commands::is_on_current_workspace,
             // setters
             desktop_commands::center,
             desktop_commands::request_user_attention,
@@ -258,6 +293,8 @@ pub fn init<R: Runtime>() -> TauriPlugin<R> {
             desktop_commands::destroy,
             desktop_commands::set_decorations,
             desktop_commands::set_shadow,
+            desktop_commands::set_corner_preference,
+            desktop_commands::set_border_color,
             desktop_commands::set_effects,
             desktop_commands::set_always_on_top,
             desktop_commands::set_always_on_bottom,
@@ -268,6 +305,7 @@ pub fn init<R: Runtime>() -> TauriPlugin<R> {
             desktop_commands::set_size_constraints,
             desktop_commands::set_position,
             desktop_commands::set_fullscreen,
+            desktop_commands::set_fullscreen_mode,
             desktop_commands::set_focus,
             desktop_commands::set_skip_taskbar,
             desktop_commands::set_cursor_grab,
@@ -280,6 +318,7 @@ pub fn init<R: Runtime>() -> TauriPlugin<R> {
             desktop_commands::set_progress_bar,
             desktop_commands::set_icon,
             desktop_commands::set_visible_on_all_workspaces,
+            desktop_commands::move_to_workspace,
             desktop_commands::set_title_bar_style,
             desktop_commands::toggle_maximize,
             desktop_commands::internal_toggle_maximize,
@@ -292,5 +331,14 @@ pub fn init<R: Runtime>() -> TauriPlugin<R> {
         true
       }
     })
+    .setup(|_app, _api| {
+      #[cfg(mobile)]
+      super::safe_area::init(_app, &_api)?;
+      #[cfg(mobile)]
+      super::keyboard::init(_app)?;
+      #[cfg(target_os = "android")]
+      super::back::init(_app)?;
+      Ok(())
+    })
     .build()
 }
@@ -0,0 +1,236 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! The windows shown next to a tray icon, see [`Window::show_at_tray`].
+
+use tauri_runtime::dpi::{PhysicalPosition, PhysicalSize};
+
+use crate::{sealed::ManagerBase, tray::TrayIcon, Runtime, Window, WindowEvent};
+
+/// How a window is aligned with the tray icon it is shown at, along the taskbar.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum TrayAlignment {
+  /// The window starts at the start of the icon, its left or top edge.
+  Start,
+  /// The window is centered on the icon.
+  #[default]
+  Center,
+  /// The window ends at the end of the icon, its right or bottom edge.
+  End,
+}
+
+/// A rectangle in physical pixels.
+#[derive(Debug, Clone, Copy)]
+struct Bounds {
+  position: PhysicalPosition<i32>,
+  size: PhysicalSize<u32>,
+}
+
+impl Bounds {
+  fn right(&self) -> i32 {
+    self.position.x + self.size.width as i32
+  }
+
+  fn bottom(&self) -> i32 {
+    self.position.y + self.size.height as i32
+  }
+}
+
+/// The position along an axis of a window of the length aligned with the icon.
+fn align(length: u32, icon_start: i32, icon_length: u32, alignment: TrayAlignment) -> i32 {
+  match alignment {
+    TrayAlignment::Start => icon_start,
+    TrayAlignment::Center => icon_start + icon_length as i32 / 2 - length as i32 / 2,
+    TrayAlignment::End => icon_start + icon_length as i32 - length as i32,
+  }
+}
+
+/// The position of the window next to the icon, on the side of the screen opposite to the taskbar,
+/// the edge of the monitor closest to the icon.
+fn popover_position(
+  icon: Bounds,
+  window: PhysicalSize<u32>,
+  monitor: Bounds,
+  alignment: TrayAlignment,
+) -> PhysicalPosition<i32> {
+  let center_x = icon.position.x + icon.size.width as i32 / 2;
+  let center_y = icon.position.y + icon.size.height as i32 / 2;
+  let distances = [
+    center_y - monitor.position.y,
+    monitor.bottom() - center_y,
+    center_x - monitor.position.x,
+    monitor.right() - center_x,
+  ];
+  let closest = distances
+    .iter()
+    .enumerate()
+    .min_by_key(|(_, distance)| **distance)
+    .map(|(edge, _)| edge)
+    .unwrap_or(1);
+
+  let (x, y) = match closest {
+    // horizontal taskbar at the top or the bottom
+    0 | 1 => (
+      align(window.width, icon.position.x, icon.size.width, alignment),
+      if closest == 0 {
+        icon.bottom()
+      } else {
+        icon.position.y - window.height as i32
+      },
+    ),
+    // vertical taskbar on the left or the right
+    _ => (
+      if closest == 2 {
+        icon.right()
+      } else {
+        icon.position.x - window.width as i32
+      },
+      align(window.height, icon.position.y, icon.size.height, alignment),
+    ),
+  };
+
+  // keeps the window on the monitor of the icon
+  PhysicalPosition::new(
+    x.min(monitor.right() - window.width as i32)
+      .max(monitor.position.x),
+    y.min(monitor.bottom() - window.height as i32)
+      .max(monitor.position.y),
+  )
+}
+
+/// Hides the window when it loses the focus, once per window.
+fn auto_hide<R: Runtime>(window: &Window<R>) {
+  let label = window.label().to_string();
+  if !window
+    .manager()
+    .window
+    .popovers
+    .lock()
+    .unwrap()
+    .insert(label.clone())
+  {
+    return;
+  }
+
+  let window_ = window.clone();
+  window.on_window_event(move |event| match event {
+    WindowEvent::Focused(false) => {
+      if let Err(e) = window_.hide() {
+        log::error!("failed to hide the popover: {e}");
+      }
+    }
+    WindowEvent::Destroyed => {
+      window_
+        .manager()
+        .window
+        .popovers
+        .lock()
+        .unwrap()
+        .remove(&label);
+    }
+    _ => {}
+  });
+}
+
+pub(crate) fn show_at_tray<R: Runtime>(
+  window: &Window<R>,
+  tray: &TrayIcon<R>,
+  alignment: TrayAlignment,
+) -> crate::Result<()> {
+  if let Some(rect) = tray.rect()? {
+    let scale_factor = window.scale_factor()?;
+    let icon = Bounds {
+      position: rect.position.to_physical(scale_factor),
+      size: rect.size.to_physical(scale_factor),
+    };
+    let center = (
+      icon.position.x as f64 + icon.size.width as f64 / 2.,
+      icon.position.y as f64 + icon.size.height as f64 / 2.,
+    );
+    let monitor = match window.monitor_from_point(center.0, center.1)? {
+      Some(monitor) => Some(monitor),
+      None => window.primary_monitor()?,
+    };
+    if let Some(monitor) = monitor {
+      let monitor = Bounds {
+        position: *monitor.position(),
+        size: *monitor.size(),
+      };
+      let position = popover_position(icon, window.outer_size()?, monitor, alignment);
+      window.set_position(position)?;
+    }
+  } else {
+    log::warn!("the tray icon position is unknown, showing the popover at its position");
+  }
+
+  auto_hide(window);
+  window.show()?;
+  window.set_focus()
+}
+
+#[cfg(test)]
+mod tests {
+  use tauri_runtime::dpi::{PhysicalPosition, PhysicalSize};
+
+  use super::{popover_position, Bounds, TrayAlignment};
+
+  fn bounds(x: i32, y: i32, width: u32, height: u32) -> Bounds {
+    Bounds {
+      position: PhysicalPosition::new(x, y),
+      size: PhysicalSize::new(width, height),
+    }
+  }
+
+  const MONITOR: Bounds = Bounds {
+    position: PhysicalPosition { x: 0, y: 0 },
+    size: PhysicalSize {
+      width: 1920,
+      height: 1080,
+    },
+  };
+  const WINDOW: PhysicalSize<u32> = PhysicalSize {
+    width: 300,
+    height: 400,
+  };
+
+  #[test]
+  fn above_bottom_taskbar() {
+    let icon = bounds(1500, 1050, 24, 24);
+    assert_eq!(
+      popover_position(icon, WINDOW, MONITOR, TrayAlignment::Center),
+      PhysicalPosition::new(1362, 650)
+    );
+    assert_eq!(
+      popover_position(icon, WINDOW, MONITOR, TrayAlignment::End),
+      PhysicalPosition::new(1224, 650)
+    );
+  }
+
+  #[test]
+  fn below_menu_bar() {
+    let icon = bounds(1500, 0, 24, 24);
+    assert_eq!(
+      popover_position(icon, WINDOW, MONITOR, TrayAlignment::Start),
+      PhysicalPosition::new(1500, 24)
+    );
+  }
+
+  #[test]
+  fn next_to_vertical_taskbar() {
+    let icon = bounds(1890, 600, 24, 24);
+    assert_eq!(
+      popover_position(icon, WINDOW, MONITOR, TrayAlignment::Center),
+      PhysicalPosition::new(1590, 412)
+    );
+  }
+
+  #[test]
+  fn stays_on_monitor() {
+    let icon = bounds(1880, 1050, 30, 24);
+    assert_eq!(
+      popover_position(icon, WINDOW, MONITOR, TrayAlignment::Center),
+      PhysicalPosition::new(1620, 650)
+    );
+  }
+}
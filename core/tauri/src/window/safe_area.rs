@@ -0,0 +1,154 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! The safe area of the windows, i.e. the area not covered by the system bars and display cutouts.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{AppHandle, Runtime};
+
+/// The insets of the safe area of a window in logical pixels,
+/// the areas covered by the system bars and display cutouts such as notches and gesture bars.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SafeAreaInsets {
+  /// The top inset.
+  pub top: f64,
+  /// The right inset.
+  pub right: f64,
+  /// The bottom inset.
+  pub bottom: f64,
+  /// The left inset.
+  pub left: f64,
+}
+
+/// The script updating the CSS variables of the insets, used by the [edge-to-edge](crate::webview::WebviewBuilder::edge_to_edge) webviews.
+pub(crate) fn css_variables_script(insets: &SafeAreaInsets) -> String {
+  format!(
+    "(function () {{ var style = document.documentElement.style; style.setProperty('--safe-area-inset-top', '{}px'); style.setProperty('--safe-area-inset-right', '{}px'); style.setProperty('--safe-area-inset-bottom', '{}px'); style.setProperty('--safe-area-inset-left', '{}px') }})()",
+    insets.top, insets.right, insets.bottom, insets.left
+  )
+}
+
+#[cfg(desktop)]
+pub(crate) fn get<R: Runtime>(_app: &AppHandle<R>) -> crate::Result<SafeAreaInsets> {
+  Ok(SafeAreaInsets::default())
+}
+
+#[cfg(desktop)]
+pub(crate) fn set_edge_to_edge<R: Runtime>(_app: &AppHandle<R>) -> crate::Result<()> {
+  Ok(())
+}
+
+#[cfg(mobile)]
+pub(crate) use mobile::{get, init, set_edge_to_edge, RegisterListener, WindowPlugin};
+
+#[cfg(mobile)]
+mod mobile {
+  use serde::Serialize;
+
+  use super::SafeAreaInsets;
+  use crate::{
+    ipc::{Channel, InvokeBody},
+    plugin::{PluginApi, PluginHandle},
+    AppHandle, Manager, Runtime,
+  };
+
+  #[cfg(target_os = "ios")]
+  crate::swift_rs::swift!(fn init_plugin_window() -> *const std::ffi::c_void);
+
+  /// The native window plugin, reporting the insets and the back requests.
+  pub(crate) struct WindowPlugin<R: Runtime>(pub(crate) PluginHandle<R>);
+
+  #[derive(Serialize)]
+  pub(crate) struct RegisterListener {
+    pub(crate) event: &'static str,
+    pub(crate) handler: Channel<serde_json::Value>,
+  }
+
+  pub(crate) fn init<R: Runtime, C: serde::de::DeserializeOwned>(
+    app: &AppHandle<R>,
+    api: &PluginApi<R, C>,
+  ) -> crate::Result<()> {
+    #[cfg(target_os = "android")]
+    let handle = api.register_android_plugin("app.tauri", "WindowPlugin")?;
+    #[cfg(target_os = "ios")]
+    let handle = api.register_ios_plugin(init_plugin_window)?;
+
+    let app_ = app.clone();
+    let channel = Channel::new(move |body| {
+      if let InvokeBody::Json(insets) = body {
+        let insets: SafeAreaInsets = serde_json::from_value(insets)?;
+        for window in app_.manager.windows().into_values() {
+          window.dispatch_event(crate::WindowEvent::SafeAreaInsetsChanged(insets));
+        }
+      }
+      Ok(())
+    });
+    handle.run_mobile_plugin::<()>(
+      "registerListener",
+      RegisterListener {
+        event: "insetsChanged",
+        handler: channel,
+      },
+    )?;
+
+    app.manage(WindowPlugin(handle));
+    Ok(())
+  }
+
+  pub(crate) fn get<R: Runtime>(app: &AppHandle<R>) -> crate::Result<SafeAreaInsets> {
+    app
+      .state::<WindowPlugin<R>>()
+      .0
+      .run_mobile_plugin("getSafeAreaInsets", ())
+      .map_err(Into::into)
+  }
+
+  pub(crate) fn set_edge_to_edge<R: Runtime>(app: &AppHandle<R>) -> crate::Result<()> {
+    app
+      .state::<WindowPlugin<R>>()
+      .0
+      .run_mobile_plugin::<()>("setEdgeToEdge", serde_json::json!({ "enabled": true }))
+      .map_err(Into::into)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::sync::{Arc, Mutex};
+
+  use super::SafeAreaInsets;
+  use crate::{Manager, WindowEvent};
+
+  #[test]
+  fn dispatch_insets() {
+    let app = crate::test::mock_app();
+    let window = crate::window::WindowBuilder::new(&app, "main")
+      .build()
+      .unwrap();
+    assert_eq!(
+      window.safe_area_insets().unwrap(),
+      SafeAreaInsets::default()
+    );
+
+    let received = Arc::new(Mutex::new(None));
+    let received_ = received.clone();
+    window.on_window_event(move |event| {
+      if let WindowEvent::SafeAreaInsetsChanged(insets) = event {
+        received_.lock().unwrap().replace(*insets);
+      }
+    });
+
+    let insets = SafeAreaInsets {
+      top: 47.,
+      bottom: 34.,
+      ..Default::default()
+    };
+    app
+      .get_window("main")
+      .unwrap()
+      .dispatch_event(WindowEvent::SafeAreaInsetsChanged(insets));
+    assert_eq!(*received.lock().unwrap(), Some(insets));
+  }
+}
@@ -0,0 +1,131 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+;(function () {
+  const osName = __TEMPLATE_os_name__
+
+  //-----------------------//
+  // resize the window on mousedown on the elements with a `data-tauri-resize-region` attribute,
+  // set to the edge or corner they resize, e.g. `top` or `bottom-right`
+  //-----------------------//
+  const TAURI_RESIZE_REGION_ATTR = 'data-tauri-resize-region'
+  const regions = {
+    top: ['North', 'ns-resize'],
+    bottom: ['South', 'ns-resize'],
+    left: ['West', 'ew-resize'],
+    right: ['East', 'ew-resize'],
+    'top-left': ['NorthWest', 'nwse-resize'],
+    'top-right': ['NorthEast', 'nesw-resize'],
+    'bottom-left': ['SouthWest', 'nesw-resize'],
+    'bottom-right': ['SouthEast', 'nwse-resize']
+  }
+
+  // resize cursors
+  const style = document.createElement('style')
+  style.textContent = Object.entries(regions)
+    .map(
+      ([region, [, cursor]]) =>
+        `[${TAURI_RESIZE_REGION_ATTR}="${region}"] { cursor: ${cursor}; }`
+    )
+    .join('\n')
+  if (document.head) {
+    document.head.appendChild(style)
+  } else {
+    document.addEventListener('DOMContentLoaded', () =>
+      document.head.appendChild(style)
+    )
+  }
+
+  const invoke = (cmd, args) =>
+    window.__TAURI_INTERNALS__.invoke('plugin:window|' + cmd, args)
+
+  // macOS has no native resize drag, so the window is resized with the mouse moves
+  let resize = null
+  let frame = null
+
+  document.addEventListener('mousedown', (e) => {
+    const region =
+      e.target.getAttribute && e.target.getAttribute(TAURI_RESIZE_REGION_ATTR)
+    if (!region || !(region in regions) || e.button !== 0 || e.detail !== 1) {
+      return
+    }
+
+    // prevents text cursor and the drag region handler
+    e.preventDefault()
+    e.stopImmediatePropagation()
+
+    const direction = regions[region][0]
+    if (osName !== 'macos') {
+      invoke('start_resize_dragging', { value: direction })
+      return
+    }
+
+    const startX = e.screenX
+    const startY = e.screenY
+    Promise.all([
+      invoke('outer_position'),
+      invoke('inner_size'),
+      invoke('scale_factor')
+    ]).then(([position, size, scaleFactor]) => {
+      resize = {
+        direction,
+        startX,
+        startY,
+        x: position.x / scaleFactor,
+        y: position.y / scaleFactor,
+        width: size.width / scaleFactor,
+        height: size.height / scaleFactor,
+        lastX: startX,
+        lastY: startY
+      }
+    })
+  })
+
+  if (osName === 'macos') {
+    document.addEventListener('mousemove', (e) => {
+      if (!resize) {
+        return
+      }
+      resize.lastX = e.screenX
+      resize.lastY = e.screenY
+      if (frame !== null) {
+        return
+      }
+      frame = requestAnimationFrame(() => {
+        frame = null
+        if (!resize) {
+          return
+        }
+        const dx = resize.lastX - resize.startX
+        const dy = resize.lastY - resize.startY
+        let { x, y, width, height } = resize
+        if (resize.direction.includes('East')) {
+          width += dx
+        }
+        if (resize.direction.includes('West')) {
+          width -= dx
+          x += dx
+        }
+        if (resize.direction.includes('South')) {
+          height += dy
+        }
+        if (resize.direction.includes('North')) {
+          height -= dy
+          y += dy
+        }
+        invoke('set_size', {
+          value: {
+            Logical: { width: Math.max(width, 1), height: Math.max(height, 1) }
+          }
+        })
+        if (x !== resize.x || y !== resize.y) {
+          invoke('set_position', { value: { Logical: { x, y } } })
+        }
+      })
+    })
+    document.addEventListener('mouseup', () => {
+      resize = null
+    })
+  }
+})()
@@ -0,0 +1,88 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+;(function () {
+  if (window !== window.top) {
+    return
+  }
+
+  const osName = __TEMPLATE_os_name__
+  const overlay = __TEMPLATE_overlay__
+
+  //-----------------------//
+  // the caption buttons over the webview and the `--tauri-titlebar-area-*` CSS variables
+  // for the area left to the title bar of the page
+  //-----------------------//
+  // the traffic lights on macOS, three buttons of the Windows size elsewhere
+  const CONTROLS_WIDTH = osName === 'macos' ? 78 : 3 * 46
+  const placement = osName === 'macos' ? 'left' : overlay.placement
+
+  const rgba = (color, fallback) =>
+    color
+      ? `rgba(${color[0]}, ${color[1]}, ${color[2]}, ${color[3] / 255})`
+      : fallback
+
+  function updateArea() {
+    const width = Math.max(window.innerWidth - CONTROLS_WIDTH, 0)
+    const style = document.documentElement.style
+    style.setProperty(
+      '--tauri-titlebar-area-x',
+      `${placement === 'left' ? CONTROLS_WIDTH : 0}px`
+    )
+    style.setProperty('--tauri-titlebar-area-y', '0px')
+    style.setProperty('--tauri-titlebar-area-width', `${width}px`)
+    style.setProperty('--tauri-titlebar-area-height', `${overlay.height}px`)
+  }
+
+  const invoke = (cmd) =>
+    window.__TAURI_INTERNALS__.invoke('plugin:window|' + cmd)
+
+  const buttons = [
+    ['minimize', 'M 0,5 H 10', () => invoke('minimize')],
+    ['maximize', 'M 0.5,0.5 H 9.5 V 9.5 H 0.5 Z', () => invoke('internal_toggle_maximize')],
+    ['close', 'M 0,0 L 10,10 M 10,0 L 0,10', () => invoke('close')]
+  ]
+
+  function drawButtons() {
+    const host = document.createElement('div')
+    host.setAttribute('data-tauri-titlebar-overlay', '')
+    host.style.cssText = `position: fixed; top: 0; ${placement}: 0; z-index: 2147483647;`
+    const root = host.attachShadow({ mode: 'closed' })
+
+    const symbol = rgba(overlay.symbolColor, 'currentColor')
+    const style = document.createElement('style')
+    style.textContent = `
+      div { display: flex; height: ${overlay.height}px; background: ${rgba(overlay.color, 'transparent')}; }
+      button { width: 46px; height: 100%; border: none; padding: 0; background: transparent; color: ${symbol}; }
+      button:hover { background: ${rgba(overlay.hoverColor, 'rgba(128, 128, 128, 0.2)')}; }
+      button.close:hover { background: #e81123; color: #fff; }
+      svg { width: 10px; height: 10px; }
+    `
+    const container = document.createElement('div')
+    for (const [name, path, onClick] of buttons) {
+      const button = document.createElement('button')
+      button.className = name
+      button.setAttribute('aria-label', name)
+      button.innerHTML = `<svg viewBox="0 0 10 10"><path d="${path}" stroke="currentColor" fill="none"/></svg>`
+      button.addEventListener('click', onClick)
+      container.appendChild(button)
+    }
+    root.append(style, container)
+    document.body.appendChild(host)
+  }
+
+  function init() {
+    updateArea()
+    if (osName !== 'macos') {
+      drawButtons()
+    }
+  }
+
+  window.addEventListener('resize', updateArea)
+  if (document.readyState === 'loading') {
+    document.addEventListener('DOMContentLoaded', init)
+  } else {
+    init()
+  }
+})()
@@ -0,0 +1,126 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Native surfaces for external renderers such as wgpu or OpenGL.
+
+use std::sync::{Arc, Mutex};
+
+use crate::{
+  runtime::{dpi::PhysicalSize, SurfaceId, WindowDispatch},
+  Runtime, Window, WindowEvent,
+};
+
+/// The size of a render surface, forwarded to the [`ChildSurface::on_resize`] handlers.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SurfaceSize {
+  /// The size in physical pixels, i.e. the size of the swapchain.
+  pub size: PhysicalSize<u32>,
+  /// The scale factor of the window.
+  pub scale_factor: f64,
+}
+
+/// A native surface filling the window content beneath its webviews, created with [`Window::create_child_surface`].
+///
+/// It implements [`raw_window_handle::HasWindowHandle`] and [`raw_window_handle::HasDisplayHandle`],
+/// so renderers such as wgpu can create their surface on it and draw behind a transparent webview
+/// without fighting over the window with the webview.
+///
+/// The surface follows the window size and is destroyed when dropped.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// tauri::Builder::default().setup(|app| {
+///   use tauri::Manager;
+///   let window = app.get_webview_window("main").unwrap();
+///   let surface = window.create_child_surface()?;
+///   surface.on_resize(|size| {
+///     // reconfigure the swapchain with `size.size`
+///     println!("{:?}", size);
+///   });
+///   // create the wgpu surface with `instance.create_surface(surface)`
+///   Ok(())
+/// });
+/// ```
+pub struct ChildSurface<R: Runtime> {
+  id: SurfaceId,
+  window: Window<R>,
+}
+
+impl<R: Runtime> std::fmt::Debug for ChildSurface<R> {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("ChildSurface")
+      .field("id", &self.id)
+      .field("window", &self.window.label())
+      .finish()
+  }
+}
+
+impl<R: Runtime> ChildSurface<R> {
+  pub(crate) fn new(window: &Window<R>) -> crate::Result<Self> {
+    let id = window.window.dispatcher.create_child_surface()?;
+    Ok(Self {
+      id,
+      window: window.clone(),
+    })
+  }
+
+  /// The window of the surface.
+  pub fn window(&self) -> &Window<R> {
+    &self.window
+  }
+
+  /// The current size of the surface.
+  pub fn size(&self) -> crate::Result<SurfaceSize> {
+    Ok(SurfaceSize {
+      size: self.window.inner_size()?,
+      scale_factor: self.window.scale_factor()?,
+    })
+  }
+
+  /// Registers a handler called when the surface is resized or the window moves to a display with another scale factor.
+  pub fn on_resize<F: Fn(SurfaceSize) + Send + 'static>(&self, f: F) {
+    let scale_factor = Arc::new(Mutex::new(self.window.scale_factor().unwrap_or(1.0)));
+    self.window.on_window_event(move |event| match event {
+      WindowEvent::Resized(size) => f(SurfaceSize {
+        size: *size,
+        scale_factor: *scale_factor.lock().unwrap(),
+      }),
+      WindowEvent::ScaleFactorChanged {
+        scale_factor: new_scale_factor,
+        new_inner_size,
+        ..
+      } => {
+        *scale_factor.lock().unwrap() = *new_scale_factor;
+        f(SurfaceSize {
+          size: *new_inner_size,
+          scale_factor: *new_scale_factor,
+        })
+      }
+      _ => {}
+    });
+  }
+}
+
+impl<R: Runtime> raw_window_handle::HasWindowHandle for ChildSurface<R> {
+  fn window_handle(
+    &self,
+  ) -> std::result::Result<raw_window_handle::WindowHandle<'_>, raw_window_handle::HandleError> {
+    self.window.window.dispatcher.child_surface_handle(self.id)
+  }
+}
+
+impl<R: Runtime> raw_window_handle::HasDisplayHandle for ChildSurface<R> {
+  fn display_handle(
+    &self,
+  ) -> std::result::Result<raw_window_handle::DisplayHandle<'_>, raw_window_handle::HandleError> {
+    raw_window_handle::HasDisplayHandle::display_handle(&self.window)
+  }
+}
+
+impl<R: Runtime> Drop for ChildSurface<R> {
+  fn drop(&mut self) {
+    let _ = self.window.window.dispatcher.destroy_child_surface(self.id);
+  }
+}
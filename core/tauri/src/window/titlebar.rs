@@ -0,0 +1,239 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! The title bar overlay of the windows drawing their own title bar, see [`TitleBarOverlay`].
+
+use serde::Serialize;
+use serialize_to_javascript::{default_template, DefaultTemplate, Template};
+
+use super::Color;
+use crate::{Runtime, Window};
+
+/// The side of the window of the caption buttons.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CaptionButtonPlacement {
+  /// The buttons are on the left of the title bar, the default on macOS.
+  Left,
+  /// The buttons are on the right of the title bar, the default on Windows and Linux.
+  Right,
+}
+
+impl Default for CaptionButtonPlacement {
+  fn default() -> Self {
+    if cfg!(target_os = "macos") {
+      Self::Left
+    } else {
+      Self::Right
+    }
+  }
+}
+
+/// An overlay of the caption buttons over the webview, replacing the title bar of the window,
+/// set with [`WebviewWindowBuilder::title_bar_overlay`](crate::WebviewWindowBuilder::title_bar_overlay).
+///
+/// The page draws its title bar in the area left by the buttons, available as the
+/// `--tauri-titlebar-area-x`, `--tauri-titlebar-area-y`, `--tauri-titlebar-area-width` and
+/// `--tauri-titlebar-area-height` CSS variables of the document element, updated on resize:
+///
+/// ```css
+/// header {
+///   position: fixed;
+///   left: var(--tauri-titlebar-area-x);
+///   width: var(--tauri-titlebar-area-width);
+///   height: var(--tauri-titlebar-area-height);
+/// }
+/// ```
+///
+/// Marking the title bar with the `data-tauri-drag-region` attribute moves the window on drag
+/// and follows the platform convention on double click.
+///
+/// ## Platform-specific
+///
+/// - **macOS:** Uses the native traffic lights with [`TitleBarStyle::Overlay`](crate::TitleBarStyle::Overlay),
+///   their placement and colors can't be changed.
+/// - **Windows / Linux:** The window has no decorations and the buttons are drawn in the webview,
+///   requiring the `core:window:allow-minimize` and `core:window:allow-close` permissions.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TitleBarOverlay {
+  placement: CaptionButtonPlacement,
+  height: f64,
+  color: Option<Color>,
+  symbol_color: Option<Color>,
+  hover_color: Option<Color>,
+}
+
+impl Default for TitleBarOverlay {
+  fn default() -> Self {
+    Self {
+      placement: CaptionButtonPlacement::default(),
+      height: if cfg!(target_os = "macos") { 28. } else { 32. },
+      color: None,
+      symbol_color: None,
+      hover_color: None,
+    }
+  }
+}
+
+impl TitleBarOverlay {
+  /// An overlay with the platform placement and colors.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Sets the side of the caption buttons.
+  #[must_use]
+  pub fn placement(mut self, placement: CaptionButtonPlacement) -> Self {
+    self.placement = placement;
+    self
+  }
+
+  /// Sets the height of the title bar in logical pixels.
+  #[must_use]
+  pub fn height(mut self, height: f64) -> Self {
+    self.height = height;
+    self
+  }
+
+  /// Sets the background color of the caption buttons, transparent by default.
+  #[must_use]
+  pub fn color(mut self, color: Color) -> Self {
+    self.color.replace(color);
+    self
+  }
+
+  /// Sets the color of the symbols of the caption buttons.
+  #[must_use]
+  pub fn symbol_color(mut self, color: Color) -> Self {
+    self.symbol_color.replace(color);
+    self
+  }
+
+  /// Sets the background color of the hovered caption buttons, except the close button.
+  #[must_use]
+  pub fn hover_color(mut self, color: Color) -> Self {
+    self.hover_color.replace(color);
+    self
+  }
+
+  /// The initialization script drawing the overlay.
+  pub(crate) fn script(&self) -> String {
+    #[derive(Template)]
+    #[default_template("./scripts/titlebar-overlay.js")]
+    struct TitleBarOverlayJavascript<'a> {
+      os_name: &'a str,
+      overlay: &'a TitleBarOverlay,
+    }
+
+    TitleBarOverlayJavascript {
+      os_name: std::env::consts::OS,
+      overlay: self,
+    }
+    .render_default(&Default::default())
+    .unwrap()
+    .into_string()
+  }
+}
+
+/// What a double click on the title bar does.
+#[derive(Debug, PartialEq, Eq)]
+enum DoubleClickAction {
+  ToggleMaximize,
+  Minimize,
+  None,
+}
+
+impl DoubleClickAction {
+  /// The action of the `AppleActionOnDoubleClick` user default on macOS.
+  #[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+  fn from_preference(preference: Option<&str>) -> Self {
+    match preference {
+      Some("Minimize") => Self::Minimize,
+      Some("None") => Self::None,
+      _ => Self::ToggleMaximize,
+    }
+  }
+}
+
+#[cfg(target_os = "macos")]
+fn double_click_action() -> DoubleClickAction {
+  use std::ffi::CStr;
+
+  use cocoa::{
+    base::{id, nil},
+    foundation::NSString,
+  };
+  use objc::{class, msg_send, sel, sel_impl};
+
+  let preference = unsafe {
+    let defaults: id = msg_send![class!(NSUserDefaults), standardUserDefaults];
+    let key = NSString::alloc(nil).init_str("AppleActionOnDoubleClick");
+    let value: id = msg_send![defaults, stringForKey: key];
+    let _: () = msg_send![key, release];
+    (value != nil).then(|| {
+      CStr::from_ptr(value.UTF8String())
+        .to_string_lossy()
+        .into_owned()
+    })
+  };
+  DoubleClickAction::from_preference(preference.as_deref())
+}
+
+#[cfg(not(target_os = "macos"))]
+fn double_click_action() -> DoubleClickAction {
+  DoubleClickAction::ToggleMaximize
+}
+
+/// Handles a double click on the title bar drawn by the page, following the platform convention.
+pub(crate) fn double_click<R: Runtime>(window: &Window<R>) -> crate::Result<()> {
+  match double_click_action() {
+    DoubleClickAction::ToggleMaximize => {
+      if window.is_resizable()? {
+        match window.is_maximized()? {
+          true => window.unmaximize()?,
+          false => window.maximize()?,
+        };
+      }
+    }
+    DoubleClickAction::Minimize => {
+      if window.is_minimizable()? {
+        window.minimize()?;
+      }
+    }
+    DoubleClickAction::None => {}
+  }
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{DoubleClickAction, TitleBarOverlay};
+
+  #[test]
+  fn double_click_preference() {
+    assert_eq!(
+      DoubleClickAction::from_preference(None),
+      DoubleClickAction::ToggleMaximize
+    );
+    assert_eq!(
+      DoubleClickAction::from_preference(Some("Fill")),
+      DoubleClickAction::ToggleMaximize
+    );
+    assert_eq!(
+      DoubleClickAction::from_preference(Some("Minimize")),
+      DoubleClickAction::Minimize
+    );
+    assert_eq!(
+      DoubleClickAction::from_preference(Some("None")),
+      DoubleClickAction::None
+    );
+  }
+
+  #[test]
+  fn renders_overlay_script() {
+    let script = TitleBarOverlay::new().height(40.).script();
+    assert!(script.contains("\"height\":40.0"));
+  }
+}
@@ -0,0 +1,245 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! The transitions of the windows shown and hidden, see [`Window::show_animated`].
+
+use std::{
+  sync::Arc,
+  time::{Duration, Instant},
+};
+
+use tauri_runtime::dpi::{PhysicalPosition, Position};
+
+use crate::{Runtime, Window};
+
+/// The delay between two frames of a transition.
+const FRAME_INTERVAL: Duration = Duration::from_millis(16);
+
+/// How a window is shown or hidden, see [`Window::show_animated`] and [`Window::hide_animated`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub enum Transition {
+  /// Shows or hides the window at once.
+  None,
+  /// Fades the window in or out.
+  Fade {
+    /// The duration of the transition.
+    duration: Duration,
+  },
+  /// Slides the window from or to the position while fading it,
+  /// e.g. from the position of the tray icon returned by [`TrayIcon::rect`](crate::tray::TrayIcon::rect).
+  Slide {
+    /// The position of the top-left corner of the window when hidden.
+    from: Position,
+    /// The duration of the transition.
+    duration: Duration,
+  },
+}
+
+impl Transition {
+  /// Fades the window in or out in 150 milliseconds.
+  pub fn fade() -> Self {
+    Self::Fade {
+      duration: Duration::from_millis(150),
+    }
+  }
+
+  /// Slides the window from or to the position in 200 milliseconds.
+  pub fn slide_from(from: impl Into<Position>) -> Self {
+    Self::Slide {
+      from: from.into(),
+      duration: Duration::from_millis(200),
+    }
+  }
+}
+
+/// Eases out the progress of the transition, from 0 to 1.
+fn ease_out(progress: f64) -> f64 {
+  1. - (1. - progress).powi(3)
+}
+
+fn interpolate(
+  from: PhysicalPosition<i32>,
+  to: PhysicalPosition<i32>,
+  progress: f64,
+) -> PhysicalPosition<i32> {
+  PhysicalPosition::new(
+    from.x + ((to.x - from.x) as f64 * progress).round() as i32,
+    from.y + ((to.y - from.y) as f64 * progress).round() as i32,
+  )
+}
+
+#[cfg(windows)]
+mod platform {
+  use windows::Win32::{
+    Foundation::COLORREF,
+    UI::WindowsAndMessaging::{
+      GetWindowLongPtrW, SetLayeredWindowAttributes, SetWindowLongPtrW, GWL_EXSTYLE, LWA_ALPHA,
+      WS_EX_LAYERED,
+    },
+  };
+
+  use crate::{Runtime, Window};
+
+  pub fn set_opacity<R: Runtime>(window: &Window<R>, opacity: f64) -> crate::Result<()> {
+    let hwnd = window.hwnd()?;
+    unsafe {
+      // only the layered windows can be translucent
+      let style = GetWindowLongPtrW(hwnd, GWL_EXSTYLE);
+      if style & WS_EX_LAYERED.0 as isize == 0 {
+        SetWindowLongPtrW(hwnd, GWL_EXSTYLE, style | WS_EX_LAYERED.0 as isize);
+      }
+      SetLayeredWindowAttributes(hwnd, COLORREF(0), (opacity * 255.).round() as u8, LWA_ALPHA)
+    }
+    .map_err(|e| crate::Error::Transition(e.to_string()))
+  }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+  use cocoa::base::id;
+  use objc::{msg_send, sel, sel_impl};
+
+  use crate::{Runtime, Window};
+
+  pub fn set_opacity<R: Runtime>(window: &Window<R>, opacity: f64) -> crate::Result<()> {
+    let ns_window = window.ns_window()? as id;
+    let _: () = unsafe { msg_send![ns_window, setAlphaValue: opacity] };
+    Ok(())
+  }
+}
+
+#[cfg(any(
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd"
+))]
+mod platform {
+  use gtk::prelude::*;
+
+  use crate::{Runtime, Window};
+
+  pub fn set_opacity<R: Runtime>(window: &Window<R>, opacity: f64) -> crate::Result<()> {
+    // requires a compositing window manager
+    window.gtk_window()?.set_opacity(opacity);
+    Ok(())
+  }
+}
+
+type Step<R> = dyn Fn(&Window<R>, f64) -> crate::Result<()> + Send + Sync;
+
+/// Runs the steps of the transition on the main thread, with the eased progress, then the last step.
+fn animate<R: Runtime>(
+  window: &Window<R>,
+  duration: Duration,
+  step: Arc<Step<R>>,
+  done: impl FnOnce(&Window<R>) -> crate::Result<()> + Send + 'static,
+) {
+  let window = window.clone();
+  std::thread::spawn(move || {
+    let start = Instant::now();
+    loop {
+      let progress = (start.elapsed().as_secs_f64() / duration.as_secs_f64()).min(1.);
+      let step = step.clone();
+      let window_ = window.clone();
+      let scheduled = window.run_on_main_thread(move || {
+        if let Err(e) = step(&window_, ease_out(progress)) {
+          log::debug!("failed to animate the window: {e}");
+        }
+      });
+      if scheduled.is_err() || progress >= 1. {
+        break;
+      }
+      std::thread::sleep(FRAME_INTERVAL);
+    }
+    let window_ = window.clone();
+    let _ = window.run_on_main_thread(move || {
+      if let Err(e) = done(&window_) {
+        log::error!("failed to finish the window transition: {e}");
+      }
+    });
+  });
+}
+
+pub(crate) fn show<R: Runtime>(window: &Window<R>, transition: Transition) -> crate::Result<()> {
+  let (duration, from) = match transition {
+    Transition::None => return window.show(),
+    Transition::Fade { duration } => (duration, None),
+    Transition::Slide { from, duration } => (duration, Some(from)),
+  };
+
+  let to = window.outer_position()?;
+  let from = from.map(|from| from.to_physical::<i32>(window.scale_factor().unwrap_or(1.)));
+  // the window is transparent before being shown, so it doesn't flash
+  platform::set_opacity(window, 0.)?;
+  if let Some(from) = from {
+    window.set_position(from)?;
+  }
+  window.show()?;
+
+  animate(
+    window,
+    duration,
+    Arc::new(move |window, progress| {
+      if let Some(from) = from {
+        window.set_position(interpolate(from, to, progress))?;
+      }
+      platform::set_opacity(window, progress)
+    }),
+    |_| Ok(()),
+  );
+  Ok(())
+}
+
+pub(crate) fn hide<R: Runtime>(window: &Window<R>, transition: Transition) -> crate::Result<()> {
+  let (duration, to) = match transition {
+    Transition::None => return window.hide(),
+    Transition::Fade { duration } => (duration, None),
+    Transition::Slide { from, duration } => (duration, Some(from)),
+  };
+
+  let from = window.outer_position()?;
+  let to = to.map(|to| to.to_physical::<i32>(window.scale_factor().unwrap_or(1.)));
+
+  animate(
+    window,
+    duration,
+    Arc::new(move |window, progress| {
+      if let Some(to) = to {
+        window.set_position(interpolate(from, to, progress))?;
+      }
+      platform::set_opacity(window, 1. - progress)
+    }),
+    move |window| {
+      // restores the window so it shows as it was
+      window.hide()?;
+      if to.is_some() {
+        window.set_position(from)?;
+      }
+      platform::set_opacity(window, 1.)
+    },
+  );
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use tauri_runtime::dpi::PhysicalPosition;
+
+  use super::{ease_out, interpolate};
+
+  #[test]
+  fn interpolates_positions() {
+    let from = PhysicalPosition::new(100, 800);
+    let to = PhysicalPosition::new(200, 400);
+    assert_eq!(interpolate(from, to, 0.), from);
+    assert_eq!(interpolate(from, to, 0.5), PhysicalPosition::new(150, 600));
+    assert_eq!(interpolate(from, to, 1.), to);
+    assert_eq!(ease_out(0.), 0.);
+    assert_eq!(ease_out(1.), 1.);
+    assert!(ease_out(0.5) > 0.5);
+  }
+}
@@ -0,0 +1,186 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! The virtual desktops and workspaces of the windows, see [`Window::workspace`].
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Runtime, Window};
+
+/// A virtual desktop on Windows or a workspace on X11, identified by an opaque string.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Workspace(String);
+
+impl Workspace {
+  /// The identifier of the workspace, only meaningful to the current system.
+  pub fn id(&self) -> &str {
+    &self.0
+  }
+}
+
+/// Runs the function on the main thread and waits for its result.
+fn on_main_thread<R: Runtime, T: Send + 'static>(
+  window: &Window<R>,
+  f: impl FnOnce(&Window<R>) -> crate::Result<T> + Send + 'static,
+) -> crate::Result<T> {
+  let (tx, rx) = std::sync::mpsc::channel();
+  let window_ = window.clone();
+  window.run_on_main_thread(move || {
+    let _ = tx.send(f(&window_));
+  })?;
+  rx.recv()
+    .map_err(|_| crate::Error::FailedToReceiveMessage)?
+}
+
+#[cfg(windows)]
+mod platform {
+  use windows::{
+    core::GUID,
+    Win32::{
+      System::Com::{CoCreateInstance, CLSCTX_ALL},
+      UI::Shell::{IVirtualDesktopManager, VirtualDesktopManager},
+    },
+  };
+
+  use super::Workspace;
+  use crate::{Runtime, Window};
+
+  fn manager() -> crate::Result<IVirtualDesktopManager> {
+    // the main thread is initialized for COM by the webview
+    unsafe { CoCreateInstance(&VirtualDesktopManager, None, CLSCTX_ALL) }
+      .map_err(|e| crate::Error::Workspace(e.to_string()))
+  }
+
+  pub fn workspace<R: Runtime>(window: &Window<R>) -> crate::Result<Option<Workspace>> {
+    let id = unsafe { manager()?.GetWindowDesktopId(window.hwnd()?) }
+      .map_err(|e| crate::Error::Workspace(e.to_string()))?;
+    Ok((id != GUID::zeroed()).then(|| Workspace(format!("{:032x}", id.to_u128()))))
+  }
+
+  pub fn move_to_workspace<R: Runtime>(
+    window: &Window<R>,
+    workspace: &Workspace,
+  ) -> crate::Result<()> {
+    let id = u128::from_str_radix(workspace.id(), 16)
+      .map_err(|_| crate::Error::Workspace(format!("invalid workspace `{}`", workspace.id())))?;
+    unsafe { manager()?.MoveWindowToDesktop(window.hwnd()?, &GUID::from_u128(id)) }
+      .map_err(|e| crate::Error::Workspace(e.to_string()))
+  }
+
+  pub fn is_on_current_workspace<R: Runtime>(window: &Window<R>) -> crate::Result<bool> {
+    unsafe { manager()?.IsWindowOnCurrentVirtualDesktop(window.hwnd()?) }
+      .map(|on_current| on_current.as_bool())
+      .map_err(|e| crate::Error::Workspace(e.to_string()))
+  }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+  use cocoa::base::{id, BOOL, YES};
+  use objc::{msg_send, sel, sel_impl};
+
+  use super::Workspace;
+  use crate::{Runtime, Window};
+
+  pub fn workspace<R: Runtime>(_window: &Window<R>) -> crate::Result<Option<Workspace>> {
+    Ok(None)
+  }
+
+  pub fn move_to_workspace<R: Runtime>(
+    _window: &Window<R>,
+    _workspace: &Workspace,
+  ) -> crate::Result<()> {
+    Err(crate::Error::Workspace(
+      "the Spaces can't be selected on macOS".into(),
+    ))
+  }
+
+  pub fn is_on_current_workspace<R: Runtime>(window: &Window<R>) -> crate::Result<bool> {
+    let ns_window = window.ns_window()? as id;
+    let on_active_space: BOOL = unsafe { msg_send![ns_window, isOnActiveSpace] };
+    Ok(on_active_space == YES)
+  }
+}
+
+#[cfg(any(
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd"
+))]
+mod platform {
+  use gdkx11::{X11Screen, X11Window};
+  use gtk::prelude::*;
+
+  use super::Workspace;
+  use crate::{Runtime, Window};
+
+  /// The desktop of the windows shown on all the desktops.
+  const ALL_DESKTOPS: u32 = u32::MAX;
+
+  /// The X11 window, `None` on Wayland.
+  fn x11_window<R: Runtime>(window: &Window<R>) -> crate::Result<Option<X11Window>> {
+    let gdk_window = window
+      .gtk_window()?
+      .window()
+      .ok_or_else(|| crate::Error::Workspace("the window is not realized".into()))?;
+    Ok(gdk_window.downcast::<X11Window>().ok())
+  }
+
+  pub fn workspace<R: Runtime>(window: &Window<R>) -> crate::Result<Option<Workspace>> {
+    Ok(
+      x11_window(window)?
+        .map(|window| window.desktop())
+        .filter(|desktop| *desktop != ALL_DESKTOPS)
+        .map(|desktop| Workspace(desktop.to_string())),
+    )
+  }
+
+  pub fn move_to_workspace<R: Runtime>(
+    window: &Window<R>,
+    workspace: &Workspace,
+  ) -> crate::Result<()> {
+    let desktop = workspace
+      .id()
+      .parse()
+      .map_err(|_| crate::Error::Workspace(format!("invalid workspace `{}`", workspace.id())))?;
+    let x11_window = x11_window(window)?.ok_or_else(|| {
+      crate::Error::Workspace("the workspaces can't be selected on Wayland".into())
+    })?;
+    x11_window.move_to_desktop(desktop);
+    Ok(())
+  }
+
+  pub fn is_on_current_workspace<R: Runtime>(window: &Window<R>) -> crate::Result<bool> {
+    let Some(x11_window) = x11_window(window)? else {
+      return Ok(true);
+    };
+    let desktop = x11_window.desktop();
+    let current = x11_window
+      .screen()
+      .downcast::<X11Screen>()
+      .map(|screen| screen.current_desktop())
+      .unwrap_or(desktop);
+    Ok(desktop == ALL_DESKTOPS || desktop == current)
+  }
+}
+
+pub(crate) fn workspace<R: Runtime>(window: &Window<R>) -> crate::Result<Option<Workspace>> {
+  on_main_thread(window, platform::workspace)
+}
+
+pub(crate) fn move_to_workspace<R: Runtime>(
+  window: &Window<R>,
+  workspace: Workspace,
+) -> crate::Result<()> {
+  on_main_thread(window, move |window| {
+    platform::move_to_workspace(window, &workspace)
+  })
+}
+
+pub(crate) fn is_on_current_workspace<R: Runtime>(window: &Window<R>) -> crate::Result<bool> {
+  on_main_thread(window, platform::is_on_current_workspace)
+}
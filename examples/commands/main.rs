@@ -223,12 +223,31 @@ fn raw_request(request: Request<'_>) -> Response {
   Response::new(include_bytes!("./README.md").to_vec())
 }
 
+// ------------------------ Commands as methods of the managed state ------------------------
+
+#[derive(Default, tauri::ipc::Commands)]
+struct Counter(std::sync::atomic::AtomicUsize);
+
+#[command]
+impl Counter {
+  fn service_command(&self, the_argument: String) -> String {
+    let count = self.0.fetch_add(1, std::sync::atomic::Ordering::SeqCst) + 1;
+    format!("{the_argument} #{count}")
+  }
+
+  async fn async_service_command(&self, the_argument: String) -> Result<usize, ()> {
+    println!("{the_argument}");
+    Ok(self.0.load(std::sync::atomic::Ordering::SeqCst))
+  }
+}
+
 fn main() {
   tauri::Builder::default()
     .manage(MyState {
       value: 0,
       label: "Tauri!".into(),
     })
+    .manage(Counter::default())
     .invoke_handler(tauri::generate_handler![
       borrow_cmd,
       borrow_cmd_async,
@@ -262,6 +281,7 @@ fn main() {
       future_simple_command_with_return,
       future_simple_command_with_result,
       async_stateful_command_with_result,
+      <Counter>,
     ])
     .run(tauri::generate_context!(
       "../../examples/commands/tauri.conf.json"
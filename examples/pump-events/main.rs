@@ -0,0 +1,56 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+#![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
+
+use std::time::{Duration, Instant};
+
+use tauri::{Emitter, Manager};
+
+/// The frame duration of the host loop, at 60 frames per second.
+const FRAME: Duration = Duration::from_micros(16_667);
+
+fn main() {
+  let mut app = tauri::Builder::default()
+    .build(tauri::generate_context!(
+      "../../examples/pump-events/tauri.conf.json"
+    ))
+    .expect("error while building tauri application");
+
+  // a background thread wakes up the loop with `run_on_main_thread`
+  let handle = app.handle().clone();
+  std::thread::spawn(move || loop {
+    std::thread::sleep(Duration::from_secs(1));
+    let handle_ = handle.clone();
+    if handle
+      .run_on_main_thread(move || {
+        let _ = handle_.emit("tick", ());
+      })
+      .is_err()
+    {
+      break;
+    }
+  });
+
+  let mut frames = 0u64;
+  loop {
+    let start = Instant::now();
+
+    // update and render the frame of the host application
+    frames += 1;
+
+    // process the Tauri events until the next frame
+    app.pump_events(
+      Some(FRAME.saturating_sub(start.elapsed())),
+      |_app, _event| {},
+    );
+
+    if app.webview_windows().is_empty() {
+      app.cleanup_before_exit();
+      break;
+    }
+  }
+
+  println!("rendered {frames} frames");
+}
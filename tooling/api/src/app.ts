@@ -101,4 +101,30 @@ async function defaultWindowIcon(): Promise<Image | null> {
   )
 }
 
-export { getName, getVersion, getTauriVersion, show, hide, defaultWindowIcon }
+/**
+ * Opens the URL or absolute path with the default app of the system,
+ * if allowed by the opener policy of the app.
+ *
+ * By default only the `http`, `https`, `mailto` and `tel` URLs are opened.
+ *
+ * @example
+ * ```typescript
+ * import { openWithDefaultApp } from '@tauri-apps/api/app';
+ * await openWithDefaultApp('https://tauri.app');
+ * ```
+ *
+ * @since 2.0.0
+ */
+async function openWithDefaultApp(target: string): Promise<void> {
+  return invoke('plugin:app|open_with_default_app', { target })
+}
+
+export {
+  getName,
+  getVersion,
+  getTauriVersion,
+  show,
+  hide,
+  defaultWindowIcon,
+  openWithDefaultApp
+}
@@ -49,12 +49,16 @@ interface Options {
 enum TauriEvent {
   WINDOW_RESIZED = 'tauri://resize',
   WINDOW_MOVED = 'tauri://move',
+  WINDOW_RESIZE_ENDED = 'tauri://resize-ended',
+  WINDOW_MOVE_ENDED = 'tauri://move-ended',
+  WINDOW_IME = 'tauri://ime',
   WINDOW_CLOSE_REQUESTED = 'tauri://close-requested',
   WINDOW_DESTROYED = 'tauri://destroyed',
   WINDOW_FOCUS = 'tauri://focus',
   WINDOW_BLUR = 'tauri://blur',
   WINDOW_SCALE_FACTOR_CHANGED = 'tauri://scale-change',
   WINDOW_THEME_CHANGED = 'tauri://theme-changed',
+  WINDOW_FULLSCREEN_CHANGED = 'tauri://fullscreen-changed',
   WINDOW_CREATED = 'tauri://window-created',
   WEBVIEW_CREATED = 'tauri://webview-created',
   DRAG_ENTER = 'tauri://drag-enter',
@@ -0,0 +1,91 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+import { Channel, invoke } from './core'
+import type { UnlistenFn } from './event'
+
+/**
+ * Watching files and directories for changes.
+ *
+ * Requires the `fs-watch` Cargo feature of the `tauri` crate,
+ * and the `core:fs-watch:allow-watch` permission, which is not included in `core:default`,
+ * with a scope of the paths that can be watched:
+ *
+ * ```json
+ * {
+ *   "identifier": "core:fs-watch:allow-watch",
+ *   "allow": [{ "path": "$APPDATA/**" }]
+ * }
+ * ```
+ *
+ * @module
+ */
+
+/**
+ * The kind of a change.
+ *
+ * @since 2.0.0
+ */
+export type ChangeKind = 'create' | 'modify' | 'rename' | 'remove' | 'other'
+
+/**
+ * The changes of a kind reported together.
+ *
+ * @since 2.0.0
+ */
+export interface ChangeEvent {
+  /** The kind of the changes. */
+  kind: ChangeKind
+  /** The changed paths, sorted. */
+  paths: string[]
+}
+
+/**
+ * The options of {@link watch}.
+ *
+ * @since 2.0.0
+ */
+export interface WatchOptions {
+  /** Watches the whole tree of the directories instead of their direct children. */
+  recursive?: boolean
+  /** How long the paths must be quiet before their changes are reported, in milliseconds. Defaults to 500. */
+  debounce?: number
+}
+
+/**
+ * Watches the paths allowed by the scope of the `core:fs-watch:allow-watch` permission,
+ * calling the handler with their debounced changes.
+ *
+ * @example
+ * ```typescript
+ * import { watch } from '@tauri-apps/api/fs';
+ * import { appConfigDir } from '@tauri-apps/api/path';
+ * const unwatch = await watch([await appConfigDir()], (event) => {
+ *   console.log(event.kind, event.paths);
+ * });
+ * ```
+ *
+ * @returns A promise resolving to a function to stop watching.
+ *
+ * @since 2.0.0
+ */
+async function watch(
+  paths: string | string[],
+  handler: (event: ChangeEvent) => void,
+  options: WatchOptions = {}
+): Promise<UnlistenFn> {
+  const onEvent = new Channel<ChangeEvent>()
+  onEvent.onmessage = handler
+  const rid = await invoke<number>('plugin:fs-watch|watch', {
+    paths: typeof paths === 'string' ? [paths] : paths,
+    recursive: options.recursive ?? false,
+    debounce: options.debounce,
+    onEvent
+  })
+  return () => {
+    void invoke('plugin:resources|close', { rid })
+  }
+}
+
+export { watch }
@@ -0,0 +1,92 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+import { invoke } from './core'
+import { listen, type UnlistenFn } from './event'
+
+/**
+ * Translates the messages of the locale bundles loaded by the app,
+ * the same source used by the Rust code for the menus, tray and dialogs.
+ *
+ * The `window.__TAURI_I18N__.t(key, args)` helper injected in the webviews translates synchronously
+ * once its `ready` promise resolves.
+ *
+ * @module
+ */
+
+/**
+ * The arguments replacing the `{name}` placeholders of a message.
+ *
+ * @since 2.0.0
+ */
+export type TranslationArgs = Record<string, string | number | boolean>
+
+/**
+ * Translates the message of the key in the current locale.
+ *
+ * Resolves to the key itself if the message is missing.
+ *
+ * @example
+ * ```typescript
+ * import { t } from '@tauri-apps/api/i18n';
+ * const greeting = await t('greeting', { name: 'Tauri' });
+ * ```
+ *
+ * @since 2.0.0
+ */
+async function t(key: string, args?: TranslationArgs): Promise<string> {
+  return invoke('plugin:i18n|translate', { key, args })
+}
+
+/**
+ * The current locale, set with {@link setLocale} or detected from the system.
+ *
+ * @since 2.0.0
+ */
+async function getLocale(): Promise<string> {
+  return invoke('plugin:i18n|locale')
+}
+
+/**
+ * The locales of the loaded bundles.
+ *
+ * @since 2.0.0
+ */
+async function availableLocales(): Promise<string[]> {
+  return invoke('plugin:i18n|available_locales')
+}
+
+/**
+ * Changes the locale of the app and persists it, or restores the locale of the system with `null`.
+ *
+ * Requires the `core:i18n:allow-set-locale` permission, which is not included in `core:default`.
+ *
+ * @example
+ * ```typescript
+ * import { setLocale } from '@tauri-apps/api/i18n';
+ * await setLocale('pt-BR');
+ * ```
+ *
+ * @since 2.0.0
+ */
+async function setLocale(locale: string | null): Promise<void> {
+  return invoke('plugin:i18n|set_locale', { locale })
+}
+
+/**
+ * Listens to the locale changes.
+ *
+ * @returns A promise resolving to a function to stop listening.
+ *
+ * @since 2.0.0
+ */
+async function onLocaleChanged(
+  handler: (locale: string) => void
+): Promise<UnlistenFn> {
+  return listen<string>('tauri://locale-changed', (event) => {
+    handler(event.payload)
+  })
+}
+
+export { t, getLocale, availableLocales, setLocale, onLocaleChanged }
@@ -12,6 +12,16 @@ export interface ImageSize {
   height: number
 }
 
+/**
+ * The filter used to resize an image, from the fastest to the smoothest.
+ */
+export type ResizeFilter =
+  | 'nearest'
+  | 'triangle'
+  | 'catmullRom'
+  | 'gaussian'
+  | 'lanczos3'
+
 /** An RGBA Image in row-major order from top to bottom. */
 export class Image extends Resource {
   /**
@@ -40,9 +50,9 @@ export class Image extends Resource {
    * Creates a new image using the provided bytes by inferring the file format.
    * If the format is known, prefer [@link Image.fromPngBytes] or [@link Image.fromIcoBytes].
    *
-   * Only `ico` and `png` are supported (based on activated feature flag).
+   * Only `ico`, `png`, `jpeg` and `svg` are supported (based on activated feature flag).
    *
-   * Note that you need the `image-ico` or `image-png` Cargo features to use this API.
+   * Note that you need the `image-ico`, `image-png`, `image-jpeg` or `image-svg` Cargo features to use this API.
    * To enable it, change your Cargo.toml file:
    * ```toml
    * [dependencies]
@@ -60,9 +70,9 @@ export class Image extends Resource {
   /**
    * Creates a new image using the provided path.
    *
-   * Only `ico` and `png` are supported (based on activated feature flag).
+   * Only `ico`, `png`, `jpeg` and `svg` are supported (based on activated feature flag).
    *
-   * Note that you need the `image-ico` or `image-png` Cargo features to use this API.
+   * Note that you need the `image-ico`, `image-png`, `image-jpeg` or `image-svg` Cargo features to use this API.
    * To enable it, change your Cargo.toml file:
    * ```toml
    * [dependencies]
@@ -75,6 +85,27 @@ export class Image extends Resource {
     )
   }
 
+  /**
+   * Resizes the image to the exact width and height, returning a new image.
+   *
+   * Note that you need one of the `image-ico`, `image-png`, `image-jpeg` or `image-svg` Cargo features to use this API,
+   * and the `core:image:allow-resize` permission. The width and height can't exceed 8192.
+   *
+   * @param filter The resize filter, defaults to `lanczos3`.
+   */
+  async resize(
+    width: number,
+    height: number,
+    filter?: ResizeFilter
+  ): Promise<Image> {
+    return invoke<number>('plugin:image|resize', {
+      rid: this.rid,
+      width,
+      height,
+      filter
+    }).then((rid) => new Image(rid))
+  }
+
   /** Returns the RGBA data for this image, in row-major order from top to bottom.  */
   async rgba(): Promise<Uint8Array> {
     return invoke<number[]>('plugin:image|rgba', {
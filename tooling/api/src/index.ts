@@ -17,7 +17,10 @@ import * as app from './app'
 import * as core from './core'
 import * as dpi from './dpi'
 import * as event from './event'
+import * as fs from './fs'
+import * as i18n from './i18n'
 import * as image from './image'
+import * as input from './input'
 import * as menu from './menu'
 import * as mocks from './mocks'
 import * as path from './path'
@@ -31,7 +34,10 @@ export {
   core,
   dpi,
   event,
+  fs,
+  i18n,
   image,
+  input,
   menu,
   mocks,
   path,
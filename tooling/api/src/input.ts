@@ -0,0 +1,76 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+import { Channel, invoke } from './core'
+import type { UnlistenFn } from './event'
+
+/**
+ * System-wide keyboard and mouse events.
+ *
+ * Requires the `global-input-listener` Cargo feature of the `tauri` crate,
+ * and the `core:input:allow-listen-global` and `core:input:allow-unlisten-global` permissions,
+ * which are not included in `core:default`.
+ *
+ * @module
+ */
+
+/**
+ * A mouse button, or the platform code of the other buttons.
+ *
+ * @since 2.0.0
+ */
+export type MouseButton = 'left' | 'right' | 'middle' | { other: number }
+
+/**
+ * A system-wide keyboard or mouse event.
+ *
+ * The key events carry the name of the physical key, e.g. `KeyA`, `ShiftLeft` or `Space`,
+ * never the text it produces.
+ *
+ * @since 2.0.0
+ */
+export type GlobalInputEvent =
+  | { type: 'keyPressed'; key: string }
+  | { type: 'keyReleased'; key: string }
+  | { type: 'mouseButtonPressed'; button: MouseButton }
+  | { type: 'mouseButtonReleased'; button: MouseButton }
+  | { type: 'mouseMoved'; position: { x: number; y: number } }
+  | { type: 'mouseWheel'; deltaX: number; deltaY: number }
+
+/**
+ * Listens to the keyboard and mouse events of the whole system, even when the app is not focused.
+ *
+ * @example
+ * ```typescript
+ * import { listenGlobalInput } from '@tauri-apps/api/input';
+ * const unlisten = await listenGlobalInput((event) => {
+ *   if (event.type === 'keyPressed' && event.key === 'F13') {
+ *     console.log('push to talk');
+ *   }
+ * });
+ * ```
+ *
+ * #### Platform-specific
+ *
+ * - **macOS:** Prompts the user to grant the Accessibility access, and rejects until it is granted.
+ * - **Linux:** Only X11 is supported.
+ *
+ * @returns A promise resolving to a function to stop listening.
+ *
+ * @since 2.0.0
+ */
+async function listenGlobalInput(
+  handler: (event: GlobalInputEvent) => void
+): Promise<UnlistenFn> {
+  const channel = new Channel<GlobalInputEvent>()
+  channel.onmessage = handler
+  const id = await invoke<number>('plugin:input|listen_global', {
+    handler: channel
+  })
+  return () => {
+    void invoke('plugin:input|unlisten_global', { id })
+  }
+}
+
+export { listenGlobalInput }
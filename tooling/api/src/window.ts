@@ -55,6 +55,13 @@ export interface Monitor {
 
 type Theme = 'light' | 'dark'
 type TitleBarStyle = 'visible' | 'transparent' | 'overlay'
+type CornerPreference = 'default' | 'doNotRound' | 'round' | 'roundSmall'
+/**
+ * How a window covers a monitor in fullscreen:
+ * - `borderless`: the window covers the monitor without changing its video mode.
+ * - `exclusive`: the monitor switches to its video mode with the highest resolution and refresh rate, falling back to `borderless` on Linux.
+ */
+type FullscreenMode = 'borderless' | 'exclusive'
 
 type ResizeDirection =
   | 'East'
@@ -795,6 +802,45 @@ class Window {
     })
   }
 
+  /**
+   * Gets the virtual desktop or workspace of the window, to restore it with {@link Window.moveToWorkspace}.
+   *
+   * #### Platform-specific
+   *
+   * - **Linux:** Returns `null` on Wayland and when the window is visible on all workspaces.
+   * - **macOS:** Always returns `null`.
+   *
+   * @example
+   * ```typescript
+   * import { getCurrentWindow } from '@tauri-apps/api/window';
+   * const workspace = await getCurrentWindow().workspace();
+   * ```
+   *
+   * @returns The opaque identifier of the workspace.
+   *
+   * @since 2.0.0
+   */
+  async workspace(): Promise<string | null> {
+    return invoke('plugin:window|workspace', {
+      label: this.label
+    })
+  }
+
+  /**
+   * Gets whether the window is on the virtual desktop or workspace shown to the user.
+   *
+   * #### Platform-specific
+   *
+   * - **Linux:** Always `true` on Wayland.
+   *
+   * @since 2.0.0
+   */
+  async isOnCurrentWorkspace(): Promise<boolean> {
+    return invoke('plugin:window|is_on_current_workspace', {
+      label: this.label
+    })
+  }
+
   // Setters
 
   /**
@@ -1145,6 +1191,54 @@ class Window {
     })
   }
 
+  /**
+   * Sets how the corners of the window are rounded.
+   *
+   * #### Platform-specific
+   *
+   * - **Windows:** Requires Windows 11.
+   * - **macOS:** Rounds the content of the window, mostly useful without decorations.
+   * - **Linux:** Unsupported.
+   *
+   * @example
+   * ```typescript
+   * import { getCurrentWindow } from '@tauri-apps/api/window';
+   * await getCurrentWindow().setCornerPreference('roundSmall');
+   * ```
+   *
+   * @returns A promise indicating the success or failure of the operation.
+   */
+  async setCornerPreference(preference: CornerPreference): Promise<void> {
+    return invoke('plugin:window|set_corner_preference', {
+      label: this.label,
+      value: preference
+    })
+  }
+
+  /**
+   * Sets the color of the window border, `null` restoring the system color.
+   *
+   * #### Platform-specific
+   *
+   * - **Windows:** Requires Windows 11.
+   * - **macOS:** Draws a 1px border around the content of the window.
+   * - **Linux:** Unsupported.
+   *
+   * @example
+   * ```typescript
+   * import { getCurrentWindow } from '@tauri-apps/api/window';
+   * await getCurrentWindow().setBorderColor([255, 0, 0, 255]);
+   * ```
+   *
+   * @returns A promise indicating the success or failure of the operation.
+   */
+  async setBorderColor(color: Color | null): Promise<void> {
+    return invoke('plugin:window|set_border_color', {
+      label: this.label,
+      value: color
+    })
+  }
+
   /**
    * Set window effects.
    */
@@ -1400,6 +1494,31 @@ class Window {
     })
   }
 
+  /**
+   * Makes the window fullscreen with the mode, on the monitor or on the current monitor if not set.
+   * Use {@link Window.setFullscreen} to leave fullscreen.
+   * @example
+   * ```typescript
+   * import { availableMonitors, getCurrentWindow } from '@tauri-apps/api/window';
+   * const [, secondary] = await availableMonitors();
+   * await getCurrentWindow().setFullscreenMode('exclusive', secondary);
+   * ```
+   *
+   * @param mode How the window covers the monitor.
+   * @param monitor The monitor to cover, identified by its name.
+   * @returns A promise indicating the success or failure of the operation.
+   */
+  async setFullscreenMode(
+    mode: FullscreenMode,
+    monitor?: Monitor | null
+  ): Promise<void> {
+    return invoke('plugin:window|set_fullscreen_mode', {
+      label: this.label,
+      mode,
+      monitor: monitor?.name ?? null
+    })
+  }
+
   /**
    * Bring the window to front and focus.
    * @example
@@ -1605,10 +1724,14 @@ class Window {
 
   /**
    * Starts resize-dragging the window.
+   *
+   * The elements with a `data-tauri-resize-region` attribute set to `top`, `bottom`, `left`, `right`,
+   * `top-left`, `top-right`, `bottom-left` or `bottom-right` start it on mousedown and show the matching resize cursor.
+   * On macOS these elements resize the window with `setSize` and `setPosition` instead.
    * @example
    * ```typescript
    * import { getCurrentWindow } from '@tauri-apps/api/window';
-   * await getCurrentWindow().startResizeDragging();
+   * await getCurrentWindow().startResizeDragging('SouthEast');
    * ```
    *
    * @return A promise indicating the success or failure of the operation.
@@ -1662,6 +1785,23 @@ class Window {
     })
   }
 
+  /**
+   * Moves the window to a virtual desktop or workspace returned by {@link Window.workspace}.
+   *
+   * #### Platform-specific
+   *
+   * - **Linux:** Unsupported on Wayland.
+   * - **macOS:** Unsupported.
+   *
+   * @since 2.0.0
+   */
+  async moveToWorkspace(workspace: string): Promise<void> {
+    return invoke('plugin:window|move_to_workspace', {
+      label: this.label,
+      value: workspace
+    })
+  }
+
   /**
    * Sets the title bar style. **macOS only**.
    *
@@ -1931,6 +2071,32 @@ class Window {
   async onThemeChanged(handler: EventCallback<Theme>): Promise<UnlistenFn> {
     return this.listen<Theme>(TauriEvent.WINDOW_THEME_CHANGED, handler)
   }
+
+  /**
+   * Listen to the window entering or leaving fullscreen, including the changes made by the system, e.g. with the green button on macOS.
+   *
+   * @example
+   * ```typescript
+   * import { getCurrentWindow } from "@tauri-apps/api/window";
+   * const unlisten = await getCurrentWindow().onFullscreenChanged(({ payload: fullscreen }) => {
+   *  console.log('Fullscreen: ' + fullscreen);
+   * });
+   *
+   * // you need to call unlisten if your handler goes out of scope e.g. the component is unmounted
+   * unlisten();
+   * ```
+   *
+   * @returns A promise resolving to a function to unlisten to the event.
+   * Note that removing the listener is required if your listener goes out of scope e.g. the component is unmounted.
+   */
+  async onFullscreenChanged(
+    handler: EventCallback<boolean>
+  ): Promise<UnlistenFn> {
+    return this.listen<boolean>(
+      TauriEvent.WINDOW_FULLSCREEN_CHANGED,
+      handler
+    )
+  }
 }
 
 /**
@@ -2362,6 +2528,8 @@ export type {
   Effects,
   Theme,
   TitleBarStyle,
+  CornerPreference,
+  FullscreenMode,
   ScaleFactorChanged,
   WindowOptions,
   Color,
@@ -3,31 +3,48 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
+mod analysis;
+mod attestation;
 mod category;
 mod common;
+mod hooks;
 #[cfg(target_os = "linux")]
 mod linux;
 #[cfg(target_os = "macos")]
 mod macos;
 mod path_utils;
 mod platform;
+mod sbom;
 mod settings;
 mod updater_bundle;
+mod updater_delta;
 mod windows;
 
 use tauri_utils::display_path;
 
 pub use self::{
+  analysis::{
+    analyze_bundles, write_analysis, ArtifactSize, BundleAnalysis, CategorySize, FileSize,
+    SizeCategory, ANALYSIS_FILE_NAME,
+  },
   category::AppCategory,
+  hooks::BundlerHook,
+  sbom::{write_sboms, SbomDependencies, SbomFormat, SbomPackage},
   settings::{
-    AppImageSettings, BundleBinary, BundleSettings, DebianSettings, DmgSettings, MacOsSettings,
-    PackageSettings, PackageType, Position, RpmSettings, Settings, SettingsBuilder, Size,
-    UpdaterSettings,
+    AppImageSettings, BundleBinary, BundleSettings, DebianSettings, DmgSettings, FlatpakSettings,
+    MacOsSettings, PackageSettings, PackageType, PkgSettings, Position, RpmSettings, Settings,
+    SettingsBuilder, Size, SnapSettings, UpdaterDeltaBase, UpdaterSettings,
   },
 };
 #[cfg(target_os = "macos")]
 use anyhow::Context;
-pub use settings::{NsisSettings, WindowsSettings, WixLanguage, WixLanguageConfig, WixSettings};
+pub use settings::{
+  MsixSettings, NsisSettings, PortableSettings, WindowsSettings, WixLanguage, WixLanguageConfig,
+  WixSettings,
+};
+pub use windows::sign::{
+  signer_from_config, AzureTrustedSigner, CommandSigner, SignParams, SigntoolSigner, WindowsSigner,
+};
 
 use std::{fmt::Write, path::PathBuf};
 
@@ -43,6 +60,15 @@ pub struct Bundle {
 /// Bundles the project.
 /// Returns the list of paths where the bundles can be found.
 pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<Bundle>> {
+  bundle_project_with_hooks(settings, &mut [])
+}
+
+/// Bundles the project, running the given hooks around the packaging steps.
+/// Returns the list of paths where the bundles can be found.
+pub fn bundle_project_with_hooks(
+  settings: &Settings,
+  hooks: &mut [&mut dyn BundlerHook],
+) -> crate::Result<Vec<Bundle>> {
   let mut package_types = settings.package_types()?;
   if package_types.is_empty() {
     return Ok(Vec::new());
@@ -70,24 +96,17 @@ pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<Bundle>> {
       }
 
       // Sign the sidecar binaries
-      for bin in settings.external_binaries() {
-        let path = bin?;
-        let skip =
-          std::env::var("TAURI_SKIP_SIDECAR_SIGNATURE_CHECK").map_or(false, |v| v == "true");
-        if skip {
-          continue;
-        }
-
-        #[cfg(windows)]
-        if windows::sign::verify(&path)? {
-          log::info!(
-            "sidecar at \"{}\" already signed. Skipping...",
-            path.display()
-          );
-          continue;
+      let skip_sidecars =
+        std::env::var("TAURI_SKIP_SIDECAR_SIGNATURE_CHECK").map_or(false, |v| v == "true");
+      if !skip_sidecars {
+        for bin in settings.external_binaries() {
+          windows::sign::try_sign_unsigned(&bin?, settings)?;
         }
+      }
 
-        windows::sign::try_sign(&path, settings)?;
+      // Sign the libraries next to the app executable and in the resources
+      for dll in windows::sign::libraries(settings)? {
+        windows::sign::try_sign_unsigned(&dll, settings)?;
       }
     } else {
       #[cfg(not(target_os = "windows"))]
@@ -95,6 +114,10 @@ pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<Bundle>> {
     }
   }
 
+  for hook in hooks.iter_mut() {
+    hook.pre_package(settings)?;
+  }
+
   let mut bundles = Vec::<Bundle>::new();
   for package_type in &package_types {
     // bundle was already built! e.g. DMG already built .app
@@ -102,6 +125,8 @@ pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<Bundle>> {
       continue;
     }
 
+    let built_bundles = bundles.len();
+
     let bundle_paths = match package_type {
       #[cfg(target_os = "macos")]
       PackageType::MacOsBundle => macos::app::bundle_project(settings)?,
@@ -119,10 +144,25 @@ pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<Bundle>> {
         }
         bundled.dmg
       }
+      // pkg is dependent of MacOsBundle too
+      #[cfg(target_os = "macos")]
+      PackageType::Pkg => {
+        let bundled = macos::pkg::bundle_project(settings, &bundles)?;
+        if !bundled.app.is_empty() {
+          bundles.push(Bundle {
+            package_type: PackageType::MacOsBundle,
+            bundle_paths: bundled.app,
+          });
+        }
+        bundled.pkg
+      }
 
       #[cfg(target_os = "windows")]
       PackageType::WindowsMsi => windows::msi::bundle_project(settings, false)?,
       PackageType::Nsis => windows::nsis::bundle_project(settings, false)?,
+      #[cfg(target_os = "windows")]
+      PackageType::Msix => windows::msix::bundle_project(settings)?,
+      PackageType::Portable => windows::portable::bundle_project(settings)?,
 
       #[cfg(target_os = "linux")]
       PackageType::Deb => linux::debian::bundle_project(settings)?,
@@ -130,6 +170,10 @@ pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<Bundle>> {
       PackageType::Rpm => linux::rpm::bundle_project(settings)?,
       #[cfg(target_os = "linux")]
       PackageType::AppImage => linux::appimage::bundle_project(settings)?,
+      #[cfg(target_os = "linux")]
+      PackageType::Flatpak => linux::flatpak::bundle_project(settings)?,
+      #[cfg(target_os = "linux")]
+      PackageType::Snap => linux::snap::bundle_project(settings)?,
       _ => {
         log::warn!("ignoring {}", package_type.short_name());
         continue;
@@ -140,6 +184,7 @@ pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<Bundle>> {
       package_type: package_type.to_owned(),
       bundle_paths,
     });
+    hooks::run_artifact_hooks(hooks, settings, &bundles[built_bundles..])?;
   }
 
   if let Some(updater) = settings.updater() {
@@ -161,6 +206,7 @@ pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<Bundle>> {
         package_type: PackageType::Updater,
         bundle_paths: updater_paths,
       });
+      hooks::run_artifact_hooks(hooks, settings, &bundles[bundles.len() - 1..])?;
     } else if updater.v1_compatible
       || !package_types.iter().any(|package_type| {
         // Self contained updater, no need to zip
@@ -172,6 +218,18 @@ pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<Bundle>> {
     {
       log::warn!("The bundler was configured to create updater artifacts but no updater-enabled targets were built. Please enable one of these targets: app, appimage, msi, nsis");
     }
+
+    if !updater.delta_bases.is_empty() {
+      let delta_paths = updater_delta::bundle_project(settings, updater, &bundles)?;
+      if !delta_paths.is_empty() {
+        bundles.push(Bundle {
+          package_type: PackageType::Updater,
+          bundle_paths: delta_paths,
+        });
+        hooks::run_artifact_hooks(hooks, settings, &bundles[bundles.len() - 1..])?;
+      }
+    }
+
     if updater.v1_compatible {
       log::warn!("Legacy v1 compatible updater is deprecated and will be removed in v3, change bundle > createUpdaterArtifacts to true when your users are updated to the version with v2 updater plugin");
     }
@@ -208,6 +266,15 @@ pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<Bundle>> {
     return Err(anyhow::anyhow!("No bundles were built").into());
   }
 
+  if let Some(source_date_epoch) = settings.source_date_epoch() {
+    let attestation_path = attestation::write_attestation(settings, source_date_epoch, &bundles)?;
+    log::info!(action = "Attesting"; "{}", display_path(&attestation_path));
+  }
+
+  for hook in hooks.iter_mut() {
+    hook.post_package(settings, &bundles)?;
+  }
+
   let bundles_wo_updater = bundles
     .iter()
     .filter(|b| b.package_type != PackageType::Updater)
@@ -0,0 +1,262 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use super::{common, Bundle};
+use crate::Settings;
+
+use serde::Serialize;
+use std::{
+  collections::BTreeMap,
+  path::{Path, PathBuf},
+};
+use walkdir::WalkDir;
+
+/// The bundle size analysis file name, written to the bundle directory.
+pub const ANALYSIS_FILE_NAME: &str = "analysis.json";
+
+/// The number of files listed in [`BundleAnalysis::largest_files`].
+const LARGEST_FILES_COUNT: usize = 10;
+
+/// The category of the files shipped with the app.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SizeCategory {
+  /// The app binaries.
+  Binary,
+  /// The frontend assets.
+  ///
+  /// They are embedded in the main binary, so their size is already included in the [`Self::Binary`] size.
+  Frontend,
+  /// The `bundle > resources` files.
+  Resources,
+  /// The `bundle > externalBin` sidecars.
+  Sidecars,
+  /// The macOS frameworks and libraries.
+  Frameworks,
+}
+
+impl SizeCategory {
+  /// The category name.
+  pub fn name(&self) -> &'static str {
+    match self {
+      Self::Binary => "binary",
+      Self::Frontend => "frontend",
+      Self::Resources => "resources",
+      Self::Sidecars => "sidecars",
+      Self::Frameworks => "frameworks",
+    }
+  }
+}
+
+/// The size of a category.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CategorySize {
+  /// The category.
+  pub category: SizeCategory,
+  /// The total size of the category files, in bytes.
+  pub size: u64,
+  /// The number of files in the category.
+  pub file_count: usize,
+}
+
+/// The size of a file shipped with the app.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileSize {
+  /// The file path.
+  pub path: PathBuf,
+  /// The category of the file.
+  pub category: SizeCategory,
+  /// The file size, in bytes.
+  pub size: u64,
+}
+
+/// The size of a generated artifact.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArtifactSize {
+  /// The artifact path.
+  pub path: PathBuf,
+  /// The short name of the bundle type.
+  pub bundle: &'static str,
+  /// The artifact size, in bytes. The size of a directory such as the macOS app bundle is the size of its files.
+  pub size: u64,
+}
+
+/// The size breakdown of the app and its bundles.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleAnalysis {
+  /// The product name.
+  pub product_name: String,
+  /// The app version.
+  pub version: String,
+  /// The target triple.
+  pub target: String,
+  /// The uncompressed size of the app, in bytes, without the frontend assets embedded in the binary.
+  pub total_size: u64,
+  /// The size of each category.
+  pub categories: Vec<CategorySize>,
+  /// The largest files, biggest first.
+  pub largest_files: Vec<FileSize>,
+  /// The size of each generated artifact.
+  pub artifacts: Vec<ArtifactSize>,
+}
+
+/// Computes the size breakdown of the app files by category and of the generated artifacts.
+///
+/// `frontend_dist` lists the frontend files and directories embedded in the app.
+pub fn analyze_bundles(
+  settings: &Settings,
+  frontend_dist: &[PathBuf],
+  bundles: &[Bundle],
+) -> crate::Result<BundleAnalysis> {
+  let mut files = Vec::new();
+
+  for bin in settings.binaries() {
+    push_files(&mut files, SizeCategory::Binary, &settings.binary_path(bin))?;
+  }
+  for path in frontend_dist {
+    push_files(&mut files, SizeCategory::Frontend, path)?;
+  }
+  for src in settings.resource_files() {
+    push_files(&mut files, SizeCategory::Resources, &src?)?;
+  }
+  for src in settings.external_binaries() {
+    push_files(&mut files, SizeCategory::Sidecars, &src?)?;
+  }
+  for path in framework_paths(settings) {
+    push_files(&mut files, SizeCategory::Frameworks, &path)?;
+  }
+
+  let mut categories = BTreeMap::new();
+  for file in &files {
+    let entry = categories.entry(file.category).or_insert(CategorySize {
+      category: file.category,
+      size: 0,
+      file_count: 0,
+    });
+    entry.size += file.size;
+    entry.file_count += 1;
+  }
+  let categories = categories.into_values().collect::<Vec<_>>();
+  let total_size = categories
+    .iter()
+    .filter(|c| c.category != SizeCategory::Frontend)
+    .map(|c| c.size)
+    .sum();
+
+  files.sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.path.cmp(&b.path)));
+  files.truncate(LARGEST_FILES_COUNT);
+
+  let mut artifacts = Vec::new();
+  for bundle in bundles {
+    for path in &bundle.bundle_paths {
+      artifacts.push(ArtifactSize {
+        path: path.clone(),
+        bundle: bundle.package_type.short_name(),
+        size: size_of(path)?,
+      });
+    }
+  }
+
+  Ok(BundleAnalysis {
+    product_name: settings.product_name().into(),
+    version: settings.version_string().into(),
+    target: settings.target().into(),
+    total_size,
+    categories,
+    largest_files: files,
+    artifacts,
+  })
+}
+
+/// Writes the analysis to the bundle directory.
+pub fn write_analysis(settings: &Settings, analysis: &BundleAnalysis) -> crate::Result<PathBuf> {
+  let path = settings
+    .project_out_directory()
+    .join("bundle")
+    .join(ANALYSIS_FILE_NAME);
+  let file = common::create_file(&path)?;
+  serde_json::to_writer_pretty(file, analysis)?;
+  Ok(path)
+}
+
+/// Pushes the file, or each file of the directory.
+fn push_files(files: &mut Vec<FileSize>, category: SizeCategory, path: &Path) -> crate::Result<()> {
+  for entry in WalkDir::new(path).sort_by_file_name() {
+    let entry = entry?;
+    if entry.file_type().is_file() {
+      files.push(FileSize {
+        path: entry.path().to_path_buf(),
+        category,
+        size: entry.metadata()?.len(),
+      });
+    }
+  }
+  Ok(())
+}
+
+/// The size of the file, or of the files of the directory.
+fn size_of(path: &Path) -> crate::Result<u64> {
+  let mut size = 0;
+  for entry in WalkDir::new(path) {
+    let entry = entry?;
+    if entry.file_type().is_file() {
+      size += entry.metadata()?.len();
+    }
+  }
+  Ok(size)
+}
+
+/// The paths of the bundled macOS frameworks and libraries, looked up like the app bundler does.
+fn framework_paths(settings: &Settings) -> Vec<PathBuf> {
+  if !settings.target().ends_with("apple-darwin") {
+    return Vec::new();
+  }
+
+  let mut search_dirs = Vec::new();
+  if let Some(home_dir) = dirs::home_dir() {
+    search_dirs.push(home_dir.join("Library/Frameworks"));
+  }
+  search_dirs.push(PathBuf::from("/Library/Frameworks"));
+  search_dirs.push(PathBuf::from("/Network/Library/Frameworks"));
+
+  let frameworks = settings.macos().frameworks.as_deref().unwrap_or_default();
+  frameworks
+    .iter()
+    .filter_map(|framework| {
+      if framework.ends_with(".framework") || framework.ends_with(".dylib") {
+        Some(PathBuf::from(framework))
+      } else {
+        search_dirs
+          .iter()
+          .map(|dir| dir.join(format!("{framework}.framework")))
+          .find(|path| path.exists())
+      }
+    })
+    .filter(|path| path.exists())
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn file_sizes() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(dir.path().join("assets")).unwrap();
+    std::fs::write(dir.path().join("index.html"), [0; 10]).unwrap();
+    std::fs::write(dir.path().join("assets").join("app.js"), [0; 32]).unwrap();
+
+    let mut files = Vec::new();
+    push_files(&mut files, SizeCategory::Frontend, dir.path()).unwrap();
+    assert_eq!(files.len(), 2);
+    assert!(files.iter().all(|f| f.category == SizeCategory::Frontend));
+    assert_eq!(size_of(dir.path()).unwrap(), 42);
+    assert_eq!(size_of(&dir.path().join("index.html")).unwrap(), 10);
+  }
+}
@@ -0,0 +1,97 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use super::{common, Bundle};
+use crate::Settings;
+
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+/// The build attestation file name, written to the bundle directory in reproducible mode.
+pub const ATTESTATION_FILE_NAME: &str = "attestation.json";
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Attestation<'a> {
+  product_name: &'a str,
+  version: &'a str,
+  target: &'a str,
+  source_date_epoch: u64,
+  artifacts: Vec<Artifact>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Artifact {
+  /// Path relative to the project output directory, using forward slashes.
+  path: String,
+  bundle: &'static str,
+  sha256: String,
+}
+
+/// Writes the build attestation listing the SHA-256 hash of each generated artifact,
+/// so the artifacts can be verified against an independent build of the same sources.
+///
+/// Directories, such as the macOS app bundle, are not listed since only their archives are distributed.
+pub fn write_attestation(
+  settings: &Settings,
+  source_date_epoch: u64,
+  bundles: &[Bundle],
+) -> crate::Result<PathBuf> {
+  let out_dir = settings.project_out_directory();
+
+  let mut artifacts = Vec::new();
+  for bundle in bundles {
+    for path in bundle.bundle_paths.iter().filter(|path| path.is_file()) {
+      artifacts.push(Artifact {
+        path: relative_path(out_dir, path),
+        bundle: bundle.package_type.short_name(),
+        sha256: common::sha256_file(path)?,
+      });
+    }
+  }
+  artifacts.sort_by(|a, b| a.path.cmp(&b.path));
+
+  let attestation = Attestation {
+    product_name: settings.product_name(),
+    version: settings.version_string(),
+    target: settings.target(),
+    source_date_epoch,
+    artifacts,
+  };
+
+  let attestation_path = out_dir.join("bundle").join(ATTESTATION_FILE_NAME);
+  let file = common::create_file(&attestation_path)?;
+  serde_json::to_writer_pretty(file, &attestation)?;
+
+  Ok(attestation_path)
+}
+
+fn relative_path(out_dir: &Path, path: &Path) -> String {
+  path
+    .strip_prefix(out_dir)
+    .unwrap_or(path)
+    .components()
+    .map(|c| c.as_os_str().to_string_lossy())
+    .collect::<Vec<_>>()
+    .join("/")
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn relative_artifact_path() {
+    let out_dir = Path::new("target").join("release");
+    assert_eq!(
+      relative_path(
+        &out_dir,
+        &out_dir.join("bundle").join("deb").join("app.deb")
+      ),
+      "bundle/deb/app.deb"
+    );
+    assert_eq!(relative_path(&out_dir, Path::new("other.deb")), "other.deb");
+  }
+}
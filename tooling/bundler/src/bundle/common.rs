@@ -6,12 +6,17 @@
 use std::{
   ffi::OsStr,
   fs::{self, File},
-  io::{self, BufRead, BufReader, BufWriter},
+  io::{self, BufRead, BufReader, BufWriter, Read},
   path::Path,
   process::{Command, ExitStatus, Output, Stdio},
   sync::{Arc, Mutex},
+  time::{SystemTime, UNIX_EPOCH},
 };
 
+use anyhow::Context;
+use sha2::Digest;
+use tauri_utils::display_path;
+
 /// Returns true if the path has a filename indicating that it is a high-density
 /// "retina" icon.  Specifically, returns true the file stem ends with
 /// "@2x" (a convention specified by the [Apple developer docs](
@@ -62,6 +67,22 @@ fn symlink_file(src: &Path, dst: &Path) -> io::Result<()> {
   std::os::windows::fs::symlink_file(src, dst)
 }
 
+/// Computes the SHA-256 hash of the file, encoded as a hex string.
+pub fn sha256_file(path: &Path) -> crate::Result<String> {
+  let mut file =
+    File::open(path).with_context(|| format!("failed to open {}", display_path(path)))?;
+  let mut hasher = sha2::Sha256::new();
+  let mut buf = [0; 64 * 1024];
+  loop {
+    let n = file.read(&mut buf)?;
+    if n == 0 {
+      break;
+    }
+    hasher.update(&buf[..n]);
+  }
+  Ok(hex::encode(hasher.finalize()))
+}
+
 /// Copies a regular file from one path to another, creating any parent
 /// directories of the destination path as necessary.  Fails if the source path
 /// is a directory or doesn't exist.
@@ -240,9 +261,60 @@ impl CommandExt for Command {
   }
 }
 
+/// The timestamp of the build: the source date epoch if set, or the current time.
+pub fn build_timestamp(source_date_epoch: Option<u64>) -> u64 {
+  source_date_epoch.unwrap_or_else(|| {
+    SystemTime::now()
+      .duration_since(UNIX_EPOCH)
+      .map(|d| d.as_secs())
+      .unwrap_or_default()
+  })
+}
+
+/// Formats the UNIX timestamp as an RFC 3339 date in UTC, e.g. `2024-05-01T00:00:00Z`.
+pub fn rfc3339(timestamp: u64) -> String {
+  let (year, month, day) = civil_from_days((timestamp / 86400) as i64);
+  let seconds = timestamp % 86400;
+  format!(
+    "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+    seconds / 3600,
+    seconds / 60 % 60,
+    seconds % 60
+  )
+}
+
+/// The days since the UNIX epoch of a proleptic Gregorian date, see
+/// <http://howardhinnant.github.io/date_algorithms.html>.
+#[allow(dead_code)]
+pub fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+  let year = if month <= 2 { year - 1 } else { year };
+  let era = year.div_euclid(400);
+  let year_of_era = year - era * 400;
+  let month = month as i64;
+  let day_of_year =
+    (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day as i64 - 1;
+  let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+  era * 146097 + day_of_era - 719468
+}
+
+/// The proleptic Gregorian date as (year, month, day) of the days since the UNIX epoch.
+pub fn civil_from_days(days: i64) -> (i64, u32, u32) {
+  let days = days + 719468;
+  let era = days.div_euclid(146097);
+  let day_of_era = days - era * 146097;
+  let year_of_era =
+    (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+  let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+  let mp = (5 * day_of_year + 2) / 153;
+  let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u32;
+  let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+  let year = year_of_era + era * 400 + i64::from(month <= 2);
+  (year, month, day)
+}
+
 #[cfg(test)]
 mod tests {
-  use super::{create_file, is_retina};
+  use super::{civil_from_days, create_file, days_from_civil, is_retina, rfc3339};
   use std::{io::Write, path::PathBuf};
   use tauri_utils::resources::resource_relpath;
 
@@ -331,4 +403,13 @@ mod tests {
       PathBuf::from("_root_/home/ferris/crab.png")
     );
   }
+
+  #[test]
+  fn dates() {
+    assert_eq!(days_from_civil(1970, 1, 1), 0);
+    assert_eq!(days_from_civil(2000, 3, 1), 11017);
+    assert_eq!(civil_from_days(11017), (2000, 3, 1));
+    assert_eq!(civil_from_days(19844), (2024, 5, 1));
+    assert_eq!(rfc3339(1714608000 + 3723), "2024-05-02T01:02:03Z");
+  }
 }
@@ -0,0 +1,50 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use super::{Bundle, PackageType, Settings};
+
+use std::path::Path;
+
+/// Hooks into the bundling process, to run custom packaging steps such as
+/// signing with an external service, scanning or uploading the artifacts.
+///
+/// Use [`crate::bundle_project_with_hooks`] to run the bundler with hooks.
+pub trait BundlerHook {
+  /// Called before the bundles are packaged.
+  fn pre_package(&mut self, _settings: &Settings) -> crate::Result<()> {
+    Ok(())
+  }
+
+  /// Called for each artifact once its bundle is packaged,
+  /// before the updater artifacts are created from it.
+  fn artifact(
+    &mut self,
+    _settings: &Settings,
+    _package_type: PackageType,
+    _path: &Path,
+  ) -> crate::Result<()> {
+    Ok(())
+  }
+
+  /// Called after all bundles are packaged.
+  fn post_package(&mut self, _settings: &Settings, _bundles: &[Bundle]) -> crate::Result<()> {
+    Ok(())
+  }
+}
+
+/// Runs the artifact hooks for each path of the given bundles.
+pub(crate) fn run_artifact_hooks(
+  hooks: &mut [&mut dyn BundlerHook],
+  settings: &Settings,
+  bundles: &[Bundle],
+) -> crate::Result<()> {
+  for bundle in bundles {
+    for path in &bundle.bundle_paths {
+      for hook in hooks.iter_mut() {
+        hook.artifact(settings, bundle.package_type, path)?;
+      }
+    }
+  }
+  Ok(())
+}
@@ -16,9 +16,10 @@ use handlebars::Handlebars;
 use std::{
   collections::BTreeMap,
   fs::{remove_dir_all, write},
-  path::PathBuf,
+  path::{Path, PathBuf},
   process::{Command, Stdio},
 };
+use tauri_utils::updater::ZSYNC_FILE_EXTENSION;
 
 /// Bundles the project.
 /// Returns a vector of PathBuf that shows where the AppImage was created.
@@ -54,7 +55,29 @@ pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<PathBuf>> {
 
   // setup data to insert into shell script
   let mut sh_map = BTreeMap::new();
-  sh_map.insert("arch", settings.target().split('-').next().unwrap());
+  // the AppImage runtime runs on the target, while linuxdeploy runs on the build host
+  sh_map.insert(
+    "arch",
+    match settings.binary_arch() {
+      "arm" => "armhf",
+      _ => settings.target().split('-').next().unwrap(),
+    },
+  );
+  sh_map.insert(
+    "linuxdeploy_arch",
+    match std::env::consts::ARCH {
+      "x86" => "i386",
+      "arm" => "armhf",
+      other => other,
+    },
+  );
+  let sysroot = settings
+    .appimage()
+    .sysroot
+    .as_ref()
+    .map(|sysroot| sysroot.to_string_lossy())
+    .unwrap_or_else(|| "/".into());
+  sh_map.insert("sysroot", &sysroot);
   sh_map.insert("crate_name", settings.main_binary_name());
   sh_map.insert("appimage_filename", &appimage_filename);
   let tauri_tools_path = dirs::cache_dir().map_or_else(
@@ -105,12 +128,94 @@ pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<PathBuf>> {
     .output()
     .expect("Failed to chmod script");
 
+  let update_information = settings.appimage().update_information.as_deref();
+  if let Some(update_information) = update_information {
+    validate_update_information(update_information)?;
+  }
+
   // execute the shell script to build the appimage.
+  // mksquashfs reads the source date epoch to normalize the filesystem timestamps
+  // and the linuxdeploy AppImage plugin embeds the update information
   Command::new(&sh_file)
-    .current_dir(output_path)
+    .envs(
+      settings
+        .source_date_epoch()
+        .map(|epoch| ("SOURCE_DATE_EPOCH", epoch.to_string())),
+    )
+    .envs(update_information.map(|info| ("LDAI_UPDATE_INFORMATION", info)))
+    .current_dir(&output_path)
     .output_ok()
     .context("error running build_appimage.sh")?;
 
   remove_dir_all(&package_dir)?;
+
+  // the .zsync file is written next to the AppImage but isn't a bundle,
+  // so the updater and the other consumers of the bundle paths don't pick it up
+  if update_information.is_some() {
+    generate_zsync(&appimage_path, &appimage_filename)?;
+  }
   Ok(vec![appimage_path])
 }
+
+/// Checks the update information uses one of the transport formats supported by AppImageUpdate.
+fn validate_update_information(update_information: &str) -> crate::Result<()> {
+  let fields = update_information.split('|').collect::<Vec<_>>();
+  let expected_fields = match fields[0] {
+    "zsync" => 2,
+    "gh-releases-zsync" => 5,
+    _ => return Err(crate::Error::GenericError(format!(
+      "Unsupported AppImage update information `{update_information}`, expected `zsync|<url>` or `gh-releases-zsync|<user>|<repo>|<tag>|<file name>`"
+    ))),
+  };
+  if fields.len() != expected_fields || fields.iter().any(|field| field.is_empty()) {
+    return Err(crate::Error::GenericError(format!(
+      "Invalid AppImage update information `{update_information}`, expected {expected_fields} `|` separated fields"
+    )));
+  }
+  if !fields.last().map_or(false, |file| file.ends_with(".zsync")) {
+    return Err(crate::Error::GenericError(format!(
+      "Invalid AppImage update information `{update_information}`, it must reference the `.zsync` file"
+    )));
+  }
+  Ok(())
+}
+
+/// Returns the `.zsync` file next to the AppImage, generating it with `zsyncmake`
+/// if the AppImage tooling did not.
+fn generate_zsync(appimage_path: &Path, appimage_filename: &str) -> crate::Result<PathBuf> {
+  let zsync_path =
+    appimage_path.with_file_name(format!("{appimage_filename}.{ZSYNC_FILE_EXTENSION}"));
+  if zsync_path.exists() {
+    return Ok(zsync_path);
+  }
+
+  log::info!(action = "Bundling"; "{}", zsync_path.display());
+  Command::new("zsyncmake")
+    // the AppImage URL relative to the .zsync file
+    .arg("-u")
+    .arg(appimage_filename)
+    .arg("-o")
+    .arg(&zsync_path)
+    .arg(appimage_path)
+    .output_ok()
+    .context("failed to generate the AppImage .zsync file, make sure `zsyncmake` is installed")?;
+
+  Ok(zsync_path)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn update_information() {
+    assert!(validate_update_information("zsync|https://example.com/app.AppImage.zsync").is_ok());
+    assert!(validate_update_information(
+      "gh-releases-zsync|tauri-apps|tauri|latest|app_*_amd64.AppImage.zsync"
+    )
+    .is_ok());
+    assert!(validate_update_information("zsync|https://example.com/app.AppImage").is_err());
+    assert!(validate_update_information("gh-releases-zsync|tauri-apps|tauri").is_err());
+    assert!(validate_update_information("bintray-zsync|a|b|c|d.zsync").is_err());
+  }
+}
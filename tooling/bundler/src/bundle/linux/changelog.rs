@@ -0,0 +1,228 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Conversion of a Markdown changelog to the Debian and RPM changelogs.
+//!
+//! The Markdown file follows the [Keep a Changelog] layout: each release is a level 2 heading
+//! such as `## [1.0.0] - 2024-05-01`, followed by its bullet list items.
+//! The level 3 headings grouping the items are dropped and the `Unreleased` section is skipped.
+//!
+//! [Keep a Changelog]: https://keepachangelog.com
+
+use crate::bundle::common::{civil_from_days, days_from_civil};
+
+/// A release of the changelog.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Entry {
+  /// The released version.
+  pub version: String,
+  /// The release date as (year, month, day), if set in the heading.
+  pub date: Option<(i64, u32, u32)>,
+  /// The changes of the release.
+  pub items: Vec<String>,
+}
+
+impl Entry {
+  /// The UNIX timestamp of the release, falling back to the given timestamp without a release date.
+  pub fn timestamp(&self, fallback: u64) -> u64 {
+    self.date.map_or(fallback, |(year, month, day)| {
+      days_from_civil(year, month, day).max(0) as u64 * 86400
+    })
+  }
+}
+
+/// Whether the path looks like a Markdown file.
+pub fn is_markdown(path: &std::path::Path) -> bool {
+  path
+    .extension()
+    .map_or(false, |ext| ext.eq_ignore_ascii_case("md"))
+}
+
+/// Parses the releases of a Markdown changelog, newest first as written in the file.
+pub fn parse(markdown: &str) -> Vec<Entry> {
+  let mut entries = Vec::new();
+  // `None` while outside of a release section, e.g. in the header or `Unreleased` section
+  let mut current: Option<Entry> = None;
+
+  for line in markdown.lines() {
+    let trimmed = line.trim();
+    if let Some(heading) = trimmed.strip_prefix("## ") {
+      entries.extend(current.take());
+      current = parse_heading(heading);
+    } else if trimmed.starts_with("# ") {
+      entries.extend(current.take());
+    } else if let Some(entry) = current.as_mut() {
+      if let Some(item) = trimmed
+        .strip_prefix("- ")
+        .or_else(|| trimmed.strip_prefix("* "))
+      {
+        entry.items.push(item.trim().to_string());
+      } else if !trimmed.is_empty() && !trimmed.starts_with('#') && line.starts_with(' ') {
+        // continuation of the previous item
+        if let Some(item) = entry.items.last_mut() {
+          item.push(' ');
+          item.push_str(trimmed);
+        }
+      }
+    }
+  }
+  entries.extend(current);
+  entries.retain(|entry| !entry.items.is_empty());
+  entries
+}
+
+/// Parses a release heading such as `[1.0.0] - 2024-05-01`.
+fn parse_heading(heading: &str) -> Option<Entry> {
+  let (version, date) = match heading.split_once(" - ") {
+    Some((version, date)) => (version, Some(date)),
+    None => (heading, None),
+  };
+  let version = version
+    .trim()
+    .trim_start_matches('[')
+    .trim_end_matches(']')
+    .trim_start_matches('v');
+  if version.is_empty() || version.eq_ignore_ascii_case("unreleased") {
+    return None;
+  }
+  Some(Entry {
+    version: version.to_string(),
+    date: date.and_then(parse_date),
+    items: Vec::new(),
+  })
+}
+
+fn parse_date(date: &str) -> Option<(i64, u32, u32)> {
+  let mut parts = date.trim().splitn(3, '-');
+  let year = parts.next()?.parse().ok()?;
+  let month = parts.next()?.parse().ok()?;
+  let day = parts.next()?.get(..2)?.parse().ok()?;
+  ((1..=12).contains(&month) && (1..=31).contains(&day)).then_some((year, month, day))
+}
+
+/// Renders the Debian changelog, see <https://www.debian.org/doc/debian-policy/ch-source.html#debian-changelog-debian-changelog>.
+pub fn to_debian(package: &str, maintainer: &str, entries: &[Entry], fallback: u64) -> String {
+  let mut changelog = String::new();
+  for entry in entries {
+    changelog.push_str(&format!(
+      "{package} ({}) unstable; urgency=medium\n\n",
+      entry.version
+    ));
+    for item in &entry.items {
+      changelog.push_str(&format!("  * {item}\n"));
+    }
+    changelog.push_str(&format!(
+      "\n -- {maintainer}  {}\n\n",
+      rfc2822(entry.timestamp(fallback))
+    ));
+  }
+  changelog
+}
+
+/// Renders the content of an RPM changelog entry.
+pub fn to_rpm_content(entry: &Entry) -> String {
+  entry
+    .items
+    .iter()
+    .map(|item| format!("- {item}"))
+    .collect::<Vec<_>>()
+    .join("\n")
+}
+
+/// Formats the UNIX timestamp as an RFC 2822 date in UTC.
+fn rfc2822(timestamp: u64) -> String {
+  const WEEKDAYS: [&str; 7] = ["Thu", "Fri", "Sat", "Sun", "Mon", "Tue", "Wed"];
+  const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+  ];
+  let days = (timestamp / 86400) as i64;
+  let seconds = timestamp % 86400;
+  let (year, month, day) = civil_from_days(days);
+  format!(
+    "{}, {day:02} {} {year} {:02}:{:02}:{:02} +0000",
+    WEEKDAYS[(days % 7) as usize],
+    MONTHS[month as usize - 1],
+    seconds / 3600,
+    seconds / 60 % 60,
+    seconds % 60
+  )
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const CHANGELOG: &str = "# Changelog
+
+All notable changes to this project will be documented in this file.
+
+## [Unreleased]
+
+- Work in progress
+
+## [1.1.0] - 2024-05-01
+
+### Added
+
+- Dark mode
+- A very long change
+  spanning two lines
+
+## 1.0.0
+
+* Initial release
+";
+
+  #[test]
+  fn parse_markdown() {
+    assert_eq!(
+      parse(CHANGELOG),
+      vec![
+        Entry {
+          version: "1.1.0".into(),
+          date: Some((2024, 5, 1)),
+          items: vec![
+            "Dark mode".into(),
+            "A very long change spanning two lines".into()
+          ],
+        },
+        Entry {
+          version: "1.0.0".into(),
+          date: None,
+          items: vec!["Initial release".into()],
+        },
+      ]
+    );
+  }
+
+  #[test]
+  fn debian_changelog() {
+    let changelog = to_debian("app", "Tauri <tauri@example.com>", &parse(CHANGELOG), 0);
+    assert_eq!(
+      changelog,
+      "app (1.1.0) unstable; urgency=medium
+
+  * Dark mode
+  * A very long change spanning two lines
+
+ -- Tauri <tauri@example.com>  Wed, 01 May 2024 00:00:00 +0000
+
+app (1.0.0) unstable; urgency=medium
+
+  * Initial release
+
+ -- Tauri <tauri@example.com>  Thu, 01 Jan 1970 00:00:00 +0000
+
+"
+    );
+  }
+
+  #[test]
+  fn dates() {
+    assert_eq!(
+      rfc2822(1714608000 + 3723),
+      "Thu, 02 May 2024 01:02:03 +0000"
+    );
+  }
+}
@@ -18,22 +18,33 @@
 //         usr/share/icons/hicolor/...               # Icon files (for apps)
 //         usr/lib/foobar/...                        # Other resource files
 //
+// With the `opt` install prefix, the binary and resource files are installed to
+// opt/foobar/bin/ and opt/foobar/lib/foobar/ instead.
+//
 // For cargo-bundle, we put bundle resource files under /usr/lib/package_name/,
 // and then generate the desktop file and control file from the bundle
-// metadata, as well as generating the md5sums file.  Currently we do not
-// generate postinst or prerm files.
+// metadata, as well as generating the md5sums file.  The postinst and prerm
+// files are generated to enable the systemd user service, if any.
 
-use super::{super::common, freedesktop};
+use super::{
+  super::common,
+  changelog, freedesktop,
+  service::{self, Script, ScriptFormat},
+};
 use crate::Settings;
 use anyhow::Context;
 use flate2::{write::GzEncoder, Compression};
 use tar::HeaderMode;
+use tauri_utils::config::LinuxInstallPrefix;
 use walkdir::WalkDir;
 
 use std::{
   fs::{self, File, OpenOptions},
   io::{self, Write},
-  os::unix::fs::{MetadataExt, OpenOptionsExt},
+  os::unix::{
+    ffi::OsStrExt,
+    fs::{MetadataExt, OpenOptionsExt},
+  },
   path::{Path, PathBuf},
 };
 
@@ -66,10 +77,13 @@ pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<PathBuf>> {
 
   log::info!(action = "Bundling"; "{} ({})", package_name, package_path.display());
 
-  let (data_dir, _) = generate_data(settings, &package_dir)
+  let install_prefix = settings.deb().install_prefix;
+  let (data_dir, _) = generate_data_with_prefix(settings, &package_dir, install_prefix)
     .with_context(|| "Failed to build data folders and files")?;
   common::copy_custom_files(&settings.deb().files, &data_dir)
     .with_context(|| "Failed to copy custom files")?;
+  service::generate_service_files(settings, &bin_dir(settings, install_prefix), &data_dir)
+    .with_context(|| "Failed to create service files")?;
 
   // Generate control files.
   let control_dir = package_dir.join("control");
@@ -85,26 +99,38 @@ pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<PathBuf>> {
     .with_context(|| "Failed to create debian-binary file")?;
 
   // Apply tar/gzip/ar to create the final package file.
-  let control_tar_gz_path =
-    tar_and_gzip_dir(control_dir).with_context(|| "Failed to tar/gzip control directory")?;
-  let data_tar_gz_path =
-    tar_and_gzip_dir(data_dir).with_context(|| "Failed to tar/gzip data directory")?;
+  let source_date_epoch = settings.source_date_epoch();
+  let control_tar_gz_path = tar_and_gzip_dir(control_dir, source_date_epoch)
+    .with_context(|| "Failed to tar/gzip control directory")?;
+  let data_tar_gz_path = tar_and_gzip_dir(data_dir, source_date_epoch)
+    .with_context(|| "Failed to tar/gzip data directory")?;
   create_archive(
     vec![debian_binary_path, control_tar_gz_path, data_tar_gz_path],
     &package_path,
+    source_date_epoch,
   )
   .with_context(|| "Failed to create package archive")?;
   Ok(vec![package_path])
 }
 
-/// Generate the debian data folders and files.
+/// Generate the debian data folders and files, installed to `/usr`.
 pub fn generate_data(
   settings: &Settings,
   package_dir: &Path,
+) -> crate::Result<(PathBuf, Vec<freedesktop::Icon>)> {
+  generate_data_with_prefix(settings, package_dir, LinuxInstallPrefix::Usr)
+}
+
+/// Generate the debian data folders and files, with the binaries and resources installed to the given prefix.
+pub fn generate_data_with_prefix(
+  settings: &Settings,
+  package_dir: &Path,
+  install_prefix: LinuxInstallPrefix,
 ) -> crate::Result<(PathBuf, Vec<freedesktop::Icon>)> {
   // Generate data files.
   let data_dir = package_dir.join("data");
-  let bin_dir = data_dir.join("usr/bin");
+  let bin_install_dir = bin_dir(settings, install_prefix);
+  let bin_dir = data_dir.join(bin_install_dir.strip_prefix("/")?);
 
   for bin in settings.binaries() {
     let bin_path = settings.binary_path(bin);
@@ -112,7 +138,9 @@ pub fn generate_data(
       .with_context(|| format!("Failed to copy binary from {bin_path:?}"))?;
   }
 
-  copy_resource_files(settings, &data_dir).with_context(|| "Failed to copy resource files")?;
+  settings
+    .copy_resources(&data_dir.join(resource_dir(settings, install_prefix).strip_prefix("/")?))
+    .with_context(|| "Failed to copy resource files")?;
 
   settings
     .copy_binaries(&bin_dir)
@@ -120,25 +148,74 @@ pub fn generate_data(
 
   let icons = freedesktop::copy_icon_files(settings, &data_dir)
     .with_context(|| "Failed to create icon files")?;
-  freedesktop::generate_desktop_file(settings, &settings.deb().desktop_template, &data_dir)
-    .with_context(|| "Failed to create desktop file")?;
+  freedesktop::generate_desktop_file(
+    settings,
+    &settings.deb().desktop_template,
+    &desktop_exec(settings, install_prefix),
+    &data_dir,
+  )
+  .with_context(|| "Failed to create desktop file")?;
   generate_changelog_file(settings, &data_dir)
     .with_context(|| "Failed to create changelog.gz file")?;
 
   Ok((data_dir, icons))
 }
 
+/// The absolute directory of the binaries for the install prefix.
+pub fn bin_dir(settings: &Settings, install_prefix: LinuxInstallPrefix) -> PathBuf {
+  match install_prefix {
+    LinuxInstallPrefix::Usr => PathBuf::from("/usr/bin"),
+    LinuxInstallPrefix::Opt => Path::new("/opt")
+      .join(settings.main_binary_name())
+      .join("bin"),
+  }
+}
+
+/// The absolute directory of the resources for the install prefix,
+/// matching the lookup of `tauri_utils::platform::resource_dir`.
+pub fn resource_dir(settings: &Settings, install_prefix: LinuxInstallPrefix) -> PathBuf {
+  let bin_name = settings.main_binary_name();
+  match install_prefix {
+    LinuxInstallPrefix::Usr => Path::new("/usr/lib").join(bin_name),
+    LinuxInstallPrefix::Opt => Path::new("/opt").join(bin_name).join("lib").join(bin_name),
+  }
+}
+
+/// The `Exec` value of the desktop entry: the binary name when it is on the `PATH`, its absolute path otherwise.
+pub fn desktop_exec(settings: &Settings, install_prefix: LinuxInstallPrefix) -> String {
+  match install_prefix {
+    LinuxInstallPrefix::Usr => settings.main_binary_name().to_string(),
+    LinuxInstallPrefix::Opt => bin_dir(settings, install_prefix)
+      .join(settings.main_binary_name())
+      .to_string_lossy()
+      .into_owned(),
+  }
+}
+
 /// Generate the Changelog file by compressing, to be stored at /usr/share/doc/package-name/changelog.gz. See
 /// <https://www.debian.org/doc/debian-policy/ch-docs.html#changelog-files-and-release-notes>
+///
+/// A Markdown changelog is converted to the Debian changelog format first.
 fn generate_changelog_file(settings: &Settings, data_dir: &Path) -> crate::Result<()> {
   if let Some(changelog_src_path) = &settings.deb().changelog {
-    let mut src_file = File::open(changelog_src_path)?;
+    let contents = if changelog::is_markdown(changelog_src_path) {
+      let markdown = fs::read_to_string(changelog_src_path)?;
+      changelog::to_debian(
+        &heck::AsKebabCase(settings.product_name()).to_string(),
+        &settings.authors_comma_separated().unwrap_or_default(),
+        &changelog::parse(&markdown),
+        common::build_timestamp(settings.source_date_epoch()),
+      )
+      .into_bytes()
+    } else {
+      fs::read(changelog_src_path)?
+    };
     let bin_name = settings.main_binary_name();
     let dest_path = data_dir.join(format!("usr/share/doc/{}/changelog.gz", bin_name));
 
     let changelog_file = common::create_file(&dest_path)?;
     let mut gzip_encoder = GzEncoder::new(changelog_file, Compression::new(9));
-    io::copy(&mut src_file, &mut gzip_encoder)?;
+    gzip_encoder.write_all(&contents)?;
 
     let mut changelog_file = gzip_encoder.finish()?;
     changelog_file.flush()?;
@@ -237,14 +314,22 @@ fn generate_scripts(settings: &Settings, control_dir: &Path) -> crate::Result<()
     create_script_file_from_path(script_path, &dest_path)?
   }
 
-  if let Some(script_path) = &settings.deb().post_install_script {
-    let dest_path = control_dir.join("postinst");
-    create_script_file_from_path(script_path, &dest_path)?
+  if let Some(script) = service::maintainer_script(
+    settings,
+    ScriptFormat::Deb,
+    Script::PostInstall,
+    settings.deb().post_install_script.as_deref(),
+  )? {
+    create_script_file(&script, &control_dir.join("postinst"))?
   }
 
-  if let Some(script_path) = &settings.deb().pre_remove_script {
-    let dest_path = control_dir.join("prerm");
-    create_script_file_from_path(script_path, &dest_path)?
+  if let Some(script) = service::maintainer_script(
+    settings,
+    ScriptFormat::Deb,
+    Script::PreRemove,
+    settings.deb().pre_remove_script.as_deref(),
+  )? {
+    create_script_file(&script, &control_dir.join("prerm"))?
   }
 
   if let Some(script_path) = &settings.deb().post_remove_script {
@@ -266,12 +351,23 @@ fn create_script_file_from_path(from: &PathBuf, to: &PathBuf) -> crate::Result<(
   Ok(())
 }
 
+fn create_script_file(script: &str, to: &Path) -> crate::Result<()> {
+  let mut file = OpenOptions::new()
+    .create(true)
+    .truncate(true)
+    .write(true)
+    .mode(0o755)
+    .open(to)?;
+  file.write_all(script.as_bytes())?;
+  Ok(())
+}
+
 /// Create an `md5sums` file in the `control_dir` containing the MD5 checksums
 /// for each file within the `data_dir`.
 fn generate_md5sums(control_dir: &Path, data_dir: &Path) -> crate::Result<()> {
   let md5sums_path = control_dir.join("md5sums");
   let mut md5sums_file = common::create_file(&md5sums_path)?;
-  for entry in WalkDir::new(data_dir) {
+  for entry in WalkDir::new(data_dir).sort_by_file_name() {
     let entry = entry?;
     let path = entry.path();
     if path.is_dir() {
@@ -293,13 +389,6 @@ fn generate_md5sums(control_dir: &Path, data_dir: &Path) -> crate::Result<()> {
   Ok(())
 }
 
-/// Copy the bundle's resource files into an appropriate directory under the
-/// `data_dir`.
-fn copy_resource_files(settings: &Settings, data_dir: &Path) -> crate::Result<()> {
-  let resource_dir = data_dir.join("usr/lib").join(settings.main_binary_name());
-  settings.copy_resources(&resource_dir)
-}
-
 /// Create an empty file at the given path, creating any parent directories as
 /// needed, then write `data` into the file.
 fn create_file_with_data<P: AsRef<Path>>(path: P, data: &str) -> crate::Result<()> {
@@ -320,10 +409,16 @@ fn total_dir_size(dir: &Path) -> crate::Result<u64> {
 }
 
 /// Writes a tar file to the given writer containing the given directory.
-fn create_tar_from_dir<P: AsRef<Path>, W: Write>(src_dir: P, dest_file: W) -> crate::Result<W> {
+///
+/// The modification times are clamped to the source date epoch if set.
+fn create_tar_from_dir<P: AsRef<Path>, W: Write>(
+  src_dir: P,
+  dest_file: W,
+  source_date_epoch: Option<u64>,
+) -> crate::Result<W> {
   let src_dir = src_dir.as_ref();
   let mut tar_builder = tar::Builder::new(dest_file);
-  for entry in WalkDir::new(src_dir) {
+  for entry in WalkDir::new(src_dir).sort_by_file_name() {
     let entry = entry?;
     let src_path = entry.path();
     if src_path == src_dir {
@@ -333,7 +428,8 @@ fn create_tar_from_dir<P: AsRef<Path>, W: Write>(src_dir: P, dest_file: W) -> cr
     let stat = fs::metadata(src_path)?;
     let mut header = tar::Header::new_gnu();
     header.set_metadata_in_mode(&stat, HeaderMode::Deterministic);
-    header.set_mtime(stat.mtime() as u64);
+    let mtime = stat.mtime() as u64;
+    header.set_mtime(source_date_epoch.map_or(mtime, |epoch| mtime.min(epoch)));
 
     if entry.file_type().is_dir() {
       tar_builder.append_data(&mut header, dest_path, &mut io::empty())?;
@@ -349,12 +445,15 @@ fn create_tar_from_dir<P: AsRef<Path>, W: Write>(src_dir: P, dest_file: W) -> cr
 /// Creates a `.tar.gz` file from the given directory (placing the new file
 /// within the given directory's parent directory), then deletes the original
 /// directory and returns the path to the new file.
-fn tar_and_gzip_dir<P: AsRef<Path>>(src_dir: P) -> crate::Result<PathBuf> {
+fn tar_and_gzip_dir<P: AsRef<Path>>(
+  src_dir: P,
+  source_date_epoch: Option<u64>,
+) -> crate::Result<PathBuf> {
   let src_dir = src_dir.as_ref();
   let dest_path = src_dir.with_extension("tar.gz");
   let dest_file = common::create_file(&dest_path)?;
   let gzip_encoder = GzEncoder::new(dest_file, Compression::default());
-  let gzip_encoder = create_tar_from_dir(src_dir, gzip_encoder)?;
+  let gzip_encoder = create_tar_from_dir(src_dir, gzip_encoder, source_date_epoch)?;
   let mut dest_file = gzip_encoder.finish()?;
   dest_file.flush()?;
   Ok(dest_path)
@@ -362,10 +461,26 @@ fn tar_and_gzip_dir<P: AsRef<Path>>(src_dir: P) -> crate::Result<PathBuf> {
 
 /// Creates an `ar` archive from the given source files and writes it to the
 /// given destination path.
-fn create_archive(srcs: Vec<PathBuf>, dest: &Path) -> crate::Result<()> {
+///
+/// With a source date epoch, the member headers do not depend on the build host
+/// and the modification time is set to the epoch.
+fn create_archive(
+  srcs: Vec<PathBuf>,
+  dest: &Path,
+  source_date_epoch: Option<u64>,
+) -> crate::Result<()> {
   let mut builder = ar::Builder::new(common::create_file(dest)?);
   for path in &srcs {
-    builder.append_path(path)?;
+    if let Some(epoch) = source_date_epoch {
+      let file = File::open(path)?;
+      let name = path.file_name().expect("archive member has no file name");
+      let mut header = ar::Header::new(name.as_bytes().to_vec(), file.metadata()?.len());
+      header.set_mtime(epoch);
+      header.set_mode(0o100644);
+      builder.append(&header, file)?;
+    } else {
+      builder.append_path(path)?;
+    }
   }
   builder.into_inner()?.flush()?;
   Ok(())
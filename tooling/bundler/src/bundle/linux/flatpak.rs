@@ -0,0 +1,130 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+// The Flatpak bundle is built with `flatpak-builder` from a generated manifest:
+//
+// bundle/flatpak/
+//     com.tauri.dev.json                   # Flatpak manifest
+//     data/usr/...                         # Same file hierarchy as the debian data, installed to /app
+//     repo/                                # OSTree repository exported by flatpak-builder
+//     foobar_1.2.3_x86_64.flatpak          # Single-file bundle exported from the repository
+//
+// The `data/usr` folder is copied to `/app`, so the binary is at `/app/bin/foobar` and the
+// resources at `/app/lib/foobar`, which is where the Tauri runtime looks for them in a Flatpak sandbox.
+
+use super::{
+  super::common::{self, CommandExt},
+  debian,
+};
+use crate::Settings;
+use anyhow::Context;
+use serde_json::json;
+use std::{
+  fs,
+  path::{Path, PathBuf},
+  process::Command,
+};
+
+/// Bundles the project.
+/// Returns a vector of PathBuf that shows where the Flatpak was created.
+pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<PathBuf>> {
+  let arch = match settings.binary_arch() {
+    "x86" => "i386",
+    other => other,
+  };
+  let app_id = settings.bundle_identifier();
+  let package_base_name = format!(
+    "{}_{}_{}",
+    settings.product_name(),
+    settings.version_string(),
+    arch
+  );
+  let bundle_name = format!("{package_base_name}.flatpak");
+
+  let base_dir = settings.project_out_directory().join("bundle/flatpak");
+  if base_dir.exists() {
+    fs::remove_dir_all(&base_dir).with_context(|| "Failed to remove old flatpak bundle")?;
+  }
+  let bundle_path = base_dir.join(&bundle_name);
+
+  log::info!(action = "Bundling"; "{} ({})", bundle_name, bundle_path.display());
+
+  let (data_dir, _) = debian::generate_data(settings, &base_dir)
+    .with_context(|| "Failed to build data folders and files")?;
+  common::copy_custom_files(&settings.flatpak().files, &data_dir.join("usr"))
+    .with_context(|| "Failed to copy custom files")?;
+
+  let manifest_path = base_dir.join(format!("{app_id}.json"));
+  generate_manifest(settings, &manifest_path).with_context(|| "Failed to create manifest")?;
+
+  let repo_dir = base_dir.join("repo");
+  let build_dir = base_dir.join("build");
+
+  Command::new("flatpak-builder")
+    .arg("--force-clean")
+    .arg("--disable-rofiles-fuse")
+    .arg(format!("--arch={arch}"))
+    .arg(format!("--repo={}", repo_dir.display()))
+    .arg(&build_dir)
+    .arg(&manifest_path)
+    .current_dir(&base_dir)
+    .output_ok()
+    .with_context(|| {
+      format!(
+        "failed to run flatpak-builder, make sure it is installed along the {} {} runtime and SDK",
+        settings.flatpak().runtime,
+        settings.flatpak().runtime_version
+      )
+    })?;
+
+  Command::new("flatpak")
+    .arg("build-bundle")
+    .arg(format!("--arch={arch}"))
+    .arg(&repo_dir)
+    .arg(&bundle_path)
+    .arg(app_id)
+    .current_dir(&base_dir)
+    .output_ok()
+    .context("failed to export the flatpak bundle")?;
+
+  fs::remove_dir_all(&build_dir)?;
+  fs::remove_dir_all(data_dir)?;
+
+  Ok(vec![bundle_path])
+}
+
+/// Generates the flatpak-builder manifest.
+fn generate_manifest(settings: &Settings, manifest_path: &Path) -> crate::Result<()> {
+  let flatpak = settings.flatpak();
+  let bin_name = settings.main_binary_name();
+
+  let manifest = json!({
+    "id": settings.bundle_identifier(),
+    "runtime": flatpak.runtime,
+    "runtime-version": flatpak.runtime_version,
+    "sdk": flatpak.sdk,
+    "command": bin_name,
+    // the desktop file and icons are named after the binary, flatpak requires the app identifier
+    "rename-desktop-file": format!("{bin_name}.desktop"),
+    "rename-icon": bin_name,
+    "finish-args": flatpak.finish_args,
+    "modules": [
+      {
+        "name": bin_name,
+        "buildsystem": "simple",
+        "build-commands": ["cp -a usr/. /app/"],
+        "sources": [
+          {
+            "type": "dir",
+            "path": "data"
+          }
+        ]
+      }
+    ]
+  });
+
+  let file = common::create_file(manifest_path)?;
+  serde_json::to_writer_pretty(file, &manifest)?;
+  Ok(())
+}
@@ -89,11 +89,13 @@ pub fn copy_icon_files(settings: &Settings, data_dir: &Path) -> crate::Result<Ve
 }
 
 /// Generate the application desktop file and store it under the `data_dir`.
+/// `exec` is the command launching the app.
 /// Returns the path of the resulting file (source path) and the destination
 /// path in the package.
 pub fn generate_desktop_file(
   settings: &Settings,
   custom_template_path: &Option<PathBuf>,
+  exec: &str,
   data_dir: &Path,
 ) -> crate::Result<(PathBuf, PathBuf)> {
   let bin_name = settings.main_binary_name();
@@ -159,7 +161,7 @@ pub fn generate_desktop_file(
       } else {
         None
       },
-      exec: bin_name,
+      exec,
       icon: bin_name,
       name: settings.product_name(),
       mime_type,
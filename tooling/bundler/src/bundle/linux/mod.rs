@@ -4,6 +4,10 @@
 // SPDX-License-Identifier: MIT
 
 pub mod appimage;
+pub mod changelog;
 pub mod debian;
+pub mod flatpak;
 pub mod freedesktop;
 pub mod rpm;
+pub mod service;
+pub mod snap;
@@ -10,10 +10,16 @@ use rpm::{self, signature::pgp, Dependency, FileMode, FileOptions};
 use std::{
   env,
   fs::{self, File},
-  path::{Path, PathBuf},
+  path::PathBuf,
 };
 
-use super::freedesktop;
+use super::{
+  super::common,
+  changelog,
+  debian::{bin_dir, desktop_exec, resource_dir},
+  freedesktop,
+  service::{self, Script, ScriptFormat},
+};
 
 /// Bundles the project.
 /// Returns a vector of PathBuf that shows where the RPM was created.
@@ -52,6 +58,12 @@ pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<PathBuf>> {
     // This matches .deb compression. On a 240MB source binary the bundle will be 100KB larger than rpm's default while reducing build times by ~25%.
     .compression(rpm::CompressionWithLevel::Gzip(6));
 
+  if let Some(epoch) = settings.source_date_epoch() {
+    // also clamps the file modification times
+    let source_date = u32::try_from(epoch).context("source date epoch out of range for rpm")?;
+    builder = builder.source_date(source_date).build_host("localhost");
+  }
+
   if let Some(description) = settings.long_description() {
     builder = builder.description(description);
   }
@@ -60,9 +72,26 @@ pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<PathBuf>> {
     builder = builder.url(homepage);
   }
 
+  // Add changelog entries
+  if let Some(changelog_path) = &settings.rpm().changelog {
+    let markdown = fs::read_to_string(changelog_path)
+      .with_context(|| format!("Failed to read changelog {}", changelog_path.display()))?;
+    let authors = settings.authors_comma_separated().unwrap_or_default();
+    let fallback = common::build_timestamp(settings.source_date_epoch());
+    for entry in changelog::parse(&markdown) {
+      let timestamp =
+        u32::try_from(entry.timestamp(fallback)).context("changelog date out of range for rpm")?;
+      builder = builder.add_changelog_entry(
+        format!("{authors} - {}", entry.version),
+        changelog::to_rpm_content(&entry),
+        timestamp,
+      );
+    }
+  }
+
   // Add requirements
   for dep in settings.rpm().depends.as_ref().cloned().unwrap_or_default() {
-    builder = builder.requires(Dependency::any(dep));
+    builder = builder.requires(dependency(&dep));
   }
 
   // Add provides
@@ -73,7 +102,7 @@ pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<PathBuf>> {
     .cloned()
     .unwrap_or_default()
   {
-    builder = builder.provides(Dependency::any(dep));
+    builder = builder.provides(dependency(&dep));
   }
 
   // Add conflicts
@@ -84,7 +113,7 @@ pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<PathBuf>> {
     .cloned()
     .unwrap_or_default()
   {
-    builder = builder.conflicts(Dependency::any(dep));
+    builder = builder.conflicts(dependency(&dep));
   }
 
   // Add obsoletes
@@ -95,20 +124,23 @@ pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<PathBuf>> {
     .cloned()
     .unwrap_or_default()
   {
-    builder = builder.obsoletes(Dependency::any(dep));
+    builder = builder.obsoletes(dependency(&dep));
   }
 
+  let install_prefix = settings.rpm().install_prefix;
+  let bin_dir = bin_dir(settings, install_prefix);
+
   // Add binaries
   for bin in settings.binaries() {
     let src = settings.binary_path(bin);
-    let dest = Path::new("/usr/bin").join(bin.name());
+    let dest = bin_dir.join(bin.name());
     builder = builder.with_file(src, FileOptions::new(dest.to_string_lossy()))?;
   }
 
   // Add external binaries
   for src in settings.external_binaries() {
     let src = src?;
-    let dest = Path::new("/usr/bin").join(
+    let dest = bin_dir.join(
       src
         .file_name()
         .expect("failed to extract external binary filename")
@@ -124,13 +156,21 @@ pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<PathBuf>> {
     builder = builder.pre_install_script(script);
   }
 
-  if let Some(script_path) = &settings.rpm().post_install_script {
-    let script = fs::read_to_string(script_path)?;
+  if let Some(script) = service::maintainer_script(
+    settings,
+    ScriptFormat::Rpm,
+    Script::PostInstall,
+    settings.rpm().post_install_script.as_deref(),
+  )? {
     builder = builder.post_install_script(script);
   }
 
-  if let Some(script_path) = &settings.rpm().pre_remove_script {
-    let script = fs::read_to_string(script_path)?;
+  if let Some(script) = service::maintainer_script(
+    settings,
+    ScriptFormat::Rpm,
+    Script::PreRemove,
+    settings.rpm().pre_remove_script.as_deref(),
+  )? {
     builder = builder.pre_uninstall_script(script);
   }
 
@@ -141,12 +181,12 @@ pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<PathBuf>> {
 
   // Add resources
   if settings.resource_files().count() > 0 {
-    let resource_dir = Path::new("/usr/lib").join(settings.main_binary_name());
+    let resource_dir = resource_dir(settings, install_prefix);
     // Create an empty file, needed to add a directory to the RPM package
     // (cf https://github.com/rpm-rs/rpm/issues/177)
     let empty_file_path = &package_dir.join("empty");
     File::create(empty_file_path)?;
-    // Then add the resource directory `/usr/lib/<binary_name>` to the package.
+    // Then add the resource directory, e.g. `/usr/lib/<binary_name>`, to the package.
     builder = builder.with_file(
       empty_file_path,
       FileOptions::new(resource_dir.to_string_lossy()).mode(FileMode::Dir { permissions: 0o755 }),
@@ -160,13 +200,22 @@ pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<PathBuf>> {
   }
 
   // Add Desktop entry file
-  let (desktop_src_path, desktop_dest_path) =
-    freedesktop::generate_desktop_file(settings, &settings.rpm().desktop_template, &package_dir)?;
+  let (desktop_src_path, desktop_dest_path) = freedesktop::generate_desktop_file(
+    settings,
+    &settings.rpm().desktop_template,
+    &desktop_exec(settings, install_prefix),
+    &package_dir,
+  )?;
   builder = builder.with_file(
     desktop_src_path,
     FileOptions::new(desktop_dest_path.to_string_lossy()),
   )?;
 
+  // Add the systemd user service and the D-Bus activation file
+  for (src, dest) in service::generate_service_files(settings, &bin_dir, &package_dir)? {
+    builder = builder.with_file(src, FileOptions::new(dest.to_string_lossy()))?;
+  }
+
   // Add icons
   for (icon, src) in &freedesktop::list_icon_files(settings, &PathBuf::from("/"))? {
     builder = builder.with_file(src, FileOptions::new(icon.path.to_string_lossy()))?;
@@ -203,3 +252,15 @@ pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<PathBuf>> {
 
   Ok(vec![package_path])
 }
+
+/// Parses a dependency such as `libfoo >= 1.0`, validated by the CLI.
+fn dependency(dep: &str) -> Dependency {
+  match tauri_utils::config::parse_rpm_dependency(dep) {
+    Some((name, Some(("<", version)))) => Dependency::less(name, version),
+    Some((name, Some(("<=", version)))) => Dependency::less_eq(name, version),
+    Some((name, Some(("=", version)))) => Dependency::eq(name, version),
+    Some((name, Some((">=", version)))) => Dependency::greater_eq(name, version),
+    Some((name, Some((">", version)))) => Dependency::greater(name, version),
+    _ => Dependency::any(dep),
+  }
+}
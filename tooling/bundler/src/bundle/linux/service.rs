@@ -0,0 +1,292 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Systemd user service and D-Bus activation files of the Debian and RPM packages.
+//!
+//! - The [systemd unit] is installed to `/usr/lib/systemd/user/<name>.service`.
+//! - The [D-Bus activation file] is installed to `/usr/share/dbus-1/services/<bus name>.service`,
+//!   and hands the activation over to the systemd unit.
+//!
+//! The maintainer scripts enable the unit globally after the installation and disable it before the removal.
+//!
+//! [systemd unit]: https://www.freedesktop.org/software/systemd/man/latest/systemd.service.html
+//! [D-Bus activation file]: https://dbus.freedesktop.org/doc/dbus-specification.html#message-bus-starting-services
+
+use std::{
+  fs,
+  path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+use handlebars::Handlebars;
+use serde::Serialize;
+use tauri_utils::config::{LinuxServiceConfig, LinuxServiceRestart};
+
+use crate::{bundle::common, Settings};
+
+/// The package format the maintainer scripts are generated for,
+/// as Debian and RPM pass different arguments to the scripts.
+#[derive(Clone, Copy)]
+pub enum ScriptFormat {
+  Deb,
+  Rpm,
+}
+
+/// A maintainer script of the package.
+#[derive(Clone, Copy)]
+pub enum Script {
+  PostInstall,
+  PreRemove,
+}
+
+/// The generated service files, as (source path, destination path in the package) pairs.
+///
+/// `bin_dir` is the absolute directory the main binary is installed to.
+pub fn generate_service_files(
+  settings: &Settings,
+  bin_dir: &Path,
+  data_dir: &Path,
+) -> crate::Result<Vec<(PathBuf, PathBuf)>> {
+  let Some(service) = settings.linux_service() else {
+    return Ok(Vec::new());
+  };
+
+  let unit = unit_name(settings, service)?;
+  let bus_name = service.dbus_name.as_deref();
+  if let Some(bus_name) = bus_name {
+    if !is_valid_bus_name(bus_name) {
+      return Err(crate::Error::GenericError(format!(
+        "Invalid D-Bus name `{bus_name}`, expected at least two dot-separated elements such as `com.tauri.example`"
+      )));
+    }
+  }
+
+  let binary = bin_dir.join(settings.main_binary_name());
+  let exec = std::iter::once(binary.to_string_lossy().into_owned())
+    .chain(service.args.iter().cloned())
+    .collect::<Vec<_>>();
+
+  let mut environment = service
+    .environment
+    .iter()
+    .map(|(key, value)| systemd_quote(&format!("{key}={value}")))
+    .collect::<Vec<_>>();
+  environment.sort();
+
+  let mut handlebars = Handlebars::new();
+  handlebars.register_escape_fn(handlebars::no_escape);
+  handlebars
+    .register_template_string(
+      "systemd.service",
+      include_str!("./templates/systemd.service"),
+    )
+    .with_context(|| "Failed to setup systemd unit template")?;
+  handlebars
+    .register_template_string("dbus.service", include_str!("./templates/dbus.service"))
+    .with_context(|| "Failed to setup D-Bus service template")?;
+
+  #[derive(Serialize)]
+  struct ServiceTemplateParams<'a> {
+    description: &'a str,
+    unit: &'a str,
+    bus_name: Option<&'a str>,
+    exec: String,
+    restart: &'static str,
+    environment: Vec<String>,
+  }
+
+  let mut files = Vec::new();
+
+  let unit_path = PathBuf::from("usr/lib/systemd/user").join(&unit);
+  let file_path = data_dir.join(&unit_path);
+  handlebars.render_to_write(
+    "systemd.service",
+    &ServiceTemplateParams {
+      description: service
+        .description
+        .as_deref()
+        .unwrap_or_else(|| settings.product_name()),
+      unit: &unit,
+      bus_name,
+      exec: exec
+        .iter()
+        .map(|arg| systemd_quote(arg))
+        .collect::<Vec<_>>()
+        .join(" "),
+      restart: match service.restart {
+        LinuxServiceRestart::No => "no",
+        LinuxServiceRestart::OnFailure => "on-failure",
+        LinuxServiceRestart::Always => "always",
+      },
+      environment,
+    },
+    common::create_file(&file_path)?,
+  )?;
+  files.push((file_path, Path::new("/").join(unit_path)));
+
+  if let Some(bus_name) = bus_name {
+    let activation_path =
+      PathBuf::from("usr/share/dbus-1/services").join(format!("{bus_name}.service"));
+    let file_path = data_dir.join(&activation_path);
+    handlebars.render_to_write(
+      "dbus.service",
+      &ServiceTemplateParams {
+        description: "",
+        unit: &unit,
+        bus_name: Some(bus_name),
+        exec: exec
+          .iter()
+          .map(|arg| quote(arg))
+          .collect::<Vec<_>>()
+          .join(" "),
+        restart: "",
+        environment: Vec::new(),
+      },
+      common::create_file(&file_path)?,
+    )?;
+    files.push((file_path, Path::new("/").join(activation_path)));
+  }
+
+  Ok(files)
+}
+
+/// The maintainer script of the package: the service snippet followed by the user script, if any.
+///
+/// Returns `None` if there is neither a service to enable nor a user script.
+pub fn maintainer_script(
+  settings: &Settings,
+  format: ScriptFormat,
+  script: Script,
+  user_script: Option<&Path>,
+) -> crate::Result<Option<String>> {
+  let user_script = user_script
+    .map(|path| {
+      fs::read_to_string(path)
+        .with_context(|| format!("Failed to read maintainer script {}", path.display()))
+    })
+    .transpose()?;
+
+  let snippet = match settings.linux_service() {
+    Some(service) if service.autostart => Some(service_snippet(
+      &unit_name(settings, service)?,
+      format,
+      script,
+    )),
+    _ => None,
+  };
+
+  Ok(match (snippet, user_script) {
+    (None, user_script) => user_script,
+    (Some(snippet), None) => Some(format!("#!/bin/sh\n{snippet}")),
+    (Some(snippet), Some(user_script)) => Some(with_snippet(&user_script, &snippet)),
+  })
+}
+
+/// Inserts the snippet after the shebang of the user script,
+/// so it runs even if the user script exits early.
+fn with_snippet(user_script: &str, snippet: &str) -> String {
+  match user_script.strip_prefix("#!") {
+    Some(rest) => {
+      let (shebang, body) = rest.split_once('\n').unwrap_or((rest, ""));
+      format!("#!{shebang}\n{snippet}{body}")
+    }
+    None => format!("#!/bin/sh\n{snippet}{user_script}"),
+  }
+}
+
+/// The shell snippet enabling or disabling the unit for all users.
+///
+/// The unit is only disabled when the package is removed, not when it is upgraded.
+fn service_snippet(unit: &str, format: ScriptFormat, script: Script) -> String {
+  let (condition, action) = match (format, script) {
+    (ScriptFormat::Deb, Script::PostInstall) => (r#"[ "$1" = "configure" ]"#, "enable"),
+    (ScriptFormat::Deb, Script::PreRemove) => (r#"[ "$1" = "remove" ]"#, "disable"),
+    (ScriptFormat::Rpm, Script::PostInstall) => (r#"[ "$1" -eq 1 ]"#, "enable"),
+    (ScriptFormat::Rpm, Script::PreRemove) => (r#"[ "$1" -eq 0 ]"#, "disable"),
+  };
+  format!(
+    "if {condition} && command -v systemctl >/dev/null 2>&1; then\n  systemctl --global {action} {unit} >/dev/null 2>&1 || true\nfi\n"
+  )
+}
+
+/// The unit file name, e.g. `app.service`.
+fn unit_name(settings: &Settings, service: &LinuxServiceConfig) -> crate::Result<String> {
+  let name = service
+    .name
+    .as_deref()
+    .unwrap_or_else(|| settings.main_binary_name());
+  let is_valid = !name.is_empty()
+    && name
+      .chars()
+      .all(|c| c.is_ascii_alphanumeric() || ":-_.\\".contains(c));
+  if !is_valid {
+    return Err(crate::Error::GenericError(format!(
+      "Invalid systemd unit name `{name}`, only ASCII letters, digits and `:-_.\\` are allowed"
+    )));
+  }
+  Ok(format!("{name}.service"))
+}
+
+/// Whether the name is a valid well-known D-Bus name.
+fn is_valid_bus_name(name: &str) -> bool {
+  let elements = name.split('.').collect::<Vec<_>>();
+  name.len() <= 255
+    && elements.len() >= 2
+    && elements.iter().all(|element| {
+      !element.is_empty()
+        && !element.starts_with(|c: char| c.is_ascii_digit())
+        && element
+          .chars()
+          .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    })
+}
+
+/// Quotes the argument if it contains whitespace or quotes.
+fn quote(arg: &str) -> String {
+  if arg.is_empty() || arg.contains(|c: char| c.is_whitespace() || c == '"' || c == '\\') {
+    format!("\"{}\"", arg.replace('\\', "\\\\").replace('"', "\\\""))
+  } else {
+    arg.to_string()
+  }
+}
+
+/// Quotes the argument for systemd, which also expands `%` specifiers.
+fn systemd_quote(arg: &str) -> String {
+  quote(&arg.replace('%', "%%"))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn bus_names() {
+    assert!(is_valid_bus_name("com.tauri.example"));
+    assert!(is_valid_bus_name("org.my-app"));
+    assert!(!is_valid_bus_name("example"));
+    assert!(!is_valid_bus_name("com..example"));
+    assert!(!is_valid_bus_name("com.1example"));
+  }
+
+  #[test]
+  fn quoting() {
+    assert_eq!(systemd_quote("--background"), "--background");
+    assert_eq!(systemd_quote("KEY=a b"), "\"KEY=a b\"");
+    assert_eq!(systemd_quote("100%"), "100%%");
+    assert_eq!(quote("say \"hi\""), "\"say \\\"hi\\\"\"");
+  }
+
+  #[test]
+  fn snippet_after_shebang() {
+    let snippet = service_snippet("app.service", ScriptFormat::Deb, Script::PostInstall);
+    assert_eq!(
+      with_snippet("#!/bin/bash\necho done\nexit 0\n", &snippet),
+      format!("#!/bin/bash\n{snippet}echo done\nexit 0\n")
+    );
+    assert_eq!(
+      with_snippet("echo done\n", &snippet),
+      format!("#!/bin/sh\n{snippet}echo done\n")
+    );
+  }
+}
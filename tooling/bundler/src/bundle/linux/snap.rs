@@ -0,0 +1,115 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+// The Snap bundle is built with `snapcraft` from a generated project:
+//
+// bundle/snap/
+//     snap/snapcraft.yaml                  # Snapcraft project, using the gnome extension for webkit2gtk
+//     data/usr/...                         # Same file hierarchy as the debian data, dumped in the snap root
+//     foobar_1.2.3_amd64.snap              # The snap
+//
+// The binary is at `$SNAP/usr/bin/foobar` and the resources at `$SNAP/usr/lib/foobar`.
+
+use super::{
+  super::common::{self, CommandExt},
+  debian,
+};
+use crate::Settings;
+use anyhow::Context;
+use handlebars::Handlebars;
+use serde::Serialize;
+use std::{fs, path::PathBuf, process::Command};
+
+/// Bundles the project.
+/// Returns a vector of PathBuf that shows where the Snap was created.
+pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<PathBuf>> {
+  let arch = match settings.binary_arch() {
+    "x86" => "i386",
+    "x86_64" => "amd64",
+    "arm" => "armhf",
+    "aarch64" => "arm64",
+    other => other,
+  };
+  // snap names must be lowercase and can only contain letters, numbers and hyphens
+  let name = heck::AsKebabCase(settings.product_name()).to_string();
+  let snap_name = format!("{}_{}_{}.snap", name, settings.version_string(), arch);
+
+  let base_dir = settings.project_out_directory().join("bundle/snap");
+  if base_dir.exists() {
+    fs::remove_dir_all(&base_dir).with_context(|| "Failed to remove old snap bundle")?;
+  }
+  let snap_path = base_dir.join(&snap_name);
+
+  log::info!(action = "Bundling"; "{} ({})", snap_name, snap_path.display());
+
+  let (data_dir, _) = debian::generate_data(settings, &base_dir)
+    .with_context(|| "Failed to build data folders and files")?;
+  common::copy_custom_files(&settings.snap().files, &data_dir)
+    .with_context(|| "Failed to copy custom files")?;
+
+  generate_snapcraft_file(settings, &name, &base_dir.join("snap/snapcraft.yaml"))
+    .with_context(|| "Failed to create snapcraft.yaml")?;
+
+  Command::new("snapcraft")
+    .arg("pack")
+    .arg("--output")
+    .arg(&snap_path)
+    .current_dir(&base_dir)
+    .output_ok()
+    .context("failed to run snapcraft, make sure it is installed")?;
+
+  fs::remove_dir_all(data_dir)?;
+
+  Ok(vec![snap_path])
+}
+
+/// Generates the snapcraft project file.
+fn generate_snapcraft_file(
+  settings: &Settings,
+  name: &str,
+  dest_path: &std::path::Path,
+) -> crate::Result<()> {
+  // string values are JSON encoded, which is valid YAML and takes care of escaping
+  #[derive(Serialize)]
+  struct SnapcraftTemplateParams<'a> {
+    name: &'a str,
+    base: &'a str,
+    version: String,
+    title: String,
+    summary: String,
+    description: String,
+    grade: String,
+    confinement: String,
+    binary: &'a str,
+    plugs: &'a [String],
+  }
+
+  let snap = settings.snap();
+  let params = SnapcraftTemplateParams {
+    name,
+    base: &snap.base,
+    version: serde_json::to_string(settings.version_string())?,
+    title: serde_json::to_string(settings.product_name())?,
+    summary: serde_json::to_string(settings.short_description())?,
+    description: serde_json::to_string(
+      settings
+        .long_description()
+        .unwrap_or_else(|| settings.short_description()),
+    )?,
+    grade: serde_json::to_string(&snap.grade)?,
+    confinement: serde_json::to_string(&snap.confinement)?,
+    binary: settings.main_binary_name(),
+    plugs: &snap.plugs,
+  };
+
+  let mut handlebars = Handlebars::new();
+  handlebars.register_escape_fn(handlebars::no_escape);
+  handlebars
+    .register_template_string("snapcraft.yaml", include_str!("templates/snapcraft.yaml"))
+    .expect("Failed to register template for handlebars");
+
+  let file = common::create_file(dest_path)?;
+  handlebars.render_to_write("snapcraft.yaml", &params, file)?;
+  Ok(())
+}
@@ -123,7 +123,7 @@ pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<PathBuf>> {
     // notarization is required for distribution
     match notarize_auth() {
       Ok(auth) => {
-        notarize(&keychain, app_bundle_path.clone(), &auth)?;
+        notarize(&keychain, app_bundle_path.clone(), &auth, settings)?;
       }
       Err(e) => {
         if matches!(e, NotarizeAuthError::MissingTeamId) {
@@ -135,9 +135,36 @@ pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<PathBuf>> {
     }
   }
 
+  if let Some(epoch) = settings.source_date_epoch() {
+    normalize_timestamps(&app_bundle_path, epoch)?;
+  }
+
   Ok(vec![app_bundle_path])
 }
 
+/// Sets the modification time of the app bundle files to the source date epoch,
+/// so the archives created from it do not depend on the build time.
+fn normalize_timestamps(app_bundle_path: &Path, epoch: u64) -> crate::Result<()> {
+  let date = time::OffsetDateTime::from_unix_timestamp(epoch as i64)
+    .map_err(|e| anyhow::anyhow!("invalid source date epoch: {e}"))?;
+  let timestamp = format!(
+    "{:04}{:02}{:02}{:02}{:02}.{:02}",
+    date.year(),
+    u8::from(date.month()),
+    date.day(),
+    date.hour(),
+    date.minute(),
+    date.second()
+  );
+  Command::new("find")
+    .arg(app_bundle_path)
+    .args(["-exec", "touch", "-h", "-t", &timestamp, "{}", "+"])
+    .env("TZ", "UTC")
+    .output_ok()
+    .context("failed to normalize the app bundle timestamps")?;
+  Ok(())
+}
+
 fn remove_extra_attr(app_bundle_path: &Path) -> crate::Result<()> {
   Command::new("xattr")
     .arg("-crs")
@@ -3,7 +3,12 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
-use super::{app, icon::create_icns_file};
+use super::{
+  app,
+  icon::create_icns_file,
+  sign::{keychain, notarize, notarize_auth, NotarizeAuthError},
+  sla,
+};
 use crate::{
   bundle::{common::CommandExt, Bundle},
   PackageType, Settings,
@@ -12,11 +17,13 @@ use crate::{
 use anyhow::Context;
 
 use std::{
+  collections::HashMap,
   env,
   fs::{self, write},
   path::PathBuf,
   process::{Command, Stdio},
 };
+use tauri_utils::config::DmgLicense;
 
 pub struct Bundled {
   pub dmg: Vec<PathBuf>,
@@ -47,8 +54,33 @@ pub fn bundle_project(settings: &Settings, bundles: &[Bundle]) -> crate::Result<
       other => other,
     }
   );
-  let dmg_name = format!("{}.dmg", &package_base_name);
-  let dmg_path = output_path.join(&dmg_name);
+
+  let dmg_settings = settings.dmg();
+  let current_dir = env::current_dir()?;
+
+  // Finder displays a single background per disk image, so each language gets its own disk image
+  let mut variants: Vec<(String, Option<PathBuf>)> =
+    if dmg_settings.localized_backgrounds.is_empty() {
+      vec![(
+        format!("{package_base_name}.dmg"),
+        dmg_settings
+          .background
+          .as_ref()
+          .map(|background| current_dir.join(background)),
+      )]
+    } else {
+      dmg_settings
+        .localized_backgrounds
+        .iter()
+        .map(|(language, background)| {
+          (
+            format!("{package_base_name}_{language}.dmg"),
+            Some(current_dir.join(background)),
+          )
+        })
+        .collect()
+    };
+  variants.sort();
 
   let product_name = settings.product_name();
   let bundle_file_name = format!("{}.app", product_name);
@@ -57,7 +89,7 @@ pub fn bundle_project(settings: &Settings, bundles: &[Bundle]) -> crate::Result<
   let support_directory_path = output_path.join("support");
   if output_path.exists() {
     fs::remove_dir_all(&output_path)
-      .with_context(|| format!("Failed to remove old {}", dmg_name))?;
+      .with_context(|| format!("Failed to remove old {}", output_path.display()))?;
   }
   fs::create_dir_all(&support_directory_path).with_context(|| {
     format!(
@@ -69,8 +101,6 @@ pub fn bundle_project(settings: &Settings, bundles: &[Bundle]) -> crate::Result<
   // create paths for script
   let bundle_script_path = output_path.join("bundle_dmg.sh");
 
-  log::info!(action = "Bundling"; "{} ({})", dmg_name, dmg_path.display());
-
   // write the scripts
   write(
     &bundle_script_path,
@@ -95,8 +125,6 @@ pub fn bundle_project(settings: &Settings, bundles: &[Bundle]) -> crate::Result<
     .output()
     .expect("Failed to chmod script");
 
-  let dmg_settings = settings.dmg();
-
   let app_position = &dmg_settings.app_position;
   let application_folder_position = &dmg_settings.application_folder_position;
   let window_size = &dmg_settings.window_size;
@@ -108,97 +136,150 @@ pub fn bundle_project(settings: &Settings, bundles: &[Bundle]) -> crate::Result<
   let window_size_width = window_size.width.to_string();
   let window_size_height = window_size.height.to_string();
 
-  let mut bundle_dmg_cmd = Command::new(&bundle_script_path);
-
-  bundle_dmg_cmd.args([
-    "--volname",
-    product_name,
-    "--icon",
-    &bundle_file_name,
-    &app_position_x,
-    &app_position_y,
-    "--app-drop-link",
-    &application_folder_position_x,
-    &application_folder_position_y,
-    "--window-size",
-    &window_size_width,
-    &window_size_height,
-    "--hide-extension",
-    &bundle_file_name,
-  ]);
-
   let window_position = dmg_settings
     .window_position
     .as_ref()
     .map(|position| (position.x.to_string(), position.y.to_string()));
 
-  if let Some(window_position) = &window_position {
-    bundle_dmg_cmd.arg("--window-pos");
-    bundle_dmg_cmd.arg(&window_position.0);
-    bundle_dmg_cmd.arg(&window_position.1);
-  }
-
-  let background_path = if let Some(background_path) = &dmg_settings.background {
-    Some(env::current_dir()?.join(background_path))
+  let icns_icon_path = if let Some(volume_icon) = &dmg_settings.volume_icon {
+    Some(current_dir.join(volume_icon))
   } else {
-    None
+    create_icns_file(&output_path, settings)?
   };
 
-  if let Some(background_path) = &background_path {
-    bundle_dmg_cmd.arg("--background");
-    bundle_dmg_cmd.arg(background_path);
-  }
-
-  let icns_icon_path = create_icns_file(&output_path, settings)?;
-  if let Some(icon) = &icns_icon_path {
-    bundle_dmg_cmd.arg("--volicon");
-    bundle_dmg_cmd.arg(icon);
-  }
+  let licenses: HashMap<String, PathBuf> = match &dmg_settings.license {
+    Some(DmgLicense::Path(path)) => HashMap::from([("en".into(), current_dir.join(path))]),
+    Some(DmgLicense::Localized(licenses)) => licenses
+      .iter()
+      .map(|(language, path)| (language.clone(), current_dir.join(path)))
+      .collect(),
+    None => settings
+      .license_file()
+      .map(|path| ("en".into(), current_dir.join(path)))
+      .into_iter()
+      .collect(),
+  };
 
-  let license_path = if let Some(license_path) = settings.license_file() {
-    Some(env::current_dir()?.join(license_path))
-  } else {
+  let eula_resources_path = if licenses.is_empty() {
     None
+  } else {
+    let eula_resources_path = support_directory_path.join("eula-resources.xml");
+    sla::write_resources(&licenses, &eula_resources_path)?;
+    Some(eula_resources_path)
   };
 
-  if let Some(license_path) = &license_path {
-    bundle_dmg_cmd.arg("--eula");
-    bundle_dmg_cmd.arg(license_path);
-  }
+  let signing_identity = dmg_settings
+    .signing_identity
+    .as_ref()
+    .or(settings.macos().signing_identity.as_ref());
+  let keychain = signing_identity
+    .map(|identity| keychain(identity))
+    .transpose()?;
+
+  let mut dmg_paths = Vec::new();
+  for (dmg_name, background_path) in variants {
+    let dmg_path = output_path.join(&dmg_name);
+
+    log::info!(action = "Bundling"; "{} ({})", dmg_name, dmg_path.display());
 
-  // Issue #592 - Building MacOS dmg files on CI
-  // https://github.com/tauri-apps/tauri/issues/592
-  if let Some(value) = env::var_os("CI") {
-    if value == "true" {
-      bundle_dmg_cmd.arg("--skip-jenkins");
+    let mut bundle_dmg_cmd = Command::new(&bundle_script_path);
+
+    bundle_dmg_cmd.args([
+      "--volname",
+      dmg_settings.volume_name.as_deref().unwrap_or(product_name),
+      "--icon",
+      &bundle_file_name,
+      &app_position_x,
+      &app_position_y,
+      "--app-drop-link",
+      &application_folder_position_x,
+      &application_folder_position_y,
+      "--window-size",
+      &window_size_width,
+      &window_size_height,
+      "--hide-extension",
+      &bundle_file_name,
+    ]);
+
+    if let Some(window_position) = &window_position {
+      bundle_dmg_cmd.arg("--window-pos");
+      bundle_dmg_cmd.arg(&window_position.0);
+      bundle_dmg_cmd.arg(&window_position.1);
+    }
+
+    if let Some(icon_size) = dmg_settings.icon_size {
+      bundle_dmg_cmd.arg("--icon-size");
+      bundle_dmg_cmd.arg(icon_size.to_string());
+    }
+
+    if let Some(text_size) = dmg_settings.text_size {
+      bundle_dmg_cmd.arg("--text-size");
+      bundle_dmg_cmd.arg(text_size.to_string());
+    }
+
+    if let Some(background_path) = &background_path {
+      bundle_dmg_cmd.arg("--background");
+      bundle_dmg_cmd.arg(background_path);
+    }
+
+    if let Some(icon) = &icns_icon_path {
+      bundle_dmg_cmd.arg("--volicon");
+      bundle_dmg_cmd.arg(icon);
+    }
+
+    if let Some(eula_resources_path) = &eula_resources_path {
+      bundle_dmg_cmd.arg("--eula-resources");
+      bundle_dmg_cmd.arg(eula_resources_path);
+    }
+
+    // Issue #592 - Building MacOS dmg files on CI
+    // https://github.com/tauri-apps/tauri/issues/592
+    if let Some(value) = env::var_os("CI") {
+      if value == "true" {
+        bundle_dmg_cmd.arg("--skip-jenkins");
+      }
+    }
+
+    log::info!(action = "Running"; "bundle_dmg.sh");
+
+    // execute the bundle script
+    bundle_dmg_cmd
+      .current_dir(bundle_dir.clone())
+      .args(vec![dmg_name.as_str(), bundle_file_name.as_str()])
+      .output_ok()
+      .context("error running bundle_dmg.sh")?;
+
+    fs::rename(bundle_dir.join(&dmg_name), dmg_path.clone())?;
+
+    // Sign DMG if needed, after the license agreement resources are attached
+    if let (Some(identity), Some(keychain)) = (signing_identity, &keychain) {
+      log::info!(action = "Signing"; "{} with identity \"{}\"", dmg_name, identity);
+      // the entitlements and hardened runtime only apply to the app bundle
+      keychain.sign(&dmg_path, None, false)?;
+
+      if dmg_settings.notarize {
+        match notarize_auth() {
+          Ok(auth) => {
+            notarize(keychain, dmg_path.clone(), &auth, settings)?;
+          }
+          Err(e) => {
+            if matches!(e, NotarizeAuthError::MissingTeamId) {
+              return Err(anyhow::anyhow!("{e}").into());
+            } else {
+              log::warn!("skipping dmg notarization, {}", e.to_string());
+            }
+          }
+        }
+      }
+    } else if dmg_settings.notarize {
+      log::warn!("skipping dmg notarization, the disk image is not signed");
     }
-  }
 
-  log::info!(action = "Running"; "bundle_dmg.sh");
-
-  // execute the bundle script
-  bundle_dmg_cmd
-    .current_dir(bundle_dir.clone())
-    .args(vec![dmg_name.as_str(), bundle_file_name.as_str()])
-    .output_ok()
-    .context("error running bundle_dmg.sh")?;
-
-  fs::rename(bundle_dir.join(dmg_name), dmg_path.clone())?;
-
-  // Sign DMG if needed
-  if let Some(identity) = &settings.macos().signing_identity {
-    super::sign::sign(
-      vec![super::sign::SignTarget {
-        path: dmg_path.clone(),
-        is_an_executable: false,
-      }],
-      identity,
-      settings,
-    )?;
+    dmg_paths.push(dmg_path);
   }
 
   Ok(Bundled {
-    dmg: vec![dmg_path],
+    dmg: dmg_paths,
     app: app_bundle_paths,
   })
 }
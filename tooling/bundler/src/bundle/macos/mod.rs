@@ -7,4 +7,6 @@ pub mod app;
 pub mod dmg;
 pub mod icon;
 pub mod ios;
+pub mod pkg;
 pub mod sign;
+pub mod sla;
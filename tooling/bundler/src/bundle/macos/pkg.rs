@@ -0,0 +1,179 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+// The installer package is assembled with `productbuild` from component packages:
+//
+// bundle/pkg/
+//     scripts/                             # preinstall and postinstall scripts
+//     components/foobar.pkg                # The app bundle component, built with `pkgbuild`
+//     foobar_1.2.3_x64.pkg                 # The installer package
+
+use super::{
+  app,
+  sign::{installer_keychain, keychain, notarize, notarize_auth, NotarizeAuthError},
+};
+use crate::{
+  bundle::{common::CommandExt, Bundle},
+  PackageType, Settings,
+};
+
+use anyhow::Context;
+
+use std::{
+  fs,
+  path::{Path, PathBuf},
+  process::Command,
+};
+
+pub struct Bundled {
+  pub pkg: Vec<PathBuf>,
+  pub app: Vec<PathBuf>,
+}
+
+/// Bundles the project.
+/// Returns a vector of PathBuf that shows where the installer package was created.
+pub fn bundle_project(settings: &Settings, bundles: &[Bundle]) -> crate::Result<Bundled> {
+  // generate the .app bundle if needed
+  let app_bundle_paths = if !bundles
+    .iter()
+    .any(|bundle| bundle.package_type == PackageType::MacOsBundle)
+  {
+    app::bundle_project(settings)?
+  } else {
+    Vec::new()
+  };
+
+  let output_path = settings.project_out_directory().join("bundle/pkg");
+  let package_base_name = format!(
+    "{}_{}_{}",
+    settings.product_name(),
+    settings.version_string(),
+    match settings.binary_arch() {
+      "x86_64" => "x64",
+      other => other,
+    }
+  );
+  let pkg_name = format!("{}.pkg", &package_base_name);
+  let pkg_path = output_path.join(&pkg_name);
+
+  let app_bundle_path = settings
+    .project_out_directory()
+    .join("bundle/macos")
+    .join(format!("{}.app", settings.product_name()));
+
+  if output_path.exists() {
+    fs::remove_dir_all(&output_path)
+      .with_context(|| format!("Failed to remove old {}", pkg_name))?;
+  }
+  let components_path = output_path.join("components");
+  fs::create_dir_all(&components_path)
+    .with_context(|| format!("Failed to create output directory at {:?}", components_path))?;
+
+  log::info!(action = "Bundling"; "{} ({})", pkg_name, pkg_path.display());
+
+  let pkg_settings = settings.pkg();
+
+  // build the app component package
+  let component_path = components_path.join(format!("{}.pkg", settings.product_name()));
+  let mut pkgbuild = Command::new("pkgbuild");
+  pkgbuild
+    .arg("--component")
+    .arg(&app_bundle_path)
+    .arg("--install-location")
+    .arg(&pkg_settings.install_location)
+    .arg("--identifier")
+    .arg(settings.bundle_identifier())
+    .arg("--version")
+    .arg(settings.version_string());
+  if let Some(scripts_path) = generate_scripts(settings, &output_path.join("scripts"))? {
+    pkgbuild.arg("--scripts").arg(scripts_path);
+  }
+  pkgbuild
+    .arg(&component_path)
+    .output_ok()
+    .context("error running pkgbuild")?;
+
+  // assemble the installer package
+  let mut productbuild = Command::new("productbuild");
+  productbuild.arg("--package").arg(&component_path);
+  for component in &pkg_settings.component_packages {
+    productbuild.arg("--package").arg(component);
+  }
+
+  // the keychain must outlive productbuild since it may be a temporary keychain
+  let installer_keychain = match &pkg_settings.signing_identity {
+    Some(identity) => {
+      log::info!(action = "Signing"; "with identity \"{}\"", identity);
+      let keychain = installer_keychain(identity)?;
+      productbuild.arg("--sign").arg(identity);
+      if let Some(path) = keychain.path() {
+        productbuild.arg("--keychain").arg(path);
+      }
+      Some(keychain)
+    }
+    None => None,
+  };
+
+  productbuild
+    .arg(&pkg_path)
+    .output_ok()
+    .context("error running productbuild")?;
+  drop(installer_keychain);
+
+  // notarization requires both the installer package and the app bundle to be signed
+  if let (Some(_), Some(identity)) = (
+    &pkg_settings.signing_identity,
+    &settings.macos().signing_identity,
+  ) {
+    match notarize_auth() {
+      Ok(auth) => {
+        let keychain = keychain(identity)?;
+        notarize(&keychain, pkg_path.clone(), &auth, settings)?;
+      }
+      Err(e) => {
+        if matches!(e, NotarizeAuthError::MissingTeamId) {
+          return Err(anyhow::anyhow!("{e}").into());
+        } else {
+          log::warn!("skipping pkg notarization, {}", e.to_string());
+        }
+      }
+    }
+  }
+
+  Ok(Bundled {
+    pkg: vec![pkg_path],
+    app: app_bundle_paths,
+  })
+}
+
+/// Copies the preinstall and postinstall scripts to the scripts folder.
+/// Returns `None` if no script is configured.
+fn generate_scripts(settings: &Settings, scripts_path: &Path) -> crate::Result<Option<PathBuf>> {
+  let pkg_settings = settings.pkg();
+  let scripts = [
+    ("preinstall", &pkg_settings.pre_install_script),
+    ("postinstall", &pkg_settings.post_install_script),
+  ];
+
+  if scripts.iter().all(|(_, script)| script.is_none()) {
+    return Ok(None);
+  }
+
+  fs::create_dir_all(scripts_path)?;
+  for (name, script) in scripts {
+    if let Some(script) = script {
+      let dest = scripts_path.join(name);
+      fs::copy(script, &dest)
+        .with_context(|| format!("Failed to copy {} script {}", name, script.display()))?;
+      // the installer only runs executable scripts
+      Command::new("chmod")
+        .arg("755")
+        .arg(&dest)
+        .output_ok()
+        .with_context(|| format!("Failed to make {} script executable", name))?;
+    }
+  }
+
+  Ok(Some(scripts_path.to_path_buf()))
+}
@@ -7,6 +7,7 @@ use std::{
   env::{var, var_os},
   ffi::OsString,
   path::{Path, PathBuf},
+  time::Duration,
 };
 
 use crate::Settings;
@@ -23,16 +24,7 @@ pub fn sign(
 ) -> crate::Result<tauri_macos_sign::Keychain> {
   log::info!(action = "Signing"; "with identity \"{}\"", identity);
 
-  let keychain = if let (Some(certificate_encoded), Some(certificate_password)) = (
-    var_os("APPLE_CERTIFICATE"),
-    var_os("APPLE_CERTIFICATE_PASSWORD"),
-  ) {
-    // setup keychain allow you to import your certificate
-    // for CI build
-    tauri_macos_sign::Keychain::with_certificate(&certificate_encoded, &certificate_password)?
-  } else {
-    tauri_macos_sign::Keychain::with_signing_identity(identity)
-  };
+  let keychain = keychain(identity)?;
 
   log::info!("Signing app bundle...");
 
@@ -47,12 +39,58 @@ pub fn sign(
   Ok(keychain)
 }
 
+/// Resolves the keychain holding the given signing identity.
+///
+/// The certificate is imported in a temporary keychain if the `APPLE_CERTIFICATE`
+/// and `APPLE_CERTIFICATE_PASSWORD` environment variables are set.
+pub fn keychain(identity: &str) -> crate::Result<tauri_macos_sign::Keychain> {
+  keychain_with_certificate_vars(identity, "APPLE_CERTIFICATE", "APPLE_CERTIFICATE_PASSWORD")
+}
+
+/// Resolves the keychain holding the given installer signing identity, e.g. `Developer ID Installer: Tauri Programme (TEAMID)`.
+///
+/// The installer certificate is imported in a temporary keychain if the `APPLE_INSTALLER_CERTIFICATE`
+/// and `APPLE_INSTALLER_CERTIFICATE_PASSWORD` environment variables are set.
+pub fn installer_keychain(identity: &str) -> crate::Result<tauri_macos_sign::Keychain> {
+  keychain_with_certificate_vars(
+    identity,
+    "APPLE_INSTALLER_CERTIFICATE",
+    "APPLE_INSTALLER_CERTIFICATE_PASSWORD",
+  )
+}
+
+fn keychain_with_certificate_vars(
+  identity: &str,
+  certificate_var: &str,
+  password_var: &str,
+) -> crate::Result<tauri_macos_sign::Keychain> {
+  if let (Some(certificate_encoded), Some(certificate_password)) =
+    (var_os(certificate_var), var_os(password_var))
+  {
+    // setup keychain allow you to import your certificate
+    // for CI build
+    tauri_macos_sign::Keychain::with_certificate(&certificate_encoded, &certificate_password)
+      .map_err(Into::into)
+  } else {
+    Ok(tauri_macos_sign::Keychain::with_signing_identity(identity))
+  }
+}
+
+/// Notarizes the app bundle, disk image or installer package with the configured notarization settings.
 pub fn notarize(
   keychain: &tauri_macos_sign::Keychain,
   app_bundle_path: PathBuf,
   credentials: &tauri_macos_sign::AppleNotarizationCredentials,
+  settings: &Settings,
 ) -> crate::Result<()> {
-  tauri_macos_sign::notarize(keychain, &app_bundle_path, credentials).map_err(Into::into)
+  let notarization = &settings.macos().notarization;
+  let options = tauri_macos_sign::NotarizeOptions {
+    timeout: Duration::from_secs(notarization.timeout),
+    attempts: notarization.attempts.max(1),
+    staple: notarization.staple,
+  };
+  tauri_macos_sign::notarize_with_options(keychain, &app_bundle_path, credentials, &options)
+    .map_err(Into::into)
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -67,6 +105,16 @@ pub enum NotarizeAuthError {
 
 pub fn notarize_auth() -> Result<tauri_macos_sign::AppleNotarizationCredentials, NotarizeAuthError>
 {
+  // credentials stored with `xcrun notarytool store-credentials`
+  if let Some(profile) = var_os("APPLE_KEYCHAIN_PROFILE") {
+    return Ok(
+      tauri_macos_sign::AppleNotarizationCredentials::KeychainProfile {
+        profile,
+        keychain: var_os("APPLE_KEYCHAIN").map(PathBuf::from),
+      },
+    );
+  }
+
   match (
     var_os("APPLE_ID"),
     var_os("APPLE_PASSWORD"),
@@ -111,7 +159,7 @@ pub fn notarize_auth() -> Result<tauri_macos_sign::AppleNotarizationCredentials,
             Err(anyhow::anyhow!("could not find API key file. Please set the APPLE_API_KEY_PATH environment variables to the path to the {api_key_file_name:?} file").into())
           }
         }
-        _ => Err(anyhow::anyhow!("no APPLE_ID & APPLE_PASSWORD & APPLE_TEAM_ID or APPLE_API_KEY & APPLE_API_ISSUER & APPLE_API_KEY_PATH environment variables found").into())
+        _ => Err(anyhow::anyhow!("no APPLE_ID & APPLE_PASSWORD & APPLE_TEAM_ID, APPLE_API_KEY & APPLE_API_ISSUER & APPLE_API_KEY_PATH or APPLE_KEYCHAIN_PROFILE environment variables found").into())
       }
     }
   }
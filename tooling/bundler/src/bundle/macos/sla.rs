@@ -0,0 +1,319 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Software License Agreement (SLA) resources of disk images.
+//!
+//! The license agreement is stored as legacy Mac resources attached to the disk image with `hdiutil udifrez`:
+//! - `LPic` maps the region codes to the resource ids of each language.
+//! - `STR#` holds the language name and the localized button texts.
+//! - `TEXT` or `RTF ` holds the license text itself.
+//!
+//! The texts are encoded with the Mac OS Roman encoding, which is used by the supported languages.
+
+use std::{
+  collections::HashMap,
+  fs,
+  path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+
+/// The id of the first language resources.
+const BASE_RESOURCE_ID: u16 = 5000;
+
+/// The default language, if a license is provided for it.
+const DEFAULT_LANGUAGE: &str = "en";
+
+/// A language supported by the license agreement dialog.
+struct Language {
+  /// The language subtag.
+  code: &'static str,
+  /// The Mac region code, see `Script.h`.
+  region: u16,
+  /// The language name, followed by the agree, disagree, print and save button texts and the instructions.
+  strings: [&'static str; 6],
+}
+
+const LANGUAGES: &[Language] = &[
+  Language {
+    code: "da",
+    region: 9,
+    strings: [
+      "Dansk",
+      "Enig",
+      "Uenig",
+      "Udskriv",
+      "Arkiver...",
+      "Hvis du accepterer betingelserne i licensaftalen, skal du klikke på \"Enig\" for at installere softwaren. Klik på \"Uenig\" for at annullere installeringen.",
+    ],
+  },
+  Language {
+    code: "de",
+    region: 3,
+    strings: [
+      "Deutsch",
+      "Akzeptieren",
+      "Ablehnen",
+      "Drucken",
+      "Sichern...",
+      "Klicken Sie auf \"Akzeptieren\", wenn Sie mit den Bestimmungen des Software-Lizenzvertrags einverstanden sind. Falls nicht, bitte \"Ablehnen\" anklicken.",
+    ],
+  },
+  Language {
+    code: "en",
+    region: 0,
+    strings: [
+      "English",
+      "Agree",
+      "Disagree",
+      "Print",
+      "Save...",
+      "If you agree with the terms of this license, press \"Agree\" to install the software. If you do not agree, press \"Disagree\".",
+    ],
+  },
+  Language {
+    code: "es",
+    region: 8,
+    strings: [
+      "Español",
+      "Aceptar",
+      "No aceptar",
+      "Imprimir",
+      "Guardar...",
+      "Si está de acuerdo con los términos de esta licencia, pulse \"Aceptar\" para instalar el software. En el supuesto de que no esté de acuerdo, pulse \"No aceptar\".",
+    ],
+  },
+  Language {
+    code: "fi",
+    region: 17,
+    strings: [
+      "Suomi",
+      "Hyväksyn",
+      "En hyväksy",
+      "Tulosta",
+      "Tallenna...",
+      "Hyväksy lisenssisopimuksen ehdot osoittamalla \"Hyväksyn\". Jos et hyväksy sopimuksen ehtoja, osoita \"En hyväksy\".",
+    ],
+  },
+  Language {
+    code: "fr",
+    region: 1,
+    strings: [
+      "Français",
+      "Accepter",
+      "Refuser",
+      "Imprimer",
+      "Enregistrer...",
+      "Si vous acceptez les termes de la présente licence, cliquez sur \"Accepter\" afin d'installer le logiciel. Si vous n'êtes pas d'accord, cliquez sur \"Refuser\".",
+    ],
+  },
+  Language {
+    code: "it",
+    region: 4,
+    strings: [
+      "Italiano",
+      "Accetto",
+      "Rifiuto",
+      "Stampa",
+      "Registra...",
+      "Se accetti le condizioni di questa licenza, fai clic su \"Accetto\" per installare il software. Altrimenti fai clic su \"Rifiuto\".",
+    ],
+  },
+  Language {
+    code: "nb",
+    region: 12,
+    strings: [
+      "Norsk",
+      "Enig",
+      "Ikke enig",
+      "Skriv ut",
+      "Arkiver...",
+      "Hvis De er enig i bestemmelsene i denne lisensavtalen, klikker De på \"Enig\" for å installere programvaren. Hvis De ikke er enig, klikker De på \"Ikke enig\".",
+    ],
+  },
+  Language {
+    code: "nl",
+    region: 5,
+    strings: [
+      "Nederlands",
+      "Ja",
+      "Nee",
+      "Print",
+      "Bewaar...",
+      "Indien u akkoord gaat met de voorwaarden van deze licentie, kunt u op \"Ja\" klikken om het programma te installeren. Indien u niet akkoord gaat, klikt u op \"Nee\".",
+    ],
+  },
+  Language {
+    code: "pt",
+    region: 71,
+    strings: [
+      "Português",
+      "Concordar",
+      "Discordar",
+      "Imprimir",
+      "Salvar...",
+      "Se está de acordo com os termos desta licença, pressione \"Concordar\" para instalar o software. Se não está de acordo, pressione \"Discordar\".",
+    ],
+  },
+  Language {
+    code: "sv",
+    region: 7,
+    strings: [
+      "Svensk",
+      "Godkänns",
+      "Avböjs",
+      "Skriv ut",
+      "Spara...",
+      "Om Du godkänner licensvillkoren klicka på \"Godkänns\" för att installera programprodukten. Om Du inte godkänner licensvillkoren, klicka på \"Avböjs\".",
+    ],
+  },
+];
+
+/// The Mac OS Roman characters from `0x80` to `0xFF`.
+const MAC_ROMAN: &str = "ÄÅÇÉÑÖÜáàâäãåçéèêëíìîïñóòôöõúùûü†°¢£§•¶ß®©™´¨≠ÆØ∞±≤≥¥µ∂∑∏π∫ªºΩæø¿¡¬√ƒ≈∆«»…\u{a0}ÀÃÕŒœ–—“”‘’÷◊ÿŸ⁄€‹›ﬁﬂ‡·‚„‰ÂÊÁËÈÍÎÏÌÓÔ\u{f8ff}ÒÚÛÙıˆ˜¯˘˙˚¸˝˛ˇ";
+
+/// Encodes the text with the Mac OS Roman encoding, replacing unsupported characters with `?`.
+fn mac_roman(text: &str) -> Vec<u8> {
+  text
+    .chars()
+    .map(|c| {
+      if c.is_ascii() {
+        c as u8
+      } else {
+        MAC_ROMAN
+          .chars()
+          .position(|m| m == c)
+          .map_or(b'?', |i| 0x80 + i as u8)
+      }
+    })
+    .collect()
+}
+
+/// Finds the supported language of a language tag such as `en-US`.
+fn find_language(tag: &str) -> Option<&'static Language> {
+  let subtag = tag.split(|c| c == '-' || c == '_').next()?.to_lowercase();
+  // `no` is the macro language of the Norwegian Bokmål
+  let subtag = if subtag == "no" { "nb".into() } else { subtag };
+  LANGUAGES.iter().find(|language| language.code == subtag)
+}
+
+/// Writes the license agreement resources file to use with `hdiutil udifrez`.
+///
+/// `licenses` maps the language tags to the license files.
+pub fn write_resources(licenses: &HashMap<String, PathBuf>, path: &Path) -> crate::Result<()> {
+  let mut entries = Vec::new();
+  for (tag, license_path) in licenses {
+    let language = find_language(tag).ok_or_else(|| {
+      anyhow::anyhow!(
+        "Unsupported DMG license language `{tag}`, expected one of {}",
+        LANGUAGES
+          .iter()
+          .map(|language| language.code)
+          .collect::<Vec<_>>()
+          .join(", ")
+      )
+    })?;
+    if entries
+      .iter()
+      .any(|(l, _): &(&Language, _)| l.code == language.code)
+    {
+      anyhow::bail!("Duplicated DMG license language `{tag}`");
+    }
+    let contents = fs::read(license_path)
+      .with_context(|| format!("Failed to read license file {}", license_path.display()))?;
+    entries.push((language, contents));
+  }
+  // the default language comes first, so the dialog opens with it
+  entries.sort_by_key(|(language, _)| (language.code != DEFAULT_LANGUAGE, language.code));
+
+  resources(&entries).to_file_xml(path)?;
+  Ok(())
+}
+
+fn resources(entries: &[(&Language, Vec<u8>)]) -> plist::Value {
+  let mut lpic = Vec::new();
+  let default_region = entries.first().map_or(0, |(language, _)| language.region);
+  lpic.extend(default_region.to_be_bytes());
+  lpic.extend((entries.len() as u16).to_be_bytes());
+
+  let mut strings = Vec::new();
+  let mut texts = Vec::new();
+  let mut rtfs = Vec::new();
+  for (offset, (language, contents)) in entries.iter().enumerate() {
+    let offset = offset as u16;
+    // region code, resource id offset and whether the language uses two-byte characters
+    lpic.extend(language.region.to_be_bytes());
+    lpic.extend(offset.to_be_bytes());
+    lpic.extend(0u16.to_be_bytes());
+
+    let mut data = (language.strings.len() as u16).to_be_bytes().to_vec();
+    for string in language.strings {
+      // pascal strings, prefixed with their length
+      let mut bytes = mac_roman(string);
+      bytes.truncate(u8::MAX as usize);
+      data.push(bytes.len() as u8);
+      data.extend(bytes);
+    }
+    let id = BASE_RESOURCE_ID + offset;
+    strings.push(resource(id, language.strings[0], data));
+
+    if contents.starts_with(b"{\\rtf") {
+      rtfs.push(resource(id, language.strings[0], contents.clone()));
+    } else {
+      let text = String::from_utf8_lossy(contents)
+        .replace("\r\n", "\r")
+        .replace('\n', "\r");
+      texts.push(resource(id, language.strings[0], mac_roman(&text)));
+    }
+  }
+
+  let mut dict = plist::Dictionary::new();
+  dict.insert(
+    "LPic".into(),
+    plist::Value::Array(vec![resource(BASE_RESOURCE_ID, "", lpic)]),
+  );
+  dict.insert("STR#".into(), plist::Value::Array(strings));
+  if !texts.is_empty() {
+    dict.insert("TEXT".into(), plist::Value::Array(texts));
+  }
+  if !rtfs.is_empty() {
+    dict.insert("RTF ".into(), plist::Value::Array(rtfs));
+  }
+  plist::Value::Dictionary(dict)
+}
+
+fn resource(id: u16, name: &str, data: Vec<u8>) -> plist::Value {
+  let mut dict = plist::Dictionary::new();
+  dict.insert("Attributes".into(), "0x0000".into());
+  dict.insert("Data".into(), plist::Value::Data(data));
+  dict.insert("ID".into(), id.to_string().into());
+  dict.insert("Name".into(), name.into());
+  plist::Value::Dictionary(dict)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn mac_roman_encoding() {
+    assert_eq!(MAC_ROMAN.chars().count(), 128);
+    assert_eq!(mac_roman("Añ€"), vec![b'A', 0x96, 0xDB]);
+    assert_eq!(mac_roman("中"), vec![b'?']);
+  }
+
+  #[test]
+  fn languages() {
+    assert_eq!(find_language("en-US").map(|l| l.code), Some("en"));
+    assert_eq!(find_language("pt_BR").map(|l| l.code), Some("pt"));
+    assert_eq!(find_language("no").map(|l| l.code), Some("nb"));
+    assert!(find_language("ja").is_none());
+    for language in LANGUAGES {
+      for string in language.strings {
+        assert!(mac_roman(string).len() <= u8::MAX as usize);
+        assert!(!mac_roman(string).contains(&b'?'));
+      }
+    }
+  }
+}
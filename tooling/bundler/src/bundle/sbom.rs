@@ -0,0 +1,463 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Software bill of materials (SBOM) of the generated artifacts.
+//!
+//! Each artifact gets a [CycloneDX] or [SPDX] JSON document next to it, listing:
+//! - the Rust crates the app depends on, with their dependency graph;
+//! - the sidecars, with their SHA-256 hash;
+//! - the frontend assets embedded in the app, with their SHA-256 hash.
+//!
+//! [CycloneDX]: https://cyclonedx.org/docs/1.5/json/
+//! [SPDX]: https://spdx.github.io/spdx-spec/v2.3/
+
+use super::{common, Bundle};
+use crate::Settings;
+
+use serde_json::{json, Value};
+use std::{
+  ffi::OsString,
+  path::{Path, PathBuf},
+};
+use walkdir::WalkDir;
+
+/// The SBOM document format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SbomFormat {
+  /// CycloneDX 1.5 JSON, written to `<artifact>.cdx.json`.
+  CycloneDx,
+  /// SPDX 2.3 JSON, written to `<artifact>.spdx.json`.
+  Spdx,
+}
+
+impl SbomFormat {
+  /// The extension appended to the artifact file name.
+  pub fn extension(&self) -> &'static str {
+    match self {
+      Self::CycloneDx => "cdx.json",
+      Self::Spdx => "spdx.json",
+    }
+  }
+}
+
+/// A Rust package of the app dependency graph, usually read from `cargo metadata`.
+#[derive(Debug, Clone)]
+pub struct SbomPackage {
+  /// The unique package id.
+  pub id: String,
+  /// The package name.
+  pub name: String,
+  /// The package version.
+  pub version: String,
+  /// The SPDX license expression of the package.
+  pub license: Option<String>,
+  /// The registry or git source of the package, `None` for local packages.
+  pub source: Option<String>,
+  /// The ids of the packages this package depends on.
+  pub dependencies: Vec<String>,
+}
+
+/// The app dependency graph.
+#[derive(Debug, Clone)]
+pub struct SbomDependencies {
+  /// The id of the app package.
+  pub root: String,
+  /// The packages of the graph, including the app package.
+  pub packages: Vec<SbomPackage>,
+}
+
+/// A component shipped with the app that is not a Rust package.
+#[derive(Debug)]
+struct FileComponent {
+  name: String,
+  sha256: String,
+}
+
+/// Writes an SBOM next to each artifact of the bundles.
+///
+/// `frontend_dist` lists the frontend files and directories embedded in the app.
+pub fn write_sboms(
+  settings: &Settings,
+  format: SbomFormat,
+  dependencies: &SbomDependencies,
+  frontend_dist: &[PathBuf],
+  bundles: &[Bundle],
+) -> crate::Result<Vec<PathBuf>> {
+  let mut sidecars = Vec::new();
+  for src in settings.external_binaries() {
+    let src = src?;
+    let name = src
+      .file_name()
+      .expect("failed to extract external binary filename")
+      .to_string_lossy()
+      .replace(&format!("-{}", settings.target()), "");
+    sidecars.push(FileComponent {
+      name,
+      sha256: common::sha256_file(&src)?,
+    });
+  }
+
+  let mut frontend = Vec::new();
+  for path in frontend_dist {
+    for entry in WalkDir::new(path).sort_by_file_name() {
+      let entry = entry?;
+      if entry.file_type().is_file() {
+        let relative = entry.path().strip_prefix(path).unwrap_or(entry.path());
+        let relative = if relative.as_os_str().is_empty() {
+          entry.path()
+        } else {
+          relative
+        };
+        frontend.push(FileComponent {
+          name: slash_path(relative),
+          sha256: common::sha256_file(entry.path())?,
+        });
+      }
+    }
+  }
+
+  let timestamp = common::rfc3339(common::build_timestamp(settings.source_date_epoch()));
+
+  let mut paths = Vec::new();
+  for bundle in bundles {
+    for path in &bundle.bundle_paths {
+      let artifact_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+      // directories such as the macOS app bundle are not hashed
+      let artifact_hash = path
+        .is_file()
+        .then(|| common::sha256_file(path))
+        .transpose()?;
+      let artifact = Artifact {
+        name: &artifact_name,
+        sha256: artifact_hash.as_deref(),
+      };
+
+      let document = match format {
+        SbomFormat::CycloneDx => cyclonedx(
+          settings,
+          &timestamp,
+          &artifact,
+          dependencies,
+          &sidecars,
+          &frontend,
+        ),
+        SbomFormat::Spdx => spdx(
+          settings,
+          &timestamp,
+          &artifact,
+          dependencies,
+          &sidecars,
+          &frontend,
+        ),
+      };
+
+      let mut file_name = OsString::from(&artifact_name);
+      file_name.push(".");
+      file_name.push(format.extension());
+      let sbom_path = path.with_file_name(file_name);
+      let file = common::create_file(&sbom_path)?;
+      serde_json::to_writer_pretty(file, &document)?;
+      paths.push(sbom_path);
+    }
+  }
+
+  Ok(paths)
+}
+
+/// The artifact described by the SBOM.
+struct Artifact<'a> {
+  name: &'a str,
+  sha256: Option<&'a str>,
+}
+
+fn cyclonedx(
+  settings: &Settings,
+  timestamp: &str,
+  artifact: &Artifact<'_>,
+  dependencies: &SbomDependencies,
+  sidecars: &[FileComponent],
+  frontend: &[FileComponent],
+) -> Value {
+  let mut components = Vec::new();
+  let mut graph = Vec::new();
+
+  for package in &dependencies.packages {
+    let mut component = json!({
+      "type": "library",
+      "bom-ref": package.id,
+      "name": package.name,
+      "version": package.version,
+      "purl": purl(package),
+    });
+    if let Some(license) = &package.license {
+      component["licenses"] = json!([{ "expression": license_expression(license) }]);
+    }
+    if let Some(url) = package.source.as_deref().and_then(git_url) {
+      component["externalReferences"] = json!([{ "type": "vcs", "url": url }]);
+    }
+    components.push(component);
+    graph.push(json!({ "ref": package.id, "dependsOn": package.dependencies }));
+  }
+
+  let mut app_dependencies = vec![dependencies.root.clone()];
+  for (kind, prefix, files) in [
+    ("application", "sidecar", sidecars),
+    ("file", "frontend", frontend),
+  ] {
+    for file in files {
+      let bom_ref = format!("{prefix}:{}", file.name);
+      components.push(json!({
+        "type": kind,
+        "bom-ref": bom_ref,
+        "name": file.name,
+        "hashes": [{ "alg": "SHA-256", "content": file.sha256 }],
+      }));
+      app_dependencies.push(bom_ref);
+    }
+  }
+  graph.push(json!({ "ref": "artifact", "dependsOn": app_dependencies }));
+
+  let mut subject = json!({
+    "type": "application",
+    "bom-ref": "artifact",
+    "name": artifact.name,
+    "version": settings.version_string(),
+  });
+  if let Some(sha256) = artifact.sha256 {
+    subject["hashes"] = json!([{ "alg": "SHA-256", "content": sha256 }]);
+  }
+  if let Some(license) = settings.license() {
+    subject["licenses"] = json!([{ "expression": license_expression(&license) }]);
+  }
+
+  json!({
+    "bomFormat": "CycloneDX",
+    "specVersion": "1.5",
+    "version": 1,
+    "metadata": {
+      "timestamp": timestamp,
+      "tools": {
+        "components": [{
+          "type": "application",
+          "name": "tauri-bundler",
+          "version": env!("CARGO_PKG_VERSION"),
+        }],
+      },
+      "component": subject,
+    },
+    "components": components,
+    "dependencies": graph,
+  })
+}
+
+fn spdx(
+  settings: &Settings,
+  timestamp: &str,
+  artifact: &Artifact<'_>,
+  dependencies: &SbomDependencies,
+  sidecars: &[FileComponent],
+  frontend: &[FileComponent],
+) -> Value {
+  const ARTIFACT_ID: &str = "SPDXRef-Artifact";
+  let package_id = |index: usize| format!("SPDXRef-Crate-{index}");
+  let id_of = |id: &str| {
+    dependencies
+      .packages
+      .iter()
+      .position(|package| package.id == id)
+      .map(package_id)
+  };
+
+  let mut packages = Vec::new();
+  let mut relationships = vec![json!({
+    "spdxElementId": "SPDXRef-DOCUMENT",
+    "relationshipType": "DESCRIBES",
+    "relatedSpdxElement": ARTIFACT_ID,
+  })];
+
+  let mut subject = spdx_package(
+    ARTIFACT_ID,
+    artifact.name,
+    Some(settings.version_string()),
+    artifact.sha256,
+    settings.license().as_deref(),
+  );
+  if settings.homepage_url().is_some() {
+    subject["homepage"] = json!(settings.homepage_url());
+  }
+  subject["primaryPackagePurpose"] = json!("APPLICATION");
+  packages.push(subject);
+
+  if let Some(root) = id_of(&dependencies.root) {
+    relationships.push(json!({
+      "spdxElementId": ARTIFACT_ID,
+      "relationshipType": "CONTAINS",
+      "relatedSpdxElement": root,
+    }));
+  }
+
+  for (index, package) in dependencies.packages.iter().enumerate() {
+    let mut spdx_package = spdx_package(
+      &package_id(index),
+      &package.name,
+      Some(&package.version),
+      None,
+      package.license.as_deref(),
+    );
+    spdx_package["downloadLocation"] = json!(download_location(package));
+    spdx_package["externalRefs"] = json!([{
+      "referenceCategory": "PACKAGE-MANAGER",
+      "referenceType": "purl",
+      "referenceLocator": purl(package),
+    }]);
+    packages.push(spdx_package);
+    for dependency in package.dependencies.iter().filter_map(|id| id_of(id)) {
+      relationships.push(json!({
+        "spdxElementId": package_id(index),
+        "relationshipType": "DEPENDS_ON",
+        "relatedSpdxElement": dependency,
+      }));
+    }
+  }
+
+  for (prefix, purpose, files) in [
+    ("SPDXRef-Sidecar", "APPLICATION", sidecars),
+    ("SPDXRef-Frontend", "FILE", frontend),
+  ] {
+    for (index, file) in files.iter().enumerate() {
+      let id = format!("{prefix}-{index}");
+      let mut package = spdx_package(&id, &file.name, None, Some(&file.sha256), None);
+      package["primaryPackagePurpose"] = json!(purpose);
+      packages.push(package);
+      relationships.push(json!({
+        "spdxElementId": ARTIFACT_ID,
+        "relationshipType": "CONTAINS",
+        "relatedSpdxElement": id,
+      }));
+    }
+  }
+
+  json!({
+    "spdxVersion": "SPDX-2.3",
+    "dataLicense": "CC0-1.0",
+    "SPDXID": "SPDXRef-DOCUMENT",
+    "name": artifact.name,
+    "documentNamespace": format!(
+      "https://spdx.org/spdxdocs/{}-{}-{}",
+      settings.product_name().replace(char::is_whitespace, "-"),
+      settings.version_string(),
+      artifact.sha256.unwrap_or(artifact.name),
+    ),
+    "creationInfo": {
+      "created": timestamp,
+      "creators": [format!("Tool: tauri-bundler-{}", env!("CARGO_PKG_VERSION"))],
+    },
+    "documentDescribes": [ARTIFACT_ID],
+    "packages": packages,
+    "relationships": relationships,
+  })
+}
+
+fn spdx_package(
+  id: &str,
+  name: &str,
+  version: Option<&str>,
+  sha256: Option<&str>,
+  license: Option<&str>,
+) -> Value {
+  let mut package = json!({
+    "SPDXID": id,
+    "name": name,
+    "downloadLocation": "NOASSERTION",
+    "filesAnalyzed": false,
+    "licenseConcluded": "NOASSERTION",
+    "licenseDeclared": license.map_or_else(|| "NOASSERTION".into(), license_expression),
+    "copyrightText": "NOASSERTION",
+  });
+  if let Some(version) = version {
+    package["versionInfo"] = json!(version);
+  }
+  if let Some(sha256) = sha256 {
+    package["checksums"] = json!([{ "algorithm": "SHA256", "checksumValue": sha256 }]);
+  }
+  package
+}
+
+/// The package URL of the crate, see <https://github.com/package-url/purl-spec>.
+fn purl(package: &SbomPackage) -> String {
+  format!("pkg:cargo/{}@{}", package.name, package.version)
+}
+
+/// The repository URL of a git source such as `git+https://github.com/tauri-apps/tauri?branch=dev#<commit>`.
+fn git_url(source: &str) -> Option<&str> {
+  source.strip_prefix("git+")
+}
+
+/// The SPDX download location of the crate.
+fn download_location(package: &SbomPackage) -> String {
+  match package.source.as_deref() {
+    Some(source) if source.contains("github.com/rust-lang/crates.io-index") => format!(
+      "https://crates.io/api/v1/crates/{}/{}/download",
+      package.name, package.version
+    ),
+    Some(source) => {
+      git_url(source).map_or_else(|| "NOASSERTION".into(), |url| format!("git+{url}"))
+    }
+    None => "NOASSERTION".into(),
+  }
+}
+
+/// Converts the deprecated `MIT/Apache-2.0` license syntax to an SPDX expression.
+fn license_expression(license: &str) -> String {
+  license
+    .split('/')
+    .map(str::trim)
+    .collect::<Vec<_>>()
+    .join(" OR ")
+}
+
+fn slash_path(path: &Path) -> String {
+  path
+    .components()
+    .map(|c| c.as_os_str().to_string_lossy())
+    .collect::<Vec<_>>()
+    .join("/")
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn licenses() {
+    assert_eq!(license_expression("MIT/Apache-2.0"), "MIT OR Apache-2.0");
+    assert_eq!(license_expression("Apache-2.0 OR MIT"), "Apache-2.0 OR MIT");
+  }
+
+  #[test]
+  fn package_url() {
+    let package = SbomPackage {
+      id: "serde 1.0.0".into(),
+      name: "serde".into(),
+      version: "1.0.0".into(),
+      license: None,
+      source: None,
+      dependencies: Vec::new(),
+    };
+    assert_eq!(purl(&package), "pkg:cargo/serde@1.0.0");
+    assert_eq!(download_location(&package), "NOASSERTION");
+
+    let package = SbomPackage {
+      source: Some("registry+https://github.com/rust-lang/crates.io-index".into()),
+      ..package
+    };
+    assert_eq!(
+      download_location(&package),
+      "https://crates.io/api/v1/crates/serde/1.0.0/download"
+    );
+  }
+}
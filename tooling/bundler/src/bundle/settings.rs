@@ -4,16 +4,22 @@
 // SPDX-License-Identifier: MIT
 
 use super::category::AppCategory;
-use crate::bundle::{common, platform::target_triple};
+use crate::bundle::{common, platform::target_triple, WindowsSigner};
 pub use tauri_utils::config::WebviewInstallMode;
 use tauri_utils::{
-  config::{BundleType, DeepLinkProtocol, FileAssociation, NSISInstallerMode, NsisCompression},
+  config::{
+    BundleType, DeepLinkProtocol, DmgLicense, FileAssociation, LinuxInstallPrefix,
+    LinuxServiceConfig, NSISInstallerMode, NotarizationConfig, NsisComponent, NsisCompression,
+    NsisWebInstallerConfig, SnapConfinement, SnapGrade, WixCustomAction, WixInstallMode,
+    WixRegistryValue, WixService, WixUpgradeConfig,
+  },
   resources::{external_binaries, ResourcePaths},
 };
 
 use std::{
   collections::HashMap,
   path::{Path, PathBuf},
+  sync::Arc,
 };
 
 /// The type of the package we're bundling.
@@ -34,8 +40,18 @@ pub enum PackageType {
   Rpm,
   /// The Linux AppImage bundle (.AppImage).
   AppImage,
+  /// The Linux Flatpak bundle (.flatpak).
+  Flatpak,
+  /// The Linux Snap bundle (.snap).
+  Snap,
+  /// The Windows MSIX package (.msix).
+  Msix,
+  /// The Windows portable app archive (.zip).
+  Portable,
   /// The macOS DMG bundle (.dmg).
   Dmg,
+  /// The macOS installer package (.pkg).
+  Pkg,
   /// The Updater bundle.
   Updater,
 }
@@ -50,13 +66,18 @@ impl From<BundleType> for PackageType {
       BundleType::Nsis => Self::Nsis,
       BundleType::App => Self::MacOsBundle,
       BundleType::Dmg => Self::Dmg,
+      BundleType::Flatpak => Self::Flatpak,
+      BundleType::Snap => Self::Snap,
+      BundleType::Msix => Self::Msix,
+      BundleType::Pkg => Self::Pkg,
+      BundleType::Portable => Self::Portable,
     }
   }
 }
 
 impl PackageType {
   /// Maps a short name to a PackageType.
-  /// Possible values are "deb", "ios", "msi", "app", "rpm", "appimage", "dmg", "updater".
+  /// Possible values are "deb", "ios", "msi", "app", "rpm", "appimage", "flatpak", "snap", "msix", "portable", "dmg", "pkg", "updater".
   pub fn from_short_name(name: &str) -> Option<PackageType> {
     // Other types we may eventually want to support: apk.
     match name {
@@ -67,7 +88,12 @@ impl PackageType {
       "app" => Some(PackageType::MacOsBundle),
       "rpm" => Some(PackageType::Rpm),
       "appimage" => Some(PackageType::AppImage),
+      "flatpak" => Some(PackageType::Flatpak),
+      "snap" => Some(PackageType::Snap),
+      "msix" => Some(PackageType::Msix),
+      "portable" => Some(PackageType::Portable),
       "dmg" => Some(PackageType::Dmg),
+      "pkg" => Some(PackageType::Pkg),
       "updater" => Some(PackageType::Updater),
       _ => None,
     }
@@ -84,7 +110,12 @@ impl PackageType {
       PackageType::MacOsBundle => "app",
       PackageType::Rpm => "rpm",
       PackageType::AppImage => "appimage",
+      PackageType::Flatpak => "flatpak",
+      PackageType::Snap => "snap",
+      PackageType::Msix => "msix",
+      PackageType::Portable => "portable",
       PackageType::Dmg => "dmg",
+      PackageType::Pkg => "pkg",
       PackageType::Updater => "updater",
     }
   }
@@ -109,7 +140,12 @@ impl PackageType {
       PackageType::Deb => 0,
       PackageType::Rpm => 0,
       PackageType::AppImage => 0,
+      PackageType::Flatpak => 0,
+      PackageType::Snap => 0,
+      PackageType::Msix => 0,
+      PackageType::Portable => 0,
       PackageType::Dmg => 1,
+      PackageType::Pkg => 1,
       PackageType::Updater => 2,
     }
   }
@@ -124,14 +160,24 @@ const ALL_PACKAGE_TYPES: &[PackageType] = &[
   PackageType::WindowsMsi,
   #[cfg(target_os = "windows")]
   PackageType::Nsis,
+  #[cfg(target_os = "windows")]
+  PackageType::Msix,
+  #[cfg(target_os = "windows")]
+  PackageType::Portable,
   #[cfg(target_os = "macos")]
   PackageType::MacOsBundle,
   #[cfg(target_os = "linux")]
   PackageType::Rpm,
   #[cfg(target_os = "macos")]
   PackageType::Dmg,
+  #[cfg(target_os = "macos")]
+  PackageType::Pkg,
   #[cfg(target_os = "linux")]
   PackageType::AppImage,
+  #[cfg(target_os = "linux")]
+  PackageType::Flatpak,
+  #[cfg(target_os = "linux")]
+  PackageType::Snap,
   PackageType::Updater,
 ];
 
@@ -159,8 +205,21 @@ pub struct UpdaterSettings {
   pub v1_compatible: bool,
   /// Signature public key.
   pub pubkey: String,
+  /// Additional public keys trusted by the updater with their validity window, e.g. while rotating the signing key.
+  pub trusted_keys: Vec<tauri_utils::updater::TrustedKey>,
   /// Args to pass to `msiexec.exe` to run the updater on Windows.
   pub msiexec_args: &'static [&'static str],
+  /// Previous releases to generate differential (delta) update artifacts against.
+  pub delta_bases: Vec<UpdaterDeltaBase>,
+}
+
+/// A previous release used as the base of a differential update artifact.
+#[derive(Debug, Clone)]
+pub struct UpdaterDeltaBase {
+  /// The version of the previous release.
+  pub version: String,
+  /// Directory containing the update artifacts of the previous release.
+  pub path: PathBuf,
 }
 
 /// The Linux debian bundle settings.
@@ -194,7 +253,11 @@ pub struct DebianSettings {
   pub priority: Option<String>,
   /// Path of the uncompressed Changelog file, to be stored at /usr/share/doc/package-name/changelog.gz. See
   /// <https://www.debian.org/doc/debian-policy/ch-docs.html#changelog-files-and-release-notes>
+  ///
+  /// A Markdown file is converted to the Debian changelog format.
   pub changelog: Option<PathBuf>,
+  /// Where the app binaries and resources are installed.
+  pub install_prefix: LinuxInstallPrefix,
   /// Path to script that will be executed before the package is unpacked. See
   /// <https://www.debian.org/doc/debian-policy/ch-maintainerscripts.html>
   pub pre_install_script: Option<PathBuf>,
@@ -214,6 +277,42 @@ pub struct DebianSettings {
 pub struct AppImageSettings {
   /// The files to include in the Appimage Binary.
   pub files: HashMap<PathBuf, PathBuf>,
+  /// The target sysroot when cross compiling, used to find the webkit2gtk helper processes.
+  pub sysroot: Option<PathBuf>,
+  /// The update information embedded in the AppImage, e.g. `zsync|https://example.com/app.AppImage.zsync`.
+  ///
+  /// A `.zsync` file is generated next to the AppImage when set.
+  pub update_information: Option<String>,
+}
+
+/// The Linux Flatpak bundle settings.
+#[derive(Clone, Debug, Default)]
+pub struct FlatpakSettings {
+  /// The Flatpak runtime.
+  pub runtime: String,
+  /// The Flatpak runtime version.
+  pub runtime_version: String,
+  /// The Flatpak SDK used to build the bundle.
+  pub sdk: String,
+  /// The sandbox permissions, see <https://docs.flatpak.org/en/latest/sandbox-permissions.html>.
+  pub finish_args: Vec<String>,
+  /// The files to include in the Flatpak, with the destination relative to `/app`.
+  pub files: HashMap<PathBuf, PathBuf>,
+}
+
+/// The Linux Snap bundle settings.
+#[derive(Clone, Debug, Default)]
+pub struct SnapSettings {
+  /// The base snap providing the run-time environment.
+  pub base: String,
+  /// The snap confinement.
+  pub confinement: SnapConfinement,
+  /// The snap grade.
+  pub grade: SnapGrade,
+  /// The interfaces the app connects to, see <https://snapcraft.io/docs/supported-interfaces>.
+  pub plugs: Vec<String>,
+  /// The files to include in the Snap, with the destination relative to the snap root.
+  pub files: HashMap<PathBuf, PathBuf>,
 }
 
 /// The RPM bundle settings.
@@ -245,6 +344,10 @@ pub struct RpmSettings {
   #[doc = include_str!("./linux/templates/main.desktop")]
   /// ```
   pub desktop_template: Option<PathBuf>,
+  /// Path to a Markdown changelog, converted to the package changelog entries.
+  pub changelog: Option<PathBuf>,
+  /// Where the app binaries and resources are installed.
+  pub install_prefix: LinuxInstallPrefix,
   /// Path to script that will be executed before the package is unpacked. See
   /// <http://ftp.rpm.org/max-rpm/s1-rpm-inside-scripts.html>
   pub pre_install_script: Option<PathBuf>,
@@ -282,6 +385,8 @@ pub struct Size {
 pub struct DmgSettings {
   /// Image to use as the background in dmg file. Accepted formats: `png`/`jpg`/`gif`.
   pub background: Option<PathBuf>,
+  /// A map of languages and the background image in that language, building one disk image per language.
+  pub localized_backgrounds: HashMap<String, PathBuf>,
   /// Position of volume window on screen.
   pub window_position: Option<Position>,
   /// Size of volume window.
@@ -290,6 +395,36 @@ pub struct DmgSettings {
   pub app_position: Position,
   /// Position of application folder on window.
   pub application_folder_position: Position,
+  /// The volume name. Defaults to the product name.
+  pub volume_name: Option<String>,
+  /// Path to the `.icns` volume icon. Defaults to the app icon.
+  pub volume_icon: Option<PathBuf>,
+  /// Size of the icons on the window.
+  pub icon_size: Option<u32>,
+  /// Size of the icon labels on the window.
+  pub text_size: Option<u32>,
+  /// The license agreement displayed when the disk image is opened. Defaults to the bundle license file.
+  pub license: Option<DmgLicense>,
+  /// Identity to use for signing the disk image. Defaults to the macOS signing identity.
+  pub signing_identity: Option<String>,
+  /// Notarize the disk image.
+  pub notarize: bool,
+}
+
+/// The macOS installer package (.pkg) settings.
+#[derive(Clone, Debug, Default)]
+pub struct PkgSettings {
+  /// The folder where the app bundle is installed.
+  pub install_location: PathBuf,
+  /// Path to script that will be executed before the app is installed.
+  pub pre_install_script: Option<PathBuf>,
+  /// Path to script that will be executed after the app is installed.
+  pub post_install_script: Option<PathBuf>,
+  /// Additional component packages to include in the installer.
+  pub component_packages: Vec<PathBuf>,
+  /// Identity to use for signing the installer package, a `Developer ID Installer` identity
+  /// which is different from the identity signing the app bundle.
+  pub signing_identity: Option<String>,
 }
 
 /// The macOS bundle settings.
@@ -328,6 +463,8 @@ pub struct MacOsSettings {
   pub entitlements: Option<String>,
   /// Path to the Info.plist file for the bundle.
   pub info_plist_path: Option<PathBuf>,
+  /// Notarization settings.
+  pub notarization: NotarizationConfig,
 }
 
 /// Configuration for a target language for the WiX build.
@@ -381,6 +518,48 @@ pub struct WixSettings {
   pub dialog_image_path: Option<PathBuf>,
   /// Enables FIPS compliant algorithms.
   pub fips_compliant: bool,
+  /// Whether the installation will be for all users or just the current user.
+  pub install_mode: WixInstallMode,
+  /// The major upgrade behavior when another version of the app is already installed.
+  pub upgrade: WixUpgradeConfig,
+  /// Custom actions to run during the installation or uninstallation.
+  pub custom_actions: Vec<WixCustomAction>,
+  /// Additional registry values written by the installer and removed on uninstall.
+  pub registry: Vec<WixRegistryValue>,
+  /// Windows services installed from the app binaries.
+  pub services: Vec<WixService>,
+  /// Automatically reference the `ComponentGroup` elements defined in the fragments.
+  pub merge_fragments: bool,
+}
+
+/// Settings specific to the MSIX package.
+#[derive(Clone, Debug)]
+pub struct MsixSettings {
+  /// The package identity name. Defaults to the bundle identifier.
+  pub identity_name: Option<String>,
+  /// The package publisher, which must match the subject of the signing certificate.
+  pub publisher: String,
+  /// The publisher name displayed to users. Defaults to the bundle publisher or the product name.
+  pub publisher_display_name: Option<String>,
+  /// Additional capabilities declared by the package. `runFullTrust` is always declared.
+  pub capabilities: Vec<String>,
+  /// The minimum Windows version required by the package.
+  pub min_version: String,
+}
+
+/// Settings specific to the portable app archive.
+#[derive(Clone, Debug)]
+pub struct PortableSettings {
+  /// Whether the app stores its data in a `data` folder next to the executable.
+  pub data_next_to_executable: bool,
+}
+
+impl Default for PortableSettings {
+  fn default() -> Self {
+    Self {
+      data_next_to_executable: true,
+    }
+  }
 }
 
 /// Settings specific to the NSIS implementation.
@@ -457,6 +636,16 @@ pub struct NsisSettings {
   /// !macroend
   /// ```
   pub installer_hooks: Option<PathBuf>,
+  /// Optional components the user can select on the installer components page.
+  ///
+  /// Each component installs a group of resources,
+  /// which are no longer installed unconditionally with the app.
+  pub components: Vec<NsisComponent>,
+  /// Download the components on demand instead of embedding them in the installer.
+  pub web_installer: Option<NsisWebInstallerConfig>,
+  /// Paths to `.nsh` files defining custom installer pages,
+  /// included in order after the components page and before the install directory page.
+  pub custom_pages: Vec<PathBuf>,
 }
 
 /// The Windows bundle settings.
@@ -475,6 +664,10 @@ pub struct WindowsSettings {
   pub wix: Option<WixSettings>,
   /// Nsis configuration.
   pub nsis: Option<NsisSettings>,
+  /// MSIX configuration.
+  pub msix: Option<MsixSettings>,
+  /// Portable app archive configuration.
+  pub portable: PortableSettings,
   /// The path to the application icon. Defaults to `./icons/icon.ico`.
   pub icon_path: PathBuf,
   /// The installation mode for the Webview2 runtime.
@@ -505,6 +698,11 @@ pub struct WindowsSettings {
   /// if you are on another platform and want to cross-compile and sign you will
   /// need to use another tool like `osslsigncode`.
   pub sign_command: Option<String>,
+  /// The signer of the executables, sidecars, libraries and installers.
+  ///
+  /// Takes precedence over [`Self::certificate_thumbprint`] and [`Self::sign_command`].
+  /// Use [`crate::signer_from_config`] to create it from the configuration, or implement [`WindowsSigner`] to plug in your own.
+  pub signer: Option<Arc<dyn WindowsSigner>>,
 }
 
 impl Default for WindowsSettings {
@@ -516,11 +714,14 @@ impl Default for WindowsSettings {
       tsp: false,
       wix: None,
       nsis: None,
+      msix: None,
+      portable: Default::default(),
       icon_path: PathBuf::from("icons/icon.ico"),
       webview_install_mode: Default::default(),
       webview_fixed_runtime_path: None,
       allow_downgrades: true,
       sign_command: None,
+      signer: None,
     }
   }
 }
@@ -593,8 +794,16 @@ pub struct BundleSettings {
   pub appimage: AppImageSettings,
   /// Rpm-specific settings.
   pub rpm: RpmSettings,
+  /// Flatpak-specific settings.
+  pub flatpak: FlatpakSettings,
+  /// Snap-specific settings.
+  pub snap: SnapSettings,
+  /// The systemd user service installed with the Debian and RPM packages.
+  pub linux_service: Option<LinuxServiceConfig>,
   /// DMG-specific settings.
   pub dmg: DmgSettings,
+  /// PKG-specific settings.
+  pub pkg: PkgSettings,
   /// MacOS-specific settings.
   pub macos: MacOsSettings,
   /// Updater configuration.
@@ -682,6 +891,8 @@ pub struct Settings {
   binaries: Vec<BundleBinary>,
   /// The target triple.
   target: String,
+  /// The UNIX timestamp used for the bundled files in reproducible mode.
+  source_date_epoch: Option<u64>,
 }
 
 /// A builder for [`Settings`].
@@ -782,6 +993,7 @@ impl SettingsBuilder {
         ..self.bundle_settings
       },
       target,
+      source_date_epoch: None,
     })
   }
 }
@@ -863,10 +1075,21 @@ impl Settings {
       .replace("darwin", "macos");
 
     let platform_types = match target_os.as_str() {
-      "macos" => vec![PackageType::MacOsBundle, PackageType::Dmg],
+      "macos" => vec![PackageType::MacOsBundle, PackageType::Dmg, PackageType::Pkg],
       "ios" => vec![PackageType::IosBundle],
-      "linux" => vec![PackageType::Deb, PackageType::Rpm, PackageType::AppImage],
-      "windows" => vec![PackageType::WindowsMsi, PackageType::Nsis],
+      "linux" => vec![
+        PackageType::Deb,
+        PackageType::Rpm,
+        PackageType::AppImage,
+        PackageType::Flatpak,
+        PackageType::Snap,
+      ],
+      "windows" => vec![
+        PackageType::WindowsMsi,
+        PackageType::Nsis,
+        PackageType::Msix,
+        PackageType::Portable,
+      ],
       os => {
         return Err(crate::Error::GenericError(format!(
           "Native {os} bundles not yet supported."
@@ -888,7 +1111,22 @@ impl Settings {
       }
       Ok(types)
     } else {
-      Ok(platform_types)
+      // bundles that require extra tooling or are an alternative distribution must be explicitly requested
+      Ok(
+        platform_types
+          .into_iter()
+          .filter(|t| {
+            !matches!(
+              t,
+              PackageType::Flatpak
+                | PackageType::Snap
+                | PackageType::Msix
+                | PackageType::Pkg
+                | PackageType::Portable
+            )
+          })
+          .collect(),
+      )
     }
   }
 
@@ -1061,11 +1299,31 @@ impl Settings {
     &self.bundle_settings.rpm
   }
 
+  /// Returns the Flatpak settings.
+  pub fn flatpak(&self) -> &FlatpakSettings {
+    &self.bundle_settings.flatpak
+  }
+
+  /// Returns the Snap settings.
+  pub fn snap(&self) -> &SnapSettings {
+    &self.bundle_settings.snap
+  }
+
+  /// Returns the systemd user service installed with the Debian and RPM packages.
+  pub fn linux_service(&self) -> Option<&LinuxServiceConfig> {
+    self.bundle_settings.linux_service.as_ref()
+  }
+
   /// Returns the DMG settings.
   pub fn dmg(&self) -> &DmgSettings {
     &self.bundle_settings.dmg
   }
 
+  /// Returns the PKG settings.
+  pub fn pkg(&self) -> &PkgSettings {
+    &self.bundle_settings.pkg
+  }
+
   /// Returns the MacOS settings.
   pub fn macos(&self) -> &MacOsSettings {
     &self.bundle_settings.macos
@@ -1080,4 +1338,27 @@ impl Settings {
   pub fn updater(&self) -> Option<&UpdaterSettings> {
     self.bundle_settings.updater.as_ref()
   }
+
+  /// Sets the previous releases to generate differential update artifacts against.
+  ///
+  /// Does nothing if the updater is not enabled.
+  pub fn set_updater_delta_bases(&mut self, bases: Vec<UpdaterDeltaBase>) {
+    if let Some(updater) = self.bundle_settings.updater.as_mut() {
+      updater.delta_bases = bases;
+    }
+  }
+
+  /// Enables the reproducible mode, normalizing the file timestamps, ordering and archive metadata
+  /// of the deb, rpm, AppImage and NSIS bundles to the given UNIX timestamp, usually read from `SOURCE_DATE_EPOCH`.
+  /// The DMG bundles are not reproducible as `hdiutil` writes the build time to the image.
+  ///
+  /// A build attestation with the artifact hashes is written to the bundle directory.
+  pub fn set_source_date_epoch(&mut self, source_date_epoch: u64) {
+    self.source_date_epoch.replace(source_date_epoch);
+  }
+
+  /// Returns the UNIX timestamp used for the bundled files if the reproducible mode is enabled.
+  pub fn source_date_epoch(&self) -> Option<u64> {
+    self.source_date_epoch
+  }
 }
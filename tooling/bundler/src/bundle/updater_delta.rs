@@ -0,0 +1,174 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use super::common::{self, CommandExt};
+
+use crate::{
+  bundle::{Bundle, PackageType},
+  Settings, UpdaterSettings,
+};
+use tauri_utils::{
+  display_path,
+  updater::{
+    ArtifactDeltas, DeltaArtifact, DeltaFormat, DELTAS_FILE_EXTENSION, ZSYNC_FILE_EXTENSION,
+  },
+};
+
+use std::{ffi::OsStr, path::PathBuf, process::Command};
+
+use anyhow::Context;
+
+/// Generates zstd patches of each update artifact against the configured previous releases.
+///
+/// For each update artifact, a `<artifact>.<base version>.patch` file is created for every base
+/// that has an artifact of the same kind and target, and the delta metadata is written to `<artifact>.deltas.json`.
+/// Returns the paths of the generated patches so they are signed like any other update artifact.
+pub fn bundle_project(
+  settings: &Settings,
+  updater: &UpdaterSettings,
+  bundles: &[Bundle],
+) -> crate::Result<Vec<PathBuf>> {
+  let artifacts = bundles
+    .iter()
+    .filter(|bundle| {
+      if updater.v1_compatible {
+        matches!(bundle.package_type, PackageType::Updater)
+      } else {
+        matches!(
+          bundle.package_type,
+          PackageType::Updater
+            | PackageType::Nsis
+            | PackageType::WindowsMsi
+            | PackageType::AppImage
+        )
+      }
+    })
+    .flat_map(|bundle| bundle.bundle_paths.iter())
+    // the AppImage zsync file is already a differential update mechanism
+    .filter(|path| path.is_file() && path.extension() != Some(OsStr::new(ZSYNC_FILE_EXTENSION)))
+    .cloned()
+    .collect::<Vec<_>>();
+
+  if artifacts.is_empty() {
+    return Ok(Vec::new());
+  }
+
+  Command::new("zstd").arg("--version").output_ok().context(
+    "`zstd` is required to generate delta update artifacts but it was not found in PATH",
+  )?;
+
+  let version = settings.version_string();
+  let mut patches = Vec::new();
+
+  for artifact in &artifacts {
+    let file_name = artifact
+      .file_name()
+      .expect("update artifact has no file name")
+      .to_string_lossy()
+      .into_owned();
+
+    let mut deltas = Vec::new();
+
+    for base in &updater.delta_bases {
+      let base_artifact = base.path.join(base_artifact_name(
+        &file_name,
+        settings.product_name(),
+        version,
+        &base.version,
+      ));
+      if !base_artifact.is_file() {
+        log::warn!(
+          "skipping delta update artifact from {} for {}: base artifact {} not found",
+          base.version,
+          file_name,
+          display_path(&base_artifact)
+        );
+        continue;
+      }
+
+      let patch_name = format!("{file_name}.{}.patch", base.version);
+      let patch_path = artifact.with_file_name(&patch_name);
+
+      log::info!(action = "Bundling"; "{} ({} -> {})", display_path(&patch_path), base.version, version);
+
+      Command::new("zstd")
+        .arg("-19")
+        .arg("-f")
+        .arg("-q")
+        .arg(format!("--patch-from={}", base_artifact.display()))
+        .arg(artifact)
+        .arg("-o")
+        .arg(&patch_path)
+        .output_ok()
+        .with_context(|| {
+          format!(
+            "failed to generate delta update artifact for {} from {}",
+            file_name, base.version
+          )
+        })?;
+
+      deltas.push(DeltaArtifact {
+        from: base.version.clone(),
+        file: patch_name,
+        base_sha256: common::sha256_file(&base_artifact)?,
+        format: DeltaFormat::ZstdPatch,
+      });
+      patches.push(patch_path);
+    }
+
+    if deltas.is_empty() {
+      continue;
+    }
+
+    let deltas_path = artifact.with_file_name(format!("{file_name}.{DELTAS_FILE_EXTENSION}"));
+    let metadata = ArtifactDeltas {
+      version: version.to_string(),
+      artifact: file_name,
+      sha256: common::sha256_file(artifact)?,
+      deltas,
+    };
+    let file = common::create_file(&deltas_path)?;
+    serde_json::to_writer_pretty(file, &metadata)?;
+  }
+
+  Ok(patches)
+}
+
+/// The file name of the artifact with the same kind and target in the given previous release.
+///
+/// The versioned update artifacts are named `<product name>_<version><target and kind suffix>`,
+/// e.g. `app_1.0.0_x64-setup.exe`, so only that version prefix is replaced and the suffix must match.
+/// The unversioned ones, e.g. the macOS `app.app.tar.gz`, keep the same name across releases.
+fn base_artifact_name(
+  file_name: &str,
+  product_name: &str,
+  version: &str,
+  base_version: &str,
+) -> String {
+  match file_name.strip_prefix(&format!("{product_name}_{version}")) {
+    Some(suffix) => format!("{product_name}_{base_version}{suffix}"),
+    None => file_name.to_string(),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::base_artifact_name;
+
+  #[test]
+  fn base_artifact_name_matches_kind_and_target() {
+    assert_eq!(
+      base_artifact_name("app_1.1.0_x64-setup.exe", "app", "1.1.0", "1.0.0"),
+      "app_1.0.0_x64-setup.exe"
+    );
+    assert_eq!(
+      base_artifact_name("app 1_1.1.0_x64_en-US.msi", "app 1", "1.1.0", "1.0.0"),
+      "app 1_1.0.0_x64_en-US.msi"
+    );
+    assert_eq!(
+      base_artifact_name("app 1.app.tar.gz", "app 1", "1", "0.9.0"),
+      "app 1.app.tar.gz"
+    );
+  }
+}
@@ -5,7 +5,10 @@
 
 #[cfg(target_os = "windows")]
 pub mod msi;
+#[cfg(target_os = "windows")]
+pub mod msix;
 pub mod nsis;
+pub mod portable;
 pub mod sign;
 
 mod util;
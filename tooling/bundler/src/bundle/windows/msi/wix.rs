@@ -26,7 +26,13 @@ use std::{
   path::{Path, PathBuf},
   process::Command,
 };
-use tauri_utils::{config::WebviewInstallMode, display_path};
+use tauri_utils::{
+  config::{
+    WebviewInstallMode, WixCustomAction as WixCustomActionConfig, WixCustomActionStep,
+    WixInstallMode, WixRegistryRoot, WixRegistryValueType, WixServiceStart,
+  },
+  display_path,
+};
 use uuid::Uuid;
 
 // URLS for the WIX toolchain.  Can be used for cross-platform compilation.
@@ -76,6 +82,46 @@ struct Binary {
   id: String,
   /// the binary path.
   path: String,
+  /// the Windows services installed from this binary.
+  services: Vec<Service>,
+}
+
+/// A Windows service installed from one of the bundled binaries.
+#[derive(Serialize)]
+struct Service {
+  /// the id to use on the WIX XML.
+  id: String,
+  name: String,
+  display_name: String,
+  description: Option<String>,
+  arguments: Option<String>,
+  start: WixServiceStart,
+  /// whether the service is started after the installation.
+  start_on_install: bool,
+  account: Option<String>,
+}
+
+/// A custom action running a command from the installation directory.
+#[derive(Serialize)]
+struct CustomAction {
+  id: String,
+  command: String,
+  impersonate: &'static str,
+  return_type: &'static str,
+  /// the standard action the custom action is sequenced before.
+  before: &'static str,
+  condition: String,
+}
+
+/// A registry value written in the `RegistryEntries` component.
+/// The config enums serialize to the WiX attribute values.
+#[derive(Serialize)]
+struct RegistryValue {
+  root: WixRegistryRoot,
+  key: String,
+  name: Option<String>,
+  value: String,
+  value_type: WixRegistryValueType,
 }
 
 /// A Resource file to bundle with WIX.
@@ -540,6 +586,51 @@ pub fn build_wix_app_installer(
     to_json(settings.windows().allow_downgrades),
   );
 
+  let upgrade = settings
+    .windows()
+    .wix
+    .as_ref()
+    .map(|wix| wix.upgrade.clone())
+    .unwrap_or_default();
+  data.insert(
+    "allow_same_version_upgrades",
+    to_json(upgrade.allow_same_version_upgrades),
+  );
+  data.insert(
+    "upgrade_schedule",
+    // the config values serialize to the WiX attribute values
+    to_json(upgrade.schedule),
+  );
+  data.insert(
+    "downgrade_error_message",
+    to_json(
+      upgrade
+        .downgrade_error_message
+        .as_deref()
+        .map(html_escape)
+        .unwrap_or_else(|| "!(loc.DowngradeErrorMessage)".into()),
+    ),
+  );
+
+  let install_mode = settings
+    .windows()
+    .wix
+    .as_ref()
+    .map(|wix| wix.install_mode)
+    .unwrap_or_default();
+  data.insert(
+    "per_machine",
+    to_json(install_mode == WixInstallMode::PerMachine),
+  );
+  data.insert(
+    "current_user",
+    to_json(install_mode == WixInstallMode::CurrentUser),
+  );
+  data.insert(
+    "install_scope_selection",
+    to_json(install_mode == WixInstallMode::Both),
+  );
+
   let path_guid = generate_package_guid(settings).to_string();
   data.insert("path_component_guid", to_json(path_guid.as_str()));
 
@@ -549,7 +640,9 @@ pub fn build_wix_app_installer(
   let app_exe_name = settings.main_binary_name().to_string();
   data.insert("app_exe_name", to_json(app_exe_name));
 
-  let binaries = generate_binaries_data(settings)?;
+  let mut binaries = generate_binaries_data(settings)?;
+  let main_binary_services = assign_services(settings, install_mode, &mut binaries)?;
+  data.insert("main_binary_services", to_json(main_binary_services));
 
   let binaries_json = to_json(binaries);
   data.insert("binaries", binaries_json);
@@ -585,7 +678,19 @@ pub fn build_wix_app_installer(
   let mut enable_elevated_update_task = false;
 
   if let Some(wix) = &settings.windows().wix {
-    data.insert("component_group_refs", to_json(&wix.component_group_refs));
+    let mut component_group_refs = wix.component_group_refs.clone();
+    if wix.merge_fragments {
+      let current_dir = std::env::current_dir()?;
+      for fragment_path in &wix.fragment_paths {
+        let fragment = fs::read_to_string(current_dir.join(fragment_path))?;
+        for id in fragment_component_groups(&fragment) {
+          if !component_group_refs.contains(&id) {
+            component_group_refs.push(id);
+          }
+        }
+      }
+    }
+    data.insert("component_group_refs", to_json(component_group_refs));
     data.insert("component_refs", to_json(&wix.component_refs));
     data.insert("feature_group_refs", to_json(&wix.feature_group_refs));
     data.insert("feature_refs", to_json(&wix.feature_refs));
@@ -594,6 +699,27 @@ pub fn build_wix_app_installer(
     enable_elevated_update_task = wix.enable_elevated_update_task;
     custom_template_path.clone_from(&wix.template);
 
+    data.insert(
+      "custom_actions",
+      to_json(generate_custom_actions_data(&wix.custom_actions)?),
+    );
+    data.insert(
+      "registry_values",
+      to_json(
+        wix
+          .registry
+          .iter()
+          .map(|value| RegistryValue {
+            root: value.root,
+            key: html_escape(&value.key),
+            name: value.name.as_deref().map(html_escape),
+            value: html_escape(&value.value),
+            value_type: value.value_type,
+          })
+          .collect::<Vec<_>>(),
+      ),
+    );
+
     if let Some(banner_path) = &wix.banner_path {
       let filename = banner_path
         .file_name()
@@ -813,6 +939,120 @@ pub fn build_wix_app_installer(
   Ok(output_paths)
 }
 
+/// Whether the id is a valid MSI identifier, see <https://learn.microsoft.com/en-us/windows/win32/msi/identifier>.
+fn is_valid_identifier(id: &str) -> bool {
+  let mut chars = id.chars();
+  chars
+    .next()
+    .map_or(false, |c| c.is_ascii_alphabetic() || c == '_')
+    && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.')
+}
+
+/// Generates the data required for the custom actions.
+fn generate_custom_actions_data(
+  custom_actions: &[WixCustomActionConfig],
+) -> crate::Result<Vec<CustomAction>> {
+  let mut actions = Vec::new();
+  for action in custom_actions {
+    if !is_valid_identifier(&action.id) {
+      bail!(
+        "Invalid WiX custom action id `{}`, it must start with a letter or an underscore and only contain letters, digits, underscores and periods",
+        action.id
+      );
+    }
+    if actions.iter().any(|a: &CustomAction| a.id == action.id) {
+      bail!("Duplicated WiX custom action id `{}`", action.id);
+    }
+
+    let (before, default_condition) = match action.on {
+      WixCustomActionStep::Install => ("InstallFinalize", r#"NOT (REMOVE = "ALL")"#),
+      WixCustomActionStep::Uninstall => (
+        "RemoveFiles",
+        r#"(REMOVE = "ALL") AND NOT UPGRADINGPRODUCTCODE"#,
+      ),
+    };
+    actions.push(CustomAction {
+      id: action.id.clone(),
+      command: html_escape(&action.command),
+      impersonate: if action.elevated { "no" } else { "yes" },
+      return_type: if action.ignore_exit_code {
+        "ignore"
+      } else {
+        "check"
+      },
+      before,
+      condition: action
+        .condition
+        .clone()
+        .unwrap_or_else(|| default_condition.into()),
+    });
+  }
+  Ok(actions)
+}
+
+/// Assigns the configured services to the binaries installing them
+/// and returns the services installed from the main binary.
+fn assign_services(
+  settings: &Settings,
+  install_mode: WixInstallMode,
+  binaries: &mut [Binary],
+) -> crate::Result<Vec<Service>> {
+  let services = settings
+    .windows()
+    .wix
+    .as_ref()
+    .map(|wix| wix.services.as_slice())
+    .unwrap_or_default();
+  if !services.is_empty() && install_mode != WixInstallMode::PerMachine {
+    bail!("WiX services can only be installed with the `perMachine` install mode");
+  }
+
+  let regex = Regex::new(r"[^\w\d\.]")?;
+  let mut main_binary_services = Vec::new();
+  for service in services {
+    let binary = service.binary.trim_end_matches(".exe");
+    let data = Service {
+      id: regex
+        .replace_all(&service.name.replace('-', "_"), "")
+        .to_string(),
+      name: html_escape(&service.name),
+      display_name: html_escape(service.display_name.as_deref().unwrap_or(&service.name)),
+      description: service.description.as_deref().map(html_escape),
+      arguments: service.arguments.as_deref().map(html_escape),
+      start: service.start,
+      start_on_install: service.start == WixServiceStart::Auto,
+      account: service.account.as_deref().map(html_escape),
+    };
+
+    if binary == settings.main_binary_name().trim_end_matches(".exe") {
+      main_binary_services.push(data);
+    } else if let Some(bin) = binaries.iter_mut().find(|bin| {
+      Path::new(&bin.path)
+        .file_stem()
+        .map_or(false, |stem| stem == binary)
+    }) {
+      bin.services.push(data);
+    } else {
+      bail!(
+        "Failed to find the binary `{}` of the `{}` WiX service",
+        service.binary,
+        service.name
+      );
+    }
+  }
+
+  Ok(main_binary_services)
+}
+
+/// The ids of the `ComponentGroup` elements defined in a WiX fragment.
+fn fragment_component_groups(fragment: &str) -> Vec<String> {
+  let regex = Regex::new(r#"<ComponentGroup\s[^>]*?\bId="([^"]+)""#).unwrap();
+  regex
+    .captures_iter(fragment)
+    .map(|cap| cap[1].to_string())
+    .collect()
+}
+
 /// Generates the data required for the external binaries and extra binaries bundling.
 fn generate_binaries_data(settings: &Settings) -> crate::Result<Vec<Binary>> {
   let mut binaries = Vec::new();
@@ -839,6 +1079,7 @@ fn generate_binaries_data(settings: &Settings) -> crate::Result<Vec<Binary>> {
       id: regex
         .replace_all(&dest_filename.replace('-', "_"), "")
         .to_string(),
+      services: Vec::new(),
     });
   }
 
@@ -854,6 +1095,7 @@ fn generate_binaries_data(settings: &Settings) -> crate::Result<Vec<Binary>> {
         id: regex
           .replace_all(&bin.name().replace('-', "_"), "")
           .to_string(),
+        services: Vec::new(),
       })
     }
   }
@@ -1015,3 +1257,35 @@ fn generate_resource_data(settings: &Settings) -> crate::Result<ResourceMap> {
 
   Ok(resources)
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn component_groups() {
+    let fragment = r#"<Wix xmlns="http://schemas.microsoft.com/wix/2006/wi">
+  <Fragment>
+    <ComponentGroup Id="Drivers" Directory="INSTALLDIR">
+      <ComponentGroupRef Id="Shared" />
+    </ComponentGroup>
+    <ComponentGroup
+      Directory="INSTALLDIR"
+      Id="Shared" />
+  </Fragment>
+</Wix>"#;
+    assert_eq!(
+      fragment_component_groups(fragment),
+      vec!["Drivers", "Shared"]
+    );
+  }
+
+  #[test]
+  fn identifiers() {
+    assert!(is_valid_identifier("RegisterDriver"));
+    assert!(is_valid_identifier("_register.driver_2"));
+    assert!(!is_valid_identifier("2RegisterDriver"));
+    assert!(!is_valid_identifier("register-driver"));
+    assert!(!is_valid_identifier(""));
+  }
+}
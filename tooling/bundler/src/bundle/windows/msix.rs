@@ -0,0 +1,289 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+// The MSIX package is built with `makeappx` from a package layout:
+//
+// bundle/msix/
+//     layout/                              # Package root
+//         AppxManifest.xml                 # Package manifest
+//         foobar.exe                       # Main binary, along the sidecars and resources
+//         Assets/                          # Logos generated from the app icons
+//     foobar_1.2.3_x64.msix                # The MSIX package
+
+use super::{nsis::add_build_number_if_needed, sign::try_sign, util::copy_app_files};
+use crate::{
+  bundle::common::{self, CommandExt},
+  Settings,
+};
+use anyhow::Context;
+use handlebars::Handlebars;
+use image::GenericImageView;
+use serde::Serialize;
+use std::{
+  fs,
+  path::{Path, PathBuf},
+  process::Command,
+};
+
+/// The logos required by the package manifest, with their size in pixels.
+const LOGOS: &[(&str, u32)] = &[
+  ("StoreLogo.png", 50),
+  ("Square44x44Logo.png", 44),
+  ("Square150x150Logo.png", 150),
+];
+
+/// Capabilities declared in the `uap` namespace.
+const UAP_CAPABILITIES: &[&str] = &[
+  "documentsLibrary",
+  "picturesLibrary",
+  "videosLibrary",
+  "musicLibrary",
+  "removableStorage",
+  "enterpriseAuthentication",
+  "sharedUserCertificates",
+  "contacts",
+  "appointments",
+  "userAccountInformation",
+];
+
+/// Capabilities declared as device capabilities.
+const DEVICE_CAPABILITIES: &[&str] = &[
+  "webcam",
+  "microphone",
+  "location",
+  "proximity",
+  "bluetooth",
+  "usb",
+  "humaninterfacedevice",
+  "serialcommunication",
+];
+
+/// Bundles the project.
+/// Returns a vector of PathBuf that shows where the MSIX package was created.
+pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<PathBuf>> {
+  let msix = settings.windows().msix.as_ref().ok_or_else(|| {
+    crate::Error::GenericError(
+      "the msix bundle requires `bundle > windows > msix > publisher` to be set".into(),
+    )
+  })?;
+
+  let arch = match settings.binary_arch() {
+    "x86_64" => "x64",
+    "x86" => "x86",
+    "aarch64" => "arm64",
+    target => {
+      return Err(crate::Error::ArchError(format!(
+        "unsupported architecture: {}",
+        target
+      )))
+    }
+  };
+
+  let package_name = format!(
+    "{}_{}_{}.msix",
+    settings.product_name(),
+    settings.version_string(),
+    arch
+  );
+
+  let base_dir = settings.project_out_directory().join("bundle/msix");
+  if base_dir.exists() {
+    fs::remove_dir_all(&base_dir).with_context(|| "Failed to remove old msix bundle")?;
+  }
+  let layout_dir = base_dir.join("layout");
+  let package_path = base_dir.join(&package_name);
+
+  log::info!(action = "Bundling"; "{} ({})", package_name, package_path.display());
+
+  let main_binary_path = copy_app_files(settings, &layout_dir)?;
+  let main_binary_name = main_binary_path
+    .file_name()
+    .expect("failed to extract main binary filename")
+    .to_string_lossy()
+    .into_owned();
+
+  generate_logos(settings, &layout_dir.join("Assets"))
+    .with_context(|| "Failed to generate package logos")?;
+
+  generate_manifest(
+    settings,
+    msix,
+    arch,
+    &main_binary_name,
+    &layout_dir.join("AppxManifest.xml"),
+  )
+  .with_context(|| "Failed to create AppxManifest.xml")?;
+
+  let makeappx = super::sign::sdk_tool("makeappx.exe")?.ok_or(crate::Error::MakeAppxNotFound)?;
+  Command::new(makeappx)
+    .arg("pack")
+    .arg("/o")
+    .arg("/d")
+    .arg(&layout_dir)
+    .arg("/p")
+    .arg(&package_path)
+    .output_ok()
+    .context("failed to run makeappx")?;
+
+  // the package is only installable if its signature matches the manifest publisher
+  try_sign(&package_path, settings)?;
+
+  fs::remove_dir_all(layout_dir)?;
+
+  Ok(vec![package_path])
+}
+
+/// Generates the package logos from the largest PNG app icon.
+fn generate_logos(settings: &Settings, assets_dir: &Path) -> crate::Result<()> {
+  let mut icon: Option<image::DynamicImage> = None;
+  for icon_path in settings.icon_files() {
+    let icon_path = icon_path?;
+    if icon_path.extension().map_or(true, |ext| ext != "png") {
+      continue;
+    }
+    let candidate = image::open(&icon_path)?;
+    if icon
+      .as_ref()
+      .map_or(true, |icon| candidate.width() > icon.width())
+    {
+      icon.replace(candidate);
+    }
+  }
+  let icon = icon.ok_or_else(|| {
+    crate::Error::GenericError("No usable PNG icon files found for the msix logos".to_owned())
+  })?;
+
+  fs::create_dir_all(assets_dir)?;
+  for (name, size) in LOGOS {
+    icon
+      .resize_exact(*size, *size, image::imageops::FilterType::Lanczos3)
+      .save(assets_dir.join(name))?;
+  }
+  Ok(())
+}
+
+/// Generates the package manifest.
+fn generate_manifest(
+  settings: &Settings,
+  msix: &crate::MsixSettings,
+  arch: &str,
+  main_binary_name: &str,
+  dest_path: &Path,
+) -> crate::Result<()> {
+  #[derive(Serialize)]
+  struct FileAssociation {
+    name: String,
+    description: Option<String>,
+    extensions: Vec<String>,
+  }
+
+  #[derive(Serialize)]
+  struct AppxManifestParams<'a> {
+    identity_name: &'a str,
+    publisher: &'a str,
+    version: String,
+    arch: &'a str,
+    display_name: &'a str,
+    publisher_display_name: &'a str,
+    description: &'a str,
+    min_version: &'a str,
+    executable: &'a str,
+    file_associations: Vec<FileAssociation>,
+    protocols: Vec<String>,
+    capabilities: Vec<Capability<'a>>,
+    device_capabilities: Vec<&'a str>,
+  }
+
+  #[derive(Serialize)]
+  struct Capability<'a> {
+    element: &'static str,
+    name: &'a str,
+  }
+
+  let file_associations = settings
+    .file_associations()
+    .map(|associations| {
+      associations
+        .iter()
+        .map(|association| FileAssociation {
+          // the association name must be lowercase
+          name: association
+            .name
+            .clone()
+            .unwrap_or_else(|| association.ext[0].to_string())
+            .to_lowercase(),
+          description: association.description.clone(),
+          extensions: association
+            .ext
+            .iter()
+            .map(|ext| format!(".{ext}").to_lowercase())
+            .collect(),
+        })
+        .collect()
+    })
+    .unwrap_or_default();
+
+  let protocols = settings
+    .deep_link_protocols()
+    .map(|protocols| {
+      protocols
+        .iter()
+        .flat_map(|protocol| protocol.schemes.iter())
+        .map(|scheme| scheme.to_lowercase())
+        .collect()
+    })
+    .unwrap_or_default();
+
+  let (device_capabilities, capabilities): (Vec<&str>, Vec<&str>) = msix
+    .capabilities
+    .iter()
+    .map(String::as_str)
+    .partition(|capability| DEVICE_CAPABILITIES.contains(capability));
+  let capabilities = capabilities
+    .into_iter()
+    .map(|name| Capability {
+      element: if UAP_CAPABILITIES.contains(&name) {
+        "uap:Capability"
+      } else {
+        "Capability"
+      },
+      name,
+    })
+    .collect();
+
+  let params = AppxManifestParams {
+    identity_name: msix
+      .identity_name
+      .as_deref()
+      .unwrap_or_else(|| settings.bundle_identifier()),
+    publisher: &msix.publisher,
+    version: add_build_number_if_needed(settings.version_string())?,
+    arch,
+    display_name: settings.product_name(),
+    publisher_display_name: msix
+      .publisher_display_name
+      .as_deref()
+      .or_else(|| settings.publisher())
+      .unwrap_or_else(|| settings.product_name()),
+    description: settings.short_description(),
+    min_version: &msix.min_version,
+    executable: main_binary_name,
+    file_associations,
+    protocols,
+    capabilities,
+    device_capabilities,
+  };
+
+  let mut handlebars = Handlebars::new();
+  handlebars
+    .register_template_string(
+      "AppxManifest.xml",
+      include_str!("./templates/AppxManifest.xml"),
+    )
+    .expect("Failed to register template for handlebars");
+
+  let file = common::create_file(dest_path)?;
+  handlebars.render_to_write("AppxManifest.xml", &params, file)?;
+  Ok(())
+}
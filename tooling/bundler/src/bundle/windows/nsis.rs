@@ -6,7 +6,7 @@ use crate::bundle::windows::sign::{sign_command, try_sign};
 
 use crate::{
   bundle::{
-    common::CommandExt,
+    common::{self, CommandExt},
     windows::util::{
       download_and_verify, download_webview2_bootstrapper, download_webview2_offline_installer,
       verify_file_hash, HashAlgorithm, NSIS_OUTPUT_FOLDER_NAME, NSIS_UPDATER_OUTPUT_FOLDER_NAME,
@@ -18,11 +18,14 @@ use tauri_utils::display_path;
 
 use anyhow::Context;
 use handlebars::{to_json, Handlebars};
-use tauri_utils::config::{NSISInstallerMode, NsisCompression, WebviewInstallMode};
+use serde::Serialize;
+use tauri_utils::config::{NSISInstallerMode, NsisComponent, NsisCompression, WebviewInstallMode};
+use zip::write::FileOptions;
 
 use std::{
   collections::BTreeMap,
-  fs,
+  fs::{self, File},
+  io::{self, Write},
   path::{Path, PathBuf},
   process::Command,
 };
@@ -122,7 +125,7 @@ fn get_and_extract_nsis(nsis_toolset_path: &Path, _tauri_tools_path: &Path) -> c
   Ok(())
 }
 
-fn add_build_number_if_needed(version_str: &str) -> anyhow::Result<String> {
+pub(super) fn add_build_number_if_needed(version_str: &str) -> anyhow::Result<String> {
   let version = semver::Version::parse(version_str).context("invalid app version")?;
   if !version.build.is_empty() {
     let build = version.build.parse::<u64>();
@@ -197,7 +200,7 @@ fn build_nsis_app_installer(
   data.insert("copyright", to_json(settings.copyright_string()));
 
   if settings.can_sign() {
-    let sign_cmd = format!("{:?}", sign_command("%1", &settings.sign_params())?);
+    let sign_cmd = format!("{:?}", sign_command("%1", settings)?);
     data.insert("uninstaller_sign_cmd", to_json(sign_cmd));
   }
 
@@ -213,6 +216,11 @@ fn build_nsis_app_installer(
     to_json(settings.windows().allow_downgrades),
   );
 
+  data.insert(
+    "reproducible",
+    to_json(settings.source_date_epoch().is_some()),
+  );
+
   if let Some(license_file) = settings.license_file() {
     let license_file = dunce::canonicalize(license_file)?;
     let license_file_with_bom = output_path.join("license_file");
@@ -257,6 +265,13 @@ fn build_nsis_app_installer(
     if let Some(start_menu_folder) = &nsis.start_menu_folder {
       data.insert("start_menu_folder", to_json(start_menu_folder));
     }
+
+    let custom_pages = nsis
+      .custom_pages
+      .iter()
+      .map(dunce::canonicalize)
+      .collect::<Result<Vec<_>, _>>()?;
+    data.insert("custom_pages", to_json(custom_pages));
   }
 
   let compression = settings
@@ -346,12 +361,37 @@ fn build_nsis_app_installer(
   let out_file = "nsis-output.exe";
   data.insert("out_file", to_json(out_file));
 
+  let package_base_name = format!(
+    "{}_{}_{}-setup",
+    settings.product_name(),
+    settings.version_string(),
+    arch,
+  );
+
+  let nsis_output_path = output_path.join(out_file);
+  let nsis_installer_path = settings.project_out_directory().to_path_buf().join(format!(
+    "bundle/{}/{}.exe",
+    if updater {
+      NSIS_UPDATER_OUTPUT_FOLDER_NAME
+    } else {
+      NSIS_OUTPUT_FOLDER_NAME
+    },
+    package_base_name
+  ));
+  fs::create_dir_all(nsis_installer_path.parent().unwrap())?;
+
   let resources = generate_resource_data(settings)?;
+  let components = nsis.map(|n| n.components.as_slice()).unwrap_or_default();
+  let (resources, components_resources) = split_components_resources(resources, components)?;
   let resources_dirs =
     std::collections::HashSet::<PathBuf>::from_iter(resources.values().map(|r| r.0.to_owned()));
 
-  let mut resources_ancestors = resources_dirs
+  // the components directories are removed by the uninstaller as well
+  let mut resources_ancestors = components_resources
     .iter()
+    .flat_map(|resources| resources.values())
+    .map(|r| r.0.as_path())
+    .chain(resources_dirs.iter().map(PathBuf::as_path))
     .flat_map(|p| p.ancestors())
     .collect::<Vec<_>>();
   resources_ancestors.sort_unstable();
@@ -359,35 +399,66 @@ fn build_nsis_app_installer(
   resources_ancestors.sort_by_key(|p| std::cmp::Reverse(p.components().count()));
   resources_ancestors.pop(); // Last one is always ""
 
-  // We need to convert / to \ for nsis to move the files into the correct dirs
-  #[cfg(not(target_os = "windows"))]
-  let resources: ResourcesMap = resources
+  let resources = to_nsis_resources(resources);
+  let resources_ancestors = resources_ancestors
     .into_iter()
-    .map(|(r, p)| {
-      (
-        r,
-        (
-          p.0.display().to_string().replace('/', "\\").into(),
-          p.1.display().to_string().replace('/', "\\").into(),
-        ),
-      )
-    })
-    .collect();
-  #[cfg(not(target_os = "windows"))]
-  let resources_ancestors: Vec<PathBuf> = resources_ancestors
-    .into_iter()
-    .map(|p| p.display().to_string().replace('/', "\\").into())
-    .collect();
-  #[cfg(not(target_os = "windows"))]
-  let resources_dirs: Vec<PathBuf> = resources_dirs
-    .into_iter()
-    .map(|p| p.display().to_string().replace('/', "\\").into())
-    .collect();
+    .map(to_nsis_path)
+    .collect::<Vec<_>>();
+  let resources_dirs = resources_dirs
+    .iter()
+    .map(|p| to_nsis_path(p))
+    .collect::<Vec<_>>();
 
   data.insert("resources_ancestors", to_json(resources_ancestors));
   data.insert("resources_dirs", to_json(resources_dirs));
   data.insert("resources", to_json(&resources));
 
+  let web_installer_url = nsis
+    .and_then(|n| n.web_installer.as_ref())
+    .map(|web_installer| web_installer.url.trim_end_matches('/'));
+  if web_installer_url.is_some() && components.is_empty() {
+    log::warn!("The NSIS web installer is enabled but no components are defined in `bundle > windows > nsis > components`, the installer won't download anything");
+  }
+
+  let mut components_data = Vec::new();
+  let mut component_archives = Vec::new();
+  for (component, resources) in components.iter().zip(components_resources) {
+    let (archive_name, archive_sha256) = if web_installer_url.is_some() {
+      let archive_name = format!(
+        "{}_{}_{}_{}.zip",
+        settings.product_name(),
+        settings.version_string(),
+        arch,
+        component.id
+      );
+      let archive_path = nsis_installer_path.with_file_name(&archive_name);
+      create_component_archive(&resources, &archive_path)
+        .with_context(|| format!("Failed to create the `{}` component archive", component.id))?;
+      let sha256 = common::sha256_file(&archive_path)?.to_uppercase();
+      component_archives.push(archive_path);
+      (Some(encode_url_path_segment(&archive_name)), Some(sha256))
+    } else {
+      (None, None)
+    };
+
+    let resources_dirs =
+      std::collections::BTreeSet::<PathBuf>::from_iter(resources.values().map(|r| r.0.to_owned()));
+
+    components_data.push(ComponentData {
+      name: &component.name,
+      description: component.description.as_deref(),
+      selected: component.selected,
+      resources: to_nsis_resources(resources),
+      resources_dirs: resources_dirs.iter().map(|p| to_nsis_path(p)).collect(),
+      archive_name,
+      archive_sha256,
+    });
+  }
+  data.insert("components", to_json(&components_data));
+  if let Some(url) = web_installer_url {
+    data.insert("web_installer_url", to_json(url));
+  }
+
   let binaries = generate_binaries_data(settings)?;
   data.insert("binaries", to_json(&binaries));
 
@@ -508,25 +579,6 @@ fn build_nsis_app_installer(
     handlebars.render("installer.nsi", &data)?,
   )?;
 
-  let package_base_name = format!(
-    "{}_{}_{}-setup",
-    settings.product_name(),
-    settings.version_string(),
-    arch,
-  );
-
-  let nsis_output_path = output_path.join(out_file);
-  let nsis_installer_path = settings.project_out_directory().to_path_buf().join(format!(
-    "bundle/{}/{}.exe",
-    if updater {
-      NSIS_UPDATER_OUTPUT_FOLDER_NAME
-    } else {
-      NSIS_OUTPUT_FOLDER_NAME
-    },
-    package_base_name
-  ));
-  fs::create_dir_all(nsis_installer_path.parent().unwrap())?;
-
   log::info!(action = "Running"; "makensis.exe to produce {}", display_path(&nsis_installer_path));
 
   #[cfg(target_os = "windows")]
@@ -545,6 +597,11 @@ fn build_nsis_app_installer(
     .arg(installer_nsi_path)
     .env_remove("NSISDIR")
     .env_remove("NSISCONFDIR")
+    .envs(
+      settings
+        .source_date_epoch()
+        .map(|epoch| ("SOURCE_DATE_EPOCH", epoch.to_string())),
+    )
     .current_dir(output_path)
     .piped()
     .context("error running makensis.exe")?;
@@ -558,6 +615,14 @@ fn build_nsis_app_installer(
     log::warn!("Signing, by default, is only supported on Windows hosts, but you can specify a custom signing command in `bundler > windows > sign_command`, for now, skipping signing the installer...");
   }
 
+  // the archives are auxiliary files downloaded by the installer, they are not bundles on their own
+  for archive in component_archives {
+    log::info!(
+      "The NSIS web installer downloads {}, publish it at `bundle > windows > nsis > webInstaller > url`",
+      display_path(&archive)
+    );
+  }
+
   Ok(vec![nsis_installer_path])
 }
 
@@ -635,6 +700,108 @@ fn generate_resource_data(settings: &Settings) -> crate::Result<ResourcesMap> {
   Ok(resources)
 }
 
+/// Splits the resources between the main installation and each optional component,
+/// a resource belonging to the first component that includes it.
+fn split_components_resources(
+  mut resources: ResourcesMap,
+  components: &[NsisComponent],
+) -> crate::Result<(ResourcesMap, Vec<ResourcesMap>)> {
+  let mut components_resources = Vec::new();
+  for component in components {
+    if component.id.is_empty()
+      || !component
+        .id
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+      return Err(crate::Error::GenericError(format!(
+        "invalid NSIS component id `{}`, it must only contain alphanumeric characters, `-` and `_`",
+        component.id
+      )));
+    }
+
+    let (component_resources, rest): (ResourcesMap, ResourcesMap) =
+      resources.into_iter().partition(|(_, (_, target))| {
+        component
+          .resources
+          .iter()
+          .any(|path| target.starts_with(path))
+      });
+    if component_resources.is_empty() {
+      log::warn!(
+        "The NSIS component `{}` does not match any resource",
+        component.id
+      );
+    }
+    components_resources.push(component_resources);
+    resources = rest;
+  }
+  Ok((resources, components_resources))
+}
+
+#[derive(Serialize)]
+struct ComponentData<'a> {
+  name: &'a str,
+  description: Option<&'a str>,
+  selected: bool,
+  resources: ResourcesMap,
+  resources_dirs: Vec<PathBuf>,
+  /// The archive file name, encoded for the download URL. Only set for the web installer.
+  archive_name: Option<String>,
+  archive_sha256: Option<String>,
+}
+
+/// Creates the web installer archive of a component, with its resources at their target path.
+fn create_component_archive(resources: &ResourcesMap, dst_file: &Path) -> crate::Result<()> {
+  let writer = common::create_file(dst_file)?;
+  let mut zip = zip::ZipWriter::new(writer);
+  let options = FileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+
+  for (src, (_, target)) in resources {
+    // zip entries always use forward slashes
+    let name = target
+      .components()
+      .map(|c| c.as_os_str().to_string_lossy())
+      .collect::<Vec<_>>()
+      .join("/");
+    zip.start_file(name, options)?;
+    io::copy(&mut File::open(src)?, &mut zip)?;
+  }
+
+  zip.finish()?.flush()?;
+  Ok(())
+}
+
+/// Percent-encodes a URL path segment.
+fn encode_url_path_segment(segment: &str) -> String {
+  let mut encoded = String::new();
+  for byte in segment.bytes() {
+    match byte {
+      b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+        encoded.push(byte as char)
+      }
+      _ => encoded.push_str(&format!("%{byte:02X}")),
+    }
+  }
+  encoded
+}
+
+// We need to convert / to \ for nsis to move the files into the correct dirs
+fn to_nsis_path(path: &Path) -> PathBuf {
+  if cfg!(target_os = "windows") {
+    path.to_path_buf()
+  } else {
+    path.display().to_string().replace('/', "\\").into()
+  }
+}
+
+fn to_nsis_resources(resources: ResourcesMap) -> ResourcesMap {
+  resources
+    .into_iter()
+    .map(|(r, p)| (r, (to_nsis_path(&p.0), to_nsis_path(&p.1))))
+    .collect()
+}
+
 /// BTreeMap<OriginalPath, TargetFileName>
 type BinariesMap = BTreeMap<PathBuf, String>;
 fn generate_binaries_data(settings: &Settings) -> crate::Result<BinariesMap> {
@@ -721,3 +888,57 @@ fn write_utf8_with_bom<P: AsRef<Path>, C: AsRef<[u8]>>(path: P, content: C) -> c
   output.write_all(content.as_ref())?;
   Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn components_resources() {
+    let mut resources = ResourcesMap::new();
+    for target in [
+      "icon.png",
+      "assets/models/a.bin",
+      "assets/models/b.bin",
+      "extras/x.txt",
+    ] {
+      let target = PathBuf::from(target);
+      resources.insert(
+        Path::new("src").join(&target),
+        (target.parent().unwrap().to_path_buf(), target),
+      );
+    }
+
+    let component = |id: &str, resources: &[&str]| NsisComponent {
+      id: id.into(),
+      name: id.into(),
+      description: None,
+      resources: resources.iter().map(PathBuf::from).collect(),
+      selected: true,
+    };
+    let (main, components) = split_components_resources(
+      resources,
+      &[
+        component("models", &["assets/models"]),
+        component("extras", &["extras/x.txt", "assets/models/a.bin"]),
+      ],
+    )
+    .unwrap();
+
+    assert_eq!(main.len(), 1);
+    assert!(main.contains_key(Path::new("src/icon.png")));
+    assert_eq!(components[0].len(), 2);
+    assert_eq!(components[1].len(), 1);
+    assert!(components[1].contains_key(&Path::new("src").join("extras/x.txt")));
+
+    assert!(split_components_resources(ResourcesMap::new(), &[component("a b", &[])]).is_err());
+  }
+
+  #[test]
+  fn url_path_segment() {
+    assert_eq!(
+      encode_url_path_segment("My App_1.0.0_x64_models.zip"),
+      "My%20App_1.0.0_x64_models.zip"
+    );
+  }
+}
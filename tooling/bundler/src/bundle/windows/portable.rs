@@ -0,0 +1,128 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+// The portable app archive is a zip of the app folder, which can be extracted and run anywhere:
+//
+// bundle/portable/
+//     foobar_1.2.3_x64_portable/
+//         foobar.exe                       # Main binary, along the sidecars and resources
+//         .portable                        # Stores the app data in the `data` folder next to the executable
+//         MicrosoftEdgeWebview2Setup.exe   # With the `embedBootstrapper` or `offlineInstaller` webview install modes
+//     foobar_1.2.3_x64_portable.zip        # The portable app archive
+
+use super::util::{
+  copy_app_files, download_webview2_bootstrapper, download_webview2_offline_installer,
+};
+use crate::{bundle::common, Settings};
+use tauri_utils::{config::WebviewInstallMode, platform::PORTABLE_MARKER_FILE_NAME};
+
+use anyhow::Context;
+use zip::write::FileOptions;
+
+use std::{
+  fs::{self, File},
+  io::{self, Write},
+  path::{Path, PathBuf},
+};
+
+/// Bundles the project.
+/// Returns a vector of PathBuf that shows where the portable app archive was created.
+pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<PathBuf>> {
+  let arch = match settings.binary_arch() {
+    "x86_64" => "x64",
+    "x86" => "x86",
+    "aarch64" => "arm64",
+    target => {
+      return Err(crate::Error::ArchError(format!(
+        "unsupported target: {}",
+        target
+      )))
+    }
+  };
+
+  let package_base_name = format!(
+    "{}_{}_{}_portable",
+    settings.product_name(),
+    settings.version_string(),
+    arch
+  );
+  let zip_name = format!("{package_base_name}.zip");
+
+  let base_dir = settings.project_out_directory().join("bundle/portable");
+  if base_dir.exists() {
+    fs::remove_dir_all(&base_dir).with_context(|| "Failed to remove old portable bundle")?;
+  }
+  let app_dir = base_dir.join(&package_base_name);
+  let zip_path = base_dir.join(&zip_name);
+
+  log::info!(action = "Bundling"; "{} ({})", zip_name, zip_path.display());
+
+  copy_app_files(settings, &app_dir)?;
+
+  if settings.windows().portable.data_next_to_executable {
+    File::create(app_dir.join(PORTABLE_MARKER_FILE_NAME))?;
+  }
+
+  // the fixed runtime is already copied with the resources,
+  // the installers are shipped along the app so the runtime can be installed offline
+  let tauri_tools_path = dirs::cache_dir().unwrap().join("tauri");
+  match settings.windows().webview_install_mode {
+    WebviewInstallMode::EmbedBootstrapper { .. } => {
+      fs::create_dir_all(&tauri_tools_path)?;
+      let bootstrapper = download_webview2_bootstrapper(&tauri_tools_path)?;
+      common::copy_file(
+        &bootstrapper,
+        app_dir.join("MicrosoftEdgeWebview2Setup.exe"),
+      )?;
+    }
+    WebviewInstallMode::OfflineInstaller { .. } => {
+      let installer = download_webview2_offline_installer(&tauri_tools_path.join(arch), arch)?;
+      common::copy_file(
+        &installer,
+        app_dir.join(
+          installer
+            .file_name()
+            .expect("failed to get installer filename"),
+        ),
+      )?;
+    }
+    _ => {}
+  }
+
+  create_zip(&app_dir, &zip_path).with_context(|| "Failed to create portable app archive")?;
+
+  Ok(vec![zip_path])
+}
+
+/// Creates a zip archive of the given folder, keeping the folder as the archive root.
+fn create_zip(src_dir: &Path, dst_file: &Path) -> crate::Result<()> {
+  let writer = common::create_file(dst_file)?;
+  let mut zip = zip::ZipWriter::new(writer);
+  let options = FileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+
+  let root = src_dir
+    .parent()
+    .expect("failed to get portable app folder parent");
+  for entry in walkdir::WalkDir::new(src_dir).sort_by_file_name() {
+    let entry = entry?;
+    let path = entry.path();
+    // zip entries always use forward slashes
+    let name = path
+      .strip_prefix(root)?
+      .components()
+      .map(|c| c.as_os_str().to_string_lossy())
+      .collect::<Vec<_>>()
+      .join("/");
+
+    if entry.file_type().is_dir() {
+      zip.add_directory(name, options)?;
+    } else {
+      zip.start_file(name, options)?;
+      io::copy(&mut File::open(path)?, &mut zip)?;
+    }
+  }
+
+  zip.finish()?.flush()?;
+  Ok(())
+}
@@ -8,14 +8,47 @@ use crate::bundle::windows::util;
 use crate::{bundle::common::CommandExt, Settings};
 use anyhow::Context;
 #[cfg(windows)]
-use std::path::PathBuf;
-#[cfg(windows)]
 use std::sync::OnceLock;
-use std::{path::Path, process::Command};
+use std::{
+  path::{Path, PathBuf},
+  process::Command,
+  sync::Arc,
+};
+use tauri_utils::config::WindowsSignerConfig;
 
 impl Settings {
+  /// The signer of the Windows binaries and installers.
+  ///
+  /// By precedence: [`WindowsSettings::signer`], [`WindowsSettings::sign_command`]
+  /// and `signtool.exe` with [`WindowsSettings::certificate_thumbprint`].
+  ///
+  /// [`WindowsSettings::signer`]: crate::WindowsSettings::signer
+  /// [`WindowsSettings::sign_command`]: crate::WindowsSettings::sign_command
+  /// [`WindowsSettings::certificate_thumbprint`]: crate::WindowsSettings::certificate_thumbprint
+  pub(crate) fn windows_signer(&self) -> Option<Arc<dyn WindowsSigner>> {
+    let windows = self.windows();
+    if let Some(signer) = &windows.signer {
+      Some(signer.clone())
+    } else if let Some(command) = &windows.sign_command {
+      Some(Arc::new(CommandSigner::parse(command)))
+    } else {
+      windows
+        .certificate_thumbprint
+        .as_ref()
+        .map(|thumbprint| -> Arc<dyn WindowsSigner> {
+          Arc::new(SigntoolSigner {
+            certificate_thumbprint: Some(thumbprint.clone()),
+            ..Default::default()
+          })
+        })
+    }
+  }
+
+  /// Whether a signer is configured and can run on this host.
   pub(crate) fn can_sign(&self) -> bool {
-    self.windows().sign_command.is_some() || self.windows().certificate_thumbprint.is_some()
+    self
+      .windows_signer()
+      .map_or(false, |signer| signer.is_supported())
   }
 
   pub(crate) fn sign_params(&self) -> SignParams {
@@ -27,141 +60,283 @@ impl Settings {
         .as_ref()
         .map(|algorithm| algorithm.to_string())
         .unwrap_or_else(|| "sha256".to_string()),
-      certificate_thumbprint: self
-        .windows()
-        .certificate_thumbprint
-        .clone()
-        .unwrap_or_default(),
       timestamp_url: self
         .windows()
         .timestamp_url
         .as_ref()
         .map(|url| url.to_string()),
       tsp: self.windows().tsp,
-      sign_command: self.windows().sign_command.clone(),
     }
   }
 }
 
-#[cfg_attr(not(windows), allow(dead_code))]
+/// The signing options shared by all signers.
+#[derive(Debug, Clone)]
 pub struct SignParams {
+  /// The description added to the signature.
   pub product_name: String,
+  /// The file digest algorithm.
   pub digest_algorithm: String,
-  pub certificate_thumbprint: String,
+  /// Server to use during timestamping.
   pub timestamp_url: Option<String>,
+  /// Whether to use Time-Stamp Protocol (TSP, a.k.a. RFC 3161) for the timestamp server.
   pub tsp: bool,
-  pub sign_command: Option<String>,
 }
 
-#[cfg(windows)]
-fn signtool() -> Option<PathBuf> {
-  // sign code forked from https://github.com/forbjok/rust-codesign
-  static SIGN_TOOL: OnceLock<crate::Result<PathBuf>> = OnceLock::new();
-  SIGN_TOOL
-    .get_or_init(|| {
-      const INSTALLED_ROOTS_REGKEY_PATH: &str = r"SOFTWARE\Microsoft\Windows Kits\Installed Roots";
-      const KITS_ROOT_REGVALUE_NAME: &str = r"KitsRoot10";
-
-      // Open 32-bit HKLM "Installed Roots" key
-      let installed_roots_key = windows_registry::LOCAL_MACHINE
-        .open(INSTALLED_ROOTS_REGKEY_PATH)
-        .map_err(|_| crate::Error::OpenRegistry(INSTALLED_ROOTS_REGKEY_PATH.to_string()))?;
-
-      // Get the Windows SDK root path
-      let kits_root_10_path: String = installed_roots_key
-        .get_string(KITS_ROOT_REGVALUE_NAME)
-        .map_err(|_| crate::Error::GetRegistryValue(KITS_ROOT_REGVALUE_NAME.to_string()))?;
-
-      // Construct Windows SDK bin path
-      let kits_root_10_bin_path = Path::new(&kits_root_10_path).join("bin");
-
-      let mut installed_kits: Vec<String> = installed_roots_key
-        .keys()
-        .map_err(|_| crate::Error::FailedToEnumerateRegKeys)?
-        .collect();
-
-      // Sort installed kits
-      installed_kits.sort();
-
-      /* Iterate through installed kit version keys in reverse (from newest to oldest),
-      adding their bin paths to the list.
-      Windows SDK 10 v10.0.15063.468 and later will have their signtools located there. */
-      let mut kit_bin_paths: Vec<PathBuf> = installed_kits
-        .iter()
-        .rev()
-        .map(|kit| kits_root_10_bin_path.join(kit))
-        .collect();
-
-      /* Add kits root bin path.
-      For Windows SDK 10 versions earlier than v10.0.15063.468, signtool will be located there. */
-      kit_bin_paths.push(kits_root_10_bin_path);
-
-      // Choose which version of SignTool to use based on OS bitness
-      let arch_dir = util::os_bitness().ok_or(crate::Error::UnsupportedBitness)?;
-
-      /* Iterate through all bin paths, checking for existence of a SignTool executable. */
-      for kit_bin_path in &kit_bin_paths {
-        /* Construct SignTool path. */
-        let signtool_path = kit_bin_path.join(arch_dir).join("signtool.exe");
-
-        /* Check if SignTool exists at this location. */
-        if signtool_path.exists() {
-          // SignTool found. Return it.
-          return Ok(signtool_path);
-        }
+/// Signs the Windows executables, libraries and installers.
+///
+/// The bundler creates the signing command of each file, which lets the NSIS installer
+/// reuse it to sign the uninstaller it generates.
+pub trait WindowsSigner: std::fmt::Debug + Send + Sync {
+  /// Creates the command signing the file at the given path.
+  fn sign_command(&self, path: &Path, params: &SignParams) -> crate::Result<Command>;
+
+  /// Whether the signer can run on the current host.
+  fn is_supported(&self) -> bool {
+    true
+  }
+}
+
+/// Creates the signer described by the configuration.
+pub fn signer_from_config(config: &WindowsSignerConfig) -> Arc<dyn WindowsSigner> {
+  match config.clone() {
+    WindowsSignerConfig::Signtool {
+      certificate_thumbprint,
+      subject_name,
+      certificate_file,
+      csp,
+      key_container,
+      additional_certificate,
+    } => Arc::new(SigntoolSigner {
+      certificate_thumbprint,
+      subject_name,
+      certificate_file,
+      csp,
+      key_container,
+      additional_certificate,
+    }),
+    WindowsSignerConfig::AzureTrustedSigning {
+      endpoint,
+      account,
+      certificate_profile,
+      dlib,
+      correlation_id,
+    } => Arc::new(AzureTrustedSigner {
+      endpoint,
+      account,
+      certificate_profile,
+      dlib,
+      correlation_id,
+    }),
+    WindowsSignerConfig::Command { command, args } => Arc::new(CommandSigner { command, args }),
+  }
+}
+
+/// Signs with `signtool.exe`, using the certificate store, a certificate file or a key stored on a HSM.
+#[derive(Debug, Clone, Default)]
+pub struct SigntoolSigner {
+  /// The SHA1 hash of the certificate in the certificate store.
+  pub certificate_thumbprint: Option<String>,
+  /// The subject name of the certificate in the certificate store.
+  pub subject_name: Option<String>,
+  /// The `.pfx` certificate file, or the `.cer` file of a key stored on a HSM.
+  pub certificate_file: Option<PathBuf>,
+  /// The cryptographic service provider holding the private key.
+  pub csp: Option<String>,
+  /// The key container in the cryptographic service provider.
+  pub key_container: Option<String>,
+  /// An additional certificate to add to the signature block.
+  pub additional_certificate: Option<PathBuf>,
+}
+
+impl WindowsSigner for SigntoolSigner {
+  fn sign_command(&self, path: &Path, params: &SignParams) -> crate::Result<Command> {
+    let mut cmd = signtool_sign_command(params)?;
+    if let Some(thumbprint) = &self.certificate_thumbprint {
+      cmd.args(["/sha1", thumbprint]);
+    }
+    if let Some(subject_name) = &self.subject_name {
+      cmd.args(["/n", subject_name]);
+    }
+    if let Some(certificate_file) = &self.certificate_file {
+      // the password would be visible in the process list and written in the NSIS script with `/p`,
+      // so the protected certificate is signed with from the certificate store instead
+      let is_pfx = certificate_file.extension().map_or(false, |ext| {
+        ext.eq_ignore_ascii_case("pfx") || ext.eq_ignore_ascii_case("p12")
+      });
+      if is_pfx && std::env::var_os(PASSWORD_ENV).is_some() {
+        cmd.args(["/sha1", &import_pfx(certificate_file)?]);
+      } else {
+        cmd.arg("/f").arg(certificate_file);
       }
+    }
+    if let Some(csp) = &self.csp {
+      cmd.args(["/csp", csp]);
+    }
+    if let Some(key_container) = &self.key_container {
+      cmd.args(["/kc", key_container]);
+    }
+    if let Some(additional_certificate) = &self.additional_certificate {
+      cmd.arg("/ac").arg(additional_certificate);
+    }
+    cmd.arg(path);
+    Ok(cmd)
+  }
 
-      Err(crate::Error::SignToolNotFound)
-    })
-    .as_ref()
-    .ok()
-    .cloned()
+  fn is_supported(&self) -> bool {
+    cfg!(windows)
+  }
 }
 
-/// Check if binary is already signed.
-/// Used to skip sidecar binaries that are already signed.
+/// The environment variable with the password of the `.pfx` certificate file.
+const PASSWORD_ENV: &str = "TAURI_WINDOWS_SIGNTOOL_PASSWORD";
+
+/// Imports the `.pfx` certificate file into the certificate store of the user, returning its thumbprint.
+///
+/// The password is only read from the environment by PowerShell, it is never part of a command line.
 #[cfg(windows)]
-pub fn verify(path: &Path) -> crate::Result<bool> {
-  let signtool = signtool().ok_or(crate::Error::SignToolNotFound)?;
+fn import_pfx(certificate_file: &Path) -> crate::Result<String> {
+  use std::{collections::HashMap, sync::Mutex};
 
-  let mut cmd = Command::new(signtool);
-  cmd.arg("verify");
-  cmd.arg("/pa");
-  cmd.arg(path);
+  static THUMBPRINTS: OnceLock<Mutex<HashMap<PathBuf, String>>> = OnceLock::new();
+  let mut thumbprints = THUMBPRINTS.get_or_init(Default::default).lock().unwrap();
+  if let Some(thumbprint) = thumbprints.get(certificate_file) {
+    return Ok(thumbprint.clone());
+  }
 
-  Ok(cmd.status()?.success())
+  let output = Command::new("powershell.exe")
+    .args(["-NoProfile", "-NonInteractive", "-Command"])
+    .arg(format!(
+      "(Import-PfxCertificate -FilePath $env:TAURI_SIGNTOOL_CERTIFICATE_FILE -CertStoreLocation Cert:\\CurrentUser\\My -Password (ConvertTo-SecureString -String $env:{PASSWORD_ENV} -AsPlainText -Force)).Thumbprint"
+    ))
+    .env("TAURI_SIGNTOOL_CERTIFICATE_FILE", certificate_file)
+    .output_ok()
+    .context("failed to import the certificate file into the certificate store")?;
+  let thumbprint = String::from_utf8_lossy(&output.stdout).trim().to_string();
+  if thumbprint.is_empty() {
+    return Err(crate::Error::GenericError(format!(
+      "failed to import {} into the certificate store",
+      certificate_file.display()
+    )));
+  }
+  thumbprints.insert(certificate_file.to_path_buf(), thumbprint.clone());
+  Ok(thumbprint)
 }
 
-pub fn sign_command_custom<P: AsRef<Path>>(path: P, command: &str) -> crate::Result<Command> {
-  let path = path.as_ref();
+#[cfg(not(windows))]
+fn import_pfx(_certificate_file: &Path) -> crate::Result<String> {
+  Err(crate::Error::SignToolNotFound)
+}
 
-  let mut args = command.trim().split(' ');
-  let bin = args
-    .next()
-    .context("custom signing command doesn't contain a bin?")?;
+/// Signs with Azure Trusted Signing, using `signtool.exe` and the Trusted Signing dlib.
+#[derive(Debug, Clone)]
+pub struct AzureTrustedSigner {
+  /// The Trusted Signing account endpoint.
+  pub endpoint: String,
+  /// The Trusted Signing account name.
+  pub account: String,
+  /// The certificate profile name.
+  pub certificate_profile: String,
+  /// Path to the `Azure.CodeSigning.Dlib.dll` file.
+  pub dlib: PathBuf,
+  /// Identifier added to the signing requests.
+  pub correlation_id: Option<String>,
+}
 
-  let mut cmd = Command::new(bin);
-  for arg in args {
-    if arg == "%1" {
-      cmd.arg(path);
-    } else {
-      cmd.arg(arg);
+/// The timestamp server of Azure Trusted Signing, used when no timestamp URL is configured.
+const AZURE_TIMESTAMP_URL: &str = "http://timestamp.acs.microsoft.com";
+
+impl AzureTrustedSigner {
+  /// Writes the metadata file read by the dlib, describing the account and certificate profile.
+  fn write_metadata(&self) -> crate::Result<PathBuf> {
+    let mut metadata = serde_json::json!({
+      "Endpoint": self.endpoint,
+      "CodeSigningAccountName": self.account,
+      "CertificateProfileName": self.certificate_profile,
+    });
+    if let Some(correlation_id) = &self.correlation_id {
+      metadata["CorrelationId"] = correlation_id.clone().into();
     }
+    let path = std::env::temp_dir().join(format!(
+      "tauri-trusted-signing-{}-{}.json",
+      self.account, self.certificate_profile
+    ));
+    std::fs::write(&path, serde_json::to_vec_pretty(&metadata)?)?;
+    Ok(path)
   }
-  Ok(cmd)
 }
 
+impl WindowsSigner for AzureTrustedSigner {
+  fn sign_command(&self, path: &Path, params: &SignParams) -> crate::Result<Command> {
+    let params = SignParams {
+      timestamp_url: params
+        .timestamp_url
+        .clone()
+        .or_else(|| Some(AZURE_TIMESTAMP_URL.into())),
+      tsp: params.tsp || params.timestamp_url.is_none(),
+      ..params.clone()
+    };
+    let metadata = self.write_metadata()?;
+
+    let mut cmd = signtool_sign_command(&params)?;
+    cmd.arg("/dlib").arg(&self.dlib);
+    cmd.arg("/dmdf").arg(metadata);
+    cmd.arg(path);
+    Ok(cmd)
+  }
+
+  fn is_supported(&self) -> bool {
+    cfg!(windows)
+  }
+}
+
+/// Signs with an external command, replacing the `%1` argument with the path of the file to sign.
+#[derive(Debug, Clone)]
+pub struct CommandSigner {
+  /// The program to run.
+  pub command: String,
+  /// The program arguments.
+  pub args: Vec<String>,
+}
+
+impl CommandSigner {
+  /// Parses a command line such as `sign-cli --arg1 %1`, splitting the arguments on spaces.
+  pub fn parse(command_line: &str) -> Self {
+    let mut args = command_line.trim().split(' ').map(ToString::to_string);
+    Self {
+      command: args.next().unwrap_or_default(),
+      args: args.collect(),
+    }
+  }
+}
+
+impl WindowsSigner for CommandSigner {
+  fn sign_command(&self, path: &Path, _params: &SignParams) -> crate::Result<Command> {
+    if self.command.is_empty() {
+      return Err(crate::Error::GenericError(
+        "custom signing command doesn't contain a bin?".into(),
+      ));
+    }
+    let mut cmd = Command::new(&self.command);
+    for arg in &self.args {
+      if arg == "%1" {
+        cmd.arg(path);
+      } else {
+        cmd.arg(arg);
+      }
+    }
+    Ok(cmd)
+  }
+}
+
+/// The `signtool sign` command with the digest algorithm, description and timestamp server.
 #[cfg(windows)]
-pub fn sign_command_default<P: AsRef<Path>>(
-  path: P,
-  params: &SignParams,
-) -> crate::Result<Command> {
+fn signtool_sign_command(params: &SignParams) -> crate::Result<Command> {
   let signtool = signtool().ok_or(crate::Error::SignToolNotFound)?;
 
   let mut cmd = Command::new(signtool);
   cmd.arg("sign");
   cmd.args(["/fd", &params.digest_algorithm]);
-  cmd.args(["/sha1", &params.certificate_thumbprint]);
   cmd.args(["/d", &params.product_name]);
 
   if let Some(ref timestamp_url) = params.timestamp_url {
@@ -173,49 +348,106 @@ pub fn sign_command_default<P: AsRef<Path>>(
     }
   }
 
-  cmd.arg(path.as_ref());
-
   Ok(cmd)
 }
 
-pub fn sign_command<P: AsRef<Path>>(path: P, params: &SignParams) -> crate::Result<Command> {
-  match &params.sign_command {
-    Some(custom_command) => sign_command_custom(path, custom_command),
-    #[cfg(windows)]
-    None => sign_command_default(path, params),
+#[cfg(not(windows))]
+fn signtool_sign_command(_params: &SignParams) -> crate::Result<Command> {
+  Err(crate::Error::SignToolNotFound)
+}
 
-    // should not be reachable
-    #[cfg(not(windows))]
-    None => Ok(Command::new("")),
-  }
+#[cfg(windows)]
+fn signtool() -> Option<PathBuf> {
+  // sign code forked from https://github.com/forbjok/rust-codesign
+  static SIGN_TOOL: OnceLock<crate::Result<PathBuf>> = OnceLock::new();
+  SIGN_TOOL
+    .get_or_init(|| sdk_tool("signtool.exe")?.ok_or(crate::Error::SignToolNotFound))
+    .as_ref()
+    .ok()
+    .cloned()
 }
 
-pub fn sign_custom<P: AsRef<Path>>(path: P, custom_command: &str) -> crate::Result<()> {
-  let path = path.as_ref();
+/// Finds a tool shipped with the Windows SDK, using the newest installed SDK that provides it.
+#[cfg(windows)]
+pub(crate) fn sdk_tool(name: &str) -> crate::Result<Option<PathBuf>> {
+  const INSTALLED_ROOTS_REGKEY_PATH: &str = r"SOFTWARE\Microsoft\Windows Kits\Installed Roots";
+  const KITS_ROOT_REGVALUE_NAME: &str = r"KitsRoot10";
+
+  // Open 32-bit HKLM "Installed Roots" key
+  let installed_roots_key = windows_registry::LOCAL_MACHINE
+    .open(INSTALLED_ROOTS_REGKEY_PATH)
+    .map_err(|_| crate::Error::OpenRegistry(INSTALLED_ROOTS_REGKEY_PATH.to_string()))?;
+
+  // Get the Windows SDK root path
+  let kits_root_10_path: String = installed_roots_key
+    .get_string(KITS_ROOT_REGVALUE_NAME)
+    .map_err(|_| crate::Error::GetRegistryValue(KITS_ROOT_REGVALUE_NAME.to_string()))?;
+
+  // Construct Windows SDK bin path
+  let kits_root_10_bin_path = Path::new(&kits_root_10_path).join("bin");
+
+  let mut installed_kits: Vec<String> = installed_roots_key
+    .keys()
+    .map_err(|_| crate::Error::FailedToEnumerateRegKeys)?
+    .collect();
+
+  // Sort installed kits
+  installed_kits.sort();
+
+  /* Iterate through installed kit version keys in reverse (from newest to oldest),
+  adding their bin paths to the list.
+  Windows SDK 10 v10.0.15063.468 and later will have their tools located there. */
+  let mut kit_bin_paths: Vec<PathBuf> = installed_kits
+    .iter()
+    .rev()
+    .map(|kit| kits_root_10_bin_path.join(kit))
+    .collect();
+
+  /* Add kits root bin path.
+  For Windows SDK 10 versions earlier than v10.0.15063.468, the tools will be located there. */
+  kit_bin_paths.push(kits_root_10_bin_path);
+
+  // Choose which version of the tool to use based on OS bitness
+  let arch_dir = util::os_bitness().ok_or(crate::Error::UnsupportedBitness)?;
+
+  /* Iterate through all bin paths, checking for existence of the tool executable. */
+  for kit_bin_path in &kit_bin_paths {
+    let tool_path = kit_bin_path.join(arch_dir).join(name);
+    if tool_path.exists() {
+      return Ok(Some(tool_path));
+    }
+  }
 
-  log::info!(action = "Signing";"{} with a custom signing command", tauri_utils::display_path(path));
+  Ok(None)
+}
 
-  let mut cmd = sign_command_custom(path, custom_command)?;
+/// Check if binary is already signed.
+/// Used to skip sidecar binaries that are already signed.
+#[cfg(windows)]
+pub fn verify(path: &Path) -> crate::Result<bool> {
+  let signtool = signtool().ok_or(crate::Error::SignToolNotFound)?;
 
-  let output = cmd.output_ok()?;
+  let mut cmd = Command::new(signtool);
+  cmd.arg("verify");
+  cmd.arg("/pa");
+  cmd.arg(path);
 
-  let stdout = String::from_utf8_lossy(output.stdout.as_slice()).into_owned();
-  log::info!("{:?}", stdout);
+  Ok(cmd.status()?.success())
+}
 
-  Ok(())
+/// Creates the command signing the file at the given path with the configured signer.
+pub fn sign_command<P: AsRef<Path>>(path: P, settings: &Settings) -> crate::Result<Command> {
+  let signer = settings
+    .windows_signer()
+    .context("no Windows signer configured")?;
+  signer.sign_command(path.as_ref(), &settings.sign_params())
 }
 
-#[cfg(windows)]
-pub fn sign_default<P: AsRef<Path>>(path: P, params: &SignParams) -> crate::Result<()> {
-  let signtool = signtool().ok_or(crate::Error::SignToolNotFound)?;
+pub fn sign<P: AsRef<Path>>(path: P, settings: &Settings) -> crate::Result<()> {
   let path = path.as_ref();
+  let mut cmd = sign_command(path, settings)?;
+  log::debug!("Running {:?}", cmd.get_program());
 
-  log::info!(action = "Signing"; "{} with identity \"{}\"", tauri_utils::display_path(path), params.certificate_thumbprint);
-
-  let mut cmd = sign_command_default(path, params)?;
-  log::debug!("Running signtool {:?}", signtool);
-
-  // Execute SignTool command
   let output = cmd.output_ok()?;
 
   let stdout = String::from_utf8_lossy(output.stdout.as_slice()).into_owned();
@@ -224,22 +456,73 @@ pub fn sign_default<P: AsRef<Path>>(path: P, params: &SignParams) -> crate::Resu
   Ok(())
 }
 
-pub fn sign<P: AsRef<Path>>(path: P, params: &SignParams) -> crate::Result<()> {
-  match &params.sign_command {
-    Some(custom_command) => sign_custom(path, custom_command),
-    #[cfg(windows)]
-    None => sign_default(path, params),
-    // should not be reachable, as user should either use Windows
-    // or specify a custom sign_command but we succeed anyways
-    #[cfg(not(windows))]
-    None => Ok(()),
-  }
-}
-
 pub fn try_sign(file_path: &std::path::PathBuf, settings: &Settings) -> crate::Result<()> {
   if settings.can_sign() {
     log::info!(action = "Signing"; "{}", tauri_utils::display_path(file_path));
-    sign(file_path, &settings.sign_params())?;
+    sign(file_path, settings)?;
   }
   Ok(())
 }
+
+/// Signs the file unless it is already signed, e.g. a sidecar or library provided by a vendor.
+pub fn try_sign_unsigned(file_path: &std::path::PathBuf, settings: &Settings) -> crate::Result<()> {
+  #[cfg(windows)]
+  if verify(file_path)? {
+    log::info!(
+      "{} already signed. Skipping...",
+      tauri_utils::display_path(file_path)
+    );
+    return Ok(());
+  }
+
+  try_sign(file_path, settings)
+}
+
+/// The libraries shipped with the app: the `.dll` files next to the app executable and in the resources.
+pub fn libraries(settings: &Settings) -> crate::Result<Vec<PathBuf>> {
+  let is_dll = |path: &Path| {
+    path
+      .extension()
+      .map_or(false, |ext| ext.eq_ignore_ascii_case("dll"))
+  };
+
+  let mut libraries = Vec::new();
+  for entry in std::fs::read_dir(settings.project_out_directory())? {
+    let path = entry?.path();
+    if path.is_file() && is_dll(&path) {
+      libraries.push(path);
+    }
+  }
+  for resource in settings.resource_files() {
+    let path = resource?;
+    if is_dll(&path) && !libraries.contains(&path) {
+      libraries.push(path);
+    }
+  }
+  Ok(libraries)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn command_signer() {
+    let params = SignParams {
+      product_name: "app".into(),
+      digest_algorithm: "sha256".into(),
+      timestamp_url: None,
+      tsp: false,
+    };
+    let signer = CommandSigner::parse("sign-cli --arg1 %1 --arg2");
+    let cmd = signer.sign_command(Path::new("app.exe"), &params).unwrap();
+    assert_eq!(cmd.get_program(), "sign-cli");
+    assert_eq!(
+      cmd.get_args().collect::<Vec<_>>(),
+      ["--arg1", "app.exe", "--arg2"]
+    );
+    assert!(CommandSigner::parse(" ")
+      .sign_command(Path::new("app.exe"), &params)
+      .is_err());
+  }
+}
@@ -8,9 +8,12 @@ use std::{
   path::{Path, PathBuf},
 };
 
+use anyhow::Context;
 use sha2::Digest;
 use zip::ZipArchive;
 
+use crate::{bundle::common, Settings};
+
 pub const WEBVIEW2_BOOTSTRAPPER_URL: &str = "https://go.microsoft.com/fwlink/p/?LinkId=2124703";
 pub const WEBVIEW2_OFFLINE_INSTALLER_X86_URL: &str =
   "https://go.microsoft.com/fwlink/?linkid=2099617";
@@ -165,6 +168,47 @@ pub fn extract_zip(data: &[u8], path: &Path) -> crate::Result<()> {
   Ok(())
 }
 
+/// Copies the main binary, the other binaries, the sidecars and the resources to the given folder,
+/// laid out like an installed app.
+/// Returns the path to the copied main binary.
+pub fn copy_app_files(settings: &Settings, dest_dir: &Path) -> crate::Result<PathBuf> {
+  let main_binary = settings
+    .binaries()
+    .iter()
+    .find(|bin| bin.main())
+    .ok_or_else(|| anyhow::anyhow!("Failed to get main binary"))?;
+  let main_binary_path = settings.binary_path(main_binary).with_extension("exe");
+  let main_binary_dest = dest_dir.join(
+    main_binary_path
+      .file_name()
+      .expect("failed to extract main binary filename"),
+  );
+
+  create_dir_all(dest_dir)?;
+  common::copy_file(&main_binary_path, &main_binary_dest)?;
+  for bin in settings.binaries() {
+    if !bin.main() {
+      let bin_path = settings.binary_path(bin);
+      common::copy_file(
+        &bin_path,
+        dest_dir.join(
+          bin_path
+            .file_name()
+            .expect("failed to extract binary filename"),
+        ),
+      )?;
+    }
+  }
+  settings
+    .copy_binaries(dest_dir)
+    .with_context(|| "Failed to copy external binaries")?;
+  settings
+    .copy_resources(dest_dir)
+    .with_context(|| "Failed to copy resources")?;
+
+  Ok(main_binary_dest)
+}
+
 #[cfg(target_os = "windows")]
 pub fn os_bitness<'a>() -> Option<&'a str> {
   use windows_sys::Win32::System::SystemInformation::{
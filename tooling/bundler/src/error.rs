@@ -88,6 +88,9 @@ pub enum Error {
   /// Windows SignTool not found.
   #[error("SignTool not found")]
   SignToolNotFound,
+  /// Windows MakeAppx not found.
+  #[error("MakeAppx not found, make sure the Windows SDK is installed")]
+  MakeAppxNotFound,
   /// Failed to open Windows registry.
   #[error("failed to open registry {0}")]
   OpenRegistry(String),
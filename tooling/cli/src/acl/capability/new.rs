@@ -101,6 +101,7 @@ pub fn command(options: Options) -> Result<()> {
       })
       .collect(),
     platforms: None,
+    os_permissions: Vec::new(),
   };
 
   let path = match options.out {
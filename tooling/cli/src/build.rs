@@ -3,11 +3,13 @@
 // SPDX-License-Identifier: MIT
 
 use crate::{
-  bundle::BundleFormat,
+  bundle::{BundleFormat, SbomFormat, UpdaterDeltaBase},
   helpers::{
     self,
     app_paths::tauri_dir,
     config::{get as get_config, ConfigHandle, FrontendDist},
+    event::{self, ArtifactKind, Phase},
+    workspace,
   },
   interface::{AppInterface, AppSettings, Interface},
   ConfigValue, Result,
@@ -47,9 +49,36 @@ pub struct Options {
   /// Skip the bundling step even if `bundle > active` is `true` in tauri config.
   #[clap(long)]
   pub no_bundle: bool,
+  /// Generate differential updater artifacts against a previous release, in the `<VERSION>=<PATH>` format
+  /// where `<PATH>` is the directory containing that release's updater artifacts. Can be used multiple times.
+  ///
+  /// Requires `zstd` to be installed.
+  #[clap(long, action = ArgAction::Append)]
+  pub updater_delta_base: Option<Vec<UpdaterDeltaBase>>,
+  /// Normalize the timestamps, file ordering and archive metadata of the deb, rpm, AppImage and NSIS bundles
+  /// so they can be reproduced, and write a build attestation with the artifact hashes to the bundle directory.
+  ///
+  /// The timestamps are set to `SOURCE_DATE_EPOCH`, defaulting to the date of the last git commit.
+  /// The DMG bundles are not reproducible.
+  #[clap(long)]
+  pub reproducible: bool,
+  /// Print the size breakdown of the app by category and its largest files after bundling,
+  /// and write it as JSON to `bundle/analysis.json` in the target directory.
+  #[clap(long)]
+  pub analyze: bool,
+  /// Write a software bill of materials next to each artifact, listing the Rust dependencies,
+  /// the sidecars and the frontend assets of the app.
+  #[clap(long, value_enum)]
+  pub sbom: Option<SbomFormat>,
   /// JSON string or path to JSON file to merge with tauri.conf.json
   #[clap(short, long)]
   pub config: Option<ConfigValue>,
+  /// Name of the workspace app to run the command for, see `tauri.workspace.json`.
+  #[clap(long)]
+  pub app: Option<String>,
+  /// Build each app of the workspace, sharing the compiled dependencies.
+  #[clap(long, conflicts_with = "app")]
+  pub all: bool,
   /// Command line arguments passed to the runner. Use `--` to explicitly mark the start of the arguments.
   pub args: Vec<String>,
   /// Skip prompting for values
@@ -58,6 +87,21 @@ pub struct Options {
 }
 
 pub fn command(mut options: Options, verbosity: u8) -> Result<()> {
+  if options.all {
+    return workspace::for_each_app(|_| {
+      command(
+        Options {
+          all: false,
+          ..options.clone()
+        },
+        verbosity,
+      )
+    });
+  }
+  if let Some(app) = &options.app {
+    workspace::select_app(app)?;
+  }
+
   let ci = options.ci;
 
   let target = options
@@ -84,9 +128,10 @@ pub fn command(mut options: Options, verbosity: u8) -> Result<()> {
   let bin_path = app_settings.app_binary_path(&interface_options)?;
   let out_dir = bin_path.parent().unwrap();
 
-  interface.build(interface_options)?;
+  event::phase(Phase::Build, || interface.build(interface_options))?;
 
   log::info!(action ="Built"; "application at: {}", tauri_utils::display_path(&bin_path));
+  event::artifact(ArtifactKind::Binary, None, &bin_path);
 
   let app_settings = interface.app_settings();
 
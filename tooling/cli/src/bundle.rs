@@ -3,23 +3,27 @@
 // SPDX-License-Identifier: MIT
 
 use std::{
+  collections::{HashMap, HashSet},
+  fmt::Write,
   path::{Path, PathBuf},
+  process::Command,
   str::FromStr,
   sync::OnceLock,
 };
 
 use anyhow::Context;
-use base64::Engine;
 use clap::{builder::PossibleValue, ArgAction, Parser, ValueEnum};
-use tauri_bundler::PackageType;
-use tauri_utils::platform::Target;
+use serde::Deserialize;
+use tauri_bundler::{BundlerHook, PackageType};
+use tauri_utils::{config::BundleHooksConfig, platform::Target};
 
 use crate::{
   helpers::{
     self,
     app_paths::tauri_dir,
-    config::{get as get_config, ConfigMetadata},
-    updater_signature,
+    config::{get as get_config, ConfigMetadata, FrontendDist},
+    event::{self, ArtifactKind, Phase},
+    updater_signature, workspace,
   },
   interface::{AppInterface, AppSettings, Interface},
   ConfigValue,
@@ -48,6 +52,43 @@ impl ValueEnum for BundleFormat {
   }
 }
 
+/// The format of the software bill of materials.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum SbomFormat {
+  /// CycloneDX 1.5 JSON.
+  #[value(name = "cyclonedx")]
+  CycloneDx,
+  /// SPDX 2.3 JSON.
+  Spdx,
+}
+
+impl From<SbomFormat> for tauri_bundler::SbomFormat {
+  fn from(value: SbomFormat) -> Self {
+    match value {
+      SbomFormat::CycloneDx => Self::CycloneDx,
+      SbomFormat::Spdx => Self::Spdx,
+    }
+  }
+}
+
+/// A `<VERSION>=<PATH>` pair pointing to the update artifacts of a previous release.
+#[derive(Debug, Clone)]
+pub struct UpdaterDeltaBase(tauri_bundler::UpdaterDeltaBase);
+
+impl FromStr for UpdaterDeltaBase {
+  type Err = anyhow::Error;
+  fn from_str(s: &str) -> crate::Result<Self> {
+    let (version, path) = s.split_once('=').ok_or_else(|| {
+      anyhow::anyhow!("invalid updater delta base {s}, expected <VERSION>=<PATH>")
+    })?;
+    Ok(Self(tauri_bundler::UpdaterDeltaBase {
+      version: version.into(),
+      path: dunce::canonicalize(path)
+        .with_context(|| format!("failed to read updater delta base directory {path}"))?,
+    }))
+  }
+}
+
 #[derive(Debug, Parser, Clone)]
 #[clap(
   about = "Generate bundles and installers for your app (already built by `tauri build`)",
@@ -65,6 +106,9 @@ pub struct Options {
   /// JSON string or path to JSON file to merge with tauri.conf.json
   #[clap(short, long)]
   pub config: Option<ConfigValue>,
+  /// Name of the workspace app to run the command for, see `tauri.workspace.json`.
+  #[clap(long)]
+  pub app: Option<String>,
   /// Space or comma separated list of features, should be the same features passed to `tauri build` if any.
   #[clap(short, long, action = ArgAction::Append, num_args(0..))]
   pub features: Option<Vec<String>>,
@@ -75,6 +119,27 @@ pub struct Options {
   /// Note that compiling an universal macOS application requires both `aarch64-apple-darwin` and `x86_64-apple-darwin` targets to be installed.
   #[clap(short, long)]
   pub target: Option<String>,
+  /// Generate differential updater artifacts against a previous release, in the `<VERSION>=<PATH>` format
+  /// where `<PATH>` is the directory containing that release's updater artifacts. Can be used multiple times.
+  ///
+  /// Requires `zstd` to be installed.
+  #[clap(long, action = ArgAction::Append)]
+  pub updater_delta_base: Option<Vec<UpdaterDeltaBase>>,
+  /// Normalize the timestamps, file ordering and archive metadata of the deb, rpm, AppImage and NSIS bundles
+  /// so they can be reproduced, and write a build attestation with the artifact hashes to the bundle directory.
+  ///
+  /// The timestamps are set to `SOURCE_DATE_EPOCH`, defaulting to the date of the last git commit.
+  /// The DMG bundles are not reproducible.
+  #[clap(long)]
+  pub reproducible: bool,
+  /// Print the size breakdown of the app by category and its largest files after bundling,
+  /// and write it as JSON to `bundle/analysis.json` in the target directory.
+  #[clap(long)]
+  pub analyze: bool,
+  /// Write a software bill of materials next to each artifact, listing the Rust dependencies,
+  /// the sidecars and the frontend assets of the app.
+  #[clap(long, value_enum)]
+  pub sbom: Option<SbomFormat>,
   /// Skip prompting for values
   #[clap(long, env = "CI")]
   pub ci: bool,
@@ -84,16 +149,25 @@ impl From<crate::build::Options> for Options {
   fn from(value: crate::build::Options) -> Self {
     Self {
       bundles: value.bundles,
+      updater_delta_base: value.updater_delta_base,
+      reproducible: value.reproducible,
+      analyze: value.analyze,
+      sbom: value.sbom,
       target: value.target,
       features: value.features,
       debug: value.debug,
       ci: value.ci,
       config: value.config,
+      app: value.app,
     }
   }
 }
 
 pub fn command(options: Options, verbosity: u8) -> crate::Result<()> {
+  if let Some(app) = &options.app {
+    workspace::select_app(app)?;
+  }
+
   let ci = options.ci;
 
   let target = options
@@ -180,6 +254,14 @@ pub fn bundle<A: AppSettings>(
     _ => log::Level::Trace,
   });
 
+  if let Some(bases) = &options.updater_delta_base {
+    settings.set_updater_delta_bases(bases.iter().map(|b| b.0.clone()).collect());
+  }
+
+  if options.reproducible {
+    settings.set_source_date_epoch(source_date_epoch()?);
+  }
+
   // set env vars used by the bundler
   #[cfg(target_os = "linux")]
   {
@@ -198,18 +280,316 @@ pub fn bundle<A: AppSettings>(
     }
   }
 
-  let bundles = tauri_bundler::bundle_project(&settings)
-    .map_err(|e| match e {
-      tauri_bundler::Error::BundlerError(e) => e,
-      e => anyhow::anyhow!("{e:#}"),
-    })
-    .with_context(|| "failed to bundle project")?;
+  let mut hooks = CommandHooks {
+    hooks: &config.bundle.hooks,
+    interface,
+    debug: options.debug,
+  };
+  let bundles = event::phase(Phase::Bundle, || {
+    tauri_bundler::bundle_project_with_hooks(&settings, &mut [&mut hooks])
+      .map_err(|e| match e {
+        tauri_bundler::Error::BundlerError(e) => e,
+        e => anyhow::anyhow!("{e:#}"),
+      })
+      .with_context(|| "failed to bundle project")
+  })?;
+
+  if options.analyze {
+    analyze(&settings, config, &bundles)?;
+  }
+
+  if let Some(format) = options.sbom {
+    let dependencies = rust_dependencies(settings.target(), options.features.as_deref())
+      .with_context(|| "failed to read the Rust dependency graph")?;
+    let paths = tauri_bundler::write_sboms(
+      &settings,
+      format.into(),
+      &dependencies,
+      &frontend_dist(config),
+      &bundles,
+    )
+    .with_context(|| "failed to write the software bill of materials")?;
+    for path in paths {
+      log::info!(action = "SBOM"; "{}", tauri_utils::display_path(&path));
+    }
+  }
 
   sign_updaters(settings, bundles, ci)?;
 
   Ok(())
 }
 
+/// Runs the `bundle > hooks` commands around the packaging steps.
+struct CommandHooks<'a> {
+  hooks: &'a BundleHooksConfig,
+  interface: &'a AppInterface,
+  debug: bool,
+}
+
+impl BundlerHook for CommandHooks<'_> {
+  fn pre_package(&mut self, _settings: &tauri_bundler::Settings) -> tauri_bundler::Result<()> {
+    if let Some(hook) = &self.hooks.pre_package {
+      helpers::run_hook("prePackage hook", hook.clone(), self.interface, self.debug)?;
+    }
+    Ok(())
+  }
+
+  fn artifact(
+    &mut self,
+    _settings: &tauri_bundler::Settings,
+    package_type: PackageType,
+    path: &Path,
+  ) -> tauri_bundler::Result<()> {
+    event::artifact(ArtifactKind::Bundle, Some(package_type.short_name()), path);
+    if let Some(hook) = &self.hooks.artifact {
+      let env = HashMap::from([
+        ("TAURI_BUNDLE_ARTIFACT_PATH", path.display().to_string()),
+        ("TAURI_BUNDLE_TYPE", package_type.short_name().to_string()),
+      ]);
+      helpers::run_hook_with_env(
+        "artifact hook",
+        hook.clone(),
+        self.interface,
+        self.debug,
+        env,
+      )?;
+    }
+    Ok(())
+  }
+
+  fn post_package(
+    &mut self,
+    _settings: &tauri_bundler::Settings,
+    bundles: &[tauri_bundler::Bundle],
+  ) -> tauri_bundler::Result<()> {
+    if let Some(hook) = &self.hooks.post_package {
+      let paths = std::env::join_paths(bundles.iter().flat_map(|b| b.bundle_paths.iter()))
+        .context("failed to join the artifact paths")?;
+      let env = HashMap::from([(
+        "TAURI_BUNDLE_ARTIFACT_PATHS",
+        paths.to_string_lossy().into_owned(),
+      )]);
+      helpers::run_hook_with_env(
+        "postPackage hook",
+        hook.clone(),
+        self.interface,
+        self.debug,
+        env,
+      )?;
+    }
+    Ok(())
+  }
+}
+
+/// Prints the size breakdown of the app and writes it to the bundle directory.
+fn analyze(
+  settings: &tauri_bundler::Settings,
+  config: &ConfigMetadata,
+  bundles: &[tauri_bundler::Bundle],
+) -> crate::Result<()> {
+  let analysis = tauri_bundler::analyze_bundles(settings, &frontend_dist(config), bundles)
+    .with_context(|| "failed to analyze the bundles")?;
+  let analysis_path = tauri_bundler::write_analysis(settings, &analysis)
+    .with_context(|| "failed to write the bundle analysis")?;
+
+  let mut summary = String::new();
+  writeln!(summary, "total {}", format_size(analysis.total_size))?;
+  for category in &analysis.categories {
+    writeln!(
+      summary,
+      "        {:<12}{:>12}  ({} files)",
+      category.category.name(),
+      format_size(category.size),
+      category.file_count
+    )?;
+  }
+  writeln!(summary, "      largest files:")?;
+  for file in &analysis.largest_files {
+    writeln!(
+      summary,
+      "        {:>12}  {} ({})",
+      format_size(file.size),
+      tauri_utils::display_path(&file.path),
+      file.category.name()
+    )?;
+  }
+  writeln!(summary, "      artifacts:")?;
+  for artifact in &analysis.artifacts {
+    writeln!(
+      summary,
+      "        {:>12}  {}",
+      format_size(artifact.size),
+      tauri_utils::display_path(&artifact.path)
+    )?;
+  }
+  log::info!(action = "Analyzed"; "{summary}");
+  log::info!(action = "Analyzed"; "written to {}", tauri_utils::display_path(&analysis_path));
+
+  Ok(())
+}
+
+/// The frontend files and directories embedded in the app.
+fn frontend_dist(config: &ConfigMetadata) -> Vec<PathBuf> {
+  match &config.build.frontend_dist {
+    Some(FrontendDist::Directory(path)) => vec![path.clone()],
+    Some(FrontendDist::Files(files)) => files.clone(),
+    _ => Vec::new(),
+  }
+}
+
+/// Reads the normal (non dev and build) dependency graph of the app crate with `cargo metadata`.
+fn rust_dependencies(
+  target: &str,
+  features: Option<&[String]>,
+) -> crate::Result<tauri_bundler::SbomDependencies> {
+  #[derive(Deserialize)]
+  struct Metadata {
+    packages: Vec<Package>,
+    resolve: Resolve,
+  }
+  #[derive(Deserialize)]
+  struct Package {
+    id: String,
+    name: String,
+    version: String,
+    license: Option<String>,
+    source: Option<String>,
+    manifest_path: PathBuf,
+  }
+  #[derive(Deserialize)]
+  struct Resolve {
+    nodes: Vec<Node>,
+  }
+  #[derive(Deserialize)]
+  struct Node {
+    id: String,
+    deps: Vec<NodeDep>,
+  }
+  #[derive(Deserialize)]
+  struct NodeDep {
+    pkg: String,
+    dep_kinds: Vec<DepKind>,
+  }
+  #[derive(Deserialize)]
+  struct DepKind {
+    kind: Option<String>,
+  }
+
+  let mut command = Command::new("cargo");
+  command
+    .args([
+      "metadata",
+      "--format-version",
+      "1",
+      "--filter-platform",
+      target,
+    ])
+    .current_dir(tauri_dir());
+  if let Some(features) = features.filter(|f| !f.is_empty()) {
+    command.arg("--features").arg(features.join(","));
+  }
+  let output = command.output()?;
+  if !output.status.success() {
+    anyhow::bail!(
+      "cargo metadata command exited with a non zero exit code: {}",
+      String::from_utf8_lossy(&output.stderr)
+    );
+  }
+  let metadata: Metadata = serde_json::from_slice(&output.stdout)?;
+
+  let manifest_path = dunce::canonicalize(tauri_dir().join("Cargo.toml"))?;
+  let root = metadata
+    .packages
+    .iter()
+    .find(|package| dunce::canonicalize(&package.manifest_path).ok() == Some(manifest_path.clone()))
+    .map(|package| package.id.clone())
+    .context("failed to find the app crate in the cargo metadata")?;
+
+  let dependencies = metadata
+    .resolve
+    .nodes
+    .into_iter()
+    .map(|node| {
+      let deps = node
+        .deps
+        .into_iter()
+        .filter(|dep| dep.dep_kinds.iter().any(|k| k.kind.is_none()))
+        .map(|dep| dep.pkg)
+        .collect::<Vec<_>>();
+      (node.id, deps)
+    })
+    .collect::<HashMap<_, _>>();
+
+  // only keep the packages reachable from the app crate
+  let mut reachable = HashSet::from([root.clone()]);
+  let mut queue = vec![root.clone()];
+  while let Some(id) = queue.pop() {
+    for dep in dependencies.get(&id).into_iter().flatten() {
+      if reachable.insert(dep.clone()) {
+        queue.push(dep.clone());
+      }
+    }
+  }
+
+  let packages = metadata
+    .packages
+    .into_iter()
+    .filter(|package| reachable.contains(&package.id))
+    .map(|package| tauri_bundler::SbomPackage {
+      dependencies: dependencies.get(&package.id).cloned().unwrap_or_default(),
+      id: package.id,
+      name: package.name,
+      version: package.version,
+      license: package.license,
+      source: package.source,
+    })
+    .collect();
+
+  Ok(tauri_bundler::SbomDependencies { root, packages })
+}
+
+/// Formats the size in bytes with a binary unit, e.g. `1.5 MiB`.
+fn format_size(size: u64) -> String {
+  const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+  if size < 1024 {
+    return format!("{size} B");
+  }
+  let mut value = size as f64 / 1024.0;
+  let mut unit = UNITS[0];
+  for next in &UNITS[1..] {
+    if value < 1024.0 {
+      break;
+    }
+    value /= 1024.0;
+    unit = next;
+  }
+  format!("{value:.1} {unit}")
+}
+
+/// Reads the `SOURCE_DATE_EPOCH` environment variable, falling back to the date of the last git commit.
+///
+/// See <https://reproducible-builds.org/specs/source-date-epoch/>
+fn source_date_epoch() -> crate::Result<u64> {
+  if let Ok(epoch) = std::env::var("SOURCE_DATE_EPOCH") {
+    return epoch
+      .trim()
+      .parse()
+      .with_context(|| format!("invalid SOURCE_DATE_EPOCH value {epoch}"));
+  }
+
+  let output = Command::new("git")
+    .args(["log", "-1", "--pretty=%ct"])
+    .current_dir(tauri_dir())
+    .output()
+    .ok()
+    .filter(|output| output.status.success())
+    .context("failed to read the last git commit date, set the SOURCE_DATE_EPOCH environment variable for reproducible bundles")?;
+  String::from_utf8_lossy(&output.stdout)
+    .trim()
+    .parse()
+    .context("failed to parse the last git commit date")
+}
+
 fn sign_updaters(
   settings: tauri_bundler::Settings,
   bundles: Vec<tauri_bundler::Bundle>,
@@ -268,9 +648,12 @@ fn sign_updaters(
   };
   let secret_key = updater_signature::secret_key(private_key, password)?;
 
-  let pubkey = base64::engine::general_purpose::STANDARD.decode(pubkey)?;
-  let pub_key_decoded = String::from_utf8_lossy(&pubkey);
-  let public_key = minisign::PublicKeyBox::from_string(&pub_key_decoded)?.into_public_key()?;
+  let public_key = updater_signature::public_key(pubkey)?;
+  let trusted_keys = update_settings
+    .trusted_keys
+    .iter()
+    .map(|key| Ok((updater_signature::public_key(&key.pubkey)?, key)))
+    .collect::<crate::Result<Vec<_>>>()?;
 
   let mut signed_paths = Vec::new();
   for bundle in update_enabled_bundles {
@@ -280,8 +663,24 @@ fn sign_updaters(
       // sign our path from environment variables
       let (signature_path, signature) = updater_signature::sign_file(&secret_key, path)?;
       if signature.keynum() != public_key.keynum() {
-        log::warn!("The updater secret key from `TAURI_SIGNING_PRIVATE_KEY` does not match the public key from `plugins > updater > pubkey`. If you are not rotating keys, this means your configuration is wrong and won't be accepted at runtime when performing update.");
+        match trusted_keys
+          .iter()
+          .find(|(key, _)| signature.keynum() == key.keynum())
+        {
+          Some((_, trusted_key)) if !trusted_key.is_valid_now() => {
+            log::warn!("The updater secret key from `TAURI_SIGNING_PRIVATE_KEY` matches a key of `plugins > updater > trustedKeys` that is not valid now. The updater checks the validity window against its current time, so this signature will be rejected at runtime while the key is not valid.");
+          }
+          Some(_) => {}
+          None => {
+            log::warn!("The updater secret key from `TAURI_SIGNING_PRIVATE_KEY` does not match the public key from `plugins > updater > pubkey` or any of `plugins > updater > trustedKeys`. If you are not rotating keys, this means your configuration is wrong and won't be accepted at runtime when performing update.");
+          }
+        }
       }
+      event::artifact(
+        ArtifactKind::UpdaterSignature,
+        Some(bundle.package_type.short_name()),
+        &signature_path,
+      );
       signed_paths.push(signature_path);
     }
   }
@@ -292,7 +691,6 @@ fn sign_updaters(
 }
 
 fn print_signed_updater_archive(output_paths: &[PathBuf]) -> crate::Result<()> {
-  use std::fmt::Write;
   if !output_paths.is_empty() {
     let finished_bundles = output_paths.len();
     let pluralised = if finished_bundles == 1 {
@@ -9,15 +9,17 @@ use crate::{
     config::{
       get as get_config, reload as reload_config, BeforeDevCommand, ConfigHandle, FrontendDist,
     },
+    event, workspace,
   },
   interface::{AppInterface, DevProcess, ExitReason, Interface},
   CommandExt, ConfigValue, Result,
 };
 
 use anyhow::{bail, Context};
+use builtin_dev_server::{Certificate, Content, ProxyRoute, ServerOptions};
 use clap::{ArgAction, Parser};
 use shared_child::SharedChild;
-use tauri_utils::platform::Target;
+use tauri_utils::{config::DevHttpsConfig, platform::Target};
 
 use std::{
   env::set_current_dir,
@@ -62,6 +64,9 @@ pub struct Options {
   /// JSON string or path to JSON file to merge with tauri.conf.json
   #[clap(short, long)]
   pub config: Option<ConfigValue>,
+  /// Name of the workspace app to run the command for, see `tauri.workspace.json`.
+  #[clap(long)]
+  pub app: Option<String>,
   /// Run the code in release mode
   #[clap(long = "release")]
   pub release_mode: bool,
@@ -76,7 +81,7 @@ pub struct Options {
   #[clap(long)]
   pub no_watch: bool,
 
-  /// Disable the built-in dev server for static files.
+  /// Disable the built-in dev server for static files and for the `build > devProxy` and `build > devHttps` options.
   #[clap(long)]
   pub no_dev_server: bool,
   /// Specify port for the built-in dev server for static files. Defaults to 1430.
@@ -96,6 +101,10 @@ pub fn command(options: Options) -> Result<()> {
 }
 
 fn command_internal(mut options: Options) -> Result<()> {
+  if let Some(app) = &options.app {
+    workspace::select_app(app)?;
+  }
+
   let target = options
     .target
     .as_deref()
@@ -183,7 +192,7 @@ pub fn setup(interface: &AppInterface, options: &mut Options, config: ConfigHand
         }
       } else {
         command.stdin(Stdio::piped());
-        command.stdout(os_pipe::dup_stdout()?);
+        command.stdout(event::child_stdout()?);
         command.stderr(os_pipe::dup_stderr()?);
 
         let child = SharedChild::spawn(&mut command)
@@ -247,40 +256,90 @@ pub fn setup(interface: &AppInterface, options: &mut Options, config: ConfigHand
     .build
     .frontend_dist
     .clone();
-  if !options.no_dev_server && dev_url.is_none() {
-    if let Some(FrontendDist::Directory(path)) = &frontend_dist {
-      if path.exists() {
-        let path = path.canonicalize()?;
+  let dev_proxy = config
+    .lock()
+    .unwrap()
+    .as_ref()
+    .unwrap()
+    .build
+    .dev_proxy
+    .clone();
+  let dev_https = config
+    .lock()
+    .unwrap()
+    .as_ref()
+    .unwrap()
+    .build
+    .dev_https
+    .clone()
+    .filter(DevHttpsConfig::enabled);
+  if !options.no_dev_server {
+    let content = match (&dev_url, &frontend_dist) {
+      // the dev server runs in front of the frontend dev server to add the proxy routes and HTTPS
+      (Some(url), _) if !dev_proxy.is_empty() || dev_https.is_some() => {
+        Some(Content::Proxy(url.clone()))
+      }
+      (None, Some(FrontendDist::Directory(path))) if path.exists() => {
+        Some(Content::Directory(path.canonicalize()?))
+      }
+      _ => None,
+    };
 
-        let ip = options
-          .host
-          .unwrap_or_else(|| Ipv4Addr::new(127, 0, 0, 1).into());
+    if let Some(content) = content {
+      let serves_directory = matches!(content, Content::Directory(_));
 
-        let server_url = builtin_dev_server::start(path, ip, options.port)?;
-        let server_url = format!("http://{server_url}");
-        dev_url = Some(server_url.parse().unwrap());
+      let ip = options
+        .host
+        .unwrap_or_else(|| Ipv4Addr::new(127, 0, 0, 1).into());
 
-        if let Some(c) = &mut options.config {
-          if let Some(build) = c
-            .0
-            .as_object_mut()
-            .and_then(|root| root.get_mut("build"))
-            .and_then(|build| build.as_object_mut())
-          {
-            build.insert("devUrl".into(), server_url.into());
-          }
-        } else {
-          options
-            .config
-            .replace(crate::ConfigValue(serde_json::json!({
-              "build": {
-                "devUrl": server_url
-              }
-            })));
+      let tls = match dev_https {
+        Some(DevHttpsConfig::Certificate { cert, key }) => Some(Certificate::read(&cert, &key)?),
+        Some(DevHttpsConfig::Enabled(_)) => {
+          let dir = crate::interface::rust::get_cargo_target_dir()?.join("tauri-dev-server");
+          Some(Certificate::self_signed(&dir, ip)?)
         }
+        None => None,
+      };
+      let proxy = dev_proxy
+        .iter()
+        .map(|(prefix, rule)| ProxyRoute::new(prefix.clone(), rule))
+        .collect();
+
+      let server_url = builtin_dev_server::start(
+        content,
+        ServerOptions {
+          ip,
+          port: options.port,
+          proxy,
+          tls,
+        },
+      )?;
+      let server_url = server_url.as_str().trim_end_matches('/').to_string();
+      // when proxying, wait for the frontend dev server instead
+      if serves_directory {
+        dev_url = Some(server_url.parse().unwrap());
+      }
 
-        reload_config(options.config.as_ref().map(|c| &c.0))?;
+      if let Some(c) = &mut options.config {
+        if let Some(build) = c
+          .0
+          .as_object_mut()
+          .and_then(|root| root.get_mut("build"))
+          .and_then(|build| build.as_object_mut())
+        {
+          build.insert("devUrl".into(), server_url.into());
+        }
+      } else {
+        options
+          .config
+          .replace(crate::ConfigValue(serde_json::json!({
+            "build": {
+              "devUrl": server_url
+            }
+          })));
       }
+
+      reload_config(options.config.as_ref().map(|c| &c.0))?;
     }
   }
 
@@ -3,33 +3,67 @@
 // SPDX-License-Identifier: MIT
 
 use axum::{
-  extract::{ws, State, WebSocketUpgrade},
+  extract::{ws, Request, State, WebSocketUpgrade},
   http::{header, StatusCode, Uri},
   response::{IntoResponse, Response},
+  Router,
 };
 use html5ever::{namespace_url, ns, LocalName, QualName};
+use hyper_util::rt::TokioIo;
 use kuchiki::{traits::TendrilSink, NodeRef};
 use std::{
   net::{IpAddr, SocketAddr},
   path::{Path, PathBuf},
+  sync::Arc,
   thread,
   time::Duration,
 };
 use tauri_utils::mime_type::MimeType;
 use tokio::sync::broadcast::{channel, Sender};
+use tokio_rustls::{rustls::ServerConfig, TlsAcceptor};
+use tower_service::Service;
+use url::Url;
+
+mod proxy;
+mod tls;
+
+pub use proxy::ProxyRoute;
+pub use tls::Certificate;
 
 const RELOAD_SCRIPT: &str = include_str!("./auto-reload.js");
 
+/// What the dev server serves, besides the proxy routes.
+pub enum Content {
+  /// The frontend files of the directory, reloading the app when they change.
+  Directory(PathBuf),
+  /// The responses of another dev server.
+  Proxy(Url),
+}
+
+pub struct ServerOptions {
+  pub ip: IpAddr,
+  pub port: Option<u16>,
+  pub proxy: Vec<ProxyRoute>,
+  pub tls: Option<Certificate>,
+}
+
 #[derive(Clone)]
 struct ServerState {
-  dir: PathBuf,
+  dir: Option<PathBuf>,
   address: SocketAddr,
+  https: bool,
+  routes: Arc<Vec<ProxyRoute>>,
   tx: Sender<()>,
 }
 
-pub fn start<P: AsRef<Path>>(dir: P, ip: IpAddr, port: Option<u16>) -> crate::Result<SocketAddr> {
-  let dir = dir.as_ref();
-  let dir = dunce::canonicalize(dir)?;
+/// Starts the dev server, returning its URL.
+pub fn start(content: Content, options: ServerOptions) -> crate::Result<Url> {
+  let ServerOptions {
+    ip,
+    port,
+    proxy: mut routes,
+    tls,
+  } = options;
 
   // bind port and tcp listener
   let auto_port = port.is_none();
@@ -50,13 +84,31 @@ pub fn start<P: AsRef<Path>>(dir: P, ip: IpAddr, port: Option<u16>) -> crate::Re
 
   let (tx, _) = channel(1);
 
-  // watch dir for changes
-  let tx_c = tx.clone();
-  watch(dir.clone(), move || {
-    let _ = tx_c.send(());
-  });
+  let dir = match content {
+    Content::Directory(dir) => {
+      let dir = dunce::canonicalize(dir)?;
+      // watch dir for changes
+      let tx_c = tx.clone();
+      watch(dir.clone(), move || {
+        let _ = tx_c.send(());
+      });
+      Some(dir)
+    }
+    Content::Proxy(url) => {
+      routes.push(ProxyRoute::fallback(url));
+      None
+    }
+  };
 
-  let state = ServerState { dir, tx, address };
+  let tls_config = tls.map(Certificate::server_config).transpose()?;
+  let state = ServerState {
+    dir,
+    address,
+    https: tls_config.is_some(),
+    routes: Arc::new(routes),
+    tx,
+  };
+  let url = format!("{}://{address}", if state.https { "https" } else { "http" }).parse()?;
 
   // start router thread
   std::thread::spawn(move || {
@@ -65,20 +117,62 @@ pub fn start<P: AsRef<Path>>(dir: P, ip: IpAddr, port: Option<u16>) -> crate::Re
       .build()
       .expect("failed to start tokio runtime for builtin dev server")
       .block_on(async move {
-        let router = axum::Router::new()
-          .fallback(handler)
-          .route("/__tauri_cli", axum::routing::get(ws_handler))
-          .with_state(state);
+        let mut router = Router::new().fallback(handler);
+        if state.dir.is_some() {
+          router = router.route("/__tauri_cli", axum::routing::get(ws_handler));
+        }
+        let router = router.with_state(state);
 
-        axum::serve(tokio::net::TcpListener::from_std(tcp_listener)?, router).await
+        let listener = tokio::net::TcpListener::from_std(tcp_listener)?;
+        match tls_config {
+          Some(config) => serve_tls(listener, router, config).await,
+          None => axum::serve(listener, router).await,
+        }
       })
       .expect("builtin server errored");
   });
 
-  Ok(address)
+  Ok(url)
+}
+
+/// Serves the router over HTTPS.
+async fn serve_tls(
+  listener: tokio::net::TcpListener,
+  router: Router,
+  config: Arc<ServerConfig>,
+) -> std::io::Result<()> {
+  let acceptor = TlsAcceptor::from(config);
+  loop {
+    let (stream, _) = listener.accept().await?;
+    let acceptor = acceptor.clone();
+    let router = router.clone();
+    tokio::spawn(async move {
+      let Ok(stream) = acceptor.accept(stream).await else {
+        return;
+      };
+      let service = hyper::service::service_fn(move |request: Request<hyper::body::Incoming>| {
+        router.clone().call(request)
+      });
+      let _ = hyper::server::conn::http1::Builder::new()
+        .serve_connection(TokioIo::new(stream), service)
+        .with_upgrades()
+        .await;
+    });
+  }
+}
+
+async fn handler(state: State<ServerState>, request: Request) -> Response {
+  if let Some(route) = proxy::find_route(&state.routes, request.uri().path()) {
+    return proxy::forward(route, request, state.https).await;
+  }
+
+  match &state.dir {
+    Some(dir) => serve_file(dir, request.uri(), &state).into_response(),
+    None => StatusCode::NOT_FOUND.into_response(),
+  }
 }
 
-async fn handler(uri: Uri, state: State<ServerState>) -> impl IntoResponse {
+fn serve_file(dir: &Path, uri: &Uri, state: &ServerState) -> impl IntoResponse {
   // Frontend files should not contain query parameters. This seems to be how vite handles it.
   let uri = uri.path();
 
@@ -88,16 +182,16 @@ async fn handler(uri: Uri, state: State<ServerState>) -> impl IntoResponse {
     uri.strip_prefix('/').unwrap_or(uri)
   };
 
-  let bytes = fs_read_scoped(state.dir.join(uri), &state.dir)
-    .or_else(|_| fs_read_scoped(state.dir.join(format!("{}.html", &uri)), &state.dir))
-    .or_else(|_| fs_read_scoped(state.dir.join(format!("{}/index.html", &uri)), &state.dir))
-    .or_else(|_| std::fs::read(state.dir.join("index.html")));
+  let bytes = fs_read_scoped(dir.join(uri), dir)
+    .or_else(|_| fs_read_scoped(dir.join(format!("{}.html", &uri)), dir))
+    .or_else(|_| fs_read_scoped(dir.join(format!("{}/index.html", &uri)), dir))
+    .or_else(|_| std::fs::read(dir.join("index.html")));
 
   match bytes {
     Ok(mut bytes) => {
       let mime_type = MimeType::parse_with_fallback(&bytes, uri, MimeType::OctetStream);
       if mime_type == MimeType::Html.to_string() {
-        bytes = inject_address(bytes, &state.address);
+        bytes = inject_address(bytes, &state.address, state.https);
       }
       (StatusCode::OK, [(header::CONTENT_TYPE, mime_type)], bytes)
     }
@@ -124,7 +218,7 @@ async fn ws_handler(ws: WebSocketUpgrade, state: State<ServerState>) -> Response
   })
 }
 
-fn inject_address(html_bytes: Vec<u8>, address: &SocketAddr) -> Vec<u8> {
+fn inject_address(html_bytes: Vec<u8>, address: &SocketAddr, https: bool) -> Vec<u8> {
   fn with_html_head<F: FnOnce(&NodeRef)>(document: &mut NodeRef, f: F) {
     if let Ok(ref node) = document.select_first("head") {
       f(node.as_node())
@@ -140,7 +234,11 @@ fn inject_address(html_bytes: Vec<u8>, address: &SocketAddr) -> Vec<u8> {
 
   let mut document = kuchiki::parse_html().one(String::from_utf8_lossy(&html_bytes).into_owned());
   with_html_head(&mut document, |head| {
-    let script = RELOAD_SCRIPT.replace("{{reload_url}}", &format!("ws://{address}/__tauri_cli"));
+    let scheme = if https { "wss" } else { "ws" };
+    let script = RELOAD_SCRIPT.replace(
+      "{{reload_url}}",
+      &format!("{scheme}://{address}/__tauri_cli"),
+    );
     let script_el = NodeRef::new_element(QualName::new(None, ns!(html), "script".into()), None);
     script_el.append(NodeRef::new_text(script));
     head.prepend(script_el);
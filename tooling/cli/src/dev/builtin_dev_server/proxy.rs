@@ -0,0 +1,271 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Forwarding of the dev server requests, including WebSocket connections, to another server.
+
+use std::sync::Arc;
+
+use axum::{
+  body::Body,
+  http::{header, HeaderValue, Request, StatusCode, Uri},
+  response::{IntoResponse, Response},
+};
+use hyper_util::rt::TokioIo;
+use tauri_utils::config::DevProxyRule;
+use tokio::{
+  io::{AsyncRead, AsyncWrite},
+  net::TcpStream,
+};
+use tokio_rustls::{
+  rustls::{pki_types::ServerName, ClientConfig, RootCertStore},
+  TlsConnector,
+};
+use url::Url;
+
+/// The headers only meaningful for a single connection, which are not forwarded.
+const HOP_BY_HOP_HEADERS: &[&str] = &[
+  "connection",
+  "keep-alive",
+  "proxy-authenticate",
+  "proxy-authorization",
+  "te",
+  "trailer",
+  "transfer-encoding",
+  "upgrade",
+];
+
+/// A route forwarded to another server.
+#[derive(Debug, Clone)]
+pub struct ProxyRoute {
+  /// The path prefix of the forwarded requests.
+  pub prefix: String,
+  pub target: Url,
+  /// The path replacing the prefix.
+  pub rewrite: Option<String>,
+  pub change_origin: bool,
+}
+
+impl ProxyRoute {
+  pub fn new(prefix: impl Into<String>, rule: &DevProxyRule) -> Self {
+    Self {
+      prefix: prefix.into(),
+      target: rule.target().clone(),
+      rewrite: rule.rewrite().map(ToString::to_string),
+      change_origin: rule.change_origin(),
+    }
+  }
+
+  /// Forwards every request to the server.
+  pub fn fallback(target: Url) -> Self {
+    Self {
+      prefix: "/".into(),
+      target,
+      rewrite: None,
+      change_origin: true,
+    }
+  }
+
+  fn matches(&self, path: &str) -> bool {
+    match path.strip_prefix(self.prefix.as_str()) {
+      Some(rest) => self.prefix.ends_with('/') || rest.is_empty() || rest.starts_with('/'),
+      None => false,
+    }
+  }
+
+  /// The path and query of the forwarded request.
+  fn forwarded_path(&self, uri: &Uri) -> String {
+    let path = uri.path();
+    let rest = &path[self.prefix.len().min(path.len())..];
+    let prefix = self.rewrite.as_deref().unwrap_or(&self.prefix);
+    let base = self.target.path().trim_end_matches('/');
+
+    let mut forwarded = format!("{base}/{}", prefix.trim_matches('/'));
+    let forwarded_len = forwarded.trim_end_matches('/').len();
+    forwarded.truncate(forwarded_len);
+    if !rest.is_empty() && !rest.starts_with('/') {
+      forwarded.push('/');
+    }
+    forwarded.push_str(rest);
+    if forwarded.is_empty() {
+      forwarded.push('/');
+    }
+    if let Some(query) = uri.query() {
+      forwarded.push('?');
+      forwarded.push_str(query);
+    }
+    forwarded
+  }
+}
+
+/// The route matching the path, preferring the longest prefix.
+pub fn find_route<'a>(routes: &'a [ProxyRoute], path: &str) -> Option<&'a ProxyRoute> {
+  routes
+    .iter()
+    .filter(|route| route.matches(path))
+    .max_by_key(|route| route.prefix.len())
+}
+
+/// Forwards the request to the route target, passing through the connection upgrades such as WebSockets.
+pub async fn forward(route: &ProxyRoute, mut request: Request<Body>, https: bool) -> Response {
+  let target = &route.target;
+  let (host, port) = match (target.host_str(), target.port_or_known_default()) {
+    (Some(host), Some(port)) => (host.to_string(), port),
+    _ => return bad_gateway(format!("invalid proxy target {target}")),
+  };
+
+  let is_upgrade = request.headers().contains_key(header::UPGRADE);
+  let client_upgrade = is_upgrade.then(|| hyper::upgrade::on(&mut request));
+
+  let original_host = request.headers().get(header::HOST).cloned();
+  *request.uri_mut() = match route.forwarded_path(request.uri()).parse() {
+    Ok(uri) => uri,
+    Err(e) => return bad_gateway(format!("invalid forwarded path: {e}")),
+  };
+
+  let headers = request.headers_mut();
+  if !is_upgrade {
+    for name in HOP_BY_HOP_HEADERS {
+      headers.remove(*name);
+    }
+  }
+  if route.change_origin || original_host.is_none() {
+    let host = match target.port() {
+      Some(port) => format!("{host}:{port}"),
+      None => host.clone(),
+    };
+    if let Ok(host) = HeaderValue::from_str(&host) {
+      headers.insert(header::HOST, host);
+    }
+  }
+  headers.insert(
+    "x-forwarded-proto",
+    HeaderValue::from_static(if https { "https" } else { "http" }),
+  );
+  if let Some(original_host) = original_host {
+    headers.insert("x-forwarded-host", original_host);
+  }
+
+  let stream = match TcpStream::connect((host.as_str(), port)).await {
+    Ok(stream) => stream,
+    Err(e) => return bad_gateway(format!("failed to connect to {target}: {e}")),
+  };
+  let response = if matches!(target.scheme(), "https" | "wss") {
+    let server_name = match ServerName::try_from(host) {
+      Ok(name) => name,
+      Err(e) => return bad_gateway(format!("invalid proxy target host: {e}")),
+    };
+    match TlsConnector::from(client_config())
+      .connect(server_name, stream)
+      .await
+    {
+      Ok(stream) => send(stream, request).await,
+      Err(e) => return bad_gateway(format!("TLS handshake with {target} failed: {e}")),
+    }
+  } else {
+    send(stream, request).await
+  };
+
+  let mut response = match response {
+    Ok(response) => response,
+    Err(e) => return bad_gateway(format!("failed to forward the request to {target}: {e}")),
+  };
+
+  if response.status() == StatusCode::SWITCHING_PROTOCOLS {
+    if let Some(client_upgrade) = client_upgrade {
+      let server_upgrade = hyper::upgrade::on(&mut response);
+      tokio::spawn(async move {
+        if let (Ok(client), Ok(server)) = tokio::join!(client_upgrade, server_upgrade) {
+          let _ =
+            tokio::io::copy_bidirectional(&mut TokioIo::new(client), &mut TokioIo::new(server))
+              .await;
+        }
+      });
+    }
+  } else {
+    for name in HOP_BY_HOP_HEADERS {
+      response.headers_mut().remove(*name);
+    }
+  }
+
+  response.map(Body::new)
+}
+
+async fn send<S: AsyncRead + AsyncWrite + Unpin + Send + 'static>(
+  stream: S,
+  request: Request<Body>,
+) -> hyper::Result<Response<hyper::body::Incoming>> {
+  let (mut sender, connection) =
+    hyper::client::conn::http1::handshake(TokioIo::new(stream)).await?;
+  tokio::spawn(connection.with_upgrades());
+  sender.send_request(request).await
+}
+
+fn client_config() -> Arc<ClientConfig> {
+  let mut roots = RootCertStore::empty();
+  roots.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
+  Arc::new(
+    ClientConfig::builder()
+      .with_root_certificates(roots)
+      .with_no_client_auth(),
+  )
+}
+
+fn bad_gateway(message: String) -> Response {
+  log::error!("{message}");
+  (StatusCode::BAD_GATEWAY, message).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn route(prefix: &str, target: &str, rewrite: Option<&str>) -> ProxyRoute {
+    ProxyRoute {
+      prefix: prefix.into(),
+      target: target.parse().unwrap(),
+      rewrite: rewrite.map(Into::into),
+      change_origin: true,
+    }
+  }
+
+  #[test]
+  fn find_routes() {
+    let routes = [
+      route("/api", "http://localhost:8080", None),
+      route("/api/auth", "http://localhost:9090", None),
+    ];
+    assert_eq!(find_route(&routes, "/api").unwrap().prefix, "/api");
+    assert_eq!(find_route(&routes, "/api/users").unwrap().prefix, "/api");
+    assert_eq!(
+      find_route(&routes, "/api/auth/login").unwrap().prefix,
+      "/api/auth"
+    );
+    assert!(find_route(&routes, "/apidocs").is_none());
+    assert!(find_route(&routes, "/index.html").is_none());
+  }
+
+  #[test]
+  fn forwarded_paths() {
+    let uri = |s: &str| s.parse::<Uri>().unwrap();
+    let api = route("/api", "http://localhost:8080", None);
+    assert_eq!(
+      api.forwarded_path(&uri("/api/users?page=2")),
+      "/api/users?page=2"
+    );
+
+    let rewritten = route("/api", "http://localhost:8080/v1/", Some(""));
+    assert_eq!(rewritten.forwarded_path(&uri("/api/users")), "/v1/users");
+    assert_eq!(rewritten.forwarded_path(&uri("/api")), "/v1");
+
+    let root = route("/api", "http://localhost:8080", Some("/"));
+    assert_eq!(root.forwarded_path(&uri("/api")), "/");
+
+    let fallback = ProxyRoute::fallback("http://localhost:5173".parse().unwrap());
+    assert_eq!(fallback.forwarded_path(&uri("/")), "/");
+    assert_eq!(
+      fallback.forwarded_path(&uri("/src/main.ts?t=1")),
+      "/src/main.ts?t=1"
+    );
+  }
+}
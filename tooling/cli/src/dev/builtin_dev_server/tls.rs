@@ -0,0 +1,146 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! The TLS certificate of the dev server.
+
+use std::{
+  net::{IpAddr, Ipv4Addr, Ipv6Addr},
+  path::{Path, PathBuf},
+  sync::Arc,
+};
+
+use anyhow::Context;
+use tokio_rustls::rustls::{
+  pki_types::{
+    CertificateDer, PrivateKeyDer, PrivatePkcs1KeyDer, PrivatePkcs8KeyDer, PrivateSec1KeyDer,
+  },
+  ServerConfig,
+};
+
+/// The certificate and private key of the dev server.
+pub struct Certificate {
+  chain: Vec<CertificateDer<'static>>,
+  key: PrivateKeyDer<'static>,
+}
+
+impl Certificate {
+  /// Reads the PEM encoded certificate chain and private key.
+  pub fn read(cert: &Path, key: &Path) -> crate::Result<Self> {
+    let chain = std::fs::read(cert)
+      .with_context(|| format!("failed to read certificate {}", cert.display()))?;
+    let key = std::fs::read(key)
+      .with_context(|| format!("failed to read private key {}", key.display()))?;
+    Self::from_pem(&chain, &key)
+  }
+
+  /// Reads the self-signed certificate for the host from the directory, generating it if needed.
+  ///
+  /// The certificate is reused across runs so it only has to be trusted once.
+  pub fn self_signed(dir: &Path, ip: IpAddr) -> crate::Result<Self> {
+    let (cert_path, key_path) = self_signed_paths(dir, ip);
+    if let (Ok(cert), Ok(key)) = (std::fs::read(&cert_path), std::fs::read(&key_path)) {
+      if let Ok(certificate) = Self::from_pem(&cert, &key) {
+        return Ok(certificate);
+      }
+    }
+
+    let mut params = rcgen::CertificateParams::default();
+    params
+      .distinguished_name
+      .push(rcgen::DnType::CommonName, "Tauri dev server");
+    params.subject_alt_names = vec![
+      rcgen::SanType::DnsName("localhost".into()),
+      rcgen::SanType::IpAddress(Ipv4Addr::LOCALHOST.into()),
+      rcgen::SanType::IpAddress(Ipv6Addr::LOCALHOST.into()),
+    ];
+    if !ip.is_loopback() && !ip.is_unspecified() {
+      params.subject_alt_names.push(rcgen::SanType::IpAddress(ip));
+    }
+    let certificate = rcgen::Certificate::from_params(params)?;
+    let cert = certificate.serialize_pem()?;
+    let key = certificate.serialize_private_key_pem();
+
+    std::fs::create_dir_all(dir)?;
+    std::fs::write(&cert_path, &cert)?;
+    std::fs::write(&key_path, &key)?;
+    log::info!(
+      action = "Generated";
+      "self-signed certificate for the dev server at {}. Add it to the trusted certificates of your system for the webview to load the app.",
+      tauri_utils::display_path(&cert_path)
+    );
+
+    Self::from_pem(cert.as_bytes(), key.as_bytes())
+  }
+
+  fn from_pem(chain: &[u8], key: &[u8]) -> crate::Result<Self> {
+    let chain = pem::parse_many(chain)
+      .context("failed to parse certificate")?
+      .into_iter()
+      .filter(|pem| pem.tag() == "CERTIFICATE")
+      .map(|pem| CertificateDer::from(pem.into_contents()))
+      .collect::<Vec<_>>();
+    if chain.is_empty() {
+      anyhow::bail!("no certificate found");
+    }
+
+    let key = pem::parse_many(key)
+      .context("failed to parse private key")?
+      .into_iter()
+      .find_map(|pem| match pem.tag() {
+        "PRIVATE KEY" => Some(PrivatePkcs8KeyDer::from(pem.into_contents()).into()),
+        "RSA PRIVATE KEY" => Some(PrivatePkcs1KeyDer::from(pem.into_contents()).into()),
+        "EC PRIVATE KEY" => Some(PrivateSec1KeyDer::from(pem.into_contents()).into()),
+        _ => None,
+      })
+      .ok_or_else(|| anyhow::anyhow!("no private key found"))?;
+
+    Ok(Self { chain, key })
+  }
+
+  pub fn server_config(self) -> crate::Result<Arc<ServerConfig>> {
+    let mut config = ServerConfig::builder()
+      .with_no_client_auth()
+      .with_single_cert(self.chain, self.key)
+      .context("invalid dev server certificate")?;
+    config.alpn_protocols = vec![b"http/1.1".to_vec()];
+    Ok(Arc::new(config))
+  }
+}
+
+fn self_signed_paths(dir: &Path, ip: IpAddr) -> (PathBuf, PathBuf) {
+  let name = if ip.is_loopback() || ip.is_unspecified() {
+    "localhost".to_string()
+  } else {
+    ip.to_string().replace(':', "_")
+  };
+  (
+    dir.join(format!("{name}.pem")),
+    dir.join(format!("{name}-key.pem")),
+  )
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn self_signed_certificate() {
+    let dir = std::env::temp_dir().join("tauri-dev-server-certificate-test");
+    let _ = std::fs::remove_dir_all(&dir);
+    let ip = Ipv4Addr::new(192, 168, 1, 2).into();
+    Certificate::self_signed(&dir, ip).unwrap();
+    let (cert, key) = self_signed_paths(&dir, ip);
+    assert_eq!(cert, dir.join("192.168.1.2.pem"));
+    assert!(cert.exists() && key.exists());
+
+    // reused on the next run
+    let pem = std::fs::read(&cert).unwrap();
+    Certificate::self_signed(&dir, ip)
+      .unwrap()
+      .server_config()
+      .unwrap();
+    assert_eq!(std::fs::read(&cert).unwrap(), pem);
+    std::fs::remove_dir_all(&dir).unwrap();
+  }
+}
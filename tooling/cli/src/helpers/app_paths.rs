@@ -7,7 +7,7 @@ use std::{
   env::current_dir,
   ffi::OsStr,
   path::{Path, PathBuf},
-  sync::OnceLock,
+  sync::{Mutex, OnceLock},
 };
 
 use ignore::WalkBuilder;
@@ -127,8 +127,23 @@ fn get_app_dir() -> Option<PathBuf> {
   .map(|p| p.parent().unwrap().to_path_buf())
 }
 
+fn app_dir_cache() -> &'static Mutex<Option<&'static PathBuf>> {
+  static APP_DIR: OnceLock<Mutex<Option<&'static PathBuf>>> = OnceLock::new();
+  APP_DIR.get_or_init(Default::default)
+}
+
 pub fn app_dir() -> &'static PathBuf {
-  static APP_DIR: OnceLock<PathBuf> = OnceLock::new();
-  APP_DIR
-    .get_or_init(|| get_app_dir().unwrap_or_else(|| tauri_dir().parent().unwrap().to_path_buf()))
+  let mut app_dir = app_dir_cache().lock().unwrap();
+  app_dir.get_or_insert_with(|| {
+    // leaked once per app, see `reset_app_dir`
+    Box::leak(Box::new(
+      get_app_dir().unwrap_or_else(|| tauri_dir().parent().unwrap().to_path_buf()),
+    ))
+  })
+}
+
+/// Clears the cached app directory, so it is looked up again from the current directory
+/// when running a command for another app of the workspace.
+pub fn reset_app_dir() {
+  app_dir_cache().lock().unwrap().take();
 }
@@ -0,0 +1,72 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Loading of the app capabilities.
+
+use std::path::Path;
+
+use tauri_utils::{
+  acl::capability::{Capability, CapabilityFile, OsPermission},
+  config::{CapabilityEntry, Config},
+  platform::Target,
+};
+
+/// Loads the capabilities of the app enabled for the given target.
+pub fn enabled_capabilities(
+  config: &Config,
+  tauri_dir: &Path,
+  target: Target,
+) -> crate::Result<Vec<Capability>> {
+  let mut capabilities = Vec::new();
+  let mut references = Vec::new();
+
+  for entry in &config.app.security.capabilities {
+    match entry {
+      CapabilityEntry::Inlined(capability) => capabilities.push(capability.clone()),
+      CapabilityEntry::Reference(identifier) => references.push(identifier.as_str()),
+    }
+  }
+
+  // when the config lists capabilities, only those are enabled
+  let enable_all = config.app.security.capabilities.is_empty();
+
+  let pattern = tauri_dir.join("capabilities/**/*");
+  for path in glob::glob(&pattern.to_string_lossy())?.flatten() {
+    if !path.is_file() {
+      continue;
+    }
+    let list = match CapabilityFile::load(&path)? {
+      CapabilityFile::Capability(capability) => vec![capability],
+      CapabilityFile::List(list) | CapabilityFile::NamedList { capabilities: list } => list,
+    };
+    capabilities.extend(
+      list
+        .into_iter()
+        .filter(|c| enable_all || references.contains(&c.identifier.as_str())),
+    );
+  }
+
+  capabilities.retain(|c| {
+    c.platforms
+      .as_ref()
+      .map_or(true, |platforms| platforms.contains(&target))
+  });
+
+  Ok(capabilities)
+}
+
+/// The operating system permissions required by the capabilities enabled for the given target, without duplicates.
+pub fn os_permissions(
+  config: &Config,
+  tauri_dir: &Path,
+  target: Target,
+) -> crate::Result<Vec<OsPermission>> {
+  let mut permissions: Vec<OsPermission> = enabled_capabilities(config, tauri_dir, target)?
+    .into_iter()
+    .flat_map(|c| c.os_permissions)
+    .collect();
+  permissions.sort();
+  permissions.dedup();
+  Ok(permissions)
+}
@@ -10,7 +10,6 @@ pub use tauri_utils::{config::*, platform::Target};
 use std::{
   collections::HashMap,
   env::{current_dir, set_current_dir, set_var, var_os},
-  ffi::OsStr,
   process::exit,
   sync::{Arc, Mutex, OnceLock},
 };
@@ -91,6 +90,12 @@ pub fn wix_settings(config: WixConfig) -> tauri_bundler::WixSettings {
     banner_path: config.banner_path,
     dialog_image_path: config.dialog_image_path,
     fips_compliant: var_os("TAURI_BUNDLER_WIX_FIPS_COMPLIANT").map_or(false, |v| v == "true"),
+    install_mode: config.install_mode,
+    upgrade: config.upgrade,
+    custom_actions: config.custom_actions,
+    registry: config.registry,
+    services: config.services,
+    merge_fragments: config.merge_fragments,
   }
 }
 
@@ -107,6 +112,19 @@ pub fn nsis_settings(config: NsisConfig) -> tauri_bundler::NsisSettings {
     compression: config.compression,
     start_menu_folder: config.start_menu_folder,
     installer_hooks: config.installer_hooks,
+    components: config.components,
+    web_installer: config.web_installer,
+    custom_pages: config.custom_pages,
+  }
+}
+
+pub fn msix_settings(config: MsixConfig) -> tauri_bundler::MsixSettings {
+  tauri_bundler::MsixSettings {
+    identity_name: config.identity_name,
+    publisher: config.publisher,
+    publisher_display_name: config.publisher_display_name,
+    capabilities: config.capabilities,
+    min_version: config.min_version,
   }
 }
 
@@ -115,6 +133,12 @@ fn config_handle() -> &'static ConfigHandle {
   CONFIG_HANDLE.get_or_init(Default::default)
 }
 
+/// Clears the loaded config, so it is read again from the current directory
+/// when running a command for another app of the workspace.
+pub fn reset() {
+  config_handle().lock().unwrap().take();
+}
+
 /// Gets the static parsed config from `tauri.conf.json`.
 fn get_internal(
   merge_config: Option<&serde_json::Value>,
@@ -131,8 +155,15 @@ fn get_internal(
   let config_file_name = config_path.file_name().unwrap().to_string_lossy();
   let mut extensions = HashMap::new();
 
+  let base_config = super::workspace::base_config(&tauri_dir)?;
+  if let Some(base_config) = &base_config {
+    let mut app_config = base_config.clone();
+    merge(&mut app_config, &config);
+    config = app_config;
+  }
+
   if let Some((platform_config, config_path)) =
-    tauri_utils::config::parse::read_platform(target, tauri_dir)?
+    tauri_utils::config::parse::read_platform(target, tauri_dir.clone())?
   {
     merge(&mut config, &platform_config);
     extensions.insert(
@@ -141,6 +172,19 @@ fn get_internal(
     );
   }
 
+  let profile = std::env::var(tauri_utils::config::parse::PROFILE_ENV)
+    .ok()
+    .filter(|profile| !profile.is_empty());
+  if let Some(profile) = &profile {
+    let (profile_config, config_path) =
+      tauri_utils::config::parse::read_profile(profile, &tauri_dir)?;
+    merge(&mut config, &profile_config);
+    extensions.insert(
+      config_path.file_name().unwrap().to_str().unwrap().into(),
+      profile_config,
+    );
+  }
+
   if let Some(merge_config) = merge_config {
     let merge_config_str = serde_json::to_string(&merge_config).unwrap();
     set_var("TAURI_CONFIG", merge_config_str);
@@ -148,25 +192,45 @@ fn get_internal(
     extensions.insert(MERGE_CONFIG_EXTENSION_NAME.into(), merge_config.clone());
   };
 
-  if config_path.extension() == Some(OsStr::new("json"))
-    || config_path.extension() == Some(OsStr::new("json5"))
+  let interpolated = tauri_utils::config::parse::interpolate_env(&mut config)?;
+
+  if base_config.is_some() || profile.is_some() || interpolated {
+    // the app build script only reads the app config files, so it gets the whole resolved config
+    set_var("TAURI_CONFIG", serde_json::to_string(&config)?);
+  }
+
+  // the TOML keys are normalized when parsed, so every format is validated against the same schema
+  let mut schema: JsonValue = serde_json::from_str(include_str!("../../schema.json"))?;
+  // the plugin configuration schemas are saved by the app build script
+  if let Some(plugin_schemas) = std::fs::read_to_string(
+    tauri_dir
+      .join("gen/schemas")
+      .join(tauri_utils::plugin::PLUGIN_CONFIG_SCHEMAS_FILE_NAME),
+  )
+  .ok()
+  .and_then(|plugin_schemas| serde_json::from_str(&plugin_schemas).ok())
   {
-    let schema: JsonValue = serde_json::from_str(include_str!("../../schema.json"))?;
-    let schema = jsonschema::JSONSchema::compile(&schema).unwrap();
-    let result = schema.validate(&config);
-    if let Err(errors) = result {
-      for error in errors {
-        let path = error.instance_path.clone().into_vec().join(" > ");
-        if path.is_empty() {
-          log::error!("`{}` error: {}", config_file_name, error);
-        } else {
-          log::error!("`{}` error on `{}`: {}", config_file_name, path, error);
-        }
-      }
-      if !reload {
-        exit(1);
+    tauri_utils::plugin::extend_config_schema(&mut schema, &plugin_schemas);
+  }
+  let schema = jsonschema::JSONSchema::compile(&schema).unwrap();
+  if let Err(errors) = schema.validate(&config) {
+    for error in errors {
+      let path = error.instance_path.clone().into_vec().join(" > ");
+      if path.is_empty() {
+        log::error!("`{}` error: {}", config_file_name, error);
+      } else {
+        log::error!("`{}` error on `{}`: {}", config_file_name, path, error);
       }
     }
+    if crate::migrate::is_v1_config(&config) {
+      log::error!(
+        "`{}` uses the Tauri v1 format, run `tauri migrate` to update it",
+        config_file_name
+      );
+    }
+    if !reload {
+      exit(1);
+    }
   }
 
   // the `Config` deserializer for `package > version` can resolve the version from a path relative to the config path
@@ -177,6 +241,18 @@ fn get_internal(
   // revert to previous working directory
   set_current_dir(current_dir)?;
 
+  let errors = config.bundle.linux.validate();
+  for error in &errors {
+    log::error!(
+      "`{}` error on `bundle > linux`: {}",
+      config_file_name,
+      error
+    );
+  }
+  if !errors.is_empty() && !reload {
+    exit(1);
+  }
+
   for (plugin, conf) in &config.plugins.0 {
     set_var(
       format!(
@@ -196,6 +272,51 @@ fn get_internal(
   Ok(config_handle().clone())
 }
 
+/// Whether the configurations only differ on the settings the app applies at runtime with `AppHandle::reload_config_subset`.
+pub fn is_reloadable_at_runtime(previous: &Config, current: &Config) -> bool {
+  // the app keeps its CSP if the reloaded configuration doesn't have one
+  let has_csp =
+    |config: &Config| config.app.security.csp.is_some() || config.app.security.dev_csp.is_some();
+  if has_csp(previous) && !has_csp(current) {
+    return false;
+  }
+
+  let without_reloadable_settings = |config: &Config| {
+    let mut config = serde_json::to_value(config).ok()?;
+    if let Some(windows) = config
+      .pointer_mut("/app/windows")
+      .and_then(JsonValue::as_array_mut)
+    {
+      for window in windows.iter_mut().filter_map(JsonValue::as_object_mut) {
+        window.remove("title");
+        window.remove("theme");
+      }
+    }
+    for (pointer, keys) in [
+      ("/app/trayIcon", &["tooltip", "title"][..]),
+      ("/app/security", &["csp", "devCsp"][..]),
+    ] {
+      if let Some(object) = config
+        .pointer_mut(pointer)
+        .and_then(JsonValue::as_object_mut)
+      {
+        for key in keys {
+          object.remove(*key);
+        }
+      }
+    }
+    Some(config)
+  };
+
+  match (
+    without_reloadable_settings(previous),
+    without_reloadable_settings(current),
+  ) {
+    (Some(previous), Some(current)) => previous == current,
+    _ => false,
+  }
+}
+
 pub fn get(
   target: Target,
   merge_config: Option<&serde_json::Value>,
@@ -215,3 +336,41 @@ pub fn reload(merge_config: Option<&serde_json::Value>) -> crate::Result<ConfigH
     Err(anyhow::anyhow!("config not loaded"))
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn reloadable_at_runtime() {
+    let config: Config = serde_json::from_value(serde_json::json!({
+      "identifier": "com.tauri.dev",
+      "app": {
+        "windows": [{ "title": "Tauri" }],
+        "security": { "csp": "default-src 'self'" }
+      },
+      "plugins": { "updater": { "endpoints": ["https://releases.tauri.app"], "pubkey": "key" } }
+    }))
+    .unwrap();
+
+    let mut reloadable = config.clone();
+    reloadable.app.windows[0].title = "Tauri App".into();
+    reloadable.app.windows[0].theme = Some(tauri_utils::Theme::Dark);
+    assert!(is_reloadable_at_runtime(&config, &reloadable));
+
+    let mut updater = config.clone();
+    updater.plugins.0.insert(
+      "updater".into(),
+      serde_json::json!({ "endpoints": [], "pubkey": "key" }),
+    );
+    assert!(!is_reloadable_at_runtime(&config, &updater));
+
+    let mut structural = config.clone();
+    structural.app.windows[0].width = 1024.;
+    assert!(!is_reloadable_at_runtime(&config, &structural));
+
+    let mut without_csp = config.clone();
+    without_csp.app.security.csp = None;
+    assert!(!is_reloadable_at_runtime(&config, &without_csp));
+  }
+}
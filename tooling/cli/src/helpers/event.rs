@@ -0,0 +1,195 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Structured events written to stdout with `--output-format json`.
+//!
+//! Each event is a JSON object on its own line, tagged by its `type` field:
+//!
+//! ```json
+//! {"type":"phase","phase":"build","status":"started"}
+//! {"type":"log","level":"info","action":"Built","message":"application at: target/release/app"}
+//! {"type":"artifact","kind":"bundle","bundle":"deb","path":"target/release/bundle/deb/app_0.1.0_amd64.deb"}
+//! {"type":"phase","phase":"build","status":"finished"}
+//! ```
+//!
+//! The output of the commands run by the CLI, such as cargo and the hooks, is written to stderr instead
+//! so stdout only contains events.
+
+use std::{
+  io::Write,
+  path::Path,
+  sync::atomic::{AtomicBool, Ordering},
+};
+
+use clap::ValueEnum;
+use serde::Serialize;
+
+static JSON_OUTPUT: AtomicBool = AtomicBool::new(false);
+
+/// The format of the CLI output.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+  /// Human readable logs on stderr.
+  #[default]
+  Text,
+  /// One JSON event per line on stdout.
+  Json,
+}
+
+/// Sets the output format for the rest of the process.
+pub fn set_output_format(format: OutputFormat) {
+  JSON_OUTPUT.store(format == OutputFormat::Json, Ordering::Relaxed);
+}
+
+/// Whether the events are written to stdout.
+pub fn is_json() -> bool {
+  JSON_OUTPUT.load(Ordering::Relaxed)
+}
+
+/// A build step.
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(tag = "phase", rename_all = "camelCase")]
+pub enum Phase<'a> {
+  /// A `beforeDevCommand`, `beforeBuildCommand`, `beforeBundleCommand` or bundle hook.
+  Hook { name: &'a str },
+  /// The compilation of the app.
+  Build,
+  /// The packaging of the app.
+  Bundle,
+  /// The development app process.
+  Run,
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PhaseStatus {
+  Started,
+  Finished,
+}
+
+/// The kind of a generated file.
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ArtifactKind {
+  /// The app binary.
+  Binary,
+  /// A bundle, such as a `.deb` or `.msi` file.
+  Bundle,
+  /// The signature of an updater artifact.
+  UpdaterSignature,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum Event<'a> {
+  /// A log message, including warnings and errors.
+  Log {
+    level: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    action: Option<&'a str>,
+    target: &'a str,
+    message: String,
+  },
+  /// A line printed by a command run by the CLI.
+  Output { stream: &'a str, line: String },
+  Phase {
+    #[serde(flatten)]
+    phase: Phase<'a>,
+    status: PhaseStatus,
+  },
+  Artifact {
+    kind: ArtifactKind,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    bundle: Option<&'a str>,
+    path: &'a Path,
+  },
+}
+
+impl Event<'_> {
+  /// Serializes the event to a single line.
+  pub fn to_line(&self) -> String {
+    let mut line = serde_json::to_string(self).expect("failed to serialize event");
+    line.push('\n');
+    line
+  }
+
+  /// Writes the event to stdout when the JSON output is enabled.
+  pub fn emit(&self) {
+    if is_json() {
+      let mut stdout = std::io::stdout().lock();
+      let _ = stdout.write_all(self.to_line().as_bytes());
+      let _ = stdout.flush();
+    }
+  }
+}
+
+/// Emits the start of the phase, runs it and emits its end if it succeeded.
+pub fn phase<T>(phase: Phase<'_>, f: impl FnOnce() -> crate::Result<T>) -> crate::Result<T> {
+  Event::Phase {
+    phase,
+    status: PhaseStatus::Started,
+  }
+  .emit();
+  let result = f()?;
+  Event::Phase {
+    phase,
+    status: PhaseStatus::Finished,
+  }
+  .emit();
+  Ok(result)
+}
+
+/// Emits a generated file.
+pub fn artifact(kind: ArtifactKind, bundle: Option<&str>, path: &Path) {
+  Event::Artifact { kind, bundle, path }.emit();
+}
+
+/// The stdout handle given to the commands run by the CLI.
+///
+/// It is stderr with the JSON output, so the command output does not mix with the events.
+pub fn child_stdout() -> std::io::Result<os_pipe::PipeWriter> {
+  if is_json() {
+    os_pipe::dup_stderr()
+  } else {
+    os_pipe::dup_stdout()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn serialize_events() {
+    assert_eq!(
+      Event::Phase {
+        phase: Phase::Hook {
+          name: "beforeBuildCommand"
+        },
+        status: PhaseStatus::Started
+      }
+      .to_line(),
+      "{\"type\":\"phase\",\"phase\":\"hook\",\"name\":\"beforeBuildCommand\",\"status\":\"started\"}\n"
+    );
+    assert_eq!(
+      Event::Artifact {
+        kind: ArtifactKind::Bundle,
+        bundle: Some("deb"),
+        path: Path::new("app.deb"),
+      }
+      .to_line(),
+      "{\"type\":\"artifact\",\"kind\":\"bundle\",\"bundle\":\"deb\",\"path\":\"app.deb\"}\n"
+    );
+    assert_eq!(
+      Event::Log {
+        level: "warn",
+        action: None,
+        target: "tauri_cli",
+        message: "careful".into(),
+      }
+      .to_line(),
+      "{\"type\":\"log\",\"level\":\"warn\",\"target\":\"tauri_cli\",\"message\":\"careful\"}\n"
+    );
+  }
+}
@@ -3,15 +3,19 @@
 // SPDX-License-Identifier: MIT
 
 pub mod app_paths;
+pub mod capabilities;
 pub mod cargo;
 pub mod cargo_manifest;
 pub mod config;
+pub mod event;
 pub mod flock;
 pub mod framework;
 pub mod npm;
 pub mod prompts;
+pub mod sandbox;
 pub mod template;
 pub mod updater_signature;
+pub mod workspace;
 
 use std::{
   collections::HashMap,
@@ -70,6 +74,17 @@ pub fn run_hook(
   hook: HookCommand,
   interface: &AppInterface,
   debug: bool,
+) -> crate::Result<()> {
+  run_hook_with_env(name, hook, interface, debug, HashMap::new())
+}
+
+/// Runs the hook command with additional environment variables.
+pub fn run_hook_with_env(
+  name: &str,
+  hook: HookCommand,
+  interface: &AppInterface,
+  debug: bool,
+  hook_env: HashMap<&'static str, String>,
 ) -> crate::Result<()> {
   let (script, script_cwd) = match hook {
     HookCommand::Script(s) if s.is_empty() => (None, None),
@@ -78,10 +93,17 @@ pub fn run_hook(
   };
   let cwd = script_cwd.unwrap_or_else(|| app_dir().clone());
   if let Some(script) = script {
+    event::Event::Phase {
+      phase: event::Phase::Hook { name },
+      status: event::PhaseStatus::Started,
+    }
+    .emit();
+
     log::info!(action = "Running"; "{} `{}`", name, script);
 
     let mut env = command_env(debug);
     env.extend(interface.env());
+    env.extend(hook_env);
 
     log::debug!("Setting environment for hook {:?}", env);
 
@@ -111,6 +133,12 @@ pub fn run_hook(
         status.code().unwrap_or_default()
       );
     }
+
+    event::Event::Phase {
+      phase: event::Phase::Hook { name },
+      status: event::PhaseStatus::Finished,
+    }
+    .emit();
   }
 
   Ok(())
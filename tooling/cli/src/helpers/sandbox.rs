@@ -0,0 +1,159 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Linux sandbox permissions derived from the app capabilities.
+
+use std::path::Path;
+
+use tauri_utils::{acl::capability::Capability, config::Config, platform::Target};
+
+use super::capabilities::enabled_capabilities;
+
+/// Flatpak sandbox permissions every app needs to render its windows.
+const FLATPAK_BASE_FINISH_ARGS: &[&str] = &[
+  "--share=ipc",
+  "--socket=wayland",
+  "--socket=fallback-x11",
+  "--device=dri",
+];
+
+/// Snap interfaces every app needs, on top of the ones connected by the gnome extension.
+const SNAP_BASE_PLUGS: &[&str] = &["browser-support"];
+
+/// The Flatpak sandbox permissions required by the permissions of the given plugin.
+///
+/// Files selected with the dialog plugin are exposed through the document portal,
+/// so only direct file system access requires static permissions.
+fn plugin_finish_args(plugin: &str) -> &'static [&'static str] {
+  match plugin {
+    "http" | "upload" | "websocket" => &["--share=network"],
+    "fs" => &["--filesystem=xdg-documents", "--filesystem=xdg-download"],
+    "notification" => &["--talk-name=org.freedesktop.Notifications"],
+    _ => &[],
+  }
+}
+
+/// The Snap interfaces required by the permissions of the given plugin.
+fn plugin_plugs(plugin: &str) -> &'static [&'static str] {
+  match plugin {
+    "http" | "upload" | "websocket" => &["network"],
+    "fs" => &["home"],
+    _ => &[],
+  }
+}
+
+/// Collects the sandbox permissions required by the capabilities, followed by the `extra` ones.
+fn permissions(
+  capabilities: &[Capability],
+  base: &[&str],
+  plugin_permissions: fn(&str) -> &'static [&'static str],
+  network: &str,
+  extra: &[String],
+) -> Vec<String> {
+  let mut permissions: Vec<String> = base.iter().map(|p| p.to_string()).collect();
+  let mut push = |permission: &str| {
+    if !permissions.iter().any(|p| p == permission) {
+      permissions.push(permission.to_string());
+    }
+  };
+
+  for capability in capabilities {
+    for permission in &capability.permissions {
+      if let Some(plugin) = permission.identifier().get_prefix() {
+        for p in plugin_permissions(plugin) {
+          push(p);
+        }
+      }
+    }
+    // remote URLs are loaded from the network
+    if capability.remote.is_some() {
+      push(network);
+    }
+  }
+
+  for p in extra {
+    push(p);
+  }
+
+  permissions
+}
+
+/// Computes the Flatpak sandbox permissions (`finish-args`) from the app capabilities,
+/// followed by the `bundle > linux > flatpak > finishArgs` configuration.
+pub fn flatpak_finish_args(config: &Config, tauri_dir: &Path) -> crate::Result<Vec<String>> {
+  Ok(permissions(
+    &enabled_capabilities(config, tauri_dir, Target::Linux)?,
+    FLATPAK_BASE_FINISH_ARGS,
+    plugin_finish_args,
+    "--share=network",
+    &config.bundle.linux.flatpak.finish_args,
+  ))
+}
+
+/// Computes the Snap interfaces (`plugs`) from the app capabilities,
+/// followed by the `bundle > linux > snap > plugs` configuration.
+pub fn snap_plugs(config: &Config, tauri_dir: &Path) -> crate::Result<Vec<String>> {
+  Ok(permissions(
+    &enabled_capabilities(config, tauri_dir, Target::Linux)?,
+    SNAP_BASE_PLUGS,
+    plugin_plugs,
+    "network",
+    &config.bundle.linux.snap.plugs,
+  ))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{flatpak_finish_args, snap_plugs};
+  use tauri_utils::config::{CapabilityEntry, Config};
+
+  #[test]
+  fn derives_permissions_from_capabilities() {
+    let mut config: Config = serde_json::from_value(serde_json::json!({
+      "identifier": "com.tauri.test",
+      "bundle": {
+        "linux": {
+          "flatpak": {
+            "finishArgs": ["--socket=pulseaudio", "--share=network"]
+          },
+          "snap": {
+            "plugs": ["audio-playback"]
+          }
+        }
+      }
+    }))
+    .unwrap();
+    config.app.security.capabilities = vec![CapabilityEntry::Inlined(
+      serde_json::from_value(serde_json::json!({
+        "identifier": "main",
+        "windows": ["main"],
+        "permissions": ["core:default", "http:default", "notification:default", "fs:allow-read-text-file"]
+      }))
+      .unwrap(),
+    )];
+
+    let tauri_dir = std::env::temp_dir().join("tauri-sandbox-test");
+    let args = flatpak_finish_args(&config, &tauri_dir).unwrap();
+    assert_eq!(
+      args,
+      vec![
+        "--share=ipc",
+        "--socket=wayland",
+        "--socket=fallback-x11",
+        "--device=dri",
+        "--share=network",
+        "--talk-name=org.freedesktop.Notifications",
+        "--filesystem=xdg-documents",
+        "--filesystem=xdg-download",
+        "--socket=pulseaudio",
+      ]
+    );
+
+    let plugs = snap_plugs(&config, &tauri_dir).unwrap();
+    assert_eq!(
+      plugs,
+      vec!["browser-support", "network", "home", "audio-playback"]
+    );
+  }
+}
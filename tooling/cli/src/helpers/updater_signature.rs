@@ -4,7 +4,9 @@
 
 use anyhow::Context;
 use base64::Engine;
-use minisign::{sign, KeyPair as KP, SecretKey, SecretKeyBox, SignatureBox};
+use minisign::{
+  sign, KeyPair as KP, PublicKey, PublicKeyBox, SecretKey, SecretKeyBox, SignatureBox,
+};
 use std::{
   fs::{self, File, OpenOptions},
   io::{BufReader, BufWriter, Write},
@@ -141,6 +143,29 @@ pub fn secret_key<S: AsRef<[u8]>>(
   Ok(sk)
 }
 
+/// Gets the updater public key from the given base64 encoded key.
+pub fn public_key<S: AsRef<[u8]>>(public_key: S) -> crate::Result<PublicKey> {
+  let decoded = decode_key(public_key)?;
+  let pk = PublicKeyBox::from_string(&decoded)
+    .with_context(|| "failed to load updater public key")?
+    .into_public_key()
+    .with_context(|| "invalid updater public key")?;
+  Ok(pk)
+}
+
+/// Signs the given data, returning the base64 encoded signature.
+pub fn sign_data(secret_key: &SecretKey, data: &[u8]) -> crate::Result<String> {
+  let trusted_comment = format!("timestamp:{}", unix_timestamp());
+  let signature_box = sign(
+    None,
+    secret_key,
+    data,
+    Some(trusted_comment.as_str()),
+    Some("signature from tauri secret key"),
+  )?;
+  Ok(base64::engine::general_purpose::STANDARD.encode(signature_box.to_string()))
+}
+
 fn unix_timestamp() -> u64 {
   let start = SystemTime::now();
   let since_the_epoch = start
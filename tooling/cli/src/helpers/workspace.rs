@@ -0,0 +1,265 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Multiple Tauri apps in a cargo or npm workspace.
+//!
+//! The workspace root is the closest directory containing a `tauri.workspace.json` file:
+//!
+//! ```json
+//! {
+//!   "apps": ["apps/desktop/src-tauri", "apps/admin/src-tauri"],
+//!   "config": { "bundle": { "publisher": "Tauri Programme" } }
+//! }
+//! ```
+//!
+//! - `apps` lists the Tauri directories of the apps, relative to the workspace root.
+//!   Defaults to every directory containing a Tauri configuration file.
+//! - `config` is the base configuration each app configuration is merged into.
+//!
+//! Without a workspace file, the apps are looked up from the current directory.
+
+use std::{
+  env::{current_dir, set_current_dir},
+  ffi::OsString,
+  fs::read_to_string,
+  path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+use serde::Deserialize;
+use serde_json::Value as JsonValue;
+use tauri_utils::{
+  config::parse::{folder_has_configuration_file, ConfigFormat},
+  platform::Target,
+};
+
+use super::app_paths::walk_builder;
+
+/// The workspace file name.
+pub const WORKSPACE_FILE_NAME: &str = "tauri.workspace.json";
+
+/// How deep the apps are looked up from the workspace root.
+const WORKSPACE_LOOKUP_DEPTH: usize = 5;
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct WorkspaceFile {
+  #[serde(default)]
+  apps: Vec<PathBuf>,
+  #[serde(default)]
+  config: Option<JsonValue>,
+}
+
+/// A Tauri app of the workspace.
+#[derive(Debug, Clone)]
+pub struct WorkspaceApp {
+  /// The app crate name, or the directory name if it can't be read.
+  pub name: String,
+  /// The directory containing the Tauri configuration file.
+  pub tauri_dir: PathBuf,
+}
+
+impl WorkspaceApp {
+  /// The directory the CLI runs from for this app.
+  fn root(&self) -> &Path {
+    app_root(&self.tauri_dir)
+  }
+}
+
+/// The parent of a `src-tauri` directory, or the Tauri directory itself.
+fn app_root(tauri_dir: &Path) -> &Path {
+  match tauri_dir.file_name() {
+    Some(name) if name == "src-tauri" => tauri_dir.parent().unwrap_or(tauri_dir),
+    _ => tauri_dir,
+  }
+}
+
+fn dir_name(dir: &Path) -> String {
+  dir
+    .file_name()
+    .map(|name| name.to_string_lossy().into_owned())
+    .unwrap_or_default()
+}
+
+/// The workspace root and its file, if any.
+fn workspace_file() -> crate::Result<Option<(PathBuf, WorkspaceFile)>> {
+  let cwd = current_dir()?;
+  for dir in cwd.ancestors() {
+    let path = dir.join(WORKSPACE_FILE_NAME);
+    if path.is_file() {
+      let file = serde_json::from_str(&read_to_string(&path)?)
+        .with_context(|| format!("failed to parse {}", path.display()))?;
+      return Ok(Some((dir.to_path_buf(), file)));
+    }
+  }
+  Ok(None)
+}
+
+/// The base configuration of the workspace apps, set in the `config` field of the workspace file.
+///
+/// It is looked up from the app Tauri directory.
+pub fn base_config(tauri_dir: &Path) -> crate::Result<Option<JsonValue>> {
+  for dir in tauri_dir.ancestors() {
+    let path = dir.join(WORKSPACE_FILE_NAME);
+    if path.is_file() {
+      let file: WorkspaceFile = serde_json::from_str(&read_to_string(&path)?)
+        .with_context(|| format!("failed to parse {}", path.display()))?;
+      return Ok(file.config);
+    }
+  }
+  Ok(None)
+}
+
+/// Lists the Tauri apps of the workspace, sorted by name.
+pub fn apps() -> crate::Result<Vec<WorkspaceApp>> {
+  let (root, file) = match workspace_file()? {
+    Some((root, file)) => (root, file),
+    None => (current_dir()?, WorkspaceFile::default()),
+  };
+
+  let tauri_dirs = if file.apps.is_empty() {
+    let mut builder = walk_builder(&root);
+    builder
+      .require_git(false)
+      .max_depth(Some(WORKSPACE_LOOKUP_DEPTH));
+    builder
+      .build()
+      .flatten()
+      .map(|entry| entry.into_path())
+      .filter(|path| path.is_dir() && folder_has_configuration_file(Target::Linux, path))
+      .collect::<Vec<_>>()
+  } else {
+    file
+      .apps
+      .iter()
+      .map(|dir| root.join(dir))
+      .inspect(|dir| {
+        if !folder_has_configuration_file(Target::Linux, dir) {
+          log::warn!(
+            "`{}` lists `{}` but it does not contain a `{}` file",
+            WORKSPACE_FILE_NAME,
+            dir.display(),
+            ConfigFormat::Json.into_file_name()
+          );
+        }
+      })
+      .collect()
+  };
+
+  let mut apps = tauri_dirs
+    .into_iter()
+    .map(|tauri_dir| WorkspaceApp {
+      name: crate_name(&tauri_dir).unwrap_or_else(|| dir_name(app_root(&tauri_dir))),
+      tauri_dir,
+    })
+    .collect::<Vec<_>>();
+  apps.sort_by(|a, b| a.name.cmp(&b.name));
+  Ok(apps)
+}
+
+/// The package name of the app crate.
+fn crate_name(tauri_dir: &Path) -> Option<String> {
+  let manifest: toml::Table = read_to_string(tauri_dir.join("Cargo.toml"))
+    .ok()?
+    .parse()
+    .ok()?;
+  manifest
+    .get("package")?
+    .get("name")?
+    .as_str()
+    .map(ToString::to_string)
+}
+
+/// Changes the current directory to the app with the given name, so the CLI commands run for it.
+pub fn select_app(name: &str) -> crate::Result<()> {
+  let apps = apps()?;
+  let app = apps.iter().find(|app| app.name == name).ok_or_else(|| {
+    anyhow::anyhow!(
+      "app `{name}` not found in the workspace, expected one of: {}",
+      apps
+        .iter()
+        .map(|app| app.name.as_str())
+        .collect::<Vec<_>>()
+        .join(", ")
+    )
+  })?;
+  set_current_dir(app.root()).with_context(|| {
+    format!(
+      "failed to change current directory to {}",
+      app.root().display()
+    )
+  })
+}
+
+/// Runs the command for each app of the workspace.
+///
+/// The apps share the cargo target directory of the workspace root, unless `CARGO_TARGET_DIR` is set,
+/// so their common dependencies are only compiled once.
+pub fn for_each_app<F: FnMut(&WorkspaceApp) -> crate::Result<()>>(mut f: F) -> crate::Result<()> {
+  let cwd = current_dir()?;
+  let root = match workspace_file()? {
+    Some((root, _)) => root,
+    None => cwd.clone(),
+  };
+  let apps = apps()?;
+  if apps.is_empty() {
+    anyhow::bail!("no Tauri app found in the workspace");
+  }
+
+  if std::env::var_os("CARGO_TARGET_DIR").is_none() {
+    std::env::set_var("CARGO_TARGET_DIR", root.join("target"));
+  }
+  // the commands set environment variables such as `TAURI_CONFIG` that must not leak to the next app
+  let env = std::env::vars_os().collect::<Vec<(OsString, OsString)>>();
+
+  for app in &apps {
+    log::info!(action = "Workspace"; "{} ({})", app.name, tauri_utils::display_path(&app.tauri_dir));
+    restore_env(&env);
+    super::app_paths::reset_app_dir();
+    super::config::reset();
+    set_current_dir(app.root())?;
+    f(app).with_context(|| format!("failed to run the command for app `{}`", app.name))?;
+  }
+
+  restore_env(&env);
+  set_current_dir(cwd)?;
+  Ok(())
+}
+
+fn restore_env(env: &[(OsString, OsString)]) {
+  for (key, _) in std::env::vars_os() {
+    if !env.iter().any(|(k, _)| *k == key) {
+      std::env::remove_var(key);
+    }
+  }
+  for (key, value) in env {
+    std::env::set_var(key, value);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn app_roots() {
+    assert_eq!(
+      app_root(Path::new("apps/desktop/src-tauri")),
+      Path::new("apps/desktop")
+    );
+    assert_eq!(app_root(Path::new("apps/admin")), Path::new("apps/admin"));
+    assert_eq!(dir_name(Path::new("apps/desktop")), "desktop");
+  }
+
+  #[test]
+  fn parse_workspace_file() {
+    let file: WorkspaceFile = serde_json::from_str(
+      r#"{ "apps": ["apps/desktop/src-tauri"], "config": { "version": "1.0.0" } }"#,
+    )
+    .unwrap();
+    assert_eq!(file.apps, vec![PathBuf::from("apps/desktop/src-tauri")]);
+    assert_eq!(file.config, Some(serde_json::json!({ "version": "1.0.0" })));
+    assert!(serde_json::from_str::<WorkspaceFile>(r#"{ "app": [] }"#).is_err());
+  }
+}
@@ -41,7 +41,10 @@ struct PngEntry {
 }
 
 #[derive(Debug, Parser)]
-#[clap(about = "Generate various icons for all major platforms")]
+#[clap(
+  about = "Generate various icons for all major platforms",
+  long_about = "Generate various icons for all major platforms: the Windows ICO and Appx logos, the macOS ICNS and tray template icons, the Linux PNG icons, the Android legacy and adaptive icons, the iOS app icons with their dark and tinted variants and the favicon set in the `web` directory."
+)]
 pub struct Options {
   /// Path to the source icon (squared PNG or SVG file with transparency).
   #[clap(default_value = "./app-icon.png")]
@@ -58,6 +61,28 @@ pub struct Options {
   /// The background color of the iOS icon - string as defined in the W3C's CSS Color Module Level 4 <https://www.w3.org/TR/css-color-4/>.
   #[clap(long, default_value = "#fff")]
   ios_color: String,
+  /// The background layer color of the Android adaptive icon - string as defined in the W3C's CSS Color Module Level 4 <https://www.w3.org/TR/css-color-4/>.
+  #[clap(long, default_value = "#fff")]
+  android_color: String,
+
+  /// Path to the source of the Android icons. Defaults to the input icon.
+  #[clap(long)]
+  android_input: Option<PathBuf>,
+  /// Path to the source of the Android monochrome icon layer. Defaults to a silhouette of the Android icon.
+  #[clap(long)]
+  android_monochrome: Option<PathBuf>,
+  /// Path to the source of the iOS icons. Defaults to the input icon.
+  #[clap(long)]
+  ios_input: Option<PathBuf>,
+  /// Path to the source of the macOS icons. Defaults to the input icon.
+  #[clap(long)]
+  macos_input: Option<PathBuf>,
+  /// Path to the source of the Windows icons. Defaults to the input icon.
+  #[clap(long)]
+  windows_input: Option<PathBuf>,
+  /// Path to the source of the macOS tray template icons. Defaults to a silhouette of the input icon.
+  #[clap(long)]
+  tray_input: Option<PathBuf>,
 }
 
 enum Source {
@@ -96,13 +121,81 @@ impl Source {
       Self::DynamicImage(i) => Ok(i.resize_exact(size, size, FilterType::Lanczos3)),
     }
   }
+
+  /// Reads the squared PNG or SVG icon.
+  fn read(input: &Path) -> Result<Self> {
+    let source = if let Some(extension) = input.extension() {
+      if extension == "svg" {
+        let rtree = {
+          let mut fontdb = usvg::fontdb::Database::new();
+          fontdb.load_system_fonts();
+
+          let opt = usvg::Options {
+            // Get file's absolute directory.
+            resources_dir: std::fs::canonicalize(input)
+              .ok()
+              .and_then(|p| p.parent().map(|p| p.to_path_buf())),
+            fontdb: Arc::new(fontdb),
+            ..Default::default()
+          };
+
+          let svg_data = std::fs::read(input)
+            .with_context(|| format!("Can't read source image {}", input.display()))?;
+          usvg::Tree::from_data(&svg_data, &opt)
+            .with_context(|| format!("Can't parse source image {}", input.display()))?
+        };
+
+        Source::Svg(rtree)
+      } else {
+        Source::DynamicImage(DynamicImage::ImageRgba8(
+          open(input)
+            .with_context(|| format!("Can't read and decode source image {}", input.display()))?
+            .into_rgba8(),
+        ))
+      }
+    } else {
+      anyhow::bail!("Error loading image {}", input.display());
+    };
+
+    if source.height() != source.width() {
+      anyhow::bail!("Source image {} must be square", input.display());
+    }
+
+    Ok(source)
+  }
 }
 
-pub fn command(options: Options) -> Result<()> {
-  let input = options.input;
-  let out_dir = options.output.unwrap_or_else(|| tauri_dir().join("icons"));
-  let png_icon_sizes = options.png.unwrap_or_default();
-  let ios_color = css_color::Srgb::from_str(&options.ios_color)
+/// The source icons, with the optional per-platform overrides.
+struct Sources {
+  default: Source,
+  android: Option<Source>,
+  android_monochrome: Option<Source>,
+  ios: Option<Source>,
+  macos: Option<Source>,
+  windows: Option<Source>,
+  tray: Option<Source>,
+}
+
+impl Sources {
+  fn android(&self) -> &Source {
+    self.android.as_ref().unwrap_or(&self.default)
+  }
+
+  fn ios(&self) -> &Source {
+    self.ios.as_ref().unwrap_or(&self.default)
+  }
+
+  fn macos(&self) -> &Source {
+    self.macos.as_ref().unwrap_or(&self.default)
+  }
+
+  fn windows(&self) -> &Source {
+    self.windows.as_ref().unwrap_or(&self.default)
+  }
+}
+
+fn parse_color(color: &str, name: &str) -> Result<Rgba<u8>> {
+  css_color::Srgb::from_str(color)
     .map(|color| {
       Rgba([
         (color.red * 255.) as u8,
@@ -111,51 +204,50 @@ pub fn command(options: Options) -> Result<()> {
         (color.alpha * 255.) as u8,
       ])
     })
-    .map_err(|_| anyhow::anyhow!("failed to parse iOS color"))?;
-
-  create_dir_all(&out_dir).context("Can't create output directory")?;
+    .map_err(|_| anyhow::anyhow!("failed to parse {name} color"))
+}
 
-  let source = if let Some(extension) = input.extension() {
-    if extension == "svg" {
-      let rtree = {
-        let mut fontdb = usvg::fontdb::Database::new();
-        fontdb.load_system_fonts();
-
-        let opt = usvg::Options {
-          // Get file's absolute directory.
-          resources_dir: std::fs::canonicalize(&input)
-            .ok()
-            .and_then(|p| p.parent().map(|p| p.to_path_buf())),
-          fontdb: Arc::new(fontdb),
-          ..Default::default()
-        };
+fn read_override(input: Option<&Path>) -> Result<Option<Source>> {
+  input.map(Source::read).transpose()
+}
 
-        let svg_data = std::fs::read(&input).unwrap();
-        usvg::Tree::from_data(&svg_data, &opt).unwrap()
-      };
+pub fn command(options: Options) -> Result<()> {
+  let input = options.input;
+  let out_dir = options.output.unwrap_or_else(|| tauri_dir().join("icons"));
+  let png_icon_sizes = options.png.unwrap_or_default();
+  let ios_color = parse_color(&options.ios_color, "iOS")?;
+  let android_color = parse_color(&options.android_color, "Android")?;
 
-      Source::Svg(rtree)
-    } else {
-      Source::DynamicImage(DynamicImage::ImageRgba8(
-        open(&input)
-          .context("Can't read and decode source image")?
-          .into_rgba8(),
-      ))
-    }
-  } else {
-    anyhow::bail!("Error loading image");
-  };
+  create_dir_all(&out_dir).context("Can't create output directory")?;
 
-  if source.height() != source.width() {
-    anyhow::bail!("Source image must be square");
-  }
+  let source = Source::read(&input)?;
 
   if png_icon_sizes.is_empty() {
-    appx(&source, &out_dir).context("Failed to generate appx icons")?;
-    icns(&source, &out_dir).context("Failed to generate .icns file")?;
-    ico(&source, &out_dir).context("Failed to generate .ico file")?;
-
-    png(&source, &out_dir, ios_color).context("Failed to generate png icons")?;
+    let sources = Sources {
+      default: source,
+      android: read_override(options.android_input.as_deref())?,
+      android_monochrome: read_override(options.android_monochrome.as_deref())?,
+      ios: read_override(options.ios_input.as_deref())?,
+      macos: read_override(options.macos_input.as_deref())?,
+      windows: read_override(options.windows_input.as_deref())?,
+      tray: read_override(options.tray_input.as_deref())?,
+    };
+
+    appx(sources.windows(), &out_dir).context("Failed to generate appx icons")?;
+    icns(sources.macos(), &out_dir).context("Failed to generate .icns file")?;
+    ico(
+      sources.windows(),
+      &WINDOWS_ICO_SIZES,
+      &out_dir.join("icon.ico"),
+    )
+    .context("Failed to generate .ico file")?;
+    tray(sources.tray.as_ref().unwrap_or(sources.macos()), &out_dir)
+      .context("Failed to generate tray icons")?;
+
+    png(&sources, &out_dir, ios_color, android_color).context("Failed to generate png icons")?;
+
+    favicon(&sources.default, &input, &out_dir, ios_color)
+      .context("Failed to generate favicons")?;
   } else {
     for target in png_icon_sizes
       .into_iter()
@@ -225,13 +317,16 @@ fn icns(source: &Source, out_dir: &Path) -> Result<()> {
   Ok(())
 }
 
-// Generate .ico file with layers for the most common sizes.
+/// The layers of the Windows icon, covering the sizes used by the shell at every display scale.
+const WINDOWS_ICO_SIZES: [u32; 14] = [32, 16, 20, 24, 30, 36, 40, 48, 60, 64, 72, 80, 96, 256];
+
+// Generate .ico file with layers for the given sizes.
 // Main target: Windows
-fn ico(source: &Source, out_dir: &Path) -> Result<()> {
-  log::info!(action = "ICO"; "Creating icon.ico");
+fn ico(source: &Source, sizes: &[u32], out_path: &Path) -> Result<()> {
+  log::info!(action = "ICO"; "Creating {}", out_path.file_name().unwrap().to_string_lossy());
   let mut frames = Vec::new();
 
-  for size in [32, 16, 24, 48, 64, 256] {
+  for &size in sizes {
     let image = source.resize_exact(size)?;
 
     // Only the 256px layer can be compressed according to the ico specs.
@@ -251,7 +346,7 @@ fn ico(source: &Source, out_dir: &Path) -> Result<()> {
     }
   }
 
-  let mut out_file = BufWriter::new(File::create(out_dir.join("icon.ico"))?);
+  let mut out_file = BufWriter::new(File::create(out_path)?);
   let encoder = IcoEncoder::new(&mut out_file);
   encoder.encode_images(&frames)?;
   out_file.flush()?;
@@ -259,9 +354,49 @@ fn ico(source: &Source, out_dir: &Path) -> Result<()> {
   Ok(())
 }
 
+// Generate the template images of the tray icon, which macOS tints to match the menu bar.
+// Main target: macOS
+fn tray(source: &Source, out_dir: &Path) -> Result<()> {
+  for (name, size) in [("tray-template.png", 22), ("tray-template@2x.png", 44)] {
+    log::info!(action = "Tray"; "Creating {}", name);
+    let image = silhouette(&source.resize_exact(size)?, Rgba([0, 0, 0, 255]));
+    save_png(&image, &out_dir.join(name))?;
+  }
+  Ok(())
+}
+
+// Generate the favicon set in the `web` directory.
+// Main target: the frontend when served in a browser
+fn favicon(source: &Source, input: &Path, out_dir: &Path, ios_color: Rgba<u8>) -> Result<()> {
+  let out_dir = out_dir.join("web");
+  create_dir_all(&out_dir).context("Can't create favicon output directory")?;
+
+  ico(source, &[16, 32, 48], &out_dir.join("favicon.ico"))?;
+  if input.extension().map_or(false, |ext| ext == "svg") {
+    log::info!(action = "Favicon"; "Creating favicon.svg");
+    std::fs::copy(input, out_dir.join("favicon.svg"))?;
+  }
+  for (name, size, bg_color) in [
+    ("favicon-16x16.png", 16, None),
+    ("favicon-32x32.png", 32, None),
+    ("apple-touch-icon.png", 180, Some(ios_color)),
+    ("icon-192x192.png", 192, None),
+    ("icon-512x512.png", 512, None),
+  ] {
+    log::info!(action = "Favicon"; "Creating {}", name);
+    resize_and_save_png(source, size, &out_dir.join(name), bg_color)?;
+  }
+  Ok(())
+}
+
 // Generate .png files in 32x32, 128x128, 256x256, 512x512 (icon.png)
 // Main target: Linux
-fn png(source: &Source, out_dir: &Path, ios_color: Rgba<u8>) -> Result<()> {
+fn png(
+  sources: &Sources,
+  out_dir: &Path,
+  ios_color: Rgba<u8>,
+  android_color: Rgba<u8>,
+) -> Result<()> {
   fn desktop_entries(out_dir: &Path) -> Vec<PngEntry> {
     let mut entries = Vec::new();
 
@@ -282,7 +417,7 @@ fn png(source: &Source, out_dir: &Path, ios_color: Rgba<u8>) -> Result<()> {
     entries
   }
 
-  fn android_entries(out_dir: &Path) -> Result<Vec<PngEntry>> {
+  fn android_entries(out_dir: &Path) -> Result<Vec<(AndroidImage, PngEntry)>> {
     struct AndroidEntry {
       name: &'static str,
       size: u32,
@@ -325,21 +460,29 @@ fn png(source: &Source, out_dir: &Path, ios_color: Rgba<u8>) -> Result<()> {
 
       create_dir_all(&out_folder).context("Can't create Android mipmap output directory")?;
 
-      entries.push(PngEntry {
-        name: format!("{}/{}", folder_name, "ic_launcher_foreground.png"),
-        out_path: out_folder.join("ic_launcher_foreground.png"),
-        size: target.foreground_size,
-      });
-      entries.push(PngEntry {
-        name: format!("{}/{}", folder_name, "ic_launcher_round.png"),
-        out_path: out_folder.join("ic_launcher_round.png"),
-        size: target.size,
-      });
-      entries.push(PngEntry {
-        name: format!("{}/{}", folder_name, "ic_launcher.png"),
-        out_path: out_folder.join("ic_launcher.png"),
-        size: target.size,
-      });
+      for (image, file_name, size) in [
+        (
+          AndroidImage::Foreground,
+          "ic_launcher_foreground.png",
+          target.foreground_size,
+        ),
+        (
+          AndroidImage::Monochrome,
+          "ic_launcher_monochrome.png",
+          target.foreground_size,
+        ),
+        (AndroidImage::Legacy, "ic_launcher_round.png", target.size),
+        (AndroidImage::Legacy, "ic_launcher.png", target.size),
+      ] {
+        entries.push((
+          image,
+          PngEntry {
+            name: format!("{folder_name}/{file_name}"),
+            out_path: out_folder.join(file_name),
+            size,
+          },
+        ));
+      }
     }
 
     Ok(entries)
@@ -419,7 +562,7 @@ fn png(source: &Source, out_dir: &Path, ios_color: Rgba<u8>) -> Result<()> {
     Ok(entries)
   }
 
-  let mut entries = desktop_entries(out_dir);
+  let entries = desktop_entries(out_dir);
 
   let android_out = out_dir
     .parent()
@@ -432,7 +575,8 @@ fn png(source: &Source, out_dir: &Path, ios_color: Rgba<u8>) -> Result<()> {
     create_dir_all(&out).context("Can't create Android output directory")?;
     out
   };
-  entries.extend(android_entries(&out)?);
+  let android_entries = android_entries(&out)?;
+  let android_out = out;
 
   let ios_out = out_dir
     .parent()
@@ -448,17 +592,158 @@ fn png(source: &Source, out_dir: &Path, ios_color: Rgba<u8>) -> Result<()> {
 
   for entry in entries {
     log::info!(action = "PNG"; "Creating {}", entry.name);
-    resize_and_save_png(source, entry.size, &entry.out_path, None)?;
+    resize_and_save_png(&sources.default, entry.size, &entry.out_path, None)?;
   }
 
+  for (image, entry) in android_entries {
+    log::info!(action = "PNG"; "Creating {}", entry.name);
+    match image {
+      AndroidImage::Legacy => {
+        resize_and_save_png(sources.android(), entry.size, &entry.out_path, None)?
+      }
+      AndroidImage::Foreground => save_png(
+        &adaptive_layer(sources.android(), entry.size)?,
+        &entry.out_path,
+      )?,
+      AndroidImage::Monochrome => {
+        let layer = match &sources.android_monochrome {
+          Some(source) => adaptive_layer(source, entry.size)?,
+          None => silhouette(
+            &adaptive_layer(sources.android(), entry.size)?,
+            Rgba([255, 255, 255, 255]),
+          ),
+        };
+        save_png(&layer, &entry.out_path)?
+      }
+    }
+  }
+  adaptive_icon(&android_out, android_color)?;
+
   for entry in ios_entries(&out)? {
     log::info!(action = "iOS"; "Creating {}", entry.name);
-    resize_and_save_png(source, entry.size, &entry.out_path, Some(ios_color))?;
+    resize_and_save_png(sources.ios(), entry.size, &entry.out_path, Some(ios_color))?;
+  }
+  ios_appearances(sources.ios(), &out)?;
+
+  Ok(())
+}
+
+/// An image of the Android launcher icon.
+enum AndroidImage {
+  /// The icon used before Android 8.
+  Legacy,
+  /// The foreground layer of the adaptive icon.
+  Foreground,
+  /// The layer of the adaptive icon used by the themed icons of Android 13.
+  Monochrome,
+}
+
+/// The adaptive icon layer of the given size, with the icon scaled down to the 66dp safe zone of the 108dp layer
+/// so the launcher mask never crops it.
+fn adaptive_layer(source: &Source, size: u32) -> Result<DynamicImage> {
+  let icon_size = size * 66 / 108;
+  let icon = source.resize_exact(icon_size)?;
+  let mut layer = ImageBuffer::from_pixel(size, size, Rgba([0, 0, 0, 0]));
+  let offset = i64::from((size - icon_size) / 2);
+  image::imageops::overlay(&mut layer, &icon, offset, offset);
+  Ok(layer.into())
+}
+
+// Generate the adaptive icon resources, using the generated foreground and monochrome layers.
+fn adaptive_icon(res_dir: &Path, background: Rgba<u8>) -> Result<()> {
+  let adaptive_icon = r#"<?xml version="1.0" encoding="utf-8"?>
+<adaptive-icon xmlns:android="http://schemas.android.com/apk/res/android">
+    <background android:drawable="@color/ic_launcher_background"/>
+    <foreground android:drawable="@mipmap/ic_launcher_foreground"/>
+    <monochrome android:drawable="@mipmap/ic_launcher_monochrome"/>
+</adaptive-icon>
+"#;
+
+  let anydpi_dir = res_dir.join("mipmap-anydpi-v26");
+  create_dir_all(&anydpi_dir).context("Can't create Android adaptive icon output directory")?;
+  for file_name in ["ic_launcher.xml", "ic_launcher_round.xml"] {
+    log::info!(action = "Android"; "Creating mipmap-anydpi-v26/{}", file_name);
+    std::fs::write(anydpi_dir.join(file_name), adaptive_icon)?;
   }
 
+  let values_dir = res_dir.join("values");
+  create_dir_all(&values_dir).context("Can't create Android values output directory")?;
+  let [r, g, b, a] = background.0;
+  log::info!(action = "Android"; "Creating values/ic_launcher_background.xml");
+  std::fs::write(
+    values_dir.join("ic_launcher_background.xml"),
+    format!(
+      r#"<?xml version="1.0" encoding="utf-8"?>
+<resources>
+    <color name="ic_launcher_background">#{a:02X}{r:02X}{g:02X}{b:02X}</color>
+</resources>
+"#
+    ),
+  )?;
+
   Ok(())
 }
 
+// Generate the dark and tinted variants of the iOS app icon and register them in the asset catalog.
+fn ios_appearances(source: &Source, out_dir: &Path) -> Result<()> {
+  let size = 1024;
+
+  log::info!(action = "iOS"; "Creating {}", IOS_DARK_ICON);
+  // the system draws the dark background
+  resize_and_save_png(source, size, &out_dir.join(IOS_DARK_ICON), None)?;
+
+  log::info!(action = "iOS"; "Creating {}", IOS_TINTED_ICON);
+  // the system tints the grayscale image
+  let grayscale = source.resize_exact(size)?.grayscale();
+  let mut tinted = ImageBuffer::from_pixel(size, size, Rgba([0, 0, 0, 255]));
+  image::imageops::overlay(&mut tinted, &grayscale.into_rgba8(), 0, 0);
+  save_png(&tinted.into(), &out_dir.join(IOS_TINTED_ICON))?;
+
+  let contents_path = out_dir.join("Contents.json");
+  let contents = match std::fs::read_to_string(&contents_path) {
+    Ok(contents) => contents,
+    Err(_) => {
+      include_str!("../templates/mobile/ios/Assets.xcassets/AppIcon.appiconset/Contents.json")
+        .to_string()
+    }
+  };
+  let mut contents: serde_json::Value =
+    serde_json::from_str(&contents).context("failed to parse the app icon Contents.json")?;
+  add_ios_appearances(&mut contents);
+  std::fs::write(&contents_path, serde_json::to_string_pretty(&contents)?)?;
+
+  Ok(())
+}
+
+const IOS_DARK_ICON: &str = "AppIcon-512@2x-dark.png";
+const IOS_TINTED_ICON: &str = "AppIcon-512@2x-tinted.png";
+
+/// Adds the dark and tinted variants of the App Store icon to the asset catalog contents.
+fn add_ios_appearances(contents: &mut serde_json::Value) {
+  let Some(images) = contents.get_mut("images").and_then(|i| i.as_array_mut()) else {
+    return;
+  };
+  images.retain(|image| image.get("appearances").is_none());
+  for (filename, appearance) in [(IOS_DARK_ICON, "dark"), (IOS_TINTED_ICON, "tinted")] {
+    images.push(serde_json::json!({
+      "size": "1024x1024",
+      "idiom": "ios-marketing",
+      "filename": filename,
+      "scale": "1x",
+      "appearances": [{ "appearance": "luminosity", "value": appearance }]
+    }));
+  }
+}
+
+/// The image with every pixel in the given color, keeping its transparency.
+fn silhouette(image: &DynamicImage, color: Rgba<u8>) -> DynamicImage {
+  let mut image = image.to_rgba8();
+  for pixel in image.pixels_mut() {
+    *pixel = Rgba([color[0], color[1], color[2], pixel[3]]);
+  }
+  image.into()
+}
+
 // Resize image and save it to disk.
 fn resize_and_save_png(
   source: &Source,
@@ -474,8 +759,13 @@ fn resize_and_save_png(
     image = bg_img.into();
   }
 
+  save_png(&image, file_path)
+}
+
+// Save the square image to disk.
+fn save_png(image: &DynamicImage, file_path: &Path) -> Result<()> {
   let mut out_file = BufWriter::new(File::create(file_path)?);
-  write_png(image.as_bytes(), &mut out_file, size)?;
+  write_png(image.as_bytes(), &mut out_file, image.width())?;
   Ok(out_file.flush()?)
 }
 
@@ -485,3 +775,41 @@ fn write_png<W: Write>(image_data: &[u8], w: W, size: u32) -> Result<()> {
   encoder.write_image(image_data, size, size, ColorType::Rgba8)?;
   Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn adds_ios_appearances() {
+    let mut contents: serde_json::Value = serde_json::from_str(include_str!(
+      "../templates/mobile/ios/Assets.xcassets/AppIcon.appiconset/Contents.json"
+    ))
+    .unwrap();
+    let count = contents["images"].as_array().unwrap().len();
+    add_ios_appearances(&mut contents);
+    // running it again does not duplicate the variants
+    add_ios_appearances(&mut contents);
+
+    let images = contents["images"].as_array().unwrap();
+    assert_eq!(images.len(), count + 2);
+    assert_eq!(images[count]["filename"], IOS_DARK_ICON);
+    assert_eq!(images[count + 1]["appearances"][0]["value"], "tinted");
+  }
+
+  #[test]
+  fn adaptive_layer_safe_zone() {
+    let source = Source::DynamicImage(DynamicImage::ImageRgba8(ImageBuffer::from_pixel(
+      64,
+      64,
+      Rgba([255, 0, 0, 255]),
+    )));
+    let layer = adaptive_layer(&source, 108).unwrap().into_rgba8();
+    assert_eq!(layer.get_pixel(0, 0)[3], 0);
+    assert_eq!(layer.get_pixel(54, 54), &Rgba([255, 0, 0, 255]));
+
+    let white = silhouette(&layer.into(), Rgba([255, 255, 255, 255])).into_rgba8();
+    assert_eq!(white.get_pixel(0, 0)[3], 0);
+    assert_eq!(white.get_pixel(54, 54), &Rgba([255, 255, 255, 255]));
+  }
+}
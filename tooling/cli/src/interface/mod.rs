@@ -11,8 +11,9 @@ use std::{
   sync::Arc,
 };
 
-use crate::helpers::config::Config;
+use crate::helpers::config::{AssetStorage, Config};
 use tauri_bundler::bundle::{PackageType, Settings, SettingsBuilder};
+use tauri_utils::resources::ResourcePaths;
 
 pub use rust::{MobileOptions, Options, Rust as AppInterface};
 
@@ -54,9 +55,14 @@ pub trait AppSettings {
       tauri_utils::platform::target_triple()?
     };
 
+    let mut bundle_settings = self.get_bundle_settings(config, &enabled_features)?;
+    if config.build.asset_storage == AssetStorage::Packfile {
+      add_asset_packfile(&mut bundle_settings, &config.identifier, out_dir)?;
+    }
+
     SettingsBuilder::new()
       .package_settings(self.get_package_settings())
-      .bundle_settings(self.get_bundle_settings(config, &enabled_features)?)
+      .bundle_settings(bundle_settings)
       .binaries(self.get_binaries(&target)?)
       .project_out_directory(out_dir)
       .target(target)
@@ -66,6 +72,36 @@ pub trait AppSettings {
   }
 }
 
+/// Bundles the asset packfile written by the app build next to its executable.
+///
+/// The packfile must be at the root of the resource directory,
+/// so the resources are converted to a map of their targets if needed.
+fn add_asset_packfile(
+  settings: &mut tauri_bundler::BundleSettings,
+  identifier: &str,
+  out_dir: &Path,
+) -> crate::Result<()> {
+  let file_name = format!("{identifier}.pak");
+  let mut resources_map = match (settings.resources.take(), settings.resources_map.take()) {
+    (_, Some(map)) => map,
+    (Some(paths), None) => {
+      let mut map = HashMap::new();
+      for resource in ResourcePaths::new(&paths, true).iter() {
+        let resource = resource?;
+        map.insert(
+          resource.path().display().to_string(),
+          resource.target().display().to_string(),
+        );
+      }
+      map
+    }
+    (None, None) => HashMap::new(),
+  };
+  resources_map.insert(out_dir.join(&file_name).display().to_string(), file_name);
+  settings.resources_map.replace(resources_map);
+  Ok(())
+}
+
 #[derive(Debug)]
 pub enum ExitReason {
   /// Killed manually.
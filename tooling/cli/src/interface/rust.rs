@@ -22,21 +22,31 @@ use notify_debouncer_mini::new_debouncer;
 use serde::{Deserialize, Deserializer};
 use tauri_bundler::{
   AppCategory, AppImageSettings, BundleBinary, BundleSettings, DebianSettings, DmgSettings,
-  MacOsSettings, PackageSettings, Position, RpmSettings, Size, UpdaterSettings, WindowsSettings,
+  FlatpakSettings, MacOsSettings, PackageSettings, PkgSettings, PortableSettings, Position,
+  RpmSettings, Size, SnapSettings, UpdaterSettings, WindowsSettings,
+};
+use tauri_utils::config::{
+  parse::{is_configuration_file, is_profile_configuration_file, PROFILE_ENV},
+  DeepLinkProtocol, LinuxCrossCompileConfig, Updater,
 };
-use tauri_utils::config::{parse::is_configuration_file, DeepLinkProtocol, Updater};
 
 use super::{AppSettings, DevProcess, ExitReason, Interface};
 use crate::{
   helpers::{
     app_paths::{app_dir, tauri_dir},
-    config::{nsis_settings, reload as reload_config, wix_settings, BundleResources, Config},
+    config::{
+      is_reloadable_at_runtime, msix_settings, nsis_settings, reload as reload_config,
+      wix_settings, BundleResources, Config,
+    },
+    sandbox,
   },
   ConfigValue,
 };
 use tauri_utils::{display_path, platform::Target};
 
+mod cache;
 mod cargo_config;
+mod cross;
 mod desktop;
 pub mod installation;
 pub mod manifest;
@@ -149,6 +159,8 @@ impl Interface for Rust {
       std::env::set_var("MACOSX_DEPLOYMENT_TARGET", minimum_system_version);
     }
 
+    cache::configure(config.build.cache.as_ref(), &tauri_dir())?;
+
     let app_settings = RustAppSettings::new(config, manifest, target)?;
 
     Ok(Self {
@@ -502,6 +514,21 @@ impl Rust {
     config: Option<serde_json::Value>,
     run: Arc<F>,
   ) -> crate::Result<()> {
+    // `AppHandle::reload_config_subset` applies some configuration changes without restarting the desktop app
+    let reload_config_file = if self.app_settings.target.is_desktop() {
+      let file = DevConfigFile(get_cargo_target_dir()?.join("tauri-dev-config.json"));
+      let _ = std::fs::remove_file(&file.0);
+      std::env::set_var("TAURI_DEV_RELOAD_CONFIG_PATH", &file.0);
+      Some(file)
+    } else {
+      None
+    };
+    let mut app_config = crate::helpers::config::get(self.app_settings.target, config.as_ref())?
+      .lock()
+      .unwrap()
+      .as_ref()
+      .map(|config| (**config).clone());
+
     let child = run(self)?;
 
     let process = Arc::new(Mutex::new(child));
@@ -545,16 +572,41 @@ impl Rust {
           let event_path = event.path;
 
           if !ignore_matcher.is_ignore(&event_path, event_path.is_dir()) {
-            if is_configuration_file(self.app_settings.target, &event_path) {
+            if is_configuration_file(self.app_settings.target, &event_path)
+              || std::env::var(PROFILE_ENV).map_or(false, |profile| {
+                is_profile_configuration_file(&profile, &event_path)
+              })
+            {
               if let Ok(config) = reload_config(config.as_ref()) {
-                let (manifest, modified) =
-                  rewrite_manifest(config.lock().unwrap().as_ref().unwrap())?;
+                let config = config.lock().unwrap();
+                let config = config.as_ref().unwrap();
+                let (manifest, modified) = rewrite_manifest(config)?;
                 if modified {
                   *self.app_settings.manifest.lock().unwrap() = manifest;
                   // no need to run the watcher logic, the manifest was modified
                   // and it will trigger the watcher again
                   continue;
                 }
+
+                let previous_config = app_config.replace((**config).clone());
+                if let (Some(file), Some(previous_config)) = (&reload_config_file, previous_config)
+                {
+                  if is_reloadable_at_runtime(&previous_config, config) {
+                    log::info!(
+                      "File {} changed. Reloading configuration...",
+                      display_path(event_path.strip_prefix(app_path).unwrap_or(&event_path))
+                    );
+                    match serde_json::to_vec(&**config)
+                      .map_err(std::io::Error::from)
+                      .and_then(|config| std::fs::write(&file.0, config))
+                    {
+                      Ok(()) => continue,
+                      Err(e) => log::error!(
+                        "Failed to write the configuration for the app to reload: {e}, rebuilding application instead"
+                      ),
+                    }
+                  }
+                }
               }
             }
 
@@ -581,6 +633,15 @@ impl Rust {
   }
 }
 
+/// The file `tauri dev` writes the reloadable configuration to, removed when the watcher stops.
+struct DevConfigFile(PathBuf);
+
+impl Drop for DevConfigFile {
+  fn drop(&mut self) {
+    let _ = std::fs::remove_file(&self.0);
+  }
+}
+
 // Taken from https://github.com/rust-lang/cargo/blob/70898e522116f6c23971e2a554b2dc85fd4c84cd/src/cargo/util/toml/mod.rs#L1008-L1065
 /// Enum that allows for the parsing of `field.workspace = true` in a Cargo.toml
 ///
@@ -721,6 +782,7 @@ pub struct RustAppSettings {
   cargo_config: CargoConfig,
   target_triple: String,
   target: Target,
+  linux_cross_compile: HashMap<String, LinuxCrossCompileConfig>,
 }
 
 #[derive(Deserialize)]
@@ -734,6 +796,9 @@ enum DesktopDeepLinks {
 pub struct UpdaterConfig {
   /// Signature public key.
   pub pubkey: String,
+  /// Additional public keys trusted to sign updates, with their validity window.
+  #[serde(default, rename = "trustedKeys")]
+  pub trusted_keys: Vec<tauri_utils::updater::TrustedKey>,
   /// The Windows configuration for the updater.
   #[serde(default)]
   pub windows: UpdaterWindowsConfig,
@@ -823,7 +888,9 @@ impl AppSettings for RustAppSettings {
       Some(UpdaterSettings {
         v1_compatible,
         pubkey: updater.pubkey,
+        trusted_keys: updater.trusted_keys,
         msiexec_args: updater.windows.install_mode.msiexec_args(),
+        delta_bases: Vec::new(),
       })
     } else {
       None
@@ -838,6 +905,9 @@ impl AppSettings for RustAppSettings {
       arch64bits,
     )?;
 
+    settings.flatpak.finish_args = sandbox::flatpak_finish_args(config, &tauri_dir())?;
+    settings.snap.plugs = sandbox::snap_plugs(config, &tauri_dir())?;
+
     if let Some(plugin_config) = config
       .plugins
       .0
@@ -1074,6 +1144,7 @@ impl RustAppSettings {
       cargo_config,
       target_triple,
       target,
+      linux_cross_compile: config.bundle.linux.cross_compile.clone(),
     })
   }
 
@@ -1081,6 +1152,17 @@ impl RustAppSettings {
     &self.cargo_package_settings
   }
 
+  /// The configured cross compilation settings for the Linux target,
+  /// if it must be cross compiled from this host.
+  fn linux_cross_compile(&self, options: &Options) -> Option<(String, LinuxCrossCompileConfig)> {
+    let target = self.target(options).unwrap_or(&self.target_triple);
+    self
+      .linux_cross_compile
+      .get(target)
+      .filter(|_| cross::is_cross_compiling(target))
+      .map(|config| (target.to_string(), config.clone()))
+  }
+
   fn target<'a>(&'a self, options: &'a Options) -> Option<&'a str> {
     options
       .target
@@ -1159,6 +1241,15 @@ pub fn get_workspace_dir() -> crate::Result<PathBuf> {
   )
 }
 
+/// Executes `cargo metadata` to get the cargo target directory.
+pub fn get_cargo_target_dir() -> crate::Result<PathBuf> {
+  Ok(
+    get_cargo_metadata()
+      .with_context(|| "failed to get cargo metadata")?
+      .target_directory,
+  )
+}
+
 pub fn get_profile(options: &Options) -> &str {
   get_cargo_option(&options.args, "--profile").unwrap_or(if options.debug {
     "dev"
@@ -1292,6 +1383,16 @@ fn tauri_config_to_bundle_settings(
     None => config.macos.signing_identity,
   };
 
+  let installer_signing_identity = match std::env::var_os("APPLE_INSTALLER_SIGNING_IDENTITY") {
+    Some(signing_identity) => Some(
+      signing_identity
+        .to_str()
+        .expect("failed to convert APPLE_INSTALLER_SIGNING_IDENTITY to string")
+        .to_string(),
+    ),
+    None => config.macos.pkg.signing_identity,
+  };
+
   let provider_short_name = match std::env::var_os("APPLE_PROVIDER_SHORT_NAME") {
     Some(provider_short_name) => Some(
       provider_short_name
@@ -1340,6 +1441,7 @@ fn tauri_config_to_bundle_settings(
       section: config.linux.deb.section,
       priority: config.linux.deb.priority,
       changelog: config.linux.deb.changelog,
+      install_prefix: config.linux.deb.install_prefix,
       pre_install_script: config.linux.deb.pre_install_script,
       post_install_script: config.linux.deb.post_install_script,
       pre_remove_script: config.linux.deb.pre_remove_script,
@@ -1347,7 +1449,28 @@ fn tauri_config_to_bundle_settings(
     },
     appimage: AppImageSettings {
       files: config.linux.appimage.files,
+      sysroot: config
+        .linux
+        .cross_compile
+        .get(&settings.target_triple)
+        .and_then(|cross_compile| cross_compile.sysroot.clone()),
+      update_information: config.linux.appimage.update_information,
+    },
+    flatpak: FlatpakSettings {
+      runtime: config.linux.flatpak.runtime,
+      runtime_version: config.linux.flatpak.runtime_version,
+      sdk: config.linux.flatpak.sdk,
+      finish_args: config.linux.flatpak.finish_args,
+      files: config.linux.flatpak.files,
+    },
+    snap: SnapSettings {
+      base: config.linux.snap.base,
+      confinement: config.linux.snap.confinement,
+      grade: config.linux.snap.grade,
+      plugs: config.linux.snap.plugs,
+      files: config.linux.snap.files,
     },
+    linux_service: config.linux.service,
     rpm: RpmSettings {
       depends: if depends_rpm.is_empty() {
         None
@@ -1361,6 +1484,8 @@ fn tauri_config_to_bundle_settings(
       epoch: config.linux.rpm.epoch,
       files: config.linux.rpm.files,
       desktop_template: config.linux.rpm.desktop_template,
+      changelog: config.linux.rpm.changelog,
+      install_prefix: config.linux.rpm.install_prefix,
       pre_install_script: config.linux.rpm.pre_install_script,
       post_install_script: config.linux.rpm.post_install_script,
       pre_remove_script: config.linux.rpm.pre_remove_script,
@@ -1368,6 +1493,7 @@ fn tauri_config_to_bundle_settings(
     },
     dmg: DmgSettings {
       background: config.macos.dmg.background,
+      localized_backgrounds: config.macos.dmg.localized_backgrounds,
       window_position: config
         .macos
         .dmg
@@ -1388,6 +1514,35 @@ fn tauri_config_to_bundle_settings(
         x: config.macos.dmg.application_folder_position.x,
         y: config.macos.dmg.application_folder_position.y,
       },
+      volume_name: config.macos.dmg.volume_name,
+      volume_icon: config.macos.dmg.volume_icon,
+      icon_size: config.macos.dmg.icon_size,
+      text_size: config.macos.dmg.text_size,
+      license: config.macos.dmg.license,
+      signing_identity: config.macos.dmg.signing_identity,
+      notarize: config.macos.dmg.notarize,
+    },
+    pkg: PkgSettings {
+      install_location: config.macos.pkg.install_location,
+      // the paths are relative to the Tauri config directory
+      pre_install_script: config
+        .macos
+        .pkg
+        .pre_install_script
+        .map(|script| tauri_dir().join(script)),
+      post_install_script: config
+        .macos
+        .pkg
+        .post_install_script
+        .map(|script| tauri_dir().join(script)),
+      component_packages: config
+        .macos
+        .pkg
+        .component_packages
+        .into_iter()
+        .map(|component| tauri_dir().join(component))
+        .collect(),
+      signing_identity: installer_signing_identity,
     },
     macos: MacOsSettings {
       frameworks: config.macos.frameworks,
@@ -1406,6 +1561,7 @@ fn tauri_config_to_bundle_settings(
           None
         }
       },
+      notarization: config.macos.notarization,
     },
     windows: WindowsSettings {
       timestamp_url: config.windows.timestamp_url,
@@ -1414,11 +1570,20 @@ fn tauri_config_to_bundle_settings(
       certificate_thumbprint: config.windows.certificate_thumbprint,
       wix: config.windows.wix.map(wix_settings),
       nsis: config.windows.nsis.map(nsis_settings),
+      msix: config.windows.msix.map(msix_settings),
+      portable: PortableSettings {
+        data_next_to_executable: config.windows.portable.data_next_to_executable,
+      },
       icon_path: windows_icon_path,
       webview_install_mode: config.windows.webview_install_mode,
       webview_fixed_runtime_path: config.windows.webview_fixed_runtime_path,
       allow_downgrades: config.windows.allow_downgrades,
       sign_command: config.windows.sign_command,
+      signer: config
+        .windows
+        .signer
+        .as_ref()
+        .map(tauri_bundler::signer_from_config),
     },
     license: config.license.or_else(|| {
       settings
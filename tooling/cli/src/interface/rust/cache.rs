@@ -0,0 +1,162 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Caching of the compiled Rust crates with [sccache](https://github.com/mozilla/sccache).
+//!
+//! The cache is configured with environment variables, so it applies to every cargo command
+//! run by the CLI, including the mobile builds started by Xcode and Gradle.
+
+use std::{
+  ffi::OsString,
+  path::{Path, PathBuf},
+  process::Command,
+};
+
+use anyhow::Context;
+use tauri_utils::config::BuildCacheConfig;
+
+/// The environment variable enabling the build cache without changing the configuration, e.g. on CI.
+pub const BUILD_CACHE_ENV: &str = "TAURI_BUILD_CACHE";
+
+const DEFAULT_SCCACHE: &str = "sccache";
+
+/// Whether the build cache is enabled by the configuration or the environment variable.
+fn is_enabled(config: Option<&BuildCacheConfig>, env: Option<&str>) -> bool {
+  match env {
+    Some(value) if !value.is_empty() => value == "true" || value == "1",
+    _ => config.map_or(false, BuildCacheConfig::enabled),
+  }
+}
+
+/// Resolves the configured sccache binary: a bare name is looked up on `PATH`,
+/// other relative paths are relative to the Tauri directory.
+fn sccache_path(path: &Path, tauri_dir: &Path) -> PathBuf {
+  if path.components().count() == 1 && path.is_relative() {
+    path.to_path_buf()
+  } else {
+    tauri_dir.join(path)
+  }
+}
+
+/// The environment variables making cargo use sccache.
+fn cache_env(
+  config: Option<&BuildCacheConfig>,
+  sccache: PathBuf,
+  tauri_dir: &Path,
+) -> Vec<(&'static str, OsString)> {
+  let mut env = vec![
+    ("RUSTC_WRAPPER", sccache.into_os_string()),
+    // sccache can't cache the incrementally compiled crates
+    ("CARGO_INCREMENTAL", "0".into()),
+  ];
+  if let Some(dir) = config.and_then(BuildCacheConfig::dir) {
+    env.push(("SCCACHE_DIR", tauri_dir.join(dir).into_os_string()));
+  }
+  env
+}
+
+/// Configures cargo to use sccache when the build cache is enabled.
+///
+/// A `RUSTC_WRAPPER` already defined by the user takes precedence.
+pub fn configure(config: Option<&BuildCacheConfig>, tauri_dir: &Path) -> crate::Result<()> {
+  if !is_enabled(config, std::env::var(BUILD_CACHE_ENV).ok().as_deref()) {
+    return Ok(());
+  }
+
+  if let Some(wrapper) = std::env::var_os("RUSTC_WRAPPER") {
+    log::debug!(
+      "`RUSTC_WRAPPER` is set to {}, the build cache is not configured",
+      wrapper.to_string_lossy()
+    );
+    return Ok(());
+  }
+
+  let sccache = config
+    .and_then(BuildCacheConfig::sccache_path)
+    .map(|path| sccache_path(path, tauri_dir))
+    .unwrap_or_else(|| DEFAULT_SCCACHE.into());
+  let version = Command::new(&sccache)
+    .arg("--version")
+    .output()
+    .ok()
+    .filter(|output| output.status.success())
+    .with_context(|| {
+      format!(
+        "failed to run `{}`, install sccache with `cargo install sccache --locked` or disable `build > cache`",
+        sccache.display()
+      )
+    })?;
+  log::info!(
+    action = "Caching";
+    "Rust build artifacts with {}",
+    String::from_utf8_lossy(&version.stdout).trim()
+  );
+
+  for (key, value) in cache_env(config, sccache, tauri_dir) {
+    std::env::set_var(key, value);
+  }
+
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn enabled() {
+    let options = BuildCacheConfig::Options {
+      sccache_path: None,
+      dir: None,
+    };
+    assert!(!is_enabled(None, None));
+    assert!(is_enabled(Some(&BuildCacheConfig::Enabled(true)), None));
+    assert!(is_enabled(Some(&options), None));
+    assert!(!is_enabled(Some(&BuildCacheConfig::Enabled(false)), None));
+    assert!(is_enabled(None, Some("true")));
+    assert!(!is_enabled(Some(&options), Some("false")));
+    assert!(is_enabled(Some(&BuildCacheConfig::Enabled(true)), Some("")));
+  }
+
+  #[test]
+  fn resolve_sccache() {
+    let tauri_dir = Path::new("/app/src-tauri");
+    assert_eq!(
+      sccache_path(Path::new("sccache-dist"), tauri_dir),
+      PathBuf::from("sccache-dist")
+    );
+    assert_eq!(
+      sccache_path(Path::new("../bin/sccache"), tauri_dir),
+      tauri_dir.join("../bin/sccache")
+    );
+    assert_eq!(
+      sccache_path(Path::new("/usr/local/bin/sccache"), tauri_dir),
+      PathBuf::from("/usr/local/bin/sccache")
+    );
+  }
+
+  #[test]
+  fn env() {
+    let tauri_dir = Path::new("/app/src-tauri");
+    assert_eq!(
+      cache_env(None, "sccache".into(), tauri_dir),
+      vec![
+        ("RUSTC_WRAPPER", OsString::from("sccache")),
+        ("CARGO_INCREMENTAL", OsString::from("0")),
+      ]
+    );
+
+    let config = BuildCacheConfig::Options {
+      sccache_path: None,
+      dir: Some("../.cache/sccache".into()),
+    };
+    assert_eq!(
+      cache_env(Some(&config), "sccache".into(), tauri_dir)[2],
+      (
+        "SCCACHE_DIR",
+        tauri_dir.join("../.cache/sccache").into_os_string()
+      )
+    );
+  }
+}
@@ -0,0 +1,217 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Cross compilation of Linux targets.
+//!
+//! With a local toolchain, cargo is configured to use the target linker and `pkg-config`
+//! resolves the system libraries (webkit2gtk, gtk, ...) from the target sysroot.
+//! With a container image, the app is built with [`cross`](https://github.com/cross-rs/cross).
+
+use std::{path::Path, process::Command};
+
+use tauri_utils::config::LinuxCrossCompileConfig;
+
+/// The runner used to build the app in a container image.
+pub const CROSS_RUNNER: &str = "cross";
+
+/// Whether building for the given target requires cross compiling from the current host.
+pub fn is_cross_compiling(target: &str) -> bool {
+  target.contains("linux")
+    && (!cfg!(target_os = "linux") || target_arch(target) != Some(std::env::consts::ARCH))
+}
+
+/// Maps the target triple architecture to the [`std::env::consts::ARCH`] naming.
+fn target_arch(target: &str) -> Option<&'static str> {
+  match target.split('-').next()? {
+    "x86_64" => Some("x86_64"),
+    "i686" | "i586" => Some("x86"),
+    "aarch64" => Some("aarch64"),
+    arch if arch.starts_with("arm") => Some("arm"),
+    "riscv64gc" => Some("riscv64"),
+    _ => None,
+  }
+}
+
+/// The GNU toolchain prefix for a Linux target, e.g. `aarch64-linux-gnu` or `arm-linux-musleabihf`.
+fn toolchain_prefix(target: &str) -> Option<String> {
+  let mut parts = target.split('-');
+  let _arch = parts.next()?;
+  let (os, env) = match (parts.next()?, parts.next(), parts.next()) {
+    (_vendor, Some(os), Some(env)) => (os, env),
+    (os, Some(env), None) => (os, env),
+    _ => return None,
+  };
+  if os != "linux" {
+    return None;
+  }
+  let arch = match target_arch(target)? {
+    "x86" => "i686",
+    arch => arch,
+  };
+  Some(format!("{arch}-linux-{env}"))
+}
+
+/// Configures the build command to cross compile the app for the given target.
+///
+/// The environment variables already defined by the user take precedence.
+pub fn configure(
+  build_cmd: &mut Command,
+  target: &str,
+  config: &LinuxCrossCompileConfig,
+) -> crate::Result<()> {
+  let cargo_target = target.to_uppercase().replace('-', "_");
+
+  if let Some(image) = &config.image {
+    // only read by `cross`, which sets up the toolchain and sysroot in the container
+    set_env_if_unset(
+      build_cmd,
+      &format!("CROSS_TARGET_{cargo_target}_IMAGE"),
+      image,
+    );
+    return Ok(());
+  }
+
+  let Some(linker) = config
+    .linker
+    .clone()
+    .or_else(|| toolchain_prefix(target).map(|prefix| format!("{prefix}-gcc")))
+  else {
+    anyhow::bail!(
+      "cannot determine the linker to cross compile `{target}`, set it in `bundle > linux > crossCompile > {target} > linker`"
+    );
+  };
+  set_env_if_unset(
+    build_cmd,
+    &format!("CARGO_TARGET_{cargo_target}_LINKER"),
+    &linker,
+  );
+
+  // build scripts compiling C code with the `cc` crate
+  let cc_target = target.replace('-', "_");
+  if let Some(prefix) = toolchain_prefix(target) {
+    set_env_if_unset(
+      build_cmd,
+      &format!("CC_{cc_target}"),
+      &format!("{prefix}-gcc"),
+    );
+    set_env_if_unset(
+      build_cmd,
+      &format!("AR_{cc_target}"),
+      &format!("{prefix}-ar"),
+    );
+  }
+
+  // the `pkg-config` crate refuses to cross compile unless explicitly allowed
+  set_env_if_unset(build_cmd, "PKG_CONFIG_ALLOW_CROSS", "1");
+
+  if let Some(sysroot) = &config.sysroot {
+    let sysroot = dunce::canonicalize(sysroot).unwrap_or_else(|_| sysroot.clone());
+    let sysroot_str = sysroot.to_string_lossy();
+
+    // target specific variables so the host build scripts still use the host libraries
+    set_env_if_unset(
+      build_cmd,
+      &format!("PKG_CONFIG_SYSROOT_DIR_{cc_target}"),
+      &sysroot_str,
+    );
+    let libdir = pkg_config_libdir(&sysroot, target);
+    if !libdir.is_empty() {
+      set_env_if_unset(
+        build_cmd,
+        &format!("PKG_CONFIG_LIBDIR_{cc_target}"),
+        &libdir,
+      );
+    }
+
+    set_env_if_unset(
+      build_cmd,
+      &format!("CFLAGS_{cc_target}"),
+      &format!("--sysroot={sysroot_str}"),
+    );
+    set_env_if_unset(
+      build_cmd,
+      &format!("CARGO_TARGET_{cargo_target}_RUSTFLAGS"),
+      &format!("-C link-arg=--sysroot={sysroot_str}"),
+    );
+  }
+
+  Ok(())
+}
+
+/// The `pkg-config` search path in the sysroot, using both the multiarch and the plain layout.
+fn pkg_config_libdir(sysroot: &Path, target: &str) -> String {
+  let mut dirs = Vec::new();
+  if let Some(prefix) = toolchain_prefix(target) {
+    dirs.push(sysroot.join("usr/lib").join(&prefix).join("pkgconfig"));
+  }
+  dirs.push(sysroot.join("usr/lib/pkgconfig"));
+  dirs.push(sysroot.join("usr/lib64/pkgconfig"));
+  dirs.push(sysroot.join("usr/share/pkgconfig"));
+
+  dirs
+    .into_iter()
+    .filter(|dir| dir.exists())
+    .map(|dir| dir.to_string_lossy().into_owned())
+    .collect::<Vec<_>>()
+    .join(":")
+}
+
+fn set_env_if_unset(build_cmd: &mut Command, key: &str, value: &str) {
+  if std::env::var_os(key).is_none() {
+    build_cmd.env(key, value);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn toolchain() {
+    assert_eq!(
+      toolchain_prefix("aarch64-unknown-linux-gnu").as_deref(),
+      Some("aarch64-linux-gnu")
+    );
+    assert_eq!(
+      toolchain_prefix("armv7-unknown-linux-gnueabihf").as_deref(),
+      Some("arm-linux-gnueabihf")
+    );
+    assert_eq!(
+      toolchain_prefix("i686-unknown-linux-musl").as_deref(),
+      Some("i686-linux-musl")
+    );
+    assert_eq!(toolchain_prefix("x86_64-pc-windows-msvc"), None);
+    assert_eq!(toolchain_prefix("aarch64-apple-darwin"), None);
+    assert_eq!(toolchain_prefix("wasm32-unknown-unknown"), None);
+  }
+
+  #[test]
+  fn configure_linker() {
+    let mut cmd = Command::new("cargo");
+    configure(
+      &mut cmd,
+      "aarch64-unknown-linux-gnu",
+      &LinuxCrossCompileConfig::default(),
+    )
+    .unwrap();
+    let linker = cmd
+      .get_envs()
+      .find(|(key, _)| *key == "CARGO_TARGET_AARCH64_UNKNOWN_LINUX_GNU_LINKER")
+      .and_then(|(_, value)| value);
+    if std::env::var_os("CARGO_TARGET_AARCH64_UNKNOWN_LINUX_GNU_LINKER").is_none() {
+      assert_eq!(linker, Some(std::ffi::OsStr::new("aarch64-linux-gnu-gcc")));
+    }
+  }
+
+  #[test]
+  fn configure_unknown_target() {
+    let mut cmd = Command::new("cargo");
+    assert!(configure(
+      &mut cmd,
+      "x86_64-pc-windows-msvc",
+      &LinuxCrossCompileConfig::default()
+    )
+    .is_err());
+  }
+}
@@ -2,8 +2,11 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
-use super::{AppSettings, DevProcess, ExitReason, Options, RustAppSettings, RustupTarget};
-use crate::CommandExt;
+use super::{cross, AppSettings, DevProcess, ExitReason, Options, RustAppSettings, RustupTarget};
+use crate::{
+  helpers::event::{self, ArtifactKind, Event, Phase, PhaseStatus},
+  CommandExt,
+};
 
 use anyhow::Context;
 use shared_child::SharedChild;
@@ -15,6 +18,7 @@ use std::{
     Arc, Mutex,
   },
 };
+use tauri_utils::config::LinuxCrossCompileConfig;
 
 pub struct DevChild {
   manually_killed_app: Arc<AtomicBool>,
@@ -73,14 +77,32 @@ pub fn run_dev<F: Fn(Option<i32>, ExitReason) + Send + Sync + 'static>(
   let app_child = Arc::new(Mutex::new(None));
   let app_child_ = app_child.clone();
 
+  Event::Phase {
+    phase: Phase::Build,
+    status: PhaseStatus::Started,
+  }
+  .emit();
+
   let build_child = build_dev_app(
     options,
     available_targets,
     config_features,
     move |status, reason| {
       if status == Some(0) {
+        Event::Phase {
+          phase: Phase::Build,
+          status: PhaseStatus::Finished,
+        }
+        .emit();
+        event::artifact(ArtifactKind::Binary, None, &bin_path);
+        Event::Phase {
+          phase: Phase::Run,
+          status: PhaseStatus::Started,
+        }
+        .emit();
+
         let mut app = Command::new(bin_path);
-        app.stdout(os_pipe::dup_stdout().unwrap());
+        app.stdout(event::child_stdout().unwrap());
         app.stderr(os_pipe::dup_stderr().unwrap());
         app.args(run_args);
         let app_child = Arc::new(SharedChild::spawn(&mut app).unwrap());
@@ -145,7 +167,7 @@ pub fn build(
         .out_dir(&options)
         .with_context(|| format!("failed to get {triple} out dir"))?;
 
-      build_production_app(options, available_targets, config_features.clone())
+      build_production_app(options, available_targets, config_features.clone(), None)
         .with_context(|| format!("failed to build {triple} binary"))?;
 
       lipo_cmd.arg(triple_out_dir.join(bin_name));
@@ -158,8 +180,24 @@ pub fn build(
       )));
     }
   } else {
-    build_production_app(options, available_targets, config_features)
-      .with_context(|| "failed to build app")?;
+    let mut options = options;
+    let cross_compile = app_settings.linux_cross_compile(&options);
+    if let Some((target, config)) = &cross_compile {
+      if config.image.is_some() && options.runner.is_none() {
+        options.runner.replace(cross::CROSS_RUNNER.into());
+      }
+      log::info!(action = "Cross-compiling"; "{target}");
+    }
+
+    build_production_app(
+      options,
+      available_targets,
+      config_features,
+      cross_compile
+        .as_ref()
+        .map(|(target, config)| (target.as_str(), config)),
+    )
+    .with_context(|| "failed to build app")?;
   }
 
   Ok(())
@@ -191,7 +229,7 @@ fn build_dev_app<F: FnOnce(Option<i32>, ExitReason) + Send + 'static>(
   build_cmd.arg("--color");
   build_cmd.arg("always");
 
-  build_cmd.stdout(os_pipe::dup_stdout()?);
+  build_cmd.stdout(event::child_stdout()?);
   build_cmd.stderr(Stdio::piped());
 
   let build_child = match SharedChild::spawn(&mut build_cmd) {
@@ -259,9 +297,13 @@ fn build_production_app(
   options: Options,
   available_targets: &mut Option<Vec<RustupTarget>>,
   config_features: Vec<String>,
+  cross_compile: Option<(&str, &LinuxCrossCompileConfig)>,
 ) -> crate::Result<()> {
   let mut build_cmd = build_command(options, available_targets, config_features)?;
   let runner = build_cmd.get_program().to_string_lossy().into_owned();
+  if let Some((target, config)) = cross_compile {
+    cross::configure(&mut build_cmd, target, config)?;
+  }
   match build_cmd.piped() {
     Ok(status) if status.success() => Ok(()),
     Ok(_) => Err(anyhow::anyhow!("failed to build app")),
@@ -285,7 +327,12 @@ fn build_command(
 ) -> crate::Result<Command> {
   let runner = options.runner.unwrap_or_else(|| "cargo".into());
 
-  if let Some(target) = &options.target {
+  // `cross` installs the target in the container
+  if let Some(target) = options
+    .target
+    .as_ref()
+    .filter(|_| runner != cross::CROSS_RUNNER)
+  {
     if available_targets.is_none() {
       *available_targets = fetch_available_targets();
     }
@@ -4,7 +4,7 @@
 
 use crate::helpers::{
   app_paths::tauri_dir,
-  config::{Config, PatternKind},
+  config::{AssetStorage, Config, PatternKind},
 };
 
 use anyhow::Context;
@@ -294,7 +294,11 @@ pub fn rewrite_manifest(config: &Config) -> crate::Result<(Manifest, bool)> {
   });
 
   // tauri
-  let tauri_features = HashSet::from_iter(config.app.features().into_iter().map(|f| f.to_string()));
+  let mut tauri_features =
+    HashSet::from_iter(config.app.features().into_iter().map(|f| f.to_string()));
+  if config.build.asset_storage == AssetStorage::Packfile {
+    tauri_features.insert("asset-packfile".to_string());
+  }
   dependencies.push(DependencyAllowlist {
     name: "tauri".into(),
     kind: DependencyKind::Normal,
@@ -28,11 +28,14 @@ mod interface;
 mod migrate;
 mod mobile;
 mod plugin;
+mod publish;
 mod signer;
+mod test;
 
 use clap::{ArgAction, CommandFactory, FromArgMatches, Parser, Subcommand, ValueEnum};
 use env_logger::fmt::style::{AnsiColor, Style};
 use env_logger::Builder;
+use helpers::event::{self, Event, OutputFormat};
 use log::Level;
 use serde::Deserialize;
 use std::io::{BufReader, Write};
@@ -40,9 +43,8 @@ use std::process::{exit, Command, ExitStatus, Output, Stdio};
 use std::{
   ffi::OsString,
   fmt::Display,
-  fs::read_to_string,
   io::BufRead,
-  path::PathBuf,
+  path::{Path, PathBuf},
   str::FromStr,
   sync::{Arc, Mutex},
 };
@@ -56,16 +58,25 @@ impl FromStr for ConfigValue {
 
   fn from_str(config: &str) -> std::result::Result<Self, Self::Err> {
     if config.starts_with('{') {
-      Ok(Self(
-        serde_json::from_str(config).context("invalid configuration JSON")?,
-      ))
+      let json = serde_json::from_str(config).context("invalid configuration JSON");
+      // accept JSON5 too, e.g. with unquoted keys
+      let json = json.or_else(|error| {
+        tauri_utils::config::parse::parse_json5_value(config, Path::new("--config"))
+          .map_err(|_| error)
+      });
+      Ok(Self(json?))
     } else {
       let path = PathBuf::from(config);
       if path.exists() {
-        Ok(Self(serde_json::from_str(
-          &read_to_string(&path)
+        Ok(Self(
+          tauri_utils::config::parse::parse_file_value(&path)
             .with_context(|| format!("invalid configuration at file {config}"))?,
-        )?))
+        ))
+      } else if config.contains('=') {
+        let mut toml: serde_json::Value =
+          toml::from_str(config).context("invalid configuration TOML")?;
+        tauri_utils::config::parse::normalize_keys(&mut toml);
+        Ok(Self(toml))
       } else {
         anyhow::bail!("provided configuration path does not exist")
       }
@@ -127,6 +138,13 @@ pub(crate) struct Cli {
   /// Enables verbose logging
   #[clap(short, long, global = true, action = ArgAction::Count)]
   verbose: u8,
+  /// The format of the output. `json` writes one event per line to stdout, for IDEs and CI tools
+  #[clap(long, global = true, value_enum, default_value_t)]
+  output_format: OutputFormat,
+  /// The configuration profile, merging `tauri.conf.<PROFILE>.json` on top of the app configuration,
+  /// e.g. `--profile staging` for `tauri.conf.staging.json`
+  #[clap(long, global = true, env = "TAURI_CONFIG_PROFILE")]
+  profile: Option<String>,
   #[clap(subcommand)]
   command: Commands,
 }
@@ -137,21 +155,45 @@ enum Commands {
   Dev(dev::Options),
   Build(build::Options),
   Bundle(bundle::Options),
+  Publish(publish::Options),
   Android(mobile::android::Cli),
   #[cfg(target_os = "macos")]
   Ios(mobile::ios::Cli),
-  /// Migrate from v1 to v2
+  /// Migrate from v1 or v2 beta to the current version, listing the changes to make manually
   Migrate,
   Info(info::Options),
   Add(add::Options),
   Plugin(plugin::Cli),
   Icon(icon::Options),
+  Test(test::Options),
   Signer(signer::Cli),
   Completions(completions::Options),
   Permission(acl::permission::Cli),
   Capability(acl::capability::Cli),
 }
 
+/// The JSON event of a log record.
+fn log_event<'a>(record: &'a log::Record<'a>, action: Option<&'a str>) -> Event<'a> {
+  match action {
+    Some(stream @ ("stdout" | "stderr")) => Event::Output {
+      stream,
+      line: record.args().to_string(),
+    },
+    action => Event::Log {
+      level: match record.level() {
+        Level::Error => "error",
+        Level::Warn => "warn",
+        Level::Info => "info",
+        Level::Debug => "debug",
+        Level::Trace => "trace",
+      },
+      action,
+      target: record.target(),
+      message: record.args().to_string(),
+    },
+  }
+}
+
 fn format_error<I: CommandFactory>(err: clap::Error) -> clap::Error {
   let mut app = I::command();
   err.format(&mut app)
@@ -213,11 +255,28 @@ where
     Err(e) => e.exit(),
   };
 
+  event::set_output_format(cli.output_format);
+  if let Some(profile) = &cli.profile {
+    // read by the config helpers and the app build script
+    std::env::set_var(tauri_utils::config::parse::PROFILE_ENV, profile);
+  }
+
   let mut builder = Builder::from_default_env();
+  if event::is_json() {
+    builder.target(env_logger::Target::Stdout);
+  }
   let init_res = builder
     .format_indent(Some(12))
     .filter(None, verbosity_level(cli.verbose).to_level_filter())
     .format(|f, record| {
+      if event::is_json() {
+        let action = record
+          .key_values()
+          .get("action".into())
+          .map(|action| action.to_cow_str().unwrap().into_owned());
+        return write!(f, "{}", log_event(record, action.as_deref()).to_line());
+      }
+
       let mut is_command_output = false;
       if let Some(action) = record.key_values().get("action".into()) {
         let action = action.to_cow_str().unwrap();
@@ -253,9 +312,11 @@ where
   match cli.command {
     Commands::Build(options) => build::command(options, cli.verbose)?,
     Commands::Bundle(options) => bundle::command(options, cli.verbose)?,
+    Commands::Publish(options) => publish::command(options)?,
     Commands::Dev(options) => dev::command(options)?,
     Commands::Add(options) => add::command(options)?,
     Commands::Icon(options) => icon::command(options)?,
+    Commands::Test(options) => test::command(options, cli.verbose)?,
     Commands::Info(options) => info::command(options)?,
     Commands::Init(options) => init::command(options)?,
     Commands::Plugin(cli) => plugin::command(cli)?,
@@ -301,7 +362,7 @@ pub trait CommandExt {
 
 impl CommandExt for Command {
   fn piped(&mut self) -> std::io::Result<ExitStatus> {
-    self.stdout(os_pipe::dup_stdout()?);
+    self.stdout(event::child_stdout()?);
     self.stderr(os_pipe::dup_stderr()?);
     let program = self.get_program().to_string_lossy().into_owned();
     log::debug!(action = "Running"; "Command `{} {}`", program, self.get_args().map(|arg| arg.to_string_lossy()).fold(String::new(), |acc, arg| format!("{acc} {arg}")));
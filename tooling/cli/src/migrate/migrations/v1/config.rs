@@ -17,55 +17,80 @@ use std::{
 };
 
 pub fn migrate(tauri_dir: &Path) -> Result<MigratedConfig> {
-  if let Ok((mut config, config_path)) =
-    tauri_utils_v1::config::parse::parse_value(tauri_dir.join("tauri.conf.json"))
-  {
-    let migrated = migrate_config(&mut config)?;
-    fs::write(&config_path, serde_json::to_string_pretty(&config)?)?;
-
-    let mut permissions: Vec<PermissionEntry> = vec!["core:default"]
-      .into_iter()
-      .map(|p| PermissionEntry::PermissionRef(p.to_string().try_into().unwrap()))
-      .collect();
-    permissions.extend(migrated.permissions.clone());
-
-    let capabilities_path = config_path.parent().unwrap().join("capabilities");
-    fs::create_dir_all(&capabilities_path)?;
-    fs::write(
-      capabilities_path.join("migrated.json"),
-      serde_json::to_string_pretty(&Capability {
-        identifier: "migrated".to_string(),
-        description: "permissions that were migrated from v1".into(),
-        local: true,
-        remote: None,
-        windows: vec!["main".into()],
-        webviews: vec![],
-        permissions,
-        platforms: None,
-      })?,
-    )?;
-
-    return Ok(migrated);
+  match tauri_utils_v1::config::parse::parse_value(tauri_dir.join("tauri.conf.json")) {
+    Ok((mut config, config_path)) => {
+      let windows = window_labels(&config);
+      let migrated = migrate_config(&mut config)?;
+      fs::write(&config_path, serde_json::to_string_pretty(&config)?)?;
+
+      let mut permissions: Vec<PermissionEntry> = vec!["core:default"]
+        .into_iter()
+        .map(|p| PermissionEntry::PermissionRef(p.to_string().try_into().unwrap()))
+        .collect();
+      permissions.extend(migrated.permissions.clone());
+
+      let capabilities_path = config_path.parent().unwrap().join("capabilities");
+      fs::create_dir_all(&capabilities_path)?;
+      fs::write(
+        capabilities_path.join("migrated.json"),
+        serde_json::to_string_pretty(&Capability {
+          identifier: "migrated".to_string(),
+          description: "permissions that were migrated from v1".into(),
+          local: true,
+          remote: None,
+          windows,
+          webviews: vec![],
+          permissions,
+          platforms: None,
+          os_permissions: Vec::new(),
+        })?,
+      )?;
+
+      Ok(migrated)
+    }
+    Err(e) => Ok(MigratedConfig {
+      manual_steps: vec![format!(
+        "the configuration could not be read as a Tauri v1 configuration and was not migrated: {e}"
+      )],
+      ..Default::default()
+    }),
   }
+}
 
-  Ok(Default::default())
+/// The labels of the windows defined in the v1 configuration, which get the migrated permissions.
+fn window_labels(config: &Value) -> Vec<String> {
+  let mut labels = config
+    .get("tauri")
+    .and_then(|t| t.get("windows"))
+    .and_then(|w| w.as_array())
+    .map(|windows| {
+      windows
+        .iter()
+        .map(|w| w.get("label").and_then(|l| l.as_str()).unwrap_or("main"))
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+    })
+    .unwrap_or_default();
+  if labels.is_empty() {
+    labels.push("main".into());
+  }
+  labels
 }
 
 #[derive(Default)]
 pub struct MigratedConfig {
   pub permissions: Vec<PermissionEntry>,
   pub plugins: HashSet<String>,
+  /// The changes that could not be migrated automatically.
+  pub manual_steps: Vec<String>,
 }
 
 fn migrate_config(config: &mut Value) -> Result<MigratedConfig> {
-  let mut migrated = MigratedConfig {
-    permissions: Vec::new(),
-    plugins: HashSet::new(),
-  };
+  let mut migrated = MigratedConfig::default();
 
   if let Some(config) = config.as_object_mut() {
     process_package_metadata(config);
-    process_build(config);
+    process_build(config, &mut migrated.manual_steps);
 
     let mut plugins = config
       .entry("plugins")
@@ -78,8 +103,15 @@ fn migrate_config(config: &mut Value) -> Result<MigratedConfig> {
       // allowlist
       if let Some(allowlist) = tauri_config.remove("allowlist") {
         let allowlist = process_allowlist(tauri_config, allowlist)?;
+        if let tauri_utils_v1::config::ShellAllowlistOpen::Validate(open) = &allowlist.shell.open {
+          let mut shell = Map::new();
+          shell.insert("open".into(), open.clone().into());
+          plugins.insert("shell".into(), shell.into());
+        }
         let permissions = allowlist_to_permissions(allowlist);
-        migrated.plugins = plugins_from_permissions(&permissions);
+        migrated
+          .plugins
+          .extend(plugins_from_permissions(&permissions));
         migrated.permissions = permissions;
       }
 
@@ -108,10 +140,13 @@ fn migrate_config(config: &mut Value) -> Result<MigratedConfig> {
       // cli
       if let Some(cli) = tauri_config.remove("cli") {
         process_cli(&mut plugins, cli)?;
+        migrated.plugins.insert("cli".into());
       }
 
-      // cli
-      process_updater(tauri_config, &mut plugins)?;
+      // updater
+      if process_updater(tauri_config, &mut plugins, &mut migrated.manual_steps)? {
+        migrated.plugins.insert("updater".into());
+      }
     }
 
     config.insert("plugins".into(), plugins.into());
@@ -150,7 +185,7 @@ fn process_package_metadata(config: &mut Map<String, Value>) {
   }
 }
 
-fn process_build(config: &mut Map<String, Value>) {
+fn process_build(config: &mut Map<String, Value>, manual_steps: &mut Vec<String>) {
   if let Some(build_config) = config.get_mut("build").and_then(|b| b.as_object_mut()) {
     if let Some(dist_dir) = build_config.remove("distDir") {
       build_config.insert("frontendDist".into(), dist_dir);
@@ -159,6 +194,10 @@ fn process_build(config: &mut Map<String, Value>) {
       let is_url = url::Url::parse(dev_path.as_str().unwrap_or_default()).is_ok();
       if is_url {
         build_config.insert("devUrl".into(), dev_path);
+      } else if build_config.get("frontendDist") != Some(&dev_path) {
+        manual_steps.push(format!(
+          "`build > devPath` ({dev_path}) was removed since `tauri dev` now serves `build > frontendDist` when `build > devUrl` is not set, move the development assets there or run a dev server and set `build > devUrl`"
+        ));
       }
     }
     if let Some(with_global_tauri) = build_config.remove("withGlobalTauri") {
@@ -509,30 +548,40 @@ fn process_cli(plugins: &mut Map<String, Value>, cli: Value) -> Result<()> {
   Ok(())
 }
 
+/// Moves the updater configuration to `plugins > updater`, returning whether the updater plugin is used.
 fn process_updater(
   tauri_config: &mut Map<String, Value>,
   plugins: &mut Map<String, Value>,
-) -> Result<()> {
+  manual_steps: &mut Vec<String>,
+) -> Result<bool> {
   if let Some(mut updater) = tauri_config.remove("updater") {
     if let Some(updater) = updater.as_object_mut() {
-      updater.remove("dialog");
+      let dialog = updater
+        .remove("dialog")
+        .and_then(|d| d.as_bool())
+        .unwrap_or(true);
 
       // we only migrate the updater config if it's active
       // since we now assume it's always active if the config object is set
       // we also migrate if pubkey is set so we do not lose that information on the migration
       // in this case, the user need to deal with the updater being inactive on their own
-      if updater
+      let active = updater
         .remove("active")
         .and_then(|a| a.as_bool())
-        .unwrap_or_default()
-        || updater.get("pubkey").is_some()
-      {
+        .unwrap_or_default();
+      if active || updater.get("pubkey").is_some() {
+        if !active {
+          manual_steps.push("the updater was inactive but it is now enabled when `plugins > updater` is set, remove it if the app should not check for updates".into());
+        } else if dialog {
+          manual_steps.push("the built-in updater dialog was removed, use the `@tauri-apps/plugin-updater` and `@tauri-apps/plugin-dialog` APIs to ask for the user confirmation".into());
+        }
         plugins.insert("updater".into(), serde_json::to_value(updater)?);
+        return Ok(true);
       }
     }
   }
 
-  Ok(())
+  Ok(false)
 }
 
 const KNOWN_PLUGINS: &[&str] = &[
@@ -945,4 +994,43 @@ mod test {
       original["build"]["frontendDist"]
     );
   }
+
+  #[test]
+  fn reports_manual_steps() {
+    let mut config = serde_json::json!({
+      "build": {
+        "devPath": "../public",
+        "distDir": "../dist"
+      },
+      "tauri": {
+        "updater": {
+          "active": true
+        },
+        "allowlist": {
+          "shell": {
+            "open": "^https://tauri.app"
+          }
+        }
+      }
+    });
+
+    let migrated = super::migrate_config(&mut config).expect("failed to migrate config");
+    assert_eq!(migrated.manual_steps.len(), 2);
+    assert!(migrated.manual_steps[0].contains("build > devPath"));
+    assert!(migrated.manual_steps[1].contains("updater dialog"));
+    assert!(migrated.plugins.contains("updater"));
+    assert!(migrated.plugins.contains("shell"));
+    assert_eq!(config["plugins"]["shell"]["open"], "^https://tauri.app");
+  }
+
+  #[test]
+  fn migrate_window_labels() {
+    let config = serde_json::json!({
+      "tauri": {
+        "windows": [{ "label": "main" }, { "label": "settings" }]
+      }
+    });
+    assert_eq!(super::window_labels(&config), vec!["main", "settings"]);
+    assert_eq!(super::window_labels(&serde_json::json!({})), vec!["main"]);
+  }
 }
@@ -13,7 +13,8 @@ mod config;
 mod frontend;
 mod manifest;
 
-pub fn run() -> Result<()> {
+/// Migrates the app from v1, returning the changes that must be made manually.
+pub fn run() -> Result<Vec<String>> {
   let tauri_dir = tauri_dir();
   let app_dir = app_dir();
 
@@ -32,5 +33,5 @@ pub fn run() -> Result<()> {
     .with_context(|| format!("Could not migrate plugin '{plugin}'"))?;
   }
 
-  Ok(())
+  Ok(migrated.manual_steps)
 }
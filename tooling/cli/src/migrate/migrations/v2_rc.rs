@@ -16,7 +16,8 @@ use std::{fs::read_to_string, path::Path};
 use anyhow::Context;
 use toml_edit::{Document, Item, Table, TableLike, Value};
 
-pub fn run() -> Result<()> {
+/// Migrates the app from v2 beta, returning the changes that must be made manually.
+pub fn run() -> Result<Vec<String>> {
   let app_dir = app_dir();
   let tauri_dir = tauri_dir();
 
@@ -31,7 +32,7 @@ pub fn run() -> Result<()> {
   std::fs::write(&manifest_path, serialize_manifest(&manifest))
     .context("failed to rewrite Cargo manifest")?;
 
-  Ok(())
+  Ok(Vec::new())
 }
 
 fn migrate_npm_dependencies(app_dir: &Path) -> Result<()> {
@@ -102,7 +103,9 @@ fn migrate_permissions(tauri_dir: &Path) -> Result<()> {
   for entry in walkdir::WalkDir::new(tauri_dir.join("capabilities")) {
     let entry = entry?;
     let path = entry.path();
-    if path.extension().map_or(false, |ext| ext == "json") {
+    if path.extension().map_or(false, |ext| {
+      ext == "json" || ext == "json5" || ext == "toml"
+    }) {
       let mut capability = read_to_string(path).context("failed to read capability")?;
       for plugin in core_plugins {
         capability = capability.replace(&format!("\"{plugin}:"), &format!("\"core:{plugin}:"));
@@ -14,9 +14,17 @@ use crate::{
 use std::{fs::read_to_string, str::FromStr};
 
 use anyhow::Context;
+use serde_json::Value as JsonValue;
 
 mod migrations;
 
+/// Whether the configuration uses the Tauri v1 format, which has the `package` and `tauri` objects.
+pub fn is_v1_config(config: &JsonValue) -> bool {
+  config.as_object().map_or(false, |c| {
+    c.contains_key("tauri") || c.contains_key("package")
+  })
+}
+
 pub fn command() -> Result<()> {
   let tauri_dir = tauri_dir();
 
@@ -39,13 +47,31 @@ pub fn command() -> Result<()> {
   let tauri_version = crate_version(&tauri_dir, Some(&manifest), lock.as_ref(), "tauri").version;
   let tauri_version = semver::Version::from_str(&tauri_version)?;
 
-  if tauri_version.major == 1 {
-    migrations::v1::run().context("failed to migrate from v1")?;
-  } else if tauri_version.major == 2 {
-    if let Some((pre, _number)) = tauri_version.pre.as_str().split_once('.') {
-      if pre == "beta" {
-        migrations::v2_rc::run().context("failed to migrate from v2 beta to rc")?;
-      }
+  // the dependencies might have been updated without migrating the configuration
+  let v1_config = tauri_utils_v1::config::parse::parse_value(tauri_dir.join("tauri.conf.json"))
+    .map_or(false, |(config, _)| is_v1_config(&config));
+
+  let manual_steps = if tauri_version.major == 1 || v1_config {
+    migrations::v1::run().context("failed to migrate from v1")?
+  } else if tauri_version.major == 2
+    && tauri_version
+      .pre
+      .as_str()
+      .split_once('.')
+      .map_or(false, |(pre, _number)| pre == "beta")
+  {
+    migrations::v2_rc::run().context("failed to migrate from v2 beta to rc")?
+  } else {
+    log::info!("Nothing to migrate, the app already uses Tauri {tauri_version}");
+    return Ok(());
+  };
+
+  if manual_steps.is_empty() {
+    log::info!(action = "Migrated"; "the app to Tauri 2");
+  } else {
+    log::warn!("The app was migrated to Tauri 2, but the following changes must be made manually:");
+    for step in manual_steps {
+      log::warn!("- {step}");
     }
   }
 
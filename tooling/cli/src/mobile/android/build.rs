@@ -10,7 +10,7 @@ use crate::{
   build::Options as BuildOptions,
   helpers::{
     app_paths::tauri_dir,
-    config::{get as get_tauri_config, ConfigHandle},
+    config::{get as get_tauri_config, AndroidSigningConfig, ConfigHandle},
     flock,
   },
   interface::{AppInterface, AppSettings, Interface, Options as InterfaceOptions},
@@ -78,6 +78,12 @@ impl From<Options> for BuildOptions {
       features: options.features,
       bundles: None,
       no_bundle: false,
+      updater_delta_base: None,
+      reproducible: false,
+      analyze: false,
+      sbom: None,
+      app: None,
+      all: false,
       config: options.config,
       args: Vec::new(),
       ci: options.ci,
@@ -177,6 +183,23 @@ fn run_build(
     options.aab = true;
   }
 
+  let android_bundle_config = tauri_config
+    .lock()
+    .unwrap()
+    .as_ref()
+    .unwrap()
+    .bundle
+    .android
+    .clone();
+  if matches!(profile, Profile::Release) {
+    check_signing(android_bundle_config.signing.as_ref())?;
+  }
+  if options.apk && !android_bundle_config.asset_packs.is_empty() {
+    log::warn!(
+      "The asset packs are only delivered with the AABs, the APKs do not include their resources."
+    );
+  }
+
   let interface_options = InterfaceOptions {
     debug: build_options.debug,
     target: build_options.target.clone(),
@@ -234,6 +257,28 @@ fn run_build(
   Ok(handle)
 }
 
+/// Checks the signing configuration before building, since Gradle only reports a missing keystore or password at the end of the build.
+fn check_signing(signing: Option<&AndroidSigningConfig>) -> Result<()> {
+  let Some(signing) = signing else {
+    log::warn!("`bundle > android > signing` is not set, the release APKs and AABs are unsigned and must be signed before being uploaded to the Play Store.");
+    return Ok(());
+  };
+
+  let key_store = tauri_dir().join(&signing.key_store);
+  if !key_store.exists() {
+    anyhow::bail!(
+      "The Android keystore {} does not exist.",
+      key_store.display()
+    );
+  }
+  if std::env::var_os("TAURI_ANDROID_KEYSTORE_PASSWORD").is_none() {
+    anyhow::bail!(
+      "The `TAURI_ANDROID_KEYSTORE_PASSWORD` environment variable must be set to sign the Android release builds."
+    );
+  }
+  Ok(())
+}
+
 fn get_targets_or_all<'a>(targets: Vec<String>) -> Result<Vec<&'a Target<'a>>> {
   if targets.is_empty() {
     Ok(Target::all().iter().map(|t| t.1).collect())
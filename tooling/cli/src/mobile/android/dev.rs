@@ -82,6 +82,7 @@ impl From<Options> for DevOptions {
       args: Vec::new(),
       no_watch: options.no_watch,
       no_dev_server_wait: options.no_dev_server_wait,
+      app: None,
       no_dev_server: options.no_dev_server,
       port: options.port,
       release_mode: options.release_mode,
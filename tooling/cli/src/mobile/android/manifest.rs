@@ -0,0 +1,319 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Generates the intent filters of the main activity and the permissions in the `AndroidManifest.xml` file.
+
+use std::{
+  fs::{read_to_string, write},
+  path::Path,
+};
+
+use tauri_utils::acl::capability::OsPermission;
+
+use crate::{
+  helpers::config::{AndroidConfig, AndroidIntentFilter, AndroidShareTarget},
+  Result,
+};
+
+const BLOCK_IDENTIFIER: &str = "tauri intent filters";
+const PERMISSIONS_BLOCK_IDENTIFIER: &str = "tauri permissions";
+
+pub fn update(
+  project_dir: &Path,
+  config: &AndroidConfig,
+  os_permissions: &[OsPermission],
+) -> Result<()> {
+  let manifest_path = project_dir.join("app/src/main/AndroidManifest.xml");
+  let manifest = read_to_string(&manifest_path)?;
+  let rewritten = insert_into_xml(
+    &manifest,
+    "activity",
+    BLOCK_IDENTIFIER,
+    &intent_filters(config),
+  );
+  let rewritten = insert_into_xml(
+    &rewritten,
+    "manifest",
+    PERMISSIONS_BLOCK_IDENTIFIER,
+    &uses_permissions(os_permissions),
+  );
+  if rewritten != manifest {
+    write(manifest_path, rewritten)?;
+  }
+  Ok(())
+}
+
+fn intent_filters(config: &AndroidConfig) -> String {
+  let mut filters = config
+    .intent_filters
+    .iter()
+    .map(intent_filter)
+    .collect::<Vec<_>>();
+  if let Some(share_target) = &config.share_target {
+    filters.push(share_intent_filter(
+      share_target,
+      "android.intent.action.SEND",
+    ));
+    if share_target.multiple {
+      filters.push(share_intent_filter(
+        share_target,
+        "android.intent.action.SEND_MULTIPLE",
+      ));
+    }
+  }
+  filters.join("\n")
+}
+
+fn intent_filter(filter: &AndroidIntentFilter) -> String {
+  let mut xml = if filter.auto_verify {
+    r#"<intent-filter android:autoVerify="true">"#.to_string()
+  } else {
+    "<intent-filter>".to_string()
+  };
+  xml.push_str(&format!(
+    "\n    <action android:name=\"{}\" />",
+    escape(&filter.action)
+  ));
+  for category in &filter.categories {
+    xml.push_str(&format!(
+      "\n    <category android:name=\"{}\" />",
+      escape(category)
+    ));
+  }
+  for data in &filter.data {
+    let attributes = [
+      ("scheme", &data.scheme),
+      ("host", &data.host),
+      ("pathPrefix", &data.path_prefix),
+      ("pathPattern", &data.path_pattern),
+      ("mimeType", &data.mime_type),
+    ]
+    .into_iter()
+    .filter_map(|(name, value)| {
+      value
+        .as_ref()
+        .map(|value| format!(" android:{name}=\"{}\"", escape(value)))
+    })
+    .collect::<String>();
+    xml.push_str(&format!("\n    <data{attributes} />"));
+  }
+  xml.push_str("\n</intent-filter>");
+  xml
+}
+
+fn share_intent_filter(share_target: &AndroidShareTarget, action: &str) -> String {
+  let mut xml = format!(
+    "<intent-filter>\n    <action android:name=\"{action}\" />\n    <category android:name=\"android.intent.category.DEFAULT\" />"
+  );
+  for mime_type in &share_target.mime_types {
+    xml.push_str(&format!(
+      "\n    <data android:mimeType=\"{}\" />",
+      escape(mime_type)
+    ));
+  }
+  xml.push_str("\n</intent-filter>");
+  xml
+}
+
+/// The `uses-permission` elements of the operating system permissions required by the capabilities.
+fn uses_permissions(os_permissions: &[OsPermission]) -> String {
+  os_permissions
+    .iter()
+    .flat_map(|p| {
+      p.android_permissions()
+        .iter()
+        .map(|p| format!("<uses-permission android:name=\"{p}\" />"))
+        .chain(p.android_legacy_permissions().iter().map(|(p, max_sdk)| {
+          format!("<uses-permission android:name=\"{p}\" android:maxSdkVersion=\"{max_sdk}\" />")
+        }))
+    })
+    .collect::<Vec<_>>()
+    .join("\n")
+}
+
+fn escape(value: &str) -> String {
+  value
+    .replace('&', "&amp;")
+    .replace('"', "&quot;")
+    .replace('<', "&lt;")
+    .replace('>', "&gt;")
+}
+
+fn xml_block_comment(id: &str) -> String {
+  format!("<!-- {id}. AUTO-GENERATED. DO NOT REMOVE. -->")
+}
+
+/// Replaces the generated block of the parent tag, removing it if the contents are empty.
+fn insert_into_xml(xml: &str, parent_tag: &str, block_identifier: &str, contents: &str) -> String {
+  let block_comment = xml_block_comment(block_identifier);
+
+  let mut rewritten = Vec::new();
+  let mut found_block = false;
+  let mut inserted = false;
+  let parent_closing_tag = format!("</{parent_tag}>");
+  for line in xml.split('\n') {
+    if line.contains(&block_comment) {
+      found_block = !found_block;
+      continue;
+    }
+
+    // found previous block which should be removed
+    if found_block {
+      continue;
+    }
+
+    if let Some(index) = line.find(&parent_closing_tag) {
+      if !inserted && !contents.is_empty() {
+        let indentation = " ".repeat(index + 4);
+        rewritten.push(format!("{indentation}{block_comment}"));
+        for l in contents.split('\n') {
+          rewritten.push(format!("{indentation}{l}"));
+        }
+        rewritten.push(format!("{indentation}{block_comment}"));
+      }
+      inserted = true;
+    }
+
+    rewritten.push(line.to_string());
+  }
+
+  rewritten.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+  use tauri_utils::acl::capability::OsPermission;
+
+  use crate::helpers::config::{AndroidConfig, AndroidIntentFilterData, AndroidShareTarget};
+
+  const MANIFEST: &str = r#"<manifest>
+    <application>
+        <activity android:name=".MainActivity">
+            <intent-filter>
+                <action android:name="android.intent.action.MAIN" />
+            </intent-filter>
+        </activity>
+    </application>
+</manifest>"#;
+
+  #[test]
+  fn generate_intent_filters() {
+    let config: AndroidConfig = serde_json::from_value(serde_json::json!({
+      "intentFilters": [{
+        "autoVerify": true,
+        "data": [{ "scheme": "https", "host": "example.com", "pathPrefix": "/open" }]
+      }],
+      "shareTarget": { "mimeTypes": ["text/plain", "image/*"] }
+    }))
+    .unwrap();
+    assert_eq!(
+      config.share_target,
+      Some(AndroidShareTarget {
+        mime_types: vec!["text/plain".into(), "image/*".into()],
+        multiple: false,
+      })
+    );
+    assert_eq!(
+      config.intent_filters[0].data[0],
+      AndroidIntentFilterData {
+        scheme: Some("https".into()),
+        host: Some("example.com".into()),
+        path_prefix: Some("/open".into()),
+        ..Default::default()
+      }
+    );
+
+    let manifest = super::insert_into_xml(
+      MANIFEST,
+      "activity",
+      super::BLOCK_IDENTIFIER,
+      &super::intent_filters(&config),
+    );
+    let block_comment = super::xml_block_comment(super::BLOCK_IDENTIFIER);
+    let expected = format!(
+      r#"<manifest>
+    <application>
+        <activity android:name=".MainActivity">
+            <intent-filter>
+                <action android:name="android.intent.action.MAIN" />
+            </intent-filter>
+            {block_comment}
+            <intent-filter android:autoVerify="true">
+                <action android:name="android.intent.action.VIEW" />
+                <category android:name="android.intent.category.DEFAULT" />
+                <category android:name="android.intent.category.BROWSABLE" />
+                <data android:scheme="https" android:host="example.com" android:pathPrefix="/open" />
+            </intent-filter>
+            <intent-filter>
+                <action android:name="android.intent.action.SEND" />
+                <category android:name="android.intent.category.DEFAULT" />
+                <data android:mimeType="text/plain" />
+                <data android:mimeType="image/*" />
+            </intent-filter>
+            {block_comment}
+        </activity>
+    </application>
+</manifest>"#
+    );
+    assert_eq!(manifest, expected);
+
+    // the block is replaced, and removed once the config is empty
+    assert_eq!(
+      super::insert_into_xml(
+        &manifest,
+        "activity",
+        super::BLOCK_IDENTIFIER,
+        &super::intent_filters(&config)
+      ),
+      expected
+    );
+    assert_eq!(
+      super::insert_into_xml(
+        &manifest,
+        "activity",
+        super::BLOCK_IDENTIFIER,
+        &super::intent_filters(&AndroidConfig::default())
+      ),
+      MANIFEST
+    );
+  }
+
+  #[test]
+  fn generate_uses_permissions() {
+    let permissions = [
+      OsPermission::Camera,
+      OsPermission::Location,
+      OsPermission::Photos,
+    ];
+    let manifest = super::insert_into_xml(
+      MANIFEST,
+      "manifest",
+      super::PERMISSIONS_BLOCK_IDENTIFIER,
+      &super::uses_permissions(&permissions),
+    );
+    let block_comment = super::xml_block_comment(super::PERMISSIONS_BLOCK_IDENTIFIER);
+    assert_eq!(
+      manifest,
+      format!(
+        r#"<manifest>
+    <application>
+        <activity android:name=".MainActivity">
+            <intent-filter>
+                <action android:name="android.intent.action.MAIN" />
+            </intent-filter>
+        </activity>
+    </application>
+    {block_comment}
+    <uses-permission android:name="android.permission.CAMERA" />
+    <uses-permission android:name="android.permission.ACCESS_COARSE_LOCATION" />
+    <uses-permission android:name="android.permission.ACCESS_FINE_LOCATION" />
+    <uses-permission android:name="android.permission.READ_MEDIA_IMAGES" />
+    <uses-permission android:name="android.permission.READ_MEDIA_VIDEO" />
+    <uses-permission android:name="android.permission.READ_EXTERNAL_STORAGE" android:maxSdkVersion="32" />
+    {block_comment}
+</manifest>"#
+      )
+    );
+  }
+}
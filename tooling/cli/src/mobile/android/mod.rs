@@ -37,6 +37,7 @@ use crate::{helpers::config::Config as TauriConfig, Result};
 mod android_studio_script;
 mod build;
 mod dev;
+mod manifest;
 mod open;
 pub(crate) mod project;
 
@@ -309,5 +310,16 @@ fn inject_assets(config: &AndroidConfig, tauri_config: &TauriConfig) -> Result<(
     serde_json::to_string(&tauri_config)?,
   )?;
 
+  let os_permissions = crate::helpers::capabilities::os_permissions(
+    tauri_config,
+    &crate::helpers::app_paths::tauri_dir(),
+    tauri_utils::platform::Target::Android,
+  )?;
+  manifest::update(
+    &config.project_dir(),
+    &tauri_config.bundle.android,
+    &os_permissions,
+  )?;
+
   Ok(())
 }
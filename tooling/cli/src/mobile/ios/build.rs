@@ -3,8 +3,9 @@
 // SPDX-License-Identifier: MIT
 
 use super::{
-  configure_cargo, detect_target_ok, ensure_init, env, get_app, get_config, inject_assets,
-  log_finished, merge_plist, open_and_wait, MobileTarget, OptionsHandle,
+  check_usage_descriptions, configure_cargo, detect_target_ok, ensure_init, env, get_app,
+  get_config, inject_assets, log_finished, merge_plist, open_and_wait, update_entitlements,
+  MobileTarget, OptionsHandle,
 };
 use crate::{
   build::Options as BuildOptions,
@@ -109,6 +110,12 @@ impl From<Options> for BuildOptions {
       features: options.features,
       bundles: None,
       no_bundle: false,
+      updater_delta_base: None,
+      reproducible: false,
+      analyze: false,
+      sbom: None,
+      app: None,
+      all: false,
       config: options.config,
       args: Vec::new(),
       ci: options.ci,
@@ -170,6 +177,11 @@ pub fn command(options: Options, noise_level: NoiseLevel) -> Result<()> {
     ],
     &info_plist_path,
   )?;
+  update_entitlements(&config, tauri_config.lock().unwrap().as_ref().unwrap())?;
+  check_usage_descriptions(
+    &info_plist_path,
+    tauri_config.lock().unwrap().as_ref().unwrap(),
+  )?;
 
   let mut env = env()?;
   configure_cargo(&app, None)?;
@@ -3,8 +3,8 @@
 // SPDX-License-Identifier: MIT
 
 use super::{
-  configure_cargo, device_prompt, ensure_init, env, get_app, get_config, inject_assets,
-  merge_plist, open_and_wait, MobileTarget,
+  check_usage_descriptions, configure_cargo, device_prompt, ensure_init, env, get_app, get_config,
+  hot_swap, inject_assets, merge_plist, open_and_wait, update_entitlements, MobileTarget,
 };
 use crate::{
   dev::Options as DevOptions,
@@ -23,7 +23,7 @@ use anyhow::Context;
 use cargo_mobile2::{
   apple::{
     config::Config as AppleConfig,
-    device::{Device, DeviceKind},
+    device::{self, Device, DeviceKind},
   },
   config::app::App,
   env::Env,
@@ -33,7 +33,10 @@ use cargo_mobile2::{
 use std::{
   env::set_current_dir,
   net::{IpAddr, Ipv4Addr, SocketAddr},
-  sync::OnceLock,
+  sync::{
+    atomic::{AtomicBool, Ordering},
+    OnceLock,
+  },
 };
 
 #[derive(Debug, Clone, Parser)]
@@ -43,6 +46,9 @@ use std::{
 It makes use of the `build.devUrl` property from your `tauri.conf.json` file.
 It also runs your `build.beforeDevCommand` which usually starts your frontend devServer.
 
+On the iOS simulator, the Rust code is hot-swapped on changes: it is rebuilt as a dynamic library
+loaded by the relaunched app, without rebuilding the app with Xcode.
+
 When connected to a physical iOS device, the public network address must be used instead of `localhost`
 for the devUrl property. Tauri makes that change automatically, but your dev server might need
 a different configuration to listen on the public address. You can check the `TAURI_DEV_HOST`
@@ -95,6 +101,9 @@ pub struct Options {
   /// Specify port for the built-in dev server for static files. Defaults to 1430.
   #[clap(long, env = "TAURI_CLI_PORT")]
   pub port: Option<u16>,
+  /// Rebuild the app with Xcode on Rust changes instead of hot-swapping the Rust code on the simulator.
+  #[clap(long)]
+  pub no_hot_swap: bool,
 }
 
 impl From<Options> for DevOptions {
@@ -110,6 +119,7 @@ impl From<Options> for DevOptions {
       no_watch: options.no_watch,
       no_dev_server: options.no_dev_server,
       no_dev_server_wait: options.no_dev_server_wait,
+      app: None,
       port: options.port,
       host: None,
     }
@@ -182,6 +192,11 @@ fn run_command(options: Options, noise_level: NoiseLevel) -> Result<()> {
     ],
     &info_plist_path,
   )?;
+  update_entitlements(&config, tauri_config.lock().unwrap().as_ref().unwrap())?;
+  check_usage_descriptions(
+    &info_plist_path,
+    tauri_config.lock().unwrap().as_ref().unwrap(),
+  )?;
 
   run_dev(
     interface,
@@ -339,12 +354,17 @@ fn run_dev(
   config: &AppleConfig,
   noise_level: NoiseLevel,
 ) -> Result<()> {
-  // when running on an actual device we must use the network IP
+  // when running on an actual device we must use the network IP,
+  // and Xcode might run on any of the connected devices
   if options.host.is_some()
     || device
       .as_ref()
       .map(|device| !matches!(device.kind(), DeviceKind::Simulator))
       .unwrap_or(false)
+    || (options.open
+      && device::list_devices(&env)
+        .map(|devices| !devices.is_empty())
+        .unwrap_or(false))
   {
     use_network_address_for_dev_url(&tauri_config, &mut options, &mut dev_options)?;
   }
@@ -365,6 +385,15 @@ fn run_dev(
   let open = options.open;
   let exit_on_panic = options.exit_on_panic;
   let no_watch = options.no_watch;
+  let hot_swap = !options.no_hot_swap
+    && device
+      .as_ref()
+      .map(|device| matches!(device.kind(), DeviceKind::Simulator))
+      .unwrap_or(false);
+  // the first run installs the app with Xcode, the next ones hot-swap the Rust code
+  let installed = AtomicBool::new(false);
+  let target = dev_options.target.clone().unwrap();
+  let release = dev_options.release_mode;
   interface.mobile_dev(
     MobileOptions {
       debug: true,
@@ -388,9 +417,23 @@ fn run_dev(
 
       if open {
         open_and_wait(config, &env)
+      } else if hot_swap && installed.load(Ordering::Relaxed) {
+        let dylib = hot_swap::build_dylib(
+          &target,
+          &app.lib_name(),
+          out_dir,
+          options.features.as_ref(),
+          release,
+        )?;
+        let c = hot_swap::relaunch(&app.reverse_identifier(), &dylib)?;
+        crate::dev::wait_dev_process(c.clone(), move |status, reason| {
+          crate::dev::on_app_exit(status, reason, exit_on_panic, no_watch)
+        });
+        Ok(Box::new(c) as Box<dyn DevProcess + Send>)
       } else if let Some(device) = &device {
         match run(device, options, config, &env) {
           Ok(c) => {
+            installed.store(true, Ordering::Relaxed);
             crate::dev::wait_dev_process(c.clone(), move |status, reason| {
               crate::dev::on_app_exit(status, reason, exit_on_panic, no_watch)
             });
@@ -0,0 +1,140 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Hot-swapping of the Rust code on the iOS simulator.
+//!
+//! The simulator apps run on the host file system, so instead of rebuilding and reinstalling the app with Xcode
+//! the Rust library is rebuilt as a dylib and the app is relaunched with the `TAURI_DEV_DYLIB` environment variable,
+//! which makes the `main.mm` of the Xcode project load the dylib instead of the statically linked library.
+
+use crate::{interface::DevProcess, Result};
+
+use anyhow::Context;
+use shared_child::SharedChild;
+
+use std::{
+  path::{Path, PathBuf},
+  process::{Command, ExitStatus},
+  sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+  },
+};
+
+/// The environment variable of the app with the path of the dylib to load.
+const DYLIB_ENV_VAR: &str = "TAURI_DEV_DYLIB";
+
+/// The app process launched by `simctl`.
+#[derive(Clone)]
+pub struct SimulatorChild {
+  child: Arc<SharedChild>,
+  manually_killed_process: Arc<AtomicBool>,
+}
+
+impl DevProcess for SimulatorChild {
+  fn kill(&self) -> std::io::Result<()> {
+    self.manually_killed_process.store(true, Ordering::Relaxed);
+    match self.child.kill() {
+      Ok(_) => Ok(()),
+      Err(e) => {
+        self.manually_killed_process.store(false, Ordering::Relaxed);
+        Err(e)
+      }
+    }
+  }
+
+  fn try_wait(&self) -> std::io::Result<Option<ExitStatus>> {
+    self.child.try_wait()
+  }
+
+  fn wait(&self) -> std::io::Result<ExitStatus> {
+    self.child.wait()
+  }
+
+  fn manually_killed_process(&self) -> bool {
+    self.manually_killed_process.load(Ordering::Relaxed)
+  }
+}
+
+/// Builds the Rust library of the app as a dylib for the simulator target, returning its path.
+///
+/// The symbols of the Swift code linked in the app executable are resolved when the dylib is loaded.
+pub fn build_dylib(
+  target: &str,
+  lib_name: &str,
+  out_dir: &Path,
+  features: Option<&Vec<String>>,
+  release: bool,
+) -> Result<PathBuf> {
+  let sdk_path = Command::new("xcrun")
+    .args(["--sdk", "iphonesimulator", "--show-sdk-path"])
+    .output()
+    .context("failed to find the iOS simulator SDK")?;
+  let isysroot = format!(
+    "-isysroot {}",
+    String::from_utf8_lossy(&sdk_path.stdout).trim()
+  );
+  let env_triple = target.replace('-', "_");
+
+  let mut command = Command::new("cargo");
+  command
+    .args([
+      "rustc",
+      "--lib",
+      "--crate-type",
+      "cdylib",
+      "--target",
+      target,
+    ])
+    .env(format!("CFLAGS_{env_triple}"), &isysroot)
+    .env(format!("CXXFLAGS_{env_triple}"), &isysroot);
+  if let Some(features) = features.filter(|f| !f.is_empty()) {
+    command.arg("--features").arg(features.join(","));
+  }
+  if release {
+    command.arg("--release");
+  }
+  command.args([
+    "--",
+    "-C",
+    "link-arg=-undefined",
+    "-C",
+    "link-arg=dynamic_lookup",
+  ]);
+
+  let status = command
+    .status()
+    .context("failed to run cargo to build the Rust library")?;
+  if !status.success() {
+    anyhow::bail!("failed to build the Rust library for the simulator");
+  }
+
+  let dylib = out_dir.join(format!("lib{lib_name}.dylib"));
+  if !dylib.exists() {
+    anyhow::bail!("Library not found at {}.", dylib.display());
+  }
+  Ok(dylib)
+}
+
+/// Relaunches the app on the booted simulator, loading the Rust code from the dylib.
+pub fn relaunch(bundle_identifier: &str, dylib: &Path) -> Result<SimulatorChild> {
+  log::info!(action = "Relaunching"; "{bundle_identifier} with the hot-swapped Rust library");
+  let mut command = Command::new("xcrun");
+  command
+    .args([
+      "simctl",
+      "launch",
+      "--console-pty",
+      "--terminate-running-process",
+      "booted",
+      bundle_identifier,
+    ])
+    // simctl forwards the variables prefixed by `SIMCTL_CHILD_` to the app
+    .env(format!("SIMCTL_CHILD_{DYLIB_ENV_VAR}"), dylib);
+  let child = SharedChild::spawn(&mut command).context("failed to launch the app with simctl")?;
+  Ok(SimulatorChild {
+    child: Arc::new(child),
+    manually_killed_process: Default::default(),
+  })
+}
@@ -42,6 +42,7 @@ use std::{
 
 mod build;
 mod dev;
+mod hot_swap;
 mod open;
 pub(crate) mod project;
 mod xcode_script;
@@ -359,6 +360,78 @@ fn merge_plist(src: Vec<PlistKind>, dest: &Path) -> Result<()> {
   Ok(())
 }
 
+/// Sets the entitlements generated from the Tauri configuration, i.e. the associated domains.
+fn update_entitlements(config: &AppleConfig, tauri_config: &TauriConfig) -> Result<()> {
+  const ASSOCIATED_DOMAINS: &str = "com.apple.developer.associated-domains";
+
+  let path = config
+    .project_dir()
+    .join(config.scheme())
+    .join(format!("{}.entitlements", config.scheme()));
+  let mut entitlements = if path.exists() {
+    plist::Value::from_file(&path)?
+  } else {
+    plist::Value::Dictionary(Default::default())
+  };
+  let Some(dict) = entitlements.as_dictionary_mut() else {
+    return Ok(());
+  };
+
+  let previous = dict.get(ASSOCIATED_DOMAINS).cloned();
+  let domains = &tauri_config.bundle.ios.associated_domains;
+  if domains.is_empty() {
+    dict.remove(ASSOCIATED_DOMAINS);
+  } else {
+    dict.insert(
+      ASSOCIATED_DOMAINS.into(),
+      plist::Value::Array(domains.iter().cloned().map(Into::into).collect()),
+    );
+  }
+
+  if dict.get(ASSOCIATED_DOMAINS) != previous.as_ref() {
+    entitlements.to_file_xml(&path)?;
+  }
+
+  Ok(())
+}
+
+/// Warns about the usage descriptions missing in the `Info.plist` file for the OS permissions of the capabilities,
+/// without which iOS terminates the app when the permission is requested.
+fn check_usage_descriptions(info_plist_path: &Path, tauri_config: &TauriConfig) -> Result<()> {
+  let os_permissions = crate::helpers::capabilities::os_permissions(
+    tauri_config,
+    &tauri_dir(),
+    tauri_utils::platform::Target::Ios,
+  )?;
+  if os_permissions.is_empty() {
+    return Ok(());
+  }
+
+  let info_plist = plist::Value::from_file(info_plist_path)?;
+  let missing = os_permissions
+    .iter()
+    .flat_map(|p| p.ios_usage_description_keys())
+    .filter(|key| {
+      info_plist
+        .as_dictionary()
+        .and_then(|dict| dict.get(key))
+        .is_none()
+    })
+    .collect::<Vec<_>>();
+  if !missing.is_empty() {
+    log::warn!(
+      "The capabilities require OS permissions but the Info.plist file is missing the {} usage descriptions, add them to the Info.ios.plist file.",
+      missing
+        .iter()
+        .map(|k| k.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+    );
+  }
+
+  Ok(())
+}
+
 pub fn signing_from_env() -> Result<(
   Option<tauri_macos_sign::Keychain>,
   Option<tauri_macos_sign::ProvisioningProfile>,
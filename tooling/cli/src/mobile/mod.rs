@@ -160,6 +160,8 @@ fn env_vars() -> HashMap<String, OsString> {
       && k != "TAURI_SIGNING_PRIVATE_KEY_PASSWORD")
       || k.starts_with("WRY")
       || k.starts_with("CARGO_")
+      || k.starts_with("SCCACHE_")
+      || k == "RUSTC_WRAPPER"
       || k == "TMPDIR"
       || k == "PATH"
     {
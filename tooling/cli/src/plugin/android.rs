@@ -8,6 +8,7 @@ use crate::{
 };
 use clap::{Parser, Subcommand};
 use handlebars::Handlebars;
+use heck::ToSnakeCase;
 
 use std::{
   collections::BTreeMap,
@@ -61,7 +62,7 @@ pub fn command(cli: Cli) -> Result<()> {
 
       let plugin_id = prompts::input(
         "What should be the Android Package ID for your plugin?",
-        Some(format!("com.plugin.{}", plugin_name)),
+        Some(format!("com.plugin.{}", plugin_name.to_snake_case())),
         false,
         false,
       )?
@@ -106,11 +107,7 @@ tauri-build = "{}"
 "#,
         metadata.tauri_build
       );
-      let build_file = super::init::TEMPLATE_DIR
-        .get_file("build.rs")
-        .unwrap()
-        .contents_utf8()
-        .unwrap();
+      let build_file = super::init::render_build_file(true, out_dir.join("ios").exists())?;
       let init_fn = format!(
         r#"
 pub fn init<R: Runtime>() -> TauriPlugin<R> {{
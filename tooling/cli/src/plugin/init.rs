@@ -57,6 +57,9 @@ pub struct Options {
   /// Type of framework to use for the iOS project.
   #[clap(long)]
   pub(crate) ios_framework: Option<IosFrameworkKind>,
+  /// Package ID of the Android project, e.g. `com.plugin.example`. Prompted if not set.
+  #[clap(long)]
+  pub(crate) android_package_id: Option<String>,
 }
 
 #[derive(Debug, Clone, ValueEnum)]
@@ -138,6 +141,7 @@ pub fn command(mut options: Options) -> Result<()> {
     data.insert("tauri_build_dep", to_json(tauri_build_dep));
     data.insert("tauri_plugin_dep", to_json(tauri_plugin_dep));
     data.insert("author", to_json(options.author));
+    data.insert("api", to_json(!options.no_api));
 
     if options.tauri {
       data.insert(
@@ -152,14 +156,30 @@ pub fn command(mut options: Options) -> Result<()> {
       );
     }
 
-    let plugin_id = if options.android || options.mobile {
-      let plugin_id = prompts::input(
-        "What should be the Android Package ID for your plugin?",
-        Some(format!("com.plugin.{}", plugin_name)),
-        false,
-        false,
-      )?
-      .unwrap();
+    let android = options.android || options.mobile;
+    let ios = options.ios || options.mobile;
+    data.insert("android", to_json(android));
+    data.insert("ios", to_json(ios));
+    // the Rust implementation calls the Kotlin or Swift plugin on these targets
+    let native_cfg = match (android, ios) {
+      (true, true) => Some("mobile"),
+      (true, false) => Some(r#"target_os = "android""#),
+      (false, true) => Some(r#"target_os = "ios""#),
+      (false, false) => None,
+    };
+    data.insert("native_cfg", to_json(native_cfg));
+
+    let plugin_id = if android {
+      let plugin_id = match options.android_package_id {
+        Some(id) => id,
+        None => prompts::input(
+          "What should be the Android Package ID for your plugin?",
+          Some(format!("com.plugin.{}", plugin_name.to_snake_case())),
+          false,
+          false,
+        )?
+        .unwrap(),
+      };
 
       data.insert("android_package_id", to_json(&plugin_id));
       Some(plugin_id)
@@ -196,7 +216,7 @@ pub fn command(mut options: Options) -> Result<()> {
               }
             }
             "android" => {
-              if options.android || options.mobile {
+              if android {
                 return generate_android_out_file(
                   &path,
                   &template_target_path,
@@ -207,7 +227,7 @@ pub fn command(mut options: Options) -> Result<()> {
                 return Ok(None);
               }
             }
-            "ios-spm" | "ios-xcode" if !(options.ios || options.mobile) => return Ok(None),
+            "ios-spm" | "ios-xcode" if !ios => return Ok(None),
             "ios-spm" if !matches!(ios_framework, IosFrameworkKind::Spm) => return Ok(None),
             "ios-xcode" if !matches!(ios_framework, IosFrameworkKind::Xcode) => return Ok(None),
             "ios-spm" | "ios-xcode" => {
@@ -228,6 +248,7 @@ pub fn command(mut options: Options) -> Result<()> {
             {
               return Ok(None);
             }
+            "src" if native_cfg.is_none() && path.ends_with("mobile.rs") => return Ok(None),
             _ => (),
           }
         }
@@ -244,17 +265,6 @@ pub fn command(mut options: Options) -> Result<()> {
     .with_context(|| "failed to render plugin template")?;
   }
 
-  let permissions_dir = template_target_path.join("permissions");
-  std::fs::create_dir(&permissions_dir)
-    .with_context(|| "failed to create `permissions` directory")?;
-
-  let default_permissions = r#"[default]
-description = "Default permissions for the plugin"
-permissions = ["allow-ping"]
-"#;
-  std::fs::write(permissions_dir.join("default.toml"), default_permissions)
-    .with_context(|| "failed to write `permissions/default.toml`")?;
-
   Ok(())
 }
 
@@ -271,6 +281,23 @@ pub fn plugin_name_data(data: &mut BTreeMap<&'static str, serde_json::Value>, pl
   );
 }
 
+/// Renders the plugin `build.rs` file for the given mobile projects.
+pub fn render_build_file(android: bool, ios: bool) -> Result<String> {
+  let template = TEMPLATE_DIR
+    .get_file("build.rs")
+    .unwrap()
+    .contents_utf8()
+    .unwrap();
+  let mut handlebars = Handlebars::new();
+  handlebars.register_escape_fn(handlebars::no_escape);
+  handlebars
+    .render_template(
+      template,
+      &BTreeMap::from([("android", android), ("ios", ios)]),
+    )
+    .map_err(Into::into)
+}
+
 pub fn crates_metadata() -> Result<VersionMetadata> {
   serde_json::from_str::<VersionMetadata>(include_str!("../../metadata-v2.json"))
     .map_err(Into::into)
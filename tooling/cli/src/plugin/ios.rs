@@ -5,6 +5,7 @@
 use crate::{helpers::template, Result};
 use clap::{Parser, Subcommand};
 use handlebars::Handlebars;
+use heck::ToSnakeCase;
 
 use std::{
   collections::BTreeMap,
@@ -72,8 +73,8 @@ pub fn command(cli: Cli) -> Result<()> {
           let mut components = path.components();
           let root = components.next().unwrap();
           if let Component::Normal(component) = root {
-            if component == OsStr::new("ios") {
-              let path = out_dir.join(path);
+            if component == OsStr::new("ios-spm") {
+              let path = out_dir.join("ios").join(components.as_path());
               let parent = path.parent().unwrap().to_path_buf();
               if !created_dirs.contains(&parent) {
                 create_dir_all(&parent)?;
@@ -96,27 +97,24 @@ tauri-build = "{}"
 "#,
         metadata.tauri_build
       );
-      let build_file = super::init::TEMPLATE_DIR
-        .get_file("build.rs")
-        .unwrap()
-        .contents_utf8()
-        .unwrap();
+      let build_file = super::init::render_build_file(out_dir.join("android").exists(), true)?;
       let init_fn = format!(
         r#"
 #[cfg(target_os = "ios")]
-tauri::ios_plugin_binding!(init_plugin_{name});
+tauri::ios_plugin_binding!(init_plugin_{binding});
 
 pub fn init<R: Runtime>() -> TauriPlugin<R> {{
   Builder::new("{name}")
-    .setup(|app| {{
+    .setup(|app, api| {{
       #[cfg(target_os = "ios")]
-      app.register_ios_plugin(init_plugin_{name})?;
+      let handle = api.register_ios_plugin(init_plugin_{binding})?;
       Ok(())
     }})
     .build()
 }}
 "#,
         name = plugin_name,
+        binding = plugin_name.to_snake_case(),
       );
 
       log::info!("iOS project added");
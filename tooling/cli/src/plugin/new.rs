@@ -7,7 +7,10 @@ use clap::Parser;
 use std::path::PathBuf;
 
 #[derive(Debug, Parser)]
-#[clap(about = "Initializes a new Tauri plugin project")]
+#[clap(
+  about = "Initializes a new Tauri plugin project",
+  long_about = "Initializes a new Tauri plugin project with its Rust crate, permissions, TypeScript API, Android and iOS projects and an example app. Use `--android` or `--ios` to only scaffold one of the mobile projects, or `--no-mobile` to skip them."
+)]
 pub struct Options {
   /// Name of your Tauri plugin
   plugin_name: String,
@@ -32,12 +35,18 @@ pub struct Options {
   /// Whether to initialize an iOS project for the plugin.
   #[clap(long)]
   ios: bool,
-  /// Whether to initialize Android and iOS projects for the plugin.
+  /// Whether to initialize Android and iOS projects for the plugin. This is the default unless `--android` or `--ios` is set.
   #[clap(long)]
   mobile: bool,
+  /// Skip the Android and iOS projects.
+  #[clap(long, conflicts_with_all = ["android", "ios", "mobile"])]
+  no_mobile: bool,
   /// Type of framework to use for the iOS project.
   #[clap(long)]
   pub(crate) ios_framework: Option<super::init::IosFrameworkKind>,
+  /// Package ID of the Android project, e.g. `com.plugin.example`. Prompted if not set.
+  #[clap(long)]
+  android_package_id: Option<String>,
 }
 
 impl From<Options> for super::init::Options {
@@ -51,8 +60,9 @@ impl From<Options> for super::init::Options {
       author: o.author,
       android: o.android,
       ios: o.ios,
-      mobile: o.mobile,
+      mobile: o.mobile || !(o.android || o.ios || o.no_mobile),
       ios_framework: o.ios_framework,
+      android_package_id: o.android_package_id,
     }
   }
 }
@@ -0,0 +1,176 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Upload to the assets of a GitHub release.
+
+use std::io::Read;
+
+use anyhow::Context;
+use serde::Deserialize;
+
+use super::{Backend, UpdateManifest, MANIFEST_NAME};
+use crate::Result;
+
+const API_URL: &str = "https://api.github.com";
+const UPLOADS_URL: &str = "https://uploads.github.com";
+
+#[derive(Debug, Deserialize)]
+struct Release {
+  id: u64,
+  tag_name: String,
+  assets: Vec<Asset>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Asset {
+  id: u64,
+  name: String,
+  url: String,
+}
+
+pub struct GitHub {
+  owner: String,
+  repo: String,
+  tag: String,
+  draft: bool,
+  prerelease: bool,
+  release: Option<Release>,
+}
+
+impl GitHub {
+  pub fn new(owner: &str, repo: &str, tag: &str, draft: bool, prerelease: bool) -> Self {
+    Self {
+      owner: owner.into(),
+      repo: repo.into(),
+      tag: tag.into(),
+      draft,
+      prerelease,
+      release: None,
+    }
+  }
+
+  fn request(&self, method: &str, url: &str) -> Result<ureq::Request> {
+    let token = std::env::var("GITHUB_TOKEN")
+      .context("the `GITHUB_TOKEN` environment variable is required to publish to GitHub")?;
+    Ok(
+      ureq::request(method, url)
+        .set("Authorization", &format!("Bearer {token}"))
+        .set("Accept", "application/vnd.github+json")
+        .set("X-GitHub-Api-Version", "2022-11-28")
+        .set("User-Agent", "tauri-cli"),
+    )
+  }
+
+  /// Finds the release of the tag, creating it if needed.
+  ///
+  /// The releases are listed instead of fetched by tag so the draft releases are found too.
+  fn release(&mut self) -> Result<&mut Release> {
+    if self.release.is_none() {
+      let url = format!(
+        "{API_URL}/repos/{}/{}/releases?per_page=100",
+        self.owner, self.repo
+      );
+      let releases: Vec<Release> =
+        serde_json::from_str(&self.request("GET", &url)?.call()?.into_string()?)?;
+      let release = match releases
+        .into_iter()
+        .find(|release| release.tag_name == self.tag)
+      {
+        Some(release) => release,
+        None => {
+          log::info!(action = "Creating"; "GitHub release {}", self.tag);
+          let url = format!("{API_URL}/repos/{}/{}/releases", self.owner, self.repo);
+          let body = serde_json::json!({
+            "tag_name": self.tag,
+            "name": self.tag,
+            "draft": self.draft,
+            "prerelease": self.prerelease,
+          });
+          serde_json::from_str(
+            &self
+              .request("POST", &url)?
+              .send_string(&body.to_string())?
+              .into_string()?,
+          )?
+        }
+      };
+      self.release.replace(release);
+    }
+    Ok(self.release.as_mut().unwrap())
+  }
+}
+
+/// The name GitHub gives to an uploaded asset.
+fn asset_name(file_name: &str) -> String {
+  file_name.replace(' ', ".")
+}
+
+impl Backend for GitHub {
+  fn url(&self, file_name: &str) -> String {
+    format!(
+      "https://github.com/{}/{}/releases/download/{}/{}",
+      self.owner,
+      self.repo,
+      self.tag,
+      asset_name(file_name)
+    )
+  }
+
+  fn read_manifest(&mut self) -> Result<Option<UpdateManifest>> {
+    let Some(url) = self
+      .release()?
+      .assets
+      .iter()
+      .find(|asset| asset.name == MANIFEST_NAME)
+      .map(|asset| asset.url.clone())
+    else {
+      return Ok(None);
+    };
+    let mut manifest = Vec::new();
+    self
+      .request("GET", &url)?
+      .set("Accept", "application/octet-stream")
+      .call()?
+      .into_reader()
+      .read_to_end(&mut manifest)?;
+    Ok(serde_json::from_slice(&manifest).ok())
+  }
+
+  fn upload(&mut self, file_name: &str, data: Vec<u8>, content_type: &str) -> Result<()> {
+    let name = asset_name(file_name);
+    let (owner, repo) = (self.owner.clone(), self.repo.clone());
+    let release = self.release()?;
+    let release_id = release.id;
+    let existing = release
+      .assets
+      .iter()
+      .position(|asset| asset.name == name)
+      .map(|index| release.assets.remove(index));
+
+    // a release can't have two assets with the same name
+    if let Some(asset) = existing {
+      let url = format!(
+        "{API_URL}/repos/{owner}/{repo}/releases/assets/{}",
+        asset.id
+      );
+      self.request("DELETE", &url)?.call()?;
+    }
+
+    let mut url = url::Url::parse(&format!(
+      "{UPLOADS_URL}/repos/{owner}/{repo}/releases/{release_id}/assets"
+    ))?;
+    url.query_pairs_mut().append_pair("name", &name);
+    let asset: Asset = serde_json::from_str(
+      &self
+        .request("POST", url.as_str())?
+        .set("Content-Type", content_type)
+        .send_bytes(&data)?
+        .into_string()?,
+    )?;
+    if let Some(release) = &mut self.release {
+      release.assets.push(asset);
+    }
+    Ok(())
+  }
+}
@@ -0,0 +1,101 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Upload to a generic HTTP server.
+
+use std::collections::HashMap;
+
+use url::Url;
+
+use super::{Backend, UpdateManifest, MANIFEST_NAME};
+use crate::Result;
+
+pub struct Http {
+  url: Url,
+  method: String,
+  headers: HashMap<String, String>,
+  public_url: Url,
+  token: Option<String>,
+}
+
+impl Http {
+  pub fn new(
+    url: &Url,
+    method: Option<&str>,
+    headers: &HashMap<String, String>,
+    public_url: Option<&Url>,
+  ) -> Result<Self> {
+    if url.cannot_be_a_base() {
+      anyhow::bail!("invalid publish URL {url}");
+    }
+    Ok(Self {
+      url: url.clone(),
+      method: method.unwrap_or("PUT").to_uppercase(),
+      headers: headers.clone(),
+      public_url: public_url.unwrap_or(url).clone(),
+      token: std::env::var("TAURI_PUBLISH_TOKEN")
+        .ok()
+        .filter(|token| !token.is_empty()),
+    })
+  }
+}
+
+/// The URL of the file in the directory URL.
+fn file_url(base: &Url, file_name: &str) -> Url {
+  let mut url = base.clone();
+  if let Ok(mut segments) = url.path_segments_mut() {
+    segments.pop_if_empty().push(file_name);
+  }
+  url
+}
+
+impl Backend for Http {
+  fn url(&self, file_name: &str) -> String {
+    file_url(&self.public_url, file_name).into()
+  }
+
+  fn read_manifest(&mut self) -> Result<Option<UpdateManifest>> {
+    let url = file_url(&self.public_url, MANIFEST_NAME);
+    match ureq::get(url.as_str()).call() {
+      Ok(response) => Ok(serde_json::from_str(&response.into_string()?).ok()),
+      Err(ureq::Error::Status(status, _)) => {
+        log::debug!("no published {MANIFEST_NAME} at {url}, status {status}");
+        Ok(None)
+      }
+      Err(e) => Err(e.into()),
+    }
+  }
+
+  fn upload(&mut self, file_name: &str, data: Vec<u8>, content_type: &str) -> Result<()> {
+    let mut request = ureq::request(&self.method, file_url(&self.url, file_name).as_str())
+      .set("Content-Type", content_type);
+    if let Some(token) = &self.token {
+      request = request.set("Authorization", &format!("Bearer {token}"));
+    }
+    for (name, value) in &self.headers {
+      request = request.set(name, value);
+    }
+    request.send_bytes(&data)?;
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn file_urls() {
+    let base = Url::parse("https://releases.example.com/app/").unwrap();
+    assert_eq!(
+      file_url(&base, "My App_1.0.0_x64-setup.exe").as_str(),
+      "https://releases.example.com/app/My%20App_1.0.0_x64-setup.exe"
+    );
+    let base = Url::parse("https://releases.example.com/app").unwrap();
+    assert_eq!(
+      file_url(&base, MANIFEST_NAME).as_str(),
+      "https://releases.example.com/app/latest.json"
+    );
+  }
+}
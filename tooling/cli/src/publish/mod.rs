@@ -0,0 +1,532 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Upload of the bundled artifacts and of the updater manifest to the destinations of `bundle > publish`.
+
+use std::{
+  collections::{BTreeMap, HashMap, HashSet},
+  path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+use clap::Parser;
+use serde::{Deserialize, Serialize};
+use tauri_utils::{
+  config::{BundleType, PublishTarget, Updater},
+  platform::Target,
+};
+use time::{format_description::well_known::Rfc3339, OffsetDateTime};
+
+use crate::{
+  helpers::{app_paths::tauri_dir, config::get as get_config},
+  interface::{AppInterface, AppSettings, Interface},
+  ConfigValue, Result,
+};
+
+mod github;
+mod http;
+mod s3;
+
+/// The file name of the updater manifest.
+const MANIFEST_NAME: &str = "latest.json";
+
+/// The updater artifacts of a bundle type.
+struct UpdaterBundle {
+  /// The bundle type producing the artifacts.
+  bundle_type: BundleType,
+  /// The bundle directory containing the artifacts.
+  dir: &'static str,
+  /// The updater OS name.
+  os: &'static str,
+  /// The updater installer name.
+  installer: &'static str,
+  /// The file name suffix of the artifacts.
+  suffix: &'static str,
+  /// The file name suffix of the artifacts with `bundle > createUpdaterArtifacts` set to `v1Compatible`.
+  v1_compatible_suffix: &'static str,
+}
+
+/// The bundles producing updater artifacts, the ones signed by `tauri build`.
+///
+/// The first artifact of an OS in this order is also used for the platform key without installer.
+const UPDATER_BUNDLES: &[UpdaterBundle] = &[
+  UpdaterBundle {
+    bundle_type: BundleType::App,
+    dir: "macos",
+    os: "darwin",
+    installer: "app",
+    suffix: ".app.tar.gz",
+    v1_compatible_suffix: ".app.tar.gz",
+  },
+  UpdaterBundle {
+    bundle_type: BundleType::Nsis,
+    dir: "nsis",
+    os: "windows",
+    installer: "nsis",
+    suffix: ".exe",
+    v1_compatible_suffix: ".nsis.zip",
+  },
+  UpdaterBundle {
+    bundle_type: BundleType::Msi,
+    dir: "msi",
+    os: "windows",
+    installer: "msi",
+    suffix: ".msi",
+    v1_compatible_suffix: ".msi.zip",
+  },
+  UpdaterBundle {
+    bundle_type: BundleType::AppImage,
+    dir: "appimage",
+    os: "linux",
+    installer: "appimage",
+    suffix: ".AppImage",
+    v1_compatible_suffix: ".AppImage.tar.gz",
+  },
+];
+
+#[derive(Debug, Clone, Parser)]
+#[clap(
+  about = "Upload the bundled app and its updater manifest",
+  long_about = "Upload the app bundles (already built by `tauri build`) to the destinations of `bundle > publish`, along with the updater manifest `latest.json` listing the signed updater artifacts of each platform. The manifest is merged with the one already published for the same version, so the bundles of each platform can be published from a different machine."
+)]
+pub struct Options {
+  /// Publish the bundles built with the debug flag
+  #[clap(short, long)]
+  pub debug: bool,
+  /// Target triple the bundles were built against
+  #[clap(short, long)]
+  pub target: Option<String>,
+  /// The release notes of the updater manifest
+  #[clap(long, conflicts_with = "notes_file")]
+  pub notes: Option<String>,
+  /// Path to a file containing the release notes of the updater manifest
+  #[clap(long)]
+  pub notes_file: Option<PathBuf>,
+  /// Print what would be uploaded without uploading anything
+  #[clap(long)]
+  pub dry_run: bool,
+  /// JSON string or path to JSON file to merge with tauri.conf.json
+  #[clap(short, long)]
+  pub config: Option<ConfigValue>,
+}
+
+/// A bundled updater artifact along with its signature.
+#[derive(Debug)]
+struct Artifact {
+  path: PathBuf,
+  signature: String,
+  /// The keys of the updater manifest platforms updated with this artifact.
+  platforms: Vec<String>,
+}
+
+impl Artifact {
+  fn file_name(&self) -> String {
+    self
+      .path
+      .file_name()
+      .unwrap()
+      .to_string_lossy()
+      .into_owned()
+  }
+}
+
+/// The updater manifest read by the updater plugin.
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UpdateManifest {
+  pub version: String,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub notes: Option<String>,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub pub_date: Option<String>,
+  #[serde(default)]
+  pub platforms: BTreeMap<String, PlatformManifest>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlatformManifest {
+  pub signature: String,
+  pub url: String,
+}
+
+impl UpdateManifest {
+  /// Adds the platforms of the manifest already published for the same version.
+  fn merge(mut self, published: Option<UpdateManifest>) -> Self {
+    if let Some(published) = published.filter(|published| published.version == self.version) {
+      for (key, platform) in published.platforms {
+        self.platforms.entry(key).or_insert(platform);
+      }
+      if self.notes.is_none() {
+        self.notes = published.notes;
+      }
+    }
+    self
+  }
+}
+
+/// A destination of the published files.
+trait Backend {
+  /// The URL the uploaded file is downloaded from.
+  fn url(&self, file_name: &str) -> String;
+  /// Reads the published updater manifest, if any.
+  fn read_manifest(&mut self) -> Result<Option<UpdateManifest>>;
+  fn upload(&mut self, file_name: &str, data: Vec<u8>, content_type: &str) -> Result<()>;
+}
+
+fn backend(target: &PublishTarget, version: &str) -> Result<Box<dyn Backend>> {
+  let backend: Box<dyn Backend> = match target {
+    PublishTarget::GitHub {
+      owner,
+      repo,
+      tag,
+      draft,
+      prerelease,
+    } => Box::new(github::GitHub::new(
+      owner,
+      repo,
+      &tag
+        .as_deref()
+        .unwrap_or("v{version}")
+        .replace("{version}", version),
+      *draft,
+      *prerelease,
+    )),
+    PublishTarget::S3 {
+      bucket,
+      region,
+      endpoint,
+      prefix,
+      public_url,
+    } => Box::new(s3::S3::new(
+      bucket,
+      region.as_deref(),
+      endpoint.as_ref(),
+      &prefix
+        .as_deref()
+        .unwrap_or_default()
+        .replace("{version}", version),
+      public_url.as_ref(),
+    )),
+    PublishTarget::Http {
+      url,
+      method,
+      headers,
+      public_url,
+    } => Box::new(http::Http::new(
+      url,
+      method.as_deref(),
+      headers,
+      public_url.as_ref(),
+    )?),
+  };
+  Ok(backend)
+}
+
+fn target_name(target: &PublishTarget) -> String {
+  match target {
+    PublishTarget::GitHub { owner, repo, .. } => format!("GitHub release of {owner}/{repo}"),
+    PublishTarget::S3 { bucket, .. } => format!("S3 bucket {bucket}"),
+    PublishTarget::Http { url, .. } => url.to_string(),
+  }
+}
+
+pub fn command(options: Options) -> Result<()> {
+  let target = options
+    .target
+    .as_deref()
+    .map(Target::from_triple)
+    .unwrap_or_else(Target::current);
+  let config = get_config(target, options.config.as_ref().map(|c| &c.0))?;
+  let interface = AppInterface::new(
+    config.lock().unwrap().as_ref().unwrap(),
+    options.target.clone(),
+  )?;
+  std::env::set_current_dir(tauri_dir())
+    .with_context(|| "failed to change current working directory")?;
+
+  let config_guard = config.lock().unwrap();
+  let config_ = config_guard.as_ref().unwrap();
+  if config_.bundle.publish.is_empty() {
+    anyhow::bail!("No publish destination configured, add one to `bundle > publish`");
+  }
+
+  let app_settings = interface.app_settings();
+  let interface_options = crate::interface::Options {
+    debug: options.debug,
+    target: options.target.clone(),
+    config: options.config.clone(),
+    ..Default::default()
+  };
+  let bin_path = app_settings.app_binary_path(&interface_options)?;
+  let bundle_dir = bin_path.parent().unwrap().join("bundle");
+  let version = app_settings.get_package_settings().version;
+
+  let v1_compatible = match &config_.bundle.create_updater_artifacts {
+    Updater::Bool(false) => anyhow::bail!(
+      "No updater artifact to publish, enable `bundle > createUpdaterArtifacts` and build the app first"
+    ),
+    Updater::Bool(true) => false,
+    Updater::String(_) => true,
+  };
+  let artifacts = find_artifacts(
+    &bundle_dir,
+    &arches(options.target.as_deref()),
+    &config_.bundle.targets.to_vec(),
+    v1_compatible,
+  )?;
+  if artifacts.is_empty() {
+    anyhow::bail!(
+      "No signed updater artifact found in {}, build the app with `bundle > createUpdaterArtifacts` enabled first",
+      tauri_utils::display_path(&bundle_dir)
+    );
+  }
+
+  let notes = match &options.notes_file {
+    Some(path) => Some(
+      std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read release notes {}", path.display()))?,
+    ),
+    None => options.notes.clone(),
+  };
+  let pub_date = OffsetDateTime::now_utc().format(&Rfc3339)?;
+
+  for target in &config_.bundle.publish {
+    let name = target_name(target);
+    let mut backend = backend(target, &version)?;
+
+    let mut manifest = UpdateManifest {
+      version: version.clone(),
+      notes: notes.clone(),
+      pub_date: Some(pub_date.clone()),
+      platforms: BTreeMap::new(),
+    };
+    for artifact in &artifacts {
+      let file_name = artifact.file_name();
+      if options.dry_run {
+        log::info!(action = "Skipping"; "upload of {} to {} (dry run)", file_name, name);
+      } else {
+        log::info!(action = "Uploading"; "{} to {}", file_name, name);
+        let data = std::fs::read(&artifact.path)
+          .with_context(|| format!("failed to read {}", artifact.path.display()))?;
+        backend
+          .upload(&file_name, data, "application/octet-stream")
+          .with_context(|| format!("failed to upload {file_name} to {name}"))?;
+      }
+      for platform in &artifact.platforms {
+        manifest.platforms.insert(
+          platform.clone(),
+          PlatformManifest {
+            signature: artifact.signature.clone(),
+            url: backend.url(&file_name),
+          },
+        );
+      }
+    }
+
+    if options.dry_run {
+      log::info!(
+        "{} of {}:\n{}",
+        MANIFEST_NAME,
+        name,
+        serde_json::to_string_pretty(&manifest)?
+      );
+      continue;
+    }
+
+    let published = backend
+      .read_manifest()
+      .with_context(|| format!("failed to read the {MANIFEST_NAME} of {name}"))?;
+    let manifest = manifest.merge(published);
+    log::info!(action = "Uploading"; "{} to {}", MANIFEST_NAME, name);
+    backend
+      .upload(
+        MANIFEST_NAME,
+        serde_json::to_vec_pretty(&manifest)?,
+        "application/json",
+      )
+      .with_context(|| format!("failed to upload {MANIFEST_NAME} to {name}"))?;
+    log::info!(action = "Published"; "{} to {}", version, backend.url(MANIFEST_NAME));
+  }
+
+  Ok(())
+}
+
+/// The updater architecture names of the target, two for universal macOS apps.
+fn arches(target: Option<&str>) -> Vec<&'static str> {
+  let arch = match target {
+    Some("universal-apple-darwin") => return vec!["x86_64", "aarch64"],
+    Some(target) => target.split('-').next().unwrap_or_default(),
+    None => std::env::consts::ARCH,
+  };
+  match arch {
+    "x86_64" => vec!["x86_64"],
+    "aarch64" => vec!["aarch64"],
+    "x86" | "i686" | "i586" => vec!["i686"],
+    arch if arch.starts_with("arm") => vec!["armv7"],
+    _ => Vec::new(),
+  }
+}
+
+/// Finds the signed updater artifacts of the configured bundle types, which are the ones published for the updater.
+///
+/// Fails if several artifacts would be published for the same platform, e.g. the MSI installers of several languages.
+fn find_artifacts(
+  bundle_dir: &Path,
+  arches: &[&str],
+  bundle_types: &[BundleType],
+  v1_compatible: bool,
+) -> Result<Vec<Artifact>> {
+  let mut artifacts = Vec::new();
+  let mut generic_platforms = HashSet::new();
+  let mut installer_platforms = HashMap::new();
+
+  for bundle in UPDATER_BUNDLES
+    .iter()
+    .filter(|bundle| bundle_types.contains(&bundle.bundle_type))
+  {
+    let Ok(entries) = std::fs::read_dir(bundle_dir.join(bundle.dir)) else {
+      continue;
+    };
+    let suffix = if v1_compatible {
+      bundle.v1_compatible_suffix
+    } else {
+      bundle.suffix
+    };
+    let mut paths = entries
+      .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+      .filter(|path| {
+        path.is_file()
+          && path
+            .file_name()
+            .map_or(false, |name| name.to_string_lossy().ends_with(suffix))
+      })
+      .collect::<Vec<_>>();
+    paths.sort();
+
+    for path in paths {
+      let mut signature_path = path.clone().into_os_string();
+      signature_path.push(".sig");
+      let Ok(signature) = std::fs::read_to_string(&signature_path) else {
+        continue;
+      };
+
+      let (os, installer) = (bundle.os, bundle.installer);
+      let mut platforms = Vec::new();
+      for arch in arches {
+        let platform = format!("{os}-{arch}-{installer}");
+        if let Some(other) = installer_platforms.insert(platform.clone(), path.clone()) {
+          anyhow::bail!(
+            "Found several updater artifacts for the `{platform}` platform: {} and {}, remove the ones that must not be published",
+            tauri_utils::display_path(other),
+            tauri_utils::display_path(&path)
+          );
+        }
+        if generic_platforms.insert(format!("{os}-{arch}")) {
+          platforms.push(format!("{os}-{arch}"));
+        }
+        platforms.push(platform);
+      }
+      artifacts.push(Artifact {
+        path,
+        signature: signature.trim().to_string(),
+        platforms,
+      });
+    }
+  }
+
+  Ok(artifacts)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn platform_keys() {
+    let dir = std::env::temp_dir().join("tauri-publish-artifacts-test");
+    let _ = std::fs::remove_dir_all(&dir);
+    for (path, signature) in [
+      ("nsis/app_1.0.0_x64-setup.exe", Some("nsis-signature\n")),
+      ("nsis/app_1.0.0_x64-setup.nsis.zip", Some("v1-signature")),
+      ("msi/app_1.0.0_x64_en-US.msi", Some("msi-signature")),
+      ("msi/app_1.0.0_x64_fr-FR.msi", None),
+      ("deb/app_1.0.0_amd64.deb", Some("deb-signature")),
+    ] {
+      let path = dir.join(path);
+      std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+      std::fs::write(&path, "").unwrap();
+      if let Some(signature) = signature {
+        std::fs::write(format!("{}.sig", path.display()), signature).unwrap();
+      }
+    }
+
+    let artifacts = find_artifacts(
+      &dir,
+      &["x86_64"],
+      &[BundleType::Nsis, BundleType::Msi, BundleType::Deb],
+      false,
+    )
+    .unwrap();
+    assert_eq!(artifacts.len(), 2);
+    assert_eq!(artifacts[0].file_name(), "app_1.0.0_x64-setup.exe");
+    assert_eq!(artifacts[0].signature, "nsis-signature");
+    assert_eq!(
+      artifacts[0].platforms,
+      vec!["windows-x86_64", "windows-x86_64-nsis"]
+    );
+    assert_eq!(artifacts[1].platforms, vec!["windows-x86_64-msi"]);
+
+    // only the configured bundle types and the v1 compatible archives
+    let artifacts = find_artifacts(&dir, &["x86_64"], &[BundleType::Nsis], true).unwrap();
+    assert_eq!(artifacts.len(), 1);
+    assert_eq!(artifacts[0].file_name(), "app_1.0.0_x64-setup.nsis.zip");
+    assert_eq!(
+      artifacts[0].platforms,
+      vec!["windows-x86_64", "windows-x86_64-nsis"]
+    );
+
+    // a single artifact can be published per platform
+    std::fs::write(dir.join("msi/app_1.0.0_x64_fr-FR.msi.sig"), "signature").unwrap();
+    assert!(find_artifacts(&dir, &["x86_64"], &[BundleType::Msi], false).is_err());
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(
+      arches(Some("universal-apple-darwin")),
+      vec!["x86_64", "aarch64"]
+    );
+    assert_eq!(arches(Some("armv7-unknown-linux-gnueabihf")), vec!["armv7"]);
+    assert_eq!(arches(Some("i686-pc-windows-msvc")), vec!["i686"]);
+  }
+
+  #[test]
+  fn merge_published_manifest() {
+    let platform = |url: &str| PlatformManifest {
+      signature: "signature".into(),
+      url: url.into(),
+    };
+    let manifest = |version: &str, platforms: &[(&str, &str)]| UpdateManifest {
+      version: version.into(),
+      notes: None,
+      pub_date: None,
+      platforms: platforms
+        .iter()
+        .map(|(key, url)| (key.to_string(), platform(url)))
+        .collect(),
+    };
+
+    let published = manifest(
+      "1.0.0",
+      &[
+        ("darwin-aarch64", "app.tar.gz"),
+        ("windows-x86_64", "old.exe"),
+      ],
+    );
+    let merged = manifest("1.0.0", &[("windows-x86_64", "setup.exe")]).merge(Some(published));
+    assert_eq!(merged.platforms["darwin-aarch64"], platform("app.tar.gz"));
+    assert_eq!(merged.platforms["windows-x86_64"], platform("setup.exe"));
+
+    let previous = manifest("0.9.0", &[("darwin-aarch64", "app.tar.gz")]);
+    let merged = manifest("1.0.0", &[("windows-x86_64", "setup.exe")]).merge(Some(previous));
+    assert_eq!(merged.platforms.len(), 1);
+  }
+}
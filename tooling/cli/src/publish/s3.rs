@@ -0,0 +1,261 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Upload to an S3 bucket, with requests signed with [AWS Signature Version 4](https://docs.aws.amazon.com/AmazonS3/latest/API/sig-v4-authenticating-requests.html).
+
+use anyhow::Context;
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+use time::OffsetDateTime;
+use url::Url;
+
+use super::{Backend, UpdateManifest, MANIFEST_NAME};
+use crate::Result;
+
+const DEFAULT_REGION: &str = "us-east-1";
+/// The payload is not part of the signature so the artifacts don't have to be hashed.
+const UNSIGNED_PAYLOAD: &str = "UNSIGNED-PAYLOAD";
+
+struct Credentials {
+  access_key_id: String,
+  secret_access_key: String,
+  session_token: Option<String>,
+}
+
+impl Credentials {
+  fn from_env() -> Result<Self> {
+    Ok(Self {
+      access_key_id: std::env::var("AWS_ACCESS_KEY_ID")
+        .context("the `AWS_ACCESS_KEY_ID` environment variable is required to publish to S3")?,
+      secret_access_key: std::env::var("AWS_SECRET_ACCESS_KEY")
+        .context("the `AWS_SECRET_ACCESS_KEY` environment variable is required to publish to S3")?,
+      session_token: std::env::var("AWS_SESSION_TOKEN").ok(),
+    })
+  }
+}
+
+pub struct S3 {
+  /// The scheme and host of the bucket requests.
+  origin: String,
+  /// The path of the bucket, empty for virtual-hosted buckets.
+  bucket_path: String,
+  host: String,
+  region: String,
+  prefix: String,
+  public_url: Option<Url>,
+}
+
+impl S3 {
+  pub fn new(
+    bucket: &str,
+    region: Option<&str>,
+    endpoint: Option<&Url>,
+    prefix: &str,
+    public_url: Option<&Url>,
+  ) -> Self {
+    let region = region.unwrap_or(DEFAULT_REGION).to_string();
+    // the S3-compatible storages don't all support virtual-hosted buckets, so they use path-style requests
+    let (origin, host, bucket_path) = match endpoint {
+      Some(endpoint) => {
+        let host = match (endpoint.host_str(), endpoint.port()) {
+          (Some(host), Some(port)) => format!("{host}:{port}"),
+          (host, None) => host.unwrap_or_default().to_string(),
+          (None, Some(_)) => String::new(),
+        };
+        (
+          format!("{}://{host}", endpoint.scheme()),
+          host,
+          format!("/{}", uri_encode(bucket, true)),
+        )
+      }
+      None => {
+        let host = format!("{bucket}.s3.{region}.amazonaws.com");
+        (format!("https://{host}"), host, String::new())
+      }
+    };
+    Self {
+      origin,
+      bucket_path,
+      host,
+      region,
+      prefix: prefix.trim_matches('/').to_string(),
+      public_url: public_url.cloned(),
+    }
+  }
+
+  /// The encoded object key of the file.
+  fn key(&self, file_name: &str) -> String {
+    let file_name = uri_encode(file_name, true);
+    if self.prefix.is_empty() {
+      file_name
+    } else {
+      format!("{}/{file_name}", uri_encode(&self.prefix, false))
+    }
+  }
+
+  fn request(&self, method: &str, file_name: &str) -> Result<ureq::Request> {
+    let credentials = Credentials::from_env()?;
+    let path = format!("{}/{}", self.bucket_path, self.key(file_name));
+    let now = OffsetDateTime::now_utc();
+    let date = format!(
+      "{:04}{:02}{:02}",
+      now.year(),
+      u8::from(now.month()),
+      now.day()
+    );
+    let amz_date = format!(
+      "{date}T{:02}{:02}{:02}Z",
+      now.hour(),
+      now.minute(),
+      now.second()
+    );
+
+    let mut headers = vec![
+      ("host", self.host.clone()),
+      ("x-amz-content-sha256", UNSIGNED_PAYLOAD.to_string()),
+      ("x-amz-date", amz_date.clone()),
+    ];
+    if let Some(token) = &credentials.session_token {
+      headers.push(("x-amz-security-token", token.clone()));
+    }
+    let signed_headers = headers
+      .iter()
+      .map(|(name, _)| *name)
+      .collect::<Vec<_>>()
+      .join(";");
+    let canonical_headers = headers
+      .iter()
+      .map(|(name, value)| format!("{name}:{}\n", value.trim()))
+      .collect::<String>();
+    let canonical_request =
+      format!("{method}\n{path}\n\n{canonical_headers}\n{signed_headers}\n{UNSIGNED_PAYLOAD}");
+
+    let scope = format!("{date}/{}/s3/aws4_request", self.region);
+    let string_to_sign = format!(
+      "AWS4-HMAC-SHA256\n{amz_date}\n{scope}\n{}",
+      hex::encode(Sha256::digest(canonical_request.as_bytes()))
+    );
+    let key = signing_key(&credentials.secret_access_key, &date, &self.region, "s3");
+    let signature = hex::encode(hmac(&key, string_to_sign.as_bytes()));
+
+    let mut request = ureq::request(method, &format!("{}{path}", self.origin)).set(
+      "Authorization",
+      &format!(
+        "AWS4-HMAC-SHA256 Credential={}/{scope}, SignedHeaders={signed_headers}, Signature={signature}",
+        credentials.access_key_id
+      ),
+    );
+    for (name, value) in headers.iter().filter(|(name, _)| *name != "host") {
+      request = request.set(name, value);
+    }
+    Ok(request)
+  }
+}
+
+impl Backend for S3 {
+  fn url(&self, file_name: &str) -> String {
+    match &self.public_url {
+      Some(public_url) => format!(
+        "{}/{}",
+        public_url.as_str().trim_end_matches('/'),
+        self.key(file_name)
+      ),
+      None => format!(
+        "{}{}/{}",
+        self.origin,
+        self.bucket_path,
+        self.key(file_name)
+      ),
+    }
+  }
+
+  fn read_manifest(&mut self) -> Result<Option<UpdateManifest>> {
+    match self.request("GET", MANIFEST_NAME)?.call() {
+      Ok(response) => Ok(serde_json::from_str(&response.into_string()?).ok()),
+      Err(ureq::Error::Status(404, _)) => Ok(None),
+      Err(e) => Err(e.into()),
+    }
+  }
+
+  fn upload(&mut self, file_name: &str, data: Vec<u8>, content_type: &str) -> Result<()> {
+    self
+      .request("PUT", file_name)?
+      .set("Content-Type", content_type)
+      .send_bytes(&data)?;
+    Ok(())
+  }
+}
+
+fn hmac(key: &[u8], data: &[u8]) -> Vec<u8> {
+  let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any size");
+  mac.update(data);
+  mac.finalize().into_bytes().to_vec()
+}
+
+/// Derives the key signing the requests of the day.
+fn signing_key(secret_access_key: &str, date: &str, region: &str, service: &str) -> Vec<u8> {
+  let key = hmac(
+    format!("AWS4{secret_access_key}").as_bytes(),
+    date.as_bytes(),
+  );
+  let key = hmac(&key, region.as_bytes());
+  let key = hmac(&key, service.as_bytes());
+  hmac(&key, b"aws4_request")
+}
+
+/// Percent-encodes everything but the unreserved characters, as expected by the signature.
+fn uri_encode(value: &str, encode_slash: bool) -> String {
+  let mut encoded = String::with_capacity(value.len());
+  for byte in value.bytes() {
+    match byte {
+      b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+        encoded.push(byte as char)
+      }
+      b'/' if !encode_slash => encoded.push('/'),
+      _ => encoded.push_str(&format!("%{byte:02X}")),
+    }
+  }
+  encoded
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn derive_signing_key() {
+    // example of https://docs.aws.amazon.com/IAM/latest/UserGuide/create-signed-request.html
+    assert_eq!(
+      hex::encode(signing_key(
+        "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY",
+        "20150830",
+        "us-east-1",
+        "iam"
+      )),
+      "c4afb1cc5771d871763a393e44b703571b55cc28424d1a5e86da6ed3c154a4b9"
+    );
+  }
+
+  #[test]
+  fn object_urls() {
+    let aws = S3::new("releases", Some("eu-west-1"), None, "/app/v1.0.0/", None);
+    assert_eq!(
+      aws.url("My App.app.tar.gz"),
+      "https://releases.s3.eu-west-1.amazonaws.com/app/v1.0.0/My%20App.app.tar.gz"
+    );
+
+    let endpoint = Url::parse("https://account.r2.cloudflarestorage.com").unwrap();
+    let public_url = Url::parse("https://cdn.example.com/").unwrap();
+    let r2 = S3::new("releases", Some("auto"), Some(&endpoint), "", None);
+    assert_eq!(
+      r2.url(MANIFEST_NAME),
+      "https://account.r2.cloudflarestorage.com/releases/latest.json"
+    );
+    let cdn = S3::new("releases", None, Some(&endpoint), "", Some(&public_url));
+    assert_eq!(
+      cdn.url(MANIFEST_NAME),
+      "https://cdn.example.com/latest.json"
+    );
+  }
+}
@@ -6,13 +6,14 @@ use crate::Result;
 use clap::{Parser, Subcommand};
 
 mod generate;
+mod rotate;
 mod sign;
 
 #[derive(Parser)]
 #[clap(
   author,
   version,
-  about = "Generate signing keys for Tauri updater or sign files",
+  about = "Generate signing keys for Tauri updater, sign files or rotate keys",
   subcommand_required(true),
   arg_required_else_help(true)
 )]
@@ -25,12 +26,14 @@ pub struct Cli {
 enum Commands {
   Sign(sign::Options),
   Generate(generate::Options),
+  Rotate(rotate::Options),
 }
 
 pub fn command(cli: Cli) -> Result<()> {
   match cli.command {
     Commands::Sign(options) => sign::command(options)?,
     Commands::Generate(options) => generate::command(options)?,
+    Commands::Rotate(options) => rotate::command(options)?,
   }
   Ok(())
 }
@@ -0,0 +1,121 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::path::{Path, PathBuf};
+
+use crate::{
+  helpers::updater_signature::{public_key, secret_key, sign_data},
+  Result,
+};
+use anyhow::Context;
+use clap::Parser;
+use tauri_utils::{
+  display_path,
+  updater::{KeyRotation, TrustedKey},
+};
+
+#[derive(Debug, Parser)]
+#[clap(about = "Sign a key rotation statement introducing a new updater public key")]
+pub struct Options {
+  /// Load the private key being retired from a string
+  #[clap(
+    short = 'k',
+    long,
+    conflicts_with("private_key_path"),
+    env = "TAURI_PRIVATE_KEY"
+  )]
+  private_key: Option<String>,
+  /// Load the private key being retired from a file
+  #[clap(
+    short = 'f',
+    long,
+    conflicts_with("private_key"),
+    env = "TAURI_PRIVATE_KEY_PATH"
+  )]
+  private_key_path: Option<PathBuf>,
+  /// Set private key password when signing
+  #[clap(short, long, env = "TAURI_PRIVATE_KEY_PASSWORD")]
+  password: Option<String>,
+  /// The public key being retired, as a string or a path to the key file
+  #[clap(long)]
+  public_key: String,
+  /// The new public key, as a string or a path to the key file
+  #[clap(long)]
+  new_public_key: String,
+  /// Unix timestamp after which signatures made with the retired key are rejected
+  #[clap(long)]
+  retire_at: Option<u64>,
+  /// Unix timestamp before which signatures made with the new key are rejected
+  #[clap(long)]
+  not_before: Option<u64>,
+  /// Unix timestamp after which signatures made with the new key are rejected
+  #[clap(long)]
+  not_after: Option<u64>,
+  /// Write the key rotation statement to a file
+  #[clap(short, long)]
+  output: Option<PathBuf>,
+}
+
+fn read_key(key: String) -> Result<String> {
+  let path = Path::new(&key);
+  if path.exists() {
+    Ok(std::fs::read_to_string(path)?.trim().to_string())
+  } else {
+    Ok(key)
+  }
+}
+
+pub fn command(options: Options) -> Result<()> {
+  let private_key = if let Some(path) = options.private_key_path {
+    std::fs::read_to_string(&path)
+      .with_context(|| format!("failed to read private key at {}", display_path(&path)))?
+  } else if let Some(private_key) = options.private_key {
+    private_key
+  } else {
+    return Err(anyhow::anyhow!(
+      "Key rotation aborted: Unable to find the private key".to_string(),
+    ));
+  };
+  let secret_key = secret_key(private_key, options.password)?;
+
+  let from = read_key(options.public_key)?;
+  let to = TrustedKey {
+    pubkey: read_key(options.new_public_key)?,
+    not_before: options.not_before,
+    not_after: options.not_after,
+  };
+  // make sure both keys are valid before signing
+  let retired_key = public_key(&from)?;
+  public_key(&to.pubkey)?;
+  // the installations only accept a statement signed by the retired key
+  let signing_key = minisign::PublicKey::from_secret_key(&secret_key)
+    .with_context(|| "failed to derive the public key of the private key")?;
+  if signing_key.to_bytes() != retired_key.to_bytes() {
+    anyhow::bail!(
+      "Key rotation aborted: the private key does not match the public key being retired (`--public-key`)"
+    );
+  }
+
+  let message = KeyRotation::message_for(&from, options.retire_at, &to);
+  let rotation = KeyRotation {
+    signature: sign_data(&secret_key, message.as_bytes())
+      .with_context(|| "failed to sign key rotation statement")?,
+    from,
+    retire_at: options.retire_at,
+    to,
+  };
+  let statement = serde_json::to_string_pretty(&rotation)?;
+
+  if let Some(output) = options.output {
+    std::fs::write(&output, statement)?;
+    println!(
+      "\nYour key rotation statement was signed successfully, You can find it here:\n{}\n\nMake sure to publish it along your next update so existing installations trust the new key.",
+      display_path(output)
+    );
+  } else {
+    println!("{statement}");
+  }
+
+  Ok(())
+}
@@ -0,0 +1,304 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use crate::{
+  helpers::{
+    self,
+    app_paths::{app_dir, tauri_dir},
+    config::get as get_config,
+  },
+  interface::{rust::get_cargo_target_dir, AppInterface, AppSettings, Interface},
+  CommandExt, ConfigValue, Result,
+};
+
+use std::{
+  net::{Ipv4Addr, SocketAddr, TcpStream},
+  path::{Path, PathBuf},
+  process::Command,
+  sync::mpsc::channel,
+  time::{Duration, Instant},
+};
+
+use anyhow::Context;
+use clap::{ArgAction, Parser};
+use notify::RecursiveMode;
+use notify_debouncer_mini::new_debouncer;
+use tauri_utils::platform::Target;
+
+/// The port `tauri-driver` listens on.
+const WEBDRIVER_PORT: u16 = 4444;
+
+#[derive(Debug, Clone, Parser)]
+#[clap(
+  about = "Run the app tests",
+  long_about = "Run the app tests. The Rust tests are compiled with the `tauri/test` feature, so they can run the app on the mock runtime of `tauri::test` without opening any window. With `--webdriver`, the app is also built and the WebDriver tests run against the binary through `tauri-driver`. It runs your `build.beforeBuildCommand` first so the frontend assets can be embedded in the app."
+)]
+pub struct Options {
+  /// Binary to use to run the tests, defaults to `cargo`
+  #[clap(short, long)]
+  pub runner: Option<String>,
+  /// Build and test the app in release mode
+  #[clap(long)]
+  pub release: bool,
+  /// Target triple to build against
+  #[clap(short, long)]
+  pub target: Option<String>,
+  /// Space or comma separated list of features to activate
+  #[clap(short, long, action = ArgAction::Append, num_args(0..))]
+  pub features: Option<Vec<String>>,
+  /// Command running the WebDriver tests, e.g. `npm run test:e2e`.
+  ///
+  /// It runs from the app directory once `tauri-driver` listens on port 4444,
+  /// with the path of the built app in the `TAURI_TEST_APPLICATION` environment variable
+  /// and the WebDriver server URL in `TAURI_WEBDRIVER_URL`.
+  #[clap(long)]
+  pub webdriver: Option<String>,
+  /// Path to the `tauri-driver` binary. Defaults to `tauri-driver` from `PATH`.
+  #[clap(long, requires = "webdriver")]
+  pub driver: Option<PathBuf>,
+  /// Run the tests again when the files of the Tauri directory change.
+  #[clap(short, long)]
+  pub watch: bool,
+  /// JSON string or path to JSON file to merge with tauri.conf.json
+  #[clap(short, long)]
+  pub config: Option<ConfigValue>,
+  /// Command line arguments passed to the runner. Use `--` to explicitly mark the start of the arguments,
+  /// e.g. `tauri test -- -- --nocapture` to pass `--nocapture` to the test binaries.
+  pub args: Vec<String>,
+}
+
+pub fn command(options: Options, verbosity: u8) -> Result<()> {
+  if !options.watch {
+    return run(&options, verbosity);
+  }
+
+  let tauri_dir = tauri_dir();
+  let target_dir = get_cargo_target_dir()?;
+
+  let (tx, rx) = channel();
+  let mut debouncer = new_debouncer(Duration::from_millis(500), move |r| {
+    if let Ok(events) = r {
+      let _ = tx.send(events);
+    }
+  })?;
+  debouncer
+    .watcher()
+    .watch(&tauri_dir, RecursiveMode::Recursive)?;
+
+  loop {
+    // pick up the configuration changes
+    helpers::config::reset();
+    if let Err(e) = run(&options, verbosity) {
+      log::error!("{:#}", e);
+    }
+    log::info!(action = "Watching"; "{} for changes...", tauri_utils::display_path(&tauri_dir));
+
+    loop {
+      let events = rx.recv().context("file watcher disconnected")?;
+      if events
+        .iter()
+        .any(|event| is_source_change(&event.path, &tauri_dir, &target_dir))
+      {
+        break;
+      }
+    }
+  }
+}
+
+/// Whether the change affects the tests, ignoring the build outputs and the generated files.
+fn is_source_change(path: &Path, tauri_dir: &Path, target_dir: &Path) -> bool {
+  !path.starts_with(target_dir) && !path.starts_with(tauri_dir.join("gen"))
+}
+
+fn run(options: &Options, verbosity: u8) -> Result<()> {
+  let target = options
+    .target
+    .as_deref()
+    .map(Target::from_triple)
+    .unwrap_or_else(Target::current);
+  let config = get_config(target, options.config.as_ref().map(|c| &c.0))?;
+  let interface = AppInterface::new(
+    config.lock().unwrap().as_ref().unwrap(),
+    options.target.clone(),
+  )?;
+
+  // the WebDriver tests need the app binary, building it also builds the frontend
+  let application = if options.webdriver.is_some() {
+    let build_options = crate::build::Options {
+      runner: options.runner.clone(),
+      debug: !options.release,
+      target: options.target.clone(),
+      features: options.features.clone(),
+      bundles: None,
+      no_bundle: true,
+      updater_delta_base: None,
+      reproducible: false,
+      analyze: false,
+      sbom: None,
+      config: options.config.clone(),
+      app: None,
+      all: false,
+      args: Vec::new(),
+      ci: true,
+    };
+    let bin_path = interface
+      .app_settings()
+      .app_binary_path(&build_options.clone().into())?;
+    crate::build::command(build_options, verbosity)?;
+    Some(bin_path)
+  } else {
+    let config_guard = config.lock().unwrap();
+    let config_ = config_guard.as_ref().unwrap();
+    if let Some(before_build) = config_.build.before_build_command.clone() {
+      helpers::run_hook(
+        "beforeBuildCommand",
+        before_build,
+        &interface,
+        !options.release,
+      )?;
+    }
+    None
+  };
+
+  let features = {
+    let config_guard = config.lock().unwrap();
+    let config_ = config_guard.as_ref().unwrap();
+    let mut features = options.features.clone().unwrap_or_default();
+    features.extend(config_.build.features.clone().unwrap_or_default());
+    features.push("tauri/test".into());
+    features
+  };
+
+  let runner = options.runner.as_deref().unwrap_or("cargo");
+  let mut test_cmd = Command::new(runner);
+  test_cmd
+    .arg("test")
+    .arg("--features")
+    .arg(features.join(","))
+    .current_dir(tauri_dir())
+    .envs(interface.env());
+  if options.release {
+    test_cmd.arg("--release");
+  }
+  if let Some(target) = &options.target {
+    test_cmd.arg("--target").arg(target);
+  }
+  test_cmd.args(&options.args);
+
+  log::info!(action = "Running"; "Rust tests");
+  let status = test_cmd
+    .piped()
+    .with_context(|| format!("failed to run `{runner} test`"))?;
+  if !status.success() {
+    anyhow::bail!(
+      "Rust tests failed with exit code {}",
+      status.code().unwrap_or_default()
+    );
+  }
+
+  if let (Some(script), Some(application)) = (&options.webdriver, application) {
+    webdriver(script, &application, options.driver.as_deref())?;
+  }
+
+  Ok(())
+}
+
+/// Runs the WebDriver tests against the app while `tauri-driver` is running.
+fn webdriver(script: &str, application: &Path, driver: Option<&Path>) -> Result<()> {
+  let driver = driver.unwrap_or_else(|| Path::new("tauri-driver"));
+  let mut driver_process = Command::new(driver)
+    .arg("--port")
+    .arg(WEBDRIVER_PORT.to_string())
+    .stdout(helpers::event::child_stdout()?)
+    .spawn()
+    .with_context(|| {
+      format!(
+        "failed to run `{}`, install it with `cargo install tauri-driver --locked`",
+        driver.display()
+      )
+    })?;
+
+  let result = wait_for_driver().and_then(|()| {
+    log::info!(action = "Running"; "WebDriver tests `{}`", script);
+
+    #[cfg(target_os = "windows")]
+    let mut cmd = {
+      let mut cmd = Command::new("cmd");
+      cmd.arg("/S").arg("/C").arg(script);
+      cmd
+    };
+    #[cfg(not(target_os = "windows"))]
+    let mut cmd = {
+      let mut cmd = Command::new("sh");
+      cmd.arg("-c").arg(script);
+      cmd
+    };
+
+    let status = cmd
+      .current_dir(app_dir())
+      .env("TAURI_TEST_APPLICATION", application)
+      .env(
+        "TAURI_WEBDRIVER_URL",
+        format!("http://127.0.0.1:{WEBDRIVER_PORT}"),
+      )
+      .piped()
+      .with_context(|| format!("failed to run `{script}`"))?;
+    if !status.success() {
+      anyhow::bail!(
+        "WebDriver tests failed with exit code {}",
+        status.code().unwrap_or_default()
+      );
+    }
+    Ok(())
+  });
+
+  let _ = driver_process.kill();
+  let _ = driver_process.wait();
+
+  result
+}
+
+fn wait_for_driver() -> Result<()> {
+  let addr = SocketAddr::from((Ipv4Addr::LOCALHOST, WEBDRIVER_PORT));
+  let timeout = Duration::from_secs(10);
+  let start = Instant::now();
+  while TcpStream::connect_timeout(&addr, Duration::from_millis(200)).is_err() {
+    if start.elapsed() > timeout {
+      anyhow::bail!("tauri-driver did not start listening on port {WEBDRIVER_PORT}");
+    }
+    std::thread::sleep(Duration::from_millis(100));
+  }
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn source_changes() {
+    let tauri_dir = Path::new("/app/src-tauri");
+    let target_dir = Path::new("/app/target");
+    assert!(is_source_change(
+      &tauri_dir.join("src/lib.rs"),
+      tauri_dir,
+      target_dir
+    ));
+    assert!(is_source_change(
+      &tauri_dir.join("tauri.conf.json"),
+      tauri_dir,
+      target_dir
+    ));
+    assert!(!is_source_change(
+      &target_dir.join("debug/app"),
+      tauri_dir,
+      target_dir
+    ));
+    assert!(!is_source_change(
+      &tauri_dir.join("gen/schemas/desktop-schema.json"),
+      tauri_dir,
+      target_dir
+    ));
+  }
+}
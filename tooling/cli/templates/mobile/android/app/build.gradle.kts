@@ -26,6 +26,19 @@ android {
         versionCode = tauriProperties.getProperty("tauri.android.versionCode", "1").toInt()
         versionName = tauriProperties.getProperty("tauri.android.versionName", "1.0")
     }
+    signingConfigs {
+        val keyStore = tauriProperties.getProperty("tauri.android.signing.keyStore")
+        if (keyStore != null) {
+            create("release") {
+                storeFile = file(keyStore)
+                storePassword = System.getenv("TAURI_ANDROID_KEYSTORE_PASSWORD")
+                keyAlias = tauriProperties.getProperty("tauri.android.signing.keyAlias")
+                keyPassword = System.getenv("TAURI_ANDROID_KEY_PASSWORD") ?: storePassword
+            }
+        }
+    }
+    assetPacks += tauriProperties.getProperty("tauri.android.assetPacks", "")
+        .split(",").filter { it.isNotEmpty() }.map { ":$it" }
     buildTypes {
         getByName("debug") {
             manifestPlaceholders["usesCleartextTraffic"] = "true"
@@ -39,6 +52,7 @@ android {
             }
         }
         getByName("release") {
+            signingConfig = signingConfigs.findByName("release")
             isMinifyEnabled = true
             proguardFiles(
                 *fileTree(".") { include("**/*.pro") }
@@ -1,6 +1,22 @@
+#include <dlfcn.h>
+#include <stdio.h>
+#include <stdlib.h>
 #include "bindings/bindings.h"
 
 int main(int argc, char * argv[]) {
+#if DEBUG && TARGET_OS_SIMULATOR
+	// `tauri ios dev` hot-swaps the Rust code on the simulator by relaunching the app with a rebuilt dylib
+	const char *dylib = getenv("TAURI_DEV_DYLIB");
+	if (dylib != NULL) {
+		void *handle = dlopen(dylib, RTLD_NOW | RTLD_LOCAL);
+		void (*start_app)(void) = handle != NULL ? (void (*)(void))dlsym(handle, "start_app") : NULL;
+		if (start_app != NULL) {
+			start_app();
+			return 0;
+		}
+		fprintf(stderr, "failed to load %s: %s\n", dylib, dlerror());
+	}
+#endif
 	ffi::start_app();
 	return 0;
 }
@@ -2,7 +2,11 @@ const COMMANDS: &[&str] = &["ping"];
 
 fn main() {
   tauri_plugin::Builder::new(COMMANDS)
+{{#if android}}
     .android_path("android")
+{{/if}}
+{{#if ios}}
     .ios_path("ios")
+{{/if}}
     .build();
 }
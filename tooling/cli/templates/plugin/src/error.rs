@@ -9,9 +9,11 @@ pub type Result<T> = std::result::Result<T, Error>;
 pub enum Error {
   #[error(transparent)]
   Io(#[from] std::io::Error),
-  #[cfg(mobile)]
+{{#if native_cfg}}
+  #[cfg({{ native_cfg }})]
   #[error(transparent)]
   PluginInvoke(#[from] tauri::plugin::mobile::PluginInvokeError),
+{{/if}}
 }
 
 impl Serialize for Error {
@@ -168,6 +168,11 @@ impl Keychain {
     }
   }
 
+  /// The path to the keychain, or `None` if the default keychain is used.
+  pub fn path(&self) -> Option<&Path> {
+    self.path.as_deref()
+  }
+
   pub fn team_id(&self) -> Option<&str> {
     match &self.signing_identity {
       SigningIdentity::Team(t) => Some(&t.id),
@@ -6,6 +6,7 @@ use std::{
   ffi::{OsStr, OsString},
   path::{Path, PathBuf},
   process::Command,
+  time::{Duration, Instant},
 };
 
 use anyhow::{Context, Result};
@@ -22,6 +23,7 @@ pub enum ApiKey {
   Raw(Vec<u8>),
 }
 
+#[non_exhaustive]
 pub enum AppleNotarizationCredentials {
   AppleId {
     apple_id: OsString,
@@ -33,121 +35,252 @@ pub enum AppleNotarizationCredentials {
     key_id: OsString,
     key: ApiKey,
   },
+  /// Credentials stored in the keychain with `xcrun notarytool store-credentials`.
+  KeychainProfile {
+    profile: OsString,
+    /// The keychain holding the profile, defaults to the login keychain.
+    keychain: Option<PathBuf>,
+  },
+}
+
+/// Options of the notarization process.
+pub struct NotarizeOptions {
+  /// Maximum time to wait for Apple to process the submission.
+  pub timeout: Duration,
+  /// Number of attempts to upload the submission and staple the ticket,
+  /// to recover from transient network or service errors.
+  pub attempts: u32,
+  /// Whether to staple the notarization ticket to the notarized file.
+  pub staple: bool,
+}
+
+impl Default for NotarizeOptions {
+  fn default() -> Self {
+    Self {
+      timeout: Duration::from_secs(60 * 60),
+      attempts: 3,
+      staple: true,
+    }
+  }
 }
 
+/// Delay before the first retry, doubled on each attempt.
+const RETRY_DELAY: Duration = Duration::from_secs(5);
+/// Delay before the first status poll, doubled on each poll up to [`MAX_POLL_DELAY`].
+const POLL_DELAY: Duration = Duration::from_secs(10);
+const MAX_POLL_DELAY: Duration = Duration::from_secs(60);
+
 #[derive(Deserialize)]
 struct NotarytoolSubmitOutput {
   id: String,
+}
+
+#[derive(Deserialize)]
+struct NotarytoolInfoOutput {
+  id: String,
   status: String,
+  #[serde(default)]
   message: String,
 }
 
+/// Notarizes the app bundle, disk image or installer package and staples the notarization ticket to it.
 pub fn notarize(
   keychain: &Keychain,
   app_bundle_path: &Path,
   auth: &AppleNotarizationCredentials,
 ) -> Result<()> {
-  let bundle_stem = app_bundle_path
-    .file_stem()
-    .expect("failed to get bundle filename");
+  notarize_with_options(keychain, app_bundle_path, auth, &NotarizeOptions::default())
+}
 
+/// Notarizes the app bundle, disk image or installer package with the given options.
+///
+/// The file is submitted with `notarytool`, then the submission status is polled with an increasing delay.
+/// The notarization log is included in the error when the submission is not accepted.
+pub fn notarize_with_options(
+  keychain: &Keychain,
+  path: &Path,
+  auth: &AppleNotarizationCredentials,
+  options: &NotarizeOptions,
+) -> Result<()> {
   let tmp_dir = tempfile::tempdir()?;
-  let zip_path = tmp_dir
-    .path()
-    .join(format!("{}.zip", bundle_stem.to_string_lossy()));
-  let zip_args = vec![
-    "-c",
-    "-k",
-    "--keepParent",
-    "--sequesterRsrc",
-    app_bundle_path
-      .to_str()
-      .expect("failed to convert bundle_path to string"),
-    zip_path
-      .to_str()
-      .expect("failed to convert zip_path to string"),
-  ];
 
-  // use ditto to create a PKZip almost identical to Finder
-  // this remove almost 99% of false alarm in notarization
-  assert_command(
-    Command::new("ditto").args(zip_args).status(),
-    "failed to zip app with ditto",
-  )?;
+  // disk images and installer packages are submitted as is, app bundles must be archived
+  let is_archive = path
+    .extension()
+    .map_or(false, |ext| ext == "dmg" || ext == "pkg");
+  let submission_path = if is_archive {
+    path.to_path_buf()
+  } else {
+    let bundle_stem = path.file_stem().expect("failed to get bundle filename");
+    let zip_path = tmp_dir
+      .path()
+      .join(format!("{}.zip", bundle_stem.to_string_lossy()));
+    let zip_args = vec![
+      "-c",
+      "-k",
+      "--keepParent",
+      "--sequesterRsrc",
+      path
+        .to_str()
+        .expect("failed to convert bundle_path to string"),
+      zip_path
+        .to_str()
+        .expect("failed to convert zip_path to string"),
+    ];
 
-  // sign the zip file
-  keychain.sign(&zip_path, None, false)?;
+    // use ditto to create a PKZip almost identical to Finder
+    // this remove almost 99% of false alarm in notarization
+    assert_command(
+      Command::new("ditto").args(zip_args).status(),
+      "failed to zip app with ditto",
+    )?;
 
-  let notarize_args = vec![
-    "notarytool",
-    "submit",
+    // sign the zip file
+    keychain.sign(&zip_path, None, false)?;
     zip_path
-      .to_str()
-      .expect("failed to convert zip_path to string"),
-    "--wait",
-    "--output-format",
-    "json",
-  ];
+  };
 
-  println!("Notarizing {}", app_bundle_path.display());
+  println!("Notarizing {}", path.display());
 
-  let output = Command::new("xcrun")
-    .args(notarize_args)
+  let submission_id = retry(
+    options.attempts,
+    "upload to Apple's notarization servers",
+    || {
+      let output = Command::new("xcrun")
+        .args(["notarytool", "submit"])
+        .arg(&submission_path)
+        .args(["--output-format", "json"])
+        .notarytool_args(auth, tmp_dir.path())?
+        .output()
+        .context("failed to run notarytool")?;
+      if !output.status.success() {
+        return Err(anyhow::anyhow!(
+          "{}",
+          String::from_utf8_lossy(&output.stderr).trim()
+        ));
+      }
+      let output_str = String::from_utf8_lossy(&output.stdout);
+      serde_json::from_str::<NotarytoolSubmitOutput>(&output_str)
+        .map(|submission| submission.id)
+        .with_context(|| format!("failed to parse notarytool output as JSON: `{output_str}`"))
+    },
+  )?;
+
+  println!("Submitted with id {submission_id}, waiting for Apple to process it");
+
+  let info = wait_for_submission(&submission_id, auth, tmp_dir.path(), options.timeout)?;
+  let log_message = format!(
+    "Finished with status {} for id {} ({})",
+    info.status, info.id, info.message
+  );
+
+  if info.status == "Accepted" {
+    println!("Notarizing {}", log_message);
+    if options.staple {
+      retry(options.attempts, "staple the notarization ticket", || {
+        staple(path)
+      })?;
+    }
+    Ok(())
+  } else if let Ok(output) = Command::new("xcrun")
+    .args(["notarytool", "log"])
+    .arg(&info.id)
     .notarytool_args(auth, tmp_dir.path())?
     .output()
-    .context("failed to upload app to Apple's notarization servers.")?;
+  {
+    Err(anyhow::anyhow!(
+      "{log_message}\nLog:\n{}",
+      String::from_utf8_lossy(&output.stdout)
+    ))
+  } else {
+    Err(anyhow::anyhow!("{log_message}"))
+  }
+}
+
+/// Polls the submission status until it is processed or the timeout expires.
+fn wait_for_submission(
+  id: &str,
+  auth: &AppleNotarizationCredentials,
+  temp_dir: &Path,
+  timeout: Duration,
+) -> Result<NotarytoolInfoOutput> {
+  let started = Instant::now();
+  let mut delay = POLL_DELAY;
+  loop {
+    std::thread::sleep(delay.min(timeout.saturating_sub(started.elapsed())));
 
-  if !output.status.success() {
-    return Err(anyhow::anyhow!("failed to notarize app"));
+    // transient errors are ignored, the status is polled again until the timeout expires
+    let output = Command::new("xcrun")
+      .args(["notarytool", "info", id, "--output-format", "json"])
+      .notarytool_args(auth, temp_dir)?
+      .output();
+    match output {
+      Ok(output) if output.status.success() => {
+        let output_str = String::from_utf8_lossy(&output.stdout);
+        let info = serde_json::from_str::<NotarytoolInfoOutput>(&output_str)
+          .with_context(|| format!("failed to parse notarytool output as JSON: `{output_str}`"))?;
+        if info.status != "In Progress" {
+          return Ok(info);
+        }
+      }
+      Ok(output) => println!(
+        "Failed to get the notarization status: {}",
+        String::from_utf8_lossy(&output.stderr).trim()
+      ),
+      Err(e) => println!("Failed to get the notarization status: {e}"),
+    }
+
+    if started.elapsed() >= timeout {
+      return Err(anyhow::anyhow!(
+        "timed out waiting for the notarization of submission {id}, check its status with `xcrun notarytool info {id}`"
+      ));
+    }
+    delay = (delay * 2).min(MAX_POLL_DELAY);
   }
+}
 
-  let output_str = String::from_utf8_lossy(&output.stdout);
-  if let Ok(submit_output) = serde_json::from_str::<NotarytoolSubmitOutput>(&output_str) {
-    let log_message = format!(
-      "Finished with status {} for id {} ({})",
-      submit_output.status, submit_output.id, submit_output.message
-    );
-    if submit_output.status == "Accepted" {
-      println!("Notarizing {}", log_message);
-      staple_app(app_bundle_path.to_path_buf())?;
-      Ok(())
-    } else if let Ok(output) = Command::new("xcrun")
-      .args(["notarytool", "log"])
-      .arg(&submit_output.id)
-      .notarytool_args(auth, tmp_dir.path())?
-      .output()
-    {
-      Err(anyhow::anyhow!(
-        "{log_message}\nLog:\n{}",
-        String::from_utf8_lossy(&output.stdout)
-      ))
-    } else {
-      Err(anyhow::anyhow!("{log_message}"))
+/// Runs the action until it succeeds, up to `attempts` times, doubling the delay between attempts.
+fn retry<T>(attempts: u32, action_name: &str, mut action: impl FnMut() -> Result<T>) -> Result<T> {
+  let mut delay = RETRY_DELAY;
+  let mut attempt = 1;
+  loop {
+    match action() {
+      Ok(value) => return Ok(value),
+      Err(e) if attempt < attempts => {
+        println!(
+          "Failed to {action_name} (attempt {attempt}/{attempts}): {e}, retrying in {delay:?}"
+        );
+        std::thread::sleep(delay);
+        delay *= 2;
+        attempt += 1;
+      }
+      Err(e) => return Err(e.context(format!("failed to {action_name}"))),
     }
-  } else {
-    Err(anyhow::anyhow!(
-      "failed to parse notarytool output as JSON: `{output_str}`"
-    ))
   }
 }
 
-fn staple_app(mut app_bundle_path: PathBuf) -> Result<()> {
-  let app_bundle_path_clone = app_bundle_path.clone();
-  let filename = app_bundle_path_clone
+/// Staples the notarization ticket to the app bundle, disk image or installer package.
+fn staple(path: &Path) -> Result<()> {
+  let filename = path
     .file_name()
     .expect("failed to get bundle filename")
     .to_str()
     .expect("failed to convert bundle filename to string");
 
-  app_bundle_path.pop();
-
-  Command::new("xcrun")
+  let output = Command::new("xcrun")
     .args(vec!["stapler", "staple", "-v", filename])
-    .current_dir(app_bundle_path)
+    .current_dir(path.parent().expect("failed to get bundle directory"))
     .output()
-    .context("failed to staple app.")?;
+    .context("failed to run stapler")?;
 
-  Ok(())
+  if output.status.success() {
+    Ok(())
+  } else {
+    Err(anyhow::anyhow!(
+      "{}",
+      String::from_utf8_lossy(&output.stdout).trim()
+    ))
+  }
 }
 
 pub trait NotarytoolCmdExt {
@@ -202,6 +335,13 @@ impl NotarytoolCmdExt for Command {
             .arg(issuer),
         )
       }
+      AppleNotarizationCredentials::KeychainProfile { profile, keychain } => {
+        self.arg("--keychain-profile").arg(profile);
+        if let Some(keychain) = keychain {
+          self.arg("--keychain").arg(keychain);
+        }
+        Ok(self)
+      }
     }
   }
 }
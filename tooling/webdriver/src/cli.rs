@@ -13,7 +13,7 @@ FLAGS:
 OPTIONS:
   --port NUMBER           Sets the tauri-driver intermediary port
   --native-port NUMBER    Sets the port of the underlying WebDriver
-  --native-host HOST      Sets the host of the underlying WebDriver (Linux only)
+  --native-host HOST      Sets the host of the underlying WebDriver (Linux and macOS)
   --native-driver PATH    Sets the path to the native WebDriver binary
 ";
 
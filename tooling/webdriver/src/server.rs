@@ -52,6 +52,34 @@ impl TauriOptions {
     );
     map
   }
+
+  #[cfg(target_os = "macos")]
+  fn into_native_object(self) -> Map<String, Value> {
+    let mut map = Map::new();
+    map.insert("platformName".into(), json!("mac"));
+    map.insert("appium:automationName".into(), json!("Mac2"));
+    map.insert(
+      "appium:appPath".into(),
+      json!(app_bundle_path(self.application)),
+    );
+    map.insert("appium:arguments".into(), json!(self.args));
+    // the app is launched by the XCTest runner, which doesn't forward the environment of the driver
+    map.insert(
+      "appium:environment".into(),
+      json!({"TAURI_WEBVIEW_AUTOMATION": "true"}),
+    );
+    map
+  }
+}
+
+/// The `.app` bundle containing the application, which can also be the path of its executable.
+#[cfg(target_os = "macos")]
+fn app_bundle_path(application: PathBuf) -> PathBuf {
+  application
+    .ancestors()
+    .find(|p| p.extension().map_or(false, |ext| ext == "app"))
+    .map(ToOwned::to_owned)
+    .unwrap_or(application)
 }
 
 async fn handle(
@@ -12,6 +12,10 @@ const DRIVER_BINARY: &str = "WebKitWebDriver";
 #[cfg(target_os = "windows")]
 const DRIVER_BINARY: &str = "msedgedriver.exe";
 
+// Appium with the Mac2 driver, installed with `appium driver install mac2`
+#[cfg(target_os = "macos")]
+const DRIVER_BINARY: &str = "appium";
+
 /// Find the native driver binary in the PATH, or exits the process with an error.
 pub fn native(args: &Args) -> Command {
   let native_binary = match args.native_driver.as_deref() {
@@ -47,6 +51,9 @@ pub fn native(args: &Args) -> Command {
   let mut cmd = Command::new(native_binary);
   cmd.env("TAURI_WEBVIEW_AUTOMATION", "true");
   cmd.arg(format!("--port={}", args.native_port));
+  #[cfg(not(target_os = "macos"))]
   cmd.arg(format!("--host={}", args.native_host));
+  #[cfg(target_os = "macos")]
+  cmd.arg(format!("--address={}", args.native_host));
   cmd
 }